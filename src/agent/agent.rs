@@ -41,6 +41,17 @@ pub struct Agent {
     research_config: ResearchPhaseConfig,
 }
 
+/// Settings that can be changed on a running agent without rebuilding it.
+///
+/// Unset fields leave the current value untouched.
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeConfigUpdate {
+    pub model: Option<String>,
+    pub temperature: Option<f64>,
+    pub max_tool_iterations: Option<usize>,
+    pub auto_save: Option<bool>,
+}
+
 pub struct AgentBuilder {
     provider: Option<Box<dyn Provider>>,
     tools: Option<Vec<Box<dyn Tool>>>,
@@ -250,6 +261,21 @@ impl Agent {
         self.history.clear();
     }
 
+    pub fn apply_config_update(&mut self, update: &RuntimeConfigUpdate) {
+        if let Some(model) = &update.model {
+            self.model_name.clone_from(model);
+        }
+        if let Some(temperature) = update.temperature {
+            self.temperature = temperature;
+        }
+        if let Some(max_tool_iterations) = update.max_tool_iterations {
+            self.config.max_tool_iterations = max_tool_iterations;
+        }
+        if let Some(auto_save) = update.auto_save {
+            self.auto_save = auto_save;
+        }
+    }
+
     pub fn from_config(config: &Config) -> Result<Self> {
         let observer: Arc<dyn Observer> =
             Arc::from(observability::create_observer(&config.observability));
@@ -570,6 +596,7 @@ impl Agent {
                         } else {
                             None
                         },
+                        response_format: None,
                     },
                     &effective_model,
                     self.temperature,
@@ -952,4 +979,41 @@ mod tests {
         let seen = seen_models.lock();
         assert_eq!(seen.as_slice(), &["hint:fast".to_string()]);
     }
+
+    #[test]
+    fn apply_config_update_only_changes_set_fields() {
+        let memory_cfg = crate::config::MemoryConfig {
+            backend: "none".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> = Arc::from(
+            crate::memory::create_memory(&memory_cfg, std::path::Path::new("/tmp"), None)
+                .expect("memory creation should succeed with valid config"),
+        );
+        let observer: Arc<dyn Observer> = Arc::from(crate::observability::NoopObserver {});
+        let mut agent = Agent::builder()
+            .provider(Box::new(MockProvider {
+                responses: Mutex::new(vec![]),
+            }))
+            .tools(vec![])
+            .memory(mem)
+            .observer(observer)
+            .tool_dispatcher(Box::new(XmlToolDispatcher))
+            .workspace_dir(std::path::PathBuf::from("/tmp"))
+            .model_name("before".into())
+            .temperature(0.7)
+            .build()
+            .expect("agent builder should succeed with valid config");
+
+        agent.apply_config_update(&RuntimeConfigUpdate {
+            model: Some("after".into()),
+            max_tool_iterations: Some(3),
+            ..RuntimeConfigUpdate::default()
+        });
+
+        assert_eq!(agent.model_name, "after");
+        assert!((agent.temperature - 0.7).abs() < f64::EPSILON);
+        assert_eq!(agent.config.max_tool_iterations, 3);
+        assert!(!agent.auto_save);
+    }
 }
@@ -0,0 +1,439 @@
+//! Context-window budgeting for the tool-call loop.
+//!
+//! Before each provider call the loop measures the fixed parts of the prompt
+//! (system prompt with skills and identity, tool schemas) and gives what is
+//! left of the model window to conversation history. When history does not
+//! fit, [`fit_history`] shrinks it in the cheapest order:
+//!
+//! 1. cap every tool result at `max_tool_result_tokens`
+//! 2. shrink older tool results further, newest last
+//! 3. drop the oldest turns, never the latest user message
+//!
+//! Memory context rides inside the latest user message, so it is counted
+//! with history but never removed.
+
+use crate::config::AgentConfig;
+use crate::providers::tokens::{self, TokenCounter};
+use crate::providers::ChatMessage;
+use crate::tools::ToolSpec;
+
+/// Floor used when shrinking older tool results during step 2.
+const MIN_TOOL_RESULT_TOKENS: usize = 256;
+
+/// Share of the history allocation above which interactive sessions should
+/// summarize instead of waiting for hard truncation.
+const COMPACTION_TRIGGER_PERCENT: usize = 75;
+
+/// Marker prefix appended to truncated tool output.
+const TRUNCATION_MARKER: &str = "[… truncated";
+
+/// Token limits for one model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextBudget {
+    /// Total model context window.
+    pub context_window: usize,
+    /// Tokens kept free for the reply.
+    pub output_reserve: usize,
+    /// Cap on a single tool result kept in history.
+    pub max_tool_result_tokens: usize,
+}
+
+impl ContextBudget {
+    /// Budget from built-in model metadata and default limits.
+    pub fn for_model(model: &str) -> Self {
+        Self::from_config(&AgentConfig::default(), model)
+    }
+
+    /// Budget from `[agent]` settings, falling back to model metadata for the
+    /// window size.
+    pub fn from_config(config: &AgentConfig, model: &str) -> Self {
+        Self {
+            context_window: config
+                .context_window_tokens
+                .filter(|window| *window > 0)
+                .unwrap_or_else(|| tokens::context_window_for_model(model)),
+            output_reserve: config.output_reserve_tokens,
+            max_tool_result_tokens: config.max_tool_result_tokens.max(MIN_TOOL_RESULT_TOKENS),
+        }
+    }
+
+    /// Tokens available for the whole request.
+    pub fn input_limit(&self) -> usize {
+        self.context_window.saturating_sub(self.output_reserve)
+    }
+
+    /// Split the input limit between the system prompt, tool schemas and
+    /// history.
+    pub fn allocate(
+        &self,
+        counter: &dyn TokenCounter,
+        history: &[ChatMessage],
+        tools: &[ToolSpec],
+    ) -> BudgetAllocation {
+        let system = history
+            .iter()
+            .take_while(|m| m.role == "system")
+            .map(|m| counter.count_message(m))
+            .sum::<usize>();
+        let tools = tools
+            .iter()
+            .map(|spec| {
+                counter.count(&spec.name)
+                    + counter.count(&spec.description)
+                    + counter.count(&spec.parameters.to_string())
+            })
+            .sum::<usize>();
+        BudgetAllocation {
+            system,
+            tools,
+            history: self.input_limit().saturating_sub(system + tools),
+        }
+    }
+}
+
+/// Token allocation for one request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetAllocation {
+    /// Leading system messages (prompt, skills, identity).
+    pub system: usize,
+    /// Tool schemas sent with the request.
+    pub tools: usize,
+    /// Remaining tokens for conversation history.
+    pub history: usize,
+}
+
+/// What [`fit_history`] changed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FitReport {
+    /// History tokens before fitting.
+    pub tokens_before: usize,
+    /// History tokens after fitting.
+    pub tokens_after: usize,
+    /// Tool results that were truncated.
+    pub truncated_tool_results: usize,
+    /// Messages removed from the front of history.
+    pub dropped_messages: usize,
+}
+
+impl FitReport {
+    pub fn changed(&self) -> bool {
+        self.truncated_tool_results > 0 || self.dropped_messages > 0
+    }
+}
+
+/// Whether history is large enough that an LLM summary should replace older
+/// turns before the next request.
+pub fn should_compact(
+    budget: &ContextBudget,
+    counter: &dyn TokenCounter,
+    history: &[ChatMessage],
+) -> bool {
+    let allocation = budget.allocate(counter, history, &[]);
+    let used = history_tokens(counter, history);
+    used * 100 > allocation.history * COMPACTION_TRIGGER_PERCENT
+}
+
+/// Shrink `history` in place until it fits the allocation for `tools`.
+pub fn fit_history(
+    history: &mut Vec<ChatMessage>,
+    tools: &[ToolSpec],
+    budget: &ContextBudget,
+    counter: &dyn TokenCounter,
+) -> FitReport {
+    let allocation = budget.allocate(counter, history, tools);
+    let limit = allocation.history;
+    let start = history.iter().take_while(|m| m.role == "system").count();
+
+    let mut report = FitReport {
+        tokens_before: history_tokens(counter, history),
+        ..FitReport::default()
+    };
+    let mut used = report.tokens_before;
+
+    // 1. Cap every tool result, regardless of the overall budget.
+    for message in &mut history[start..] {
+        let before = counter.count_message(message);
+        if shrink_tool_message(message, budget.max_tool_result_tokens, counter) {
+            report.truncated_tool_results += 1;
+            used = used - before + counter.count_message(message);
+        }
+    }
+
+    // 2. Shrink older tool results (oldest first) down to the floor.
+    if used > limit {
+        for message in &mut history[start..] {
+            if used <= limit {
+                break;
+            }
+            let before = counter.count_message(message);
+            if shrink_tool_message(message, MIN_TOOL_RESULT_TOKENS, counter) {
+                report.truncated_tool_results += 1;
+                used = used - before + counter.count_message(message);
+            }
+        }
+    }
+
+    // 3. Drop the oldest turns, keeping the latest user message, and never
+    // leave tool results whose assistant call was removed.
+    let last_user = history.iter().rposition(|m| m.role == "user");
+    while used > limit && start < history.len() {
+        if last_user.is_some_and(|idx| idx - report.dropped_messages <= start) {
+            break;
+        }
+        let removed = history.remove(start);
+        used = used.saturating_sub(counter.count_message(&removed));
+        report.dropped_messages += 1;
+        while history.get(start).is_some_and(|m| m.role == "tool") {
+            let orphan = history.remove(start);
+            used = used.saturating_sub(counter.count_message(&orphan));
+            report.dropped_messages += 1;
+        }
+    }
+
+    report.tokens_after = used;
+    report
+}
+
+fn history_tokens(counter: &dyn TokenCounter, history: &[ChatMessage]) -> usize {
+    history
+        .iter()
+        .skip_while(|m| m.role == "system")
+        .map(|m| counter.count_message(m))
+        .sum()
+}
+
+/// Truncate a tool result message to `max_tokens`. Handles both native
+/// (`role = "tool"` JSON envelope) and prompt-guided (`[Tool results]` with
+/// `<tool_result>` blocks) shapes. Returns whether anything changed.
+fn shrink_tool_message(
+    message: &mut ChatMessage,
+    max_tokens: usize,
+    counter: &dyn TokenCounter,
+) -> bool {
+    if message.role == "tool" {
+        let Ok(mut envelope) = serde_json::from_str::<serde_json::Value>(&message.content) else {
+            return match truncate_to_tokens(&message.content, max_tokens, counter) {
+                Some(shorter) => {
+                    message.content = shorter;
+                    true
+                }
+                None => false,
+            };
+        };
+        let Some(content) = envelope.get("content").and_then(|c| c.as_str()) else {
+            return false;
+        };
+        let Some(shorter) = truncate_to_tokens(content, max_tokens, counter) else {
+            return false;
+        };
+        envelope["content"] = serde_json::Value::String(shorter);
+        message.content = envelope.to_string();
+        return true;
+    }
+
+    if message.role == "user" && message.content.starts_with("[Tool results]") {
+        let blocks = message.content.matches("<tool_result").count().max(1);
+        let per_block = (max_tokens / blocks).max(1);
+        let mut changed = false;
+        let mut rebuilt = String::with_capacity(message.content.len());
+        let mut rest = message.content.as_str();
+        while let Some(open) = rest.find("<tool_result") {
+            let Some(header_end) = rest[open..].find('\n').map(|i| open + i + 1) else {
+                break;
+            };
+            let Some(close) = rest[header_end..]
+                .find("</tool_result>")
+                .map(|i| header_end + i)
+            else {
+                break;
+            };
+            rebuilt.push_str(&rest[..header_end]);
+            let body = &rest[header_end..close];
+            match truncate_to_tokens(body.trim_end_matches('\n'), per_block, counter) {
+                Some(shorter) => {
+                    rebuilt.push_str(&shorter);
+                    rebuilt.push('\n');
+                    changed = true;
+                }
+                None => rebuilt.push_str(body),
+            }
+            rest = &rest[close..];
+        }
+        rebuilt.push_str(rest);
+        if changed {
+            message.content = rebuilt;
+        }
+        return changed;
+    }
+
+    false
+}
+
+/// Keep the head and tail of `text` within `max_tokens`, or `None` when it
+/// already fits or was truncated before.
+fn truncate_to_tokens(text: &str, max_tokens: usize, counter: &dyn TokenCounter) -> Option<String> {
+    let total = counter.count(text);
+    if total <= max_tokens || text.contains(TRUNCATION_MARKER) && total <= max_tokens * 2 {
+        return None;
+    }
+
+    // Scale by characters, then tighten until the counter agrees. Two thirds
+    // of the kept text comes from the head; the tail usually holds the
+    // error or summary line of command output.
+    let chars: Vec<char> = text.chars().collect();
+    let mut keep = chars.len() * max_tokens / total.max(1);
+    loop {
+        let head = keep * 2 / 3;
+        let tail = keep - head;
+        let removed = total.saturating_sub(max_tokens);
+        let candidate = format!(
+            "{}\n{TRUNCATION_MARKER} ~{removed} tokens to fit the context window …]\n{}",
+            chars[..head].iter().collect::<String>(),
+            chars[chars.len() - tail..].iter().collect::<String>()
+        );
+        if counter.count(&candidate) <= max_tokens || keep == 0 {
+            return Some(candidate);
+        }
+        keep = keep * 9 / 10;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::tokens::HeuristicCounter;
+
+    fn budget(window: usize) -> ContextBudget {
+        ContextBudget {
+            context_window: window,
+            output_reserve: 0,
+            max_tool_result_tokens: 1_000,
+        }
+    }
+
+    fn native_tool_result(content: &str) -> ChatMessage {
+        ChatMessage::tool(serde_json::json!({"tool_call_id": "c1", "content": content}).to_string())
+    }
+
+    #[test]
+    fn from_config_prefers_explicit_window_over_model_metadata() {
+        let mut config = AgentConfig::default();
+        assert_eq!(
+            ContextBudget::from_config(&config, "claude-sonnet-4").context_window,
+            200_000
+        );
+
+        config.context_window_tokens = Some(50_000);
+        let budget = ContextBudget::from_config(&config, "claude-sonnet-4");
+        assert_eq!(budget.context_window, 50_000);
+        assert_eq!(budget.input_limit(), 50_000 - 4096);
+    }
+
+    #[test]
+    fn allocate_subtracts_system_prompt_and_tool_schemas() {
+        let counter = HeuristicCounter;
+        let history = vec![
+            ChatMessage::system("x".repeat(400)),
+            ChatMessage::user("hi"),
+        ];
+        let tools = [ToolSpec {
+            name: "shell".into(),
+            description: "d".repeat(40),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+
+        let allocation = budget(1_000).allocate(&counter, &history, &tools);
+
+        assert_eq!(allocation.system, 100 + tokens::MESSAGE_OVERHEAD_TOKENS);
+        assert!(allocation.tools > 10);
+        assert_eq!(
+            allocation.history,
+            1_000 - allocation.system - allocation.tools
+        );
+    }
+
+    #[test]
+    fn fit_history_caps_tool_results_and_keeps_json_envelope() {
+        let counter = HeuristicCounter;
+        let mut history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("run it"),
+            ChatMessage::assistant("calling"),
+            native_tool_result(&format!("HEAD{}TAIL", "a".repeat(20_000))),
+        ];
+
+        let report = fit_history(&mut history, &[], &budget(100_000), &counter);
+
+        assert_eq!(report.truncated_tool_results, 1);
+        assert_eq!(report.dropped_messages, 0);
+        let envelope: serde_json::Value = serde_json::from_str(&history[3].content).unwrap();
+        assert_eq!(envelope["tool_call_id"], "c1");
+        let content = envelope["content"].as_str().unwrap();
+        assert!(content.starts_with("HEAD"));
+        assert!(content.ends_with("TAIL"));
+        assert!(content.contains(TRUNCATION_MARKER));
+        assert!(counter.count(content) <= 1_000);
+    }
+
+    #[test]
+    fn fit_history_truncates_prompt_guided_blocks_individually() {
+        let counter = HeuristicCounter;
+        let big = "b".repeat(12_000);
+        let mut history = vec![ChatMessage::user(format!(
+            "[Tool results]\n<tool_result name=\"shell\">\n{big}\n</tool_result>\n<tool_result name=\"file_read\">\nshort\n</tool_result>\n"
+        ))];
+
+        let report = fit_history(&mut history, &[], &budget(100_000), &counter);
+
+        assert_eq!(report.truncated_tool_results, 1);
+        let content = &history[0].content;
+        assert!(content.contains("<tool_result name=\"file_read\">\nshort\n</tool_result>"));
+        assert_eq!(content.matches("</tool_result>").count(), 2);
+        assert!(content.contains(TRUNCATION_MARKER));
+    }
+
+    #[test]
+    fn fit_history_drops_oldest_turns_without_orphaning_tool_results() {
+        let counter = HeuristicCounter;
+        let mut history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::assistant("a".repeat(2_000)),
+            native_tool_result(&"r".repeat(2_000)),
+            ChatMessage::assistant("older answer"),
+            ChatMessage::user("latest question"),
+        ];
+
+        let report = fit_history(&mut history, &[], &budget(200), &counter);
+
+        assert_eq!(report.dropped_messages, 2);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].role, "system");
+        assert_eq!(history[1].content, "older answer");
+        assert_eq!(history[2].content, "latest question");
+        assert!(report.tokens_after <= 200);
+    }
+
+    #[test]
+    fn fit_history_never_drops_latest_user_message() {
+        let counter = HeuristicCounter;
+        let mut history = vec![
+            ChatMessage::system("sys"),
+            ChatMessage::user("q".repeat(4_000)),
+        ];
+
+        let report = fit_history(&mut history, &[], &budget(100), &counter);
+
+        assert_eq!(report.dropped_messages, 0);
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn should_compact_triggers_above_three_quarters_of_history_budget() {
+        let counter = HeuristicCounter;
+        let small = vec![ChatMessage::user("hi")];
+        let large = vec![ChatMessage::user("x".repeat(3_400))];
+
+        assert!(!should_compact(&budget(1_000), &counter, &small));
+        assert!(should_compact(&budget(1_000), &counter, &large));
+    }
+}
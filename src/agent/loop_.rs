@@ -1,10 +1,11 @@
+use crate::agent::budget::{self, ContextBudget};
 use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
 use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, tokens, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
@@ -24,6 +25,7 @@ mod context;
 mod execution;
 mod history;
 mod parsing;
+mod streaming;
 
 use context::{build_context, build_hardware_context};
 use execution::{
@@ -31,7 +33,7 @@ use execution::{
     ToolExecutionOutcome,
 };
 #[cfg(test)]
-use history::{apply_compaction_summary, build_compaction_transcript};
+use history::{apply_compaction_summary, build_compaction_transcript, render_compaction_bullets};
 use history::{auto_compact_history, trim_history};
 #[allow(unused_imports)]
 use parsing::{
@@ -281,6 +283,8 @@ pub(crate) async fn agent_turn(
     model: &str,
     temperature: f64,
     silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
 ) -> Result<String> {
@@ -293,14 +297,15 @@ pub(crate) async fn agent_turn(
         model,
         temperature,
         silent,
-        None,
-        "channel",
+        approval,
+        channel_name,
         multimodal_config,
         max_tool_iterations,
         None,
         None,
         None,
         &[],
+        None,
     )
     .await
 }
@@ -337,6 +342,7 @@ pub(crate) async fn run_tool_call_loop(
     on_delta: Option<tokio::sync::mpsc::Sender<String>>,
     hooks: Option<&crate::hooks::HookRunner>,
     excluded_tools: &[String],
+    context_budget: Option<&ContextBudget>,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -350,7 +356,17 @@ pub(crate) async fn run_tool_call_loop(
         .map(|tool| tool.spec())
         .collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    // Stream every turn when the caller wants deltas, unless tool calls are
+    // prompt-guided: those live in the response text and must not leak into
+    // the draft before they are parsed out.
+    let stream_turns = on_delta.is_some()
+        && provider.supports_streaming()
+        && (use_native_tools || tool_specs.is_empty());
     let turn_id = Uuid::new_v4().to_string();
+    let context_budget = context_budget
+        .copied()
+        .unwrap_or_else(|| ContextBudget::for_model(model));
+    let token_counter = tokens::counter_for_model(model);
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
 
     for iteration in 0..max_iterations {
@@ -373,6 +389,24 @@ pub(crate) async fn run_tool_call_loop(
             .into());
         }
 
+        let fit = budget::fit_history(
+            history,
+            // Prompt-guided tool instructions are already in the system prompt.
+            if use_native_tools { &tool_specs } else { &[] },
+            &context_budget,
+            token_counter.as_ref(),
+        );
+        if fit.changed() {
+            tracing::info!(
+                counter = token_counter.name(),
+                tokens_before = fit.tokens_before,
+                tokens_after = fit.tokens_after,
+                truncated_tool_results = fit.truncated_tool_results,
+                dropped_messages = fit.dropped_messages,
+                "Fitted history to the context window"
+            );
+        }
+
         let prepared_messages =
             multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
 
@@ -420,14 +454,23 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &prepared_messages.messages,
-                tools: request_tools,
-            },
-            model,
-            temperature,
-        );
+        let request = ChatRequest {
+            messages: &prepared_messages.messages,
+            tools: request_tools,
+            response_format: None,
+        };
+        let chat_future = async {
+            match on_delta.as_ref().filter(|_| stream_turns) {
+                Some(tx) => streaming::stream_turn(provider, request, model, temperature, tx).await,
+                None => provider
+                    .chat(request, model, temperature)
+                    .await
+                    .map(|response| streaming::StreamedTurn {
+                        response,
+                        relayed_text: false,
+                    }),
+            }
+        };
 
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
             tokio::select! {
@@ -438,9 +481,12 @@ pub(crate) async fn run_tool_call_loop(
             chat_future.await
         };
 
+        let relayed_text: bool;
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
-                Ok(resp) => {
+                Ok(turn) => {
+                    relayed_text = turn.relayed_text;
+                    let resp = turn.response;
                     let (resp_input_tokens, resp_output_tokens) = resp
                         .usage
                         .as_ref()
@@ -605,9 +651,10 @@ pub(crate) async fn run_tool_call_loop(
                 }),
             );
             // No tool calls — this is the final response.
-            // If a streaming sender is provided, relay the text in small chunks
-            // so the channel can progressively update the draft message.
-            if let Some(ref tx) = on_delta {
+            // If a streaming sender is provided and the text was not already
+            // streamed live, relay it in small chunks so the channel can
+            // progressively update the draft message.
+            if let Some(tx) = on_delta.as_ref().filter(|_| !relayed_text) {
                 // Clear accumulated progress lines before streaming the final answer.
                 let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
                 // Split on whitespace boundaries, accumulating chunks of at least
@@ -650,7 +697,8 @@ pub(crate) async fn run_tool_call_loop(
         let mut individual_results: Vec<(Option<String>, String)> = Vec::new();
         let mut ordered_results: Vec<Option<(String, Option<String>, ToolExecutionOutcome)>> =
             (0..tool_calls.len()).map(|_| None).collect();
-        let allow_parallel_execution = should_execute_tools_in_parallel(&tool_calls, approval);
+        let allow_parallel_execution =
+            should_execute_tools_in_parallel(&tool_calls, approval).await;
         let mut executable_indices: Vec<usize> = Vec::new();
         let mut executable_calls: Vec<ParsedToolCall> = Vec::new();
 
@@ -731,21 +779,25 @@ pub(crate) async fn run_tool_call_loop(
 
             // ── Approval hook ────────────────────────────────
             if let Some(mgr) = approval {
-                if mgr.needs_approval(&tool_name) {
+                if mgr.needs_approval(&tool_name).await {
                     let request = ApprovalRequest {
                         tool_name: tool_name.clone(),
                         arguments: tool_args.clone(),
                     };
 
-                    // Only CLI supports interactive prompts today. For non-CLI channels,
-                    // fail closed instead of silently auto-approving privileged tools.
+                    // CLI prompts on stdin; other channels ask through the prompter
+                    // they installed (e.g. Teams cards) and otherwise fail closed
+                    // instead of silently auto-approving privileged tools.
                     let decision = if channel_name == "cli" {
                         mgr.prompt_cli(&request)
+                    } else if let Some(prompter) = crate::approval::channel_prompter() {
+                        prompter.prompt(&request).await
                     } else {
                         ApprovalResponse::No
                     };
 
-                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
+                    mgr.record_decision(&tool_name, &tool_args, decision, channel_name)
+                        .await;
 
                     if decision == ApprovalResponse::No {
                         let denied = "Denied by user.".to_string();
@@ -1350,6 +1402,7 @@ pub async fn run(
     let start = Instant::now();
 
     let mut final_output = String::new();
+    let context_budget = ContextBudget::from_config(&config.agent, model_name);
 
     if let Some(msg) = message {
         // Auto-save user message to memory (skip short/trivial messages)
@@ -1398,6 +1451,7 @@ pub async fn run(
             None,
             None,
             &[],
+            Some(&context_budget),
         )
         .await?;
         final_output = response.clone();
@@ -1520,6 +1574,7 @@ pub async fn run(
                 None,
                 None,
                 &[],
+                Some(&context_budget),
             )
             .await
             {
@@ -1540,12 +1595,23 @@ pub async fn run(
             }
             observer.record_event(&ObserverEvent::TurnComplete);
 
-            // Auto-compaction before hard trimming to preserve long-context signal.
+            // Auto-compaction before hard trimming to preserve long-context
+            // signal. Compact early when history nears the token budget even
+            // if the message count is still under the cap.
+            let compact_threshold = if budget::should_compact(
+                &context_budget,
+                tokens::counter_for_model(model_name).as_ref(),
+                &history,
+            ) {
+                0
+            } else {
+                config.agent.max_history_messages
+            };
             if let Ok(compacted) = auto_compact_history(
                 &mut history,
                 provider.as_ref(),
                 model_name,
-                config.agent.max_history_messages,
+                compact_threshold,
             )
             .await
             {
@@ -1745,6 +1811,12 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ChatMessage::user(&enriched),
     ];
 
+    // Channels that can ask the user (see `approval::with_channel_prompter`)
+    // get supervised-mode approvals; other callers run without prompts.
+    let prompter = crate::approval::channel_prompter();
+    let approval = prompter
+        .as_ref()
+        .map(|_| ApprovalManager::from_config(&config.autonomy));
     agent_turn(
         provider.as_ref(),
         &mut history,
@@ -1754,6 +1826,8 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &model_name,
         config.default_temperature,
         true,
+        approval.as_ref(),
+        prompter.as_ref().map_or("channel", |prompter| prompter.channel()),
         &config.multimodal,
         config.agent.max_tool_iterations,
     )
@@ -1822,6 +1896,7 @@ mod tests {
             ProviderCapabilities {
                 native_tool_calling: false,
                 vision: true,
+                structured_output: false,
             }
         }
 
@@ -1921,6 +1996,64 @@ mod tests {
         }
     }
 
+    /// Streams scripted event sequences, one per turn, through `stream_chat`.
+    struct StreamingProvider {
+        turns: Mutex<VecDeque<Vec<crate::providers::StreamEvent>>>,
+    }
+
+    impl StreamingProvider {
+        fn new(turns: Vec<Vec<crate::providers::StreamEvent>>) -> Self {
+            Self {
+                turns: Mutex::new(turns.into()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for StreamingProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                vision: false,
+                structured_output: false,
+            }
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("streaming provider should only be used through stream_chat");
+        }
+
+        fn stream_chat<'a>(
+            &'a self,
+            _request: ChatRequest<'a>,
+            _model: &str,
+            _temperature: f64,
+            _options: crate::providers::StreamOptions,
+        ) -> futures_util::stream::BoxStream<
+            'a,
+            crate::providers::traits::StreamResult<crate::providers::StreamEvent>,
+        > {
+            use futures_util::StreamExt;
+            let events = self
+                .turns
+                .lock()
+                .expect("turns lock should be valid")
+                .pop_front()
+                .unwrap_or_default();
+            futures_util::stream::iter(events.into_iter().map(Ok)).boxed()
+        }
+    }
+
     struct CountingTool {
         name: String,
         invocations: Arc<AtomicUsize>,
@@ -2069,6 +2202,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect_err("provider without vision support should fail");
@@ -2115,6 +2249,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect_err("oversized payload must fail");
@@ -2155,6 +2290,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("valid multimodal payload should pass");
@@ -2163,19 +2299,19 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
-    #[test]
-    fn should_execute_tools_in_parallel_returns_false_for_single_call() {
+    #[tokio::test]
+    async fn should_execute_tools_in_parallel_returns_false_for_single_call() {
         let calls = vec![ParsedToolCall {
             name: "file_read".to_string(),
             arguments: serde_json::json!({"path": "a.txt"}),
             tool_call_id: None,
         }];
 
-        assert!(!should_execute_tools_in_parallel(&calls, None));
+        assert!(!should_execute_tools_in_parallel(&calls, None).await);
     }
 
-    #[test]
-    fn should_execute_tools_in_parallel_returns_false_when_approval_is_required() {
+    #[tokio::test]
+    async fn should_execute_tools_in_parallel_returns_false_when_approval_is_required() {
         let calls = vec![
             ParsedToolCall {
                 name: "shell".to_string(),
@@ -2191,14 +2327,11 @@ mod tests {
         let approval_cfg = crate::config::AutonomyConfig::default();
         let approval_mgr = ApprovalManager::from_config(&approval_cfg);
 
-        assert!(!should_execute_tools_in_parallel(
-            &calls,
-            Some(&approval_mgr)
-        ));
+        assert!(!should_execute_tools_in_parallel(&calls, Some(&approval_mgr)).await);
     }
 
-    #[test]
-    fn should_execute_tools_in_parallel_returns_true_when_cli_has_no_interactive_approvals() {
+    #[tokio::test]
+    async fn should_execute_tools_in_parallel_returns_true_when_cli_has_no_interactive_approvals() {
         let calls = vec![
             ParsedToolCall {
                 name: "shell".to_string(),
@@ -2217,10 +2350,7 @@ mod tests {
         };
         let approval_mgr = ApprovalManager::from_config(&approval_cfg);
 
-        assert!(should_execute_tools_in_parallel(
-            &calls,
-            Some(&approval_mgr)
-        ));
+        assert!(should_execute_tools_in_parallel(&calls, Some(&approval_mgr)).await);
     }
 
     #[tokio::test]
@@ -2281,6 +2411,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("parallel execution should complete");
@@ -2352,6 +2483,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("tool loop should complete with denied tool execution");
@@ -2406,6 +2538,7 @@ mod tests {
             None,
             None,
             &excluded_tools,
+            None,
         )
         .await
         .expect("tool loop should complete with blocked tool execution");
@@ -2470,6 +2603,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("loop should finish after deduplicating repeated calls");
@@ -2526,6 +2660,7 @@ mod tests {
             None,
             None,
             &[],
+            None,
         )
         .await
         .expect("native fallback id flow should complete");
@@ -2546,6 +2681,94 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_streams_tool_using_turns() {
+        use crate::providers::StreamEvent;
+
+        let provider = StreamingProvider::new(vec![
+            vec![
+                StreamEvent::TextDelta("Counting now.".into()),
+                StreamEvent::ToolCallStart {
+                    index: 0,
+                    id: "call_1".into(),
+                    name: "count_tool".into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments: r#"{"value":"#.into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments: r#""X"}"#.into(),
+                },
+                StreamEvent::ToolCallEnd { index: 0 },
+                StreamEvent::Done,
+            ],
+            vec![
+                StreamEvent::TextDelta("All ".into()),
+                StreamEvent::TextDelta("done".into()),
+                StreamEvent::Done,
+            ],
+        ]);
+
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("count something"),
+        ];
+        let observer = NoopObserver;
+        let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "telegram",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            Some(tx),
+            None,
+            &[],
+            None,
+        )
+        .await
+        .expect("streamed tool loop should complete");
+
+        assert_eq!(result, "All done");
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+        assert!(history
+            .iter()
+            .any(|msg| msg.role == "tool" && msg.content.contains("\"tool_call_id\":\"call_1\"")));
+
+        let mut deltas = Vec::new();
+        while let Some(delta) = rx.recv().await {
+            deltas.push(delta);
+        }
+        let narration = deltas
+            .iter()
+            .position(|d| d == "Counting now.")
+            .expect("first turn text should stream live");
+        assert_eq!(deltas[narration - 1], DRAFT_CLEAR_SENTINEL);
+        assert_eq!(
+            deltas.last().map(String::as_str),
+            Some("All done"),
+            "final answer should be relayed once, as streamed"
+        );
+        assert_eq!(deltas.iter().filter(|d| d.contains("All done")).count(), 1);
+        assert_eq!(deltas[deltas.len() - 2], DRAFT_CLEAR_SENTINEL);
+    }
+
     #[test]
     fn parse_tool_calls_extracts_single_call() {
         let response = r#"Let me check that.
@@ -3225,6 +3448,40 @@ Tail"#;
         assert!(history[3].content.contains("recent 2"));
     }
 
+    #[test]
+    fn render_compaction_bullets_normalizes_and_caps_entries() {
+        let bullets: Vec<String> = (0..15).map(|i| format!("- fact {i}")).collect();
+        let summary = serde_json::json!({ "bullets": bullets });
+
+        let rendered = render_compaction_bullets(&summary).unwrap();
+
+        assert_eq!(rendered.lines().count(), 12);
+        assert!(rendered.starts_with("- fact 0\n"));
+        assert!(!rendered.contains("- - "));
+        assert!(render_compaction_bullets(&serde_json::json!({ "bullets": ["  "] })).is_none());
+    }
+
+    #[tokio::test]
+    async fn auto_compact_history_skips_structured_repair_turn() {
+        let provider = ScriptedProvider::from_text_responses(vec![
+            "not json",
+            r#"{"bullets": ["from repair"]}"#,
+        ]);
+        let mut history = vec![ChatMessage::system("sys")];
+        for i in 0..30 {
+            history.push(ChatMessage::user(format!("message {i}")));
+        }
+
+        let compacted = auto_compact_history(&mut history, &provider, "model", 20)
+            .await
+            .unwrap();
+
+        assert!(compacted);
+        assert!(history[1].content.contains("Compaction summary"));
+        assert!(!history[1].content.contains("from repair"));
+        assert_eq!(provider.responses.lock().unwrap().len(), 1);
+    }
+
     #[test]
     fn autosave_memory_key_has_prefix_and_uniqueness() {
         let key1 = autosave_memory_key("user_msg");
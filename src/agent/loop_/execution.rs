@@ -98,7 +98,7 @@ pub(super) struct ToolExecutionOutcome {
     pub(super) duration: Duration,
 }
 
-pub(super) fn should_execute_tools_in_parallel(
+pub(super) async fn should_execute_tools_in_parallel(
     tool_calls: &[ParsedToolCall],
     approval: Option<&ApprovalManager>,
 ) -> bool {
@@ -107,10 +107,12 @@ pub(super) fn should_execute_tools_in_parallel(
     }
 
     if let Some(mgr) = approval {
-        if tool_calls.iter().any(|call| mgr.needs_approval(&call.name)) {
-            // Approval-gated calls must keep sequential handling so the caller can
-            // enforce CLI prompt/deny policy consistently.
-            return false;
+        for call in tool_calls {
+            if mgr.needs_approval(&call.name).await {
+                // Approval-gated calls must keep sequential handling so the caller can
+                // enforce CLI prompt/deny policy consistently.
+                return false;
+            }
         }
     }
 
@@ -1,4 +1,5 @@
-use crate::providers::{ChatMessage, Provider};
+use crate::providers::structured::chat_json_with_repairs;
+use crate::providers::{ChatMessage, Provider, ResponseFormat};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use std::fmt::Write;
@@ -12,6 +13,9 @@ const COMPACTION_MAX_SOURCE_CHARS: usize = 12_000;
 /// Max characters retained in stored compaction summary.
 const COMPACTION_MAX_SUMMARY_CHARS: usize = 2_000;
 
+/// Max bullet points kept from a structured compaction summary.
+const COMPACTION_MAX_BULLETS: usize = 12;
+
 /// Trim conversation history to prevent unbounded growth.
 /// Preserves the system prompt (first message if role=system) and the most recent messages.
 pub(super) fn trim_history(history: &mut Vec<ChatMessage>, max_history: usize) {
@@ -46,6 +50,42 @@ pub(super) fn build_compaction_transcript(messages: &[ChatMessage]) -> String {
     }
 }
 
+/// Schema for the summarizer's structured reply.
+fn compaction_summary_format() -> ResponseFormat {
+    ResponseFormat::json_schema(
+        "compaction_summary",
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "bullets": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Short context bullet points, most important first"
+                }
+            },
+            "required": ["bullets"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+/// Render the structured summary as bullet lines, dropping empty entries.
+pub(super) fn render_compaction_bullets(summary: &serde_json::Value) -> Option<String> {
+    let mut rendered = String::new();
+    for bullet in summary
+        .get("bullets")?
+        .as_array()?
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .take(COMPACTION_MAX_BULLETS)
+    {
+        let _ = writeln!(rendered, "- {}", bullet.trim_start_matches(['-', '*', ' ']));
+    }
+    (!rendered.is_empty()).then_some(rendered)
+}
+
 pub(super) fn apply_compaction_summary(
     history: &mut Vec<ChatMessage>,
     start: usize,
@@ -84,20 +124,49 @@ pub(super) async fn auto_compact_history(
     let to_compact: Vec<ChatMessage> = history[start..compact_end].to_vec();
     let transcript = build_compaction_transcript(&to_compact);
 
-    let summarizer_system = "You are a conversation compaction engine. Summarize older chat history into concise context for future turns. Preserve: user preferences, commitments, decisions, unresolved tasks, key facts. Omit: filler, repeated chit-chat, verbose tool logs. Output plain text bullet points only.";
+    let summarizer_system = "You are a conversation compaction engine. Summarize older chat history into concise context for future turns. Preserve: user preferences, commitments, decisions, unresolved tasks, key facts. Omit: filler, repeated chit-chat, verbose tool logs.";
 
     let summarizer_user = format!(
-        "Summarize the following conversation history for context preservation. Keep it short (max 12 bullet points).\n\n{}",
+        "Summarize the following conversation history for context preservation. Keep it short (max {COMPACTION_MAX_BULLETS} bullet points).\n\n{}",
         transcript
     );
 
-    let summary_raw = provider
-        .chat_with_system(Some(summarizer_system), &summarizer_user, model, 0.2)
-        .await
-        .unwrap_or_else(|_| {
-            // Fallback to deterministic local truncation when summarization fails.
-            truncate_with_ellipsis(&transcript, COMPACTION_MAX_SUMMARY_CHARS)
-        });
+    let messages = [
+        ChatMessage::system(summarizer_system),
+        ChatMessage::user(&summarizer_user),
+    ];
+    // No repair turn here: a malformed summary falls back to the plain-text
+    // request below, so a repair would only add a third round trip.
+    let structured = chat_json_with_repairs(
+        provider,
+        &messages,
+        &compaction_summary_format(),
+        model,
+        0.2,
+        0,
+    )
+    .await
+    .inspect_err(|e| tracing::debug!("Structured compaction summary failed: {e}"))
+    .ok()
+    .and_then(|summary| render_compaction_bullets(&summary));
+
+    let summary_raw = match structured {
+        Some(summary) => summary,
+        None => provider
+            .chat_with_system(
+                Some(&format!(
+                    "{summarizer_system} Output plain text bullet points only."
+                )),
+                &summarizer_user,
+                model,
+                0.2,
+            )
+            .await
+            .unwrap_or_else(|_| {
+                // Fallback to deterministic local truncation when summarization fails.
+                truncate_with_ellipsis(&transcript, COMPACTION_MAX_SUMMARY_CHARS)
+            }),
+    };
 
     let summary = truncate_with_ellipsis(&summary_raw, COMPACTION_MAX_SUMMARY_CHARS);
     apply_compaction_summary(history, start, compact_end, &summary);
@@ -0,0 +1,60 @@
+use super::{DRAFT_CLEAR_SENTINEL, STREAM_CHUNK_MIN_CHARS};
+use crate::providers::{
+    ChatRequest, ChatResponse, Provider, StreamAccumulator, StreamEvent, StreamOptions,
+};
+use futures_util::StreamExt;
+use tokio::sync::mpsc;
+
+/// Result of one provider turn driven through `Provider::stream_chat`.
+pub(super) struct StreamedTurn {
+    pub(super) response: ChatResponse,
+    /// Whether answer text was already relayed to the draft during the turn.
+    pub(super) relayed_text: bool,
+}
+
+/// Run one turn through `Provider::stream_chat`, relaying answer text to
+/// `on_delta` as it arrives while tool calls, reasoning and usage are
+/// assembled by a [`StreamAccumulator`].
+///
+/// The draft is cleared before the first text of the turn so progress lines
+/// are replaced by the response. Text is relayed in chunks of at least
+/// `STREAM_CHUNK_MIN_CHARS` characters to avoid flooding draft edits.
+pub(super) async fn stream_turn(
+    provider: &dyn Provider,
+    request: ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+    on_delta: &mpsc::Sender<String>,
+) -> anyhow::Result<StreamedTurn> {
+    let mut events = provider.stream_chat(request, model, temperature, StreamOptions::new(true));
+    let mut accumulator = StreamAccumulator::new();
+    let mut pending = String::new();
+    let mut relayed_text = false;
+
+    while let Some(event) = events.next().await {
+        let event = event?;
+        if let StreamEvent::TextDelta(text) = &event {
+            if !relayed_text {
+                let _ = on_delta.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+                relayed_text = true;
+            }
+            pending.push_str(text);
+            if pending.len() >= STREAM_CHUNK_MIN_CHARS {
+                let _ = on_delta.send(std::mem::take(&mut pending)).await;
+            }
+        }
+        accumulator.push(&event);
+        if accumulator.is_done() {
+            break;
+        }
+    }
+
+    if !pending.is_empty() {
+        let _ = on_delta.send(pending).await;
+    }
+
+    Ok(StreamedTurn {
+        response: accumulator.finish(),
+        relayed_text,
+    })
+}
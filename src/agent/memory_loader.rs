@@ -69,7 +69,7 @@ impl MemoryLoader for DefaultMemoryLoader {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{Memory, MemoryCategory, MemoryEntry};
+    use crate::memory::{Memory, MemoryCategory, MemoryEntry, MemoryMetadata};
     use std::sync::Arc;
 
     struct MockMemory;
@@ -106,6 +106,8 @@ mod tests {
                 timestamp: "now".into(),
                 session_id: None,
                 score: None,
+                metadata: MemoryMetadata::default(),
+                namespace: None,
             }])
         }
 
@@ -209,6 +211,8 @@ mod tests {
                     timestamp: "now".into(),
                     session_id: None,
                     score: Some(0.95),
+                    metadata: MemoryMetadata::default(),
+                    namespace: None,
                 },
                 MemoryEntry {
                     id: "2".into(),
@@ -218,6 +222,8 @@ mod tests {
                     timestamp: "now".into(),
                     session_id: None,
                     score: Some(0.9),
+                    metadata: MemoryMetadata::default(),
+                    namespace: None,
                 },
             ]),
         };
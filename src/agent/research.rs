@@ -8,14 +8,22 @@
 //! Supports both:
 //! - Native tool calling (OpenAI, Anthropic, Bedrock, etc.)
 //! - Prompt-guided tool calling (Gemini and other providers without native support)
+//!
+//! Providers with native structured output are asked for the findings as a
+//! JSON list once gathering stops; the free-text `[RESEARCH COMPLETE]`
+//! summary remains the fallback.
 
 use crate::agent::dispatcher::{ToolDispatcher, XmlToolDispatcher};
 use crate::config::{ResearchPhaseConfig, ResearchTrigger};
 use crate::observability::Observer;
+use crate::providers::structured::chat_json_with_repairs;
 use crate::providers::traits::build_tool_instructions_text;
-use crate::providers::{ChatMessage, ChatRequest, ChatResponse, Provider, ToolCall};
+use crate::providers::{
+    ChatMessage, ChatRequest, ChatResponse, Provider, ResponseFormat, ToolCall,
+};
 use crate::tools::{Tool, ToolResult, ToolSpec};
 use anyhow::Result;
+use std::fmt::Write;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -83,6 +91,47 @@ When you have enough information, summarize what you found in this format:
 - Finding 3: ...
 "#;
 
+/// Max findings kept from a structured research summary.
+const MAX_FINDINGS: usize = 12;
+
+/// Final request for structured findings once gathering has stopped.
+const FINDINGS_REQUEST: &str = "Research is finished. List the facts you gathered that help answer the question, most important first. Do not answer the question itself.";
+
+fn findings_format() -> ResponseFormat {
+    ResponseFormat::json_schema(
+        "research_findings",
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "findings": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "One gathered fact per entry"
+                }
+            },
+            "required": ["findings"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+/// Render structured findings in the `[RESEARCH COMPLETE]` text layout.
+fn render_findings(findings: &serde_json::Value) -> Option<String> {
+    let mut rendered = String::new();
+    for finding in findings
+        .get("findings")?
+        .as_array()?
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .map(|f| f.trim().trim_start_matches(['-', '*', ' ']))
+        .filter(|f| !f.is_empty())
+        .take(MAX_FINDINGS)
+    {
+        let _ = write!(rendered, "\n- {finding}");
+    }
+    (!rendered.is_empty()).then(|| format!("[RESEARCH COMPLETE]{rendered}"))
+}
+
 /// Run the research phase.
 ///
 /// This executes a focused LLM + tools loop to gather information before
@@ -126,7 +175,7 @@ pub async fn run_research_phase(
     };
 
     let system_prompt = if uses_native_tools {
-        base_prompt
+        base_prompt.clone()
     } else {
         // Prompt-guided: append tool instructions
         format!(
@@ -163,6 +212,7 @@ pub async fn run_research_phase(
             } else {
                 None // Prompt-guided: tools are in system prompt
             },
+            response_format: None,
         };
 
         let response: ChatResponse = provider.chat(request, model, temperature).await?;
@@ -174,6 +224,7 @@ pub async fn run_research_phase(
                 if let Some(idx) = text.find("[RESEARCH COMPLETE]") {
                     collected_context = text[idx..].to_string();
                 }
+                messages.push(ChatMessage::assistant(text));
                 break;
             }
         }
@@ -201,6 +252,7 @@ pub async fn run_research_phase(
         // If no tool calls, we're done
         if tool_calls.is_empty() {
             if let Some(text) = response.text {
+                messages.push(ChatMessage::assistant(&text));
                 collected_context = text;
             }
             break;
@@ -239,6 +291,28 @@ pub async fn run_research_phase(
         }
     }
 
+    // No repair turn: the text summary above is the fallback.
+    if provider.supports_structured_output() {
+        let mut final_messages = vec![ChatMessage::system(&base_prompt)];
+        final_messages.extend(messages);
+        final_messages.push(ChatMessage::user(FINDINGS_REQUEST));
+        if let Some(findings) = chat_json_with_repairs(
+            provider,
+            &final_messages,
+            &findings_format(),
+            model,
+            temperature,
+            0,
+        )
+        .await
+        .inspect_err(|e| tracing::debug!("Structured research findings failed: {e}"))
+        .ok()
+        .and_then(|findings| render_findings(&findings))
+        {
+            collected_context = findings;
+        }
+    }
+
     let duration = start.elapsed();
 
     Ok(ResearchResult {
@@ -290,6 +364,102 @@ fn truncate(s: &str, max_len: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::observability::NoopObserver;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    /// Replies in order; reports structured output support as configured.
+    struct ScriptedProvider {
+        replies: Mutex<Vec<&'static str>>,
+        structured: bool,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&'static str], structured: bool) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().copied().collect()),
+                structured,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        fn supports_structured_output(&self) -> bool {
+            self.structured
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.replies.lock().pop().unwrap_or("").to_string())
+        }
+
+        async fn chat_with_history(
+            &self,
+            _messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.replies.lock().pop().unwrap_or("").to_string())
+        }
+    }
+
+    async fn research(provider: &ScriptedProvider) -> ResearchResult {
+        run_research_phase(
+            &ResearchPhaseConfig::default(),
+            provider,
+            &[],
+            "why is the build slow?",
+            "test-model",
+            0.0,
+            Arc::new(NoopObserver),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn structured_findings_replace_text_summary() {
+        let provider = ScriptedProvider::new(
+            &[
+                "[RESEARCH COMPLETE]\n- Finding 1: free text",
+                r#"{"findings": ["- incremental builds are off", "  ", "target dir is 40 GB"]}"#,
+            ],
+            true,
+        );
+        let result = research(&provider).await;
+        assert_eq!(
+            result.context,
+            "[RESEARCH COMPLETE]\n- incremental builds are off\n- target dir is 40 GB"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_structured_findings_keep_text_summary() {
+        let provider = ScriptedProvider::new(
+            &["[RESEARCH COMPLETE]\n- Finding 1: free text", "not json"],
+            true,
+        );
+        let result = research(&provider).await;
+        assert_eq!(
+            result.context,
+            "[RESEARCH COMPLETE]\n- Finding 1: free text"
+        );
+
+        let provider = ScriptedProvider::new(
+            &["[RESEARCH COMPLETE]\n- Finding 1: free text", "{}"],
+            false,
+        );
+        assert_eq!(
+            research(&provider).await.context,
+            "[RESEARCH COMPLETE]\n- Finding 1: free text"
+        );
+    }
 
     #[test]
     fn should_trigger_never() {
@@ -5,11 +5,14 @@
 
 use crate::config::AutonomyConfig;
 use crate::security::AutonomyLevel;
+use async_trait::async_trait;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
 use std::io::{self, BufRead, Write};
-use tokio::sync::Mutex;
+use std::sync::{Arc, LazyLock};
+use tokio::sync::{broadcast, Mutex};
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -42,6 +45,42 @@ pub struct ApprovalLogEntry {
     pub channel: String,
 }
 
+// ── Process-wide decision feed ───────────────────────────────────
+
+/// Decisions kept in memory across all managers for the gateway dashboard.
+const RECENT_DECISIONS_CAPACITY: usize = 1000;
+
+static RECENT_DECISIONS: LazyLock<std::sync::Mutex<VecDeque<ApprovalLogEntry>>> =
+    LazyLock::new(|| std::sync::Mutex::new(VecDeque::new()));
+
+static DECISION_EVENTS: LazyLock<broadcast::Sender<ApprovalLogEntry>> =
+    LazyLock::new(|| broadcast::channel(256).0);
+
+fn publish_decision(entry: &ApprovalLogEntry) {
+    let mut recent = RECENT_DECISIONS.lock().unwrap_or_else(|e| e.into_inner());
+    if recent.len() == RECENT_DECISIONS_CAPACITY {
+        recent.pop_front();
+    }
+    recent.push_back(entry.clone());
+    drop(recent);
+    let _ = DECISION_EVENTS.send(entry.clone());
+}
+
+/// Recent approval decisions from every manager in this process, oldest first.
+pub fn recent_decisions() -> Vec<ApprovalLogEntry> {
+    RECENT_DECISIONS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// Subscribe to approval decisions as they are recorded.
+pub fn subscribe_decisions() -> broadcast::Receiver<ApprovalLogEntry> {
+    DECISION_EVENTS.subscribe()
+}
+
 // ── ApprovalManager ──────────────────────────────────────────────
 
 /// Manages the interactive approval workflow.
@@ -131,6 +170,7 @@ impl ApprovalManager {
             decision,
             channel: channel.to_string(),
         };
+        publish_decision(&entry);
         let mut log = self.audit_log.lock().await;
         log.push(entry);
     }
@@ -154,6 +194,36 @@ impl ApprovalManager {
     }
 }
 
+// ── Channel prompts ──────────────────────────────────────────────
+
+/// Asks a chat user to approve a tool call (e.g. Teams adaptive-card buttons).
+#[async_trait]
+pub trait ChannelApprovalPrompter: Send + Sync {
+    /// Channel name recorded in the audit trail.
+    fn channel(&self) -> &str;
+
+    /// Ask the user and wait for their decision; fail closed with `No`.
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalResponse;
+}
+
+tokio::task_local! {
+    static CHANNEL_PROMPTER: Arc<dyn ChannelApprovalPrompter>;
+}
+
+/// Run `future` with `prompter` answering approval prompts for non-CLI
+/// channels. Without a prompter those calls are denied.
+pub async fn with_channel_prompter<F: Future>(
+    prompter: Arc<dyn ChannelApprovalPrompter>,
+    future: F,
+) -> F::Output {
+    CHANNEL_PROMPTER.scope(prompter, future).await
+}
+
+/// Prompter installed by [`with_channel_prompter`] for the current task.
+pub fn channel_prompter() -> Option<Arc<dyn ChannelApprovalPrompter>> {
+    CHANNEL_PROMPTER.try_with(Arc::clone).ok()
+}
+
 // ── CLI prompt ───────────────────────────────────────────────────
 
 /// Display the approval prompt and read user input from stdin.
@@ -372,6 +442,31 @@ mod tests {
         assert_eq!(log[0].channel, "telegram");
     }
 
+    #[tokio::test]
+    async fn decisions_are_published_process_wide() {
+        let mut rx = subscribe_decisions();
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        mgr.record_decision(
+            "decision_feed_probe",
+            &serde_json::json!({}),
+            ApprovalResponse::No,
+            "webchat",
+        )
+        .await;
+
+        // Other tests record decisions concurrently; look for ours.
+        loop {
+            let entry = rx.recv().await.unwrap();
+            if entry.tool_name == "decision_feed_probe" {
+                assert_eq!(entry.channel, "webchat");
+                break;
+            }
+        }
+        assert!(recent_decisions()
+            .iter()
+            .any(|entry| entry.tool_name == "decision_feed_probe"));
+    }
+
     // ── summarize_args ───────────────────────────────────────
 
     #[test]
@@ -429,4 +524,39 @@ mod tests {
         let parsed: ApprovalRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.tool_name, "shell");
     }
+
+    // ── Channel prompter ─────────────────────────────────────
+
+    struct FixedPrompter(ApprovalResponse);
+
+    #[async_trait]
+    impl ChannelApprovalPrompter for FixedPrompter {
+        fn channel(&self) -> &str {
+            "teams"
+        }
+
+        async fn prompt(&self, _request: &ApprovalRequest) -> ApprovalResponse {
+            self.0
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_prompter_is_scoped_to_the_task() {
+        assert!(channel_prompter().is_none());
+
+        let decision =
+            with_channel_prompter(Arc::new(FixedPrompter(ApprovalResponse::Always)), async {
+                let prompter = channel_prompter().expect("prompter in scope");
+                assert_eq!(prompter.channel(), "teams");
+                prompter
+                    .prompt(&ApprovalRequest {
+                        tool_name: "shell".into(),
+                        arguments: serde_json::json!({}),
+                    })
+                    .await
+            })
+            .await;
+        assert_eq!(decision, ApprovalResponse::Always);
+        assert!(channel_prompter().is_none());
+    }
 }
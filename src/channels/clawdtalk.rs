@@ -49,6 +49,9 @@ impl ChannelConfig for ClawdTalkConfig {
     fn name() -> &'static str {
         "ClawdTalk"
     }
+    fn key() -> &'static str {
+        "clawdtalk"
+    }
     fn desc() -> &'static str {
         "ClawdTalk Channel"
     }
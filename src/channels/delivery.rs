@@ -0,0 +1,323 @@
+//! Outbound delivery of scheduled output (cron jobs, heartbeat).
+//!
+//! [`DeliveryRouter`] resolves configured channels by name and sends one
+//! message to each target, retrying failed sends when asked.
+
+use super::traits::{Channel, SendMessage};
+use crate::config::Config;
+use crate::cron::DeliveryTarget;
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Delay before the first retry; doubles per attempt up to [`MAX_RETRY_BACKOFF`].
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Delivery-capable channels from the `[channels_config]` registry, each
+/// with whether its section is present. Names match [`Channel::name`].
+fn delivery_channels(config: &Config) -> Vec<(&'static str, bool)> {
+    let cc = &config.channels_config;
+    cc.channels_except_webhook()
+        .into_iter()
+        .map(|(handle, present)| match handle.key() {
+            // Legacy `lark.use_feishu` registers as Feishu.
+            "feishu" => (
+                "feishu",
+                present || cc.lark.as_ref().is_some_and(|lk| lk.use_feishu),
+            ),
+            key => (key, present),
+        })
+        .collect()
+}
+
+/// Whether the `[channels_config.<name>]` section for a delivery channel is
+/// present. `None` means `name` is not a channel that can deliver messages.
+pub(crate) fn channel_config_present(config: &Config, name: &str) -> Option<bool> {
+    delivery_channels(config)
+        .into_iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, present)| present)
+}
+
+/// Sends scheduled output to channels configured in `[channels_config]`.
+pub(crate) struct DeliveryRouter {
+    channels: Vec<Arc<dyn Channel>>,
+    configured: Vec<String>,
+    known: Vec<String>,
+    retry_backoff: Duration,
+}
+
+impl DeliveryRouter {
+    /// Build every configured channel once; reuse the router across sends.
+    pub(crate) fn from_config(config: &Config) -> Self {
+        let channels = super::collect_configured_channels(config, "delivery")
+            .into_iter()
+            .map(|configured| configured.channel)
+            .collect();
+        let known = delivery_channels(config);
+        let configured = known
+            .iter()
+            .filter(|(_, present)| *present)
+            .map(|(name, _)| (*name).to_string())
+            .collect();
+        Self {
+            channels,
+            configured,
+            known: known
+                .into_iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            retry_backoff: RETRY_BACKOFF,
+        }
+    }
+
+    /// Look up a channel by its [`Channel::name`] (case-insensitive).
+    pub(crate) fn resolve(&self, name: &str) -> Result<Arc<dyn Channel>> {
+        if let Some(channel) = self
+            .channels
+            .iter()
+            .find(|channel| channel.name().eq_ignore_ascii_case(name))
+        {
+            return Ok(Arc::clone(channel));
+        }
+
+        if self
+            .configured
+            .iter()
+            .any(|configured| configured.eq_ignore_ascii_case(name))
+        {
+            anyhow::bail!(
+                "{name} channel is configured but cannot send in this build (check cargo features and required fields)"
+            );
+        }
+        if self
+            .known
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(name))
+        {
+            anyhow::bail!("{name} channel not configured");
+        }
+        anyhow::bail!("unsupported delivery channel: {name}")
+    }
+
+    /// Send `content` to every target. All targets are attempted; the error
+    /// lists each one that still failed after `max_retries` retries.
+    pub(crate) async fn deliver(
+        &self,
+        targets: &[DeliveryTarget],
+        content: &str,
+        subject: Option<&str>,
+        max_retries: u32,
+    ) -> Result<()> {
+        let mut failures = Vec::new();
+        for target in targets {
+            if let Err(e) = self.send(target, content, subject, max_retries).await {
+                failures.push(format!("{}:{}: {e}", target.channel, target.to));
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("{}", failures.join("; "))
+        }
+    }
+
+    async fn send(
+        &self,
+        target: &DeliveryTarget,
+        content: &str,
+        subject: Option<&str>,
+        max_retries: u32,
+    ) -> Result<()> {
+        let channel = self.resolve(&target.channel)?;
+        let message = match subject {
+            Some(subject) => SendMessage::with_subject(content, &target.to, subject),
+            None => SendMessage::new(content, &target.to),
+        };
+
+        let mut backoff = self.retry_backoff;
+        let mut attempt = 0;
+        loop {
+            match channel.send(&message).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt < max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "Delivery to {}:{} failed (attempt {attempt}/{}): {e}",
+                        target.channel,
+                        target.to,
+                        max_retries + 1
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+
+    /// Records sends; fails the first `failures` attempts.
+    struct RecordingChannel {
+        name: &'static str,
+        failures: Mutex<u32>,
+        sent: Mutex<Vec<SendMessage>>,
+    }
+
+    impl RecordingChannel {
+        fn new(name: &'static str, failures: u32) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                failures: Mutex::new(failures),
+                sent: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn send(&self, message: &SendMessage) -> Result<()> {
+            let mut failures = self.failures.lock();
+            if *failures > 0 {
+                *failures -= 1;
+                anyhow::bail!("transient failure");
+            }
+            self.sent.lock().push(message.clone());
+            Ok(())
+        }
+
+        async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn router(channels: Vec<Arc<dyn Channel>>, configured: &[&str]) -> DeliveryRouter {
+        DeliveryRouter {
+            channels,
+            configured: configured.iter().map(|name| (*name).to_string()).collect(),
+            known: delivery_channels(&Config::default())
+                .into_iter()
+                .map(|(name, _)| name.to_string())
+                .collect(),
+            retry_backoff: Duration::from_millis(1),
+        }
+    }
+
+    fn target(channel: &str, to: &str) -> DeliveryTarget {
+        DeliveryTarget {
+            channel: channel.into(),
+            to: to.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn deliver_sends_to_every_target_by_channel_name() {
+        let matrix = RecordingChannel::new("matrix", 0);
+        let email = RecordingChannel::new("email", 0);
+        let router = router(vec![matrix.clone(), email.clone()], &[]);
+
+        router
+            .deliver(
+                &[
+                    target("Matrix", "!ops:example.org"),
+                    target("email", "ops@example.org"),
+                ],
+                "nightly backup ok",
+                Some("backup"),
+                0,
+            )
+            .await
+            .unwrap();
+
+        let matrix_sent = matrix.sent.lock();
+        assert_eq!(matrix_sent.len(), 1);
+        assert_eq!(matrix_sent[0].recipient, "!ops:example.org");
+        assert_eq!(matrix_sent[0].content, "nightly backup ok");
+        let email_sent = email.sent.lock();
+        assert_eq!(email_sent[0].subject.as_deref(), Some("backup"));
+    }
+
+    #[tokio::test]
+    async fn deliver_retries_transient_failures() {
+        let signal = RecordingChannel::new("signal", 2);
+        let router = router(vec![signal.clone()], &[]);
+
+        router
+            .deliver(&[target("signal", "+15550001")], "done", None, 2)
+            .await
+            .unwrap();
+        assert_eq!(signal.sent.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn deliver_reports_failed_targets_after_trying_all() {
+        let irc = RecordingChannel::new("irc", 5);
+        let slack = RecordingChannel::new("slack", 0);
+        let router = router(vec![irc.clone(), slack.clone()], &[]);
+
+        let err = router
+            .deliver(
+                &[target("irc", "#ops"), target("slack", "C123")],
+                "done",
+                None,
+                1,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("irc:#ops: transient failure"), "{err}");
+        assert!(!err.contains("slack"), "{err}");
+        assert_eq!(slack.sent.lock().len(), 1);
+    }
+
+    #[test]
+    fn resolve_distinguishes_unknown_missing_and_unavailable_channels() {
+        let router = router(vec![RecordingChannel::new("telegram", 0)], &["matrix"]);
+
+        assert!(router.resolve("Telegram").is_ok());
+        let unknown = router.resolve("pager").err().unwrap().to_string();
+        assert_eq!(unknown, "unsupported delivery channel: pager");
+        let missing = router.resolve("signal").err().unwrap().to_string();
+        assert_eq!(missing, "signal channel not configured");
+        let unavailable = router.resolve("matrix").err().unwrap().to_string();
+        assert!(unavailable.contains("matrix channel is configured but cannot send"));
+    }
+
+    #[test]
+    fn channel_config_present_covers_non_chat_channels() {
+        let mut config = Config::default();
+        assert_eq!(channel_config_present(&config, "email"), Some(false));
+        assert_eq!(channel_config_present(&config, "webhook"), None);
+        for (handle, _) in config.channels_config.channels_except_webhook() {
+            assert_eq!(channel_config_present(&config, handle.key()), Some(false));
+        }
+
+        config.channels_config.irc = Some(crate::config::schema::IrcConfig {
+            server: "irc.example.org".into(),
+            port: 6697,
+            nickname: "zc".into(),
+            username: None,
+            channels: vec!["#ops".into()],
+            allowed_users: vec![],
+            server_password: None,
+            nickserv_password: None,
+            sasl_password: None,
+            verify_tls: None,
+        });
+        assert_eq!(channel_config_present(&config, "IRC"), Some(true));
+    }
+}
@@ -73,6 +73,9 @@ impl crate::config::traits::ChannelConfig for EmailConfig {
     fn name() -> &'static str {
         "Email"
     }
+    fn key() -> &'static str {
+        "email"
+    }
     fn desc() -> &'static str {
         "Email over IMAP/SMTP"
     }
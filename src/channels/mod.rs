@@ -16,6 +16,7 @@
 
 pub mod clawdtalk;
 pub mod cli;
+pub mod delivery;
 pub mod dingtalk;
 pub mod discord;
 pub mod email_channel;
@@ -27,11 +28,14 @@ pub mod linq;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
+#[cfg(feature = "channel-mqtt")]
+pub mod mqtt;
 pub mod nextcloud_talk;
 pub mod nostr;
 pub mod qq;
 pub mod signal;
 pub mod slack;
+pub mod teams;
 pub mod telegram;
 pub mod traits;
 pub mod transcription;
@@ -55,11 +59,14 @@ pub use linq::LinqChannel;
 #[cfg(feature = "channel-matrix")]
 pub use matrix::MatrixChannel;
 pub use mattermost::MattermostChannel;
+#[cfg(feature = "channel-mqtt")]
+pub use mqtt::MqttChannel;
 pub use nextcloud_talk::NextcloudTalkChannel;
 pub use nostr::NostrChannel;
 pub use qq::QQChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
+pub use teams::TeamsChannel;
 pub use telegram::TelegramChannel;
 pub use traits::{Channel, SendMessage};
 pub use wati::WatiChannel;
@@ -67,11 +74,13 @@ pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
 pub use whatsapp_web::WhatsAppWebChannel;
 
+use crate::agent::budget::ContextBudget;
 use crate::agent::loop_::{
     build_shell_policy_instructions, build_tool_instructions, run_tool_call_loop, scrub_credentials,
 };
 use crate::approval::ApprovalManager;
 use crate::config::Config;
+use crate::gateway::sessions;
 use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
@@ -155,6 +164,9 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    ShowSessions,
+    ShowSession,
+    AttachSession(String),
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -212,7 +224,9 @@ struct ChannelRuntimeContext {
     model: Arc<String>,
     temperature: f64,
     auto_save_memory: bool,
+    memory_namespaces: Arc<crate::config::MemoryNamespacesConfig>,
     max_tool_iterations: usize,
+    agent_config: Arc<crate::config::AgentConfig>,
     min_relevance_score: f64,
     conversation_histories: ConversationHistoryMap,
     provider_cache: ProviderCacheMap,
@@ -498,6 +512,11 @@ fn supports_runtime_model_switch(channel_name: &str) -> bool {
     matches!(channel_name, "telegram" | "discord")
 }
 
+/// Channels whose chats can continue persisted gateway chat sessions.
+fn supports_shared_sessions(channel_name: &str) -> bool {
+    channel_name == "telegram"
+}
+
 fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
     let trimmed = content.trim();
     if !trimmed.starts_with('/') {
@@ -533,6 +552,15 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
                 Some(ChannelRuntimeCommand::SetModel(model))
             }
         }
+        "/sessions" if supports_shared_sessions(channel_name) => {
+            Some(ChannelRuntimeCommand::ShowSessions)
+        }
+        "/session" if supports_shared_sessions(channel_name) => match parts.next() {
+            Some(reference) => Some(ChannelRuntimeCommand::AttachSession(
+                reference.trim().to_string(),
+            )),
+            None => Some(ChannelRuntimeCommand::ShowSession),
+        },
         _ => None,
     }
 }
@@ -1099,8 +1127,18 @@ async fn handle_runtime_command_if_needed(
         }
         ChannelRuntimeCommand::NewSession => {
             clear_sender_history(ctx, &sender_key);
+            if supports_shared_sessions(&msg.channel) {
+                if let Err(err) = sessions::unbind_channel(&ctx.workspace_dir, &sender_key) {
+                    tracing::warn!("Failed to detach chat session for {sender_key}: {err}");
+                }
+            }
             "Conversation history cleared. Starting fresh.".to_string()
         }
+        ChannelRuntimeCommand::ShowSessions => build_sessions_response(ctx, msg, &sender_key),
+        ChannelRuntimeCommand::ShowSession => build_current_session_response(ctx, &sender_key),
+        ChannelRuntimeCommand::AttachSession(reference) => {
+            attach_shared_session(ctx, msg, &sender_key, &reference)
+        }
     };
 
     if let Err(err) = channel
@@ -1116,14 +1154,186 @@ async fn handle_runtime_command_if_needed(
     true
 }
 
+fn short_session_id(id: &str) -> &str {
+    id.get(..8).unwrap_or(id)
+}
+
+/// Find the session a `/session <ref>` command names: an exact id, or an id
+/// prefix that matches exactly one session.
+fn resolve_session_reference<'a>(
+    candidates: &'a [sessions::ChatSession],
+    reference: &str,
+) -> Option<&'a sessions::ChatSession> {
+    let reference = reference.trim().to_ascii_lowercase();
+    if reference.is_empty() {
+        return None;
+    }
+    if let Some(exact) = candidates.iter().find(|s| s.id == reference) {
+        return Some(exact);
+    }
+    let mut matches = candidates.iter().filter(|s| s.id.starts_with(&reference));
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => Some(only),
+        _ => None,
+    }
+}
+
+/// The session identity of a channel sender, as linked in `[memory.namespaces]`.
+fn shared_session_identity(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+) -> Option<String> {
+    sessions::channel_identity(&ctx.memory_namespaces, &msg.channel, &msg.sender)
+}
+
+fn build_sessions_response(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    sender_key: &str,
+) -> String {
+    let Some(identity) = shared_session_identity(ctx, msg) else {
+        return "No chat sessions are bound to this chat.".to_string();
+    };
+    let listed = match sessions::list_sessions(&ctx.workspace_dir, Some(&identity)) {
+        Ok(listed) => listed,
+        Err(err) => return format!("Failed to list chat sessions: {err}"),
+    };
+    if listed.is_empty() {
+        return format!(
+            "No chat sessions are bound to `{identity}`. Create one in the web chat with this identity."
+        );
+    }
+
+    let current = sessions::bound_session(&ctx.workspace_dir, sender_key)
+        .ok()
+        .flatten();
+    let mut response = format!("Chat sessions for `{identity}`:\n");
+    for session in &listed {
+        let marker = if current.as_deref() == Some(session.id.as_str()) {
+            " ← current"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            response,
+            "• `{}` {} ({} messages){marker}",
+            short_session_id(&session.id),
+            session.name,
+            session.message_count
+        );
+    }
+    response.push_str("Use `/session <id>` to continue one here.");
+    response
+}
+
+fn build_current_session_response(ctx: &ChannelRuntimeContext, sender_key: &str) -> String {
+    let bound = sessions::bound_session(&ctx.workspace_dir, sender_key).and_then(|bound| {
+        bound.map_or(Ok(None), |id| {
+            sessions::get_session(&ctx.workspace_dir, &id)
+        })
+    });
+    match bound {
+        Ok(Some(session)) => format!(
+            "This chat continues session `{}` — {} ({} messages). Send `/new` to detach.",
+            short_session_id(&session.id),
+            session.name,
+            session.message_count
+        ),
+        Ok(None) => "This chat is not attached to a saved session. Use `/sessions` to list yours."
+            .to_string(),
+        Err(err) => format!("Failed to look up chat session: {err}"),
+    }
+}
+
+fn attach_shared_session(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    sender_key: &str,
+    reference: &str,
+) -> String {
+    let Some(identity) = shared_session_identity(ctx, msg) else {
+        return "No chat sessions are bound to this chat.".to_string();
+    };
+    let listed = match sessions::list_sessions(&ctx.workspace_dir, Some(&identity)) {
+        Ok(listed) => listed,
+        Err(err) => return format!("Failed to list chat sessions: {err}"),
+    };
+    let Some(session) = resolve_session_reference(&listed, reference) else {
+        return format!(
+            "No chat session `{reference}` is bound to `{identity}`. Use `/sessions` to list yours."
+        );
+    };
+    if let Err(err) = sessions::bind_channel(&ctx.workspace_dir, sender_key, &session.id) {
+        return format!("Failed to attach chat session: {err}");
+    }
+    clear_sender_history(ctx, sender_key);
+    sync_shared_session_history(ctx, &msg.channel, sender_key);
+
+    let mut response = format!(
+        "Continuing session `{}` — {} ({} messages).",
+        short_session_id(&session.id),
+        session.name,
+        session.message_count
+    );
+    let last_reply = sessions::load_messages(&ctx.workspace_dir, &session.id)
+        .ok()
+        .and_then(|messages| messages.into_iter().rev().find(|m| m.role == "assistant"));
+    if let Some(reply) = last_reply {
+        let _ = write!(
+            response,
+            "\nLast reply: {}",
+            truncate_with_ellipsis(&reply.content, 300)
+        );
+    }
+    response
+}
+
+/// For a conversation attached to a persisted chat session, replace the
+/// cached history with the session's stored turns so turns added elsewhere
+/// (e.g. the web chat) are part of the context. Returns the session id.
+fn sync_shared_session_history(
+    ctx: &ChannelRuntimeContext,
+    channel_name: &str,
+    sender_key: &str,
+) -> Option<String> {
+    if !supports_shared_sessions(channel_name) {
+        return None;
+    }
+    let loaded = sessions::bound_session(&ctx.workspace_dir, sender_key).and_then(|bound| {
+        bound
+            .map(|id| Ok((sessions::context_turns(&ctx.workspace_dir, &id)?, id)))
+            .transpose()
+    });
+    match loaded {
+        Ok(Some((turns, session_id))) => {
+            let mut histories = ctx
+                .conversation_histories
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if turns.is_empty() {
+                histories.remove(sender_key);
+            } else {
+                histories.insert(sender_key.to_string(), turns);
+            }
+            Some(session_id)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            tracing::warn!("Failed to load chat session for {sender_key}: {err}");
+            None
+        }
+    }
+}
+
 async fn build_memory_context(
     mem: &dyn Memory,
+    namespace: Option<&str>,
     user_msg: &str,
     min_relevance_score: f64,
 ) -> String {
     let mut context = String::new();
 
-    if let Ok(entries) = mem.recall(user_msg, 5, None).await {
+    if let Ok(entries) = mem.recall_in(namespace, user_msg, 5, None).await {
         let mut included = 0usize;
         let mut used_chars = 0usize;
 
@@ -1459,7 +1669,7 @@ fn spawn_supervised_listener_with_health_interval(
         let max_backoff = max_backoff_secs.max(backoff);
 
         loop {
-            crate::health::mark_component_ok(&component);
+            crate::health::mark_component_ok(&component).await;
             let mut health = tokio::time::interval(health_interval);
             health.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
             let result = {
@@ -1469,7 +1679,7 @@ fn spawn_supervised_listener_with_health_interval(
                 loop {
                     tokio::select! {
                         _ = health.tick() => {
-                            crate::health::mark_component_ok(&component);
+                            crate::health::mark_component_ok(&component).await;
                         }
                         result = &mut listen_future => break result,
                     }
@@ -1483,17 +1693,18 @@ fn spawn_supervised_listener_with_health_interval(
             match result {
                 Ok(()) => {
                     tracing::warn!("Channel {} exited unexpectedly; restarting", ch.name());
-                    crate::health::mark_component_error(&component, "listener exited unexpectedly");
+                    crate::health::mark_component_error(&component, "listener exited unexpectedly")
+                        .await;
                     // Clean exit — reset backoff since the listener ran successfully
                     backoff = initial_backoff_secs.max(1);
                 }
                 Err(e) => {
                     tracing::error!("Channel {} error: {e}; restarting", ch.name());
-                    crate::health::mark_component_error(&component, e.to_string());
+                    crate::health::mark_component_error(&component, e.to_string()).await;
                 }
             }
 
-            crate::health::bump_component_restart(&component);
+            crate::health::bump_component_restart(&component).await;
             tokio::time::sleep(Duration::from_secs(backoff)).await;
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
@@ -1589,6 +1800,7 @@ async fn process_channel_message(
     } else {
         msg
     };
+    crate::cron::triggers::dispatch_message_triggers(&msg);
 
     let target_channel = ctx.channels_by_name.get(&msg.channel).cloned();
     if let Err(err) = maybe_apply_runtime_config_update(ctx.as_ref()).await {
@@ -1622,15 +1834,29 @@ async fn process_channel_message(
             return;
         }
     };
+    // Memories are read and written in the sender's namespace (plus the
+    // shared scope for recall) so one person's facts never reach another.
+    let memory_namespace =
+        crate::memory::namespace::resolve(&ctx.memory_namespaces, &msg.channel, &msg.sender);
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
         let autosave_key = conversation_memory_key(&msg);
+        let autosave_metadata = crate::memory::MemoryMetadata {
+            source: Some(crate::memory::MemorySource {
+                channel: Some(msg.channel.clone()),
+                sender: Some(msg.sender.clone()),
+                tool: None,
+            }),
+            ..crate::memory::MemoryMetadata::default()
+        };
         let _ = ctx
             .memory
-            .store(
+            .store_in(
+                memory_namespace.as_deref(),
                 &autosave_key,
                 &msg.content,
                 crate::memory::MemoryCategory::Conversation,
                 None,
+                &autosave_metadata,
             )
             .await;
     }
@@ -1638,6 +1864,8 @@ async fn process_channel_message(
     println!("  ⏳ Processing message...");
     let started_at = Instant::now();
 
+    let shared_session_id = sync_shared_session_history(ctx.as_ref(), &msg.channel, &history_key);
+
     let had_prior_history = ctx
         .conversation_histories
         .lock()
@@ -1661,8 +1889,13 @@ async fn process_channel_message(
     // Only enrich with memory context when there is no prior conversation
     // history. Follow-up turns already include context from previous messages.
     if !had_prior_history {
-        let memory_context =
-            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
+        let memory_context = build_memory_context(
+            ctx.memory.as_ref(),
+            memory_namespace.as_deref(),
+            &msg.content,
+            ctx.min_relevance_score,
+        )
+        .await;
         if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
                 last_turn.content = format!("{memory_context}{}", msg.content);
@@ -1772,31 +2005,36 @@ async fn process_channel_message(
 
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
+    let context_budget = ContextBudget::from_config(&ctx.agent_config, route.model.as_str());
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                Some(ctx.approval_manager.as_ref()),
-                msg.channel.as_str(),
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                Some(cancellation_token.clone()),
-                delta_tx,
-                ctx.hooks.as_deref(),
-                if msg.channel == "cli" {
-                    &[]
-                } else {
-                    ctx.non_cli_excluded_tools.as_ref()
-                },
+            crate::memory::namespace::with_namespace(
+                memory_namespace.clone(),
+                run_tool_call_loop(
+                    active_provider.as_ref(),
+                    &mut history,
+                    ctx.tools_registry.as_ref(),
+                    ctx.observer.as_ref(),
+                    route.provider.as_str(),
+                    route.model.as_str(),
+                    runtime_defaults.temperature,
+                    true,
+                    Some(ctx.approval_manager.as_ref()),
+                    msg.channel.as_str(),
+                    &ctx.multimodal,
+                    ctx.max_tool_iterations,
+                    Some(cancellation_token.clone()),
+                    delta_tx,
+                    ctx.hooks.as_deref(),
+                    if msg.channel == "cli" {
+                        &[]
+                    } else {
+                        ctx.non_cli_excluded_tools.as_ref()
+                    },
+                    Some(&context_budget),
+                ),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
@@ -1943,6 +2181,18 @@ async fn process_channel_message(
                 &history_key,
                 ChatMessage::assistant(&history_response),
             );
+            if let Some(session_id) = shared_session_id.as_deref() {
+                if let Err(err) = sessions::append_messages(
+                    &ctx.workspace_dir,
+                    session_id,
+                    &[
+                        ChatMessage::user(&msg.content),
+                        ChatMessage::assistant(&history_response),
+                    ],
+                ) {
+                    tracing::warn!("Failed to persist chat session {session_id}: {err}");
+                }
+            }
             println!(
                 "  🤖 Reply ({}ms): {}",
                 started_at.elapsed().as_millis(),
@@ -2679,6 +2929,11 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
                     "  ℹ️ Lark/Feishu channel support is disabled in this build (enable `channel-lark`)."
                 );
             }
+            if !cfg!(feature = "channel-mqtt") {
+                println!(
+                    "  ℹ️ MQTT channel support is disabled in this build (enable `channel-mqtt`)."
+                );
+            }
             println!("\nTo start channels: zeroclaw channel start");
             println!("To check health:    zeroclaw channel doctor");
             println!("To configure:      zeroclaw onboard");
@@ -2920,6 +3175,13 @@ fn collect_configured_channels(
         });
     }
 
+    if let Some(ref teams) = config.channels_config.teams {
+        channels.push(ConfiguredChannel {
+            display_name: "Microsoft Teams",
+            channel: Arc::new(TeamsChannel::new(teams.clone())),
+        });
+    }
+
     if let Some(ref email_cfg) = config.channels_config.email {
         channels.push(ConfiguredChannel {
             display_name: "Email",
@@ -3019,6 +3281,24 @@ fn collect_configured_channels(
         });
     }
 
+    #[cfg(feature = "channel-mqtt")]
+    if let Some(ref mq) = config.channels_config.mqtt {
+        if mq.request_topic.is_some() {
+            channels.push(ConfiguredChannel {
+                display_name: "MQTT",
+                channel: Arc::new(MqttChannel::new(mq.clone())),
+            });
+        }
+    }
+
+    #[cfg(not(feature = "channel-mqtt"))]
+    if config.channels_config.mqtt.is_some() {
+        tracing::warn!(
+            "MQTT channel is configured but this build was compiled without `channel-mqtt`; skipping MQTT {}.",
+            matrix_skip_context
+        );
+    }
+
     channels
 }
 
@@ -3112,6 +3392,8 @@ pub async fn start_channels(config: Config) -> Result<()> {
         tracing::warn!("Provider warmup failed (non-fatal): {e}");
     }
 
+    crate::cron::triggers::install_message_triggers(&config);
+
     let initial_stamp = config_file_stamp(&config.config_path).await;
     {
         let mut store = runtime_config_store()
@@ -3218,6 +3500,37 @@ pub async fn start_channels(config: Config) -> Result<()> {
         "pushover",
         "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
     ));
+    if cfg!(feature = "channel-mqtt")
+        && config.channels_config.mqtt.is_some()
+        && !config.autonomy.allowed_mqtt_topics.is_empty()
+    {
+        tool_descs.push((
+            "mqtt_publish",
+            "Publish a message to an allowlisted MQTT topic to command devices. Use when: a device must be actuated. Don't use when: the topic is not in autonomy.allowed_mqtt_topics.",
+        ));
+    }
+    if cfg!(feature = "sop") && config.sop.enabled {
+        tool_descs.push((
+            "sop_list",
+            "List loaded standard operating procedures (SOPs) with their triggers and modes.",
+        ));
+        tool_descs.push((
+            "sop_execute",
+            "Start an SOP run by name. Use when: the user asks to run a procedure. Returns the first step to carry out or an approval request.",
+        ));
+        tool_descs.push((
+            "sop_advance",
+            "Report the result of the current SOP step and get the next one. Use after finishing each step of an SOP run.",
+        ));
+        tool_descs.push((
+            "sop_approve",
+            "Approve an SOP step that is waiting for operator approval.",
+        ));
+        tool_descs.push((
+            "sop_status",
+            "Show active and recent SOP runs and their metrics.",
+        ));
+    }
     if !config.agents.is_empty() {
         tool_descs.push((
             "delegate",
@@ -3304,7 +3617,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     println!("  Listening for messages... (Ctrl+C to stop)");
     println!();
 
-    crate::health::mark_component_ok("channels");
+    crate::health::mark_component_ok("channels").await;
 
     let initial_backoff_secs = config
         .reliability
@@ -3361,7 +3674,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         model: Arc::new(model.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
+        memory_namespaces: Arc::new(config.memory.namespaces.clone()),
         max_tool_iterations: config.agent.max_tool_iterations,
+        agent_config: Arc::new(config.agent.clone()),
         min_relevance_score: config.memory.min_relevance_score,
         conversation_histories: Arc::new(Mutex::new(HashMap::new())),
         provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -3375,11 +3690,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
         hooks: if config.hooks.enabled {
-            let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
-                runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
-            }
-            Some(Arc::new(runner))
+            Some(Arc::new(crate::hooks::HookRunner::from_config(&config)))
         } else {
             None
         },
@@ -3488,6 +3799,44 @@ mod tests {
         assert!(!should_skip_memory_context_entry("telegram_123_45", "hi"));
     }
 
+    #[test]
+    fn parse_runtime_command_session_commands_are_telegram_only() {
+        assert!(matches!(
+            parse_runtime_command("telegram", "/sessions"),
+            Some(ChannelRuntimeCommand::ShowSessions)
+        ));
+        assert!(matches!(
+            parse_runtime_command("telegram", "/session"),
+            Some(ChannelRuntimeCommand::ShowSession)
+        ));
+        assert!(matches!(
+            parse_runtime_command("telegram", "/session@zeroclaw_bot 1a2b3c4d"),
+            Some(ChannelRuntimeCommand::AttachSession(reference)) if reference == "1a2b3c4d"
+        ));
+        assert!(parse_runtime_command("discord", "/sessions").is_none());
+    }
+
+    #[test]
+    fn resolve_session_reference_accepts_exact_ids_and_unique_prefixes() {
+        let session = |id: &str| sessions::ChatSession {
+            id: id.to_string(),
+            name: "chat".into(),
+            identity: Some("alice".into()),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            message_count: 0,
+        };
+        let candidates = vec![session("abc123"), session("abd456"), session("ab")];
+
+        let pick = |reference| resolve_session_reference(&candidates, reference).map(|s| &s.id);
+        assert_eq!(pick("abc"), Some(&candidates[0].id));
+        assert_eq!(pick("ABD4"), Some(&candidates[1].id));
+        assert_eq!(pick("ab"), Some(&candidates[2].id));
+        assert_eq!(pick("a"), None);
+        assert_eq!(pick("zzz"), None);
+        assert_eq!(pick(" "), None);
+    }
+
     #[test]
     fn normalize_cached_channel_turns_merges_consecutive_user_turns() {
         let turns = vec![
@@ -3585,7 +3934,9 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -3639,7 +3990,9 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -3696,7 +4049,9 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4176,7 +4531,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4240,7 +4597,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4318,7 +4677,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4382,7 +4743,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4455,7 +4818,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -4549,7 +4914,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -4625,7 +4992,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -4713,7 +5082,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("startup-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
@@ -4792,7 +5163,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 12,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4857,7 +5230,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 3,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -4984,9 +5359,21 @@ BTC is currently around $65,000 based on latest tool output."#
                 timestamp: "2026-02-20T00:00:00Z".to_string(),
                 session_id: None,
                 score: Some(0.9),
+                metadata: crate::memory::MemoryMetadata::default(),
+                namespace: None,
             }])
         }
 
+        async fn recall_in(
+            &self,
+            _namespace: Option<&str>,
+            query: &str,
+            limit: usize,
+            session_id: Option<&str>,
+        ) -> anyhow::Result<Vec<crate::memory::MemoryEntry>> {
+            self.recall(query, limit, session_id).await
+        }
+
         async fn get(&self, _key: &str) -> anyhow::Result<Option<crate::memory::MemoryEntry>> {
             Ok(None)
         }
@@ -5033,7 +5420,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -5118,7 +5507,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -5215,7 +5606,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -5294,7 +5687,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -5358,7 +5753,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 10,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -5853,11 +6250,39 @@ BTC is currently around $65,000 based on latest tool output."#
             .await
             .unwrap();
 
-        let context = build_memory_context(&mem, "age", 0.0).await;
+        let context = build_memory_context(&mem, None, "age", 0.0).await;
         assert!(context.contains("[Memory context]"));
         assert!(context.contains("Age is 45"));
     }
 
+    #[tokio::test]
+    async fn build_memory_context_isolates_namespaces() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let metadata = crate::memory::MemoryMetadata::default();
+        mem.store_in(
+            Some("telegram:1001"),
+            "age_fact",
+            "Age is 45",
+            MemoryCategory::Conversation,
+            None,
+            &metadata,
+        )
+        .await
+        .unwrap();
+        mem.store("team_fact", "Team age policy is 18+", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let own = build_memory_context(&mem, Some("telegram:1001"), "age", 0.0).await;
+        assert!(own.contains("Age is 45"));
+        assert!(own.contains("Team age policy"));
+
+        let other = build_memory_context(&mem, Some("telegram:2002"), "age", 0.0).await;
+        assert!(!other.contains("Age is 45"));
+        assert!(other.contains("Team age policy"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_restores_per_sender_history_on_follow_ups() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -5879,7 +6304,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -5969,7 +6396,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -6059,7 +6488,9 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -6499,7 +6930,7 @@ BTC is currently around $65,000 based on latest tool output."#;
         handle.abort();
         let _ = handle.await;
 
-        let snapshot = crate::health::snapshot_json();
+        let snapshot = crate::health::snapshot_json().await;
         let component = &snapshot["components"]["channel:test-supervised-fail"];
         assert_eq!(component["status"], "error");
         assert!(component["restart_count"].as_u64().unwrap_or(0) >= 1);
@@ -6530,7 +6961,7 @@ BTC is currently around $65,000 based on latest tool output."#;
         );
 
         tokio::time::sleep(Duration::from_millis(35)).await;
-        let first_last_ok = crate::health::snapshot_json()["components"][&component_name]
+        let first_last_ok = crate::health::snapshot_json().await["components"][&component_name]
             ["last_ok"]
             .as_str()
             .unwrap_or("")
@@ -6538,7 +6969,7 @@ BTC is currently around $65,000 based on latest tool output."#;
         assert!(!first_last_ok.is_empty());
 
         tokio::time::sleep(Duration::from_millis(70)).await;
-        let second_last_ok = crate::health::snapshot_json()["components"][&component_name]
+        let second_last_ok = crate::health::snapshot_json().await["components"][&component_name]
             ["last_ok"]
             .as_str()
             .unwrap_or("")
@@ -6641,7 +7072,9 @@ BTC is currently around $65,000 based on latest tool output."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -6712,7 +7145,9 @@ BTC is currently around $65,000 based on latest tool output."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_namespaces: Arc::new(crate::config::MemoryNamespacesConfig::default()),
             max_tool_iterations: 5,
+            agent_config: Arc::new(crate::config::AgentConfig::default()),
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
             provider_cache: Arc::new(Mutex::new(HashMap::new())),
@@ -1,93 +1,272 @@
-//! MQTT → SOP event fan-in listener.
+//! MQTT chat channel.
 //!
-//! This is NOT a `Channel` trait implementor — it routes MQTT messages
-//! to the SOP engine via `dispatch_sop_event`, not to the chat loop.
-
-use std::sync::{Arc, Mutex};
+//! Peers publish requests on a topic rendered from `request_topic`
+//! (e.g. `zeroclaw/chat/{sender}/request`); the `{sender}` level identifies
+//! the peer and replies go to `reply_topic` rendered for that sender.
+//! The SOP fan-in listener for `mqtt.topics` lives in `crate::sop::mqtt`.
 
+use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::MqttConfig;
 use anyhow::Result;
-use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, Transport};
-use tracing::{info, warn};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use rumqttc::{AsyncClient, Event, MqttOptions, Outgoing, Packet, QoS, Transport};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
 
-use crate::config::MqttConfig;
-use crate::sop::audit::SopAuditLogger;
-use crate::sop::dispatch::{dispatch_sop_event, process_headless_results};
-use crate::sop::engine::{now_iso8601, SopEngine};
-use crate::sop::types::{SopEvent, SopTriggerSource};
+const SENDER_PLACEHOLDER: &str = "{sender}";
+const PUBLISH_TIMEOUT_SECS: u64 = 10;
+const RECONNECT_BACKOFF_SECS: u64 = 5;
 
-/// Run the MQTT SOP listener loop.
-///
-/// Subscribes to configured topics and dispatches incoming publishes
-/// to the SOP engine. Blocks until disconnected or cancelled.
-pub async fn run_mqtt_sop_listener(
-    config: &MqttConfig,
-    engine: Arc<Mutex<SopEngine>>,
-    audit: Arc<SopAuditLogger>,
-) -> Result<()> {
-    config.validate()?;
+/// MQTT channel — converses over request/reply topic pairs on one broker.
+pub struct MqttChannel {
+    config: MqttConfig,
+    /// Client of the running listener; publishes reuse its connection.
+    client: Arc<Mutex<Option<AsyncClient>>>,
+}
 
-    let mut mqtt_options = MqttOptions::new(
-        &config.client_id,
+impl MqttChannel {
+    pub fn new(config: MqttConfig) -> Self {
+        Self {
+            config,
+            client: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn is_sender_allowed(&self, sender: &str) -> bool {
+        self.config
+            .allowed_senders
+            .iter()
+            .any(|s| s == "*" || s == sender)
+    }
+
+    /// Subscription filter for the request topic (`{sender}` → `+`).
+    fn request_filter(&self) -> Option<String> {
+        self.config
+            .request_topic
+            .as_deref()
+            .map(|template| template.replace(SENDER_PLACEHOLDER, "+"))
+    }
+
+    /// Extract the sender from a topic matching the request template.
+    fn sender_from_topic(&self, topic: &str) -> Option<String> {
+        let template = self.config.request_topic.as_deref()?;
+        let template_levels: Vec<&str> = template.split('/').collect();
+        let topic_levels: Vec<&str> = topic.split('/').collect();
+        if template_levels.len() != topic_levels.len() {
+            return None;
+        }
+
+        let mut sender = None;
+        for (expected, actual) in template_levels.iter().zip(&topic_levels) {
+            match *expected {
+                SENDER_PLACEHOLDER => sender = Some(*actual),
+                "+" => {}
+                literal if literal == *actual => {}
+                _ => return None,
+            }
+        }
+        sender.filter(|s| !s.is_empty()).map(ToString::to_string)
+    }
+
+    fn reply_topic_for(&self, sender: &str) -> Option<String> {
+        self.config
+            .reply_topic
+            .as_deref()
+            .map(|template| template.replace(SENDER_PLACEHOLDER, sender))
+    }
+
+    /// Parse an incoming publish into `(sender, content)`.
+    ///
+    /// Payloads may be plain UTF-8 text or a JSON object carrying the text
+    /// in `text`, `message` or `content`.
+    fn parse_request(&self, topic: &str, payload: &[u8]) -> Option<(String, String)> {
+        let sender = self.sender_from_topic(topic)?;
+        let raw = String::from_utf8_lossy(payload);
+        let content = match serde_json::from_str::<serde_json::Value>(&raw) {
+            Ok(serde_json::Value::Object(obj)) => ["text", "message", "content"]
+                .iter()
+                .find_map(|key| obj.get(*key).and_then(|v| v.as_str()))
+                .map(ToString::to_string)?,
+            _ => raw.into_owned(),
+        };
+        let content = content.trim();
+        if content.is_empty() {
+            return None;
+        }
+        Some((sender, content.to_string()))
+    }
+}
+
+/// Build connection options for `config` under the given client identifier.
+pub(crate) fn mqtt_options(config: &MqttConfig, client_id: &str) -> MqttOptions {
+    let mut options = MqttOptions::new(
+        client_id,
         broker_host(&config.broker_url),
         broker_port(&config.broker_url),
     );
-    mqtt_options.set_keep_alive(std::time::Duration::from_secs(config.keep_alive_secs));
-
+    options.set_keep_alive(Duration::from_secs(config.keep_alive_secs));
     if let (Some(ref user), Some(ref pass)) = (&config.username, &config.password) {
-        mqtt_options.set_credentials(user, pass);
+        options.set_credentials(user, pass);
     }
-
-    // Configure TLS transport when mqtts:// scheme is used
     if config.use_tls {
-        mqtt_options.set_transport(Transport::tls_with_default_config());
-        info!("MQTT SOP listener: TLS transport enabled");
+        options.set_transport(Transport::tls_with_default_config());
     }
+    options
+}
 
-    let (client, mut eventloop) = AsyncClient::new(mqtt_options, 64);
-
-    let qos = match config.qos {
+/// Map a configured QoS level (0–2) onto the client enum.
+pub(crate) fn qos_from_level(level: u8) -> QoS {
+    match level {
         0 => QoS::AtMostOnce,
         1 => QoS::AtLeastOnce,
         _ => QoS::ExactlyOnce,
-    };
+    }
+}
+
+/// Publish a single message over a short-lived connection.
+///
+/// Waits for the broker acknowledgement matching `qos` (none for QoS 0).
+/// A suffixed client id avoids kicking a long-running listener off the broker.
+pub(crate) async fn publish_once(
+    config: &MqttConfig,
+    topic: &str,
+    payload: Vec<u8>,
+    qos: QoS,
+    retain: bool,
+) -> Result<()> {
+    let client_id = format!(
+        "{}-pub-{}",
+        config.client_id,
+        &Uuid::new_v4().simple().to_string()[..8]
+    );
+    let (client, mut eventloop) = AsyncClient::new(mqtt_options(config, &client_id), 10);
+    client.publish(topic, qos, retain, payload).await?;
+
+    let delivered = tokio::time::timeout(Duration::from_secs(PUBLISH_TIMEOUT_SECS), async {
+        loop {
+            match eventloop.poll().await? {
+                Event::Outgoing(Outgoing::Publish(_)) if qos == QoS::AtMostOnce => break,
+                Event::Incoming(Packet::PubAck(_)) if qos == QoS::AtLeastOnce => break,
+                Event::Incoming(Packet::PubComp(_)) if qos == QoS::ExactlyOnce => break,
+                _ => {}
+            }
+        }
+        Ok::<(), rumqttc::ConnectionError>(())
+    })
+    .await;
+
+    let _ = client.disconnect().await;
+    let _ = tokio::time::timeout(Duration::from_secs(1), eventloop.poll()).await;
+
+    match delivered {
+        Ok(result) => result.map_err(|e| anyhow::anyhow!("MQTT publish to '{topic}' failed: {e}")),
+        Err(_) => {
+            anyhow::bail!("MQTT publish to '{topic}' timed out after {PUBLISH_TIMEOUT_SECS}s")
+        }
+    }
+}
 
-    // Subscribe to all configured topics
-    for topic in &config.topics {
-        client.subscribe(topic, qos).await?;
-        info!("MQTT SOP listener: subscribed to '{topic}'");
+#[async_trait]
+impl Channel for MqttChannel {
+    fn name(&self) -> &str {
+        "mqtt"
     }
 
-    crate::health::mark_component_ok("mqtt");
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let topic = self
+            .reply_topic_for(&message.recipient)
+            .ok_or_else(|| anyhow::anyhow!("MQTT reply_topic is not configured"))?;
+        let qos = qos_from_level(self.config.qos);
+        let payload = message.content.as_bytes().to_vec();
 
-    loop {
-        match eventloop.poll().await {
-            Ok(Event::Incoming(Packet::Publish(msg))) => {
-                let topic = msg.topic.clone();
-                let payload = String::from_utf8_lossy(&msg.payload).to_string();
+        let listener_client = self.client.lock().clone();
+        match listener_client {
+            Some(client) => client
+                .publish(topic, qos, self.config.retain, payload)
+                .await
+                .map_err(|e| anyhow::anyhow!("MQTT publish failed: {e}")),
+            None => publish_once(&self.config, &topic, payload, qos, self.config.retain).await,
+        }
+    }
 
-                let event = SopEvent {
-                    source: SopTriggerSource::Mqtt,
-                    topic: Some(topic),
-                    payload: Some(payload),
-                    timestamp: now_iso8601(),
-                };
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> Result<()> {
+        let filter = self
+            .request_filter()
+            .ok_or_else(|| anyhow::anyhow!("MQTT request_topic is not configured"))?;
+        let qos = qos_from_level(self.config.qos);
+        let (client, mut eventloop) =
+            AsyncClient::new(mqtt_options(&self.config, &self.config.client_id), 64);
+        *self.client.lock() = Some(client.clone());
 
-                let results = dispatch_sop_event(&engine, &audit, event).await;
-                process_headless_results(&results).await;
-            }
-            Ok(Event::Incoming(Packet::ConnAck(_))) => {
-                crate::health::mark_component_ok("mqtt");
-                info!("MQTT SOP listener: connected to broker");
-            }
-            Ok(_) => {
-                // Other events (PingResp, SubAck, etc.) — ignore
-            }
-            Err(e) => {
-                crate::health::mark_component_error("mqtt", e.to_string());
-                warn!("MQTT SOP listener: connection error: {e}");
-                // rumqttc handles auto-reconnect; loop continues
+        tracing::info!("MQTT channel listening on '{filter}'...");
+
+        loop {
+            match eventloop.poll().await {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    // Clean sessions drop subscriptions, so re-subscribe on every connect.
+                    client.subscribe(filter.clone(), qos).await?;
+                    crate::health::mark_component_ok("mqtt").await;
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    let Some((sender, content)) =
+                        self.parse_request(&publish.topic, &publish.payload)
+                    else {
+                        tracing::debug!("MQTT: ignoring publish on '{}'", publish.topic);
+                        continue;
+                    };
+                    if !self.is_sender_allowed(&sender) {
+                        tracing::warn!("MQTT: ignoring message from unauthorized sender: {sender}");
+                        continue;
+                    }
+
+                    let msg = ChannelMessage {
+                        id: Uuid::new_v4().to_string(),
+                        sender: sender.clone(),
+                        reply_target: sender,
+                        content,
+                        channel: "mqtt".to_string(),
+                        timestamp: std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap_or_default()
+                            .as_secs(),
+                        thread_ts: None,
+                    };
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    crate::health::mark_component_error("mqtt", e.to_string()).await;
+                    tracing::warn!("MQTT connection error: {e}; reconnecting...");
+                    tokio::time::sleep(Duration::from_secs(RECONNECT_BACKOFF_SECS)).await;
+                }
             }
         }
+
+        self.client.lock().take();
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        let client_id = format!("{}-health", self.config.client_id);
+        let (client, mut eventloop) = AsyncClient::new(mqtt_options(&self.config, &client_id), 4);
+        let connected = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                match eventloop.poll().await {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => return true,
+                    Ok(_) => {}
+                    Err(_) => return false,
+                }
+            }
+        })
+        .await
+        .unwrap_or(false);
+        let _ = client.disconnect().await;
+        connected
     }
 }
 
@@ -134,6 +313,10 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("qos must be 0, 1, or 2"));
@@ -150,6 +333,10 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("mqtt://"));
@@ -166,6 +353,10 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("at least one topic"));
@@ -182,6 +373,10 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("client_id must not be empty"));
@@ -198,6 +393,10 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         assert!(config.validate().is_ok());
     }
@@ -213,6 +412,10 @@ mod tests {
             password: None,
             use_tls: true,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("use_tls is true"));
@@ -229,6 +432,10 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("mqtts://"));
@@ -245,6 +452,10 @@ mod tests {
             password: None,
             use_tls: true,
             keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
         };
         assert!(config.validate().is_ok());
     }
@@ -273,4 +484,124 @@ mod tests {
     fn broker_port_defaults_8883_for_mqtts() {
         assert_eq!(broker_port("mqtts://secure.example.com"), 8883);
     }
+
+    fn chat_channel(allowed: &[&str]) -> MqttChannel {
+        MqttChannel::new(MqttConfig {
+            broker_url: "mqtt://localhost:1883".into(),
+            client_id: "zeroclaw".into(),
+            topics: vec![],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            request_topic: Some("zeroclaw/chat/{sender}/request".into()),
+            reply_topic: Some("zeroclaw/chat/{sender}/reply".into()),
+            allowed_senders: allowed.iter().map(|s| (*s).to_string()).collect(),
+            retain: false,
+        })
+    }
+
+    #[test]
+    fn mqtt_chat_config_requires_sender_placeholder() {
+        let mut config = chat_channel(&["*"]).config;
+        config.request_topic = Some("zeroclaw/chat/request".into());
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("{sender}"));
+    }
+
+    #[test]
+    fn mqtt_chat_config_requires_reply_topic() {
+        let mut config = chat_channel(&["*"]).config;
+        config.reply_topic = None;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("reply_topic is required"));
+    }
+
+    #[test]
+    fn mqtt_chat_config_accepts_chat_only() {
+        assert!(chat_channel(&["*"]).config.validate().is_ok());
+    }
+
+    #[test]
+    fn mqtt_channel_name() {
+        assert_eq!(chat_channel(&[]).name(), "mqtt");
+    }
+
+    #[test]
+    fn request_filter_replaces_sender_with_wildcard() {
+        assert_eq!(
+            chat_channel(&[]).request_filter().as_deref(),
+            Some("zeroclaw/chat/+/request")
+        );
+    }
+
+    #[test]
+    fn sender_extracted_from_request_topic() {
+        let ch = chat_channel(&[]);
+        assert_eq!(
+            ch.sender_from_topic("zeroclaw/chat/thermostat/request")
+                .as_deref(),
+            Some("thermostat")
+        );
+        assert!(ch
+            .sender_from_topic("zeroclaw/chat/thermostat/reply")
+            .is_none());
+        assert!(ch.sender_from_topic("zeroclaw/chat/request").is_none());
+    }
+
+    #[test]
+    fn reply_topic_rendered_for_sender() {
+        assert_eq!(
+            chat_channel(&[]).reply_topic_for("panel").as_deref(),
+            Some("zeroclaw/chat/panel/reply")
+        );
+    }
+
+    #[test]
+    fn parse_request_accepts_plain_text() {
+        let (sender, content) = chat_channel(&[])
+            .parse_request("zeroclaw/chat/panel/request", b"  turn on the pump ")
+            .unwrap();
+        assert_eq!(sender, "panel");
+        assert_eq!(content, "turn on the pump");
+    }
+
+    #[test]
+    fn parse_request_accepts_json_text_field() {
+        let (_, content) = chat_channel(&[])
+            .parse_request(
+                "zeroclaw/chat/panel/request",
+                br#"{"text":"status?","id":7}"#,
+            )
+            .unwrap();
+        assert_eq!(content, "status?");
+    }
+
+    #[test]
+    fn parse_request_ignores_empty_payload() {
+        assert!(chat_channel(&[])
+            .parse_request("zeroclaw/chat/panel/request", b"   ")
+            .is_none());
+    }
+
+    #[test]
+    fn sender_allowlist_empty_denies_all() {
+        assert!(!chat_channel(&[]).is_sender_allowed("panel"));
+    }
+
+    #[test]
+    fn sender_allowlist_wildcard_and_exact() {
+        assert!(chat_channel(&["*"]).is_sender_allowed("anyone"));
+        let ch = chat_channel(&["panel"]);
+        assert!(ch.is_sender_allowed("panel"));
+        assert!(!ch.is_sender_allowed("intruder"));
+    }
+
+    #[test]
+    fn qos_from_level_maps_levels() {
+        assert_eq!(qos_from_level(0), QoS::AtMostOnce);
+        assert_eq!(qos_from_level(1), QoS::AtLeastOnce);
+        assert_eq!(qos_from_level(2), QoS::ExactlyOnce);
+    }
 }
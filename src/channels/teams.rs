@@ -0,0 +1,1139 @@
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::approval::{ApprovalRequest, ApprovalResponse, ChannelApprovalPrompter};
+use crate::config::TeamsConfig;
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+/// OAuth scope for calls to the Bot Connector API.
+const BOT_FRAMEWORK_SCOPE: &str = "https://api.botframework.com/.default";
+/// Signing keys are refreshed daily, as recommended by the Bot Framework docs.
+const SIGNING_KEYS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Minimum gap between key refreshes triggered by an unknown `kid`.
+const SIGNING_KEYS_MIN_REFRESH: Duration = Duration::from_secs(5 * 60);
+/// Allowed clock skew for `exp`/`nbf`.
+const CLOCK_SKEW_SECS: i64 = 5 * 60;
+/// Teams hides the typing indicator after about 3 seconds.
+const TYPING_REFRESH: Duration = Duration::from_secs(3);
+/// Card `data` key that marks approval button submissions.
+const APPROVAL_KEY: &str = "zeroclaw_approval";
+/// Characters of tool arguments shown on an approval card.
+const APPROVAL_ARGS_MAX_CHARS: usize = 1000;
+
+/// Microsoft Teams channel via the Bot Framework, in webhook mode.
+///
+/// Incoming activities are received by the gateway endpoint `/teams`, which
+/// authenticates them with [`TeamsChannel::verify_request`]. Replies, typing
+/// indicators and approval cards go through the Bot Connector API.
+pub struct TeamsChannel {
+    config: TeamsConfig,
+    client: reqwest::Client,
+    signing_keys: tokio::sync::Mutex<Option<SigningKeys>>,
+    access_token: tokio::sync::Mutex<Option<(String, Instant)>>,
+    /// Connector service URL per conversation, learned from authenticated activities.
+    service_urls: Mutex<HashMap<String, String>>,
+    typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    pending_approvals: Mutex<HashMap<String, PendingApproval>>,
+}
+
+struct SigningKeys {
+    issuer: String,
+    keys: Vec<Value>,
+    fetched_at: Instant,
+}
+
+impl SigningKeys {
+    fn find(&self, kid: &str) -> Option<&Value> {
+        self.keys
+            .iter()
+            .find(|key| key.get("kid").and_then(Value::as_str) == Some(kid))
+    }
+}
+
+struct PendingApproval {
+    conversation_id: String,
+    decision: oneshot::Sender<ApprovalResponse>,
+}
+
+impl TeamsChannel {
+    pub fn new(config: TeamsConfig) -> Self {
+        Self {
+            config,
+            client: crate::config::build_runtime_proxy_client("channel.teams"),
+            signing_keys: tokio::sync::Mutex::new(None),
+            access_token: tokio::sync::Mutex::new(None),
+            service_urls: Mutex::new(HashMap::new()),
+            typing_handles: Mutex::new(HashMap::new()),
+            pending_approvals: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_user_allowed(&self, ids: &[&str]) -> bool {
+        self.config
+            .allowed_users
+            .iter()
+            .any(|u| u == "*" || ids.iter().any(|id| u.eq_ignore_ascii_case(id)))
+    }
+
+    /// IDs a Teams sender can be allowlisted by: Bot Framework user ID and
+    /// Entra object ID.
+    fn sender_ids(activity: &Value) -> Vec<&str> {
+        let from = activity.get("from");
+        ["id", "aadObjectId"]
+            .iter()
+            .filter_map(|key| from.and_then(|f| f.get(key)).and_then(Value::as_str))
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+
+    /// Authenticate a Bot Framework request.
+    ///
+    /// Checks the bearer JWT's RS256 signature against the keys published in
+    /// the OpenID metadata, its issuer, audience (`app_id`) and lifetime, and
+    /// that its `serviceUrl` claim matches the activity, so replies can only
+    /// go to the connector that sent it.
+    pub async fn verify_request(
+        &self,
+        authorization: &str,
+        activity: &Value,
+    ) -> anyhow::Result<()> {
+        let token = authorization
+            .strip_prefix("Bearer ")
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .context("missing bearer token")?;
+        let segments: Vec<&str> = token.split('.').collect();
+        let [header, claims, signature] = segments[..] else {
+            anyhow::bail!("malformed JWT");
+        };
+        let header = decode_json_segment(header)?;
+        if header.get("alg").and_then(Value::as_str) != Some("RS256") {
+            anyhow::bail!("unsupported JWT algorithm");
+        }
+        let kid = header
+            .get("kid")
+            .and_then(Value::as_str)
+            .context("JWT has no key id")?;
+        let claims = decode_json_segment(claims)?;
+        let signature = decode_segment(signature)?;
+
+        let (issuer, key) = self.signing_key(kid).await?;
+        let signed = &token[..token.rfind('.').unwrap_or(0)];
+        if !verify_rs256(&key, signed.as_bytes(), &signature) {
+            anyhow::bail!("invalid JWT signature");
+        }
+
+        if claims.get("iss").and_then(Value::as_str) != Some(issuer.as_str()) {
+            anyhow::bail!("unexpected JWT issuer");
+        }
+        let audience_ok = match claims.get("aud") {
+            Some(Value::String(aud)) => *aud == self.config.app_id,
+            Some(Value::Array(auds)) => auds
+                .iter()
+                .any(|aud| aud.as_str() == Some(self.config.app_id.as_str())),
+            _ => false,
+        };
+        if !audience_ok {
+            anyhow::bail!("JWT audience does not match app_id");
+        }
+        let now = chrono::Utc::now().timestamp();
+        let exp = claims
+            .get("exp")
+            .and_then(Value::as_i64)
+            .context("JWT has no expiry")?;
+        if exp + CLOCK_SKEW_SECS < now {
+            anyhow::bail!("JWT expired");
+        }
+        if claims
+            .get("nbf")
+            .and_then(Value::as_i64)
+            .is_some_and(|nbf| nbf - CLOCK_SKEW_SECS > now)
+        {
+            anyhow::bail!("JWT not yet valid");
+        }
+
+        let service_url = activity.get("serviceUrl").and_then(Value::as_str);
+        if service_url.is_none() || claims.get("serviceUrl").and_then(Value::as_str) != service_url
+        {
+            anyhow::bail!("JWT serviceUrl does not match the activity");
+        }
+        // Keys may be endorsed for specific channels only.
+        if let (Some(endorsements), Some(channel_id)) = (
+            key.get("endorsements").and_then(Value::as_array),
+            activity.get("channelId").and_then(Value::as_str),
+        ) {
+            if !endorsements
+                .iter()
+                .any(|endorsement| endorsement.as_str() == Some(channel_id))
+            {
+                anyhow::bail!("signing key is not endorsed for channel {channel_id}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Issuer and JWK for `kid`, refreshing the cached keys when they are
+    /// stale or (rate-limited) when `kid` is unknown.
+    async fn signing_key(&self, kid: &str) -> anyhow::Result<(String, Value)> {
+        let mut cached = self.signing_keys.lock().await;
+        let refresh = cached.as_ref().is_none_or(|keys| {
+            keys.fetched_at.elapsed() > SIGNING_KEYS_TTL
+                || (keys.find(kid).is_none()
+                    && keys.fetched_at.elapsed() > SIGNING_KEYS_MIN_REFRESH)
+        });
+        if refresh {
+            *cached = Some(self.fetch_signing_keys().await?);
+        }
+        let keys = cached.as_ref().context("no signing keys")?;
+        let key = keys
+            .find(kid)
+            .with_context(|| format!("unknown JWT key id {kid}"))?;
+        Ok((keys.issuer.clone(), key.clone()))
+    }
+
+    async fn fetch_signing_keys(&self) -> anyhow::Result<SigningKeys> {
+        let metadata: Value = self
+            .client
+            .get(&self.config.openid_metadata_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let issuer = metadata
+            .get("issuer")
+            .and_then(Value::as_str)
+            .context("OpenID metadata has no issuer")?;
+        let jwks_uri = metadata
+            .get("jwks_uri")
+            .and_then(Value::as_str)
+            .context("OpenID metadata has no jwks_uri")?;
+        let jwks: Value = self
+            .client
+            .get(jwks_uri)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(SigningKeys {
+            issuer: issuer.to_string(),
+            keys: jwks
+                .get("keys")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+            fetched_at: Instant::now(),
+        })
+    }
+
+    fn token_url(&self) -> String {
+        self.config.token_url.clone().unwrap_or_else(|| {
+            let tenant = self
+                .config
+                .tenant_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|tenant| !tenant.is_empty())
+                .unwrap_or("botframework.com");
+            format!("https://login.microsoftonline.com/{tenant}/oauth2/v2.0/token")
+        })
+    }
+
+    /// Bot Connector access token (client credentials), cached until shortly
+    /// before it expires.
+    async fn access_token(&self) -> anyhow::Result<String> {
+        let mut cached = self.access_token.lock().await;
+        if let Some((token, expires_at)) = cached.as_ref() {
+            if Instant::now() + Duration::from_secs(60) < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let response = self
+            .client
+            .post(self.token_url())
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.config.app_id.as_str()),
+                ("client_secret", self.config.app_password.as_str()),
+                ("scope", BOT_FRAMEWORK_SCOPE),
+            ])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let sanitized = crate::providers::sanitize_api_error(&body);
+            tracing::error!("Teams token request failed: {status} — {sanitized}");
+            anyhow::bail!("Teams token request failed: {status}");
+        }
+        let body: Value = response.json().await?;
+        let token = body
+            .get("access_token")
+            .and_then(Value::as_str)
+            .context("Teams token response has no access_token")?
+            .to_string();
+        let expires_in = body
+            .get("expires_in")
+            .and_then(Value::as_u64)
+            .unwrap_or(3600);
+        *cached = Some((
+            token.clone(),
+            Instant::now() + Duration::from_secs(expires_in),
+        ));
+        Ok(token)
+    }
+
+    fn service_url(&self, conversation_id: &str) -> String {
+        let url = self
+            .service_urls
+            .lock()
+            .get(conversation_id)
+            .cloned()
+            .unwrap_or_else(|| self.config.service_url.clone());
+        format!("{}/", url.trim_end_matches('/'))
+    }
+
+    fn activities_url(&self, conversation_id: &str, activity_id: Option<&str>) -> String {
+        let mut url = format!(
+            "{}v3/conversations/{}/activities",
+            self.service_url(conversation_id),
+            urlencoding::encode(conversation_id)
+        );
+        if let Some(activity_id) = activity_id {
+            url.push('/');
+            url.push_str(&urlencoding::encode(activity_id));
+        }
+        url
+    }
+
+    /// Post `activity` to a conversation, as a threaded reply when
+    /// `reply_to_id` is set. Returns the new activity ID when the connector
+    /// reports one.
+    async fn post_activity(
+        &self,
+        conversation_id: &str,
+        reply_to_id: Option<&str>,
+        mut activity: Value,
+    ) -> anyhow::Result<Option<String>> {
+        if let Some(reply_to_id) = reply_to_id {
+            activity["replyToId"] = Value::from(reply_to_id);
+        }
+        let url = self.activities_url(conversation_id, reply_to_id);
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(token)
+            .json(&activity)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            let sanitized = crate::providers::sanitize_api_error(&body);
+            tracing::error!("Teams send failed: {status} — {sanitized}");
+            anyhow::bail!("Teams Bot Connector API error: {status}");
+        }
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        Ok(body.get("id").and_then(Value::as_str).map(String::from))
+    }
+
+    async fn update_activity(
+        &self,
+        conversation_id: &str,
+        activity_id: &str,
+        activity: Value,
+    ) -> anyhow::Result<()> {
+        let url = self.activities_url(conversation_id, Some(activity_id));
+        let token = self.access_token().await?;
+        let response = self
+            .client
+            .put(&url)
+            .bearer_auth(token)
+            .json(&activity)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Teams Bot Connector API error: {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Parse a Bot Framework activity into channel messages.
+    ///
+    /// Relevant activity fields:
+    /// - `type` (expects `message`)
+    /// - `from.id`, `from.aadObjectId` (checked against `allowed_users`)
+    /// - `conversation.id`, `id` (reply routing; replies are threaded to `id`)
+    /// - `text` with `<at>` mentions of the bot removed
+    pub fn parse_webhook_payload(&self, activity: &Value) -> Vec<ChannelMessage> {
+        let mut messages = Vec::new();
+
+        let activity_type = activity.get("type").and_then(Value::as_str).unwrap_or("");
+        if !activity_type.eq_ignore_ascii_case("message") {
+            tracing::debug!("Teams: skipping {activity_type} activity");
+            return messages;
+        }
+        if activity
+            .get("value")
+            .and_then(|value| value.get(APPROVAL_KEY))
+            .is_some()
+        {
+            return messages;
+        }
+
+        let Some(conversation_id) = activity
+            .get("conversation")
+            .and_then(|c| c.get("id"))
+            .and_then(Value::as_str)
+            .filter(|id| !id.is_empty())
+        else {
+            tracing::warn!("Teams: missing conversation.id in activity");
+            return messages;
+        };
+
+        let sender_ids = Self::sender_ids(activity);
+        let Some(sender) = sender_ids.last() else {
+            tracing::warn!("Teams: missing from.id in activity");
+            return messages;
+        };
+        if !self.is_user_allowed(&sender_ids) {
+            tracing::warn!(
+                "Teams: ignoring message from unauthorized user: {sender}. \
+                Add to channels.teams.allowed_users in config.toml, \
+                or run `zeroclaw onboard --channels-only` to configure interactively."
+            );
+            return messages;
+        }
+
+        let content = strip_bot_mentions(activity);
+        if content.is_empty() {
+            return messages;
+        }
+
+        if let Some(service_url) = activity.get("serviceUrl").and_then(Value::as_str) {
+            self.service_urls
+                .lock()
+                .insert(conversation_id.to_string(), service_url.to_string());
+        }
+
+        let activity_id = activity
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|id| !id.is_empty())
+            .map(String::from);
+        let timestamp = activity
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .and_then(|ts| u64::try_from(ts.timestamp()).ok())
+            .unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs()
+            });
+
+        messages.push(ChannelMessage {
+            id: activity_id
+                .clone()
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            sender: (*sender).to_string(),
+            reply_target: conversation_id.to_string(),
+            content,
+            channel: "teams".to_string(),
+            timestamp,
+            thread_ts: activity_id,
+        });
+        messages
+    }
+
+    /// Settle a pending approval from an adaptive-card button press.
+    ///
+    /// Returns `true` when `activity` is an approval submission, whether or
+    /// not it resolved anything; such activities are not chat messages.
+    pub fn resolve_approval(&self, activity: &Value) -> bool {
+        let Some(value) = activity.get("value") else {
+            return false;
+        };
+        let Some(approval_id) = value.get(APPROVAL_KEY).and_then(Value::as_str) else {
+            return false;
+        };
+
+        let sender_ids = Self::sender_ids(activity);
+        if !self.is_user_allowed(&sender_ids) {
+            tracing::warn!(
+                "Teams: ignoring approval decision from unauthorized user: {}",
+                sender_ids.last().unwrap_or(&"unknown")
+            );
+            return true;
+        }
+        let decision = value
+            .get("decision")
+            .cloned()
+            .and_then(|decision| serde_json::from_value::<ApprovalResponse>(decision).ok())
+            .unwrap_or(ApprovalResponse::No);
+        let conversation_id = activity
+            .get("conversation")
+            .and_then(|c| c.get("id"))
+            .and_then(Value::as_str)
+            .unwrap_or("");
+
+        let mut pending = self.pending_approvals.lock();
+        match pending.get(approval_id) {
+            Some(approval) if approval.conversation_id == conversation_id => {
+                if let Some(approval) = pending.remove(approval_id) {
+                    let _ = approval.decision.send(decision);
+                }
+            }
+            Some(_) => {
+                tracing::warn!("Teams: approval {approval_id} answered from another conversation");
+            }
+            None => tracing::debug!("Teams: approval {approval_id} is no longer pending"),
+        }
+        true
+    }
+
+    /// Post an approval card for `request` and wait for a button press.
+    /// Denies on timeout or when the card cannot be sent.
+    pub async fn request_approval(
+        &self,
+        conversation_id: &str,
+        reply_to_id: Option<&str>,
+        request: &ApprovalRequest,
+    ) -> ApprovalResponse {
+        let approval_id = Uuid::new_v4().to_string();
+        let (tx, rx) = oneshot::channel();
+        self.pending_approvals.lock().insert(
+            approval_id.clone(),
+            PendingApproval {
+                conversation_id: conversation_id.to_string(),
+                decision: tx,
+            },
+        );
+
+        let card = approval_card(request, Some(&approval_id), None);
+        let posted = self.post_activity(conversation_id, reply_to_id, card).await;
+        let decision = match &posted {
+            Ok(_) => {
+                let timeout = Duration::from_secs(self.config.approval_timeout_secs);
+                tokio::time::timeout(timeout, rx)
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .unwrap_or(ApprovalResponse::No)
+            }
+            Err(e) => {
+                tracing::warn!("Teams: failed to send approval card: {e}");
+                ApprovalResponse::No
+            }
+        };
+        self.pending_approvals.lock().remove(&approval_id);
+
+        // Replace the buttons with the outcome so the card cannot be reused.
+        if let Ok(Some(card_id)) = posted {
+            let card = approval_card(request, None, Some(decision));
+            if let Err(e) = self.update_activity(conversation_id, &card_id, card).await {
+                tracing::debug!("Teams: failed to update approval card: {e}");
+            }
+        }
+        decision
+    }
+}
+
+/// Remove `<at>` mentions of the bot (`recipient`) from the message text.
+fn strip_bot_mentions(activity: &Value) -> String {
+    let mut text = activity
+        .get("text")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string();
+    let bot_id = activity
+        .get("recipient")
+        .and_then(|r| r.get("id"))
+        .and_then(Value::as_str);
+    for entity in activity
+        .get("entities")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let is_bot_mention = entity.get("type").and_then(Value::as_str) == Some("mention")
+            && entity
+                .get("mentioned")
+                .and_then(|m| m.get("id"))
+                .and_then(Value::as_str)
+                == bot_id;
+        if let Some(mention) = entity.get("text").and_then(Value::as_str) {
+            if is_bot_mention {
+                text = text.replace(mention, "");
+            }
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Adaptive card asking to approve `request`. With `approval_id` it carries
+/// Approve / Always / Deny buttons; with `decision` it shows the outcome.
+fn approval_card(
+    request: &ApprovalRequest,
+    approval_id: Option<&str>,
+    decision: Option<ApprovalResponse>,
+) -> Value {
+    let arguments = serde_json::to_string_pretty(&request.arguments).unwrap_or_default();
+    let mut body = vec![
+        json!({
+            "type": "TextBlock",
+            "text": format!("Approve tool call: {}", request.tool_name),
+            "weight": "Bolder",
+            "wrap": true
+        }),
+        json!({
+            "type": "TextBlock",
+            "text": crate::util::truncate_with_ellipsis(&arguments, APPROVAL_ARGS_MAX_CHARS),
+            "fontType": "Monospace",
+            "wrap": true
+        }),
+    ];
+    if let Some(decision) = decision {
+        let outcome = match decision {
+            ApprovalResponse::Yes => "✅ Approved",
+            ApprovalResponse::Always => "✅ Approved for this session",
+            ApprovalResponse::No => "❌ Denied",
+        };
+        body.push(json!({"type": "TextBlock", "text": outcome, "wrap": true}));
+    }
+    let actions: Vec<Value> = approval_id
+        .map(|id| {
+            [
+                ("Approve", "yes"),
+                ("Always allow", "always"),
+                ("Deny", "no"),
+            ]
+            .iter()
+            .map(|(title, decision)| {
+                json!({
+                    "type": "Action.Submit",
+                    "title": title,
+                    "data": {APPROVAL_KEY: id, "decision": decision}
+                })
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "type": "AdaptiveCard",
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "version": "1.4",
+                "body": body,
+                "actions": actions
+            }
+        }]
+    })
+}
+
+fn decode_segment(segment: &str) -> anyhow::Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(segment.trim_end_matches('='))
+        .context("invalid base64url in JWT")
+}
+
+fn decode_json_segment(segment: &str) -> anyhow::Result<Value> {
+    serde_json::from_slice(&decode_segment(segment)?).context("invalid JSON in JWT")
+}
+
+/// Verify an RS256 signature with an RSA JWK (`n`, `e`).
+fn verify_rs256(jwk: &Value, message: &[u8], signature: &[u8]) -> bool {
+    let component = |name: &str| {
+        jwk.get(name)
+            .and_then(Value::as_str)
+            .and_then(|value| decode_segment(value).ok())
+    };
+    let (Some(n), Some(e)) = (component("n"), component("e")) else {
+        return false;
+    };
+    ring::signature::RsaPublicKeyComponents { n: &n, e: &e }
+        .verify(
+            &ring::signature::RSA_PKCS1_2048_8192_SHA256,
+            message,
+            signature,
+        )
+        .is_ok()
+}
+
+/// Approval prompts for one Teams conversation turn, sent as cards threaded
+/// to the message that started it.
+pub struct TeamsApprovalPrompter {
+    channel: Arc<TeamsChannel>,
+    conversation_id: String,
+    reply_to_id: Option<String>,
+}
+
+impl TeamsApprovalPrompter {
+    pub fn new(channel: Arc<TeamsChannel>, message: &ChannelMessage) -> Self {
+        Self {
+            channel,
+            conversation_id: message.reply_target.clone(),
+            reply_to_id: message.thread_ts.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl ChannelApprovalPrompter for TeamsApprovalPrompter {
+    fn channel(&self) -> &str {
+        "teams"
+    }
+
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        self.channel
+            .request_approval(&self.conversation_id, self.reply_to_id.as_deref(), request)
+            .await
+    }
+}
+
+#[async_trait]
+impl Channel for TeamsChannel {
+    fn name(&self) -> &str {
+        "teams"
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        let activity = json!({
+            "type": "message",
+            "text": message.content,
+            "textFormat": "markdown"
+        });
+        self.post_activity(&message.recipient, message.thread_ts.as_deref(), activity)
+            .await
+            .map(|_| ())
+    }
+
+    async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Teams channel active (webhook mode). \
+            Set the Azure Bot messaging endpoint to your gateway's /teams endpoint."
+        );
+
+        // Keep task alive; incoming activities are handled by the gateway webhook handler.
+        loop {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        self.access_token().await.is_ok()
+    }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        self.stop_typing(recipient).await?;
+
+        let client = self.client.clone();
+        let url = self.activities_url(recipient, None);
+        let token = self.access_token().await?;
+        let handle = tokio::spawn(async move {
+            loop {
+                let _ = client
+                    .post(&url)
+                    .bearer_auth(&token)
+                    .json(&json!({"type": "typing"}))
+                    .send()
+                    .await;
+                tokio::time::sleep(TYPING_REFRESH).await;
+            }
+        });
+        self.typing_handles
+            .lock()
+            .insert(recipient.to_string(), handle);
+        Ok(())
+    }
+
+    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        if let Some(handle) = self.typing_handles.lock().remove(recipient) {
+            handle.abort();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const APP_ID: &str = "app-id";
+    const KID: &str = "test-key";
+
+    /// Local stand-in for the Bot Framework: OpenID metadata, signing keys,
+    /// token endpoint and Bot Connector, plus a key to sign activity tokens.
+    struct Emulator {
+        server: MockServer,
+        key: RsaKeyPair,
+    }
+
+    impl Emulator {
+        async fn start() -> Self {
+            let server = MockServer::start().await;
+            let der = std::fs::read(
+                std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("tests/fixtures/teams_test_key.der"),
+            )
+            .unwrap();
+            let key = RsaKeyPair::from_pkcs8(&der).unwrap();
+            let public = ring::rsa::PublicKeyComponents::<Vec<u8>>::from(key.public());
+            let jwk = json!({
+                "kty": "RSA",
+                "kid": KID,
+                "n": URL_SAFE_NO_PAD.encode(&public.n),
+                "e": URL_SAFE_NO_PAD.encode(&public.e),
+                "endorsements": ["msteams", "emulator"]
+            });
+            Mock::given(method("GET"))
+                .and(path("/openid"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "issuer": "https://api.botframework.com",
+                    "jwks_uri": format!("{}/keys", server.uri())
+                })))
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path("/keys"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({"keys": [jwk]})))
+                .mount(&server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/token"))
+                .and(body_string_contains("grant_type=client_credentials"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "access_token": "bot-token",
+                    "expires_in": 3600
+                })))
+                .mount(&server)
+                .await;
+            Self { server, key }
+        }
+
+        fn channel(&self) -> TeamsChannel {
+            TeamsChannel::new(TeamsConfig {
+                app_id: APP_ID.into(),
+                app_password: "secret".into(),
+                tenant_id: None,
+                allowed_users: vec!["aad-alice".into()],
+                openid_metadata_url: format!("{}/openid", self.server.uri()),
+                token_url: Some(format!("{}/token", self.server.uri())),
+                service_url: self.server.uri(),
+                approval_timeout_secs: 5,
+            })
+        }
+
+        fn sign(&self, claims: &Value) -> String {
+            let header = URL_SAFE_NO_PAD.encode(
+                json!({"alg": "RS256", "typ": "JWT", "kid": KID})
+                    .to_string()
+                    .as_bytes(),
+            );
+            let claims = URL_SAFE_NO_PAD.encode(claims.to_string().as_bytes());
+            let signing_input = format!("{header}.{claims}");
+            let mut signature = vec![0; self.key.public().modulus_len()];
+            self.key
+                .sign(
+                    &RSA_PKCS1_SHA256,
+                    &SystemRandom::new(),
+                    signing_input.as_bytes(),
+                    &mut signature,
+                )
+                .unwrap();
+            format!(
+                "Bearer {signing_input}.{}",
+                URL_SAFE_NO_PAD.encode(signature)
+            )
+        }
+
+        fn claims(&self) -> Value {
+            let now = chrono::Utc::now().timestamp();
+            json!({
+                "iss": "https://api.botframework.com",
+                "aud": APP_ID,
+                "exp": now + 3600,
+                "nbf": now - 60,
+                "serviceUrl": self.server.uri()
+            })
+        }
+
+        fn activity(&self, text: &str, from: &str) -> Value {
+            json!({
+                "type": "message",
+                "id": "1700000000001",
+                "timestamp": "2026-01-02T03:04:05.000Z",
+                "channelId": "msteams",
+                "serviceUrl": self.server.uri(),
+                "from": {"id": format!("29:{from}"), "aadObjectId": from, "name": "Alice"},
+                "recipient": {"id": "28:bot", "name": "ZeroClaw"},
+                "conversation": {"id": "19:team@thread.tacv2;messageid=1700000000000"},
+                "text": text,
+                "entities": [{
+                    "type": "mention",
+                    "text": "<at>ZeroClaw</at>",
+                    "mentioned": {"id": "28:bot", "name": "ZeroClaw"}
+                }]
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn teams_verify_request_accepts_valid_token() {
+        let emulator = Emulator::start().await;
+        let channel = emulator.channel();
+        let activity = emulator.activity("hi", "aad-alice");
+        let token = emulator.sign(&emulator.claims());
+        channel.verify_request(&token, &activity).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn teams_verify_request_rejects_bad_tokens() {
+        let emulator = Emulator::start().await;
+        let channel = emulator.channel();
+        let activity = emulator.activity("hi", "aad-alice");
+
+        let with = |key: &str, value: Value| {
+            let mut claims = emulator.claims();
+            claims[key] = value;
+            emulator.sign(&claims)
+        };
+        let expired = chrono::Utc::now().timestamp() - 3600;
+        for (case, token) in [
+            ("missing", String::new()),
+            ("audience", with("aud", json!("other-app"))),
+            ("issuer", with("iss", json!("https://evil.example.com"))),
+            ("expired", with("exp", json!(expired))),
+            (
+                "service url",
+                with("serviceUrl", json!("https://evil.example.com")),
+            ),
+        ] {
+            assert!(
+                channel.verify_request(&token, &activity).await.is_err(),
+                "{case} should be rejected"
+            );
+        }
+
+        // Claims swapped after signing.
+        let token = emulator.sign(&emulator.claims());
+        let mut segments: Vec<&str> = token.split('.').collect();
+        let mut claims = emulator.claims();
+        claims["aud"] = json!("other-app");
+        let forged = URL_SAFE_NO_PAD.encode(claims.to_string().as_bytes());
+        segments[1] = &forged;
+        assert!(channel
+            .verify_request(&segments.join("."), &activity)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("signature"));
+
+        let mut foreign = activity.clone();
+        foreign["channelId"] = json!("slack");
+        assert!(channel.verify_request(&token, &foreign).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn teams_parse_strips_mention_and_threads_reply() {
+        let emulator = Emulator::start().await;
+        let channel = emulator.channel();
+
+        let messages = channel
+            .parse_webhook_payload(&emulator.activity("<at>ZeroClaw</at> status?", "aad-alice"));
+        assert_eq!(messages.len(), 1);
+        let msg = &messages[0];
+        assert_eq!(msg.content, "status?");
+        assert_eq!(msg.sender, "aad-alice");
+        assert_eq!(msg.channel, "teams");
+        assert_eq!(
+            msg.reply_target,
+            "19:team@thread.tacv2;messageid=1700000000000"
+        );
+        assert_eq!(msg.thread_ts.as_deref(), Some("1700000000001"));
+        assert_eq!(msg.timestamp, 1_767_323_045);
+
+        assert!(channel
+            .parse_webhook_payload(&emulator.activity("hello", "aad-mallory"))
+            .is_empty());
+        let mut typing = emulator.activity("", "aad-alice");
+        typing["type"] = json!("typing");
+        assert!(channel.parse_webhook_payload(&typing).is_empty());
+    }
+
+    #[tokio::test]
+    async fn teams_send_replies_in_thread_with_cached_token() {
+        let emulator = Emulator::start().await;
+        let channel = emulator.channel();
+        Mock::given(method("POST"))
+            .and(path(
+                "/v3/conversations/19%3Ateam%40thread.tacv2%3Bmessageid%3D1700000000000/activities/1700000000001",
+            ))
+            .and(header("authorization", "Bearer bot-token"))
+            .and(body_string_contains("\"replyToId\":\"1700000000001\""))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "reply-1"})))
+            .expect(2)
+            .mount(&emulator.server)
+            .await;
+
+        let msg = &channel.parse_webhook_payload(&emulator.activity("hi", "aad-alice"))[0];
+        for text in ["one", "two"] {
+            channel
+                .send(&SendMessage::new(text, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+                .await
+                .unwrap();
+        }
+        let token_requests = emulator
+            .server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path() == "/token")
+            .count();
+        assert_eq!(token_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn teams_typing_indicator_posts_typing_activity() {
+        let emulator = Emulator::start().await;
+        let channel = emulator.channel();
+        Mock::given(method("POST"))
+            .and(path("/v3/conversations/conv-1/activities"))
+            .and(body_string_contains("\"type\":\"typing\""))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1..)
+            .mount(&emulator.server)
+            .await;
+
+        channel.start_typing("conv-1").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        channel.stop_typing("conv-1").await.unwrap();
+        assert!(channel.typing_handles.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn teams_approval_card_round_trip() {
+        let emulator = Emulator::start().await;
+        let channel = Arc::new(emulator.channel());
+        Mock::given(method("POST"))
+            .and(path("/v3/conversations/conv-1/activities/msg-1"))
+            .and(body_string_contains(
+                "application/vnd.microsoft.card.adaptive",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"id": "card-1"})))
+            .mount(&emulator.server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v3/conversations/conv-1/activities/card-1"))
+            .and(body_string_contains("Approved for this session"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&emulator.server)
+            .await;
+
+        let message = ChannelMessage {
+            id: "msg-1".into(),
+            sender: "aad-alice".into(),
+            reply_target: "conv-1".into(),
+            content: "clean up".into(),
+            channel: "teams".into(),
+            timestamp: 0,
+            thread_ts: Some("msg-1".into()),
+        };
+        let prompter = TeamsApprovalPrompter::new(Arc::clone(&channel), &message);
+        let pending = tokio::spawn(async move {
+            prompter
+                .prompt(&ApprovalRequest {
+                    tool_name: "shell".into(),
+                    arguments: json!({"command": "rm -rf build"}),
+                })
+                .await
+        });
+
+        // Wait for the card and pull the approval ID from its Approve button.
+        let approval_id = loop {
+            let requests = emulator.server.received_requests().await.unwrap();
+            if let Some(card) = requests
+                .iter()
+                .find(|request| request.url.path().ends_with("/activities/msg-1"))
+            {
+                let card: Value = serde_json::from_slice(&card.body).unwrap();
+                let content = &card["attachments"][0]["content"];
+                assert!(content["body"][1]["text"]
+                    .as_str()
+                    .unwrap()
+                    .contains("rm -rf build"));
+                break content["actions"][0]["data"][APPROVAL_KEY]
+                    .as_str()
+                    .unwrap()
+                    .to_string();
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        };
+
+        let submit = |from: &str, conversation: &str| {
+            json!({
+                "type": "message",
+                "from": {"id": "29:x", "aadObjectId": from},
+                "conversation": {"id": conversation},
+                "value": {APPROVAL_KEY: approval_id, "decision": "always"}
+            })
+        };
+        // Unauthorized users and other conversations cannot answer.
+        assert!(channel.resolve_approval(&submit("aad-mallory", "conv-1")));
+        assert!(channel.resolve_approval(&submit("aad-alice", "conv-2")));
+        assert!(channel
+            .parse_webhook_payload(&submit("aad-alice", "conv-1"))
+            .is_empty());
+        assert!(channel.resolve_approval(&submit("aad-alice", "conv-1")));
+
+        assert_eq!(pending.await.unwrap(), ApprovalResponse::Always);
+        assert!(channel.pending_approvals.lock().is_empty());
+        assert!(!channel.resolve_approval(&emulator.activity("hi", "aad-alice")));
+    }
+
+    #[tokio::test]
+    async fn teams_approval_denies_when_card_cannot_be_sent() {
+        let emulator = Emulator::start().await;
+        let channel = emulator.channel();
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&emulator.server)
+            .await;
+
+        let decision = channel
+            .request_approval(
+                "conv-1",
+                None,
+                &ApprovalRequest {
+                    tool_name: "shell".into(),
+                    arguments: json!({}),
+                },
+            )
+            .await;
+        assert_eq!(decision, ApprovalResponse::No);
+        assert!(channel.pending_approvals.lock().is_empty());
+    }
+}
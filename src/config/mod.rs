@@ -5,20 +5,24 @@ pub mod traits;
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AgentsIpcConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig,
-    BrowserConfig, BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config,
-    CostConfig, CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig,
-    EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig, HardwareConfig,
-    HardwareTransport, HeartbeatConfig, HooksConfig, HttpRequestConfig, IMessageConfig,
-    IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig, ModelRouteConfig, MultimodalConfig,
+    A2aConfig, A2aRemoteAgentConfig, AgentConfig, AgentsIpcConfig, AuditConfig, AutonomyConfig,
+    BrowserComputerUseConfig, BrowserConfig, BuiltinHooksConfig, ChannelsConfig,
+    ClassificationRule, ComposioConfig, Config, CostConfig, CronConfig, DelegateAgentConfig,
+    DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig, EstopConfig, ExternalHookConfig,
+    FeishuConfig, ForgeConfig, ForgeProvider, GatewayApiToken, GatewayConfig, GatewayTlsConfig,
+    HardwareConfig, HardwareTransport, HeartbeatConfig, HomeAssistantConfig, HookFailurePolicy,
+    HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig, ImageGenBackend,
+    ImageGenConfig, LarkConfig, MatrixConfig, MemoryConfig, MemoryConsolidationConfig,
+    MemoryNamespacesConfig, MemoryPersonConfig, ModelRouteConfig, MqttConfig, MultimodalConfig,
     NextcloudTalkConfig, ObservabilityConfig, OtpConfig, OtpMethod, PeripheralBoardConfig,
     PeripheralsConfig, ProviderConfig, ProxyConfig, ProxyScope, QdrantConfig,
     QueryClassificationConfig, ReliabilityConfig, ResearchPhaseConfig, ResearchTrigger,
     ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig,
-    SecretsConfig, SecurityConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig,
-    StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode, SyscallAnomalyConfig,
-    TelegramConfig, TranscriptionConfig, TunnelConfig, WasmCapabilityEscalationMode,
-    WasmRuntimeConfig, WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
+    SecretsConfig, SecurityConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig, SopConfig,
+    SopExecutionMode, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
+    SyscallAnomalyConfig, TeamsConfig, TelegramConfig, TranscriptionConfig, TunnelConfig,
+    VaultConfig, WasmCapabilityEscalationMode, WasmHooksConfig, WasmRuntimeConfig,
+    WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: Option<&T>) -> (&'static str, bool) {
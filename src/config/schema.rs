@@ -1,7 +1,8 @@
 use crate::config::traits::ChannelConfig;
 use crate::providers::{is_glm_alias, is_zai_alias};
-use crate::security::{AutonomyLevel, DomainMatcher};
+use crate::security::{AutonomyLevel, DomainMatcher, TokenScope};
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use directories::UserDirs;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -32,16 +33,19 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "channel.qq",
     "channel.signal",
     "channel.slack",
+    "channel.teams",
     "channel.telegram",
     "channel.wati",
     "channel.whatsapp",
     "tool.browser",
     "tool.composio",
     "tool.http_request",
+    "tool.image_generate",
     "tool.pushover",
     "memory.embeddings",
     "tunnel.custom",
     "transcription.groq",
+    "hooks.webhook",
 ];
 
 const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
@@ -51,6 +55,7 @@ const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
     "memory.*",
     "tunnel.*",
     "transcription.*",
+    "hooks.*",
 ];
 
 static RUNTIME_PROXY_CONFIG: OnceLock<RwLock<ProxyConfig>> = OnceLock::new();
@@ -172,6 +177,10 @@ pub struct Config {
     #[serde(default)]
     pub cron: CronConfig,
 
+    /// Standard operating procedures (`[sop]`).
+    #[serde(default)]
+    pub sop: SopConfig,
+
     /// Channel configurations: Telegram, Discord, Slack, etc. (`[channels_config]`).
     #[serde(default)]
     pub channels_config: ChannelsConfig,
@@ -256,6 +265,26 @@ pub struct Config {
     #[serde(default)]
     pub agents_ipc: AgentsIpcConfig,
 
+    /// Agent-to-Agent (A2A) protocol endpoint and remote agents (`[a2a]`).
+    #[serde(default)]
+    pub a2a: A2aConfig,
+
+    /// Home Assistant tool and state-change triggers (`[home_assistant]`).
+    #[serde(default)]
+    pub home_assistant: HomeAssistantConfig,
+
+    /// GitHub/GitLab forge tool for issues and pull requests (`[forge]`).
+    #[serde(default)]
+    pub forge: ForgeConfig,
+
+    /// Markdown/Obsidian vault tool (`[vault]`).
+    #[serde(default)]
+    pub vault: VaultConfig,
+
+    /// Image generation tool (`[image_gen]`).
+    #[serde(default)]
+    pub image_gen: ImageGenConfig,
+
     /// Vision support override for the active provider/model.
     /// - `None` (default): use provider's built-in default
     /// - `Some(true)`: force vision support on (e.g. Ollama running llava)
@@ -364,6 +393,7 @@ impl std::fmt::Debug for Config {
             self.channels_config.linq.is_some(),
             self.channels_config.wati.is_some(),
             self.channels_config.nextcloud_talk.is_some(),
+            self.channels_config.teams.is_some(),
             self.channels_config.email.is_some(),
             self.channels_config.irc.is_some(),
             self.channels_config.lark.is_some(),
@@ -372,6 +402,7 @@ impl std::fmt::Debug for Config {
             self.channels_config.qq.is_some(),
             self.channels_config.nostr.is_some(),
             self.channels_config.clawdtalk.is_some(),
+            self.channels_config.mqtt.is_some(),
         ]
         .into_iter()
         .filter(|enabled| *enabled)
@@ -513,6 +544,355 @@ impl Default for TranscriptionConfig {
     }
 }
 
+// ── A2A ─────────────────────────────────────────────────────────
+
+fn default_a2a_timeout_secs() -> u64 {
+    120
+}
+
+/// Agent-to-Agent protocol configuration (`[a2a]` section).
+///
+/// When enabled, the gateway publishes an agent card and accepts A2A
+/// JSON-RPC tasks at `/a2a`. Remote agents listed under `[a2a.agents.<name>]`
+/// are reachable through the `a2a` tool.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct A2aConfig {
+    /// Serve the agent card and `/a2a` endpoint on the gateway.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Agent name published in the agent card. Default: `ZeroClaw`.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Agent description published in the agent card.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Externally reachable gateway base URL advertised in the agent card
+    /// (e.g. a tunnel URL). Defaults to the request's `Host` header.
+    #[serde(default)]
+    pub public_url: Option<String>,
+    /// Remote A2A agents callable through the `a2a` tool.
+    #[serde(default)]
+    pub agents: HashMap<String, A2aRemoteAgentConfig>,
+}
+
+/// Remote A2A agent reachable through the `a2a` tool.
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct A2aRemoteAgentConfig {
+    /// JSON-RPC endpoint of the remote agent (for ZeroClaw: `https://host/a2a`).
+    pub url: String,
+    /// Bearer token for the remote agent (a pairing or API token on ZeroClaw).
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Request timeout in seconds. Default: `120`.
+    #[serde(default = "default_a2a_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl std::fmt::Debug for A2aRemoteAgentConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("A2aRemoteAgentConfig")
+            .field("url", &self.url)
+            .field("token_configured", &self.token.is_some())
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+// ── Home Assistant ──────────────────────────────────────────────
+
+fn default_home_assistant_url() -> String {
+    "http://localhost:8123".into()
+}
+
+fn default_home_assistant_timeout_secs() -> u64 {
+    15
+}
+
+/// Home Assistant integration (`[home_assistant]` section).
+///
+/// When enabled, registers the `home_assistant` tool (REST API) and, with
+/// cron enabled, a daemon listener on the WebSocket API that fires
+/// `state` jobs on entity state changes. Service calls are deny-by-default:
+/// only domains in `allowed_domains` may be called.
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HomeAssistantConfig {
+    /// Enable the Home Assistant tool and state-change triggers.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Base URL of the Home Assistant instance. Default: `http://localhost:8123`.
+    #[serde(default = "default_home_assistant_url")]
+    pub url: String,
+    /// Long-lived access token (Profile → Security in Home Assistant).
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Service domains the agent may call (e.g. `light`, `switch`, `scene`;
+    /// `"*"` for any). Empty means no service calls are allowed.
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Entity ID glob patterns service calls may target (e.g. `light.kitchen_*`).
+    /// Empty means any entity; when set, calls must name their targets by
+    /// `entity_id`.
+    #[serde(default)]
+    pub allowed_entities: Vec<String>,
+    /// REST request timeout in seconds. Default: `15`.
+    #[serde(default = "default_home_assistant_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for HomeAssistantConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: default_home_assistant_url(),
+            token: None,
+            allowed_domains: Vec::new(),
+            allowed_entities: Vec::new(),
+            timeout_secs: default_home_assistant_timeout_secs(),
+        }
+    }
+}
+
+impl std::fmt::Debug for HomeAssistantConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HomeAssistantConfig")
+            .field("enabled", &self.enabled)
+            .field("url", &self.url)
+            .field("token_configured", &self.token.is_some())
+            .field("allowed_domains", &self.allowed_domains)
+            .field("allowed_entities", &self.allowed_entities)
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+// ── Forge ───────────────────────────────────────────────────────
+
+fn default_forge_timeout_secs() -> u64 {
+    30
+}
+
+/// Code forge hosting the repositories the `forge` tool works on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeProvider {
+    /// GitHub or GitHub Enterprise Server.
+    #[default]
+    Github,
+    /// GitLab.com or a self-managed GitLab.
+    Gitlab,
+}
+
+/// Forge tool configuration (`[forge]` section).
+///
+/// When enabled, registers the `forge` tool for issues, comments, pull
+/// (merge) requests, CI checks and review comments. Only repositories
+/// matching `allowed_repos` are reachable.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct ForgeConfig {
+    /// Enable the `forge` tool.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Forge API flavour: `github` (default) or `gitlab`.
+    #[serde(default)]
+    pub provider: ForgeProvider,
+    /// API base URL. Default: `https://api.github.com` or
+    /// `https://gitlab.com/api/v4`; set for GitHub Enterprise or self-managed GitLab.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Personal access token used for API calls and branch pushes.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Repository patterns the tool may touch (`owner/repo`, `owner/*`,
+    /// `group/subgroup/project`). Empty means no repositories.
+    #[serde(default)]
+    pub allowed_repos: Vec<String>,
+    /// API request timeout in seconds. Default: `30`.
+    #[serde(default = "default_forge_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl std::fmt::Debug for ForgeConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForgeConfig")
+            .field("enabled", &self.enabled)
+            .field("provider", &self.provider)
+            .field("api_url", &self.api_url)
+            .field("token_configured", &self.token.is_some())
+            .field("allowed_repos", &self.allowed_repos)
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+// ── Vault ───────────────────────────────────────────────────────
+
+fn default_vault_daily_folder() -> String {
+    "Daily".into()
+}
+
+fn default_vault_daily_format() -> String {
+    "%Y-%m-%d".into()
+}
+
+fn default_vault_templates_folder() -> String {
+    "Templates".into()
+}
+
+/// Markdown vault configuration (`[vault]` section).
+///
+/// When enabled, registers the `vault` tool for an Obsidian-style folder of
+/// Markdown notes: search, wikilinks and backlinks, frontmatter, daily notes
+/// and templates. The vault must be inside the workspace or an
+/// `[autonomy].allowed_roots` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct VaultConfig {
+    /// Enable the `vault` tool.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Vault root directory (absolute, `~/…`, or relative to the workspace).
+    #[serde(default)]
+    pub path: String,
+    /// Folder for daily notes, relative to the vault root. Default: `Daily`.
+    #[serde(default = "default_vault_daily_folder")]
+    pub daily_folder: String,
+    /// `strftime` format of daily note names. Default: `%Y-%m-%d`.
+    #[serde(default = "default_vault_daily_format")]
+    pub daily_format: String,
+    /// Folder holding note templates. Default: `Templates`.
+    #[serde(default = "default_vault_templates_folder")]
+    pub templates_folder: String,
+    /// Template used when a daily note is created (name in `templates_folder`).
+    #[serde(default)]
+    pub daily_template: Option<String>,
+}
+
+impl Default for VaultConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            daily_folder: default_vault_daily_folder(),
+            daily_format: default_vault_daily_format(),
+            templates_folder: default_vault_templates_folder(),
+            daily_template: None,
+        }
+    }
+}
+
+// ── Image generation ────────────────────────────────────────────
+
+fn default_image_gen_model() -> String {
+    "gpt-image-1".into()
+}
+
+fn default_image_gen_size() -> String {
+    "1024x1024".into()
+}
+
+fn default_image_gen_output_dir() -> String {
+    "images".into()
+}
+
+fn default_image_gen_max_images() -> u32 {
+    4
+}
+
+fn default_image_gen_cost_per_image_usd() -> f64 {
+    0.04
+}
+
+fn default_image_gen_timeout_secs() -> u64 {
+    120
+}
+
+/// Image generation API spoken by the `image_generate` tool.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageGenBackend {
+    /// OpenAI-compatible `POST /v1/images/generations`.
+    #[default]
+    Openai,
+    /// Stable Diffusion web UI API (`POST /sdapi/v1/txt2img`), as served by
+    /// AUTOMATIC1111, Forge or SD.Next.
+    StableDiffusion,
+}
+
+/// Image generation configuration (`[image_gen]` section).
+///
+/// When enabled, registers the `image_generate` tool. Generated images are
+/// saved under `output_dir` in the workspace and returned as `[IMAGE:…]`
+/// markers so channels can deliver them.
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImageGenConfig {
+    /// Enable the `image_generate` tool.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Backend API: `openai` (default) or `stable_diffusion`.
+    #[serde(default)]
+    pub backend: ImageGenBackend,
+    /// API base URL. Default: `https://api.openai.com/v1` or
+    /// `http://127.0.0.1:7860` for `stable_diffusion`.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// API key sent as a bearer token. Falls back to `OPENAI_API_KEY` for the
+    /// `openai` backend.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Model name for the `openai` backend. Default: `gpt-image-1`.
+    #[serde(default = "default_image_gen_model")]
+    pub model: String,
+    /// Default image size (`WIDTHxHEIGHT`). Default: `1024x1024`.
+    #[serde(default = "default_image_gen_size")]
+    pub size: String,
+    /// Output folder, relative to the workspace. Default: `images`.
+    #[serde(default = "default_image_gen_output_dir")]
+    pub output_dir: String,
+    /// Maximum images per call. Default: `4`.
+    #[serde(default = "default_image_gen_max_images")]
+    pub max_images: u32,
+    /// Cost recorded per generated image, in USD. Default: `0.04`; set `0`
+    /// for local backends.
+    #[serde(default = "default_image_gen_cost_per_image_usd")]
+    pub cost_per_image_usd: f64,
+    /// Request timeout in seconds. Default: `120`.
+    #[serde(default = "default_image_gen_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for ImageGenConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: ImageGenBackend::default(),
+            api_url: None,
+            api_key: None,
+            model: default_image_gen_model(),
+            size: default_image_gen_size(),
+            output_dir: default_image_gen_output_dir(),
+            max_images: default_image_gen_max_images(),
+            cost_per_image_usd: default_image_gen_cost_per_image_usd(),
+            timeout_secs: default_image_gen_timeout_secs(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ImageGenConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImageGenConfig")
+            .field("enabled", &self.enabled)
+            .field("backend", &self.backend)
+            .field("api_url", &self.api_url)
+            .field("api_key_configured", &self.api_key.is_some())
+            .field("model", &self.model)
+            .field("size", &self.size)
+            .field("output_dir", &self.output_dir)
+            .field("max_images", &self.max_images)
+            .field("cost_per_image_usd", &self.cost_per_image_usd)
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
 // ── Agents IPC ──────────────────────────────────────────────────
 
 fn default_agents_ipc_db_path() -> String {
@@ -570,6 +950,24 @@ pub struct AgentConfig {
     /// Tool dispatch strategy (e.g. `"auto"`). Default: `"auto"`.
     #[serde(default = "default_agent_tool_dispatcher")]
     pub tool_dispatcher: String,
+    /// Override the model's context window in tokens. Unset uses built-in
+    /// per-model metadata (32768 for unknown models).
+    #[serde(default)]
+    pub context_window_tokens: Option<usize>,
+    /// Tokens kept free for the model's reply when budgeting input. Default: `4096`.
+    #[serde(default = "default_agent_output_reserve_tokens")]
+    pub output_reserve_tokens: usize,
+    /// Cap on a single tool result kept in history, in tokens. Default: `8000`.
+    #[serde(default = "default_agent_max_tool_result_tokens")]
+    pub max_tool_result_tokens: usize,
+}
+
+fn default_agent_output_reserve_tokens() -> usize {
+    4096
+}
+
+fn default_agent_max_tool_result_tokens() -> usize {
+    8000
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -592,6 +990,9 @@ impl Default for AgentConfig {
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
             tool_dispatcher: default_agent_tool_dispatcher(),
+            context_window_tokens: None,
+            output_reserve_tokens: default_agent_output_reserve_tokens(),
+            max_tool_result_tokens: default_agent_max_tool_result_tokens(),
         }
     }
 }
@@ -924,6 +1325,10 @@ pub struct GatewayConfig {
     /// Paired bearer tokens (managed automatically, not user-edited)
     #[serde(default)]
     pub paired_tokens: Vec<String>,
+    /// Scoped API tokens (`[[gateway.api_tokens]]`), managed with
+    /// `zeroclaw gateway tokens` or `/api/tokens`. Only token hashes are stored.
+    #[serde(default)]
+    pub api_tokens: Vec<GatewayApiToken>,
 
     /// Max `/pair` requests per minute per client key.
     #[serde(default = "default_pair_rate_limit")]
@@ -953,6 +1358,56 @@ pub struct GatewayConfig {
     /// Node-control protocol scaffold (`[gateway.node_control]`).
     #[serde(default)]
     pub node_control: NodeControlConfig,
+
+    /// Built-in TLS termination (`[gateway.tls]`).
+    #[serde(default)]
+    pub tls: GatewayTlsConfig,
+}
+
+/// A scoped gateway API token (`[[gateway.api_tokens]]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct GatewayApiToken {
+    /// Stable identifier used for revocation (`tok_…`).
+    pub id: String,
+    /// Human-readable label shown in listings and audit entries.
+    #[serde(default)]
+    pub label: String,
+    /// SHA-256 hex digest of the bearer token.
+    pub hash: String,
+    /// Granted scopes: `read`, `chat`, `admin`. `admin` implies the others.
+    pub scopes: Vec<TokenScope>,
+    #[schemars(with = "String")]
+    pub created_at: DateTime<Utc>,
+    /// Token stops working after this instant. Default: never.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Set when the token is revoked; revoked tokens are kept for auditing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<String>")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl GatewayApiToken {
+    /// Not revoked and not expired at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|at| at > now)
+    }
+
+    fn validate(&self, index: usize) -> Result<()> {
+        if self.id.trim().is_empty() {
+            anyhow::bail!("gateway.api_tokens[{index}].id must not be empty");
+        }
+        if self.hash.len() != 64 || !self.hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!(
+                "gateway.api_tokens[{index}].hash must be a SHA-256 hex digest (64 characters)"
+            );
+        }
+        if self.scopes.is_empty() {
+            anyhow::bail!("gateway.api_tokens[{index}].scopes must not be empty");
+        }
+        Ok(())
+    }
 }
 
 /// Node-control scaffold settings under `[gateway.node_control]`.
@@ -973,6 +1428,99 @@ pub struct NodeControlConfig {
     pub allowed_node_ids: Vec<String>,
 }
 
+/// Built-in TLS termination under `[gateway.tls]`.
+///
+/// Relative paths resolve against the config directory (`~/.zeroclaw`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct GatewayTlsConfig {
+    /// Serve the gateway over HTTPS instead of plain HTTP. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// PEM certificate chain. Default: `tls/gateway.crt`.
+    #[serde(default = "default_gateway_tls_cert_path")]
+    pub cert_path: String,
+
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1). Default: `tls/gateway.key`.
+    #[serde(default = "default_gateway_tls_key_path")]
+    pub key_path: String,
+
+    /// Generate a self-signed certificate on first run when `cert_path` and
+    /// `key_path` do not exist. Default: `true`.
+    #[serde(default = "default_true")]
+    pub self_signed: bool,
+
+    /// Extra DNS names or IP addresses for the generated certificate, in
+    /// addition to `localhost`, loopback addresses, the bind host and the
+    /// machine hostname. Default: `[]`.
+    #[serde(default)]
+    pub self_signed_hosts: Vec<String>,
+
+    /// How often to check the certificate files for renewal and reload them
+    /// without restarting. `0` disables reloading. Default: `300`.
+    #[serde(default = "default_gateway_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+
+    /// PEM CA bundle for mutual TLS. When set, clients must present a
+    /// certificate issued by this CA, and a verified client certificate
+    /// replaces pairing/bearer authentication. Default: unset.
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+}
+
+impl Default for GatewayTlsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cert_path: default_gateway_tls_cert_path(),
+            key_path: default_gateway_tls_key_path(),
+            self_signed: true,
+            self_signed_hosts: Vec::new(),
+            reload_interval_secs: default_gateway_tls_reload_interval_secs(),
+            client_ca_path: None,
+        }
+    }
+}
+
+impl GatewayTlsConfig {
+    /// Whether TLS is on and client certificates are required.
+    pub fn requires_client_cert(&self) -> bool {
+        self.enabled && self.client_ca_path.is_some()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.cert_path.trim().is_empty() || self.key_path.trim().is_empty() {
+            anyhow::bail!("gateway.tls.cert_path and gateway.tls.key_path must not be empty");
+        }
+        if self
+            .client_ca_path
+            .as_deref()
+            .is_some_and(|path| path.trim().is_empty())
+        {
+            anyhow::bail!("gateway.tls.client_ca_path must not be empty when set");
+        }
+        if self.self_signed_hosts.iter().any(|h| h.trim().is_empty()) {
+            anyhow::bail!("gateway.tls.self_signed_hosts must not contain empty entries");
+        }
+        Ok(())
+    }
+}
+
+fn default_gateway_tls_cert_path() -> String {
+    "tls/gateway.crt".into()
+}
+
+fn default_gateway_tls_key_path() -> String {
+    "tls/gateway.key".into()
+}
+
+fn default_gateway_tls_reload_interval_secs() -> u64 {
+    300
+}
+
 fn default_gateway_port() -> u16 {
     42617
 }
@@ -1013,6 +1561,7 @@ impl Default for GatewayConfig {
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: Vec::new(),
+            api_tokens: Vec::new(),
             pair_rate_limit_per_minute: default_pair_rate_limit(),
             webhook_rate_limit_per_minute: default_webhook_rate_limit(),
             trust_forwarded_headers: false,
@@ -1020,6 +1569,7 @@ impl Default for GatewayConfig {
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
             node_control: NodeControlConfig::default(),
+            tls: GatewayTlsConfig::default(),
         }
     }
 }
@@ -1912,6 +2462,90 @@ impl Default for QdrantConfig {
     }
 }
 
+/// LLM-driven memory consolidation (`[memory.consolidation]`).
+///
+/// Runs from the cron scheduler: extracts durable facts from conversation
+/// autosaves into `core`, merges duplicate or contradicting facts and
+/// summarizes old daily entries.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryConsolidationConfig {
+    /// Run consolidation from the scheduler. Default: false.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minimum hours between scheduled runs. Default: 24.
+    #[serde(default = "default_consolidation_interval_hours")]
+    pub interval_hours: u32,
+    /// Max conversation entries reviewed per run. Default: 50.
+    #[serde(default = "default_consolidation_batch_size")]
+    pub batch_size: usize,
+    /// Summarize daily entries older than this many days into one entry
+    /// per day. 0 disables daily summaries. Default: 7.
+    #[serde(default = "default_consolidation_summarize_after_days")]
+    pub summarize_daily_after_days: u32,
+    /// Model override for consolidation calls (defaults to `default_model`).
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn default_consolidation_interval_hours() -> u32 {
+    24
+}
+
+fn default_consolidation_batch_size() -> usize {
+    50
+}
+
+fn default_consolidation_summarize_after_days() -> u32 {
+    7
+}
+
+impl Default for MemoryConsolidationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_consolidation_interval_hours(),
+            batch_size: default_consolidation_batch_size(),
+            summarize_daily_after_days: default_consolidation_summarize_after_days(),
+            model: None,
+        }
+    }
+}
+
+/// Per-person memory namespaces (`[memory.namespaces]`).
+///
+/// Channel messages are stored and recalled in the sender's namespace plus
+/// the shared scope. Senders listed under a person share one namespace
+/// across channels; anyone else gets `channel:sender`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryNamespacesConfig {
+    /// Isolate channel memories per person. Default: true.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// People whose channel identities share one namespace.
+    #[serde(default)]
+    pub people: Vec<MemoryPersonConfig>,
+}
+
+impl Default for MemoryNamespacesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            people: Vec::new(),
+        }
+    }
+}
+
+/// A person linked across channels (`[[memory.namespaces.people]]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryPersonConfig {
+    /// Namespace name, e.g. "alice".
+    pub name: String,
+    /// Channel senders as `channel:sender`, e.g. "telegram:123456789" or
+    /// "discord:alice#0001".
+    #[serde(default)]
+    pub senders: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MemoryConfig {
@@ -1994,6 +2628,16 @@ pub struct MemoryConfig {
     /// Only used when `backend = "qdrant"`.
     #[serde(default)]
     pub qdrant: QdrantConfig,
+
+    // ── Consolidation ──────────────────────────────────────────
+    /// Scheduled fact extraction, deduplication and daily summaries.
+    #[serde(default)]
+    pub consolidation: MemoryConsolidationConfig,
+
+    // ── Namespaces ─────────────────────────────────────────────
+    /// Per-person memory isolation for channel senders.
+    #[serde(default)]
+    pub namespaces: MemoryNamespacesConfig,
 }
 
 fn default_embedding_provider() -> String {
@@ -2064,6 +2708,8 @@ impl Default for MemoryConfig {
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
             qdrant: QdrantConfig::default(),
+            consolidation: MemoryConsolidationConfig::default(),
+            namespaces: MemoryNamespacesConfig::default(),
         }
     }
 }
@@ -2111,44 +2757,211 @@ impl Default for ObservabilityConfig {
     }
 }
 
-fn default_runtime_trace_mode() -> String {
-    "none".to_string()
+fn default_runtime_trace_mode() -> String {
+    "none".to_string()
+}
+
+fn default_runtime_trace_path() -> String {
+    "state/runtime-trace.jsonl".to_string()
+}
+
+fn default_runtime_trace_max_entries() -> usize {
+    200
+}
+
+// ── Hooks ────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HooksConfig {
+    /// Enable lifecycle hook execution.
+    ///
+    /// Hooks run in-process with the same privileges as the main runtime.
+    /// Keep enabled hook handlers narrowly scoped and auditable.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub builtin: BuiltinHooksConfig,
+    /// User-scriptable hooks backed by an external command or HTTP endpoint
+    /// (`[[hooks.external]]`).
+    #[serde(default)]
+    pub external: Vec<ExternalHookConfig>,
+    /// Sandboxed WASM hook plugins (`[hooks.wasm]`).
+    #[serde(default)]
+    pub wasm: WasmHooksConfig,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            builtin: BuiltinHooksConfig::default(),
+            external: Vec::new(),
+            wasm: WasmHooksConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct BuiltinHooksConfig {
+    /// Enable the command-logger hook (logs tool calls for auditing).
+    pub command_logger: bool,
+}
+
+/// What an external hook does when it errors, times out or replies with
+/// malformed JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFailurePolicy {
+    /// Log a warning and continue with the unmodified data.
+    #[default]
+    FailOpen,
+    /// Cancel the hooked operation.
+    FailClosed,
+}
+
+/// An external hook (`[[hooks.external]]`).
+///
+/// Each invocation sends `{"hook": "<point>", "payload": {...}}` as JSON,
+/// either on the stdin of `command` or as the body of a POST to `url`.
+/// Modifying hooks may reply with `{"action": "continue", "payload": {...}}`
+/// to rewrite the payload or `{"action": "cancel", "reason": "..."}`;
+/// an empty reply continues unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExternalHookConfig {
+    /// Name used in logs and cancellation messages.
+    pub name: String,
+    /// Hook points to subscribe to (e.g. `before_tool_call`, `on_message_received`).
+    pub events: Vec<String>,
+    /// Executable to run (not passed through a shell). Mutually exclusive with `url`.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments passed to `command`.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// HTTP(S) endpoint receiving a JSON POST. Mutually exclusive with `command`.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Extra request headers for `url` hooks (e.g. `Authorization`).
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Per-invocation timeout in milliseconds. Default: `5000`.
+    #[serde(default = "default_external_hook_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Behaviour on error or timeout: `fail_open` or `fail_closed`. Default: `fail_open`.
+    #[serde(default)]
+    pub failure_policy: HookFailurePolicy,
+    /// Ordering among modifying hooks; higher runs first. Default: `0`.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+fn default_external_hook_timeout_ms() -> u64 {
+    5000
+}
+
+impl ExternalHookConfig {
+    pub fn validate(&self, index: usize) -> Result<()> {
+        let at = format!("hooks.external[{index}]");
+        if self.name.trim().is_empty() {
+            anyhow::bail!("{at}.name must not be empty");
+        }
+        match (&self.command, &self.url) {
+            (Some(command), None) if !command.trim().is_empty() => {}
+            (None, Some(url)) if url.starts_with("http://") || url.starts_with("https://") => {}
+            (None, Some(_)) => anyhow::bail!("{at}.url must start with http:// or https://"),
+            _ => anyhow::bail!("{at} must set exactly one of `command` or `url`"),
+        }
+        if self.events.is_empty() {
+            anyhow::bail!("{at}.events must list at least one hook point");
+        }
+        for event in &self.events {
+            if !crate::hooks::HOOK_POINTS.contains(&event.as_str()) {
+                anyhow::bail!(
+                    "{at}.events: unknown hook point '{event}' (expected one of: {})",
+                    crate::hooks::HOOK_POINTS.join(", ")
+                );
+            }
+        }
+        if self.timeout_ms == 0 {
+            anyhow::bail!("{at}.timeout_ms must be greater than 0");
+        }
+        Ok(())
+    }
+}
+
+/// WASM hook plugins (`[hooks.wasm]`).
+///
+/// Every `<dir>/<name>.wasm` module is loaded as a hook plugin and runs in
+/// the `wasmi` interpreter with no host imports, so plugins can inspect and
+/// rewrite hook payloads but cannot touch the filesystem or network.
+/// Requires the `runtime-wasm` build feature.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WasmHooksConfig {
+    /// Load WASM hook plugins. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Plugin directory, relative to the workspace. Default: `hooks`.
+    #[serde(default = "default_wasm_hooks_dir")]
+    pub dir: String,
+    /// Fuel (instruction budget) per invocation. Default: `10000000`.
+    #[serde(default = "default_wasm_hooks_fuel_limit")]
+    pub fuel_limit: u64,
+    /// Linear memory cap per invocation in MB. Default: `16`.
+    #[serde(default = "default_wasm_hooks_memory_limit_mb")]
+    pub memory_limit_mb: u64,
+    /// Behaviour when a plugin traps, runs out of fuel or replies with
+    /// malformed JSON. Default: `fail_closed`.
+    #[serde(default = "default_wasm_hooks_failure_policy")]
+    pub failure_policy: HookFailurePolicy,
 }
 
-fn default_runtime_trace_path() -> String {
-    "state/runtime-trace.jsonl".to_string()
+fn default_wasm_hooks_dir() -> String {
+    "hooks".into()
 }
 
-fn default_runtime_trace_max_entries() -> usize {
-    200
+fn default_wasm_hooks_fuel_limit() -> u64 {
+    10_000_000
 }
 
-// ── Hooks ────────────────────────────────────────────────────────
+fn default_wasm_hooks_memory_limit_mb() -> u64 {
+    16
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
-pub struct HooksConfig {
-    /// Enable lifecycle hook execution.
-    ///
-    /// Hooks run in-process with the same privileges as the main runtime.
-    /// Keep enabled hook handlers narrowly scoped and auditable.
-    pub enabled: bool,
-    #[serde(default)]
-    pub builtin: BuiltinHooksConfig,
+fn default_wasm_hooks_failure_policy() -> HookFailurePolicy {
+    HookFailurePolicy::FailClosed
 }
 
-impl Default for HooksConfig {
+impl Default for WasmHooksConfig {
     fn default() -> Self {
         Self {
-            enabled: true,
-            builtin: BuiltinHooksConfig::default(),
+            enabled: false,
+            dir: default_wasm_hooks_dir(),
+            fuel_limit: default_wasm_hooks_fuel_limit(),
+            memory_limit_mb: default_wasm_hooks_memory_limit_mb(),
+            failure_policy: default_wasm_hooks_failure_policy(),
         }
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
-pub struct BuiltinHooksConfig {
-    /// Enable the command-logger hook (logs tool calls for auditing).
-    pub command_logger: bool,
+impl WasmHooksConfig {
+    pub fn validate(&self) -> Result<()> {
+        let dir = Path::new(&self.dir);
+        if self.dir.trim().is_empty()
+            || dir.is_absolute()
+            || dir
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            anyhow::bail!("hooks.wasm.dir must be a workspace-relative path without '..'");
+        }
+        if self.fuel_limit == 0 {
+            anyhow::bail!("hooks.wasm.fuel_limit must be greater than 0");
+        }
+        if self.memory_limit_mb == 0 || self.memory_limit_mb > 4096 {
+            anyhow::bail!("hooks.wasm.memory_limit_mb must be between 1 and 4096");
+        }
+        Ok(())
+    }
 }
 
 // ── Autonomy / Security ──────────────────────────────────────────
@@ -2208,6 +3021,11 @@ pub struct AutonomyConfig {
     /// model in tool specs.
     #[serde(default = "default_non_cli_excluded_tools")]
     pub non_cli_excluded_tools: Vec<String>,
+
+    /// MQTT topic filters the `mqtt_publish` tool may publish to.
+    /// Supports `+`/`#` wildcards. Empty = publishing denied.
+    #[serde(default)]
+    pub allowed_mqtt_topics: Vec<String>,
 }
 
 fn default_auto_approve() -> Vec<String> {
@@ -2305,6 +3123,7 @@ impl Default for AutonomyConfig {
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
             non_cli_excluded_tools: default_non_cli_excluded_tools(),
+            allowed_mqtt_topics: Vec::new(),
         }
     }
 }
@@ -2908,6 +3727,85 @@ impl Default for CronConfig {
     }
 }
 
+// ── SOP ─────────────────────────────────────────────────────────
+
+/// How much autonomy the agent has when executing an SOP.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SopExecutionMode {
+    /// Execute all steps without human approval.
+    Auto,
+    /// Request approval before starting, then execute all steps.
+    #[default]
+    Supervised,
+    /// Request approval before each step.
+    StepByStep,
+    /// Critical/High → Auto, Normal/Low → Supervised.
+    PriorityBased,
+}
+
+impl std::fmt::Display for SopExecutionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Supervised => write!(f, "supervised"),
+            Self::StepByStep => write!(f, "step_by_step"),
+            Self::PriorityBased => write!(f, "priority_based"),
+        }
+    }
+}
+
+/// Standard operating procedure engine configuration (`[sop]` section).
+///
+/// Only used by builds with the `sop` feature; other builds accept and ignore it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SopConfig {
+    /// Load SOPs and run their triggers and tools. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directory containing SOP subdirectories. Default: `<workspace>/sops`.
+    #[serde(default)]
+    pub sops_dir: Option<String>,
+    /// Execution mode for SOPs that do not set one. Default: `supervised`.
+    #[serde(default)]
+    pub default_execution_mode: SopExecutionMode,
+    /// Maximum SOP runs active at once across all SOPs. Default: `4`.
+    #[serde(default = "default_sop_max_concurrent_total")]
+    pub max_concurrent_total: usize,
+    /// Seconds before a pending approval on a critical/high SOP is
+    /// auto-approved. `0` disables the timeout. Default: `300`.
+    #[serde(default = "default_sop_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+    /// Finished runs kept in memory for `sop_status`. Default: `100`.
+    #[serde(default = "default_sop_max_finished_runs")]
+    pub max_finished_runs: usize,
+}
+
+fn default_sop_max_concurrent_total() -> usize {
+    4
+}
+
+fn default_sop_approval_timeout_secs() -> u64 {
+    300
+}
+
+fn default_sop_max_finished_runs() -> usize {
+    100
+}
+
+impl Default for SopConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sops_dir: None,
+            default_execution_mode: SopExecutionMode::default(),
+            max_concurrent_total: default_sop_max_concurrent_total(),
+            approval_timeout_secs: default_sop_approval_timeout_secs(),
+            max_finished_runs: default_sop_max_finished_runs(),
+        }
+    }
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -2995,6 +3893,9 @@ impl<T: ChannelConfig> crate::config::traits::ConfigHandle for ConfigWrapper<T>
     fn name(&self) -> &'static str {
         T::name()
     }
+    fn key(&self) -> &'static str {
+        T::key()
+    }
     fn desc(&self) -> &'static str {
         T::desc()
     }
@@ -3032,6 +3933,8 @@ pub struct ChannelsConfig {
     pub wati: Option<WatiConfig>,
     /// Nextcloud Talk bot channel configuration.
     pub nextcloud_talk: Option<NextcloudTalkConfig>,
+    /// Microsoft Teams (Bot Framework) channel configuration.
+    pub teams: Option<TeamsConfig>,
     /// Email channel configuration.
     pub email: Option<crate::channels::email_channel::EmailConfig>,
     /// IRC channel configuration.
@@ -3047,6 +3950,8 @@ pub struct ChannelsConfig {
     pub nostr: Option<NostrConfig>,
     /// ClawdTalk voice channel configuration.
     pub clawdtalk: Option<crate::channels::clawdtalk::ClawdTalkConfig>,
+    /// MQTT chat channel and broker configuration.
+    pub mqtt: Option<MqttConfig>,
     /// Base timeout in seconds for processing a single channel message (LLM + tools).
     /// Runtime uses this as a per-turn budget that scales with tool-loop depth
     /// (up to 4x, capped) so one slow/retried model call does not consume the
@@ -3105,6 +4010,10 @@ impl ChannelsConfig {
                 Box::new(ConfigWrapper::new(self.nextcloud_talk.as_ref())),
                 self.nextcloud_talk.is_some(),
             ),
+            (
+                Box::new(ConfigWrapper::new(self.teams.as_ref())),
+                self.teams.is_some(),
+            ),
             (
                 Box::new(ConfigWrapper::new(self.email.as_ref())),
                 self.email.is_some(),
@@ -3139,6 +4048,10 @@ impl ChannelsConfig {
                 Box::new(ConfigWrapper::new(self.clawdtalk.as_ref())),
                 self.clawdtalk.is_some(),
             ),
+            (
+                Box::new(ConfigWrapper::new(self.mqtt.as_ref())),
+                self.mqtt.is_some(),
+            ),
         ]
     }
 
@@ -3172,6 +4085,7 @@ impl Default for ChannelsConfig {
             linq: None,
             wati: None,
             nextcloud_talk: None,
+            teams: None,
             email: None,
             irc: None,
             lark: None,
@@ -3180,6 +4094,7 @@ impl Default for ChannelsConfig {
             qq: None,
             nostr: None,
             clawdtalk: None,
+            mqtt: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
         }
     }
@@ -3227,6 +4142,9 @@ impl ChannelConfig for TelegramConfig {
     fn name() -> &'static str {
         "Telegram"
     }
+    fn key() -> &'static str {
+        "telegram"
+    }
     fn desc() -> &'static str {
         "connect your bot"
     }
@@ -3256,6 +4174,9 @@ impl ChannelConfig for DiscordConfig {
     fn name() -> &'static str {
         "Discord"
     }
+    fn key() -> &'static str {
+        "discord"
+    }
     fn desc() -> &'static str {
         "connect your bot"
     }
@@ -3280,6 +4201,9 @@ impl ChannelConfig for SlackConfig {
     fn name() -> &'static str {
         "Slack"
     }
+    fn key() -> &'static str {
+        "slack"
+    }
     fn desc() -> &'static str {
         "connect your bot"
     }
@@ -3311,6 +4235,9 @@ impl ChannelConfig for MattermostConfig {
     fn name() -> &'static str {
         "Mattermost"
     }
+    fn key() -> &'static str {
+        "mattermost"
+    }
     fn desc() -> &'static str {
         "connect to your bot"
     }
@@ -3329,6 +4256,9 @@ impl ChannelConfig for WebhookConfig {
     fn name() -> &'static str {
         "Webhook"
     }
+    fn key() -> &'static str {
+        "webhook"
+    }
     fn desc() -> &'static str {
         "HTTP endpoint"
     }
@@ -3345,6 +4275,9 @@ impl ChannelConfig for IMessageConfig {
     fn name() -> &'static str {
         "iMessage"
     }
+    fn key() -> &'static str {
+        "imessage"
+    }
     fn desc() -> &'static str {
         "macOS only"
     }
@@ -3376,6 +4309,9 @@ impl ChannelConfig for MatrixConfig {
     fn name() -> &'static str {
         "Matrix"
     }
+    fn key() -> &'static str {
+        "matrix"
+    }
     fn desc() -> &'static str {
         "self-hosted chat"
     }
@@ -3408,6 +4344,9 @@ impl ChannelConfig for SignalConfig {
     fn name() -> &'static str {
         "Signal"
     }
+    fn key() -> &'static str {
+        "signal"
+    }
     fn desc() -> &'static str {
         "An open-source, encrypted messaging service"
     }
@@ -3455,6 +4394,9 @@ impl ChannelConfig for WhatsAppConfig {
     fn name() -> &'static str {
         "WhatsApp"
     }
+    fn key() -> &'static str {
+        "whatsapp"
+    }
     fn desc() -> &'static str {
         "Business Cloud API"
     }
@@ -3478,6 +4420,9 @@ impl ChannelConfig for LinqConfig {
     fn name() -> &'static str {
         "Linq"
     }
+    fn key() -> &'static str {
+        "linq"
+    }
     fn desc() -> &'static str {
         "iMessage/RCS/SMS via Linq API"
     }
@@ -3507,6 +4452,9 @@ impl ChannelConfig for WatiConfig {
     fn name() -> &'static str {
         "WATI"
     }
+    fn key() -> &'static str {
+        "wati"
+    }
     fn desc() -> &'static str {
         "WhatsApp via WATI Business API"
     }
@@ -3533,11 +4481,70 @@ impl ChannelConfig for NextcloudTalkConfig {
     fn name() -> &'static str {
         "NextCloud Talk"
     }
+    fn key() -> &'static str {
+        "nextcloud_talk"
+    }
     fn desc() -> &'static str {
         "NextCloud Talk platform"
     }
 }
 
+/// Microsoft Teams bot configuration (Bot Framework webhook receive +
+/// Connector API send).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TeamsConfig {
+    /// Microsoft App ID of the Azure Bot registration. Incoming tokens must be
+    /// issued for this audience.
+    pub app_id: String,
+    /// Client secret of the bot's app registration.
+    pub app_password: String,
+    /// Tenant for single-tenant bots. Unset uses the multi-tenant
+    /// `botframework.com` token authority.
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+    /// Allowed Teams users by Entra object ID or Bot Framework user ID
+    /// (`[]` = deny all, `"*"` = allow all).
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// OpenID metadata used to fetch the Bot Framework signing keys.
+    #[serde(default = "default_teams_openid_metadata_url")]
+    pub openid_metadata_url: String,
+    /// OAuth token endpoint for outbound calls. Unset derives it from `tenant_id`.
+    #[serde(default)]
+    pub token_url: Option<String>,
+    /// Connector service URL for proactive messages (cron delivery) to
+    /// conversations that have not messaged the bot since startup.
+    #[serde(default = "default_teams_service_url")]
+    pub service_url: String,
+    /// Seconds to wait for an approval card decision before denying.
+    #[serde(default = "default_teams_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+}
+
+impl ChannelConfig for TeamsConfig {
+    fn name() -> &'static str {
+        "Microsoft Teams"
+    }
+    fn key() -> &'static str {
+        "teams"
+    }
+    fn desc() -> &'static str {
+        "Teams via Azure Bot Service"
+    }
+}
+
+fn default_teams_openid_metadata_url() -> String {
+    "https://login.botframework.com/v1/.well-known/openidconfiguration".into()
+}
+
+fn default_teams_service_url() -> String {
+    "https://smba.trafficmanager.net/teams/".into()
+}
+
+fn default_teams_approval_timeout_secs() -> u64 {
+    300
+}
+
 impl WhatsAppConfig {
     /// Detect which backend to use based on config fields.
     /// Returns "cloud" if phone_number_id is set, "web" if session_path is set.
@@ -3602,6 +4609,9 @@ impl ChannelConfig for IrcConfig {
     fn name() -> &'static str {
         "IRC"
     }
+    fn key() -> &'static str {
+        "irc"
+    }
     fn desc() -> &'static str {
         "IRC over TLS"
     }
@@ -3660,6 +4670,9 @@ impl ChannelConfig for LarkConfig {
     fn name() -> &'static str {
         "Lark"
     }
+    fn key() -> &'static str {
+        "lark"
+    }
     fn desc() -> &'static str {
         "Lark Bot"
     }
@@ -3694,6 +4707,9 @@ impl ChannelConfig for FeishuConfig {
     fn name() -> &'static str {
         "Feishu"
     }
+    fn key() -> &'static str {
+        "feishu"
+    }
     fn desc() -> &'static str {
         "Feishu Bot"
     }
@@ -4127,6 +5143,9 @@ impl ChannelConfig for DingTalkConfig {
     fn name() -> &'static str {
         "DingTalk"
     }
+    fn key() -> &'static str {
+        "dingtalk"
+    }
     fn desc() -> &'static str {
         "DingTalk Stream Mode"
     }
@@ -4160,6 +5179,9 @@ impl ChannelConfig for QQConfig {
     fn name() -> &'static str {
         "QQ Official"
     }
+    fn key() -> &'static str {
+        "qq"
+    }
     fn desc() -> &'static str {
         "Tencent QQ Bot"
     }
@@ -4182,6 +5204,9 @@ impl ChannelConfig for NostrConfig {
     fn name() -> &'static str {
         "Nostr"
     }
+    fn key() -> &'static str {
+        "nostr"
+    }
     fn desc() -> &'static str {
         "Nostr DMs"
     }
@@ -4196,6 +5221,121 @@ pub fn default_nostr_relays() -> Vec<String> {
     ]
 }
 
+/// MQTT broker configuration.
+///
+/// One broker connection backs the MQTT chat channel (request/reply topic
+/// pairs), the `mqtt_publish` tool and the SOP topic listener.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttConfig {
+    /// Broker URL: `mqtt://host:port` or `mqtts://host:port`
+    pub broker_url: String,
+    /// MQTT client identifier (must be unique per broker)
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    /// Topic filters routed to the SOP engine (`+`/`#` wildcards allowed)
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// QoS for subscriptions and publishes: 0, 1 or 2 (default: 1)
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Broker username (optional)
+    pub username: Option<String>,
+    /// Broker password (optional)
+    pub password: Option<String>,
+    /// Use TLS transport; must match the `mqtts://` scheme
+    #[serde(default)]
+    pub use_tls: bool,
+    /// Keep-alive interval in seconds (default: 30)
+    #[serde(default = "default_mqtt_keep_alive_secs")]
+    pub keep_alive_secs: u64,
+    /// Chat request topic template, e.g. `zeroclaw/chat/{sender}/request`.
+    /// The `{sender}` level identifies the peer. Unset disables the chat channel.
+    #[serde(default)]
+    pub request_topic: Option<String>,
+    /// Chat reply topic template, e.g. `zeroclaw/chat/{sender}/reply`.
+    #[serde(default)]
+    pub reply_topic: Option<String>,
+    /// Allowed chat senders (`{sender}` topic level). Empty = deny all, "*" = allow all
+    #[serde(default)]
+    pub allowed_senders: Vec<String>,
+    /// Set the retain flag on chat replies and tool publishes (default: false)
+    #[serde(default)]
+    pub retain: bool,
+}
+
+impl ChannelConfig for MqttConfig {
+    fn name() -> &'static str {
+        "MQTT"
+    }
+    fn key() -> &'static str {
+        "mqtt"
+    }
+    fn desc() -> &'static str {
+        "MQTT request/reply topics"
+    }
+}
+
+fn default_mqtt_client_id() -> String {
+    "zeroclaw".into()
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+fn default_mqtt_keep_alive_secs() -> u64 {
+    30
+}
+
+impl MqttConfig {
+    pub fn validate(&self) -> Result<()> {
+        let is_plain = self.broker_url.starts_with("mqtt://");
+        let is_tls = self.broker_url.starts_with("mqtts://");
+        if !is_plain && !is_tls {
+            anyhow::bail!(
+                "mqtt.broker_url must start with mqtt:// or mqtts:// (got '{}')",
+                self.broker_url
+            );
+        }
+        if self.use_tls && is_plain {
+            anyhow::bail!("mqtt.use_tls is true but broker_url uses the plaintext mqtt:// scheme");
+        }
+        if !self.use_tls && is_tls {
+            anyhow::bail!("mqtt.broker_url uses mqtts:// but use_tls is false");
+        }
+        if self.client_id.trim().is_empty() {
+            anyhow::bail!("mqtt.client_id must not be empty");
+        }
+        if self.qos > 2 {
+            anyhow::bail!("mqtt.qos must be 0, 1, or 2 (got {})", self.qos);
+        }
+        if self.topics.is_empty() && self.request_topic.is_none() {
+            anyhow::bail!("mqtt requires at least one topic or a chat request_topic");
+        }
+        if let Some(ref request_topic) = self.request_topic {
+            let sender_levels = request_topic
+                .split('/')
+                .filter(|level| *level == "{sender}")
+                .count();
+            if sender_levels != 1 {
+                anyhow::bail!(
+                    "mqtt.request_topic must contain exactly one '{{sender}}' topic level"
+                );
+            }
+            if request_topic.contains('#') {
+                anyhow::bail!("mqtt.request_topic must not contain the '#' wildcard");
+            }
+            let Some(ref reply_topic) = self.reply_topic else {
+                anyhow::bail!("mqtt.reply_topic is required when request_topic is set");
+            };
+            if reply_topic.contains('+') || reply_topic.contains('#') {
+                anyhow::bail!("mqtt.reply_topic must not contain wildcards");
+            }
+        }
+        Ok(())
+    }
+}
+
 // ── Config impl ──────────────────────────────────────────────────
 
 impl Default for Config {
@@ -4228,6 +5368,7 @@ impl Default for Config {
             embedding_routes: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            sop: SopConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -4250,6 +5391,11 @@ impl Default for Config {
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
             agents_ipc: AgentsIpcConfig::default(),
+            a2a: A2aConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            forge: ForgeConfig::default(),
+            vault: VaultConfig::default(),
+            image_gen: ImageGenConfig::default(),
             model_support_vision: None,
         }
     }
@@ -4689,6 +5835,13 @@ fn decrypt_channel_secrets(
             "config.channels_config.nextcloud_talk.webhook_secret",
         )?;
     }
+    if let Some(ref mut teams) = channels.teams {
+        decrypt_secret(
+            store,
+            &mut teams.app_password,
+            "config.channels_config.teams.app_password",
+        )?;
+    }
     if let Some(ref mut irc) = channels.irc {
         decrypt_optional_secret(
             store,
@@ -4756,6 +5909,13 @@ fn decrypt_channel_secrets(
             "config.channels_config.clawdtalk.webhook_secret",
         )?;
     }
+    if let Some(ref mut mqtt) = channels.mqtt {
+        decrypt_optional_secret(
+            store,
+            &mut mqtt.password,
+            "config.channels_config.mqtt.password",
+        )?;
+    }
     Ok(())
 }
 
@@ -4851,6 +6011,13 @@ fn encrypt_channel_secrets(
             "config.channels_config.nextcloud_talk.webhook_secret",
         )?;
     }
+    if let Some(ref mut teams) = channels.teams {
+        encrypt_secret(
+            store,
+            &mut teams.app_password,
+            "config.channels_config.teams.app_password",
+        )?;
+    }
     if let Some(ref mut irc) = channels.irc {
         encrypt_optional_secret(
             store,
@@ -4918,6 +6085,13 @@ fn encrypt_channel_secrets(
             "config.channels_config.clawdtalk.webhook_secret",
         )?;
     }
+    if let Some(ref mut mqtt) = channels.mqtt {
+        encrypt_optional_secret(
+            store,
+            &mut mqtt.password,
+            "config.channels_config.mqtt.password",
+        )?;
+    }
     Ok(())
 }
 
@@ -5079,6 +6253,20 @@ impl Config {
             for agent in config.agents.values_mut() {
                 decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
             }
+            for agent in config.a2a.agents.values_mut() {
+                decrypt_optional_secret(&store, &mut agent.token, "config.a2a.agents.*.token")?;
+            }
+            decrypt_optional_secret(
+                &store,
+                &mut config.home_assistant.token,
+                "config.home_assistant.token",
+            )?;
+            decrypt_optional_secret(&store, &mut config.forge.token, "config.forge.token")?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.image_gen.api_key,
+                "config.image_gen.api_key",
+            )?;
 
             decrypt_channel_secrets(&store, &mut config.channels_config)?;
 
@@ -5273,6 +6461,14 @@ impl Config {
         if self.gateway.host.trim().is_empty() {
             anyhow::bail!("gateway.host must not be empty");
         }
+        let mut seen_token_ids = std::collections::HashSet::new();
+        for (i, token) in self.gateway.api_tokens.iter().enumerate() {
+            token.validate(i)?;
+            if !seen_token_ids.insert(token.id.as_str()) {
+                anyhow::bail!("gateway.api_tokens[{i}].id is a duplicate ({})", token.id);
+            }
+        }
+        self.gateway.tls.validate()?;
 
         // Autonomy
         if self.autonomy.max_actions_per_hour == 0 {
@@ -5397,6 +6593,17 @@ impl Config {
             anyhow::bail!("scheduler.max_tasks must be greater than 0");
         }
 
+        // MQTT
+        if let Some(ref mqtt) = self.channels_config.mqtt {
+            mqtt.validate()?;
+        }
+
+        // Hooks
+        for (i, hook) in self.hooks.external.iter().enumerate() {
+            hook.validate(i)?;
+        }
+        self.hooks.wasm.validate()?;
+
         // Model routes
         for (i, route) in self.model_routes.iter().enumerate() {
             if route.hint.trim().is_empty() {
@@ -5881,6 +7088,24 @@ impl Config {
         for agent in config_to_save.agents.values_mut() {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
         }
+        for agent in config_to_save.a2a.agents.values_mut() {
+            encrypt_optional_secret(&store, &mut agent.token, "config.a2a.agents.*.token")?;
+        }
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.home_assistant.token,
+            "config.home_assistant.token",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.forge.token,
+            "config.forge.token",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.image_gen.api_key,
+            "config.image_gen.api_key",
+        )?;
 
         encrypt_channel_secrets(&store, &mut config_to_save.channels_config)?;
 
@@ -6221,6 +7446,111 @@ allowed_roots = []
             .contains("autonomy.non_cli_excluded_tools contains duplicate entry"));
     }
 
+    #[test]
+    async fn gateway_tls_defaults_and_validation() {
+        let parsed: GatewayConfig = toml::from_str(
+            r#"
+[tls]
+enabled = true
+client_ca_path = "tls/clients-ca.pem"
+"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.tls.cert_path, "tls/gateway.crt");
+        assert!(parsed.tls.self_signed);
+        assert_eq!(parsed.tls.reload_interval_secs, 300);
+        assert!(parsed.tls.requires_client_cert());
+
+        let mut config = Config::default();
+        config.gateway = parsed;
+        config.validate().unwrap();
+
+        config.gateway.tls.client_ca_path = Some("  ".into());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("client_ca_path"), "{err}");
+
+        config.gateway.tls.enabled = false;
+        config.validate().unwrap();
+        assert!(!config.gateway.tls.requires_client_cert());
+    }
+
+    #[test]
+    async fn gateway_api_tokens_parse_and_validate() {
+        let parsed: GatewayConfig = toml::from_str(
+            r#"
+[[api_tokens]]
+id = "tok_0123456789ab"
+label = "grafana"
+hash = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+scopes = ["read"]
+created_at = "2026-01-01T00:00:00Z"
+expires_at = "2026-04-01T00:00:00Z"
+"#,
+        )
+        .unwrap();
+        let token = &parsed.api_tokens[0];
+        assert_eq!(token.scopes, vec![TokenScope::Read]);
+        assert!(token.revoked_at.is_none());
+        assert!(token.is_active("2026-03-01T00:00:00Z".parse().unwrap()));
+        assert!(!token.is_active("2026-05-01T00:00:00Z".parse().unwrap()));
+
+        let mut config = Config::default();
+        config.gateway = parsed;
+        config.validate().unwrap();
+
+        config
+            .gateway
+            .api_tokens
+            .push(config.gateway.api_tokens[0].clone());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("is a duplicate"), "{err}");
+
+        config.gateway.api_tokens.pop();
+        config.gateway.api_tokens[0].hash = "zc_plaintext".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("SHA-256 hex digest"), "{err}");
+    }
+
+    #[test]
+    async fn external_hooks_parse_and_validate() {
+        let parsed: HooksConfig = toml::from_str(
+            r#"
+enabled = true
+
+[[external]]
+name = "guard"
+events = ["before_tool_call"]
+command = "/usr/local/bin/guard"
+failure_policy = "fail_closed"
+"#,
+        )
+        .unwrap();
+        let hook = &parsed.external[0];
+        assert_eq!(hook.timeout_ms, 5000);
+        assert_eq!(hook.failure_policy, HookFailurePolicy::FailClosed);
+
+        let mut config = Config::default();
+        config.hooks = parsed;
+        config.validate().unwrap();
+
+        config.hooks.external[0].url = Some("https://hooks.example.com".into());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("exactly one of `command` or `url`"), "{err}");
+
+        config.hooks.external[0].url = None;
+        config.hooks.external[0].events = vec!["before_everything".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("unknown hook point 'before_everything'"),
+            "{err}"
+        );
+
+        config.hooks.external.clear();
+        config.hooks.wasm.dir = "../plugins".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("hooks.wasm.dir"), "{err}");
+    }
+
     #[test]
     async fn runtime_config_default() {
         let r = RuntimeConfig::default();
@@ -6369,6 +7699,7 @@ default_temperature = 0.7
                 always_ask: vec![],
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
+                allowed_mqtt_topics: vec![],
             },
             security: SecurityConfig::default(),
             runtime: RuntimeConfig {
@@ -6390,6 +7721,7 @@ default_temperature = 0.7
                 to: Some("123456".into()),
             },
             cron: CronConfig::default(),
+            sop: SopConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
                 telegram: Some(TelegramConfig {
@@ -6411,6 +7743,7 @@ default_temperature = 0.7
                 linq: None,
                 wati: None,
                 nextcloud_talk: None,
+                teams: None,
                 email: None,
                 irc: None,
                 lark: None,
@@ -6419,6 +7752,7 @@ default_temperature = 0.7
                 qq: None,
                 nostr: None,
                 clawdtalk: None,
+                mqtt: None,
                 message_timeout_secs: 300,
             },
             memory: MemoryConfig::default(),
@@ -6442,6 +7776,11 @@ default_temperature = 0.7
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
             agents_ipc: AgentsIpcConfig::default(),
+            a2a: A2aConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            forge: ForgeConfig::default(),
+            vault: VaultConfig::default(),
+            image_gen: ImageGenConfig::default(),
             model_support_vision: None,
         };
 
@@ -6772,6 +8111,7 @@ tool_dispatcher = "xml"
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            sop: SopConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -6794,6 +8134,11 @@ tool_dispatcher = "xml"
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
             agents_ipc: AgentsIpcConfig::default(),
+            a2a: A2aConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            forge: ForgeConfig::default(),
+            vault: VaultConfig::default(),
+            image_gen: ImageGenConfig::default(),
             model_support_vision: None,
         };
 
@@ -7182,6 +8527,7 @@ allowed_users = ["@ops:matrix.org"]
             linq: None,
             wati: None,
             nextcloud_talk: None,
+            teams: None,
             email: None,
             irc: None,
             lark: None,
@@ -7190,6 +8536,7 @@ allowed_users = ["@ops:matrix.org"]
             qq: None,
             nostr: None,
             clawdtalk: None,
+            mqtt: None,
             message_timeout_secs: 300,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
@@ -7396,6 +8743,7 @@ channel_id = "C123"
             linq: None,
             wati: None,
             nextcloud_talk: None,
+            teams: None,
             email: None,
             irc: None,
             lark: None,
@@ -7404,6 +8752,7 @@ channel_id = "C123"
             qq: None,
             nostr: None,
             clawdtalk: None,
+            mqtt: None,
             message_timeout_secs: 300,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
@@ -7486,6 +8835,7 @@ channel_id = "C123"
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: vec!["zc_test_token".into()],
+            api_tokens: Vec::new(),
             pair_rate_limit_per_minute: 12,
             webhook_rate_limit_per_minute: 80,
             trust_forwarded_headers: true,
@@ -7497,6 +8847,7 @@ channel_id = "C123"
                 auth_token: Some("node-token".into()),
                 allowed_node_ids: vec!["node-1".into(), "node-2".into()],
             },
+            tls: GatewayTlsConfig::default(),
         };
         let toml_str = toml::to_string(&g).unwrap();
         let parsed: GatewayConfig = toml::from_str(&toml_str).unwrap();
@@ -9081,6 +10432,21 @@ default_model = "legacy-model"
         assert!(parsed.allowed_users.is_empty());
     }
 
+    #[test]
+    async fn teams_config_defaults_optional_fields() {
+        let json = r#"{"app_id":"app-id","app_password":"secret"}"#;
+        let parsed: TeamsConfig = serde_json::from_str(json).unwrap();
+        assert!(parsed.tenant_id.is_none());
+        assert!(parsed.allowed_users.is_empty());
+        assert!(parsed.token_url.is_none());
+        assert_eq!(
+            parsed.openid_metadata_url,
+            "https://login.botframework.com/v1/.well-known/openidconfiguration"
+        );
+        assert_eq!(parsed.service_url, "https://smba.trafficmanager.net/teams/");
+        assert_eq!(parsed.approval_timeout_secs, 300);
+    }
+
     // ── Config file permission hardening (Unix only) ───────────────
 
     #[cfg(unix)]
@@ -2,6 +2,8 @@
 pub trait ChannelConfig {
     /// human-readable name
     fn name() -> &'static str;
+    /// `[channels_config.<key>]` section name, also the runtime channel name
+    fn key() -> &'static str;
     /// short description
     fn desc() -> &'static str;
 }
@@ -10,5 +12,6 @@ pub trait ChannelConfig {
 
 pub trait ConfigHandle {
     fn name(&self) -> &'static str;
+    fn key(&self) -> &'static str;
     fn desc(&self) -> &'static str;
 }
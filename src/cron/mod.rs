@@ -7,6 +7,7 @@ mod store;
 mod types;
 
 pub mod scheduler;
+pub mod triggers;
 
 #[allow(unused_imports)]
 pub use schedule::{
@@ -14,10 +15,15 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_shell_job, configure_new_job, due_jobs, get_job, jobs_generation,
+    list_jobs, list_runs, record_last_run, record_run, remove_job, reschedule_after_run,
+    skip_missed_runs, update_job,
+};
+pub use triggers::TriggerEvent;
+pub use types::{
+    CatchUpPolicy, CronJob, CronJobPatch, CronRun, DeliveryCondition, DeliveryConfig,
+    DeliveryTarget, JobType, Schedule, SessionTarget,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
@@ -37,13 +43,14 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
                     .last_run
                     .map_or_else(|| "never".into(), |d| d.to_rfc3339());
                 let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
+                let next_run = if job.schedule.is_event() {
+                    "on event".to_string()
+                } else {
+                    job.next_run.to_rfc3339()
+                };
                 println!(
                     "- {} | {:?} | next={} | last={} ({})",
-                    job.id,
-                    job.schedule,
-                    job.next_run.to_rfc3339(),
-                    last_run,
-                    last_status,
+                    job.id, job.schedule, next_run, last_run, last_status,
                 );
                 if !job.command.is_empty() {
                     println!("    cmd: {}", job.command);
@@ -1,9 +1,17 @@
 use crate::cron::Schedule;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
 use cron::Schedule as CronExprSchedule;
 use std::str::FromStr;
 
+/// `next_run` stored for event-triggered jobs so the time-based poll never
+/// picks them up.
+pub(crate) fn event_next_run() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(9999, 12, 31, 23, 59, 59)
+        .single()
+        .expect("9999-12-31 is a valid date")
+}
+
 pub fn next_run_for_schedule(schedule: &Schedule, from: DateTime<Utc>) -> Result<DateTime<Utc>> {
     match schedule {
         Schedule::Cron { expr, tz } => {
@@ -35,6 +43,9 @@ pub fn next_run_for_schedule(schedule: &Schedule, from: DateTime<Utc>) -> Result
             from.checked_add_signed(delta)
                 .ok_or_else(|| anyhow::anyhow!("every_ms overflowed DateTime"))
         }
+        Schedule::Watch { .. } | Schedule::Message { .. } | Schedule::State { .. } => {
+            Ok(event_next_run())
+        }
     }
 }
 
@@ -57,6 +68,46 @@ pub fn validate_schedule(schedule: &Schedule, now: DateTime<Utc>) -> Result<()>
             }
             Ok(())
         }
+        Schedule::Watch { path, pattern } => {
+            let relative = std::path::Path::new(path.trim());
+            if path.trim().is_empty()
+                || relative.is_absolute()
+                || relative
+                    .components()
+                    .any(|c| matches!(c, std::path::Component::ParentDir))
+            {
+                anyhow::bail!("Invalid schedule: watch path must be relative to the workspace");
+            }
+            if let Some(pattern) = pattern {
+                glob::Pattern::new(pattern)
+                    .with_context(|| format!("Invalid watch pattern: {pattern}"))?;
+            }
+            Ok(())
+        }
+        Schedule::Message {
+            sender,
+            keyword,
+            regex,
+            ..
+        } => {
+            let set = |v: &Option<String>| v.as_deref().is_some_and(|v| !v.trim().is_empty());
+            if !set(sender) && !set(keyword) && !set(regex) {
+                anyhow::bail!("Invalid schedule: message trigger needs a sender, keyword or regex");
+            }
+            if let Some(regex) = regex {
+                regex::Regex::new(regex)
+                    .with_context(|| format!("Invalid message trigger regex: {regex}"))?;
+            }
+            Ok(())
+        }
+        Schedule::State { entity_id, .. } => {
+            if entity_id.trim().is_empty() {
+                anyhow::bail!("Invalid schedule: state trigger needs an entity_id");
+            }
+            glob::Pattern::new(entity_id.trim())
+                .with_context(|| format!("Invalid state trigger entity_id: {entity_id}"))?;
+            Ok(())
+        }
     }
 }
 
@@ -85,7 +136,6 @@ pub fn normalize_expression(expression: &str) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
 
     #[test]
     fn next_run_for_schedule_supports_every_and_at() {
@@ -111,4 +161,41 @@ mod tests {
         let next = next_run_for_schedule(&schedule, from).unwrap();
         assert_eq!(next, Utc.with_ymd_and_hms(2026, 2, 16, 17, 0, 0).unwrap());
     }
+
+    #[test]
+    fn validate_schedule_checks_event_triggers() {
+        let now = Utc::now();
+        let watch = |path: &str| Schedule::Watch {
+            path: path.into(),
+            pattern: Some("*.pdf".into()),
+        };
+        assert!(validate_schedule(&watch("inbox"), now).is_ok());
+        assert!(validate_schedule(&watch("/etc"), now).is_err());
+        assert!(validate_schedule(&watch("inbox/../../etc"), now).is_err());
+
+        let message = |keyword: Option<&str>, regex: Option<&str>| Schedule::Message {
+            channel: Some("telegram".into()),
+            sender: None,
+            keyword: keyword.map(Into::into),
+            regex: regex.map(Into::into),
+        };
+        assert!(validate_schedule(&message(Some("deploy"), None), now).is_ok());
+        assert!(validate_schedule(&message(None, Some(r"^invoice #\d+")), now).is_ok());
+        assert!(validate_schedule(&message(None, None), now).is_err());
+        assert!(validate_schedule(&message(None, Some("(")), now).is_err());
+
+        let state = |entity_id: &str| Schedule::State {
+            entity_id: entity_id.into(),
+            from: None,
+            to: Some("on".into()),
+        };
+        assert!(validate_schedule(&state("binary_sensor.*_door"), now).is_ok());
+        assert!(validate_schedule(&state(" "), now).is_err());
+        assert!(validate_schedule(&state("light.[kitchen"), now).is_err());
+
+        assert_eq!(
+            next_run_for_schedule(&watch("inbox"), now).unwrap(),
+            event_next_run()
+        );
+    }
 }
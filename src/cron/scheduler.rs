@@ -1,25 +1,99 @@
-use crate::channels::{
-    Channel, DiscordChannel, MattermostChannel, SendMessage, SlackChannel, TelegramChannel,
-};
+use crate::channels::delivery::DeliveryRouter;
 use crate::config::Config;
+use crate::cron::store::truncate_cron_output;
+use crate::cron::triggers::{poll_watch_triggers, WatchState};
 use crate::cron::{
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    due_jobs, get_job, next_run_for_schedule, record_last_run, record_run, remove_job,
+    reschedule_after_run, skip_missed_runs, update_job, CatchUpPolicy, CronJob, CronJobPatch,
+    DeliveryCondition, DeliveryConfig, JobType, Schedule, SessionTarget, TriggerEvent,
 };
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use futures_util::{stream, StreamExt};
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::process::Stdio;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
 use tokio::process::Command;
 use tokio::time::{self, Duration};
 
 const MIN_POLL_SECONDS: u64 = 5;
 const SHELL_JOB_TIMEOUT_SECS: u64 = 120;
 const SCHEDULER_COMPONENT: &str = "scheduler";
+/// Upper bound on runs replayed for one job under [`CatchUpPolicy::All`].
+const MAX_CATCH_UP_RUNS: usize = 100;
+/// Longest `on_success`/`on_failure` chain followed after one run.
+const MAX_CHAIN_DEPTH: usize = 10;
+
+/// In-flight run count per job id, shared by scheduler ticks and manual runs.
+fn running_jobs() -> &'static Mutex<HashMap<String, u32>> {
+    static RUNNING: OnceLock<Mutex<HashMap<String, u32>>> = OnceLock::new();
+    RUNNING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Holds one of a job's `max_concurrent` run slots until dropped.
+struct RunSlot {
+    job_id: String,
+}
+
+impl RunSlot {
+    fn acquire(job: &CronJob) -> Option<Self> {
+        let mut running = running_jobs().lock();
+        let count = running.entry(job.id.clone()).or_insert(0);
+        if *count >= job.max_concurrent.max(1) {
+            return None;
+        }
+        *count += 1;
+        Some(Self {
+            job_id: job.id.clone(),
+        })
+    }
+}
+
+impl Drop for RunSlot {
+    fn drop(&mut self) {
+        let mut running = running_jobs().lock();
+        if let Some(count) = running.get_mut(&self.job_id) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                running.remove(&self.job_id);
+            }
+        }
+    }
+}
+
+/// Set while a scheduled memory consolidation pass is in flight.
+static CONSOLIDATION_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Start a background memory consolidation pass when one is due and none is
+/// running. Consolidation calls the provider, so it must not hold up the tick.
+fn spawn_memory_consolidation_if_due(config: &Config) {
+    if !crate::memory::consolidation::is_due(config)
+        || CONSOLIDATION_RUNNING.swap(true, Ordering::AcqRel)
+    {
+        return;
+    }
+    let config = config.clone();
+    tokio::spawn(async move {
+        if let Err(e) = crate::memory::consolidation::run_if_due(&config).await {
+            tracing::warn!("Memory consolidation failed: {e}");
+        }
+        CONSOLIDATION_RUNNING.store(false, Ordering::Release);
+    });
+}
 
-pub async fn run(config: Config) -> Result<()> {
+fn already_running_message(job: &CronJob) -> String {
+    format!(
+        "skipped: job '{}' already has {} run(s) in progress (max_concurrent)",
+        job.id, job.max_concurrent
+    )
+}
+
+/// Poll for due jobs until the task is aborted. `delivery` is the daemon's
+/// shared router for announce-mode output.
+pub async fn run(config: Config, delivery: Arc<DeliveryRouter>) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
     let mut interval = time::interval(Duration::from_secs(poll_secs));
     interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
@@ -28,29 +102,48 @@ pub async fn run(config: Config) -> Result<()> {
         &config.workspace_dir,
     ));
 
-    crate::health::mark_component_ok(SCHEDULER_COMPONENT);
+    crate::health::mark_component_ok(SCHEDULER_COMPONENT).await;
+    let mut watch_state = WatchState::default();
+
+    // SOP cron triggers ride on the scheduler tick when the daemon has
+    // loaded an SOP engine.
+    #[cfg(feature = "sop")]
+    let mut sop = crate::sop::SopRuntime::shared().map(|runtime| {
+        let cache = crate::sop::dispatch::SopCronCache::from_engine(&runtime.engine);
+        (runtime, cache, Utc::now())
+    });
 
     loop {
         interval.tick().await;
         // Keep scheduler liveness fresh even when there are no due jobs.
-        crate::health::mark_component_ok(SCHEDULER_COMPONENT);
+        crate::health::mark_component_ok(SCHEDULER_COMPONENT).await;
+
+        #[cfg(feature = "sop")]
+        if let Some((runtime, cache, last_check)) = sop.as_mut() {
+            runtime.poll(cache, last_check).await;
+        }
 
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
-                crate::health::mark_component_error(SCHEDULER_COMPONENT, e.to_string());
+                crate::health::mark_component_error(SCHEDULER_COMPONENT, e.to_string()).await;
                 tracing::warn!("Scheduler query failed: {e}");
                 continue;
             }
         };
 
-        process_due_jobs(&config, &security, jobs, SCHEDULER_COMPONENT).await;
+        process_due_jobs(&config, &security, &delivery, jobs, SCHEDULER_COMPONENT).await;
+        poll_watch_triggers(&config, &security, &delivery, &mut watch_state).await;
+        spawn_memory_consolidation_if_due(&config);
     }
 }
 
 pub async fn execute_job_now(config: &Config, job: &CronJob) -> (bool, String) {
+    let Some(_slot) = RunSlot::acquire(job) else {
+        return (false, already_running_message(job));
+    };
     let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
-    execute_job_with_retry(config, &security, job).await
+    Box::pin(execute_job_with_retry(config, &security, job)).await
 }
 
 async fn execute_job_with_retry(
@@ -91,11 +184,12 @@ async fn execute_job_with_retry(
 async fn process_due_jobs(
     config: &Config,
     security: &Arc<SecurityPolicy>,
+    delivery: &Arc<DeliveryRouter>,
     jobs: Vec<CronJob>,
     component: &str,
 ) {
     // Refresh scheduler health on every successful poll cycle, including idle cycles.
-    crate::health::mark_component_ok(component);
+    crate::health::mark_component_ok(component).await;
 
     let max_concurrent = config.scheduler.max_concurrent.max(1);
     let mut in_flight =
@@ -103,9 +197,10 @@ async fn process_due_jobs(
             jobs.into_iter().map(|job| {
                 let config = config.clone();
                 let security = Arc::clone(security);
+                let delivery = Arc::clone(delivery);
                 let component = component.to_owned();
                 async move {
-                    execute_and_persist_job(&config, security.as_ref(), &job, &component).await
+                    execute_and_persist_job(&config, &security, &delivery, &job, &component).await
                 }
             }),
         )
@@ -121,18 +216,181 @@ async fn process_due_jobs(
 async fn execute_and_persist_job(
     config: &Config,
     security: &SecurityPolicy,
+    delivery: &DeliveryRouter,
     job: &CronJob,
     component: &str,
 ) -> (String, bool, String) {
-    crate::health::mark_component_ok(component);
+    crate::health::mark_component_ok(component).await;
     warn_if_high_frequency_agent_job(job);
 
+    let Some(_slot) = RunSlot::acquire(job) else {
+        // Leave next_run untouched so the job is picked up again once a slot frees.
+        tracing::debug!("{}", already_running_message(job));
+        return (job.id.clone(), true, already_running_message(job));
+    };
+
+    let now = Utc::now();
+    let runs = match missed_runs(config, job, now) {
+        Some(missed) => {
+            tracing::info!(
+                "Cron job '{}' missed {missed} run(s) while the scheduler was down (catch_up = {})",
+                job.id,
+                job.catch_up.as_str()
+            );
+            match job.catch_up {
+                CatchUpPolicy::Skip => {
+                    if let Err(e) = skip_missed_runs(config, job, now) {
+                        tracing::warn!("Failed to skip missed runs for cron job '{}': {e}", job.id);
+                    }
+                    return (job.id.clone(), true, "skipped missed runs".to_string());
+                }
+                CatchUpPolicy::Once => 1,
+                CatchUpPolicy::All => missed,
+            }
+        }
+        None => 1,
+    };
+
+    let mut job = job.clone();
+    let mut result = (true, String::new());
+    for _ in 0..runs {
+        let (success, output) = Box::pin(run_and_persist(config, security, delivery, &job)).await;
+        job.last_output = Some(truncate_cron_output(&output));
+        result = (success, output);
+    }
+
+    (job.id, result.0, result.1)
+}
+
+/// Run an event-triggered job once with `event` templated into its prompt.
+pub(crate) async fn run_triggered_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    delivery: &DeliveryRouter,
+    job: &CronJob,
+    event: &TriggerEvent,
+) -> (bool, String) {
+    let Some(_slot) = RunSlot::acquire(job) else {
+        return (false, already_running_message(job));
+    };
+    let mut job = job.clone();
+    job.prompt = Some(event.render(job.prompt.as_deref().unwrap_or_default()));
+    Box::pin(run_and_persist(config, security, delivery, &job)).await
+}
+
+/// One run: execute with retries, record the result, then follow the chain.
+async fn run_and_persist(
+    config: &Config,
+    security: &SecurityPolicy,
+    delivery: &DeliveryRouter,
+    job: &CronJob,
+) -> (bool, String) {
     let started_at = Utc::now();
-    let (success, output) = execute_job_with_retry(config, security, job).await;
+    let (success, output) = Box::pin(execute_job_with_retry(config, security, job)).await;
     let finished_at = Utc::now();
-    let success = persist_job_result(config, job, success, &output, started_at, finished_at).await;
+    let success = persist_job_result(
+        config,
+        delivery,
+        job,
+        success,
+        &output,
+        started_at,
+        finished_at,
+    )
+    .await;
+    Box::pin(run_follow_ups(config, security, delivery, job, success)).await;
+    (success, output)
+}
+
+/// Number of fire times missed by a job that is due later than one poll
+/// interval allows, or `None` when it is on time. One-shot jobs always run.
+fn missed_runs(config: &Config, job: &CronJob, now: DateTime<Utc>) -> Option<usize> {
+    if matches!(job.schedule, Schedule::At { .. }) {
+        return None;
+    }
+    let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
+    let grace = chrono::Duration::seconds(i64::try_from(poll_secs * 2).unwrap_or(i64::MAX));
+    if now - job.next_run <= grace {
+        return None;
+    }
+
+    let mut missed = 1;
+    let mut fire_time = job.next_run;
+    while missed < MAX_CATCH_UP_RUNS {
+        match next_run_for_schedule(&job.schedule, fire_time) {
+            Ok(next) if next <= now => {
+                missed += 1;
+                fire_time = next;
+            }
+            _ => break,
+        }
+    }
+    Some(missed)
+}
+
+/// Run the `on_success`/`on_failure` chain that starts at `job`. Follow-up
+/// jobs run even when disabled; cycles and over-long chains are cut off.
+async fn run_follow_ups(
+    config: &Config,
+    security: &SecurityPolicy,
+    delivery: &DeliveryRouter,
+    job: &CronJob,
+    success: bool,
+) {
+    let mut visited = vec![job.id.clone()];
+    let mut next = follow_up_id(job, success);
+
+    while let Some(id) = next {
+        if visited.contains(&id) || visited.len() > MAX_CHAIN_DEPTH {
+            tracing::warn!(
+                "Cron chain from '{}' stopped at '{id}' (cycle or more than {MAX_CHAIN_DEPTH} jobs)",
+                job.id
+            );
+            return;
+        }
+        let follow_up = match get_job(config, &id) {
+            Ok(follow_up) => follow_up,
+            Err(e) => {
+                tracing::warn!(
+                    "Cron follow-up job '{id}' of '{}' failed to load: {e}",
+                    job.id
+                );
+                return;
+            }
+        };
+        visited.push(id);
 
-    (job.id.clone(), success, output)
+        let Some(_slot) = RunSlot::acquire(&follow_up) else {
+            tracing::warn!("Cron follow-up {}", already_running_message(&follow_up));
+            return;
+        };
+        let started_at = Utc::now();
+        let (success, output) =
+            Box::pin(execute_job_with_retry(config, security, &follow_up)).await;
+        let finished_at = Utc::now();
+        let success = persist_job_result(
+            config,
+            delivery,
+            &follow_up,
+            success,
+            &output,
+            started_at,
+            finished_at,
+        )
+        .await;
+        if !success {
+            tracing::warn!("Cron follow-up job '{}' failed: {output}", follow_up.id);
+        }
+        next = follow_up_id(&follow_up, success);
+    }
+}
+
+fn follow_up_id(job: &CronJob, success: bool) -> Option<String> {
+    if success {
+        job.on_success.clone()
+    } else {
+        job.on_failure.clone()
+    }
 }
 
 async fn run_agent_job(
@@ -195,6 +453,7 @@ async fn run_agent_job(
 
 async fn persist_job_result(
     config: &Config,
+    delivery: &DeliveryRouter,
     job: &CronJob,
     mut success: bool,
     output: &str,
@@ -203,7 +462,8 @@ async fn persist_job_result(
 ) -> bool {
     let duration_ms = (finished_at - started_at).num_milliseconds();
 
-    if let Err(e) = deliver_if_configured(config, job, output).await {
+    let duration = Duration::from_millis(u64::try_from(duration_ms).unwrap_or(0));
+    if let Err(e) = deliver_if_configured(delivery, job, success, output, duration).await {
         if job.delivery.best_effort {
             tracing::warn!("Cron delivery failed (best_effort): {e}");
         } else {
@@ -270,7 +530,10 @@ fn warn_if_high_frequency_agent_job(job: &CronJob) {
                 _ => false,
             }
         }
-        Schedule::At { .. } => false,
+        Schedule::At { .. }
+        | Schedule::Watch { .. }
+        | Schedule::Message { .. }
+        | Schedule::State { .. } => false,
     };
 
     if too_frequent {
@@ -281,92 +544,72 @@ fn warn_if_high_frequency_agent_job(job: &CronJob) {
     }
 }
 
-async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) -> Result<()> {
+async fn deliver_if_configured(
+    router: &DeliveryRouter,
+    job: &CronJob,
+    success: bool,
+    output: &str,
+    duration: Duration,
+) -> Result<()> {
     let delivery: &DeliveryConfig = &job.delivery;
     if !delivery.mode.eq_ignore_ascii_case("announce") {
         return Ok(());
     }
+    let skip = match delivery.when {
+        DeliveryCondition::Always => false,
+        DeliveryCondition::OnFailure => success,
+        DeliveryCondition::OnChange => {
+            job.last_output.as_deref() == Some(truncate_cron_output(output).as_str())
+        }
+    };
+    if skip {
+        return Ok(());
+    }
 
-    let channel = delivery
-        .channel
-        .as_deref()
-        .ok_or_else(|| anyhow::anyhow!("delivery.channel is required for announce mode"))?;
-    let target = delivery
-        .to
-        .as_deref()
-        .ok_or_else(|| anyhow::anyhow!("delivery.to is required for announce mode"))?;
+    let targets = delivery.all_targets();
+    if targets.is_empty() {
+        if delivery.channel.is_none() {
+            anyhow::bail!("delivery.channel is required for announce mode");
+        }
+        anyhow::bail!("delivery.to is required for announce mode");
+    }
 
-    deliver_announcement(config, channel, target, output).await
+    let message =
+        render_delivery_message(job, delivery.template.as_deref(), success, output, duration);
+    let job_name = job.name.as_deref().unwrap_or(&job.id);
+    router
+        .deliver(&targets, &message, Some(job_name), delivery.max_retries)
+        .await
 }
 
-pub(crate) async fn deliver_announcement(
-    config: &Config,
-    channel: &str,
-    target: &str,
+/// Fill a delivery template, or fall back to the raw output.
+fn render_delivery_message(
+    job: &CronJob,
+    template: Option<&str>,
+    success: bool,
     output: &str,
-) -> Result<()> {
-    match channel.to_ascii_lowercase().as_str() {
-        "telegram" => {
-            let tg = config
-                .channels_config
-                .telegram
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("telegram channel not configured"))?;
-            let channel = TelegramChannel::new(
-                tg.bot_token.clone(),
-                tg.allowed_users.clone(),
-                tg.mention_only,
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
-        }
-        "discord" => {
-            let dc = config
-                .channels_config
-                .discord
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("discord channel not configured"))?;
-            let channel = DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
-        }
-        "slack" => {
-            let sl = config
-                .channels_config
-                .slack
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("slack channel not configured"))?;
-            let channel = SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
-        }
-        "mattermost" => {
-            let mm = config
-                .channels_config
-                .mattermost
-                .as_ref()
-                .ok_or_else(|| anyhow::anyhow!("mattermost channel not configured"))?;
-            let channel = MattermostChannel::new(
-                mm.url.clone(),
-                mm.bot_token.clone(),
-                mm.channel_id.clone(),
-                mm.allowed_users.clone(),
-                mm.thread_replies.unwrap_or(true),
-                mm.mention_only.unwrap_or(false),
-            );
-            channel.send(&SendMessage::new(output, target)).await?;
-        }
-        other => anyhow::bail!("unsupported delivery channel: {other}"),
-    }
+    duration: Duration,
+) -> String {
+    let Some(template) = template.filter(|template| !template.trim().is_empty()) else {
+        return output.to_string();
+    };
+    template
+        .replace("{job_name}", job.name.as_deref().unwrap_or(&job.id))
+        .replace("{job_id}", &job.id)
+        .replace("{status}", if success { "ok" } else { "error" })
+        .replace("{duration}", &format_duration(duration))
+        .replace("{output}", output)
+}
 
-    Ok(())
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 {
+        format!("{}m{}s", secs / 60, secs % 60)
+    } else if secs > 0 {
+        format!("{}.{}s", secs, duration.subsec_millis() / 100)
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
 }
 
 async fn run_job_command(
@@ -482,6 +725,10 @@ mod tests {
         config
     }
 
+    fn test_router(config: &Config) -> Arc<DeliveryRouter> {
+        Arc::new(DeliveryRouter::from_config(config))
+    }
+
     fn test_job(command: &str) -> CronJob {
         CronJob {
             id: "test-job".into(),
@@ -499,6 +746,10 @@ mod tests {
             enabled: true,
             delivery: DeliveryConfig::default(),
             delete_after_run: false,
+            on_success: None,
+            on_failure: None,
+            catch_up: crate::cron::CatchUpPolicy::Once,
+            max_concurrent: 1,
             created_at: Utc::now(),
             next_run: Utc::now(),
             last_run: None,
@@ -760,10 +1011,17 @@ mod tests {
         ));
         let component = unique_component("scheduler-idle");
 
-        crate::health::mark_component_error(&component, "pre-existing error");
-        process_due_jobs(&config, &security, Vec::new(), &component).await;
+        crate::health::mark_component_error(&component, "pre-existing error").await;
+        process_due_jobs(
+            &config,
+            &security,
+            &test_router(&config),
+            Vec::new(),
+            &component,
+        )
+        .await;
 
-        let snapshot = crate::health::snapshot_json();
+        let snapshot = crate::health::snapshot_json().await;
         let entry = &snapshot["components"][component.as_str()];
         assert_eq!(entry["status"], "ok");
         assert!(entry["last_ok"].as_str().is_some());
@@ -781,10 +1039,17 @@ mod tests {
         ));
         let component = unique_component("scheduler-fail");
 
-        crate::health::mark_component_ok(&component);
-        process_due_jobs(&config, &security, vec![job], &component).await;
+        crate::health::mark_component_ok(&component).await;
+        process_due_jobs(
+            &config,
+            &security,
+            &test_router(&config),
+            vec![job],
+            &component,
+        )
+        .await;
 
-        let snapshot = crate::health::snapshot_json();
+        let snapshot = crate::health::snapshot_json().await;
         let entry = &snapshot["components"][component.as_str()];
         assert_eq!(entry["status"], "ok");
     }
@@ -797,7 +1062,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            true,
+            "ok",
+            started,
+            finished,
+        )
+        .await;
         assert!(success);
 
         let runs = cron::list_runs(&config, &job.id, 10).unwrap();
@@ -825,7 +1099,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            true,
+            "ok",
+            started,
+            finished,
+        )
+        .await;
         assert!(success);
         let lookup = cron::get_job(&config, &job.id);
         assert!(lookup.is_err());
@@ -850,7 +1133,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, false, "boom", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            false,
+            "boom",
+            started,
+            finished,
+        )
+        .await;
         assert!(!success);
         let updated = cron::get_job(&config, &job.id).unwrap();
         assert!(!updated.enabled);
@@ -867,7 +1159,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            true,
+            "ok",
+            started,
+            finished,
+        )
+        .await;
         assert!(success);
         let lookup = cron::get_job(&config, &job.id);
         assert!(lookup.is_err());
@@ -883,7 +1184,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, false, "boom", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            false,
+            "boom",
+            started,
+            finished,
+        )
+        .await;
         assert!(!success);
         let updated = cron::get_job(&config, &job.id).unwrap();
         assert!(!updated.enabled);
@@ -909,6 +1219,7 @@ mod tests {
                 channel: Some("telegram".into()),
                 to: Some("123456".into()),
                 best_effort: false,
+                ..DeliveryConfig::default()
             }),
             false,
         )
@@ -916,7 +1227,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            true,
+            "ok",
+            started,
+            finished,
+        )
+        .await;
         assert!(!success);
 
         let updated = cron::get_job(&config, &job.id).unwrap();
@@ -947,6 +1267,7 @@ mod tests {
                 channel: Some("telegram".into()),
                 to: Some("123456".into()),
                 best_effort: true,
+                ..DeliveryConfig::default()
             }),
             false,
         )
@@ -954,7 +1275,16 @@ mod tests {
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
 
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            true,
+            "ok",
+            started,
+            finished,
+        )
+        .await;
         assert!(success);
 
         let updated = cron::get_job(&config, &job.id).unwrap();
@@ -986,7 +1316,16 @@ mod tests {
 
         let started = Utc::now();
         let finished = started + ChronoDuration::milliseconds(10);
-        let success = persist_job_result(&config, &job, true, "ok", started, finished).await;
+        let success = persist_job_result(
+            &config,
+            &test_router(&config),
+            &job,
+            true,
+            "ok",
+            started,
+            finished,
+        )
+        .await;
         assert!(success);
 
         let updated = cron::get_job(&config, &job.id).unwrap();
@@ -1000,15 +1339,291 @@ mod tests {
         let config = test_config(&tmp).await;
         let mut job = test_job("echo ok");
 
-        assert!(deliver_if_configured(&config, &job, "x").await.is_ok());
+        assert!(
+            deliver_if_configured(&test_router(&config), &job, true, "x", Duration::ZERO)
+                .await
+                .is_ok()
+        );
 
         job.delivery = DeliveryConfig {
             mode: "announce".into(),
             channel: Some("invalid".into()),
             to: Some("target".into()),
             best_effort: true,
+            ..DeliveryConfig::default()
         };
-        let err = deliver_if_configured(&config, &job, "x").await.unwrap_err();
+        let err = deliver_if_configured(&test_router(&config), &job, true, "x", Duration::ZERO)
+            .await
+            .unwrap_err();
         assert!(err.to_string().contains("unsupported delivery channel"));
     }
+
+    #[tokio::test]
+    async fn deliver_if_configured_reports_unconfigured_extra_target() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("echo ok");
+        job.delivery = DeliveryConfig {
+            mode: "announce".into(),
+            targets: vec![crate::cron::DeliveryTarget {
+                channel: "matrix".into(),
+                to: "!ops:example.org".into(),
+            }],
+            ..DeliveryConfig::default()
+        };
+
+        let err = deliver_if_configured(&test_router(&config), &job, true, "x", Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("matrix:!ops:example.org: matrix channel not configured"));
+    }
+
+    #[test]
+    fn render_delivery_message_fills_template_placeholders() {
+        let mut job = test_job("echo ok");
+        job.name = Some("backup".into());
+
+        let rendered = render_delivery_message(
+            &job,
+            Some("[{status}] {job_name} ({job_id}) took {duration}: {output}"),
+            false,
+            "disk full",
+            Duration::from_millis(2_500),
+        );
+        assert_eq!(
+            rendered,
+            format!("[error] backup ({}) took 2.5s: disk full", job.id)
+        );
+
+        assert_eq!(
+            render_delivery_message(&job, None, true, "raw", Duration::ZERO),
+            "raw"
+        );
+    }
+
+    #[tokio::test]
+    async fn deliver_if_configured_honors_delivery_condition() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("echo ok");
+        job.delivery = DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("invalid".into()),
+            to: Some("target".into()),
+            when: DeliveryCondition::OnFailure,
+            ..DeliveryConfig::default()
+        };
+        // A skipped delivery never reaches the (unsupported) channel.
+        assert!(
+            deliver_if_configured(&test_router(&config), &job, true, "x", Duration::ZERO)
+                .await
+                .is_ok()
+        );
+        assert!(
+            deliver_if_configured(&test_router(&config), &job, false, "x", Duration::ZERO)
+                .await
+                .is_err()
+        );
+
+        job.delivery.when = DeliveryCondition::OnChange;
+        job.last_output = Some("same".into());
+        assert!(
+            deliver_if_configured(&test_router(&config), &job, true, "same", Duration::ZERO)
+                .await
+                .is_ok()
+        );
+        assert!(deliver_if_configured(
+            &test_router(&config),
+            &job,
+            true,
+            "different",
+            Duration::ZERO
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn missed_runs_counts_fire_times_only_when_late() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("echo ok");
+        job.schedule = Schedule::Every { every_ms: 60_000 };
+        let now = Utc::now();
+
+        job.next_run = now - ChronoDuration::seconds(5);
+        assert_eq!(missed_runs(&config, &job, now), None);
+
+        job.next_run = now - ChronoDuration::seconds(150);
+        assert_eq!(missed_runs(&config, &job, now), Some(3));
+
+        job.next_run = now - ChronoDuration::days(30);
+        assert_eq!(missed_runs(&config, &job, now), Some(MAX_CATCH_UP_RUNS));
+
+        job.schedule = Schedule::At { at: job.next_run };
+        assert_eq!(missed_runs(&config, &job, now), None);
+    }
+
+    #[tokio::test]
+    async fn execute_and_persist_job_skips_missed_runs_under_skip_policy() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let job = cron::add_job(&config, "*/5 * * * *", "echo ok").unwrap();
+        let mut job = cron::update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                catch_up: Some(CatchUpPolicy::Skip),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        job.next_run = Utc::now() - ChronoDuration::hours(2);
+
+        execute_and_persist_job(
+            &config,
+            &security,
+            &test_router(&config),
+            &job,
+            &unique_component("catch-up"),
+        )
+        .await;
+
+        assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+        let stored = cron::get_job(&config, &job.id).unwrap();
+        assert!(stored.next_run > Utc::now());
+        assert!(stored.last_run.is_none());
+    }
+
+    #[tokio::test]
+    async fn execute_and_persist_job_replays_missed_runs_under_all_policy() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let job = cron::add_job(&config, "0 * * * *", "echo ok").unwrap();
+        let mut job = cron::update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                catch_up: Some(CatchUpPolicy::All),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        job.next_run -= ChronoDuration::hours(3);
+
+        execute_and_persist_job(
+            &config,
+            &security,
+            &test_router(&config),
+            &job,
+            &unique_component("catch-up"),
+        )
+        .await;
+
+        assert_eq!(cron::list_runs(&config, &job.id, 10).unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn run_slot_enforces_max_concurrent() {
+        let mut job = test_job("echo ok");
+        job.id = format!("slot-{}", uuid::Uuid::new_v4());
+        job.max_concurrent = 1;
+
+        let slot = RunSlot::acquire(&job).expect("first run gets a slot");
+        assert!(RunSlot::acquire(&job).is_none());
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let (success, output) = Box::pin(execute_job_now(&config, &job)).await;
+        assert!(!success);
+        assert!(output.contains("already has 1 run(s) in progress"));
+
+        drop(slot);
+        assert!(RunSlot::acquire(&job).is_some());
+    }
+
+    #[tokio::test]
+    async fn run_follow_ups_follows_chain_and_stops_on_cycle() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let first = cron::add_job(&config, "*/5 * * * *", "echo first").unwrap();
+        let on_ok = cron::add_job(&config, "*/5 * * * *", "echo on-ok").unwrap();
+        let on_err = cron::add_job(&config, "*/5 * * * *", "echo on-err").unwrap();
+        let first = cron::update_job(
+            &config,
+            &first.id,
+            CronJobPatch {
+                on_success: Some(on_ok.id.clone()),
+                on_failure: Some(on_err.id.clone()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        // on_ok chains back to the first job, which must not run again.
+        cron::update_job(
+            &config,
+            &on_ok.id,
+            CronJobPatch {
+                on_success: Some(first.id.clone()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+
+        Box::pin(run_follow_ups(
+            &config,
+            &security,
+            &test_router(&config),
+            &first,
+            true,
+        ))
+        .await;
+
+        assert_eq!(cron::list_runs(&config, &on_ok.id, 10).unwrap().len(), 1);
+        assert!(cron::list_runs(&config, &first.id, 10).unwrap().is_empty());
+        assert!(cron::list_runs(&config, &on_err.id, 10).unwrap().is_empty());
+        let stored = cron::get_job(&config, &on_ok.id).unwrap();
+        assert_eq!(stored.last_status.as_deref(), Some("ok"));
+    }
+
+    #[tokio::test]
+    async fn run_triggered_job_records_run_and_keeps_event_schedule() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.autonomy.level = crate::security::AutonomyLevel::ReadOnly;
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let job = cron::add_agent_job(
+            &config,
+            Some("inbox".into()),
+            Schedule::Watch {
+                path: "inbox".into(),
+                pattern: None,
+            },
+            "Summarize {path}",
+            SessionTarget::Isolated,
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        let event = TriggerEvent::File {
+            path: "inbox/report.pdf".into(),
+            change: "created",
+        };
+
+        let (success, output) =
+            run_triggered_job(&config, &security, &test_router(&config), &job, &event).await;
+        assert!(!success);
+        assert!(output.contains("read-only"));
+
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        let stored = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(stored.next_run, crate::cron::schedule::event_next_run());
+        assert!(due_jobs(&config, Utc::now()).unwrap().is_empty());
+    }
 }
@@ -1,17 +1,31 @@
 use crate::config::Config;
 use crate::cron::{
-    next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
-    CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    next_run_for_schedule, schedule_cron_expression, validate_schedule, CatchUpPolicy, CronJob,
+    CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::types::{FromSqlResult, ValueRef};
 use rusqlite::{params, Connection};
+use std::sync::atomic::{AtomicU64, Ordering};
 use uuid::Uuid;
 
 const MAX_CRON_OUTPUT_BYTES: usize = 16 * 1024;
 const TRUNCATED_OUTPUT_MARKER: &str = "\n...[truncated]";
 
+/// Bumped whenever this process adds, changes or removes a job.
+static JOBS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Counter that changes whenever this process adds, updates or removes a
+/// job, for callers that cache [`list_jobs`].
+pub fn jobs_generation() -> u64 {
+    JOBS_GENERATION.load(Ordering::Acquire)
+}
+
+fn jobs_changed() {
+    JOBS_GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
 impl rusqlite::types::FromSql for JobType {
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
         let text = value.as_str()?;
@@ -33,6 +47,9 @@ pub fn add_shell_job(
     schedule: Schedule,
     command: &str,
 ) -> Result<CronJob> {
+    if schedule.is_event() {
+        anyhow::bail!("Event triggers (watch/message) start agent jobs; use job_type 'agent'");
+    }
     let now = Utc::now();
     validate_schedule(&schedule, now)?;
     let next_run = next_run_for_schedule(&schedule, now)?;
@@ -63,6 +80,7 @@ pub fn add_shell_job(
         .context("Failed to insert cron shell job")?;
         Ok(())
     })?;
+    jobs_changed();
 
     get_job(config, &id)
 }
@@ -109,6 +127,7 @@ pub fn add_agent_job(
         .context("Failed to insert cron agent job")?;
         Ok(())
     })?;
+    jobs_changed();
 
     get_job(config, &id)
 }
@@ -117,7 +136,8 @@ pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    on_success, on_failure, catch_up, max_concurrent
              FROM cron_jobs ORDER BY next_run ASC",
         )?;
 
@@ -135,7 +155,8 @@ pub fn get_job(config: &Config, job_id: &str) -> Result<CronJob> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    on_success, on_failure, catch_up, max_concurrent
              FROM cron_jobs WHERE id = ?1",
         )?;
 
@@ -153,6 +174,7 @@ pub fn remove_job(config: &Config, id: &str) -> Result<()> {
         conn.execute("DELETE FROM cron_jobs WHERE id = ?1", params![id])
             .context("Failed to delete cron job")
     })?;
+    jobs_changed();
 
     if changed == 0 {
         anyhow::bail!("Cron job '{id}' not found");
@@ -168,7 +190,8 @@ pub fn due_jobs(config: &Config, now: DateTime<Utc>) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
             "SELECT id, expression, command, schedule, job_type, prompt, name, session_target, model,
-                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output
+                    enabled, delivery, delete_after_run, created_at, next_run, last_run, last_status, last_output,
+                    on_success, on_failure, catch_up, max_concurrent
              FROM cron_jobs
              WHERE enabled = 1 AND next_run <= ?1
              ORDER BY next_run ASC
@@ -191,6 +214,9 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
 
     if let Some(schedule) = patch.schedule {
         validate_schedule(&schedule, Utc::now())?;
+        if schedule.is_event() && job.job_type == JobType::Shell {
+            anyhow::bail!("Event triggers (watch/message) start agent jobs; use job_type 'agent'");
+        }
         job.schedule = schedule;
         job.expression = schedule_cron_expression(&job.schedule).unwrap_or_default();
         schedule_changed = true;
@@ -219,6 +245,21 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
     if let Some(delete_after_run) = patch.delete_after_run {
         job.delete_after_run = delete_after_run;
     }
+    if let Some(on_success) = patch.on_success {
+        job.on_success = follow_up_job(config, job_id, on_success)?;
+    }
+    if let Some(on_failure) = patch.on_failure {
+        job.on_failure = follow_up_job(config, job_id, on_failure)?;
+    }
+    if let Some(catch_up) = patch.catch_up {
+        job.catch_up = catch_up;
+    }
+    if let Some(max_concurrent) = patch.max_concurrent {
+        if max_concurrent == 0 {
+            anyhow::bail!("max_concurrent must be at least 1");
+        }
+        job.max_concurrent = max_concurrent;
+    }
 
     if schedule_changed {
         job.next_run = next_run_for_schedule(&job.schedule, Utc::now())?;
@@ -229,8 +270,8 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
             "UPDATE cron_jobs
              SET expression = ?1, command = ?2, schedule = ?3, job_type = ?4, prompt = ?5, name = ?6,
                  session_target = ?7, model = ?8, enabled = ?9, delivery = ?10, delete_after_run = ?11,
-                 next_run = ?12
-             WHERE id = ?13",
+                 next_run = ?12, on_success = ?13, on_failure = ?14, catch_up = ?15, max_concurrent = ?16
+             WHERE id = ?17",
             params![
                 job.expression,
                 job.command,
@@ -244,16 +285,44 @@ pub fn update_job(config: &Config, job_id: &str, patch: CronJobPatch) -> Result<
                 serde_json::to_string(&job.delivery)?,
                 if job.delete_after_run { 1 } else { 0 },
                 job.next_run.to_rfc3339(),
+                job.on_success,
+                job.on_failure,
+                job.catch_up.as_str(),
+                job.max_concurrent,
                 job.id,
             ],
         )
         .context("Failed to update cron job")?;
         Ok(())
     })?;
+    jobs_changed();
 
     get_job(config, job_id)
 }
 
+/// Apply `patch` to a job that was just created, removing the job again when
+/// the patch is rejected so a failed add leaves nothing behind.
+pub fn configure_new_job(config: &Config, job: &CronJob, patch: CronJobPatch) -> Result<CronJob> {
+    update_job(config, &job.id, patch).inspect_err(|_| {
+        if let Err(e) = remove_job(config, &job.id) {
+            tracing::warn!("Failed to remove rejected cron job '{}': {e}", job.id);
+        }
+    })
+}
+
+/// Validate a follow-up job id for `job_id`; an empty id clears it.
+fn follow_up_job(config: &Config, job_id: &str, raw: String) -> Result<Option<String>> {
+    let id = raw.trim();
+    if id.is_empty() {
+        return Ok(None);
+    }
+    if id == job_id {
+        anyhow::bail!("A cron job cannot chain to itself");
+    }
+    get_job(config, id).with_context(|| format!("Follow-up job '{id}' not found"))?;
+    Ok(Some(id.to_string()))
+}
+
 pub fn record_last_run(
     config: &Config,
     job_id: &str,
@@ -304,6 +373,19 @@ pub fn reschedule_after_run(
     })
 }
 
+/// Move `next_run` past `now` without recording a run.
+pub fn skip_missed_runs(config: &Config, job: &CronJob, now: DateTime<Utc>) -> Result<()> {
+    let next_run = next_run_for_schedule(&job.schedule, now)?;
+    with_connection(config, |conn| {
+        conn.execute(
+            "UPDATE cron_jobs SET next_run = ?1 WHERE id = ?2",
+            params![next_run.to_rfc3339(), job.id],
+        )
+        .context("Failed to skip missed cron runs")?;
+        Ok(())
+    })
+}
+
 pub fn record_run(
     config: &Config,
     job_id: &str,
@@ -354,7 +436,7 @@ pub fn record_run(
     })
 }
 
-fn truncate_cron_output(output: &str) -> String {
+pub(super) fn truncate_cron_output(output: &str) -> String {
     if output.len() <= MAX_CRON_OUTPUT_BYTES {
         return output.to_string();
     }
@@ -450,6 +532,10 @@ fn map_cron_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CronJob> {
         },
         last_status: row.get(15)?,
         last_output: row.get(16)?,
+        on_success: row.get(17)?,
+        on_failure: row.get(18)?,
+        catch_up: CatchUpPolicy::parse(&row.get::<_, String>(19)?),
+        max_concurrent: row.get::<_, u32>(20)?.max(1),
     })
 }
 
@@ -542,7 +628,11 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
             next_run         TEXT NOT NULL,
             last_run         TEXT,
             last_status      TEXT,
-            last_output      TEXT
+            last_output      TEXT,
+            on_success       TEXT,
+            on_failure       TEXT,
+            catch_up         TEXT NOT NULL DEFAULT 'once',
+            max_concurrent   INTEGER NOT NULL DEFAULT 1
         );
         CREATE INDEX IF NOT EXISTS idx_cron_jobs_next_run ON cron_jobs(next_run);
 
@@ -571,6 +661,10 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
     add_column_if_missing(&conn, "enabled", "INTEGER NOT NULL DEFAULT 1")?;
     add_column_if_missing(&conn, "delivery", "TEXT")?;
     add_column_if_missing(&conn, "delete_after_run", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(&conn, "on_success", "TEXT")?;
+    add_column_if_missing(&conn, "on_failure", "TEXT")?;
+    add_column_if_missing(&conn, "catch_up", "TEXT NOT NULL DEFAULT 'once'")?;
+    add_column_if_missing(&conn, "max_concurrent", "INTEGER NOT NULL DEFAULT 1")?;
 
     f(&conn)
 }
@@ -862,4 +956,147 @@ mod tests {
         assert!(last_output.ends_with(TRUNCATED_OUTPUT_MARKER));
         assert!(last_output.len() <= MAX_CRON_OUTPUT_BYTES);
     }
+
+    #[test]
+    fn update_job_validates_and_clears_follow_up_jobs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/5 * * * *", "echo main").unwrap();
+        let cleanup = add_job(&config, "*/5 * * * *", "echo cleanup").unwrap();
+
+        let chained = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                on_failure: Some(cleanup.id.clone()),
+                catch_up: Some(CatchUpPolicy::All),
+                max_concurrent: Some(3),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(chained.on_failure.as_deref(), Some(cleanup.id.as_str()));
+        assert_eq!(chained.catch_up, CatchUpPolicy::All);
+        assert_eq!(chained.max_concurrent, 3);
+
+        for (on_success, max_concurrent) in [
+            (Some(job.id.clone()), None),
+            (Some("missing".to_string()), None),
+            (None, Some(0)),
+        ] {
+            let patch = CronJobPatch {
+                on_success,
+                max_concurrent,
+                ..CronJobPatch::default()
+            };
+            assert!(update_job(&config, &job.id, patch).is_err());
+        }
+
+        let cleared = update_job(
+            &config,
+            &job.id,
+            CronJobPatch {
+                on_failure: Some(String::new()),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(cleared.on_failure, None);
+    }
+
+    #[test]
+    fn configure_new_job_removes_job_when_patch_is_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/5 * * * *", "echo main").unwrap();
+
+        let err = configure_new_job(
+            &config,
+            &job,
+            CronJobPatch {
+                catch_up: Some(CatchUpPolicy::Skip),
+                max_concurrent: Some(0),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("max_concurrent"), "{err}");
+        assert!(list_jobs(&config).unwrap().is_empty());
+
+        let job = add_job(&config, "*/5 * * * *", "echo main").unwrap();
+        let configured = configure_new_job(
+            &config,
+            &job,
+            CronJobPatch {
+                max_concurrent: Some(2),
+                ..CronJobPatch::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(configured.max_concurrent, 2);
+        assert_eq!(list_jobs(&config).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn migration_adds_chaining_columns_with_defaults() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let db_dir = config.workspace_dir.join("cron");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        let conn = Connection::open(db_dir.join("jobs.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE cron_jobs (
+                id TEXT PRIMARY KEY, expression TEXT NOT NULL, command TEXT NOT NULL,
+                created_at TEXT NOT NULL, next_run TEXT NOT NULL,
+                last_run TEXT, last_status TEXT, last_output TEXT
+            );",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO cron_jobs (id, expression, command, created_at, next_run)
+             VALUES ('old', '*/5 * * * *', 'echo old', ?1, ?1)",
+            params![Utc::now().to_rfc3339()],
+        )
+        .unwrap();
+        drop(conn);
+
+        let job = get_job(&config, "old").unwrap();
+        assert_eq!(job.on_success, None);
+        assert_eq!(job.on_failure, None);
+        assert_eq!(job.catch_up, CatchUpPolicy::Once);
+        assert_eq!(job.max_concurrent, 1);
+    }
+
+    #[test]
+    fn skip_missed_runs_moves_next_run_without_recording() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/5 * * * *", "echo skip").unwrap();
+        let now = Utc::now() + ChronoDuration::hours(1);
+
+        skip_missed_runs(&config, &job, now).unwrap();
+
+        let stored = get_job(&config, &job.id).unwrap();
+        assert!(stored.next_run > now);
+        assert!(stored.last_run.is_none());
+        assert!(list_runs(&config, &job.id, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn event_schedules_are_limited_to_agent_jobs() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let watch = Schedule::Watch {
+            path: "inbox".into(),
+            pattern: None,
+        };
+
+        assert!(add_shell_job(&config, None, watch.clone(), "echo hi").is_err());
+        let shell = add_job(&config, "*/5 * * * *", "echo hi").unwrap();
+        let patch = CronJobPatch {
+            schedule: Some(watch),
+            ..CronJobPatch::default()
+        };
+        assert!(update_job(&config, &shell.id, patch).is_err());
+    }
 }
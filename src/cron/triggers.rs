@@ -0,0 +1,968 @@
+//! Event triggers for agent jobs.
+//!
+//! `watch` jobs fire when files under a workspace path are created or
+//! modified (polled on the scheduler tick); `message` jobs fire when an
+//! inbound channel message matches their filters; `state` jobs fire on
+//! Home Assistant entity state changes (WebSocket subscription run by the
+//! daemon). All run through the scheduler, so they share run history,
+//! delivery and security checks with timed jobs.
+
+use super::scheduler::run_triggered_job;
+use super::{jobs_generation, list_jobs, CronJob, Schedule};
+use crate::channels::delivery::DeliveryRouter;
+use crate::channels::traits::ChannelMessage;
+use crate::config::{Config, HomeAssistantConfig};
+use crate::security::SecurityPolicy;
+use anyhow::{Context, Result};
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::task::JoinHandle;
+
+/// Files handled per watch job per tick; the rest wait for the next tick.
+const MAX_FILE_EVENTS_PER_POLL: usize = 20;
+/// How long cached trigger jobs are used without a local job change, so
+/// edits made by another process (e.g. `zeroclaw cron add`) are picked up.
+const TRIGGER_CACHE_TTL: Duration = Duration::from_secs(60);
+/// Directory depth scanned below a watch path.
+const MAX_WATCH_DEPTH: usize = 8;
+/// Interval between Home Assistant WebSocket pings.
+const HA_PING_INTERVAL_SECS: u64 = 30;
+
+/// What set off an event-triggered run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerEvent {
+    File {
+        /// Workspace-relative path of the file.
+        path: String,
+        /// `created` or `modified`.
+        change: &'static str,
+    },
+    Message {
+        channel: String,
+        sender: String,
+        content: String,
+    },
+    State {
+        entity_id: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl TriggerEvent {
+    /// Fill `{path}`/`{change}`, `{channel}`/`{sender}`/`{message}` or
+    /// `{entity_id}`/`{from}`/`{to}` in a job prompt. Prompts without placeholders get the event appended.
+    pub fn render(&self, prompt: &str) -> String {
+        let (placeholders, summary): (Vec<(&str, &str)>, String) = match self {
+            Self::File { path, change } => (
+                vec![("{path}", path), ("{change}", change)],
+                format!("[event] file {change}: {path}"),
+            ),
+            Self::Message {
+                channel,
+                sender,
+                content,
+            } => (
+                vec![
+                    ("{channel}", channel),
+                    ("{sender}", sender),
+                    ("{message}", content),
+                ],
+                format!("[event] {channel} message from {sender}:\n{content}"),
+            ),
+            Self::State {
+                entity_id,
+                from,
+                to,
+            } => (
+                vec![("{entity_id}", entity_id), ("{from}", from), ("{to}", to)],
+                format!("[event] {entity_id} changed from {from} to {to}"),
+            ),
+        };
+
+        if !placeholders.iter().any(|(key, _)| prompt.contains(key)) {
+            return if prompt.trim().is_empty() {
+                summary
+            } else {
+                format!("{prompt}\n\n{summary}")
+            };
+        }
+        placeholders
+            .iter()
+            .fold(prompt.to_string(), |acc, (key, value)| {
+                acc.replace(key, value)
+            })
+    }
+}
+
+// ── Job cache ───────────────────────────────────────────────────
+
+/// Enabled jobs of one trigger kind, reloaded only when this process changes
+/// a job or after [`TRIGGER_CACHE_TTL`], so event-driven triggers do not hit
+/// the job store for every message or state change.
+struct TriggerJobCache<T> {
+    /// Keeps the jobs this cache is for, preparing them for matching.
+    prepare: fn(CronJob) -> Option<T>,
+    loaded: Mutex<Option<LoadedJobs<T>>>,
+}
+
+struct LoadedJobs<T> {
+    generation: u64,
+    at: Instant,
+    jobs: Arc<Vec<T>>,
+}
+
+impl<T> TriggerJobCache<T> {
+    fn new(prepare: fn(CronJob) -> Option<T>) -> Self {
+        Self {
+            prepare,
+            loaded: Mutex::new(None),
+        }
+    }
+
+    fn jobs(&self, config: &Config) -> Result<Arc<Vec<T>>> {
+        let generation = jobs_generation();
+        if let Some(loaded) = self.loaded.lock().as_ref() {
+            if loaded.generation == generation && loaded.at.elapsed() < TRIGGER_CACHE_TTL {
+                return Ok(Arc::clone(&loaded.jobs));
+            }
+        }
+
+        let jobs: Arc<Vec<T>> = Arc::new(
+            list_jobs(config)?
+                .into_iter()
+                .filter(|job| job.enabled)
+                .filter_map(self.prepare)
+                .collect(),
+        );
+        *self.loaded.lock() = Some(LoadedJobs {
+            generation,
+            at: Instant::now(),
+            jobs: Arc::clone(&jobs),
+        });
+        Ok(jobs)
+    }
+}
+
+// ── File watch ──────────────────────────────────────────────────
+
+/// Scan watermarks and in-flight runs per `watch` job, kept for the life of
+/// the scheduler.
+#[derive(Default)]
+pub(crate) struct WatchState {
+    watermarks: HashMap<String, SystemTime>,
+    /// Background batch of runs per job; the job is not rescanned until it ends.
+    running: HashMap<String, JoinHandle<RunWindow>>,
+    /// When each job's last batch ran. Files modified in that window are the
+    /// job's own output and do not trigger it again.
+    last_runs: HashMap<String, RunWindow>,
+}
+
+/// Start and end of a batch of triggered runs.
+type RunWindow = (SystemTime, SystemTime);
+
+/// Start every enabled `watch` job once per file changed since its last scan.
+/// The first scan after startup looks back to the job's last run so files
+/// that arrived while the daemon was down are not missed. Runs happen in the
+/// background so a burst of file events does not hold up timed jobs.
+pub(crate) async fn poll_watch_triggers(
+    config: &Config,
+    security: &Arc<SecurityPolicy>,
+    delivery: &Arc<DeliveryRouter>,
+    state: &mut WatchState,
+) {
+    let jobs = match list_jobs(config) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            tracing::warn!("Watch trigger query failed: {e}");
+            return;
+        }
+    };
+
+    let watch_jobs: Vec<CronJob> = jobs
+        .into_iter()
+        .filter(|job| job.enabled && matches!(job.schedule, Schedule::Watch { .. }))
+        .collect();
+    let is_watched = |id: &String| watch_jobs.iter().any(|job| &job.id == id);
+    state.watermarks.retain(|id, _| is_watched(id));
+    state.running.retain(|id, _| is_watched(id));
+    state.last_runs.retain(|id, _| is_watched(id));
+
+    for job in watch_jobs {
+        let Schedule::Watch { path, pattern } = &job.schedule else {
+            continue;
+        };
+        if let Some(batch) = state.running.get(&job.id) {
+            if !batch.is_finished() {
+                continue;
+            }
+            if let Some(batch) = state.running.remove(&job.id) {
+                match batch.await {
+                    Ok(window) => {
+                        state.last_runs.insert(job.id.clone(), window);
+                    }
+                    Err(e) => tracing::warn!("Watch trigger job '{}' panicked: {e}", job.id),
+                }
+            }
+        }
+
+        let scan_started = SystemTime::now();
+        let since = *state
+            .watermarks
+            .entry(job.id.clone())
+            .or_insert_with(|| job.last_run.unwrap_or(job.created_at).into());
+
+        let mut changes = match changed_files(
+            &config.workspace_dir,
+            security,
+            path,
+            pattern.as_deref(),
+            since,
+            scan_started,
+        ) {
+            Ok(changes) => changes,
+            Err(e) => {
+                tracing::warn!("Watch trigger '{}' scan failed: {e}", job.id);
+                continue;
+            }
+        };
+        if let Some(window) = state.last_runs.get(&job.id) {
+            skip_own_writes(&mut changes, *window);
+        }
+
+        // Anything past the per-tick cap keeps its place for the next scan.
+        let next_since = changes
+            .get(MAX_FILE_EVENTS_PER_POLL)
+            .map_or(scan_started, |(_, _, modified)| {
+                *modified - Duration::from_nanos(1)
+            });
+        state.watermarks.insert(job.id.clone(), next_since);
+        if changes.is_empty() {
+            continue;
+        }
+
+        let events: Vec<TriggerEvent> = changes
+            .into_iter()
+            .take(MAX_FILE_EVENTS_PER_POLL)
+            .map(|(path, change, _)| TriggerEvent::File { path, change })
+            .collect();
+        let config = config.clone();
+        let security = Arc::clone(security);
+        let delivery = Arc::clone(delivery);
+        let job_id = job.id.clone();
+        let batch = tokio::spawn(async move {
+            let started = SystemTime::now();
+            for event in events {
+                let (success, output) = Box::pin(run_triggered_job(
+                    &config, &security, &delivery, &job, &event,
+                ))
+                .await;
+                if !success {
+                    tracing::warn!("Watch trigger job '{}' failed: {output}", job.id);
+                }
+            }
+            (started, SystemTime::now())
+        });
+        state.running.insert(job_id, batch);
+    }
+}
+
+/// Drop changes made while the job itself was running.
+fn skip_own_writes(changes: &mut Vec<(String, &'static str, SystemTime)>, window: RunWindow) {
+    let (started, finished) = window;
+    changes.retain(|(_, _, modified)| *modified < started || *modified > finished);
+}
+
+/// Files under `workspace/path` modified in `(since, until]`, oldest first,
+/// as `(workspace-relative path, change, modified)`.
+fn changed_files(
+    workspace: &Path,
+    security: &SecurityPolicy,
+    path: &str,
+    pattern: Option<&str>,
+    since: SystemTime,
+    until: SystemTime,
+) -> Result<Vec<(String, &'static str, SystemTime)>> {
+    let root = workspace.join(path);
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve watch path {}", root.display()))?;
+    if !security.is_resolved_path_allowed(&root) {
+        anyhow::bail!("{}", security.resolved_path_violation_message(&root));
+    }
+    let workspace = workspace
+        .canonicalize()
+        .unwrap_or_else(|_| workspace.to_path_buf());
+    let pattern = pattern.map(glob::Pattern::new).transpose()?;
+
+    let mut files = Vec::new();
+    collect_files(&root, 0, &mut files);
+
+    let mut changes = Vec::new();
+    for file in files {
+        let Ok(metadata) = std::fs::metadata(&file) else {
+            continue;
+        };
+        let Ok(modified) = metadata.modified() else {
+            continue;
+        };
+        if modified <= since || modified > until {
+            continue;
+        }
+        if let Some(pattern) = &pattern {
+            let name = file.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if !pattern.matches(name) {
+                continue;
+            }
+        }
+        let change = match metadata.created() {
+            Ok(created) if created > since => "created",
+            _ => "modified",
+        };
+        let relative = file.strip_prefix(&workspace).unwrap_or(&file);
+        changes.push((relative.display().to_string(), change, modified));
+    }
+    changes.sort_by_key(|(_, _, modified)| *modified);
+    Ok(changes)
+}
+
+/// Regular files under `dir`, not following symlinks.
+fn collect_files(dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+    let Ok(metadata) = std::fs::symlink_metadata(dir) else {
+        return;
+    };
+    if metadata.is_file() {
+        out.push(dir.to_path_buf());
+        return;
+    }
+    if !metadata.is_dir() || depth > MAX_WATCH_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        collect_files(&entry.path(), depth + 1, out);
+    }
+}
+
+// ── Channel messages ────────────────────────────────────────────
+
+struct MessageTriggers {
+    config: Config,
+    security: Arc<SecurityPolicy>,
+    delivery: DeliveryRouter,
+    jobs: TriggerJobCache<MessageTrigger>,
+}
+
+/// A `message` job with its regex filter compiled once.
+struct MessageTrigger {
+    job: CronJob,
+    regex: Option<regex::Regex>,
+}
+
+impl MessageTrigger {
+    fn prepare(job: CronJob) -> Option<Self> {
+        let Schedule::Message { regex, .. } = &job.schedule else {
+            return None;
+        };
+        let regex = match set(regex.as_deref()).map(regex::Regex::new).transpose() {
+            Ok(regex) => regex,
+            Err(e) => {
+                tracing::warn!("Message trigger job '{}' has an invalid regex: {e}", job.id);
+                return None;
+            }
+        };
+        Some(Self { job, regex })
+    }
+}
+
+static MESSAGE_TRIGGERS: OnceLock<MessageTriggers> = OnceLock::new();
+
+/// Enable `message` triggers for channel listeners in this process.
+pub fn install_message_triggers(config: &Config) {
+    if !config.cron.enabled {
+        return;
+    }
+    let _ = MESSAGE_TRIGGERS.get_or_init(|| MessageTriggers {
+        config: config.clone(),
+        security: Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        )),
+        delivery: DeliveryRouter::from_config(config),
+        jobs: TriggerJobCache::new(MessageTrigger::prepare),
+    });
+}
+
+/// Start every enabled `message` job matching `msg` in the background.
+/// Does nothing until [`install_message_triggers`] has run.
+pub(crate) fn dispatch_message_triggers(msg: &ChannelMessage) {
+    let Some(triggers) = MESSAGE_TRIGGERS.get() else {
+        return;
+    };
+    let msg = msg.clone();
+    tokio::spawn(async move {
+        let jobs = match triggers.jobs.jobs(&triggers.config) {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                tracing::warn!("Message trigger query failed: {e}");
+                return;
+            }
+        };
+        for trigger in jobs.iter().filter(|trigger| message_matches(trigger, &msg)) {
+            let job = trigger.job.clone();
+            let event = TriggerEvent::Message {
+                channel: msg.channel.clone(),
+                sender: msg.sender.clone(),
+                content: msg.content.clone(),
+            };
+            tokio::spawn(async move {
+                let (success, output) = Box::pin(run_triggered_job(
+                    &triggers.config,
+                    &triggers.security,
+                    &triggers.delivery,
+                    &job,
+                    &event,
+                ))
+                .await;
+                if !success {
+                    tracing::warn!("Message trigger job '{}' failed: {output}", job.id);
+                }
+            });
+        }
+    });
+}
+
+fn set(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Whether `msg` passes every filter set on a `message` job.
+fn message_matches(trigger: &MessageTrigger, msg: &ChannelMessage) -> bool {
+    let Schedule::Message {
+        channel,
+        sender,
+        keyword,
+        ..
+    } = &trigger.job.schedule
+    else {
+        return false;
+    };
+
+    if set(channel.as_deref()).is_some_and(|channel| !channel.eq_ignore_ascii_case(&msg.channel)) {
+        return false;
+    }
+    if set(sender.as_deref()).is_some_and(|sender| !sender.eq_ignore_ascii_case(&msg.sender)) {
+        return false;
+    }
+    if set(keyword.as_deref())
+        .is_some_and(|keyword| !msg.content.to_lowercase().contains(&keyword.to_lowercase()))
+    {
+        return false;
+    }
+    if trigger
+        .regex
+        .as_ref()
+        .is_some_and(|regex| !regex.is_match(&msg.content))
+    {
+        return false;
+    }
+    true
+}
+
+// ── Home Assistant state changes ────────────────────────────────
+
+/// Subscribe to Home Assistant `state_changed` events and run every enabled
+/// `state` job they match. Returns an error when the connection drops so the
+/// daemon supervisor reconnects with backoff.
+pub async fn run_state_triggers(config: Config, delivery: Arc<DeliveryRouter>) -> Result<()> {
+    let config = Arc::new(config);
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let jobs = Arc::new(TriggerJobCache::new(|job: CronJob| {
+        matches!(job.schedule, Schedule::State { .. }).then_some(job)
+    }));
+    listen_state_changes(&config.home_assistant, |event| {
+        let config = Arc::clone(&config);
+        let security = Arc::clone(&security);
+        let delivery = Arc::clone(&delivery);
+        let jobs = Arc::clone(&jobs);
+        tokio::spawn(async move {
+            let jobs = match jobs.jobs(&config) {
+                Ok(jobs) => jobs,
+                Err(e) => {
+                    tracing::warn!("State trigger query failed: {e}");
+                    return;
+                }
+            };
+            for job in jobs
+                .iter()
+                .filter(|job| state_matches(&job.schedule, &event))
+            {
+                let config = Arc::clone(&config);
+                let security = Arc::clone(&security);
+                let delivery = Arc::clone(&delivery);
+                let job = job.clone();
+                let event = event.clone();
+                tokio::spawn(async move {
+                    let (success, output) = Box::pin(run_triggered_job(
+                        &config, &security, &delivery, &job, &event,
+                    ))
+                    .await;
+                    if !success {
+                        tracing::warn!("State trigger job '{}' failed: {output}", job.id);
+                    }
+                });
+            }
+        });
+    })
+    .await
+}
+
+/// `ws(s)://…/api/websocket` for a Home Assistant base URL.
+fn websocket_url(base: &str) -> Result<String> {
+    let base = base.trim().trim_end_matches('/');
+    let url = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{rest}")
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{rest}")
+    } else if base.starts_with("ws://") || base.starts_with("wss://") {
+        base.to_string()
+    } else {
+        anyhow::bail!("Invalid home_assistant.url '{base}': expected http(s)://");
+    };
+    Ok(format!("{url}/api/websocket"))
+}
+
+/// Authenticate against the Home Assistant WebSocket API, subscribe to
+/// `state_changed` and call `on_change` for every actual state transition
+/// (attribute-only updates are skipped).
+async fn listen_state_changes(
+    ha: &HomeAssistantConfig,
+    mut on_change: impl FnMut(TriggerEvent),
+) -> Result<()> {
+    use tokio_tungstenite::tungstenite::Message;
+
+    let token = ha
+        .token
+        .as_deref()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .context("home_assistant.token is not configured")?;
+    let (ws_stream, _) = tokio_tungstenite::connect_async(websocket_url(&ha.url)?)
+        .await
+        .context("Failed to connect to the Home Assistant WebSocket API")?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let mut next_text = async || -> Result<Value> {
+        loop {
+            match read.next().await {
+                Some(Ok(Message::Text(text))) => return Ok(serde_json::from_str(text.as_ref())?),
+                Some(Ok(Message::Close(_))) | None => {
+                    anyhow::bail!("Home Assistant WebSocket closed")
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+            }
+        }
+    };
+
+    let hello = next_text().await?;
+    if hello["type"] != "auth_required" {
+        anyhow::bail!("Unexpected Home Assistant handshake: {hello}");
+    }
+    write
+        .send(Message::Text(
+            json!({"type": "auth", "access_token": token})
+                .to_string()
+                .into(),
+        ))
+        .await?;
+    let auth = next_text().await?;
+    if auth["type"] != "auth_ok" {
+        anyhow::bail!(
+            "Home Assistant authentication failed: {}",
+            auth["message"].as_str().unwrap_or("invalid token")
+        );
+    }
+    write
+        .send(Message::Text(
+            json!({"id": 1, "type": "subscribe_events", "event_type": "state_changed"})
+                .to_string()
+                .into(),
+        ))
+        .await?;
+    tracing::info!("Home Assistant: subscribed to state changes");
+
+    let mut next_id: u64 = 2;
+    let mut ping = tokio::time::interval(std::time::Duration::from_secs(HA_PING_INTERVAL_SECS));
+    ping.tick().await;
+    loop {
+        tokio::select! {
+            _ = ping.tick() => {
+                write
+                    .send(Message::Text(json!({"id": next_id, "type": "ping"}).to_string().into()))
+                    .await?;
+                next_id += 1;
+            }
+            message = next_text() => {
+                let message = message?;
+                match message["type"].as_str() {
+                    Some("result") if message["success"] == false => anyhow::bail!(
+                        "Home Assistant rejected request {}: {}",
+                        message["id"],
+                        message["error"]["message"].as_str().unwrap_or("unknown error")
+                    ),
+                    Some("event") => {
+                        if let Some(event) = state_change_event(&message["event"]) {
+                            on_change(event);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Trigger event for a `state_changed` payload; `None` for attribute-only
+/// updates and removed entities.
+fn state_change_event(event: &Value) -> Option<TriggerEvent> {
+    let data = &event["data"];
+    let entity_id = data["entity_id"].as_str()?;
+    let to = data["new_state"]["state"].as_str()?;
+    let from = data["old_state"]["state"].as_str().unwrap_or("none");
+    (from != to).then(|| TriggerEvent::State {
+        entity_id: entity_id.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+    })
+}
+
+/// Whether a state change passes the filters of a `state` schedule.
+fn state_matches(schedule: &Schedule, event: &TriggerEvent) -> bool {
+    let (
+        Schedule::State {
+            entity_id: pattern,
+            from: want_from,
+            to: want_to,
+        },
+        TriggerEvent::State {
+            entity_id,
+            from,
+            to,
+        },
+    ) = (schedule, event)
+    else {
+        return false;
+    };
+
+    if !glob::Pattern::new(pattern.trim()).is_ok_and(|pattern| pattern.matches(entity_id)) {
+        return false;
+    }
+    if set(want_from.as_deref()).is_some_and(|want| !want.eq_ignore_ascii_case(from)) {
+        return false;
+    }
+    set(want_to.as_deref()).is_none_or(|want| want.eq_ignore_ascii_case(to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cron::{add_agent_job, SessionTarget};
+    use tempfile::TempDir;
+
+    fn test_config(tmp: &TempDir) -> Config {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config
+    }
+
+    fn add_message_job(config: &Config, regex: Option<&str>) -> CronJob {
+        let schedule = Schedule::Message {
+            channel: Some("telegram".into()),
+            sender: Some("alice".into()),
+            keyword: Some("Invoice".into()),
+            regex: regex.map(Into::into),
+        };
+        add_agent_job(
+            config,
+            None,
+            schedule,
+            "file it",
+            SessionTarget::Isolated,
+            None,
+            None,
+            false,
+        )
+        .unwrap()
+    }
+
+    fn message(channel: &str, sender: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "m1".into(),
+            sender: sender.into(),
+            reply_target: sender.into(),
+            content: content.into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+        }
+    }
+
+    #[test]
+    fn message_matches_applies_every_filter() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let schedule = MessageTrigger::prepare(add_message_job(&config, Some(r"#\d+"))).unwrap();
+
+        assert!(message_matches(
+            &schedule,
+            &message("Telegram", "Alice", "new invoice #42 attached")
+        ));
+        assert!(!message_matches(
+            &schedule,
+            &message("slack", "alice", "new invoice #42")
+        ));
+        assert!(!message_matches(
+            &schedule,
+            &message("telegram", "bob", "new invoice #42")
+        ));
+        assert!(!message_matches(
+            &schedule,
+            &message("telegram", "alice", "new invoice attached")
+        ));
+    }
+
+    #[test]
+    fn message_trigger_skips_jobs_with_invalid_regex() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let mut job = add_message_job(&config, None);
+        job.schedule = Schedule::Message {
+            channel: None,
+            sender: None,
+            keyword: None,
+            regex: Some("(unclosed".into()),
+        };
+
+        assert!(MessageTrigger::prepare(job).is_none());
+    }
+
+    #[test]
+    fn trigger_job_cache_reloads_after_job_changes() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let cache = TriggerJobCache::new(MessageTrigger::prepare);
+        add_message_job(&config, None);
+
+        let first = cache.jobs(&config).unwrap();
+        assert_eq!(first.len(), 1);
+
+        add_message_job(&config, Some("#\\d+"));
+        let reloaded = cache.jobs(&config).unwrap();
+        assert_eq!(reloaded.len(), 2);
+        assert!(!Arc::ptr_eq(&first, &reloaded));
+    }
+
+    #[test]
+    fn skip_own_writes_drops_changes_inside_run_window() {
+        let start = SystemTime::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut changes = vec![
+            ("before.txt".to_string(), "modified", at(1)),
+            ("during.txt".to_string(), "modified", at(5)),
+            ("after.txt".to_string(), "created", at(9)),
+        ];
+
+        skip_own_writes(&mut changes, (at(3), at(7)));
+
+        let paths: Vec<_> = changes.iter().map(|(path, _, _)| path.as_str()).collect();
+        assert_eq!(paths, ["before.txt", "after.txt"]);
+    }
+
+    #[test]
+    fn render_fills_placeholders_or_appends_event() {
+        let event = TriggerEvent::Message {
+            channel: "slack".into(),
+            sender: "U123".into(),
+            content: "deploy web".into(),
+        };
+        assert_eq!(
+            event.render("{sender} on {channel} asked: {message}"),
+            "U123 on slack asked: deploy web"
+        );
+        assert_eq!(
+            event.render("Handle the request."),
+            "Handle the request.\n\n[event] slack message from U123:\ndeploy web"
+        );
+
+        let file = TriggerEvent::File {
+            path: "inbox/a.pdf".into(),
+            change: "created",
+        };
+        assert_eq!(file.render("Summarize {path}"), "Summarize inbox/a.pdf");
+    }
+
+    #[test]
+    fn changed_files_reports_files_in_window_matching_pattern() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+        let inbox = workspace.join("inbox");
+        std::fs::create_dir_all(inbox.join("nested")).unwrap();
+        let security = SecurityPolicy {
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        };
+
+        let before = SystemTime::now() - Duration::from_secs(5);
+        std::fs::write(inbox.join("a.pdf"), "a").unwrap();
+        std::fs::write(inbox.join("nested").join("b.pdf"), "b").unwrap();
+        std::fs::write(inbox.join("notes.txt"), "c").unwrap();
+        let until = SystemTime::now() + Duration::from_secs(5);
+
+        let mut changes: Vec<String> =
+            changed_files(workspace, &security, "inbox", Some("*.pdf"), before, until)
+                .unwrap()
+                .into_iter()
+                .map(|(path, _, _)| path)
+                .collect();
+        changes.sort();
+        assert_eq!(changes, vec!["inbox/a.pdf", "inbox/nested/b.pdf"]);
+
+        let later = changed_files(workspace, &security, "inbox", None, until, until).unwrap();
+        assert!(later.is_empty());
+        let missing = changed_files(workspace, &security, "outbox", None, before, until).unwrap();
+        assert!(missing.is_empty());
+    }
+
+    fn state_event(entity_id: &str, from: &str, to: &str) -> TriggerEvent {
+        TriggerEvent::State {
+            entity_id: entity_id.into(),
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    #[test]
+    fn state_matches_applies_entity_glob_and_transitions() {
+        let schedule = Schedule::State {
+            entity_id: "binary_sensor.*_door".into(),
+            from: None,
+            to: Some("on".into()),
+        };
+        assert!(state_matches(
+            &schedule,
+            &state_event("binary_sensor.front_door", "off", "on")
+        ));
+        assert!(!state_matches(
+            &schedule,
+            &state_event("binary_sensor.front_door", "on", "off")
+        ));
+        assert!(!state_matches(
+            &schedule,
+            &state_event("binary_sensor.kitchen_motion", "off", "on")
+        ));
+        assert!(!state_matches(
+            &Schedule::Every { every_ms: 1000 },
+            &state_event("binary_sensor.front_door", "off", "on")
+        ));
+        assert_eq!(
+            state_event("lock.front_door", "locked", "unlocked")
+                .render("Door {entity_id} went {from} -> {to}"),
+            "Door lock.front_door went locked -> unlocked"
+        );
+    }
+
+    #[test]
+    fn websocket_url_maps_http_schemes() {
+        assert_eq!(
+            websocket_url("http://homeassistant.local:8123/").unwrap(),
+            "ws://homeassistant.local:8123/api/websocket"
+        );
+        assert_eq!(
+            websocket_url("https://ha.example.com").unwrap(),
+            "wss://ha.example.com/api/websocket"
+        );
+        assert!(websocket_url("ha.example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn listen_state_changes_authenticates_and_reports_transitions() {
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let send = |value: Value| Message::Text(value.to_string().into());
+            ws.send(send(json!({"type": "auth_required"})))
+                .await
+                .unwrap();
+            let Some(Ok(Message::Text(auth))) = ws.next().await else {
+                panic!("expected auth message");
+            };
+            let auth: Value = serde_json::from_str(auth.as_ref()).unwrap();
+            assert_eq!(auth["access_token"], "ha_token");
+            ws.send(send(json!({"type": "auth_ok"}))).await.unwrap();
+            let Some(Ok(Message::Text(subscribe))) = ws.next().await else {
+                panic!("expected subscribe message");
+            };
+            let subscribe: Value = serde_json::from_str(subscribe.as_ref()).unwrap();
+            assert_eq!(subscribe["event_type"], "state_changed");
+
+            let change = |entity: &str, from: &str, to: &str| {
+                json!({"id": 1, "type": "event", "event": {
+                    "event_type": "state_changed",
+                    "data": {
+                        "entity_id": entity,
+                        "old_state": {"state": from},
+                        "new_state": {"state": to}
+                    }
+                }})
+            };
+            ws.send(send(json!({"id": 1, "type": "result", "success": true})))
+                .await
+                .unwrap();
+            ws.send(send(change("sensor.temp", "21", "21")))
+                .await
+                .unwrap();
+            ws.send(send(change("lock.front_door", "locked", "unlocked")))
+                .await
+                .unwrap();
+            ws.close(None).await.unwrap();
+        });
+
+        let ha = HomeAssistantConfig {
+            enabled: true,
+            url: format!("http://127.0.0.1:{port}"),
+            token: Some("ha_token".into()),
+            ..HomeAssistantConfig::default()
+        };
+        let mut events = Vec::new();
+        let result = listen_state_changes(&ha, |event| events.push(event)).await;
+        server.await.unwrap();
+
+        assert!(result.unwrap_err().to_string().contains("closed"));
+        assert_eq!(
+            events,
+            vec![state_event("lock.front_door", "locked", "unlocked")]
+        );
+    }
+}
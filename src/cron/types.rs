@@ -58,6 +58,37 @@ impl SessionTarget {
     }
 }
 
+/// What to do with fire times missed while the scheduler was not running.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CatchUpPolicy {
+    /// Drop missed runs and wait for the next fire time.
+    Skip,
+    /// Run once for all missed fire times.
+    #[default]
+    Once,
+    /// Run once per missed fire time.
+    All,
+}
+
+impl CatchUpPolicy {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Skip => "skip",
+            Self::Once => "once",
+            Self::All => "all",
+        }
+    }
+
+    pub(crate) fn parse(raw: &str) -> Self {
+        match raw.to_ascii_lowercase().as_str() {
+            "skip" => Self::Skip,
+            "all" => Self::All,
+            _ => Self::Once,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum Schedule {
@@ -72,6 +103,45 @@ pub enum Schedule {
     Every {
         every_ms: u64,
     },
+    /// Fires when a file under `path` (relative to the workspace) is created
+    /// or modified. `pattern` is an optional glob on the file name.
+    Watch {
+        path: String,
+        #[serde(default)]
+        pattern: Option<String>,
+    },
+    /// Fires when an inbound channel message matches every filter that is set.
+    Message {
+        #[serde(default)]
+        channel: Option<String>,
+        #[serde(default)]
+        sender: Option<String>,
+        /// Case-insensitive substring.
+        #[serde(default)]
+        keyword: Option<String>,
+        #[serde(default)]
+        regex: Option<String>,
+    },
+    /// Fires when a Home Assistant entity matching `entity_id` (a glob such
+    /// as `binary_sensor.*_door`) changes state, optionally only from/to the
+    /// given states.
+    State {
+        entity_id: String,
+        #[serde(default)]
+        from: Option<String>,
+        #[serde(default)]
+        to: Option<String>,
+    },
+}
+
+impl Schedule {
+    /// Event triggers run on demand rather than at a computed time.
+    pub fn is_event(&self) -> bool {
+        matches!(
+            self,
+            Self::Watch { .. } | Self::Message { .. } | Self::State { .. }
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -82,25 +152,95 @@ pub struct DeliveryConfig {
     pub channel: Option<String>,
     #[serde(default)]
     pub to: Option<String>,
+    /// Additional recipients, delivered alongside `channel`/`to`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<DeliveryTarget>,
+    /// Message template; `{job_name}`, `{job_id}`, `{status}`, `{duration}`
+    /// and `{output}` are substituted. Defaults to the raw output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Extra send attempts per target after a failure.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub max_retries: u32,
+    /// Which runs are announced.
+    #[serde(default, skip_serializing_if = "DeliveryCondition::is_always")]
+    pub when: DeliveryCondition,
     #[serde(default = "default_true")]
     pub best_effort: bool,
 }
 
+impl DeliveryConfig {
+    /// `channel`/`to` followed by `targets`, skipping incomplete entries.
+    pub fn all_targets(&self) -> Vec<DeliveryTarget> {
+        let primary = match (self.channel.as_deref(), self.to.as_deref()) {
+            (Some(channel), Some(to)) => Some(DeliveryTarget {
+                channel: channel.to_string(),
+                to: to.to_string(),
+            }),
+            _ => None,
+        };
+        primary
+            .into_iter()
+            .chain(self.targets.iter().cloned())
+            .filter(|target| !target.channel.trim().is_empty() && !target.to.trim().is_empty())
+            .collect()
+    }
+}
+
 impl Default for DeliveryConfig {
     fn default() -> Self {
         Self {
             mode: "none".to_string(),
             channel: None,
             to: None,
+            targets: Vec::new(),
+            template: None,
+            max_retries: 0,
+            when: DeliveryCondition::Always,
             best_effort: true,
         }
     }
 }
 
+/// Which runs a job announces.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryCondition {
+    #[default]
+    Always,
+    /// Only failed runs.
+    OnFailure,
+    /// Only runs whose output differs from the previous run's.
+    OnChange,
+}
+
+impl DeliveryCondition {
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn is_always(&self) -> bool {
+        *self == Self::Always
+    }
+}
+
+/// One recipient on a configured channel, e.g. `{channel: "matrix", to: "!room:example.org"}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveryTarget {
+    pub channel: String,
+    pub to: String,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
 fn default_true() -> bool {
     true
 }
 
+fn default_max_concurrent() -> u32 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CronJob {
     pub id: String,
@@ -115,6 +255,19 @@ pub struct CronJob {
     pub enabled: bool,
     pub delivery: DeliveryConfig,
     pub delete_after_run: bool,
+    /// Job to run after a successful run.
+    #[serde(default)]
+    pub on_success: Option<String>,
+    /// Job to run after a failed run.
+    #[serde(default)]
+    pub on_failure: Option<String>,
+    #[serde(default)]
+    pub catch_up: CatchUpPolicy,
+    /// Runs of this job allowed at once; further triggers are skipped.
+    /// Scheduled runs never overlap each other, so values above 1 only
+    /// affect manual runs, event triggers and chains.
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: u32,
     pub created_at: DateTime<Utc>,
     pub next_run: DateTime<Utc>,
     pub last_run: Option<DateTime<Utc>>,
@@ -144,11 +297,17 @@ pub struct CronJobPatch {
     pub model: Option<String>,
     pub session_target: Option<SessionTarget>,
     pub delete_after_run: Option<bool>,
+    /// `""` clears the follow-up job.
+    pub on_success: Option<String>,
+    /// `""` clears the follow-up job.
+    pub on_failure: Option<String>,
+    pub catch_up: Option<CatchUpPolicy>,
+    pub max_concurrent: Option<u32>,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::JobType;
+    use super::{DeliveryConfig, DeliveryTarget, JobType};
 
     #[test]
     fn job_type_try_from_accepts_known_values_case_insensitive() {
@@ -163,4 +322,39 @@ mod tests {
         assert!(JobType::try_from("").is_err());
         assert!(JobType::try_from("unknown").is_err());
     }
+
+    #[test]
+    fn delivery_config_merges_legacy_target_with_targets_list() {
+        let delivery: DeliveryConfig = serde_json::from_str(
+            r#"{"mode":"announce","channel":"telegram","to":"123",
+                "targets":[{"channel":"email","to":"ops@example.org"},{"channel":"irc","to":""}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            delivery.all_targets(),
+            vec![
+                DeliveryTarget {
+                    channel: "telegram".into(),
+                    to: "123".into(),
+                },
+                DeliveryTarget {
+                    channel: "email".into(),
+                    to: "ops@example.org".into(),
+                },
+            ]
+        );
+        assert_eq!(delivery.max_retries, 0);
+        assert!(delivery.best_effort);
+    }
+
+    #[test]
+    fn delivery_config_legacy_json_round_trips_without_new_fields() {
+        let legacy = r#"{"mode":"announce","channel":"slack","to":"C1","best_effort":false}"#;
+        let delivery: DeliveryConfig = serde_json::from_str(legacy).unwrap();
+        let json = serde_json::to_value(&delivery).unwrap();
+        assert!(json.get("targets").is_none());
+        assert!(json.get("template").is_none());
+        assert!(json.get("max_retries").is_none());
+    }
 }
@@ -15,7 +15,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         .channel_max_backoff_secs
         .max(initial_backoff);
 
-    crate::health::mark_component_ok("daemon");
+    crate::health::mark_component_ok("daemon").await;
 
     if config.heartbeat.enabled {
         let _ =
@@ -25,6 +25,43 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
 
+    // Load SOPs before any component starts so the gateway, channels and
+    // scheduler all share one engine.
+    #[cfg(feature = "sop")]
+    let sop_runtime = init_sop_runtime(&config);
+
+    #[cfg(all(feature = "sop", feature = "channel-mqtt"))]
+    if let (Some(sop), Some(mqtt)) = (sop_runtime.clone(), config.channels_config.mqtt.clone()) {
+        if mqtt.topics.is_empty() {
+            tracing::info!("MQTT has no SOP topics configured; SOP listener not started");
+        } else {
+            handles.push(spawn_component_supervisor(
+                "sop_mqtt",
+                initial_backoff,
+                max_backoff,
+                move || {
+                    let mqtt = mqtt.clone();
+                    let sop = sop.clone();
+                    async move {
+                        crate::sop::mqtt::run_mqtt_sop_listener(&mqtt, sop.engine, sop.audit).await
+                    }
+                },
+            ));
+        }
+    }
+    #[cfg(all(feature = "sop", not(feature = "channel-mqtt")))]
+    if sop_runtime.is_some()
+        && config
+            .channels_config
+            .mqtt
+            .as_ref()
+            .is_some_and(|mqtt| !mqtt.topics.is_empty())
+    {
+        tracing::warn!(
+            "MQTT SOP topics are configured but this build was compiled without `channel-mqtt`; skipping SOP listener."
+        );
+    }
+
     {
         let gateway_cfg = config.clone();
         let gateway_host = host.clone();
@@ -53,47 +90,73 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 },
             ));
         } else {
-            crate::health::mark_component_ok("channels");
+            crate::health::mark_component_ok("channels").await;
             tracing::info!("No real-time channels configured; channel supervisor disabled");
         }
     }
 
+    // One router for all scheduled output, so channels are built once.
+    let delivery = std::sync::Arc::new(crate::channels::delivery::DeliveryRouter::from_config(
+        &config,
+    ));
+
     if config.heartbeat.enabled {
         let heartbeat_cfg = config.clone();
+        let heartbeat_delivery = std::sync::Arc::clone(&delivery);
         handles.push(spawn_component_supervisor(
             "heartbeat",
             initial_backoff,
             max_backoff,
             move || {
                 let cfg = heartbeat_cfg.clone();
-                async move { Box::pin(run_heartbeat_worker(cfg)).await }
+                let delivery = std::sync::Arc::clone(&heartbeat_delivery);
+                async move { Box::pin(run_heartbeat_worker(cfg, delivery)).await }
             },
         ));
     }
 
     if config.cron.enabled {
         let scheduler_cfg = config.clone();
+        let scheduler_delivery = std::sync::Arc::clone(&delivery);
         handles.push(spawn_component_supervisor(
             "scheduler",
             initial_backoff,
             max_backoff,
             move || {
                 let cfg = scheduler_cfg.clone();
-                async move { crate::cron::scheduler::run(cfg).await }
+                let delivery = std::sync::Arc::clone(&scheduler_delivery);
+                async move { crate::cron::scheduler::run(cfg, delivery).await }
             },
         ));
     } else {
-        crate::health::mark_component_ok("scheduler");
+        crate::health::mark_component_ok("scheduler").await;
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.cron.enabled && config.home_assistant.enabled {
+        let triggers_cfg = config.clone();
+        let triggers_delivery = std::sync::Arc::clone(&delivery);
+        handles.push(spawn_component_supervisor(
+            "home_assistant",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = triggers_cfg.clone();
+                let delivery = std::sync::Arc::clone(&triggers_delivery);
+                async move {
+                    Box::pin(crate::cron::triggers::run_state_triggers(cfg, delivery)).await
+                }
+            },
+        ));
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
     println!("   Ctrl+C to stop");
 
     tokio::signal::ctrl_c().await?;
-    crate::health::mark_component_error("daemon", "shutdown requested");
+    crate::health::mark_component_error("daemon", "shutdown requested").await;
 
     for handle in &handles {
         handle.abort();
@@ -123,7 +186,7 @@ fn spawn_state_writer(config: Config) -> JoinHandle<()> {
         let mut interval = tokio::time::interval(Duration::from_secs(STATUS_FLUSH_SECONDS));
         loop {
             interval.tick().await;
-            let mut json = crate::health::snapshot_json();
+            let mut json = crate::health::snapshot_json().await;
             if let Some(obj) = json.as_object_mut() {
                 obj.insert(
                     "written_at".into(),
@@ -151,21 +214,22 @@ where
         let max_backoff = max_backoff_secs.max(backoff);
 
         loop {
-            crate::health::mark_component_ok(name);
+            crate::health::mark_component_ok(name).await;
             match run_component().await {
                 Ok(()) => {
-                    crate::health::mark_component_error(name, "component exited unexpectedly");
+                    crate::health::mark_component_error(name, "component exited unexpectedly")
+                        .await;
                     tracing::warn!("Daemon component '{name}' exited unexpectedly");
                     // Clean exit — reset backoff since the component ran successfully
                     backoff = initial_backoff_secs.max(1);
                 }
                 Err(e) => {
-                    crate::health::mark_component_error(name, e.to_string());
+                    crate::health::mark_component_error(name, e.to_string()).await;
                     tracing::error!("Daemon component '{name}' failed: {e}");
                 }
             }
 
-            crate::health::bump_component_restart(name);
+            crate::health::bump_component_restart(name).await;
             tokio::time::sleep(Duration::from_secs(backoff)).await;
             // Double backoff AFTER sleeping so first error uses initial_backoff
             backoff = backoff.saturating_mul(2).min(max_backoff);
@@ -173,7 +237,32 @@ where
     })
 }
 
-async fn run_heartbeat_worker(config: Config) -> Result<()> {
+#[cfg(feature = "sop")]
+fn init_sop_runtime(config: &Config) -> Option<crate::sop::SopRuntime> {
+    if !config.sop.enabled {
+        return None;
+    }
+    match crate::memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    ) {
+        Ok(memory) => Some(crate::sop::shared_runtime(
+            config,
+            std::sync::Arc::from(memory),
+        )),
+        Err(e) => {
+            tracing::error!("SOP engine disabled: memory backend for the audit log failed: {e}");
+            None
+        }
+    }
+}
+
+async fn run_heartbeat_worker(
+    config: Config,
+    router: std::sync::Arc<crate::channels::delivery::DeliveryRouter>,
+) -> Result<()> {
     let observer: std::sync::Arc<dyn crate::observability::Observer> =
         std::sync::Arc::from(crate::observability::create_observer(&config.observability));
     let engine = crate::heartbeat::engine::HeartbeatEngine::new(
@@ -181,7 +270,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         config.workspace_dir.clone(),
         observer,
     );
-    let delivery = heartbeat_delivery_target(&config)?;
+    let delivery = heartbeat_delivery_target(&config)?.map(|target| (router, target));
 
     let interval_mins = config.heartbeat.interval_minutes.max(5);
     let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
@@ -210,31 +299,28 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
             .await
             {
                 Ok(output) => {
-                    crate::health::mark_component_ok("heartbeat");
+                    crate::health::mark_component_ok("heartbeat").await;
                     let announcement = if output.trim().is_empty() {
                         "heartbeat task executed".to_string()
                     } else {
                         output
                     };
-                    if let Some((channel, target)) = &delivery {
-                        if let Err(e) = crate::cron::scheduler::deliver_announcement(
-                            &config,
-                            channel,
-                            target,
-                            &announcement,
-                        )
-                        .await
+                    if let Some((router, target)) = &delivery {
+                        if let Err(e) = router
+                            .deliver(std::slice::from_ref(target), &announcement, None, 0)
+                            .await
                         {
                             crate::health::mark_component_error(
                                 "heartbeat",
                                 format!("delivery failed: {e}"),
-                            );
+                            )
+                            .await;
                             tracing::warn!("Heartbeat delivery failed: {e}");
                         }
                     }
                 }
                 Err(e) => {
-                    crate::health::mark_component_error("heartbeat", e.to_string());
+                    crate::health::mark_component_error("heartbeat", e.to_string()).await;
                     tracing::warn!("Heartbeat task failed: {e}");
                 }
             }
@@ -257,7 +343,7 @@ fn heartbeat_tasks_for_tick(
         .unwrap_or_default()
 }
 
-fn heartbeat_delivery_target(config: &Config) -> Result<Option<(String, String)>> {
+fn heartbeat_delivery_target(config: &Config) -> Result<Option<crate::cron::DeliveryTarget>> {
     let channel = config
         .heartbeat
         .target
@@ -277,45 +363,23 @@ fn heartbeat_delivery_target(config: &Config) -> Result<Option<(String, String)>
         (None, Some(_)) => anyhow::bail!("heartbeat.target is required when heartbeat.to is set"),
         (Some(channel), Some(target)) => {
             validate_heartbeat_channel_config(config, channel)?;
-            Ok(Some((channel.to_string(), target.to_string())))
+            Ok(Some(crate::cron::DeliveryTarget {
+                channel: channel.to_string(),
+                to: target.to_string(),
+            }))
         }
     }
 }
 
 fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<()> {
-    match channel.to_ascii_lowercase().as_str() {
-        "telegram" => {
-            if config.channels_config.telegram.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to telegram but channels_config.telegram is not configured"
-                );
-            }
-        }
-        "discord" => {
-            if config.channels_config.discord.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to discord but channels_config.discord is not configured"
-                );
-            }
-        }
-        "slack" => {
-            if config.channels_config.slack.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to slack but channels_config.slack is not configured"
-                );
-            }
-        }
-        "mattermost" => {
-            if config.channels_config.mattermost.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to mattermost but channels_config.mattermost is not configured"
-                );
-            }
-        }
-        other => anyhow::bail!("unsupported heartbeat.target channel: {other}"),
+    match crate::channels::delivery::channel_config_present(config, channel) {
+        Some(true) => Ok(()),
+        Some(false) => anyhow::bail!(
+            "heartbeat.target is set to {channel} but channels_config.{} is not configured",
+            channel.to_ascii_lowercase()
+        ),
+        None => anyhow::bail!("unsupported heartbeat.target channel: {channel}"),
     }
-
-    Ok(())
 }
 
 fn has_supervised_channels(config: &Config) -> bool {
@@ -360,7 +424,7 @@ mod tests {
         handle.abort();
         let _ = handle.await;
 
-        let snapshot = crate::health::snapshot_json();
+        let snapshot = crate::health::snapshot_json().await;
         let component = &snapshot["components"]["daemon-test-fail"];
         assert_eq!(component["status"], "error");
         assert!(component["restart_count"].as_u64().unwrap_or(0) >= 1);
@@ -378,7 +442,7 @@ mod tests {
         handle.abort();
         let _ = handle.await;
 
-        let snapshot = crate::health::snapshot_json();
+        let snapshot = crate::health::snapshot_json().await;
         let component = &snapshot["components"]["daemon-test-exit"];
         assert_eq!(component["status"], "error");
         assert!(component["restart_count"].as_u64().unwrap_or(0) >= 1);
@@ -505,13 +569,29 @@ mod tests {
 
     #[test]
     fn heartbeat_delivery_target_rejects_unsupported_channel() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("pager".into());
+        config.heartbeat.to = Some("ops".into());
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported heartbeat.target channel"));
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_accepts_any_send_capable_channel() {
         let mut config = Config::default();
         config.heartbeat.target = Some("email".into());
         config.heartbeat.to = Some("ops@example.com".into());
         let err = heartbeat_delivery_target(&config).unwrap_err();
         assert!(err
             .to_string()
-            .contains("unsupported heartbeat.target channel"));
+            .contains("channels_config.email is not configured"));
+
+        config.channels_config.email = Some(crate::channels::email_channel::EmailConfig::default());
+        let target = heartbeat_delivery_target(&config).unwrap().unwrap();
+        assert_eq!(target.channel, "email");
+        assert_eq!(target.to, "ops@example.com");
     }
 
     #[test]
@@ -540,6 +620,12 @@ mod tests {
         });
 
         let target = heartbeat_delivery_target(&config).unwrap();
-        assert_eq!(target, Some(("telegram".to_string(), "123456".to_string())));
+        assert_eq!(
+            target,
+            Some(crate::cron::DeliveryTarget {
+                channel: "telegram".into(),
+                to: "123456".into(),
+            })
+        );
     }
 }
@@ -0,0 +1,954 @@
+//! Agent-to-Agent (A2A) protocol endpoint.
+//!
+//! When `[a2a].enabled` is set, the gateway publishes an agent card at
+//! `/.well-known/agent-card.json` (and the older `/.well-known/agent.json`)
+//! and serves A2A JSON-RPC at `POST /a2a`:
+//!
+//! - `message/send` — start a task and wait up to [`BLOCKING_WAIT_SECS`] for
+//!   it to finish; with `configuration.blocking = false` the submitted task
+//!   is returned immediately and the client polls `tasks/get`.
+//! - `message/stream` / `tasks/resubscribe` — the task, then status and
+//!   artifact updates, as an SSE stream of JSON-RPC responses.
+//! - `tasks/get` — poll a task.
+//! - `tasks/cancel` — cancel a running task.
+//!
+//! Each task runs one [`Agent::turn`]. Messages carrying a `contextId` reuse
+//! that context's agent, so follow-ups keep the conversation history.
+
+use super::AppState;
+use crate::agent::Agent;
+use crate::config::{A2aConfig, Config};
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// A2A protocol version implemented by this endpoint.
+pub const PROTOCOL_VERSION: &str = "0.3.0";
+/// How long a blocking `message/send` waits before returning a still-running
+/// task. Stays below the gateway request timeout.
+pub const BLOCKING_WAIT_SECS: u64 = 25;
+/// Finished tasks kept for `tasks/get`; the oldest are dropped first.
+const MAX_RETAINED_TASKS: usize = 256;
+/// Conversation contexts kept alive; the oldest is dropped first.
+const MAX_CONTEXTS: usize = 32;
+
+const DEFAULT_AGENT_NAME: &str = "ZeroClaw";
+const DEFAULT_AGENT_DESCRIPTION: &str =
+    "Autonomous assistant with tools, memory and scheduling, running on ZeroClaw.";
+
+// JSON-RPC and A2A error codes.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const TASK_NOT_FOUND: i64 = -32001;
+const TASK_NOT_CANCELABLE: i64 = -32002;
+const CONTENT_TYPE_NOT_SUPPORTED: i64 = -32005;
+
+// ── Protocol types ──────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskState {
+    Submitted,
+    Working,
+    Completed,
+    Failed,
+    Canceled,
+}
+
+impl TaskState {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Canceled)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Part {
+    Text { text: String },
+    Data { data: Value },
+    File { file: Value },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub role: String,
+    pub parts: Vec<Part>,
+    #[serde(default)]
+    pub message_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+    #[serde(default = "message_kind")]
+    pub kind: String,
+}
+
+fn message_kind() -> String {
+    "message".into()
+}
+
+impl Message {
+    fn agent_text(text: impl Into<String>, task_id: &str, context_id: &str) -> Self {
+        Self {
+            role: "agent".into(),
+            parts: vec![Part::Text { text: text.into() }],
+            message_id: Uuid::new_v4().to_string(),
+            task_id: Some(task_id.into()),
+            context_id: Some(context_id.into()),
+            kind: message_kind(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatus {
+    pub state: TaskState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+    pub timestamp: String,
+}
+
+impl TaskStatus {
+    fn new(state: TaskState, message: Option<Message>) -> Self {
+        Self {
+            state,
+            message,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    pub artifact_id: String,
+    pub name: String,
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub id: String,
+    pub context_id: String,
+    pub status: TaskStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+    pub history: Vec<Message>,
+    pub kind: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    #[serde(default)]
+    jsonrpc: String,
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageSendParams {
+    message: Message,
+    #[serde(default)]
+    configuration: Option<SendConfiguration>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SendConfiguration {
+    #[serde(default)]
+    blocking: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskQueryParams {
+    id: String,
+    #[serde(default)]
+    history_length: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn rpc_result(id: &Value, result: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "result": result})
+}
+
+fn rpc_error(id: &Value, error: &RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": error.code, "message": error.message},
+    })
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(INVALID_PARAMS, format!("Invalid params: {e}")))
+}
+
+/// Prompt text for the agent: text parts verbatim, data parts as JSON.
+fn message_text(parts: &[Part]) -> Result<String, RpcError> {
+    let mut chunks = Vec::with_capacity(parts.len());
+    for part in parts {
+        match part {
+            Part::Text { text } => chunks.push(text.clone()),
+            Part::Data { data } => chunks.push(data.to_string()),
+            Part::File { .. } => {
+                return Err(RpcError::new(
+                    CONTENT_TYPE_NOT_SUPPORTED,
+                    "File parts are not supported; send text or data parts",
+                ))
+            }
+        }
+    }
+    let text = chunks.join("\n");
+    if text.trim().is_empty() {
+        return Err(RpcError::new(
+            INVALID_PARAMS,
+            "Message has no text or data parts",
+        ));
+    }
+    Ok(text)
+}
+
+// ── Agent card ──────────────────────────────────────────────────
+
+/// Agent card for this gateway. `base_url` is the gateway origin.
+pub fn agent_card(config: &A2aConfig, base_url: &str, require_auth: bool) -> Value {
+    let name = config
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(DEFAULT_AGENT_NAME);
+    let description = config
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+        .unwrap_or(DEFAULT_AGENT_DESCRIPTION);
+
+    let mut card = json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "name": name,
+        "description": description,
+        "url": format!("{}/a2a", base_url.trim_end_matches('/')),
+        "preferredTransport": "JSONRPC",
+        "version": env!("CARGO_PKG_VERSION"),
+        "capabilities": {
+            "streaming": true,
+            "pushNotifications": false,
+            "stateTransitionHistory": false,
+        },
+        "defaultInputModes": ["text/plain", "application/json"],
+        "defaultOutputModes": ["text/plain"],
+        "skills": [{
+            "id": "general",
+            "name": "General assistant",
+            "description": description,
+            "tags": ["chat", "tools", "memory"],
+        }],
+    });
+    if require_auth {
+        card["securitySchemes"] = json!({
+            "bearer": {
+                "type": "http",
+                "scheme": "bearer",
+                "description": "ZeroClaw pairing or API token",
+            }
+        });
+        card["security"] = json!([{"bearer": []}]);
+    }
+    card
+}
+
+fn request_base_url(config: &Config, headers: &HeaderMap) -> String {
+    if let Some(url) = config
+        .a2a
+        .public_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+    {
+        return url.trim_end_matches('/').to_string();
+    }
+    let scheme = if config.gateway.tls.enabled {
+        "https"
+    } else {
+        "http"
+    };
+    let host = headers
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("localhost");
+    format!("{scheme}://{host}")
+}
+
+// ── Task registry ───────────────────────────────────────────────
+
+type AgentFactory = Box<dyn Fn(&Config) -> anyhow::Result<Agent> + Send + Sync>;
+
+struct TaskEntry {
+    updates: Arc<watch::Sender<Task>>,
+    abort: Option<tokio::task::AbortHandle>,
+}
+
+#[derive(Default)]
+struct Registry {
+    tasks: HashMap<String, TaskEntry>,
+    task_order: VecDeque<String>,
+    contexts: HashMap<String, Arc<tokio::sync::Mutex<Agent>>>,
+    context_order: VecDeque<String>,
+}
+
+impl Registry {
+    /// Keep `agent` for `context_id` and return it, or return the agent a
+    /// concurrent request registered for the context first.
+    fn insert_context(&mut self, context_id: &str, agent: Agent) -> Arc<tokio::sync::Mutex<Agent>> {
+        if let Some(existing) = self.contexts.get(context_id) {
+            return existing.clone();
+        }
+        let agent = Arc::new(tokio::sync::Mutex::new(agent));
+        self.contexts.insert(context_id.to_string(), agent.clone());
+        self.context_order.push_back(context_id.to_string());
+        while self.context_order.len() > MAX_CONTEXTS {
+            if let Some(oldest) = self.context_order.pop_front() {
+                self.contexts.remove(&oldest);
+            }
+        }
+        agent
+    }
+
+    fn insert_task(&mut self, id: String, entry: TaskEntry) {
+        self.tasks.insert(id.clone(), entry);
+        self.task_order.push_back(id);
+        if self.task_order.len() <= MAX_RETAINED_TASKS {
+            return;
+        }
+        // Drop the oldest finished task; running tasks are never evicted.
+        let finished = self.task_order.iter().position(|id| {
+            self.tasks
+                .get(id)
+                .is_none_or(|entry| entry.updates.borrow().status.state.is_terminal())
+        });
+        if let Some(index) = finished {
+            if let Some(id) = self.task_order.remove(index) {
+                self.tasks.remove(&id);
+            }
+        }
+    }
+}
+
+/// A2A tasks submitted to this gateway and the agents serving their contexts.
+pub struct A2aTasks {
+    factory: AgentFactory,
+    registry: Mutex<Registry>,
+}
+
+impl Default for A2aTasks {
+    fn default() -> Self {
+        Self::with_agent_factory(Box::new(Agent::from_config))
+    }
+}
+
+impl A2aTasks {
+    /// Registry that builds context agents with `factory` instead of
+    /// [`Agent::from_config`].
+    pub fn with_agent_factory(factory: AgentFactory) -> Self {
+        Self {
+            factory,
+            registry: Mutex::new(Registry::default()),
+        }
+    }
+
+    /// Start a task for `message` and return a receiver for its updates.
+    fn start(&self, config: &Config, message: Message) -> Result<watch::Receiver<Task>, RpcError> {
+        let text = message_text(&message.parts)?;
+
+        let (context_id, existing) = {
+            let registry = self.registry.lock();
+            // A follow-up that names only its task continues that task's context.
+            let context_id = message
+                .context_id
+                .clone()
+                .or_else(|| {
+                    message.task_id.as_ref().and_then(|id| {
+                        registry
+                            .tasks
+                            .get(id)
+                            .map(|entry| entry.updates.borrow().context_id.clone())
+                    })
+                })
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            let existing = registry.contexts.get(&context_id).cloned();
+            (context_id, existing)
+        };
+        let agent = match existing {
+            Some(agent) => agent,
+            None => {
+                // Built without the registry lock: creating an agent sets up
+                // providers, memory and tools.
+                let agent = (self.factory)(config).map_err(|e| {
+                    RpcError::new(INTERNAL_ERROR, format!("Failed to start agent: {e}"))
+                })?;
+                self.registry.lock().insert_context(&context_id, agent)
+            }
+        };
+
+        let task_id = Uuid::new_v4().to_string();
+        let user_message = Message {
+            role: "user".into(),
+            task_id: Some(task_id.clone()),
+            context_id: Some(context_id.clone()),
+            message_id: if message.message_id.is_empty() {
+                Uuid::new_v4().to_string()
+            } else {
+                message.message_id
+            },
+            ..message
+        };
+        let (tx, rx) = watch::channel(Task {
+            id: task_id.clone(),
+            context_id,
+            status: TaskStatus::new(TaskState::Submitted, None),
+            artifacts: Vec::new(),
+            history: vec![user_message],
+            kind: "task",
+        });
+        let updates = Arc::new(tx);
+
+        let worker = updates.clone();
+        let handle = tokio::spawn(async move {
+            worker.send_modify(|task| task.status = TaskStatus::new(TaskState::Working, None));
+            let result = agent.lock().await.turn(&text).await;
+            worker.send_if_modified(|task| {
+                if task.status.state.is_terminal() {
+                    return false;
+                }
+                match &result {
+                    Ok(reply) => {
+                        let reply = Message::agent_text(reply, &task.id, &task.context_id);
+                        task.artifacts.push(Artifact {
+                            artifact_id: Uuid::new_v4().to_string(),
+                            name: "response".into(),
+                            parts: reply.parts.clone(),
+                        });
+                        task.history.push(reply.clone());
+                        task.status = TaskStatus::new(TaskState::Completed, Some(reply));
+                    }
+                    Err(e) => {
+                        let error = Message::agent_text(
+                            format!("Agent error: {e}"),
+                            &task.id,
+                            &task.context_id,
+                        );
+                        task.status = TaskStatus::new(TaskState::Failed, Some(error));
+                    }
+                }
+                true
+            });
+        });
+
+        self.registry.lock().insert_task(
+            task_id,
+            TaskEntry {
+                updates,
+                abort: Some(handle.abort_handle()),
+            },
+        );
+        Ok(rx)
+    }
+
+    fn subscribe(&self, id: &str) -> Result<watch::Receiver<Task>, RpcError> {
+        self.registry
+            .lock()
+            .tasks
+            .get(id)
+            .map(|entry| entry.updates.subscribe())
+            .ok_or_else(|| RpcError::new(TASK_NOT_FOUND, format!("Task not found: {id}")))
+    }
+
+    fn get(&self, id: &str) -> Result<Task, RpcError> {
+        let task = self.subscribe(id)?.borrow().clone();
+        Ok(task)
+    }
+
+    fn cancel(&self, id: &str) -> Result<Task, RpcError> {
+        let mut registry = self.registry.lock();
+        let entry = registry
+            .tasks
+            .get_mut(id)
+            .ok_or_else(|| RpcError::new(TASK_NOT_FOUND, format!("Task not found: {id}")))?;
+        let canceled = entry.updates.send_if_modified(|task| {
+            if task.status.state.is_terminal() {
+                return false;
+            }
+            task.status = TaskStatus::new(TaskState::Canceled, None);
+            true
+        });
+        if !canceled {
+            let state = entry.updates.borrow().status.state;
+            return Err(RpcError::new(
+                TASK_NOT_CANCELABLE,
+                format!("Task {id} is already {}", task_state_label(state)),
+            ));
+        }
+        if let Some(abort) = entry.abort.take() {
+            abort.abort();
+        }
+        let task = entry.updates.borrow().clone();
+        Ok(task)
+    }
+}
+
+fn task_state_label(state: TaskState) -> String {
+    serde_json::to_value(state)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn with_history_length(mut task: Task, history_length: Option<usize>) -> Task {
+    if let Some(keep) = history_length {
+        let skip = task.history.len().saturating_sub(keep);
+        task.history.drain(..skip);
+    }
+    task
+}
+
+fn task_value(task: &Task) -> Value {
+    serde_json::to_value(task).unwrap_or(Value::Null)
+}
+
+// ── Handlers ────────────────────────────────────────────────────
+
+fn disabled_response() -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(json!({"error": "A2A endpoint is disabled — set [a2a] enabled = true"})),
+    )
+        .into_response()
+}
+
+/// GET /.well-known/agent-card.json (and /.well-known/agent.json) — agent card
+pub async fn handle_a2a_agent_card(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let (a2a, base_url) = {
+        let config = state.config.lock();
+        (config.a2a.clone(), request_base_url(&config, &headers))
+    };
+    if !a2a.enabled {
+        return disabled_response();
+    }
+    Json(agent_card(&a2a, &base_url, state.pairing.require_pairing())).into_response()
+}
+
+/// POST /a2a — A2A JSON-RPC endpoint
+pub async fn handle_a2a_rpc(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let config = state.config.lock().clone();
+    if !config.a2a.enabled {
+        return disabled_response();
+    }
+
+    let rate_key =
+        super::client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/a2a rate limit exceeded");
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(json!({"error": "Too many requests. Please retry later."})),
+        )
+            .into_response();
+    }
+
+    if state.pairing.require_pairing() {
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "))
+            .unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            tracing::warn!("/a2a: rejected — not paired / invalid bearer token");
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({
+                    "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let request: JsonRpcRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = RpcError::new(PARSE_ERROR, format!("Invalid JSON-RPC request: {e}"));
+            return Json(rpc_error(&Value::Null, &error)).into_response();
+        }
+    };
+    if request.jsonrpc != "2.0" {
+        let error = RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\"");
+        return Json(rpc_error(&request.id, &error)).into_response();
+    }
+
+    let id = request.id;
+    let outcome = match request.method.as_str() {
+        "message/send" => match parse_params::<MessageSendParams>(request.params) {
+            Ok(params) => {
+                let blocking = params
+                    .configuration
+                    .unwrap_or_default()
+                    .blocking
+                    .unwrap_or(true);
+                match state.a2a.start(&config, params.message) {
+                    Ok(mut rx) => {
+                        if blocking {
+                            let wait = Duration::from_secs(BLOCKING_WAIT_SECS);
+                            let _ = tokio::time::timeout(
+                                wait,
+                                rx.wait_for(|task| task.status.state.is_terminal()),
+                            )
+                            .await;
+                        }
+                        let task = rx.borrow().clone();
+                        Ok(task_value(&task))
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        },
+        "message/stream" => {
+            let started = parse_params::<MessageSendParams>(request.params)
+                .and_then(|params| state.a2a.start(&config, params.message));
+            match started {
+                Ok(rx) => return task_event_stream(id, rx),
+                Err(e) => Err(e),
+            }
+        }
+        "tasks/resubscribe" => {
+            let subscribed = parse_params::<TaskQueryParams>(request.params)
+                .and_then(|params| state.a2a.subscribe(&params.id));
+            match subscribed {
+                Ok(rx) => return task_event_stream(id, rx),
+                Err(e) => Err(e),
+            }
+        }
+        "tasks/get" => parse_params::<TaskQueryParams>(request.params).and_then(|params| {
+            let task = state.a2a.get(&params.id)?;
+            Ok(task_value(&with_history_length(
+                task,
+                params.history_length,
+            )))
+        }),
+        "tasks/cancel" => parse_params::<TaskQueryParams>(request.params)
+            .and_then(|params| state.a2a.cancel(&params.id))
+            .map(|task| task_value(&task)),
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {other}"),
+        )),
+    };
+
+    match outcome {
+        Ok(result) => Json(rpc_result(&id, result)).into_response(),
+        Err(error) => Json(rpc_error(&id, &error)).into_response(),
+    }
+}
+
+/// SSE stream of JSON-RPC responses: the task itself, then artifact and
+/// status updates until the task reaches a final state.
+fn task_event_stream(id: Value, mut rx: watch::Receiver<Task>) -> Response {
+    let (tx, events) = tokio::sync::mpsc::channel::<Value>(16);
+    tokio::spawn(async move {
+        let first = rx.borrow_and_update().clone();
+        let mut sent_artifacts = first.artifacts.len();
+        let mut finished = first.status.state.is_terminal();
+        if tx.send(rpc_result(&id, task_value(&first))).await.is_err() {
+            return;
+        }
+
+        while !finished && rx.changed().await.is_ok() {
+            let task = rx.borrow_and_update().clone();
+            for artifact in task.artifacts.iter().skip(sent_artifacts) {
+                let event = json!({
+                    "kind": "artifact-update",
+                    "taskId": task.id,
+                    "contextId": task.context_id,
+                    "artifact": artifact,
+                    "lastChunk": true,
+                });
+                if tx.send(rpc_result(&id, event)).await.is_err() {
+                    return;
+                }
+            }
+            sent_artifacts = task.artifacts.len();
+            finished = task.status.state.is_terminal();
+            let event = json!({
+                "kind": "status-update",
+                "taskId": task.id,
+                "contextId": task.context_id,
+                "status": task.status,
+                "final": finished,
+            });
+            if tx.send(rpc_result(&id, event)).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    let stream = ReceiverStream::new(events)
+        .map(|value| Ok::<_, Infallible>(Event::default().data(value.to_string())));
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::dispatcher::NativeToolDispatcher;
+    use crate::memory::NoneMemory;
+    use crate::observability::NoopObserver;
+    use crate::providers::Provider;
+    use async_trait::async_trait;
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl Provider for EchoProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(format!("echo: {message}"))
+        }
+    }
+
+    fn echo_agent() -> anyhow::Result<Agent> {
+        Agent::builder()
+            .provider(Box::new(EchoProvider))
+            .tools(Vec::new())
+            .memory(Arc::new(NoneMemory::new()))
+            .observer(Arc::new(NoopObserver {}))
+            .tool_dispatcher(Box::new(NativeToolDispatcher))
+            .workspace_dir(std::env::temp_dir())
+            .build()
+    }
+
+    fn echo_tasks() -> A2aTasks {
+        A2aTasks::with_agent_factory(Box::new(|_config| echo_agent()))
+    }
+
+    fn user_message(text: &str, context_id: Option<&str>) -> Message {
+        Message {
+            role: "user".into(),
+            parts: vec![Part::Text { text: text.into() }],
+            message_id: "m1".into(),
+            task_id: None,
+            context_id: context_id.map(str::to_string),
+            kind: message_kind(),
+        }
+    }
+
+    async fn finished(mut rx: watch::Receiver<Task>) -> Task {
+        rx.wait_for(|task| task.status.state.is_terminal())
+            .await
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn agent_card_advertises_endpoint_and_bearer_auth() {
+        let config = A2aConfig {
+            name: Some("Helper".into()),
+            ..A2aConfig::default()
+        };
+        let card = agent_card(&config, "https://agent.example.com/", true);
+        assert_eq!(card["name"], "Helper");
+        assert_eq!(card["url"], "https://agent.example.com/a2a");
+        assert_eq!(card["capabilities"]["streaming"], true);
+        assert_eq!(card["securitySchemes"]["bearer"]["scheme"], "bearer");
+
+        let open = agent_card(&A2aConfig::default(), "http://127.0.0.1:42617", false);
+        assert_eq!(open["name"], DEFAULT_AGENT_NAME);
+        assert!(open.get("security").is_none());
+    }
+
+    #[test]
+    fn message_text_joins_parts_and_rejects_files() {
+        let parts = vec![
+            Part::Text {
+                text: "Summarize".into(),
+            },
+            Part::Data {
+                data: json!({"rows": 2}),
+            },
+        ];
+        assert_eq!(message_text(&parts).unwrap(), "Summarize\n{\"rows\":2}");
+
+        let file = vec![Part::File {
+            file: json!({"uri": "file:///tmp/a.txt"}),
+        }];
+        assert_eq!(
+            message_text(&file).unwrap_err().code,
+            CONTENT_TYPE_NOT_SUPPORTED
+        );
+        assert_eq!(message_text(&[]).unwrap_err().code, INVALID_PARAMS);
+    }
+
+    #[test]
+    fn message_deserializes_a2a_wire_format() {
+        let message: Message = serde_json::from_value(json!({
+            "role": "user",
+            "parts": [{"kind": "text", "text": "hi"}],
+            "messageId": "abc",
+            "contextId": "ctx",
+            "kind": "message",
+        }))
+        .unwrap();
+        assert_eq!(message.message_id, "abc");
+        assert_eq!(message.context_id.as_deref(), Some("ctx"));
+        assert_eq!(message.parts, vec![Part::Text { text: "hi".into() }]);
+    }
+
+    #[tokio::test]
+    async fn task_completes_with_agent_reply_artifact() {
+        let tasks = echo_tasks();
+        let rx = tasks
+            .start(&Config::default(), user_message("hello", None))
+            .unwrap();
+        let task = finished(rx).await;
+
+        assert_eq!(task.status.state, TaskState::Completed);
+        assert_eq!(task.history.len(), 2);
+        // The agent stamps user turns with the current time.
+        let Part::Text { text } = &task.artifacts[0].parts[0] else {
+            panic!("expected a text artifact");
+        };
+        assert!(text.starts_with("echo: ") && text.ends_with("hello"));
+        let value = task_value(&tasks.get(&task.id).unwrap());
+        assert_eq!(value["kind"], "task");
+        assert_eq!(value["status"]["state"], "completed");
+        assert_eq!(value["contextId"], task.context_id);
+    }
+
+    #[tokio::test]
+    async fn follow_ups_in_a_context_share_one_agent() {
+        let tasks = echo_tasks();
+        let first = finished(
+            tasks
+                .start(&Config::default(), user_message("one", Some("ctx-1")))
+                .unwrap(),
+        )
+        .await;
+        let second = finished(
+            tasks
+                .start(&Config::default(), user_message("two", Some("ctx-1")))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(first.context_id, "ctx-1");
+        assert_eq!(second.context_id, "ctx-1");
+        assert_ne!(first.id, second.id);
+        assert_eq!(tasks.registry.lock().contexts.len(), 1);
+    }
+
+    #[test]
+    fn racing_context_inserts_keep_the_first_agent() {
+        let mut registry = Registry::default();
+        let first = registry.insert_context("ctx-1", echo_agent().unwrap());
+        let second = registry.insert_context("ctx-1", echo_agent().unwrap());
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(registry.contexts.len(), 1);
+        assert_eq!(registry.context_order.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn finished_tasks_cannot_be_canceled() {
+        let tasks = echo_tasks();
+        let task = finished(
+            tasks
+                .start(&Config::default(), user_message("hi", None))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(
+            tasks.cancel(&task.id).unwrap_err().code,
+            TASK_NOT_CANCELABLE
+        );
+        assert_eq!(tasks.get("missing").unwrap_err().code, TASK_NOT_FOUND);
+    }
+
+    #[test]
+    fn history_length_keeps_latest_messages() {
+        let task = Task {
+            id: "t".into(),
+            context_id: "c".into(),
+            status: TaskStatus::new(TaskState::Completed, None),
+            artifacts: Vec::new(),
+            history: vec![
+                user_message("one", None),
+                user_message("two", None),
+                user_message("three", None),
+            ],
+            kind: "task",
+        };
+        let trimmed = with_history_length(task, Some(1));
+        assert_eq!(
+            trimmed.history[0].parts,
+            vec![Part::Text {
+                text: "three".into()
+            }]
+        );
+    }
+}
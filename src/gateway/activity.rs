@@ -0,0 +1,417 @@
+//! Filtering and pagination for the dashboard activity endpoints
+//! (`/api/audit`, `/api/approvals`, `/api/traces`), plus forwarding of new
+//! entries onto the `/api/events` stream for live tail.
+
+use crate::approval::{ApprovalLogEntry, ApprovalResponse};
+use crate::observability::runtime_trace::RuntimeTraceEvent;
+use crate::security::audit::{AuditEvent, AuditEventType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+const DEFAULT_PAGE_LIMIT: usize = 100;
+const MAX_PAGE_LIMIT: usize = 1000;
+/// Newest records the file-backed endpoints load and filter per request.
+pub const MAX_SCANNED_RECORDS: usize = 10_000;
+
+/// How an activity record ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Success,
+    Failure,
+    Approved,
+    Denied,
+}
+
+/// Query parameters shared by the activity endpoints.
+#[derive(Debug, Default, Deserialize)]
+pub struct ActivityQuery {
+    /// Only records at or after this RFC 3339 time.
+    pub since: Option<DateTime<Utc>>,
+    /// Only records before this RFC 3339 time.
+    pub until: Option<DateTime<Utc>>,
+    pub channel: Option<String>,
+    pub tool: Option<String>,
+    pub outcome: Option<Outcome>,
+    /// Page size. Default: 100, at most 1000.
+    pub limit: Option<usize>,
+    /// Records to skip, counted from the newest.
+    pub offset: Option<usize>,
+}
+
+/// One page of records, newest first.
+#[derive(Debug, Serialize)]
+pub struct ActivityPage<T> {
+    pub items: Vec<T>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+    pub next_offset: Option<usize>,
+}
+
+/// Fields the activity filters look at.
+pub trait ActivityRecord {
+    fn timestamp(&self) -> Option<DateTime<Utc>>;
+    fn channel(&self) -> Option<&str>;
+    fn matches_tool(&self, tool: &str) -> bool;
+    fn matches_outcome(&self, outcome: Outcome) -> bool;
+}
+
+impl ActivityQuery {
+    pub fn matches(&self, record: &impl ActivityRecord) -> bool {
+        if self.since.is_some() || self.until.is_some() {
+            let Some(at) = record.timestamp() else {
+                return false;
+            };
+            if self.since.is_some_and(|since| at < since) || self.until.is_some_and(|u| at >= u) {
+                return false;
+            }
+        }
+        if let Some(channel) = self.channel.as_deref().filter(|c| !c.is_empty()) {
+            if !record
+                .channel()
+                .is_some_and(|c| c.eq_ignore_ascii_case(channel))
+            {
+                return false;
+            }
+        }
+        if let Some(tool) = self.tool.as_deref().filter(|t| !t.is_empty()) {
+            if !record.matches_tool(tool) {
+                return false;
+            }
+        }
+        self.outcome
+            .is_none_or(|outcome| record.matches_outcome(outcome))
+    }
+}
+
+/// Filter `records` (oldest first) and return the requested page, newest first.
+pub fn page<T: ActivityRecord>(records: Vec<T>, query: &ActivityQuery) -> ActivityPage<T> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_LIMIT)
+        .clamp(1, MAX_PAGE_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    let mut matching: Vec<T> = records.into_iter().filter(|r| query.matches(r)).collect();
+    let total = matching.len();
+    matching.reverse();
+    let items: Vec<T> = matching.into_iter().skip(offset).take(limit).collect();
+    let next_offset = (offset + items.len() < total).then_some(offset + items.len());
+
+    ActivityPage {
+        items,
+        total,
+        offset,
+        limit,
+        next_offset,
+    }
+}
+
+impl ActivityRecord for AuditEvent {
+    fn timestamp(&self) -> Option<DateTime<Utc>> {
+        Some(self.timestamp)
+    }
+
+    fn channel(&self) -> Option<&str> {
+        self.actor.as_ref().map(|actor| actor.channel.as_str())
+    }
+
+    /// Audit entries record a command rather than a tool; match its start
+    /// (`git` matches `git status`).
+    fn matches_tool(&self, tool: &str) -> bool {
+        self.action
+            .as_ref()
+            .and_then(|action| action.command.as_deref())
+            .and_then(|command| command.get(..tool.len()))
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(tool))
+    }
+
+    fn matches_outcome(&self, outcome: Outcome) -> bool {
+        let denied = matches!(
+            self.event_type,
+            AuditEventType::AuthFailure | AuditEventType::PolicyViolation
+        ) || self.action.as_ref().is_some_and(|action| !action.allowed);
+        let succeeded = self
+            .result
+            .as_ref()
+            .map_or(!denied, |result| result.success);
+        match outcome {
+            Outcome::Success => succeeded,
+            Outcome::Failure => !succeeded,
+            Outcome::Approved => self.action.as_ref().is_some_and(|action| action.approved),
+            Outcome::Denied => denied,
+        }
+    }
+}
+
+impl ActivityRecord for ApprovalLogEntry {
+    fn timestamp(&self) -> Option<DateTime<Utc>> {
+        parse_rfc3339(&self.timestamp)
+    }
+
+    fn channel(&self) -> Option<&str> {
+        Some(&self.channel)
+    }
+
+    fn matches_tool(&self, tool: &str) -> bool {
+        self.tool_name.eq_ignore_ascii_case(tool)
+    }
+
+    fn matches_outcome(&self, outcome: Outcome) -> bool {
+        let approved = self.decision != ApprovalResponse::No;
+        match outcome {
+            Outcome::Success | Outcome::Approved => approved,
+            Outcome::Failure | Outcome::Denied => !approved,
+        }
+    }
+}
+
+impl ActivityRecord for RuntimeTraceEvent {
+    fn timestamp(&self) -> Option<DateTime<Utc>> {
+        parse_rfc3339(&self.timestamp)
+    }
+
+    fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    fn matches_tool(&self, tool: &str) -> bool {
+        self.payload["tool"]
+            .as_str()
+            .is_some_and(|name| name.eq_ignore_ascii_case(tool))
+    }
+
+    fn matches_outcome(&self, outcome: Outcome) -> bool {
+        match outcome {
+            Outcome::Success => self.success == Some(true),
+            Outcome::Failure => self.success == Some(false),
+            Outcome::Approved => false,
+            Outcome::Denied => {
+                self.success == Some(false)
+                    && self
+                        .message
+                        .as_deref()
+                        .is_some_and(|m| m.starts_with("Denied"))
+            }
+        }
+    }
+}
+
+fn parse_rfc3339(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Whether a `{"type": ..., "event": {...}}` value from the event stream
+/// passes `query`. With a filter set, only audit, approval and trace events
+/// can match.
+pub fn event_matches(query: &ActivityQuery, value: &serde_json::Value) -> bool {
+    let filtered = query.channel.as_deref().is_some_and(|c| !c.is_empty())
+        || query.tool.as_deref().is_some_and(|t| !t.is_empty())
+        || query.outcome.is_some();
+    if !filtered {
+        return true;
+    }
+    let event = value["event"].clone();
+    match value["type"].as_str() {
+        Some("audit") => {
+            serde_json::from_value::<AuditEvent>(event).is_ok_and(|event| query.matches(&event))
+        }
+        Some("approval") => serde_json::from_value::<ApprovalLogEntry>(event)
+            .is_ok_and(|event| query.matches(&event)),
+        Some("trace") => serde_json::from_value::<RuntimeTraceEvent>(event)
+            .is_ok_and(|event| query.matches(&event)),
+        _ => false,
+    }
+}
+
+/// Forward audit entries, approval decisions and runtime traces onto the
+/// gateway event stream as `{"type":"audit"|"approval"|"trace","event":{...}}`.
+pub fn spawn_event_forwarders(event_tx: &broadcast::Sender<serde_json::Value>) {
+    forward(
+        crate::security::audit::subscribe(),
+        event_tx.clone(),
+        "audit",
+    );
+    forward(
+        crate::approval::subscribe_decisions(),
+        event_tx.clone(),
+        "approval",
+    );
+    forward(
+        crate::observability::runtime_trace::subscribe(),
+        event_tx.clone(),
+        "trace",
+    );
+}
+
+fn forward<T>(
+    mut rx: broadcast::Receiver<T>,
+    event_tx: broadcast::Sender<serde_json::Value>,
+    kind: &'static str,
+) where
+    T: Serialize + Clone + Send + 'static,
+{
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    let _ = event_tx.send(serde_json::json!({"type": kind, "event": event}));
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!("Dropped {skipped} {kind} events for the event stream");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decision(minute: u32, tool: &str, decision: ApprovalResponse) -> ApprovalLogEntry {
+        ApprovalLogEntry {
+            timestamp: format!("2026-01-01T10:{minute:02}:00Z"),
+            tool_name: tool.into(),
+            arguments_summary: String::new(),
+            decision,
+            channel: if minute.is_multiple_of(2) {
+                "telegram"
+            } else {
+                "cli"
+            }
+            .into(),
+        }
+    }
+
+    #[test]
+    fn page_filters_and_paginates_newest_first() {
+        let records: Vec<_> = (0..10)
+            .map(|m| decision(m, "shell", ApprovalResponse::Yes))
+            .collect();
+        let query = ActivityQuery {
+            channel: Some("Telegram".into()),
+            limit: Some(2),
+            offset: Some(1),
+            ..ActivityQuery::default()
+        };
+
+        let page = page(records, &query);
+        assert_eq!(page.total, 5);
+        let times: Vec<&str> = page.items.iter().map(|e| &e.timestamp[11..16]).collect();
+        assert_eq!(times, ["10:06", "10:04"]);
+        assert_eq!(page.next_offset, Some(3));
+    }
+
+    #[test]
+    fn page_filters_by_time_tool_and_outcome() {
+        let records = vec![
+            decision(1, "shell", ApprovalResponse::No),
+            decision(2, "shell", ApprovalResponse::Always),
+            decision(3, "file_write", ApprovalResponse::No),
+            decision(4, "shell", ApprovalResponse::No),
+        ];
+        let query = ActivityQuery {
+            since: parse_rfc3339("2026-01-01T10:01:30Z"),
+            until: parse_rfc3339("2026-01-01T10:04:00Z"),
+            tool: Some("SHELL".into()),
+            outcome: Some(Outcome::Approved),
+            ..ActivityQuery::default()
+        };
+
+        let page = page(records, &query);
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].decision, ApprovalResponse::Always);
+        assert_eq!(page.next_offset, None);
+    }
+
+    #[test]
+    fn audit_and_trace_records_map_to_filters() {
+        let denied = AuditEvent::new(AuditEventType::AuthFailure)
+            .with_actor("gateway".into(), None, None)
+            .with_action("PUT /api/config".into(), "high".into(), false, false);
+        assert_eq!(denied.channel(), Some("gateway"));
+        assert!(denied.matches_tool("put /api"));
+        assert!(!denied.matches_tool("GET"));
+        assert!(denied.matches_outcome(Outcome::Denied));
+        assert!(denied.matches_outcome(Outcome::Failure));
+        assert!(!denied.matches_outcome(Outcome::Success));
+
+        let trace = RuntimeTraceEvent {
+            id: "t1".into(),
+            timestamp: "2026-01-01T10:00:00Z".into(),
+            event_type: "tool_call_result".into(),
+            channel: Some("webchat".into()),
+            provider: None,
+            model: None,
+            turn_id: None,
+            success: Some(false),
+            message: Some("Denied by user.".into()),
+            payload: serde_json::json!({"tool": "shell"}),
+        };
+        assert!(trace.matches_tool("shell"));
+        assert!(trace.matches_outcome(Outcome::Denied));
+        assert!(!trace.matches_outcome(Outcome::Success));
+    }
+
+    #[test]
+    fn event_matches_applies_filters_to_stream_events() {
+        let approval = |tool: &str, decision_: ApprovalResponse| serde_json::json!({"type": "approval", "event": decision(2, tool, decision_)});
+        let query = ActivityQuery {
+            channel: Some("telegram".into()),
+            tool: Some("shell".into()),
+            outcome: Some(Outcome::Denied),
+            ..ActivityQuery::default()
+        };
+
+        assert!(event_matches(
+            &query,
+            &approval("shell", ApprovalResponse::No)
+        ));
+        assert!(!event_matches(
+            &query,
+            &approval("shell", ApprovalResponse::Yes)
+        ));
+        assert!(!event_matches(
+            &query,
+            &approval("file_write", ApprovalResponse::No)
+        ));
+        let status = serde_json::json!({"type": "status", "tool": "shell"});
+        assert!(!event_matches(&query, &status));
+        assert!(event_matches(&ActivityQuery::default(), &status));
+    }
+
+    #[tokio::test]
+    async fn forwarders_tag_events_for_the_event_stream() {
+        let (event_tx, mut event_rx) = broadcast::channel(64);
+        spawn_event_forwarders(&event_tx);
+
+        crate::observability::runtime_trace::record_event(
+            "activity_forward_probe",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            serde_json::Value::Null,
+        );
+
+        let forwarded = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let value = event_rx.recv().await.unwrap();
+                if value["event"]["event_type"] == "activity_forward_probe" {
+                    return value;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(forwarded["type"], "trace");
+    }
+}
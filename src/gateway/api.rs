@@ -2,7 +2,10 @@
 //!
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
 
+use super::activity::{self, ActivityQuery};
+use super::sessions::{self, SessionAccess};
 use super::AppState;
+use crate::security::audit::AuditEvent;
 use axum::{
     extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
@@ -32,7 +35,7 @@ async fn require_auth(
     }
 
     let token = extract_bearer_token(headers).unwrap_or("");
-    if state.pairing.is_authenticated(token).await {
+    if state.pairing.is_authenticated(token) {
         Ok(())
     } else {
         Err((
@@ -44,6 +47,26 @@ async fn require_auth(
     }
 }
 
+/// Authenticate a sessions request and work out which sessions it may use.
+fn require_session_access(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<SessionAccess, (StatusCode, Json<serde_json::Value>)> {
+    let token = extract_bearer_token(headers).unwrap_or("");
+    match state.pairing.authenticate(token) {
+        Some(grant) => Ok(SessionAccess::for_grant(
+            &state.config.lock().memory.namespaces,
+            &grant,
+        )),
+        None => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({
+                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
+            })),
+        )),
+    }
+}
+
 // ── Query parameters ─────────────────────────────────────────────
 
 #[derive(Deserialize)]
@@ -64,6 +87,39 @@ pub struct CronAddBody {
     pub name: Option<String>,
     pub schedule: String,
     pub command: String,
+    pub delivery: Option<crate::cron::DeliveryConfig>,
+    pub on_success: Option<String>,
+    pub on_failure: Option<String>,
+    pub catch_up: Option<crate::cron::CatchUpPolicy>,
+    pub max_concurrent: Option<u32>,
+}
+
+#[derive(Deserialize)]
+pub struct TokenCreateBody {
+    pub label: String,
+    pub scopes: Vec<crate::security::TokenScope>,
+    /// Lifetime in seconds; omit for a token that never expires.
+    pub expires_in_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct SessionQuery {
+    /// Only list sessions owned by this identity (admin tokens only; scoped
+    /// tokens always see just their own).
+    pub identity: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SessionCreateBody {
+    pub name: String,
+    pub identity: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SessionUpdateBody {
+    pub name: Option<String>,
+    /// Owner identity to set; an empty string unbinds the session.
+    pub identity: Option<String>,
 }
 
 // ── Handlers ────────────────────────────────────────────────────
@@ -77,7 +133,7 @@ pub async fn handle_api_status(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
     let health = crate::health::snapshot().await;
 
     let mut channels = serde_json::Map::new();
@@ -94,7 +150,7 @@ pub async fn handle_api_status(
         "gateway_port": config.gateway.port,
         "locale": "en",
         "memory_backend": state.mem.name(),
-        "paired": state.pairing.is_paired().await,
+        "paired": state.pairing.is_paired(),
         "channels": channels,
         "health": health,
     });
@@ -111,7 +167,7 @@ pub async fn handle_api_config_get(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
 
     // Serialize to TOML after masking sensitive fields.
     let masked_config = mask_sensitive_fields(&config);
@@ -187,7 +243,7 @@ pub async fn handle_api_config_put(
     }
 
     // Update in-memory config
-    *state.config.lock().await = new_config;
+    *state.config.lock() = new_config;
 
     Json(serde_json::json!({"status": "ok"})).into_response()
 }
@@ -225,23 +281,10 @@ pub async fn handle_api_cron_list(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
     match crate::cron::list_jobs(&config) {
         Ok(jobs) => {
-            let jobs_json: Vec<serde_json::Value> = jobs
-                .iter()
-                .map(|job| {
-                    serde_json::json!({
-                        "id": job.id,
-                        "name": job.name,
-                        "command": job.command,
-                        "next_run": job.next_run.to_rfc3339(),
-                        "last_run": job.last_run.map(|t| t.to_rfc3339()),
-                        "last_status": job.last_status,
-                        "enabled": job.enabled,
-                    })
-                })
-                .collect();
+            let jobs_json: Vec<serde_json::Value> = jobs.iter().map(cron_job_json).collect();
             Json(serde_json::json!({"jobs": jobs_json})).into_response()
         }
         Err(e) => (
@@ -262,13 +305,34 @@ pub async fn handle_api_cron_add(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
     let schedule = crate::cron::Schedule::Cron {
         expr: body.schedule,
         tz: None,
     };
 
-    match crate::cron::add_shell_job(&config, body.name, schedule, &body.command) {
+    // Validate follow-up jobs before creating so a bad id leaves nothing behind.
+    for id in [&body.on_success, &body.on_failure].into_iter().flatten() {
+        if let Err(e) = crate::cron::get_job(&config, id) {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": format!("Invalid follow-up job: {e}")})),
+            )
+                .into_response();
+        }
+    }
+    let patch = crate::cron::CronJobPatch {
+        delivery: body.delivery,
+        on_success: body.on_success,
+        on_failure: body.on_failure,
+        catch_up: body.catch_up,
+        max_concurrent: body.max_concurrent,
+        ..crate::cron::CronJobPatch::default()
+    };
+
+    match crate::cron::add_shell_job(&config, body.name, schedule, &body.command)
+        .and_then(|job| crate::cron::configure_new_job(&config, &job, patch))
+    {
         Ok(job) => Json(serde_json::json!({
             "status": "ok",
             "job": {
@@ -276,6 +340,10 @@ pub async fn handle_api_cron_add(
                 "name": job.name,
                 "command": job.command,
                 "enabled": job.enabled,
+                "on_success": job.on_success,
+                "on_failure": job.on_failure,
+                "catch_up": job.catch_up,
+                "max_concurrent": job.max_concurrent,
             }
         }))
         .into_response(),
@@ -297,7 +365,7 @@ pub async fn handle_api_cron_delete(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
     match crate::cron::remove_job(&config, &id) {
         Ok(()) => Json(serde_json::json!({"status": "ok"})).into_response(),
         Err(e) => (
@@ -308,6 +376,47 @@ pub async fn handle_api_cron_delete(
     }
 }
 
+/// PATCH /api/cron/:id — update fields of a cron job
+pub async fn handle_api_cron_update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(patch): Json<crate::cron::CronJobPatch>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    match crate::cron::update_job(&config, &id, patch) {
+        Ok(job) => {
+            Json(serde_json::json!({"status": "ok", "job": cron_job_json(&job)})).into_response()
+        }
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("Failed to update cron job: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+fn cron_job_json(job: &crate::cron::CronJob) -> serde_json::Value {
+    serde_json::json!({
+        "id": job.id,
+        "name": job.name,
+        "command": job.command,
+        "next_run": job.next_run.to_rfc3339(),
+        "last_run": job.last_run.map(|t| t.to_rfc3339()),
+        "last_status": job.last_status,
+        "enabled": job.enabled,
+        "on_success": job.on_success,
+        "on_failure": job.on_failure,
+        "catch_up": job.catch_up,
+        "max_concurrent": job.max_concurrent,
+        "delivery_when": job.delivery.when,
+    })
+}
+
 /// GET /api/integrations — list all integrations with status
 pub async fn handle_api_integrations(
     State(state): State<AppState>,
@@ -317,7 +426,7 @@ pub async fn handle_api_integrations(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
     let entries = crate::integrations::registry::all_integrations();
 
     let integrations: Vec<serde_json::Value> = entries
@@ -345,7 +454,7 @@ pub async fn handle_api_doctor(
         return e.into_response();
     }
 
-    let config = state.config.lock().await.clone();
+    let config = state.config.lock().clone();
     let results = crate::doctor::diagnose(&config);
 
     let ok_count = results
@@ -529,8 +638,377 @@ pub async fn handle_api_health(
     Json(serde_json::json!({"health": snapshot})).into_response()
 }
 
+/// GET /api/tokens — list scoped API tokens (hashes are never returned)
+pub async fn handle_api_tokens_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let now = chrono::Utc::now();
+    let tokens: Vec<serde_json::Value> = state
+        .pairing
+        .api_tokens()
+        .iter()
+        .map(|t| token_summary(t, now))
+        .collect();
+    Json(serde_json::json!({"tokens": tokens})).into_response()
+}
+
+/// POST /api/tokens — issue a scoped API token (plaintext returned once)
+pub async fn handle_api_tokens_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<TokenCreateBody>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    if body.scopes.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "scopes must not be empty"})),
+        )
+            .into_response();
+    }
+    let expires_at = match body.expires_in_secs.map(|secs| {
+        i64::try_from(secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .and_then(|ttl| chrono::Utc::now().checked_add_signed(ttl))
+    }) {
+        Some(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": "expires_in_secs is out of range"})),
+            )
+                .into_response();
+        }
+        Some(at) => at,
+        None => None,
+    };
+
+    let (token, record) = state
+        .pairing
+        .issue_api_token(body.label.trim(), body.scopes, expires_at);
+    if let Err(e) = super::persist_api_tokens(state.config.clone(), &state.pairing).await {
+        tracing::error!("🔐 API token issued but failed to persist: {e:#}");
+    }
+
+    let mut summary = token_summary(&record, chrono::Utc::now());
+    summary["token"] = serde_json::Value::String(token);
+    (StatusCode::CREATED, Json(summary)).into_response()
+}
+
+/// DELETE /api/tokens/:id — revoke a scoped API token
+pub async fn handle_api_tokens_revoke(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    if !state.pairing.revoke_api_token(&id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No API token with id '{id}'")})),
+        )
+            .into_response();
+    }
+    match super::persist_api_tokens(state.config.clone(), &state.pairing).await {
+        Ok(()) => Json(serde_json::json!({"status": "ok", "revoked": id})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Token revoked but not persisted: {e:#}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/audit — security audit log entries, newest first
+pub async fn handle_api_audit(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ActivityQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let Some(audit) = state.audit.as_ref() else {
+        return Json(activity::page(Vec::<AuditEvent>::new(), &query)).into_response();
+    };
+    let log_path = audit.log_path().to_path_buf();
+    let loaded = tokio::task::spawn_blocking(move || {
+        crate::security::audit::load_events(&log_path, activity::MAX_SCANNED_RECORDS)
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|result| result);
+    match loaded {
+        Ok(events) => Json(activity::page(events, &query)).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read audit log: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/approvals — recent tool approval decisions, newest first
+pub async fn handle_api_approvals(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ActivityQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    Json(activity::page(crate::approval::recent_decisions(), &query)).into_response()
+}
+
+/// GET /api/traces — runtime trace events, newest first
+pub async fn handle_api_traces(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ActivityQuery>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers).await {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let path = crate::observability::runtime_trace::resolve_trace_path(
+        &config.observability,
+        &config.workspace_dir,
+    );
+    let loaded = tokio::task::spawn_blocking(move || {
+        crate::observability::runtime_trace::load_events(
+            &path,
+            activity::MAX_SCANNED_RECORDS,
+            None,
+            None,
+        )
+    })
+    .await
+    .map_err(anyhow::Error::from)
+    .and_then(|result| result);
+    match loaded {
+        Ok(mut events) => {
+            // load_events returns newest first; page expects oldest first.
+            events.reverse();
+            Json(activity::page(events, &query)).into_response()
+        }
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to read runtime traces: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/sessions — list persisted chat sessions
+pub async fn handle_api_sessions_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<SessionQuery>,
+) -> impl IntoResponse {
+    let access = match require_session_access(&state, &headers) {
+        Ok(access) => access,
+        Err(e) => return e.into_response(),
+    };
+
+    let config = state.config.lock().clone();
+    let identity = access.list_filter(&config.memory.namespaces, params.identity.as_deref());
+    match sessions::list_sessions(&config.workspace_dir, identity.as_deref()) {
+        Ok(sessions) => Json(serde_json::json!({"sessions": sessions})).into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to list sessions: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// POST /api/sessions — create an empty chat session
+pub async fn handle_api_sessions_create(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<SessionCreateBody>,
+) -> impl IntoResponse {
+    let access = match require_session_access(&state, &headers) {
+        Ok(access) => access,
+        Err(e) => return e.into_response(),
+    };
+
+    let config = state.config.lock().clone();
+    let created = access
+        .assign_identity(&config.memory.namespaces, body.identity.as_deref())
+        .and_then(|identity| {
+            sessions::create_session(&config.workspace_dir, &body.name, identity.as_deref())
+        });
+    match created {
+        Ok(session) => (StatusCode::CREATED, Json(serde_json::json!(session))).into_response(),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/sessions/:id — a session with its full message history
+pub async fn handle_api_sessions_get(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let access = match require_session_access(&state, &headers) {
+        Ok(access) => access,
+        Err(e) => return e.into_response(),
+    };
+
+    let config = state.config.lock().clone();
+    let loaded = sessions::get_session(&config.workspace_dir, &id).and_then(|session| {
+        session
+            .filter(|s| access.allows(s))
+            .map(|s| Ok((sessions::load_messages(&config.workspace_dir, &s.id)?, s)))
+            .transpose()
+    });
+    match loaded {
+        Ok(Some((messages, session))) => {
+            Json(serde_json::json!({"session": session, "messages": messages})).into_response()
+        }
+        Ok(None) => session_not_found(&id),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to load session: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
+/// PATCH /api/sessions/:id — rename a session or change its identity
+pub async fn handle_api_sessions_update(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(body): Json<SessionUpdateBody>,
+) -> impl IntoResponse {
+    let access = match require_session_access(&state, &headers) {
+        Ok(access) => access,
+        Err(e) => return e.into_response(),
+    };
+
+    let config = state.config.lock().clone();
+    match owned_session(&config, &access, &id) {
+        Ok(true) => {}
+        Ok(false) => return session_not_found(&id),
+        Err(e) => return session_lookup_failed(&e),
+    }
+    let updated = body
+        .identity
+        .map(|raw| {
+            access
+                .assign_identity(&config.memory.namespaces, Some(&raw))
+                .map(Option::unwrap_or_default)
+        })
+        .transpose()
+        .and_then(|identity| {
+            let patch = sessions::SessionPatch {
+                name: body.name,
+                identity,
+            };
+            sessions::update_session(&config.workspace_dir, &id, &patch)
+        });
+    match updated {
+        Ok(Some(session)) => Json(serde_json::json!(session)).into_response(),
+        Ok(None) => session_not_found(&id),
+        Err(e) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// DELETE /api/sessions/:id — delete a session and its messages
+pub async fn handle_api_sessions_delete(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let access = match require_session_access(&state, &headers) {
+        Ok(access) => access,
+        Err(e) => return e.into_response(),
+    };
+
+    let config = state.config.lock().clone();
+    match owned_session(&config, &access, &id) {
+        Ok(true) => {}
+        Ok(false) => return session_not_found(&id),
+        Err(e) => return session_lookup_failed(&e),
+    }
+    match sessions::delete_session(&config.workspace_dir, &id) {
+        Ok(true) => Json(serde_json::json!({"status": "ok", "deleted": id})).into_response(),
+        Ok(false) => session_not_found(&id),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": format!("Failed to delete session: {e}")})),
+        )
+            .into_response(),
+    }
+}
+
 // ── Helpers ─────────────────────────────────────────────────────
 
+/// Whether session `id` exists and `access` may use it. Sessions owned by
+/// someone else look the same as missing ones.
+fn owned_session(
+    config: &crate::config::Config,
+    access: &SessionAccess,
+    id: &str,
+) -> anyhow::Result<bool> {
+    Ok(sessions::get_session(&config.workspace_dir, id)?.is_some_and(|s| access.allows(&s)))
+}
+
+fn session_lookup_failed(e: &anyhow::Error) -> axum::response::Response {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": format!("Failed to load session: {e}")})),
+    )
+        .into_response()
+}
+
+fn session_not_found(id: &str) -> axum::response::Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({"error": format!("No chat session with id '{id}'")})),
+    )
+        .into_response()
+}
+
+fn token_summary(
+    token: &crate::config::GatewayApiToken,
+    now: chrono::DateTime<chrono::Utc>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "id": token.id,
+        "label": token.label,
+        "scopes": token.scopes,
+        "created_at": token.created_at,
+        "expires_at": token.expires_at,
+        "revoked_at": token.revoked_at,
+        "active": token.is_active(now),
+    })
+}
+
 fn normalize_dashboard_config_toml(root: &mut toml::Value) {
     // Dashboard editors may round-trip masked reliability api_keys as a single
     // string. Accept that shape by normalizing it back to a string array.
@@ -825,6 +1303,8 @@ fn hydrate_config_for_save(
     // These are runtime-computed fields skipped from TOML serialization.
     incoming.config_path = current.config_path.clone();
     incoming.workspace_dir = current.workspace_dir.clone();
+    // API tokens are managed through /api/tokens, never via config edits.
+    incoming.gateway.api_tokens = current.gateway.api_tokens.clone();
     incoming
 }
 
@@ -7,14 +7,21 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
+pub mod a2a;
+pub mod activity;
 pub mod api;
 mod openai_compat;
+pub mod sessions;
+#[cfg(feature = "sop")]
+pub mod sop;
 pub mod sse;
 pub mod static_files;
+pub mod tls;
+pub mod tokens;
 pub mod ws;
 
 use crate::channels::{
-    Channel, LinqChannel, NextcloudTalkChannel, QQChannel, SendMessage, WatiChannel,
+    Channel, LinqChannel, NextcloudTalkChannel, QQChannel, SendMessage, TeamsChannel, WatiChannel,
     WhatsAppChannel,
 };
 use crate::config::Config;
@@ -22,18 +29,20 @@ use crate::cost::CostTracker;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
+use crate::security::audit::{AuditEvent, AuditEventType, AuditLogger};
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
-use crate::security::SecurityPolicy;
+use crate::security::{SecurityPolicy, TokenScope};
 use crate::tools::traits::ToolSpec;
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
     body::Bytes,
-    extract::{ConnectInfo, Query, State},
-    http::{header, HeaderMap, StatusCode},
-    response::{IntoResponse, Json},
-    routing::{delete, get, post, put},
+    extract::{ConnectInfo, Query, Request, State},
+    http::{header, HeaderMap, Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use parking_lot::Mutex;
@@ -76,6 +85,10 @@ fn nextcloud_talk_memory_key(msg: &crate::channels::traits::ChannelMessage) -> S
     format!("nextcloud_talk_{}_{}", msg.sender, msg.id)
 }
 
+fn teams_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String {
+    format!("teams_{}_{}", msg.sender, msg.id)
+}
+
 fn qq_memory_key(msg: &crate::channels::traits::ChannelMessage) -> String {
     format!("qq_{}_{}", msg.sender, msg.id)
 }
@@ -307,6 +320,7 @@ pub struct AppState {
     pub nextcloud_talk: Option<Arc<NextcloudTalkChannel>>,
     /// Nextcloud Talk webhook secret for signature verification
     pub nextcloud_talk_webhook_secret: Option<Arc<str>>,
+    pub teams: Option<Arc<TeamsChannel>>,
     pub wati: Option<Arc<WatiChannel>>,
     pub qq: Option<Arc<QQChannel>>,
     pub qq_webhook_enabled: bool,
@@ -324,6 +338,10 @@ pub struct AppState {
     pub cost_tracker: Option<Arc<CostTracker>>,
     /// SSE broadcast channel for real-time events
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
+    /// Audit log for admin-scoped requests (`[security.audit]`)
+    pub audit: Option<Arc<AuditLogger>>,
+    /// Tasks submitted through the A2A endpoint (`[a2a]`)
+    pub a2a: Arc<a2a::A2aTasks>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
@@ -342,11 +360,29 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     // ── Hooks ──────────────────────────────────────────────────────
     let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
-        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
+        Some(std::sync::Arc::new(crate::hooks::HookRunner::from_config(
+            &config,
+        )))
     } else {
         None
     };
 
+    // ── TLS ────────────────────────────────────────────────────────
+    let tls = if config.gateway.tls.enabled {
+        let config_dir = config
+            .config_path
+            .parent()
+            .context("Config path has no parent directory")?;
+        Some(tls::GatewayTls::from_config(
+            &config.gateway.tls,
+            config_dir,
+            host,
+        )?)
+    } else {
+        None
+    };
+    let scheme = if tls.is_some() { "https" } else { "http" };
+
     let addr: SocketAddr = format!("{host}:{port}").parse()?;
     let listener = tokio::net::TcpListener::bind(addr).await?;
     let actual_port = listener.local_addr()?.port();
@@ -431,6 +467,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     // SSE broadcast channel for real-time events
     let (event_tx, _event_rx) = tokio::sync::broadcast::channel::<serde_json::Value>(256);
+    activity::spawn_event_forwarders(&event_tx);
     // Extract webhook secret for authentication
     let webhook_secret_hash: Option<Arc<str>> =
         config.channels_config.webhook.as_ref().and_then(|webhook| {
@@ -562,11 +599,27 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             })
             .map(Arc::from);
 
+    // Microsoft Teams channel (if configured)
+    let teams_channel: Option<Arc<TeamsChannel>> = config
+        .channels_config
+        .teams
+        .as_ref()
+        .map(|teams| Arc::new(TeamsChannel::new(teams.clone())));
+
     // ── Pairing guard ──────────────────────────────────────
-    let pairing = Arc::new(PairingGuard::new(
-        config.gateway.require_pairing,
-        &config.gateway.paired_tokens,
-    ));
+    // A verified client certificate (mutual TLS) replaces pairing.
+    let pairing = Arc::new(
+        PairingGuard::new(
+            config.gateway.require_pairing && !config.gateway.tls.requires_client_cert(),
+            &config.gateway.paired_tokens,
+        )
+        .with_api_tokens(config.gateway.api_tokens.clone()),
+    );
+    let audit = config
+        .config_path
+        .parent()
+        .and_then(|dir| AuditLogger::new(config.security.audit.clone(), dir.to_path_buf()).ok())
+        .map(Arc::new);
     let rate_limit_max_keys = normalize_max_keys(
         config.gateway.rate_limit_max_keys,
         RATE_LIMIT_MAX_KEYS_DEFAULT,
@@ -603,11 +656,11 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         }
     }
 
-    println!("🦀 ZeroClaw Gateway listening on http://{display_addr}");
+    println!("🦀 ZeroClaw Gateway listening on {scheme}://{display_addr}");
     if let Some(ref url) = tunnel_url {
         println!("  🌐 Public URL: {url}");
     }
-    println!("  🌐 Web Dashboard: http://{display_addr}/");
+    println!("  🌐 Web Dashboard: {scheme}://{display_addr}/");
     println!("  POST /pair      — pair a new client (X-Pairing-Code header)");
     println!("  POST /webhook   — {{\"message\": \"your prompt\"}}");
     if whatsapp_channel.is_some() {
@@ -624,6 +677,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if nextcloud_talk_channel.is_some() {
         println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
     }
+    if teams_channel.is_some() {
+        println!("  POST /teams     — Microsoft Teams Bot Framework messaging endpoint");
+    }
     if qq_webhook_enabled {
         println!("  POST /qq        — QQ Bot webhook (validation + events)");
     }
@@ -634,9 +690,15 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     println!("  GET  /v1/models — list available models");
     println!("  GET  /api/*     — REST API (bearer token required)");
     println!("  GET  /ws/chat   — WebSocket agent chat");
+    if config.a2a.enabled {
+        println!("  POST /a2a       — A2A agent endpoint (card: /.well-known/agent-card.json)");
+    }
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
-    if let Some(code) = pairing.pairing_code() {
+    if config.gateway.tls.requires_client_cert() {
+        println!();
+        println!("  🔐 Client certificates: REQUIRED (mutual TLS replaces pairing)");
+    } else if let Some(code) = pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
         println!("     ┌──────────────┐");
@@ -650,7 +712,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     }
     println!("  Press Ctrl+C to stop.\n");
 
-    crate::health::mark_component_ok("gateway");
+    crate::health::mark_component_ok("gateway").await;
 
     // Fire gateway start hook
     if let Some(ref hooks) = hooks {
@@ -682,6 +744,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         linq_signing_secret,
         nextcloud_talk: nextcloud_talk_channel,
         nextcloud_talk_webhook_secret,
+        teams: teams_channel,
         wati: wati_channel,
         qq: qq_channel,
         qq_webhook_enabled,
@@ -692,6 +755,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         max_tool_iterations,
         cost_tracker,
         event_tx,
+        audit,
+        a2a: Arc::new(a2a::A2aTasks::default()),
     };
 
     // Config PUT needs larger body limit (1MB)
@@ -711,6 +776,12 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE,
         ));
 
+    // SOP webhook triggers (feature-gated)
+    #[cfg(feature = "sop")]
+    let sop_routes = Router::new().route("/sop/{*path}", post(sop::handle_sop_webhook));
+    #[cfg(not(feature = "sop"))]
+    let sop_routes = Router::new();
+
     // Build router with middleware
     let app = Router::new()
         // ── Existing routes ──
@@ -724,7 +795,16 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/wati", get(handle_wati_verify))
         .route("/wati", post(handle_wati_webhook))
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
+        .route("/teams", post(handle_teams_webhook))
         .route("/qq", post(handle_qq_webhook))
+        .merge(sop_routes)
+        // ── Agent-to-Agent (A2A) protocol ──
+        .route(
+            "/.well-known/agent-card.json",
+            get(a2a::handle_a2a_agent_card),
+        )
+        .route("/.well-known/agent.json", get(a2a::handle_a2a_agent_card))
+        .route("/a2a", post(a2a::handle_a2a_rpc))
         // ── OpenAI-compatible endpoints ──
         .route("/v1/models", get(openai_compat::handle_v1_models))
         .merge(openai_compat_routes)
@@ -735,6 +815,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cron", get(api::handle_api_cron_list))
         .route("/api/cron", post(api::handle_api_cron_add))
         .route("/api/cron/{id}", delete(api::handle_api_cron_delete))
+        .route("/api/cron/{id}", patch(api::handle_api_cron_update))
         .route("/api/integrations", get(api::handle_api_integrations))
         .route(
             "/api/doctor",
@@ -747,6 +828,24 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
         .route("/api/node-control", post(handle_node_control))
+        .route(
+            "/api/tokens",
+            get(api::handle_api_tokens_list).post(api::handle_api_tokens_create),
+        )
+        .route("/api/tokens/{id}", delete(api::handle_api_tokens_revoke))
+        .route(
+            "/api/sessions",
+            get(api::handle_api_sessions_list).post(api::handle_api_sessions_create),
+        )
+        .route(
+            "/api/sessions/{id}",
+            get(api::handle_api_sessions_get)
+                .patch(api::handle_api_sessions_update)
+                .delete(api::handle_api_sessions_delete),
+        )
+        .route("/api/audit", get(api::handle_api_audit))
+        .route("/api/approvals", get(api::handle_api_approvals))
+        .route("/api/traces", get(api::handle_api_traces))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
@@ -755,6 +854,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            enforce_token_scope,
+        ))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -765,11 +868,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .fallback(get(static_files::handle_spa_fallback));
 
     // Run the server
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
+    if let Some(tls) = tls {
+        tls.spawn_reloader();
+        axum::serve(tls.listener(listener)?, tls::WithConnectInfo::new(app)).await?;
+    } else {
+        let service = app.into_make_service_with_connect_info::<SocketAddr>();
+        axum::serve(listener, service).await?;
+    }
 
     Ok(())
 }
@@ -784,7 +889,7 @@ async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
         "status": "ok",
         "paired": state.pairing.is_paired(),
         "require_pairing": state.pairing.require_pairing(),
-        "runtime": crate::health::snapshot_json(),
+        "runtime": crate::health::snapshot_json().await,
     });
     Json(body)
 }
@@ -904,6 +1009,84 @@ async fn handle_pair(
     }
 }
 
+/// Scope a bearer token needs for `method path`. `None` means the route is
+/// public or authenticates by other means (pairing code, channel signatures).
+fn required_scope(method: &Method, path: &str) -> Option<TokenScope> {
+    if path == "/api/tokens" || path.starts_with("/api/tokens/") {
+        return Some(TokenScope::Admin);
+    }
+    if path == "/api/sessions" || path.starts_with("/api/sessions/") {
+        // Chat clients manage the sessions they talk in.
+        return Some(TokenScope::Chat);
+    }
+    if path.starts_with("/api/") {
+        return Some(if method == Method::GET {
+            TokenScope::Read
+        } else {
+            TokenScope::Admin
+        });
+    }
+    match path {
+        "/metrics" => Some(TokenScope::Read),
+        "/webhook" | "/ws/chat" | "/a2a" => Some(TokenScope::Chat),
+        _ if path.starts_with("/v1/") || path.starts_with("/sop/") => Some(TokenScope::Chat),
+        _ => None,
+    }
+}
+
+/// Reject valid tokens that lack the scope a route requires, and audit every
+/// admin-scoped request. Missing or invalid tokens fall through so each
+/// handler keeps its own 401 response.
+async fn enforce_token_scope(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(scope) = required_scope(request.method(), request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let token = ws::extract_ws_bearer_token(request.headers()).unwrap_or_default();
+    let Some(grant) = state.pairing.authenticate(&token) else {
+        return next.run(request).await;
+    };
+
+    let allowed = grant.allows(scope);
+    if scope == TokenScope::Admin {
+        if let Some(audit) = &state.audit {
+            let event = AuditEvent::new(if allowed {
+                AuditEventType::AuthSuccess
+            } else {
+                AuditEventType::AuthFailure
+            })
+            .with_actor(
+                "gateway".into(),
+                grant.token_id.clone(),
+                Some(grant.label.clone()),
+            )
+            .with_action(
+                format!("{} {}", request.method(), request.uri().path()),
+                "high".into(),
+                allowed,
+                allowed,
+            );
+            if let Err(e) = audit.log(&event) {
+                tracing::warn!("Failed to write gateway audit entry: {e}");
+            }
+        }
+    }
+
+    if !allowed {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({
+                "error": format!("Forbidden — token lacks the '{}' scope", scope.as_str())
+            })),
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
 async fn persist_pairing_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
     let paired_tokens = pairing.tokens();
     // This is needed because parking_lot's guard is not Send so we clone the inner
@@ -920,6 +1103,18 @@ async fn persist_pairing_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGua
     Ok(())
 }
 
+async fn persist_api_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
+    let mut updated_cfg = { config.lock().clone() };
+    updated_cfg.gateway.api_tokens = pairing.api_tokens();
+    updated_cfg
+        .save()
+        .await
+        .context("Failed to persist API tokens to config.toml")?;
+
+    *config.lock() = updated_cfg;
+    Ok(())
+}
+
 /// Simple chat for webhook endpoint (no tools, for backward compatibility and testing).
 async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Result<String> {
     let user_messages = vec![ChatMessage::user(message)];
@@ -952,10 +1147,12 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
         .await
 }
 
-/// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
+/// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk, Teams).
 async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
-    crate::agent::process_message(config, message).await
+    // Boxed: the agent loop future is large and would otherwise be inlined
+    // into every webhook handler future.
+    Box::pin(crate::agent::process_message(config, message)).await
 }
 
 fn sanitize_gateway_response(response: &str, tools: &[Box<dyn Tool>]) -> String {
@@ -1157,24 +1354,14 @@ async fn handle_node_control(
     }
 }
 
-/// POST /webhook — main webhook endpoint
-async fn handle_webhook(
-    State(state): State<AppState>,
-    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
-    headers: HeaderMap,
-    body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
-) -> impl IntoResponse {
-    let rate_key =
-        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
-    if !state.rate_limiter.allow_webhook(&rate_key) {
-        tracing::warn!("/webhook rate limit exceeded");
-        let err = serde_json::json!({
-            "error": "Too many webhook requests. Please retry later.",
-            "retry_after": RATE_LIMIT_WINDOW_SECS,
-        });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
-    }
-
+/// Auth layers shared by `/webhook` and the SOP webhook trigger: pairing
+/// bearer token and/or `X-Webhook-Secret`, with at least one required for
+/// non-loopback peers.
+fn authorize_webhook(
+    state: &AppState,
+    peer_addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
     // Require at least one auth layer for non-loopback traffic.
     if !state.pairing.require_pairing()
         && state.webhook_secret_hash.is_none()
@@ -1186,7 +1373,7 @@ async fn handle_webhook(
         let err = serde_json::json!({
             "error": "Unauthorized — configure pairing or X-Webhook-Secret for non-local webhook access"
         });
-        return (StatusCode::UNAUTHORIZED, Json(err));
+        return Err((StatusCode::UNAUTHORIZED, Json(err)));
     }
 
     // ── Bearer token auth (pairing) ──
@@ -1201,7 +1388,7 @@ async fn handle_webhook(
             let err = serde_json::json!({
                 "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
             });
-            return (StatusCode::UNAUTHORIZED, Json(err));
+            return Err((StatusCode::UNAUTHORIZED, Json(err)));
         }
     }
 
@@ -1218,11 +1405,36 @@ async fn handle_webhook(
             _ => {
                 tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
                 let err = serde_json::json!({"error": "Unauthorized — invalid or missing X-Webhook-Secret header"});
-                return (StatusCode::UNAUTHORIZED, Json(err));
+                return Err((StatusCode::UNAUTHORIZED, Json(err)));
             }
         }
     }
 
+    Ok(())
+}
+
+/// POST /webhook — main webhook endpoint
+async fn handle_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/webhook rate limit exceeded");
+        let err = serde_json::json!({
+            "error": "Too many webhook requests. Please retry later.",
+            "retry_after": RATE_LIMIT_WINDOW_SECS,
+        });
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+    }
+
+    if let Err(rejection) = authorize_webhook(&state, peer_addr, &headers) {
+        return rejection;
+    }
+
     // ── Parse body ──
     let Json(webhook_body) = match body {
         Ok(b) => b,
@@ -1255,6 +1467,11 @@ async fn handle_webhook(
 
     let message = &webhook_body.message;
 
+    #[cfg(feature = "sop")]
+    if let Some(response) = sop::try_dispatch_chat_webhook(message).await {
+        return response;
+    }
+
     if state.auto_save {
         let key = webhook_memory_key();
         let _ = state
@@ -1828,6 +2045,102 @@ async fn handle_nextcloud_talk_webhook(
     (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
 }
 
+/// POST /teams — Bot Framework activity webhook (Microsoft Teams)
+async fn handle_teams_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ref teams) = state.teams else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Teams not configured"})),
+        );
+    };
+
+    let Ok(activity) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+
+    // ── Security: Verify the Bot Framework JWT ──
+    let authorization = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if let Err(e) = teams.verify_request(authorization, &activity).await {
+        tracing::warn!("Teams webhook authentication failed: {e}");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Unauthorized"})),
+        );
+    }
+
+    // Approval card button presses settle a waiting turn.
+    if teams.resolve_approval(&activity) {
+        return (StatusCode::OK, Json(serde_json::json!({"status": "ok"})));
+    }
+
+    for msg in teams.parse_webhook_payload(&activity) {
+        tracing::info!(
+            "Teams message from {}: {}",
+            msg.sender,
+            truncate_with_ellipsis(&msg.content, 50)
+        );
+
+        if state.auto_save {
+            let key = teams_memory_key(&msg);
+            let _ = state
+                .mem
+                .store(&key, &msg.content, MemoryCategory::Conversation, None)
+                .await;
+        }
+
+        // The Bot Framework expects a response within 15 seconds and approval
+        // clicks arrive as separate requests while the turn waits, so the turn
+        // runs in the background and replies through the Connector API.
+        let state = state.clone();
+        let teams = Arc::clone(teams);
+        tokio::spawn(async move { process_teams_message(&state, teams, msg).await });
+    }
+
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
+async fn process_teams_message(
+    state: &AppState,
+    teams: Arc<TeamsChannel>,
+    msg: crate::channels::traits::ChannelMessage,
+) {
+    let _ = teams.start_typing(&msg.reply_target).await;
+    let prompter = Arc::new(crate::channels::teams::TeamsApprovalPrompter::new(
+        Arc::clone(&teams),
+        &msg,
+    ));
+    let result = crate::approval::with_channel_prompter(
+        prompter,
+        run_gateway_chat_with_tools(state, &msg.content),
+    )
+    .await;
+    let _ = teams.stop_typing(&msg.reply_target).await;
+
+    let reply = match result {
+        Ok(response) => sanitize_gateway_response(&response, state.tools_registry_exec.as_ref()),
+        Err(e) => {
+            tracing::error!("LLM error for Teams message: {e:#}");
+            "Sorry, I couldn't process your message right now.".to_string()
+        }
+    };
+    if let Err(e) = teams
+        .send(&SendMessage::new(reply, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+        .await
+    {
+        tracing::error!("Failed to send Teams reply: {e}");
+    }
+}
+
 /// POST /qq — incoming QQ Bot webhook (validation + events)
 async fn handle_qq_webhook(
     State(state): State<AppState>,
@@ -2014,6 +2327,7 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2024,6 +2338,8 @@ mod tests {
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_metrics(State(state), test_connect_info(), HeaderMap::new())
@@ -2070,6 +2386,7 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2080,6 +2397,8 @@ mod tests {
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_metrics(State(state), test_connect_info(), HeaderMap::new())
@@ -2092,6 +2411,131 @@ mod tests {
         assert!(text.contains("zeroclaw_heartbeat_ticks_total 1"));
     }
 
+    #[test]
+    fn required_scope_maps_routes_to_token_scopes() {
+        let get = Method::GET;
+        let post = Method::POST;
+        assert_eq!(required_scope(&get, "/api/status"), Some(TokenScope::Read));
+        assert_eq!(required_scope(&get, "/api/events"), Some(TokenScope::Read));
+        assert_eq!(
+            required_scope(&Method::PUT, "/api/config"),
+            Some(TokenScope::Admin)
+        );
+        assert_eq!(required_scope(&get, "/api/tokens"), Some(TokenScope::Admin));
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/tokens/tok_1"),
+            Some(TokenScope::Admin)
+        );
+        assert_eq!(
+            required_scope(&get, "/api/sessions"),
+            Some(TokenScope::Chat)
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/sessions/abc"),
+            Some(TokenScope::Chat)
+        );
+        assert_eq!(required_scope(&get, "/metrics"), Some(TokenScope::Read));
+        assert_eq!(required_scope(&post, "/webhook"), Some(TokenScope::Chat));
+        assert_eq!(required_scope(&get, "/ws/chat"), Some(TokenScope::Chat));
+        assert_eq!(required_scope(&post, "/a2a"), Some(TokenScope::Chat));
+        assert_eq!(required_scope(&get, "/.well-known/agent-card.json"), None);
+        assert_eq!(
+            required_scope(&post, "/v1/chat/completions"),
+            Some(TokenScope::Chat)
+        );
+        assert_eq!(required_scope(&post, "/pair"), None);
+        assert_eq!(required_scope(&get, "/health"), None);
+        assert_eq!(required_scope(&post, "/whatsapp"), None);
+    }
+
+    #[tokio::test]
+    async fn token_scope_middleware_forbids_and_audits_admin_use() {
+        use tower::ServiceExt;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let pairing = Arc::new(PairingGuard::new(true, &[]));
+        let (reader, _) = pairing.issue_api_token("dashboard", vec![TokenScope::Read], None);
+        let (admin, admin_record) = pairing.issue_api_token("ops", vec![TokenScope::Admin], None);
+        let state = AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            pairing,
+            trust_forwarded_headers: false,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            teams: None,
+            wati: None,
+            qq: None,
+            qq_webhook_enabled: false,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            tools_registry_exec: Arc::new(Vec::new()),
+            multimodal: crate::config::MultimodalConfig::default(),
+            max_tool_iterations: 10,
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: Some(Arc::new(
+                AuditLogger::new(
+                    crate::config::AuditConfig::default(),
+                    tmp.path().to_path_buf(),
+                )
+                .unwrap(),
+            )),
+            a2a: Arc::new(a2a::A2aTasks::default()),
+        };
+        let app = Router::new()
+            .route("/api/status", get(|| async { "status" }))
+            .route("/api/config", put(|| async { "saved" }))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                enforce_token_scope,
+            ))
+            .with_state(state);
+        let request = |method: Method, path: &str, token: &str| {
+            axum::http::Request::builder()
+                .method(method)
+                .uri(path)
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(axum::body::Body::empty())
+                .unwrap()
+        };
+
+        let unknown = "zc_unknown".to_string();
+        let cases = [
+            (Method::GET, "/api/status", &reader, StatusCode::OK),
+            (Method::PUT, "/api/config", &reader, StatusCode::FORBIDDEN),
+            (Method::PUT, "/api/config", &admin, StatusCode::OK),
+            // Unknown tokens fall through to the handler's own auth check.
+            (Method::PUT, "/api/config", &unknown, StatusCode::OK),
+        ];
+        for (method, path, token, expected) in cases {
+            let response = app
+                .clone()
+                .oneshot(request(method.clone(), path, token))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected, "{method} {path}");
+        }
+
+        let log = std::fs::read_to_string(tmp.path().join("audit.log")).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2, "{log}");
+        assert!(lines[0].contains("auth_failure") && lines[0].contains("dashboard"));
+        assert!(lines[1].contains("auth_success") && lines[1].contains(&admin_record.id));
+        assert!(lines[1].contains("PUT /api/config"));
+    }
+
     #[tokio::test]
     async fn metrics_endpoint_rejects_public_clients_when_pairing_is_disabled() {
         let state = AppState {
@@ -2112,6 +2556,7 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2122,6 +2567,8 @@ mod tests {
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_metrics(State(state), test_public_connect_info(), HeaderMap::new())
@@ -2155,6 +2602,7 @@ mod tests {
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2165,6 +2613,8 @@ mod tests {
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let unauthorized =
@@ -2624,6 +3074,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2634,6 +3085,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -2691,6 +3144,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2701,6 +3155,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_webhook(
@@ -2740,6 +3196,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2750,6 +3207,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_node_control(
@@ -2794,6 +3253,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2804,6 +3264,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_node_control(
@@ -2853,6 +3315,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2863,6 +3326,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let headers = HeaderMap::new();
@@ -2934,6 +3399,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2944,6 +3410,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_webhook(
@@ -2987,6 +3455,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -2997,6 +3466,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -3045,6 +3516,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -3055,6 +3527,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -3108,6 +3582,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -3118,6 +3593,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_nextcloud_talk_webhook(
@@ -3167,6 +3644,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: Some(channel),
             nextcloud_talk_webhook_secret: Some(Arc::from(secret)),
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -3177,6 +3655,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let mut headers = HeaderMap::new();
@@ -3219,6 +3699,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: None,
             qq_webhook_enabled: false,
@@ -3229,6 +3710,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let response = handle_qq_webhook(
@@ -3270,6 +3753,7 @@ Reminder set successfully."#;
             linq_signing_secret: None,
             nextcloud_talk: None,
             nextcloud_talk_webhook_secret: None,
+            teams: None,
             wati: None,
             qq: Some(qq),
             qq_webhook_enabled: true,
@@ -3280,6 +3764,8 @@ Reminder set successfully."#;
             max_tool_iterations: 10,
             cost_tracker: None,
             event_tx: tokio::sync::broadcast::channel(16).0,
+            audit: None,
+            a2a: Arc::new(a2a::A2aTasks::default()),
         };
 
         let mut headers = HeaderMap::new();
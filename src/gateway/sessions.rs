@@ -0,0 +1,576 @@
+//! Server-side chat sessions shared by the WebSocket chat and channels.
+//!
+//! Sessions live in `workspace/sessions/sessions.db`. Only user and assistant
+//! turns are stored; the system prompt is rebuilt on every turn so prompt or
+//! identity changes apply to resumed sessions.
+//!
+//! A session may carry an `identity`, its owner, using the person model of
+//! `[memory.namespaces]`: a person linked in `[[memory.namespaces.people]]`,
+//! or `channel:sender` for anyone else. Scoped gateway tokens act as
+//! `gateway:<token id>` and only see their own sessions; a Telegram chat can
+//! continue the sessions its sender owns.
+
+use crate::config::MemoryNamespacesConfig;
+use crate::memory::namespace;
+use crate::providers::ChatMessage;
+use crate::security::{TokenGrant, TokenScope};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Longest session name accepted by create/rename.
+pub const MAX_SESSION_NAME_CHARS: usize = 120;
+/// Most recent stored turns replayed to the model when a session continues.
+pub const MAX_CONTEXT_MESSAGES: usize = 50;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ChatSession {
+    pub id: String,
+    pub name: String,
+    pub identity: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub message_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SessionMessage {
+    pub role: String,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&SessionMessage> for ChatMessage {
+    fn from(message: &SessionMessage) -> Self {
+        ChatMessage {
+            role: message.role.clone(),
+            content: message.content.clone(),
+        }
+    }
+}
+
+/// Changes applied by [`update_session`]. An empty `identity` clears it.
+#[derive(Debug, Clone, Default)]
+pub struct SessionPatch {
+    pub name: Option<String>,
+    pub identity: Option<String>,
+}
+
+/// Normalize a session identity the same way Telegram allowlist entries are
+/// normalized: trimmed, without a leading `@`, case-insensitive.
+pub fn normalize_identity(raw: &str) -> Option<String> {
+    let identity = raw.trim().trim_start_matches('@').to_ascii_lowercase();
+    if identity.is_empty() {
+        None
+    } else {
+        Some(identity)
+    }
+}
+
+/// The session identity of `sender` on `channel`: the person it is linked to
+/// in `[memory.namespaces]`, otherwise `channel:sender`.
+pub fn channel_identity(
+    config: &MemoryNamespacesConfig,
+    channel: &str,
+    sender: &str,
+) -> Option<String> {
+    let sender = sender.trim().trim_start_matches('@');
+    if channel.trim().is_empty() || sender.is_empty() {
+        return None;
+    }
+    normalize_identity(&namespace::person(config, channel.trim(), sender))
+}
+
+/// Resolve an identity supplied by a client: `channel:sender`, the name of a
+/// linked person, or a bare Telegram username.
+pub fn parse_identity(config: &MemoryNamespacesConfig, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if let Some((channel, sender)) = raw.split_once(':') {
+        return channel_identity(config, channel, sender);
+    }
+    let name = raw.trim_start_matches('@');
+    if config
+        .people
+        .iter()
+        .any(|person| person.name.trim().eq_ignore_ascii_case(name))
+    {
+        return normalize_identity(name);
+    }
+    channel_identity(config, "telegram", name)
+}
+
+/// Which sessions a gateway caller may use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionAccess {
+    /// Admin grants: every session.
+    All,
+    /// Scoped tokens: only sessions owned by this identity.
+    Owner(String),
+}
+
+impl SessionAccess {
+    /// Access for an authenticated grant. A scoped token is the identity
+    /// `gateway:<token id>`, or the person that identity is linked to.
+    pub fn for_grant(config: &MemoryNamespacesConfig, grant: &TokenGrant) -> Self {
+        if grant.allows(TokenScope::Admin) {
+            return Self::All;
+        }
+        let token = grant.token_id.as_deref().unwrap_or(&grant.label);
+        Self::Owner(
+            channel_identity(config, "gateway", token)
+                .unwrap_or_else(|| format!("gateway:{token}")),
+        )
+    }
+
+    pub fn allows(&self, session: &ChatSession) -> bool {
+        match self {
+            Self::All => true,
+            Self::Owner(owner) => session.identity.as_deref() == Some(owner.as_str()),
+        }
+    }
+
+    /// The identity filter for listing: the owner, or `requested` for admins.
+    pub fn list_filter(
+        &self,
+        config: &MemoryNamespacesConfig,
+        requested: Option<&str>,
+    ) -> Option<String> {
+        match self {
+            Self::All => requested.and_then(|raw| parse_identity(config, raw)),
+            Self::Owner(owner) => Some(owner.clone()),
+        }
+    }
+
+    /// The identity to store for a client-supplied one. Admins may assign any
+    /// identity (empty clears it); scoped tokens only their own.
+    pub fn assign_identity(
+        &self,
+        config: &MemoryNamespacesConfig,
+        requested: Option<&str>,
+    ) -> Result<Option<String>> {
+        let requested = requested.and_then(|raw| parse_identity(config, raw));
+        match self {
+            Self::All => Ok(requested),
+            Self::Owner(owner) => match requested {
+                Some(other) if other != *owner => {
+                    anyhow::bail!("this token can only use sessions owned by `{owner}`")
+                }
+                _ => Ok(Some(owner.clone())),
+            },
+        }
+    }
+}
+
+fn normalize_name(raw: &str) -> Result<String> {
+    let name = raw.trim();
+    if name.is_empty() {
+        anyhow::bail!("session name must not be empty");
+    }
+    if name.chars().count() > MAX_SESSION_NAME_CHARS {
+        anyhow::bail!("session name must be at most {MAX_SESSION_NAME_CHARS} characters");
+    }
+    Ok(name.to_string())
+}
+
+pub fn create_session(
+    workspace_dir: &Path,
+    name: &str,
+    identity: Option<&str>,
+) -> Result<ChatSession> {
+    let name = normalize_name(name)?;
+    let identity = identity.and_then(normalize_identity);
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO chat_sessions (id, name, identity, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)",
+            params![id, name, identity, now],
+        )
+        .context("Failed to insert chat session")?;
+        Ok(())
+    })?;
+
+    get_session(workspace_dir, &id)?.context("Chat session vanished after insert")
+}
+
+/// List sessions, most recently active first. With `identity`, only sessions
+/// bound to that identity are returned.
+pub fn list_sessions(workspace_dir: &Path, identity: Option<&str>) -> Result<Vec<ChatSession>> {
+    let identity = identity.and_then(normalize_identity);
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.name, s.identity, s.created_at, s.updated_at,
+                    (SELECT COUNT(*) FROM chat_messages m WHERE m.session_id = s.id)
+             FROM chat_sessions s
+             WHERE ?1 IS NULL OR s.identity = ?1
+             ORDER BY s.updated_at DESC, s.created_at DESC",
+        )?;
+        let rows = stmt.query_map(params![identity], map_session_row)?;
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(row?);
+        }
+        Ok(sessions)
+    })
+}
+
+pub fn get_session(workspace_dir: &Path, id: &str) -> Result<Option<ChatSession>> {
+    with_connection(workspace_dir, |conn| {
+        conn.query_row(
+            "SELECT s.id, s.name, s.identity, s.created_at, s.updated_at,
+                    (SELECT COUNT(*) FROM chat_messages m WHERE m.session_id = s.id)
+             FROM chat_sessions s WHERE s.id = ?1",
+            params![id],
+            map_session_row,
+        )
+        .optional()
+        .context("Failed to load chat session")
+    })
+}
+
+/// Apply `patch` to a session. Returns `None` when the session does not exist.
+pub fn update_session(
+    workspace_dir: &Path,
+    id: &str,
+    patch: &SessionPatch,
+) -> Result<Option<ChatSession>> {
+    let name = patch.name.as_deref().map(normalize_name).transpose()?;
+    let changed = with_connection(workspace_dir, |conn| {
+        let mut changed = 0;
+        if let Some(name) = &name {
+            changed += conn.execute(
+                "UPDATE chat_sessions SET name = ?2 WHERE id = ?1",
+                params![id, name],
+            )?;
+        }
+        if let Some(identity) = &patch.identity {
+            changed += conn.execute(
+                "UPDATE chat_sessions SET identity = ?2 WHERE id = ?1",
+                params![id, normalize_identity(identity)],
+            )?;
+        }
+        Ok(changed)
+    })?;
+
+    if changed == 0 && (name.is_some() || patch.identity.is_some()) {
+        return Ok(None);
+    }
+    get_session(workspace_dir, id)
+}
+
+/// Delete a session, its messages and any channel bindings to it.
+pub fn delete_session(workspace_dir: &Path, id: &str) -> Result<bool> {
+    with_connection(workspace_dir, |conn| {
+        let deleted = conn
+            .execute("DELETE FROM chat_sessions WHERE id = ?1", params![id])
+            .context("Failed to delete chat session")?;
+        Ok(deleted > 0)
+    })
+}
+
+pub fn load_messages(workspace_dir: &Path, id: &str) -> Result<Vec<SessionMessage>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT role, content, created_at FROM chat_messages
+             WHERE session_id = ?1 ORDER BY id ASC",
+        )?;
+        let rows = stmt.query_map(params![id], |row| {
+            Ok(SessionMessage {
+                role: row.get(0)?,
+                content: row.get(1)?,
+                created_at: parse_timestamp(&row.get::<_, String>(2)?),
+            })
+        })?;
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(row?);
+        }
+        Ok(messages)
+    })
+}
+
+/// The tail of a session's history, as provider messages, for the next turn.
+pub fn context_turns(workspace_dir: &Path, id: &str) -> Result<Vec<ChatMessage>> {
+    let messages = load_messages(workspace_dir, id)?;
+    let skip = messages.len().saturating_sub(MAX_CONTEXT_MESSAGES);
+    Ok(messages[skip..].iter().map(ChatMessage::from).collect())
+}
+
+/// Append completed turns to a session and bump its `updated_at`.
+pub fn append_messages(workspace_dir: &Path, id: &str, turns: &[ChatMessage]) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let exists = tx
+            .execute(
+                "UPDATE chat_sessions SET updated_at = ?2 WHERE id = ?1",
+                params![id, now],
+            )
+            .context("Failed to touch chat session")?;
+        if exists == 0 {
+            anyhow::bail!("Chat session '{id}' not found");
+        }
+        for turn in turns {
+            tx.execute(
+                "INSERT INTO chat_messages (session_id, role, content, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, turn.role, turn.content, now],
+            )
+            .context("Failed to append chat message")?;
+        }
+        tx.commit()?;
+        Ok(())
+    })
+}
+
+/// Attach a channel conversation (keyed like the channel history cache) to a
+/// session so its turns are read from and written to the store.
+pub fn bind_channel(workspace_dir: &Path, conversation_key: &str, session_id: &str) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO chat_session_bindings (conversation_key, session_id) VALUES (?1, ?2)
+             ON CONFLICT(conversation_key) DO UPDATE SET session_id = excluded.session_id",
+            params![conversation_key, session_id],
+        )
+        .context("Failed to bind channel conversation to session")?;
+        Ok(())
+    })
+}
+
+pub fn unbind_channel(workspace_dir: &Path, conversation_key: &str) -> Result<bool> {
+    with_connection(workspace_dir, |conn| {
+        let removed = conn.execute(
+            "DELETE FROM chat_session_bindings WHERE conversation_key = ?1",
+            params![conversation_key],
+        )?;
+        Ok(removed > 0)
+    })
+}
+
+pub fn bound_session(workspace_dir: &Path, conversation_key: &str) -> Result<Option<String>> {
+    with_connection(workspace_dir, |conn| {
+        conn.query_row(
+            "SELECT session_id FROM chat_session_bindings WHERE conversation_key = ?1",
+            params![conversation_key],
+            |row| row.get(0),
+        )
+        .optional()
+        .context("Failed to look up session binding")
+    })
+}
+
+fn map_session_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ChatSession> {
+    let count: i64 = row.get(5)?;
+    Ok(ChatSession {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        identity: row.get(2)?,
+        created_at: parse_timestamp(&row.get::<_, String>(3)?),
+        updated_at: parse_timestamp(&row.get::<_, String>(4)?),
+        message_count: usize::try_from(count).unwrap_or(0),
+    })
+}
+
+fn parse_timestamp(raw: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(raw).map_or_else(|_| Utc::now(), |dt| dt.with_timezone(&Utc))
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("sessions").join("sessions.db");
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| {
+            format!("Failed to create sessions directory: {}", parent.display())
+        })?;
+    }
+
+    let conn = Connection::open(&db_path)
+        .with_context(|| format!("Failed to open sessions DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "PRAGMA foreign_keys = ON;
+         CREATE TABLE IF NOT EXISTS chat_sessions (
+            id          TEXT PRIMARY KEY,
+            name        TEXT NOT NULL,
+            identity    TEXT,
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_chat_sessions_identity ON chat_sessions(identity);
+
+         CREATE TABLE IF NOT EXISTS chat_messages (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id  TEXT NOT NULL REFERENCES chat_sessions(id) ON DELETE CASCADE,
+            role        TEXT NOT NULL,
+            content     TEXT NOT NULL,
+            created_at  TEXT NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_chat_messages_session ON chat_messages(session_id, id);
+
+         CREATE TABLE IF NOT EXISTS chat_session_bindings (
+            conversation_key TEXT PRIMARY KEY,
+            session_id       TEXT NOT NULL REFERENCES chat_sessions(id) ON DELETE CASCADE
+         );",
+    )
+    .context("Failed to initialize sessions schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sessions_persist_messages_and_order_by_activity() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+
+        let first = create_session(dir, "First", None).unwrap();
+        let second = create_session(dir, " Second ", Some("@Alice")).unwrap();
+        assert_eq!(second.name, "Second");
+        assert_eq!(second.identity.as_deref(), Some("alice"));
+
+        append_messages(
+            dir,
+            &first.id,
+            &[ChatMessage::user("hi"), ChatMessage::assistant("hello")],
+        )
+        .unwrap();
+
+        let listed = list_sessions(dir, None).unwrap();
+        assert_eq!(listed[0].id, first.id);
+        assert_eq!(listed[0].message_count, 2);
+
+        let messages = load_messages(dir, &first.id).unwrap();
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant"]);
+        assert_eq!(messages[1].content, "hello");
+
+        let alice = list_sessions(dir, Some("ALICE")).unwrap();
+        assert_eq!(alice.len(), 1);
+        assert_eq!(alice[0].id, second.id);
+
+        assert!(append_messages(dir, "missing", &[ChatMessage::user("x")]).is_err());
+        assert!(create_session(dir, "   ", None).is_err());
+    }
+
+    #[test]
+    fn update_and_delete_cascade_to_messages_and_bindings() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let session = create_session(dir, "Draft", None).unwrap();
+        append_messages(dir, &session.id, &[ChatMessage::user("hi")]).unwrap();
+        bind_channel(dir, "telegram_alice", &session.id).unwrap();
+        assert_eq!(
+            bound_session(dir, "telegram_alice").unwrap().as_deref(),
+            Some(session.id.as_str())
+        );
+
+        let patch = SessionPatch {
+            name: Some("Renamed".into()),
+            identity: Some("alice".into()),
+        };
+        let updated = update_session(dir, &session.id, &patch).unwrap().unwrap();
+        assert_eq!(updated.name, "Renamed");
+        assert_eq!(updated.identity.as_deref(), Some("alice"));
+        let clear = SessionPatch {
+            name: None,
+            identity: Some(String::new()),
+        };
+        let cleared = update_session(dir, &session.id, &clear).unwrap().unwrap();
+        assert!(cleared.identity.is_none());
+        assert!(update_session(dir, "missing", &patch).unwrap().is_none());
+
+        assert!(delete_session(dir, &session.id).unwrap());
+        assert!(!delete_session(dir, &session.id).unwrap());
+        assert!(load_messages(dir, &session.id).unwrap().is_empty());
+        assert!(bound_session(dir, "telegram_alice").unwrap().is_none());
+    }
+
+    fn namespaces_with_alice() -> MemoryNamespacesConfig {
+        MemoryNamespacesConfig {
+            enabled: true,
+            people: vec![crate::config::MemoryPersonConfig {
+                name: "Alice".into(),
+                senders: vec!["telegram:alice_tg".into(), "gateway:tok-alice".into()],
+            }],
+        }
+    }
+
+    fn scoped_grant(token_id: &str) -> TokenGrant {
+        TokenGrant {
+            token_id: Some(token_id.into()),
+            label: "phone".into(),
+            scopes: vec![TokenScope::Chat],
+        }
+    }
+
+    #[test]
+    fn identities_resolve_through_linked_people() {
+        let config = namespaces_with_alice();
+        assert_eq!(
+            parse_identity(&config, "@Alice_TG").as_deref(),
+            Some("alice")
+        );
+        assert_eq!(parse_identity(&config, "alice").as_deref(), Some("alice"));
+        assert_eq!(
+            parse_identity(&config, "Discord:Bob").as_deref(),
+            Some("discord:bob")
+        );
+        assert_eq!(
+            parse_identity(&config, "carol").as_deref(),
+            Some("telegram:carol")
+        );
+        assert_eq!(parse_identity(&config, "  "), None);
+        assert_eq!(
+            channel_identity(&config, "telegram", "alice_tg").as_deref(),
+            Some("alice")
+        );
+    }
+
+    #[test]
+    fn scoped_tokens_only_reach_their_own_sessions() {
+        let config = namespaces_with_alice();
+        let admin = TokenGrant {
+            scopes: vec![TokenScope::Admin],
+            ..scoped_grant("tok-admin")
+        };
+        assert_eq!(
+            SessionAccess::for_grant(&config, &admin),
+            SessionAccess::All
+        );
+
+        let alice = SessionAccess::for_grant(&config, &scoped_grant("tok-alice"));
+        assert_eq!(alice, SessionAccess::Owner("alice".into()));
+        let other = SessionAccess::for_grant(&config, &scoped_grant("tok-other"));
+        assert_eq!(other, SessionAccess::Owner("gateway:tok-other".into()));
+
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let identity = alice.assign_identity(&config, None).unwrap();
+        let session = create_session(dir, "Alice's", identity.as_deref()).unwrap();
+        assert!(alice.allows(&session));
+        assert!(!other.allows(&session));
+        assert!(SessionAccess::All.allows(&session));
+
+        assert!(other.assign_identity(&config, Some("alice")).is_err());
+        assert_eq!(
+            other.list_filter(&config, Some("alice")).as_deref(),
+            Some("gateway:tok-other")
+        );
+        let listed = list_sessions(dir, other.list_filter(&config, None).as_deref()).unwrap();
+        assert!(listed.is_empty());
+        assert_eq!(
+            SessionAccess::All
+                .list_filter(&config, Some("@alice_tg"))
+                .as_deref(),
+            Some("alice")
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! SOP webhook trigger.
+//!
+//! `POST /sop/{*path}` starts runs for every loaded SOP with a
+//! `webhook` trigger whose `path` equals the request path (e.g.
+//! `/sop/deploy`). The request body, if any, becomes the event payload.
+//! Auth, rate limiting and idempotency match `/webhook`, which also gives
+//! SOPs triggered on `path = "/webhook"` the first look at chat messages.
+
+use super::{authorize_webhook, client_key_from_request, AppState, RATE_LIMIT_WINDOW_SECS};
+use crate::sop::dispatch::{dispatch_sop_event, process_headless_results, DispatchResult};
+use crate::sop::engine::now_iso8601;
+use crate::sop::{SopEvent, SopRuntime, SopTriggerSource};
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json},
+};
+use std::net::SocketAddr;
+
+/// POST /sop/{*path} — dispatch a webhook event to the SOP engine
+pub async fn handle_sop_webhook(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let rate_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/sop webhook rate limit exceeded");
+        let err = serde_json::json!({
+            "error": "Too many webhook requests. Please retry later.",
+            "retry_after": RATE_LIMIT_WINDOW_SECS,
+        });
+        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+    }
+
+    if let Err(rejection) = authorize_webhook(&state, peer_addr, &headers) {
+        return rejection;
+    }
+
+    // Keys are namespaced so a `/webhook` key never suppresses a `/sop/*` call.
+    if let Some(idempotency_key) = headers
+        .get("X-Idempotency-Key")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        if !state
+            .idempotency_store
+            .record_if_new(&format!("sop:{idempotency_key}"))
+        {
+            tracing::info!("SOP webhook duplicate ignored (idempotency key: {idempotency_key})");
+            let body = serde_json::json!({
+                "status": "duplicate",
+                "idempotent": true,
+                "message": "Request already processed for this idempotency key"
+            });
+            return (StatusCode::OK, Json(body));
+        }
+    }
+
+    let Some(runtime) = SopRuntime::shared() else {
+        let err = serde_json::json!({"error": "SOP engine is not running"});
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(err));
+    };
+
+    let path = format!("/sop/{path}");
+    let payload = String::from_utf8_lossy(&body);
+    match dispatch_webhook(&runtime, &path, &payload, "sop_webhook").await {
+        Some(response) => response,
+        None => {
+            let err =
+                serde_json::json!({"error": format!("No SOP has a webhook trigger for {path}")});
+            (StatusCode::NOT_FOUND, Json(err))
+        }
+    }
+}
+
+/// Give SOPs with a `/webhook` trigger the first look at a `/webhook`
+/// message. Returns `None` when nothing matched so the caller falls back
+/// to the chat flow.
+pub(super) async fn try_dispatch_chat_webhook(
+    message: &str,
+) -> Option<(StatusCode, Json<serde_json::Value>)> {
+    let runtime = SopRuntime::shared()?;
+    dispatch_webhook(&runtime, "/webhook", message, "webhook").await
+}
+
+/// Start runs for SOPs whose webhook trigger equals `path`.
+async fn dispatch_webhook(
+    runtime: &SopRuntime,
+    path: &str,
+    payload: &str,
+    source: &str,
+) -> Option<(StatusCode, Json<serde_json::Value>)> {
+    let event = SopEvent {
+        source: SopTriggerSource::Webhook,
+        topic: Some(path.to_string()),
+        payload: (!payload.is_empty()).then(|| payload.to_string()),
+        timestamp: now_iso8601(),
+    };
+    let results = dispatch_sop_event(&runtime.engine, &runtime.audit, event).await;
+    process_headless_results(&results).await;
+
+    let mut matched = Vec::new();
+    let mut runs = Vec::new();
+    let mut skipped = Vec::new();
+    for result in results {
+        match result {
+            DispatchResult::Started {
+                run_id, sop_name, ..
+            } => {
+                runs.push(serde_json::json!({"run_id": run_id, "sop": sop_name}));
+                matched.push(sop_name);
+            }
+            DispatchResult::Skipped { sop_name, reason } => {
+                skipped.push(serde_json::json!({"sop": sop_name, "reason": reason}));
+            }
+            DispatchResult::NoMatch => {}
+        }
+    }
+
+    if runs.is_empty() && skipped.is_empty() {
+        return None;
+    }
+
+    Some((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "status": "accepted",
+            "matched_sops": matched,
+            "source": source,
+            "path": path,
+            "runs": runs,
+            "skipped": skipped,
+        })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Config, MemoryConfig};
+    use crate::memory::Memory;
+    use std::sync::Arc;
+
+    fn runtime_with_webhook_sop(workspace: &std::path::Path) -> SopRuntime {
+        let sop_dir = workspace.join("sops").join("deploy");
+        std::fs::create_dir_all(&sop_dir).unwrap();
+        std::fs::write(
+            sop_dir.join("SOP.toml"),
+            r#"
+[sop]
+name = "deploy"
+description = "Roll out a release"
+execution_mode = "auto"
+
+[[triggers]]
+type = "webhook"
+path = "/sop/deploy"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            sop_dir.join("SOP.md"),
+            "## Steps\n\n1. **Build** — Build the release.\n\n2. **Ship** — Push it.\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            workspace_dir: workspace.to_path_buf(),
+            ..Config::default()
+        };
+        let mem_cfg = MemoryConfig {
+            backend: "sqlite".into(),
+            ..MemoryConfig::default()
+        };
+        let memory: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, workspace, None).unwrap());
+        SopRuntime::new(&config, memory)
+    }
+
+    #[tokio::test]
+    async fn webhook_path_starts_matching_sop_with_payload() {
+        let tmp = tempfile::tempdir().unwrap();
+        let runtime = runtime_with_webhook_sop(tmp.path());
+
+        let (status, Json(body)) = dispatch_webhook(
+            &runtime,
+            "/sop/deploy",
+            r#"{"version":"1.2.3"}"#,
+            "sop_webhook",
+        )
+        .await
+        .expect("deploy SOP should match");
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(body["status"], "accepted");
+        assert_eq!(body["matched_sops"], serde_json::json!(["deploy"]));
+        assert_eq!(body["path"], "/sop/deploy");
+        let run_id = body["runs"][0]["run_id"].as_str().unwrap().to_string();
+        let engine = runtime.engine.lock().unwrap();
+        let run = engine.get_run(&run_id).expect("run should be active");
+        assert_eq!(
+            run.trigger_event.payload.as_deref(),
+            Some(r#"{"version":"1.2.3"}"#)
+        );
+    }
+
+    #[tokio::test]
+    async fn webhook_path_without_sop_matches_nothing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let runtime = runtime_with_webhook_sop(tmp.path());
+
+        assert!(
+            dispatch_webhook(&runtime, "/sop/unknown", "", "sop_webhook")
+                .await
+                .is_none()
+        );
+        assert!(runtime.engine.lock().unwrap().active_runs().is_empty());
+    }
+}
@@ -2,23 +2,37 @@
 //!
 //! Wraps the broadcast channel in AppState to deliver events to web dashboard clients.
 
+use super::activity::{self, ActivityQuery, Outcome};
 use super::AppState;
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{
         sse::{Event, KeepAlive, Sse},
         IntoResponse,
     },
 };
+use serde::Deserialize;
 use std::convert::Infallible;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
 
+#[derive(Deserialize)]
+pub struct EventsQuery {
+    /// Comma-separated event types to deliver (e.g. `audit,approval,trace`).
+    pub types: Option<String>,
+    /// Same filters as the activity endpoints; when any is set, only audit,
+    /// approval and trace events that pass them are delivered.
+    pub channel: Option<String>,
+    pub tool: Option<String>,
+    pub outcome: Option<Outcome>,
+}
+
 /// GET /api/events — SSE event stream
 pub async fn handle_sse_events(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Query(query): Query<EventsQuery>,
 ) -> impl IntoResponse {
     // Auth check
     if state.pairing.require_pairing() {
@@ -28,7 +42,7 @@ pub async fn handle_sse_events(
             .and_then(|auth| auth.strip_prefix("Bearer "))
             .unwrap_or("");
 
-        if !state.pairing.is_authenticated(token).await {
+        if !state.pairing.is_authenticated(token) {
             return (
                 StatusCode::UNAUTHORIZED,
                 "Unauthorized — provide Authorization: Bearer <token>",
@@ -37,17 +51,37 @@ pub async fn handle_sse_events(
         }
     }
 
+    let types: Vec<String> = query
+        .types
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    let filters = ActivityQuery {
+        channel: query.channel,
+        tool: query.tool,
+        outcome: query.outcome,
+        ..ActivityQuery::default()
+    };
+
     let rx = state.event_tx.subscribe();
     let stream = BroadcastStream::new(rx).filter_map(
-        |result: Result<
+        move |result: Result<
             serde_json::Value,
             tokio_stream::wrappers::errors::BroadcastStreamRecvError,
         >| {
             match result {
-                Ok(value) => Some(Ok::<_, Infallible>(
-                    Event::default().data(value.to_string()),
-                )),
-                Err(_) => None, // Skip lagged messages
+                Ok(value)
+                    if event_type_selected(&types, &value)
+                        && activity::event_matches(&filters, &value) =>
+                {
+                    Some(Ok::<_, Infallible>(
+                        Event::default().data(value.to_string()),
+                    ))
+                }
+                _ => None, // Skip lagged or filtered-out messages
             }
         },
     );
@@ -57,6 +91,13 @@ pub async fn handle_sse_events(
         .into_response()
 }
 
+fn event_type_selected(types: &[String], value: &serde_json::Value) -> bool {
+    types.is_empty()
+        || value["type"]
+            .as_str()
+            .is_some_and(|kind| types.iter().any(|t| t == kind))
+}
+
 /// Broadcast observer that forwards events to the SSE broadcast channel.
 pub struct BroadcastObserver {
     inner: Box<dyn crate::observability::Observer>,
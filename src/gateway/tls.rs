@@ -0,0 +1,701 @@
+//! Built-in TLS termination for the gateway (`[gateway.tls]`).
+//!
+//! - Certificate and key are read from PEM files; with `self_signed` (the
+//!   default) a self-signed ECDSA P-256 certificate is written there on first
+//!   run when both are missing, and reused afterwards.
+//! - Renewed files are picked up by polling their modification times, so
+//!   certbot/acme.sh renewals apply without a restart.
+//! - With `client_ca_path` set, clients must present a certificate issued by
+//!   that CA (mutual TLS); the gateway then skips pairing.
+
+use crate::config::GatewayTlsConfig;
+use anyhow::{Context, Result};
+use axum::extract::ConnectInfo;
+use axum::serve::IncomingStream;
+use axum::Router;
+use parking_lot::RwLock;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::{RootCertStore, ServerConfig};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::{Duration, SystemTime};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Semaphore};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tower::Service;
+use tower_http::add_extension::AddExtension;
+
+/// Slow or stalled handshakes are dropped after this long.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Handshakes in flight at once; further connections wait to be accepted.
+const MAX_CONCURRENT_HANDSHAKES: usize = 128;
+
+/// Validity of generated self-signed certificates.
+const SELF_SIGNED_VALIDITY: Duration = Duration::from_secs(5 * 365 * 24 * 60 * 60);
+
+/// Loaded TLS settings for one gateway listener.
+pub struct GatewayTls {
+    acceptor: TlsAcceptor,
+    resolver: Arc<ReloadingCertResolver>,
+    provider: Arc<CryptoProvider>,
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    reload_interval: Option<Duration>,
+}
+
+impl GatewayTls {
+    /// Load (or generate) the certificate and build the rustls server config.
+    /// Relative paths resolve against `config_dir`.
+    pub fn from_config(tls: &GatewayTlsConfig, config_dir: &Path, host: &str) -> Result<Self> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let cert_path = resolve_path(config_dir, &tls.cert_path);
+        let key_path = resolve_path(config_dir, &tls.key_path);
+
+        if tls.self_signed && !cert_path.exists() && !key_path.exists() {
+            let hosts = self_signed_hosts(host, &tls.self_signed_hosts);
+            write_self_signed(&cert_path, &key_path, &hosts)?;
+            tracing::info!(
+                "🔐 Generated self-signed gateway certificate at {} for {}",
+                cert_path.display(),
+                hosts.join(", ")
+            );
+        }
+        for path in [&cert_path, &key_path] {
+            if !path.exists() {
+                anyhow::bail!(
+                    "gateway.tls is enabled but {} does not exist (set gateway.tls.self_signed = true and remove both files to generate a certificate)",
+                    path.display()
+                );
+            }
+        }
+
+        let key = load_certified_key(&provider, &cert_path, &key_path)?;
+        let resolver = Arc::new(ReloadingCertResolver {
+            current: RwLock::new(key),
+        });
+
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .context("Failed to select TLS protocol versions")?;
+        let builder = match &tls.client_ca_path {
+            Some(ca_path) => {
+                let roots = load_client_roots(&resolve_path(config_dir, ca_path))?;
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+                        .build()
+                        .context("Failed to build client certificate verifier")?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let mut server_config = builder.with_cert_resolver(resolver.clone());
+        server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        Ok(Self {
+            acceptor: TlsAcceptor::from(Arc::new(server_config)),
+            resolver,
+            provider,
+            cert_path,
+            key_path,
+            reload_interval: (tls.reload_interval_secs > 0)
+                .then(|| Duration::from_secs(tls.reload_interval_secs)),
+        })
+    }
+
+    /// Poll the certificate files and swap in renewed ones. Failed reloads
+    /// keep serving the previous certificate.
+    pub fn spawn_reloader(&self) {
+        let Some(interval) = self.reload_interval else {
+            return;
+        };
+        let resolver = self.resolver.clone();
+        let provider = self.provider.clone();
+        let cert_path = self.cert_path.clone();
+        let key_path = self.key_path.clone();
+        tokio::spawn(async move {
+            let mut loaded = modified_times(&cert_path, &key_path);
+            loop {
+                tokio::time::sleep(interval).await;
+                let current = modified_times(&cert_path, &key_path);
+                if current == loaded {
+                    continue;
+                }
+                match load_certified_key(&provider, &cert_path, &key_path) {
+                    Ok(key) => {
+                        *resolver.current.write() = key;
+                        loaded = current;
+                        tracing::info!(
+                            "🔐 Reloaded gateway TLS certificate from {}",
+                            cert_path.display()
+                        );
+                    }
+                    Err(e) => {
+                        tracing::warn!("Gateway TLS certificate reload failed, keeping the previous one: {e:#}");
+                    }
+                }
+            }
+        });
+    }
+
+    /// Wrap a bound TCP listener so accepted connections complete a TLS
+    /// handshake before reaching axum.
+    pub fn listener(&self, tcp: TcpListener) -> Result<TlsListener> {
+        TlsListener::new(tcp, self.acceptor.clone(), MAX_CONCURRENT_HANDSHAKES)
+    }
+}
+
+/// Serves the current certificate; swapped in place by the reloader.
+#[derive(Debug)]
+struct ReloadingCertResolver {
+    current: RwLock<Arc<CertifiedKey>>,
+}
+
+impl ResolvesServerCert for ReloadingCertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().clone())
+    }
+}
+
+/// TLS listener for `axum::serve`. Handshakes run in their own tasks so a
+/// slow client cannot hold up other connections; at most `max_handshakes`
+/// run at once, after which accepting pauses until one finishes.
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    incoming: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    fn new(tcp: TcpListener, acceptor: TlsAcceptor, max_handshakes: usize) -> Result<Self> {
+        let local_addr = tcp.local_addr()?;
+        let (tx, incoming) = mpsc::channel(64);
+        let handshakes = Arc::new(Semaphore::new(max_handshakes));
+        tokio::spawn(async move {
+            loop {
+                let Ok(permit) = handshakes.clone().acquire_owned().await else {
+                    return;
+                };
+                let (stream, peer) = match tcp.accept().await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        tracing::debug!("Gateway accept failed: {e}");
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        continue;
+                    }
+                };
+                if tx.is_closed() {
+                    return;
+                }
+                let acceptor = acceptor.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let handshake =
+                        tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await;
+                    drop(permit);
+                    match handshake {
+                        Ok(Ok(tls)) => {
+                            let _ = tx.send((tls, peer)).await;
+                        }
+                        Ok(Err(e)) => tracing::debug!("TLS handshake with {peer} failed: {e}"),
+                        Err(_) => tracing::debug!("TLS handshake with {peer} timed out"),
+                    }
+                });
+            }
+        });
+        Ok(Self {
+            local_addr,
+            incoming,
+        })
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.incoming.recv().await {
+            Some(conn) => conn,
+            // The accept task only exits once this listener is dropped.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// Make-service for serving a [`Router`] on a [`TlsListener`]: hands each
+/// connection's peer address to handlers as `ConnectInfo<SocketAddr>`, as
+/// `into_make_service_with_connect_info` does for plain TCP.
+#[derive(Clone)]
+pub struct WithConnectInfo(Router);
+
+impl WithConnectInfo {
+    pub fn new(router: Router) -> Self {
+        Self(router)
+    }
+}
+
+impl Service<IncomingStream<'_, TlsListener>> for WithConnectInfo {
+    type Response = AddExtension<Router, ConnectInfo<SocketAddr>>;
+    type Error = Infallible;
+    type Future = std::future::Ready<Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut std::task::Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, incoming: IncomingStream<'_, TlsListener>) -> Self::Future {
+        let connect_info = ConnectInfo(*incoming.remote_addr());
+        std::future::ready(Ok(AddExtension::new(self.0.clone(), connect_info)))
+    }
+}
+
+fn resolve_path(config_dir: &Path, path: &str) -> PathBuf {
+    let path = PathBuf::from(shellexpand::tilde(path).as_ref());
+    if path.is_absolute() {
+        path
+    } else {
+        config_dir.join(path)
+    }
+}
+
+fn modified_times(cert_path: &Path, key_path: &Path) -> (Option<SystemTime>, Option<SystemTime>) {
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    (modified(cert_path), modified(key_path))
+}
+
+fn load_certified_key(
+    provider: &CryptoProvider,
+    cert_path: &Path,
+    key_path: &Path,
+) -> Result<Arc<CertifiedKey>> {
+    let cert_pem = std::fs::read(cert_path)
+        .with_context(|| format!("Failed to read {}", cert_path.display()))?;
+    let certs = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid PEM certificate in {}", cert_path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificate found in {}", cert_path.display());
+    }
+    let key_pem = std::fs::read(key_path)
+        .with_context(|| format!("Failed to read {}", key_path.display()))?;
+    let key = PrivateKeyDer::from_pem_slice(&key_pem)
+        .with_context(|| format!("Invalid PEM private key in {}", key_path.display()))?;
+    let certified = CertifiedKey::from_der(certs, key, provider).with_context(|| {
+        format!(
+            "{} does not match the certificate in {}",
+            key_path.display(),
+            cert_path.display()
+        )
+    })?;
+    Ok(Arc::new(certified))
+}
+
+/// Names for a generated certificate: loopback, the bind host, this
+/// machine's hostname (plus its mDNS `.local` name) and configured extras.
+fn self_signed_hosts(bind_host: &str, extra: &[String]) -> Vec<String> {
+    let mut hosts = vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ];
+    let bind_host = bind_host.trim_start_matches('[').trim_end_matches(']');
+    if !matches!(bind_host, "" | "0.0.0.0" | "::") {
+        hosts.push(bind_host.to_string());
+    }
+    if let Some(name) = hostname::get().ok().and_then(|h| h.into_string().ok()) {
+        if !name.is_empty() && name != "localhost" {
+            hosts.push(format!("{name}.local"));
+            hosts.push(name);
+        }
+    }
+    hosts.extend(extra.iter().map(|h| h.trim().to_string()));
+    let mut seen = std::collections::HashSet::new();
+    hosts.retain(|h| seen.insert(h.to_ascii_lowercase()));
+    hosts
+}
+
+/// Generate a self-signed ECDSA P-256 server certificate for `hosts` and
+/// write it and its key (owner-only) as PEM.
+fn write_self_signed(cert_path: &Path, key_path: &Path, hosts: &[String]) -> Result<()> {
+    let mut params = rcgen::CertificateParams::new(hosts.to_vec())
+        .context("Invalid host name for the self-signed certificate")?;
+    params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, "ZeroClaw Gateway");
+    let now = SystemTime::now();
+    params.not_before = (now - Duration::from_secs(60 * 60)).into();
+    params.not_after = (now + SELF_SIGNED_VALIDITY).into();
+    let key = rcgen::KeyPair::generate().context("Failed to generate TLS key")?;
+    let cert = params
+        .self_signed(&key)
+        .context("Failed to sign TLS certificate")?;
+
+    for path in [cert_path, key_path] {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+    }
+    write_private_file(key_path, &key.serialize_pem())
+        .with_context(|| format!("Failed to write {}", key_path.display()))?;
+    std::fs::write(cert_path, cert.pem())
+        .with_context(|| format!("Failed to write {}", cert_path.display()))?;
+    Ok(())
+}
+
+fn write_private_file(path: &Path, content: &str) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(content.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::write(path, content)
+    }
+}
+
+fn load_client_roots(ca_path: &Path) -> Result<RootCertStore> {
+    let pem =
+        std::fs::read(ca_path).with_context(|| format!("Failed to read {}", ca_path.display()))?;
+    let mut roots = RootCertStore::empty();
+    for cert in CertificateDer::pem_slice_iter(&pem) {
+        let cert = cert.with_context(|| format!("Invalid PEM in {}", ca_path.display()))?;
+        roots
+            .add(cert)
+            .with_context(|| format!("Unusable CA certificate in {}", ca_path.display()))?;
+    }
+    if roots.is_empty() {
+        anyhow::bail!("No CA certificates found in {}", ca_path.display());
+    }
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    /// Copy a fixture certificate and key to `tls/gateway.{crt,key}` in `dir`.
+    fn install_certificate(dir: &Path, fixture_name: &str) {
+        std::fs::create_dir_all(dir.join("tls")).unwrap();
+        for ext in ["crt", "key"] {
+            std::fs::copy(
+                fixture(&format!("{fixture_name}.{ext}")),
+                dir.join(format!("tls/gateway.{ext}")),
+            )
+            .unwrap();
+        }
+    }
+
+    fn tls_config(dir: &Path) -> GatewayTlsConfig {
+        install_certificate(dir, "gateway_tls");
+        GatewayTlsConfig {
+            enabled: true,
+            cert_path: dir.join("tls/gateway.crt").display().to_string(),
+            key_path: dir.join("tls/gateway.key").display().to_string(),
+            ..GatewayTlsConfig::default()
+        }
+    }
+
+    fn client_config(roots: RootCertStore) -> rustls::ClientConfig {
+        rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth()
+    }
+
+    async fn handshake(addr: SocketAddr, client: rustls::ClientConfig) -> Result<Vec<u8>> {
+        let stream = TcpStream::connect(addr).await?;
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client));
+        let mut tls = connector
+            .connect("localhost".try_into().unwrap(), stream)
+            .await?;
+        let (_, conn) = tls.get_ref();
+        let cert = conn.peer_certificates().unwrap()[0].to_vec();
+        tls.write_all(b"ping").await?;
+        let mut reply = [0u8; 4];
+        tls.read_exact(&mut reply).await?;
+        assert_eq!(&reply, b"pong");
+        Ok(cert)
+    }
+
+    async fn serve_once(listener: &mut TlsListener) {
+        let (mut io, _) = axum::serve::Listener::accept(listener).await;
+        let mut buf = [0u8; 4];
+        io.read_exact(&mut buf).await.unwrap();
+        io.write_all(b"pong").await.unwrap();
+        io.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn configured_certificate_is_served() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = tls_config(tmp.path());
+        let tls = GatewayTls::from_config(&config, tmp.path(), "127.0.0.1").unwrap();
+        let cert_pem = std::fs::read(tmp.path().join("tls/gateway.crt")).unwrap();
+
+        let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let mut listener = tls.listener(tcp).unwrap();
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_slice(&cert_pem).unwrap())
+            .unwrap();
+
+        let server = tokio::spawn(async move { serve_once(&mut listener).await });
+        handshake(addr, client_config(roots)).await.unwrap();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn handlers_receive_the_peer_address() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tls =
+            GatewayTls::from_config(&tls_config(tmp.path()), tmp.path(), "127.0.0.1").unwrap();
+        let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let listener = tls.listener(tcp).unwrap();
+        let app = Router::new().route(
+            "/",
+            axum::routing::get(|ConnectInfo(peer): ConnectInfo<SocketAddr>| async move {
+                format!("peer={}", peer.ip())
+            }),
+        );
+        let server =
+            tokio::spawn(async move { axum::serve(listener, WithConnectInfo::new(app)).await });
+
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_file(fixture("gateway_tls.crt")).unwrap())
+            .unwrap();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config(roots)));
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut stream = connector
+            .connect("localhost".try_into().unwrap(), stream)
+            .await
+            .unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        server.abort();
+
+        assert!(response.ends_with("peer=127.0.0.1"), "{response}");
+    }
+
+    #[tokio::test]
+    async fn missing_certificate_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = tls_config(tmp.path());
+        std::fs::remove_file(tmp.path().join("tls/gateway.key")).unwrap();
+        let err = GatewayTls::from_config(&config, tmp.path(), "127.0.0.1")
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string().contains("gateway.key does not exist"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn self_signed_certificate_is_generated_once_and_reused() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = GatewayTlsConfig {
+            enabled: true,
+            ..GatewayTlsConfig::default()
+        };
+        let tls = GatewayTls::from_config(&config, tmp.path(), "127.0.0.1").unwrap();
+        let cert_path = tmp.path().join(&config.cert_path);
+        let cert_pem = std::fs::read(&cert_path).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(tmp.path().join(&config.key_path))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let mut listener = tls.listener(tcp).unwrap();
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_slice(&cert_pem).unwrap())
+            .unwrap();
+        let server = tokio::spawn(async move { serve_once(&mut listener).await });
+        handshake(addr, client_config(roots)).await.unwrap();
+        server.await.unwrap();
+
+        GatewayTls::from_config(&config, tmp.path(), "127.0.0.1").unwrap();
+        assert_eq!(std::fs::read(&cert_path).unwrap(), cert_pem);
+    }
+
+    #[test]
+    fn missing_certificate_without_self_signed_is_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = GatewayTlsConfig {
+            enabled: true,
+            self_signed: false,
+            ..GatewayTlsConfig::default()
+        };
+        let err = GatewayTls::from_config(&config, tmp.path(), "127.0.0.1")
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("does not exist"), "{err}");
+        assert!(!tmp.path().join(&config.key_path).exists());
+    }
+
+    #[test]
+    fn self_signed_hosts_cover_loopback_bind_host_and_extras() {
+        let hosts = self_signed_hosts("192.168.1.20", &["zeroclaw.lan".into(), "LOCALHOST".into()]);
+        assert_eq!(
+            &hosts[..4],
+            ["localhost", "127.0.0.1", "::1", "192.168.1.20"]
+        );
+        assert!(hosts.contains(&"zeroclaw.lan".to_string()));
+        assert_eq!(
+            hosts
+                .iter()
+                .filter(|h| h.eq_ignore_ascii_case("localhost"))
+                .count(),
+            1
+        );
+
+        let wildcard = self_signed_hosts("0.0.0.0", &[]);
+        assert!(!wildcard.contains(&"0.0.0.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn stalled_handshakes_are_capped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tls =
+            GatewayTls::from_config(&tls_config(tmp.path()), tmp.path(), "127.0.0.1").unwrap();
+        let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let mut listener = TlsListener::new(tcp, tls.acceptor.clone(), 1).unwrap();
+
+        // A client that never sends a ClientHello holds the only slot.
+        let stalled = TcpStream::connect(addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_file(fixture("gateway_tls.crt")).unwrap())
+            .unwrap();
+        let client = tokio::spawn(handshake(addr, client_config(roots)));
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(!client.is_finished());
+
+        // Freeing the slot lets the waiting connection through.
+        drop(stalled);
+        serve_once(&mut listener).await;
+        client.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn renewed_certificate_is_reloaded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = tls_config(tmp.path());
+        let tls = GatewayTls::from_config(&config, tmp.path(), "127.0.0.1").unwrap();
+        let before = tls.resolver.current.read().cert[0].to_vec();
+        let tls = GatewayTls {
+            reload_interval: Some(Duration::from_millis(10)),
+            ..tls
+        };
+        tls.spawn_reloader();
+
+        // Simulate a renewal: new files with a later modification time.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        install_certificate(tmp.path(), "gateway_tls_renewed");
+
+        let mut reloaded = false;
+        for _ in 0..100 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            if tls.resolver.current.read().cert[0].as_ref() != before.as_slice() {
+                reloaded = true;
+                break;
+            }
+        }
+        assert!(reloaded, "certificate was not reloaded");
+    }
+
+    #[tokio::test]
+    async fn client_ca_requires_a_trusted_client_certificate() {
+        let tmp = tempfile::tempdir().unwrap();
+        // The server certificate doubles as the client CA and as the
+        // client's own certificate.
+        let server_cfg = tls_config(tmp.path());
+        let config = GatewayTlsConfig {
+            client_ca_path: Some(server_cfg.cert_path.clone()),
+            ..server_cfg
+        };
+        let tls = GatewayTls::from_config(&config, tmp.path(), "127.0.0.1").unwrap();
+
+        let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let mut listener = tls.listener(tcp).unwrap();
+        let server = tokio::spawn(async move { serve_once(&mut listener).await });
+
+        let cert_pem = std::fs::read(tmp.path().join("tls/gateway.crt")).unwrap();
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_slice(&cert_pem).unwrap())
+            .unwrap();
+        assert!(handshake(addr, client_config(roots.clone())).await.is_err());
+        server.abort();
+
+        // A client presenting a certificate issued by the CA gets through.
+        let mut listener = tls
+            .listener(TcpListener::bind("127.0.0.1:0").await.unwrap())
+            .unwrap();
+        let addr = axum::serve::Listener::local_addr(&listener).unwrap();
+        let server = tokio::spawn(async move { serve_once(&mut listener).await });
+        let key_pem = std::fs::read(tmp.path().join("tls/gateway.key")).unwrap();
+        let client = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_client_auth_cert(
+            vec![CertificateDer::from_pem_slice(&cert_pem).unwrap()],
+            PrivateKeyDer::from_pem_slice(&key_pem).unwrap(),
+        )
+        .unwrap();
+        handshake(addr, client).await.unwrap();
+        server.await.unwrap();
+    }
+}
@@ -0,0 +1,131 @@
+//! `zeroclaw gateway tokens` subcommands.
+//!
+//! Edits `[[gateway.api_tokens]]` in the config file. A running gateway picks
+//! up changes on restart; use `/api/tokens` to manage tokens live.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use console::style;
+
+use crate::config::Config;
+use crate::security::pairing::issue_api_token;
+use crate::security::TokenScope;
+
+pub async fn handle_command(command: crate::GatewayTokenCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::GatewayTokenCommands::List => {
+            list(config);
+            Ok(())
+        }
+        crate::GatewayTokenCommands::Create {
+            label,
+            scopes,
+            expires_in_days,
+        } => create(config, &label, &scopes, expires_in_days).await,
+        crate::GatewayTokenCommands::Revoke { id } => revoke(config, &id).await,
+    }
+}
+
+fn list(config: &Config) {
+    let tokens = &config.gateway.api_tokens;
+    if tokens.is_empty() {
+        println!("No API tokens. Create one with `zeroclaw gateway tokens create`.");
+        return;
+    }
+
+    let now = Utc::now();
+    println!("API tokens ({}):", tokens.len());
+    for token in tokens {
+        let status = if let Some(at) = token.revoked_at {
+            style(format!("revoked {}", at.format("%Y-%m-%d"))).red()
+        } else if token.expires_at.is_some_and(|at| at <= now) {
+            style("expired".to_string()).yellow()
+        } else if let Some(at) = token.expires_at {
+            style(format!("expires {}", at.format("%Y-%m-%d"))).green()
+        } else {
+            style("active".to_string()).green()
+        };
+        let scopes: Vec<&str> = token.scopes.iter().map(|s| s.as_str()).collect();
+        println!(
+            "  {} {} [{}] {}",
+            style(&token.id).white().bold(),
+            token.label,
+            scopes.join(", "),
+            status
+        );
+    }
+}
+
+async fn create(
+    config: &Config,
+    label: &str,
+    scopes: &[String],
+    expires_in_days: Option<u32>,
+) -> Result<()> {
+    let scopes = parse_scopes(scopes)?;
+    let expires_at = expires_in_days.map(|days| Utc::now() + chrono::Duration::days(days.into()));
+
+    let (token, record) = issue_api_token(label.trim(), scopes, expires_at);
+    let mut updated = config.clone();
+    updated.gateway.api_tokens.push(record.clone());
+    updated
+        .save()
+        .await
+        .context("Failed to save API token to config.toml")?;
+
+    println!("Created API token {}", style(&record.id).white().bold());
+    println!();
+    println!("  {}", style(&token).cyan().bold());
+    println!();
+    println!("Store it now — only its hash is kept. Restart a running gateway to load it.");
+    Ok(())
+}
+
+async fn revoke(config: &Config, id: &str) -> Result<()> {
+    let mut updated = config.clone();
+    let token = updated
+        .gateway
+        .api_tokens
+        .iter_mut()
+        .find(|t| t.id == id)
+        .with_context(|| format!("No API token with id '{id}'"))?;
+    if token.revoked_at.is_some() {
+        println!("API token {id} is already revoked.");
+        return Ok(());
+    }
+    token.revoked_at = Some(Utc::now());
+    updated
+        .save()
+        .await
+        .context("Failed to save revocation to config.toml")?;
+
+    println!("Revoked API token {id}. Restart a running gateway to apply.");
+    Ok(())
+}
+
+fn parse_scopes(raw: &[String]) -> Result<Vec<TokenScope>> {
+    let mut scopes = Vec::new();
+    for value in raw.iter().flat_map(|s| s.split(',')) {
+        let scope: TokenScope = value.parse()?;
+        if !scopes.contains(&scope) {
+            scopes.push(scope);
+        }
+    }
+    if scopes.is_empty() {
+        anyhow::bail!("at least one --scope is required");
+    }
+    Ok(scopes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scopes_accepts_repeats_and_commas() {
+        let scopes = parse_scopes(&["read,chat".into(), "READ".into()]).unwrap();
+        assert_eq!(scopes, vec![TokenScope::Read, TokenScope::Chat]);
+        assert!(parse_scopes(&["owner".into()]).is_err());
+        assert!(parse_scopes(&[]).is_err());
+    }
+}
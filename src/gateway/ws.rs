@@ -1,14 +1,23 @@
 //! WebSocket agent chat handler.
 //!
+//! Chats are persisted as named sessions (see [`super::sessions`]). A client
+//! may open or resume one before chatting; otherwise the first message
+//! creates a session named after it. Scoped tokens can only resume sessions
+//! they own, and the sessions they create are owned by them.
+//!
 //! Protocol:
 //! ```text
+//! Client -> Server: {"type":"new","name":"Trip plans","identity":"alice"}
+//! Client -> Server: {"type":"resume","session_id":"..."}
+//! Server -> Client: {"type":"session","session":{...},"messages":[...]}
 //! Client -> Server: {"type":"message","content":"Hello"}
 //! Server -> Client: {"type":"chunk","content":"Hi! "}
 //! Server -> Client: {"type":"tool_call","name":"shell","args":{...}}
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
-//! Server -> Client: {"type":"done","full_response":"..."}
+//! Server -> Client: {"type":"done","session_id":"...","full_response":"..."}
 //! ```
 
+use super::sessions::{self, ChatSession, SessionAccess, SessionMessage};
 use super::AppState;
 use crate::agent::loop_::run_tool_call_loop;
 use crate::approval::ApprovalManager;
@@ -39,26 +48,64 @@ pub async fn handle_ws_chat(
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // Auth via Authorization header or websocket protocol token.
-    if state.pairing.require_pairing() {
-        let token = extract_ws_bearer_token(&headers).unwrap_or_default();
-        if !state.pairing.is_authenticated(&token) {
-            return (
-                axum::http::StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide Authorization: Bearer <token> or Sec-WebSocket-Protocol: bearer.<token>",
-            )
-                .into_response();
-        }
-    }
+    let token = extract_ws_bearer_token(&headers).unwrap_or_default();
+    let Some(grant) = state.pairing.authenticate(&token) else {
+        return (
+            axum::http::StatusCode::UNAUTHORIZED,
+            "Unauthorized — provide Authorization: Bearer <token> or Sec-WebSocket-Protocol: bearer.<token>",
+        )
+            .into_response();
+    };
+    let access = SessionAccess::for_grant(&state.config.lock().memory.namespaces, &grant);
 
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, access))
         .into_response()
 }
 
-async fn handle_socket(mut socket: WebSocket, state: AppState) {
-    // Maintain conversation history for this WebSocket session
-    let mut history: Vec<ChatMessage> = Vec::new();
+async fn send_json(socket: &mut WebSocket, value: &serde_json::Value) {
+    let _ = socket.send(Message::Text(value.to_string().into())).await;
+}
+
+async fn send_error(socket: &mut WebSocket, message: &str) {
+    send_json(
+        socket,
+        &serde_json::json!({"type": "error", "message": message}),
+    )
+    .await;
+}
+
+async fn send_session(socket: &mut WebSocket, session: &ChatSession, messages: &[SessionMessage]) {
+    send_json(
+        socket,
+        &serde_json::json!({
+            "type": "session",
+            "session": session,
+            "messages": messages,
+        }),
+    )
+    .await;
+}
 
-    // Build system prompt once for the session
+/// Name for a session created implicitly by its first message.
+fn session_name_from_message(content: &str) -> String {
+    let first_line = content.lines().next().unwrap_or("").trim();
+    if first_line.is_empty() {
+        "New chat".to_string()
+    } else {
+        crate::util::truncate_with_ellipsis(first_line, 48)
+    }
+}
+
+async fn handle_socket(mut socket: WebSocket, state: AppState, access: SessionAccess) {
+    let (workspace_dir, namespaces) = {
+        let config_guard = state.config.lock();
+        (
+            config_guard.workspace_dir.clone(),
+            config_guard.memory.namespaces.clone(),
+        )
+    };
+
+    // Build system prompt once for the socket
     let system_prompt = {
         let config_guard = state.config.lock();
         crate::channels::build_system_prompt(
@@ -71,14 +118,15 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
         )
     };
 
-    // Add system message to history
-    history.push(ChatMessage::system(&system_prompt));
-
     let approval_manager = {
         let config_guard = state.config.lock();
         ApprovalManager::from_config(&config_guard.autonomy)
     };
 
+    // The persisted session this socket is attached to. Created on the first
+    // message unless the client sent `new` or `resume` beforehand.
+    let mut session: Option<ChatSession> = None;
+
     while let Some(msg) = socket.recv().await {
         let msg = match msg {
             Ok(Message::Text(text)) => text,
@@ -90,15 +138,48 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
         let parsed: serde_json::Value = match serde_json::from_str(&msg) {
             Ok(v) => v,
             Err(_) => {
-                let err = serde_json::json!({"type": "error", "message": "Invalid JSON"});
-                let _ = socket.send(Message::Text(err.to_string().into())).await;
+                send_error(&mut socket, "Invalid JSON").await;
                 continue;
             }
         };
 
-        let msg_type = parsed["type"].as_str().unwrap_or("");
-        if msg_type != "message" {
-            continue;
+        match parsed["type"].as_str().unwrap_or("") {
+            "new" => {
+                let name = parsed["name"].as_str().unwrap_or("New chat");
+                let created = access
+                    .assign_identity(&namespaces, parsed["identity"].as_str())
+                    .and_then(|identity| {
+                        sessions::create_session(&workspace_dir, name, identity.as_deref())
+                    });
+                match created {
+                    Ok(created) => {
+                        send_session(&mut socket, &created, &[]).await;
+                        session = Some(created);
+                    }
+                    Err(e) => send_error(&mut socket, &e.to_string()).await,
+                }
+                continue;
+            }
+            "resume" => {
+                let id = parsed["session_id"].as_str().unwrap_or("");
+                let found = sessions::get_session(&workspace_dir, id).and_then(|found| {
+                    found
+                        .filter(|s| access.allows(s))
+                        .map(|s| Ok((sessions::load_messages(&workspace_dir, &s.id)?, s)))
+                        .transpose()
+                });
+                match found {
+                    Ok(Some((messages, resumed))) => {
+                        send_session(&mut socket, &resumed, &messages).await;
+                        session = Some(resumed);
+                    }
+                    Ok(None) => send_error(&mut socket, &format!("Unknown session: {id}")).await,
+                    Err(e) => send_error(&mut socket, &e.to_string()).await,
+                }
+                continue;
+            }
+            "message" => {}
+            _ => continue,
         }
 
         let content = parsed["content"].as_str().unwrap_or("").to_string();
@@ -106,7 +187,39 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             continue;
         }
 
-        // Add user message to history
+        if session.is_none() {
+            let name = session_name_from_message(&content);
+            let created = access
+                .assign_identity(&namespaces, None)
+                .and_then(|identity| {
+                    sessions::create_session(&workspace_dir, &name, identity.as_deref())
+                });
+            match created {
+                Ok(created) => {
+                    send_session(&mut socket, &created, &[]).await;
+                    session = Some(created);
+                }
+                Err(e) => {
+                    send_error(&mut socket, &e.to_string()).await;
+                    continue;
+                }
+            }
+        }
+        let Some(session_id) = session.as_ref().map(|s| s.id.clone()) else {
+            continue;
+        };
+
+        // Rebuild history from the store every turn so turns added by other
+        // clients of the same session (another tab, a bound Telegram chat)
+        // are part of the context.
+        let mut history = vec![ChatMessage::system(&system_prompt)];
+        match sessions::context_turns(&workspace_dir, &session_id) {
+            Ok(turns) => history.extend(turns),
+            Err(e) => {
+                send_error(&mut socket, &e.to_string()).await;
+                continue;
+            }
+        }
         history.push(ChatMessage::user(&content));
 
         // Get provider info
@@ -142,6 +255,7 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             None, // delta streaming
             None, // hooks
             &[],  // excluded tools
+            None, // context budget
         )
         .await;
 
@@ -149,15 +263,27 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             Ok(response) => {
                 let safe_response =
                     sanitize_ws_response(&response, state.tools_registry_exec.as_ref());
-                // Add assistant response to history
-                history.push(ChatMessage::assistant(&safe_response));
+                if let Err(e) = sessions::append_messages(
+                    &workspace_dir,
+                    &session_id,
+                    &[
+                        ChatMessage::user(&content),
+                        ChatMessage::assistant(&safe_response),
+                    ],
+                ) {
+                    tracing::warn!("Failed to persist chat session {session_id}: {e}");
+                }
 
                 // Send the full response as a done message
-                let done = serde_json::json!({
-                    "type": "done",
-                    "full_response": safe_response,
-                });
-                let _ = socket.send(Message::Text(done.to_string().into())).await;
+                send_json(
+                    &mut socket,
+                    &serde_json::json!({
+                        "type": "done",
+                        "session_id": session_id,
+                        "full_response": safe_response,
+                    }),
+                )
+                .await;
 
                 // Broadcast agent_end event
                 let _ = state.event_tx.send(serde_json::json!({
@@ -168,11 +294,7 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             }
             Err(e) => {
                 let sanitized = crate::providers::sanitize_api_error(&e.to_string());
-                let err = serde_json::json!({
-                    "type": "error",
-                    "message": sanitized,
-                });
-                let _ = socket.send(Message::Text(err.to_string().into())).await;
+                send_error(&mut socket, &sanitized).await;
 
                 // Broadcast error event
                 let _ = state.event_tx.send(serde_json::json!({
@@ -185,7 +307,7 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
     }
 }
 
-fn extract_ws_bearer_token(headers: &HeaderMap) -> Option<String> {
+pub(super) fn extract_ws_bearer_token(headers: &HeaderMap) -> Option<String> {
     if let Some(auth_header) = headers
         .get(header::AUTHORIZATION)
         .and_then(|value| value.to_str().ok())
@@ -0,0 +1,124 @@
+//! `zeroclaw hooks` subcommands.
+
+use anyhow::{Context, Result};
+use console::style;
+
+use super::external::ExternalHook;
+use super::traits::HOOK_POINTS;
+use super::wasm;
+use crate::config::{Config, HookFailurePolicy};
+
+pub async fn handle_command(command: crate::HooksCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::HooksCommands::List => list(config),
+        crate::HooksCommands::Test {
+            name,
+            event,
+            payload,
+        } => test(config, &name, &event, &payload).await,
+    }
+}
+
+fn list(config: &Config) -> Result<()> {
+    let hooks = &config.hooks;
+    println!(
+        "Hooks: {}",
+        if hooks.enabled { "enabled" } else { "disabled" }
+    );
+    println!();
+
+    println!("Built-in:");
+    println!(
+        "  {} {}",
+        style("command_logger").white().bold(),
+        if hooks.builtin.command_logger {
+            style("on").green()
+        } else {
+            style("off").dim()
+        }
+    );
+    println!();
+
+    println!("External ({}):", hooks.external.len());
+    for hook in &hooks.external {
+        let target = hook
+            .command
+            .as_deref()
+            .or(hook.url.as_deref())
+            .unwrap_or_default();
+        println!(
+            "  {} {} [{}] — {}",
+            style(&hook.name).white().bold(),
+            style(target).dim(),
+            style(match hook.failure_policy {
+                HookFailurePolicy::FailOpen => "fail_open",
+                HookFailurePolicy::FailClosed => "fail_closed",
+            })
+            .cyan(),
+            hook.events.join(", ")
+        );
+    }
+    println!();
+
+    let plugins = wasm::discover(config)?;
+    println!(
+        "WASM plugins ({}){}: {}",
+        plugins.len(),
+        if hooks.wasm.enabled {
+            ""
+        } else {
+            " (disabled)"
+        },
+        wasm::plugin_dir(config).display()
+    );
+    for plugin in &plugins {
+        match &plugin.hook {
+            Ok(hook) => println!(
+                "  {} — {}",
+                style(&plugin.name).white().bold(),
+                hook.events().join(", ")
+            ),
+            Err(e) => println!(
+                "  {} {}",
+                style(&plugin.name).white().bold(),
+                style(format!("failed to load: {e:#}")).red()
+            ),
+        }
+    }
+    Ok(())
+}
+
+async fn test(config: &Config, name: &str, event: &str, payload: &str) -> Result<()> {
+    if !HOOK_POINTS.contains(&event) {
+        anyhow::bail!(
+            "unknown hook point '{event}' (expected one of: {})",
+            HOOK_POINTS.join(", ")
+        );
+    }
+    let payload: serde_json::Value =
+        serde_json::from_str(payload).context("--payload must be valid JSON")?;
+
+    let hook = find_hook(config, name)?;
+    if !hook.events().iter().any(|e| e == event) {
+        println!(
+            "{}",
+            style(format!(
+                "note: {name} does not subscribe to {event}; sending anyway"
+            ))
+            .yellow()
+        );
+    }
+    let reply = hook.test(event, payload).await?;
+    println!("{}", serde_json::to_string_pretty(&reply)?);
+    Ok(())
+}
+
+fn find_hook(config: &Config, name: &str) -> Result<ExternalHook> {
+    if let Some(hook) = config.hooks.external.iter().find(|h| h.name == name) {
+        return Ok(ExternalHook::new(hook.clone()));
+    }
+    if let Some(plugin) = wasm::discover(config)?.into_iter().find(|p| p.name == name) {
+        return plugin.hook;
+    }
+    anyhow::bail!("no external hook or WASM plugin named '{name}'")
+}
@@ -0,0 +1,594 @@
+//! User-scriptable hooks backed by an external command or HTTP endpoint.
+//!
+//! Each invocation sends `{"hook": "<point>", "payload": {...}}` as JSON —
+//! on the command's stdin, or as the body of a POST to the webhook URL.
+//! Modifying hooks read the reply (stdout or response body):
+//!
+//! - empty, or `{"action": "continue"}` — continue unchanged
+//! - `{"action": "continue", "payload": {...}}` — continue with the fields
+//!   present in `payload` rewritten; omitted fields keep their value
+//! - `{"action": "cancel", "reason": "..."}` — cancel the operation
+//!
+//! Errors, timeouts and malformed replies follow the hook's
+//! [`HookFailurePolicy`]. Void hooks run in the background and only log
+//! failures, since there is nothing to cancel.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+use super::traits::{HookHandler, HookResult};
+use crate::channels::traits::ChannelMessage;
+use crate::config::{ExternalHookConfig, HookFailurePolicy};
+use crate::providers::traits::{ChatMessage, ChatResponse};
+use crate::tools::traits::ToolResult;
+
+/// Cap on stderr echoed into error messages.
+const MAX_STDERR_CHARS: usize = 500;
+
+/// A reply from a modifying hook.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum HookReply {
+    Continue {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        payload: Option<Value>,
+    },
+    Cancel {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+}
+
+/// Where an [`ExternalHook`] delivers its JSON requests.
+#[async_trait]
+pub(crate) trait HookTransport: Send + Sync {
+    /// Deliver one request and return the raw reply.
+    async fn call(&self, request: Vec<u8>) -> Result<String>;
+}
+
+/// Runs an executable per event; the request goes to stdin.
+struct CommandTransport {
+    command: String,
+    args: Vec<String>,
+}
+
+#[async_trait]
+impl HookTransport for CommandTransport {
+    async fn call(&self, request: Vec<u8>) -> Result<String> {
+        // kill_on_drop reaps the child when the caller's timeout fires.
+        let mut child = tokio::process::Command::new(&self.command)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to start {}", self.command))?;
+
+        let mut stdin = child.stdin.take().context("hook stdin unavailable")?;
+        let write = async move {
+            // A hook may exit without reading its input; that is not an error.
+            let _ = stdin.write_all(&request).await;
+        };
+        let output = tokio::join!(write, child.wait_with_output()).1?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr: String = stderr.trim().chars().take(MAX_STDERR_CHARS).collect();
+            anyhow::bail!("exited with {}: {stderr}", output.status);
+        }
+        String::from_utf8(output.stdout).context("hook output is not valid UTF-8")
+    }
+}
+
+/// POSTs each event to a URL; the response body is the reply.
+struct WebhookTransport {
+    client: reqwest::Client,
+    url: String,
+    headers: HashMap<String, String>,
+}
+
+#[async_trait]
+impl HookTransport for WebhookTransport {
+    async fn call(&self, request: Vec<u8>) -> Result<String> {
+        let mut builder = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(request);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        let response = builder.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("webhook returned {status}");
+        }
+        Ok(response.text().await?)
+    }
+}
+
+/// Hook handler that forwards subscribed hook points as JSON to an external
+/// command, webhook (`[[hooks.external]]`) or WASM plugin (`[hooks.wasm]`).
+#[derive(Clone)]
+pub struct ExternalHook {
+    name: String,
+    events: Vec<String>,
+    priority: i32,
+    failure_policy: HookFailurePolicy,
+    timeout: Option<Duration>,
+    transport: Arc<dyn HookTransport>,
+}
+
+impl ExternalHook {
+    pub fn new(config: ExternalHookConfig) -> Self {
+        let transport: Arc<dyn HookTransport> = match (config.command, config.url) {
+            (Some(command), _) => Arc::new(CommandTransport {
+                command,
+                args: config.args,
+            }),
+            (None, url) => Arc::new(WebhookTransport {
+                client: crate::config::build_runtime_proxy_client("hooks.webhook"),
+                url: url.unwrap_or_default(),
+                headers: config.headers,
+            }),
+        };
+        Self {
+            name: config.name,
+            events: config.events,
+            priority: config.priority,
+            failure_policy: config.failure_policy,
+            timeout: Some(Duration::from_millis(config.timeout_ms)),
+            transport,
+        }
+    }
+
+    /// Build a hook over a custom transport. `timeout = None` leaves the
+    /// transport to bound its own run time.
+    pub(crate) fn with_transport(
+        name: String,
+        events: Vec<String>,
+        failure_policy: HookFailurePolicy,
+        timeout: Option<Duration>,
+        transport: Arc<dyn HookTransport>,
+    ) -> Self {
+        Self {
+            name,
+            events,
+            priority: 0,
+            failure_policy,
+            timeout,
+            transport,
+        }
+    }
+
+    /// Hook points this hook subscribes to.
+    pub fn events(&self) -> &[String] {
+        &self.events
+    }
+
+    fn subscribed(&self, point: &str) -> bool {
+        self.events.iter().any(|event| event == point)
+    }
+
+    /// Send one event and parse the reply. `Ok(None)` means an empty reply.
+    async fn invoke(&self, point: &str, payload: Value) -> Result<Option<HookReply>> {
+        let request = serde_json::to_vec(&json!({"hook": point, "payload": payload}))?;
+        let reply = match self.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, self.transport.call(request))
+                .await
+                .map_err(|_| anyhow::anyhow!("timed out after {}ms", timeout.as_millis()))??,
+            None => self.transport.call(request).await?,
+        };
+
+        let reply = reply.trim();
+        if reply.is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str(reply)
+            .map(Some)
+            .context("invalid hook reply")
+    }
+
+    /// Send `payload` to the hook as `point` and return its normalized
+    /// reply, regardless of subscriptions or failure policy. Used by
+    /// `zeroclaw hooks test`.
+    pub async fn test(&self, point: &str, payload: Value) -> Result<Value> {
+        let reply = self
+            .invoke(point, payload)
+            .await?
+            .unwrap_or(HookReply::Continue { payload: None });
+        Ok(serde_json::to_value(reply)?)
+    }
+
+    /// Fire a void hook in the background; failures are only logged.
+    fn notify(&self, point: &'static str, payload: impl FnOnce() -> Value) {
+        if !self.subscribed(point) {
+            return;
+        }
+        let hook = self.clone();
+        let payload = payload();
+        tokio::spawn(async move {
+            if let Err(e) = hook.invoke(point, payload).await {
+                tracing::warn!(hook = %hook.name, "{point} hook failed: {e:#}");
+            }
+        });
+    }
+
+    /// Run a modifying hook. `apply` merges the reply payload into the
+    /// original value.
+    async fn modify<T: Clone>(
+        &self,
+        point: &str,
+        original: T,
+        payload: impl FnOnce(&T) -> Value,
+        apply: impl FnOnce(T, &Value) -> Result<T>,
+    ) -> HookResult<T> {
+        if !self.subscribed(point) {
+            return HookResult::Continue(original);
+        }
+        let outcome = match self.invoke(point, payload(&original)).await {
+            Ok(None | Some(HookReply::Continue { payload: None })) => Ok(None),
+            Ok(Some(HookReply::Continue {
+                payload: Some(reply),
+            })) => Ok(Some(reply)),
+            Ok(Some(HookReply::Cancel { reason })) => {
+                let reason = reason.unwrap_or_else(|| format!("cancelled by hook {}", self.name));
+                return HookResult::Cancel(reason);
+            }
+            Err(e) => Err(e),
+        };
+
+        let error = match outcome {
+            Ok(None) => return HookResult::Continue(original),
+            Ok(Some(reply)) => match apply(original.clone(), &reply) {
+                Ok(value) => return HookResult::Continue(value),
+                Err(e) => e.context("invalid hook payload"),
+            },
+            Err(e) => e,
+        };
+        match self.failure_policy {
+            HookFailurePolicy::FailOpen => {
+                tracing::warn!(hook = %self.name, "{point} hook failed, continuing: {error:#}");
+                HookResult::Continue(original)
+            }
+            HookFailurePolicy::FailClosed => {
+                HookResult::Cancel(format!("hook {} failed: {error:#}", self.name))
+            }
+        }
+    }
+}
+
+/// Replace `target` with the string at `reply[key]`, if present.
+fn rewrite_str(target: &mut String, reply: &Value, key: &str) -> Result<()> {
+    match reply.get(key) {
+        None => Ok(()),
+        Some(Value::String(value)) => {
+            target.clone_from(value);
+            Ok(())
+        }
+        Some(_) => anyhow::bail!("`{key}` must be a string"),
+    }
+}
+
+fn message_json(message: &ChannelMessage) -> Value {
+    json!({
+        "id": message.id,
+        "sender": message.sender,
+        "reply_target": message.reply_target,
+        "content": message.content,
+        "channel": message.channel,
+        "timestamp": message.timestamp,
+        "thread_ts": message.thread_ts,
+    })
+}
+
+#[async_trait]
+impl HookHandler for ExternalHook {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    async fn on_gateway_start(&self, host: &str, port: u16) {
+        self.notify("on_gateway_start", || json!({"host": host, "port": port}));
+    }
+
+    async fn on_gateway_stop(&self) {
+        self.notify("on_gateway_stop", || json!({}));
+    }
+
+    async fn on_session_start(&self, session_id: &str, channel: &str) {
+        self.notify(
+            "on_session_start",
+            || json!({"session_id": session_id, "channel": channel}),
+        );
+    }
+
+    async fn on_session_end(&self, session_id: &str, channel: &str) {
+        self.notify(
+            "on_session_end",
+            || json!({"session_id": session_id, "channel": channel}),
+        );
+    }
+
+    async fn on_llm_input(&self, messages: &[ChatMessage], model: &str) {
+        self.notify(
+            "on_llm_input",
+            || json!({"messages": messages, "model": model}),
+        );
+    }
+
+    async fn on_llm_output(&self, response: &ChatResponse) {
+        self.notify(
+            "on_llm_output",
+            || json!({"text": response.text, "tool_calls": response.tool_calls}),
+        );
+    }
+
+    async fn on_after_tool_call(&self, tool: &str, result: &ToolResult, duration: Duration) {
+        self.notify("on_after_tool_call", || {
+            json!({
+                "tool": tool,
+                "result": result,
+                "duration_ms": u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            })
+        });
+    }
+
+    async fn on_message_sent(&self, channel: &str, recipient: &str, content: &str) {
+        self.notify(
+            "on_message_sent",
+            || json!({"channel": channel, "recipient": recipient, "content": content}),
+        );
+    }
+
+    async fn on_heartbeat_tick(&self) {
+        self.notify("on_heartbeat_tick", || json!({}));
+    }
+
+    async fn before_model_resolve(
+        &self,
+        provider: String,
+        model: String,
+    ) -> HookResult<(String, String)> {
+        self.modify(
+            "before_model_resolve",
+            (provider, model),
+            |(provider, model)| json!({"provider": provider, "model": model}),
+            |(mut provider, mut model), reply| {
+                rewrite_str(&mut provider, reply, "provider")?;
+                rewrite_str(&mut model, reply, "model")?;
+                Ok((provider, model))
+            },
+        )
+        .await
+    }
+
+    async fn before_prompt_build(&self, prompt: String) -> HookResult<String> {
+        self.modify(
+            "before_prompt_build",
+            prompt,
+            |prompt| json!({"prompt": prompt}),
+            |mut prompt, reply| {
+                rewrite_str(&mut prompt, reply, "prompt")?;
+                Ok(prompt)
+            },
+        )
+        .await
+    }
+
+    async fn before_llm_call(
+        &self,
+        messages: Vec<ChatMessage>,
+        model: String,
+    ) -> HookResult<(Vec<ChatMessage>, String)> {
+        self.modify(
+            "before_llm_call",
+            (messages, model),
+            |(messages, model)| json!({"messages": messages, "model": model}),
+            |(mut messages, mut model), reply| {
+                if let Some(value) = reply.get("messages") {
+                    messages = serde_json::from_value(value.clone())
+                        .context("`messages` must be a list of {role, content}")?;
+                }
+                rewrite_str(&mut model, reply, "model")?;
+                Ok((messages, model))
+            },
+        )
+        .await
+    }
+
+    async fn before_tool_call(&self, name: String, args: Value) -> HookResult<(String, Value)> {
+        self.modify(
+            "before_tool_call",
+            (name, args),
+            |(name, args)| json!({"tool": name, "args": args}),
+            |(mut name, mut args), reply| {
+                rewrite_str(&mut name, reply, "tool")?;
+                if let Some(value) = reply.get("args") {
+                    args = value.clone();
+                }
+                Ok((name, args))
+            },
+        )
+        .await
+    }
+
+    async fn on_message_received(&self, message: ChannelMessage) -> HookResult<ChannelMessage> {
+        // Only the content is rewritable; routing fields stay as received.
+        self.modify(
+            "on_message_received",
+            message,
+            message_json,
+            |mut message, reply| {
+                rewrite_str(&mut message.content, reply, "content")?;
+                Ok(message)
+            },
+        )
+        .await
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        self.modify(
+            "on_message_sending",
+            (channel, recipient, content),
+            |(channel, recipient, content)| {
+                json!({"channel": channel, "recipient": recipient, "content": content})
+            },
+            |(mut channel, mut recipient, mut content), reply| {
+                rewrite_str(&mut channel, reply, "channel")?;
+                rewrite_str(&mut recipient, reply, "recipient")?;
+                rewrite_str(&mut content, reply, "content")?;
+                Ok((channel, recipient, content))
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Write an executable shell script and return a hook that runs it.
+    fn script_hook(
+        dir: &std::path::Path,
+        script: &str,
+        events: &[&str],
+        failure_policy: HookFailurePolicy,
+    ) -> ExternalHook {
+        let path = dir.join("hook.sh");
+        std::fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        ExternalHook::new(ExternalHookConfig {
+            name: "test-hook".into(),
+            events: events.iter().map(|e| (*e).to_string()).collect(),
+            command: Some(path.to_string_lossy().into_owned()),
+            args: vec![],
+            url: None,
+            headers: HashMap::new(),
+            timeout_ms: 2000,
+            failure_policy,
+            priority: 0,
+        })
+    }
+
+    #[tokio::test]
+    async fn command_hook_rewrites_tool_args() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = script_hook(
+            tmp.path(),
+            r#"cat >/dev/null; echo '{"action":"continue","payload":{"args":{"command":"ls -la"}}}'"#,
+            &["before_tool_call"],
+            HookFailurePolicy::FailOpen,
+        );
+
+        match hook
+            .before_tool_call("shell".into(), json!({"command": "ls"}))
+            .await
+        {
+            HookResult::Continue((name, args)) => {
+                assert_eq!(name, "shell");
+                assert_eq!(args, json!({"command": "ls -la"}));
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn command_hook_receives_event_on_stdin_and_can_cancel() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = script_hook(
+            tmp.path(),
+            r#"if grep -q '"hook":"before_tool_call"'; then
+  echo '{"action":"cancel","reason":"shell is disabled"}'
+fi"#,
+            &["before_tool_call"],
+            HookFailurePolicy::FailOpen,
+        );
+
+        let result = hook.before_tool_call("shell".into(), json!({})).await;
+        assert!(matches!(result, HookResult::Cancel(ref r) if r == "shell is disabled"));
+    }
+
+    #[tokio::test]
+    async fn empty_reply_and_unsubscribed_points_continue_unchanged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = script_hook(
+            tmp.path(),
+            "exit 0",
+            &["before_prompt_build"],
+            HookFailurePolicy::FailClosed,
+        );
+
+        let prompt = hook.before_prompt_build("base prompt".into()).await;
+        assert!(matches!(prompt, HookResult::Continue(ref p) if p == "base prompt"));
+        let sending = hook
+            .on_message_sending("slack".into(), "C1".into(), "hi".into())
+            .await;
+        assert!(matches!(sending, HookResult::Continue((_, _, ref c)) if c == "hi"));
+    }
+
+    #[tokio::test]
+    async fn timeout_follows_failure_policy() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut open = script_hook(
+            tmp.path(),
+            "sleep 5",
+            &["before_prompt_build"],
+            HookFailurePolicy::FailOpen,
+        );
+        open.timeout = Some(Duration::from_millis(100));
+        let result = open.before_prompt_build("prompt".into()).await;
+        assert!(matches!(result, HookResult::Continue(ref p) if p == "prompt"));
+
+        let mut closed = open.clone();
+        closed.failure_policy = HookFailurePolicy::FailClosed;
+        match closed.before_prompt_build("prompt".into()).await {
+            HookResult::Cancel(reason) => {
+                assert!(reason.contains("hook test-hook failed"), "{reason}");
+                assert!(reason.contains("timed out"), "{reason}");
+            }
+            HookResult::Continue(_) => panic!("fail_closed hook should cancel on timeout"),
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_reply_fails_closed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let hook = script_hook(
+            tmp.path(),
+            "echo not-json",
+            &["on_message_received"],
+            HookFailurePolicy::FailClosed,
+        );
+        let message = ChannelMessage {
+            id: "1".into(),
+            sender: "alice".into(),
+            reply_target: "alice".into(),
+            content: "hello".into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+        };
+        let result = hook.on_message_received(message).await;
+        assert!(matches!(result, HookResult::Cancel(ref r) if r.contains("invalid hook reply")));
+    }
+}
@@ -1,10 +1,15 @@
 pub mod builtin;
+mod cli;
+pub mod external;
 mod runner;
 mod traits;
+pub mod wasm;
 
+pub use cli::handle_command;
+pub use external::ExternalHook;
 pub use runner::HookRunner;
 // HookHandler and HookResult are part of the crate's public hook API surface.
 // They may appear unused internally but are intentionally re-exported for
 // external integrations and future plugin authors.
 #[allow(unused_imports)]
-pub use traits::{HookHandler, HookResult};
+pub use traits::{HookHandler, HookResult, HOOK_POINTS};
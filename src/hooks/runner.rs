@@ -6,10 +6,14 @@ use std::panic::AssertUnwindSafe;
 use tracing::info;
 
 use crate::channels::traits::ChannelMessage;
+use crate::config::Config;
 use crate::providers::traits::{ChatMessage, ChatResponse};
 use crate::tools::traits::ToolResult;
 
+use super::builtin::CommandLoggerHook;
+use super::external::ExternalHook;
 use super::traits::{HookHandler, HookResult};
+use super::wasm;
 
 /// Dispatcher that manages registered hook handlers.
 ///
@@ -28,6 +32,36 @@ impl HookRunner {
         }
     }
 
+    /// Build a runner with the built-in, external and WASM hooks enabled in
+    /// `[hooks]`. Plugins that fail to load are skipped with a warning.
+    pub fn from_config(config: &Config) -> Self {
+        let hooks = &config.hooks;
+        let mut runner = Self::new();
+        if hooks.builtin.command_logger {
+            runner.register(Box::new(CommandLoggerHook::new()));
+        }
+        for hook in &hooks.external {
+            runner.register(Box::new(ExternalHook::new(hook.clone())));
+        }
+        if hooks.wasm.enabled {
+            match wasm::discover(config) {
+                Ok(plugins) => {
+                    for plugin in plugins {
+                        match plugin.hook {
+                            Ok(hook) => runner.register(Box::new(hook)),
+                            Err(e) => tracing::warn!(
+                                "Skipping WASM hook plugin {}: {e:#}",
+                                plugin.path.display()
+                            ),
+                        }
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load WASM hook plugins: {e:#}"),
+            }
+        }
+        runner
+    }
+
     /// Register a handler and re-sort by descending priority.
     pub fn register(&mut self, handler: Box<dyn HookHandler>) {
         self.handlers.push(handler);
@@ -19,6 +19,26 @@ impl<T> HookResult<T> {
     }
 }
 
+/// Names of the hook points, matching the [`HookHandler`] method names.
+/// Configured hooks (e.g. `[[hooks.external]]`) subscribe by these names.
+pub const HOOK_POINTS: &[&str] = &[
+    "on_gateway_start",
+    "on_gateway_stop",
+    "on_session_start",
+    "on_session_end",
+    "on_llm_input",
+    "on_llm_output",
+    "on_after_tool_call",
+    "on_message_sent",
+    "on_heartbeat_tick",
+    "before_model_resolve",
+    "before_prompt_build",
+    "before_llm_call",
+    "before_tool_call",
+    "on_message_received",
+    "on_message_sending",
+];
+
 /// Trait for hook handlers. All methods have default no-op implementations.
 /// Implement only the events you care about.
 #[async_trait]
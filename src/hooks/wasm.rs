@@ -0,0 +1,370 @@
+//! WASM hook plugins — sandboxed policy logic via `wasmi`.
+//!
+//! Each `<workspace>/<hooks.wasm.dir>/<name>.wasm` module is one plugin. It
+//! is instantiated fresh for every invocation with no host imports, a fuel
+//! budget and a linear-memory cap, so a plugin can only compute over the
+//! bytes it is given.
+//!
+//! # ABI (version 1)
+//!
+//! A plugin exports:
+//!
+//! - `memory` — its linear memory
+//! - `alloc(len: i32) -> i32` — returns a buffer of `len` bytes for the host
+//!   to write a request into
+//! - `hook_points() -> i64` — the hook points it handles, as a JSON array of
+//!   names (e.g. `["before_tool_call"]`)
+//! - `on_hook(ptr: i32, len: i32) -> i64` — handles one request
+//!
+//! Strings are UTF-8 JSON returned as `(ptr << 32) | len`; replies must lie
+//! within the plugin's memory and be at most 1 MiB. Requests and replies use
+//! the same JSON shapes as `[[hooks.external]]`: the request is
+//! `{"hook": "<point>", "payload": {...}}` and a zero-length or
+//! `{"action": "continue"}` reply continues unchanged.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::external::ExternalHook;
+use crate::config::Config;
+
+/// A `.wasm` file found in the plugin directory.
+pub struct WasmHookPlugin {
+    /// File stem, used as the hook name.
+    pub name: String,
+    pub path: PathBuf,
+    /// The loaded hook, or why it could not be loaded.
+    pub hook: Result<ExternalHook>,
+}
+
+/// Resolve the plugin directory for `config`.
+pub fn plugin_dir(config: &Config) -> PathBuf {
+    config.workspace_dir.join(&config.hooks.wasm.dir)
+}
+
+/// Load every plugin in the plugin directory, sorted by name. A missing
+/// directory yields no plugins.
+pub fn discover(config: &Config) -> Result<Vec<WasmHookPlugin>> {
+    let dir = plugin_dir(config);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut plugins = Vec::new();
+    for entry in std::fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "wasm") {
+            continue;
+        }
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        let hook = load(config, &name, &path);
+        plugins.push(WasmHookPlugin { name, path, hook });
+    }
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(plugins)
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.len() > 128
+        || !name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+    {
+        anyhow::bail!("plugin name '{name}' must be 1-128 characters of [A-Za-z0-9_-]");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "runtime-wasm")]
+fn load(config: &Config, name: &str, path: &Path) -> Result<ExternalHook> {
+    use anyhow::Context;
+
+    validate_name(name)?;
+    if std::fs::symlink_metadata(path)?.file_type().is_symlink() {
+        anyhow::bail!("plugin must not be a symlink");
+    }
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let plugin = runtime::WasmPlugin::new(&bytes, &config.hooks.wasm)?;
+    let events = plugin.hook_points()?;
+    for event in &events {
+        if !super::HOOK_POINTS.contains(&event.as_str()) {
+            anyhow::bail!("unknown hook point '{event}'");
+        }
+    }
+
+    Ok(ExternalHook::with_transport(
+        name.to_string(),
+        events,
+        config.hooks.wasm.failure_policy,
+        // Fuel bounds the run time.
+        None,
+        std::sync::Arc::new(plugin),
+    ))
+}
+
+#[cfg(not(feature = "runtime-wasm"))]
+fn load(_config: &Config, name: &str, _path: &Path) -> Result<ExternalHook> {
+    validate_name(name)?;
+    anyhow::bail!(
+        "WASM runtime is not available in this build. \
+         Rebuild with `cargo build --features runtime-wasm` to enable WASM hook plugins."
+    )
+}
+
+#[cfg(feature = "runtime-wasm")]
+mod runtime {
+    use anyhow::{Context, Result};
+    use async_trait::async_trait;
+    use wasmi::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+    use crate::config::WasmHooksConfig;
+    use crate::hooks::external::HookTransport;
+
+    /// Largest reply a plugin may return from an export.
+    const MAX_REPLY_BYTES: usize = 1024 * 1024;
+
+    /// A compiled plugin; each call runs in a fresh instance.
+    #[derive(Clone)]
+    pub(super) struct WasmPlugin {
+        engine: Engine,
+        module: Module,
+        fuel_limit: u64,
+        memory_limit_bytes: usize,
+    }
+
+    impl WasmPlugin {
+        pub(super) fn new(bytes: &[u8], config: &WasmHooksConfig) -> Result<Self> {
+            let mut engine_config = wasmi::Config::default();
+            engine_config.consume_fuel(true);
+            let engine = Engine::new(&engine_config);
+            let module = Module::new(&engine, bytes).context("invalid WASM module")?;
+            let memory_limit_bytes =
+                usize::try_from(config.memory_limit_mb.saturating_mul(1024 * 1024))
+                    .unwrap_or(usize::MAX);
+            Ok(Self {
+                engine,
+                module,
+                fuel_limit: config.fuel_limit,
+                memory_limit_bytes,
+            })
+        }
+
+        pub(super) fn hook_points(&self) -> Result<Vec<String>> {
+            let reply = self.call_export("hook_points", None)?;
+            serde_json::from_slice(&reply)
+                .context("hook_points() must return a JSON array of names")
+        }
+
+        /// Instantiate the module and call `export`, passing `input` through
+        /// `alloc` when given. Returns the bytes the export points at.
+        fn call_export(&self, export: &str, input: Option<&[u8]>) -> Result<Vec<u8>> {
+            let limits = StoreLimitsBuilder::new()
+                .memory_size(self.memory_limit_bytes)
+                .build();
+            let mut store = Store::new(&self.engine, limits);
+            store.limiter(|limits: &mut StoreLimits| limits);
+            store.set_fuel(self.fuel_limit)?;
+
+            let linker = Linker::<StoreLimits>::new(&self.engine);
+            let instance = linker
+                .instantiate_and_start(&mut store, &self.module)
+                .context("failed to instantiate plugin")?;
+            let memory = instance
+                .get_memory(&store, "memory")
+                .context("plugin must export `memory`")?;
+
+            let packed = match input {
+                Some(input) => {
+                    let alloc = instance
+                        .get_typed_func::<i32, i32>(&store, "alloc")
+                        .context("plugin must export `alloc(i32) -> i32`")?;
+                    let func = instance
+                        .get_typed_func::<(i32, i32), i64>(&store, export)
+                        .with_context(|| {
+                            format!("plugin must export `{export}(i32, i32) -> i64`")
+                        })?;
+                    let len = i32::try_from(input.len()).context("request too large")?;
+                    let ptr = alloc
+                        .call(&mut store, len)
+                        .map_err(|e| self.trap(&store, e))?;
+                    memory
+                        .write(&mut store, ptr.cast_unsigned() as usize, input)
+                        .context("alloc returned an out-of-bounds buffer")?;
+                    func.call(&mut store, (ptr, len))
+                }
+                None => instance
+                    .get_typed_func::<(), i64>(&store, export)
+                    .with_context(|| format!("plugin must export `{export}() -> i64`"))?
+                    .call(&mut store, ()),
+            }
+            .map_err(|e| self.trap(&store, e))?;
+
+            let packed = packed.cast_unsigned();
+            let ptr = (packed >> 32) as usize;
+            let len = (packed & 0xFFFF_FFFF) as usize;
+            if len > MAX_REPLY_BYTES {
+                anyhow::bail!(
+                    "{export} reply of {len} bytes exceeds the {MAX_REPLY_BYTES}-byte limit"
+                );
+            }
+            // Check against the memory before copying so a bogus pointer or
+            // length cannot make the host allocate.
+            let data = memory.data(&store);
+            let reply = ptr
+                .checked_add(len)
+                .filter(|end| *end <= memory.data_size(&store))
+                .map(|end| data[ptr..end].to_vec())
+                .with_context(|| format!("{export} returned an out-of-bounds reply"))?;
+            Ok(reply)
+        }
+
+        fn trap(&self, store: &Store<StoreLimits>, error: wasmi::Error) -> anyhow::Error {
+            if store.get_fuel().is_ok_and(|fuel| fuel == 0) {
+                anyhow::anyhow!("plugin exceeded fuel limit ({} units)", self.fuel_limit)
+            } else {
+                anyhow::anyhow!("plugin trapped: {error}")
+            }
+        }
+    }
+
+    #[async_trait]
+    impl HookTransport for WasmPlugin {
+        async fn call(&self, request: Vec<u8>) -> Result<String> {
+            let plugin = self.clone();
+            let reply =
+                tokio::task::spawn_blocking(move || plugin.call_export("on_hook", Some(&request)))
+                    .await??;
+            String::from_utf8(reply).context("plugin reply is not valid UTF-8")
+        }
+    }
+}
+
+#[cfg(all(test, feature = "runtime-wasm"))]
+mod tests {
+    use super::*;
+    use crate::hooks::{HookHandler, HookResult};
+    use serde_json::json;
+
+    /// WAT for a plugin that handles `before_tool_call` and always replies
+    /// with `reply`. `body` is spliced into `on_hook` before the return.
+    fn plugin_wat(reply: &str, body: &str) -> String {
+        let points = r#"["before_tool_call"]"#;
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        format!(
+            r#"(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "{points_data}")
+  (data (i32.const 256) "{reply_data}")
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "hook_points") (result i64) i64.const {points_len})
+  (func (export "on_hook") (param i32 i32) (result i64)
+    {body}
+    i64.const {reply_packed}))"#,
+            points_data = escape(points),
+            reply_data = escape(reply),
+            points_len = points.len(),
+            reply_packed = (256_i64 << 32) | reply.len() as i64,
+        )
+    }
+
+    fn write_plugin(workspace: &Path, name: &str, wat: &str) -> Config {
+        let mut config = Config {
+            workspace_dir: workspace.to_path_buf(),
+            ..Config::default()
+        };
+        config.hooks.wasm.enabled = true;
+        let dir = plugin_dir(&config);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{name}.wasm")), wat).unwrap();
+        config
+    }
+
+    fn only_hook(config: &Config) -> ExternalHook {
+        let mut plugins = discover(config).unwrap();
+        assert_eq!(plugins.len(), 1);
+        plugins.remove(0).hook.unwrap()
+    }
+
+    #[tokio::test]
+    async fn plugin_declares_hook_points_and_cancels() {
+        let tmp = tempfile::tempdir().unwrap();
+        let reply = r#"{"action":"cancel","reason":"blocked by policy"}"#;
+        let config = write_plugin(tmp.path(), "deny-shell", &plugin_wat(reply, ""));
+
+        let hook = only_hook(&config);
+        assert_eq!(hook.name(), "deny-shell");
+        assert_eq!(hook.events(), ["before_tool_call"]);
+        let result = hook.before_tool_call("shell".into(), json!({})).await;
+        assert!(matches!(result, HookResult::Cancel(ref r) if r == "blocked by policy"));
+    }
+
+    #[tokio::test]
+    async fn plugin_can_rewrite_payload() {
+        let tmp = tempfile::tempdir().unwrap();
+        let reply = r#"{"action":"continue","payload":{"args":{"command":"echo [redacted]"}}}"#;
+        let config = write_plugin(tmp.path(), "redact", &plugin_wat(reply, ""));
+
+        let hook = only_hook(&config);
+        match hook
+            .before_tool_call("shell".into(), json!({"command": "echo secret"}))
+            .await
+        {
+            HookResult::Continue((name, args)) => {
+                assert_eq!(name, "shell");
+                assert_eq!(args, json!({"command": "echo [redacted]"}));
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn runaway_plugin_is_stopped_by_fuel_and_fails_closed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let spin = "(loop $spin (br $spin))";
+        let mut config = write_plugin(tmp.path(), "spin", &plugin_wat("", spin));
+        config.hooks.wasm.fuel_limit = 100_000;
+
+        let hook = only_hook(&config);
+        match hook.before_tool_call("shell".into(), json!({})).await {
+            HookResult::Cancel(reason) => assert!(reason.contains("fuel limit"), "{reason}"),
+            HookResult::Continue(_) => panic!("fail_closed plugin should cancel"),
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_or_out_of_bounds_replies_are_rejected() {
+        // 4 GiB at offset 256, then 16 bytes straddling the end of the only page.
+        let oversized = (256_i64 << 32) | 0xFFFF_FFFF;
+        let past_end = (65_530_i64 << 32) | 16;
+        for (packed, expected) in [(oversized, "exceeds"), (past_end, "out-of-bounds")] {
+            let tmp = tempfile::tempdir().unwrap();
+            let body = format!("(return (i64.const {packed}))");
+            let config = write_plugin(tmp.path(), "liar", &plugin_wat("", &body));
+
+            let hook = only_hook(&config);
+            match hook.before_tool_call("shell".into(), json!({})).await {
+                HookResult::Cancel(reason) => assert!(reason.contains(expected), "{reason}"),
+                HookResult::Continue(_) => panic!("fail_closed plugin should cancel"),
+            }
+        }
+    }
+
+    #[test]
+    fn memory_limit_rejects_oversized_plugins() {
+        let tmp = tempfile::tempdir().unwrap();
+        let wat = plugin_wat("", "").replace(
+            "(memory (export \"memory\") 1)",
+            "(memory (export \"memory\") 512)",
+        );
+        let mut config = write_plugin(tmp.path(), "greedy", &wat);
+        config.hooks.wasm.memory_limit_mb = 16;
+
+        let plugins = discover(&config).unwrap();
+        let err = plugins[0].hook.as_ref().err().unwrap().to_string();
+        assert!(err.contains("instantiate"), "{err}");
+    }
+}
@@ -285,7 +285,32 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
         "GitHub" => {
             println!("  Setup:");
             println!("    1. Create a personal access token at https://github.com/settings/tokens");
-            println!("    2. Add to config: [integrations.github] token = \"ghp_...\"");
+            println!("    2. Add to config: [forge] enabled = true, token = \"ghp_...\"");
+            println!("    3. Allow repositories: allowed_repos = [\"owner/repo\", \"owner/*\"]");
+            println!("    GitLab: set provider = \"gitlab\" (and api_url when self-managed).");
+        }
+        "Microsoft Teams" => {
+            println!("  Setup:");
+            println!("    1. Create an Azure Bot and note its App ID and client secret");
+            println!("    2. Set the messaging endpoint to https://<your-host>/teams");
+            println!(
+                "    3. Add to config: [channels_config.teams] app_id, app_password, allowed_users"
+            );
+            println!("    Single-tenant bots also need tenant_id.");
+        }
+        "Image Gen" => {
+            println!("  Setup:");
+            println!("    1. Add to config: [image_gen] enabled = true, api_key = \"sk-...\"");
+            println!("    2. Optional: model = \"gpt-image-1\", size = \"1024x1024\"");
+            println!("    Local Stable Diffusion: backend = \"stable_diffusion\", api_url = \"http://127.0.0.1:7860\"");
+        }
+        "Obsidian" => {
+            println!("  Setup:");
+            println!("    1. Add to config: [vault] enabled = true, path = \"~/Notes\"");
+            println!(
+                "    2. Paths outside the workspace must be listed in [autonomy] allowed_roots."
+            );
+            println!("    Daily notes go to daily_folder; templates come from templates_folder.");
         }
         "Browser" => {
             println!("  Built-in:");
@@ -302,6 +327,13 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
             println!("    HTTP endpoint for external triggers.");
             println!("    Run: zeroclaw gateway");
         }
+        "Home Assistant" => {
+            println!("  Setup:");
+            println!("    1. Create a long-lived access token (Profile → Security)");
+            println!("    2. Add to config: [home_assistant] enabled = true, token = \"...\"");
+            println!("    3. Allow service calls: allowed_domains = [\"light\", \"switch\"]");
+            println!("    4. Optional: cron jobs with a 'state' schedule react to state changes");
+        }
         _ => {
             if status == IntegrationStatus::ComingSoon {
                 println!("  This integration is planned. Stay tuned!");
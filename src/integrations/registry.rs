@@ -1,4 +1,5 @@
 use super::{IntegrationCategory, IntegrationEntry, IntegrationStatus};
+use crate::config::ForgeProvider;
 use crate::providers::{
     is_glm_alias, is_minimax_alias, is_moonshot_alias, is_qianfan_alias, is_qwen_alias,
     is_zai_alias,
@@ -95,9 +96,15 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
         },
         IntegrationEntry {
             name: "Microsoft Teams",
-            description: "Enterprise chat support",
+            description: "Teams via Azure Bot Service",
             category: IntegrationCategory::Chat,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.channels_config.teams.is_some() {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Matrix",
@@ -501,7 +508,13 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "GitHub",
             description: "Code, issues, PRs",
             category: IntegrationCategory::Productivity,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.forge.enabled && c.forge.provider == ForgeProvider::Github {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Notion",
@@ -525,7 +538,13 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Obsidian",
             description: "Knowledge graph notes",
             category: IntegrationCategory::Productivity,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.vault.enabled {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Things 3",
@@ -575,7 +594,13 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Home Assistant",
             description: "Home automation hub",
             category: IntegrationCategory::SmartHome,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.home_assistant.enabled {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Philips Hue",
@@ -649,7 +674,13 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
             name: "Image Gen",
             description: "AI image generation",
             category: IntegrationCategory::MediaCreative,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.image_gen.enabled {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "GIF Search",
@@ -875,7 +906,7 @@ mod tests {
     fn coming_soon_integrations_stay_coming_soon() {
         let config = Config::default();
         let entries = all_integrations();
-        for name in ["Nostr", "Spotify", "Home Assistant"] {
+        for name in ["Nostr", "Spotify"] {
             let entry = entries.iter().find(|e| e.name == name).unwrap();
             assert!(
                 matches!((entry.status_fn)(&config), IntegrationStatus::ComingSoon),
@@ -884,6 +915,98 @@ mod tests {
         }
     }
 
+    #[test]
+    fn home_assistant_active_when_enabled() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let ha = entries.iter().find(|e| e.name == "Home Assistant").unwrap();
+        assert!(matches!(
+            (ha.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.home_assistant.enabled = true;
+        assert!(matches!((ha.status_fn)(&config), IntegrationStatus::Active));
+    }
+
+    #[test]
+    fn github_active_when_forge_uses_github() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let gh = entries.iter().find(|e| e.name == "GitHub").unwrap();
+        assert!(matches!(
+            (gh.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.forge.enabled = true;
+        assert!(matches!((gh.status_fn)(&config), IntegrationStatus::Active));
+        config.forge.provider = ForgeProvider::Gitlab;
+        assert!(matches!(
+            (gh.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+    }
+
+    #[test]
+    fn obsidian_active_when_vault_enabled() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let obsidian = entries.iter().find(|e| e.name == "Obsidian").unwrap();
+        assert!(matches!(
+            (obsidian.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.vault.enabled = true;
+        assert!(matches!(
+            (obsidian.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
+    #[test]
+    fn image_gen_active_when_enabled() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let image_gen = entries.iter().find(|e| e.name == "Image Gen").unwrap();
+        assert!(matches!(
+            (image_gen.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.image_gen.enabled = true;
+        assert!(matches!(
+            (image_gen.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
+    #[test]
+    fn teams_active_when_channel_configured() {
+        let mut config = Config::default();
+        let entries = all_integrations();
+        let teams = entries
+            .iter()
+            .find(|e| e.name == "Microsoft Teams")
+            .unwrap();
+        assert!(matches!(
+            (teams.status_fn)(&config),
+            IntegrationStatus::Available
+        ));
+        config.channels_config.teams = Some(crate::config::TeamsConfig {
+            app_id: "app-id".into(),
+            app_password: "secret".into(),
+            tenant_id: None,
+            allowed_users: vec!["*".into()],
+            openid_metadata_url:
+                "https://login.botframework.com/v1/.well-known/openidconfiguration".into(),
+            token_url: None,
+            service_url: "https://smba.trafficmanager.net/teams/".into(),
+            approval_timeout_secs: 300,
+        });
+        assert!(matches!(
+            (teams.status_fn)(&config),
+            IntegrationStatus::Active
+        ));
+    }
+
     #[test]
     fn whatsapp_available_when_not_configured() {
         let config = Config::default();
@@ -69,6 +69,8 @@ pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
+#[cfg(feature = "sop")]
+pub mod sop;
 pub mod tools;
 pub mod tunnel;
 pub mod util;
@@ -293,6 +295,74 @@ Examples:
     },
 }
 
+/// Gateway subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GatewayCommands {
+    /// Manage scoped gateway API tokens
+    Tokens {
+        #[command(subcommand)]
+        token_command: GatewayTokenCommands,
+    },
+}
+
+/// Gateway API token subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GatewayTokenCommands {
+    /// List API tokens with their scopes and status
+    List,
+    /// Create an API token and print it once
+    Create {
+        /// Label shown in listings and audit entries
+        #[arg(long)]
+        label: String,
+        /// Scope to grant: read, chat or admin (repeatable)
+        #[arg(long = "scope", required = true)]
+        scopes: Vec<String>,
+        /// Expire the token after this many days (default: never)
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// Revoke an API token by id
+    Revoke {
+        /// Token id (`tok_…`)
+        id: String,
+    },
+}
+
+/// Hook subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HooksCommands {
+    /// List configured hooks and WASM plugins with their hook points
+    List,
+    /// Send a test event to one external hook or WASM plugin and print its reply
+    Test {
+        /// Hook name (`[[hooks.external]]` name or WASM plugin file stem)
+        name: String,
+        /// Hook point to send, e.g. `before_tool_call`
+        event: String,
+        /// JSON payload for the event
+        #[arg(long, default_value = "{}")]
+        payload: String,
+    },
+}
+
+/// SOP (standard operating procedure) subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SopCommands {
+    /// List loaded SOPs with their triggers
+    List,
+    /// Validate SOP definitions and report warnings
+    Validate {
+        /// Only validate this SOP
+        name: Option<String>,
+    },
+    /// Show an SOP's triggers and steps
+    Show {
+        /// SOP name
+        name: String,
+    },
+}
+
 /// Memory management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MemoryCommands {
@@ -310,11 +380,17 @@ pub enum MemoryCommands {
         /// Number of entries to skip (for pagination)
         #[arg(long, default_value = "0")]
         offset: usize,
+        /// List a person namespace instead of the shared scope
+        #[arg(long)]
+        namespace: Option<String>,
     },
     /// Get a specific memory entry by key
     Get {
         /// Memory key to look up
         key: String,
+        /// Look in a person namespace instead of the shared scope
+        #[arg(long)]
+        namespace: Option<String>,
     },
     /// Show memory backend statistics and health
     Stats,
@@ -330,6 +406,38 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Export all memories to a versioned JSONL file
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Include stored embedding vectors
+        #[arg(long)]
+        include_embeddings: bool,
+    },
+    /// Import memories from a JSONL export into the configured backend
+    Import {
+        /// Export file to read
+        input: std::path::PathBuf,
+    },
+    /// Copy all memories from one backend to another
+    Migrate {
+        /// Source backend (sqlite, lucid, markdown, postgres, qdrant)
+        #[arg(long)]
+        from: String,
+        /// Target backend (sqlite, lucid, markdown, postgres, qdrant)
+        #[arg(long)]
+        to: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Extract facts, merge duplicates and summarize old daily logs
+    Consolidate {
+        /// Print the planned changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Integration subcommands
@@ -81,6 +81,8 @@ mod security;
 mod service;
 mod skillforge;
 mod skills;
+#[cfg(feature = "sop")]
+mod sop;
 mod tools;
 mod tunnel;
 mod util;
@@ -93,8 +95,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    ChannelCommands, CronCommands, GatewayCommands, GatewayTokenCommands, HardwareCommands,
+    HooksCommands, IntegrationCommands, MigrateCommands, PeripheralCommands, ServiceCommands,
+    SkillCommands, SopCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -240,8 +243,14 @@ Examples:
   zeroclaw gateway                  # use config defaults
   zeroclaw gateway -p 8080          # listen on port 8080
   zeroclaw gateway --host 0.0.0.0   # bind to all interfaces
-  zeroclaw gateway -p 0             # random available port")]
+  zeroclaw gateway -p 0             # random available port
+  zeroclaw gateway tokens list      # list scoped API tokens
+  zeroclaw gateway tokens create --label grafana --scope read --expires-in-days 90
+  zeroclaw gateway tokens revoke tok_0123456789ab")]
     Gateway {
+        #[command(subcommand)]
+        gateway_command: Option<GatewayCommands>,
+
         /// Port to listen on (use 0 for random available port); defaults to config gateway.port
         #[arg(short, long)]
         port: Option<u16>,
@@ -406,6 +415,40 @@ Examples:
         skill_command: SkillCommands,
     },
 
+    /// Inspect and test lifecycle hooks
+    #[command(long_about = "\
+Inspect and test lifecycle hooks.
+
+Lists built-in hooks, external command/webhook hooks from \
+[[hooks.external]] and WASM plugins from <workspace>/hooks/, and sends \
+a test event to a single hook.
+
+Examples:
+  zeroclaw hooks list
+  zeroclaw hooks test redact before_tool_call --payload '{\"tool\":\"shell\",\"args\":{}}'")]
+    Hooks {
+        #[command(subcommand)]
+        hooks_command: HooksCommands,
+    },
+
+    /// Inspect standard operating procedures (requires the `sop` feature)
+    #[cfg(feature = "sop")]
+    #[command(long_about = "\
+Inspect standard operating procedures.
+
+SOPs live in <workspace>/sops/<name>/ as SOP.toml (metadata and \
+triggers) plus SOP.md (numbered steps). The daemon loads them at \
+startup and starts runs from MQTT, webhook, cron and manual triggers.
+
+Examples:
+  zeroclaw sop list
+  zeroclaw sop validate
+  zeroclaw sop show restart-pump")]
+    Sop {
+        #[command(subcommand)]
+        sop_command: SopCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
@@ -454,20 +497,27 @@ Examples:
         peripheral_command: zeroclaw::PeripheralCommands,
     },
 
-    /// Manage agent memory (list, get, stats, clear)
+    /// Manage agent memory (list, get, stats, clear, export, import, migrate, consolidate)
     #[command(long_about = "\
 Manage agent memory entries.
 
 List, inspect, and clear memory entries stored by the agent. \
 Supports filtering by category and session, pagination, and \
-batch clearing with confirmation.
+batch clearing with confirmation. Memories can be exported to and \
+imported from versioned JSONL, or copied between backends. \
+Consolidation extracts durable facts from conversation entries \
+and summarizes old daily logs using the configured provider.
 
 Examples:
   zeroclaw memory stats
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
-  zeroclaw memory clear --category conversation --yes")]
+  zeroclaw memory clear --category conversation --yes
+  zeroclaw memory export --output memories.jsonl
+  zeroclaw memory import memories.jsonl
+  zeroclaw memory migrate --from sqlite --to postgres
+  zeroclaw memory consolidate --dry-run")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
@@ -688,9 +738,17 @@ enum MemoryCommands {
         limit: usize,
         #[arg(long, default_value = "0")]
         offset: usize,
+        /// List a person namespace instead of the shared scope
+        #[arg(long)]
+        namespace: Option<String>,
     },
     /// Get a specific memory entry by key
-    Get { key: String },
+    Get {
+        key: String,
+        /// Look in a person namespace instead of the shared scope
+        #[arg(long)]
+        namespace: Option<String>,
+    },
     /// Show memory backend statistics and health
     Stats,
     /// Clear memories by category, by key, or clear all
@@ -704,6 +762,32 @@ enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Export all memories to a versioned JSONL file
+    Export {
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Include stored embedding vectors
+        #[arg(long)]
+        include_embeddings: bool,
+    },
+    /// Import memories from a JSONL export into the configured backend
+    Import { input: std::path::PathBuf },
+    /// Copy all memories from one backend to another
+    Migrate {
+        #[arg(long)]
+        from: String,
+        #[arg(long)]
+        to: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Extract facts, merge duplicates and summarize old daily logs
+    Consolidate {
+        /// Print the planned changes without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[tokio::main]
@@ -893,7 +977,16 @@ async fn main() -> Result<()> {
             .map(|_| ())
         }
 
-        Commands::Gateway { port, host } => {
+        Commands::Gateway {
+            gateway_command: Some(GatewayCommands::Tokens { token_command }),
+            ..
+        } => gateway::tokens::handle_command(token_command, &config).await,
+
+        Commands::Gateway {
+            gateway_command: None,
+            port,
+            host,
+        } => {
             let port = port.unwrap_or(config.gateway.port);
             let host = host.unwrap_or_else(|| config.gateway.host.clone());
             if port == 0 {
@@ -1129,6 +1222,11 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
+        Commands::Hooks { hooks_command } => hooks::handle_command(hooks_command, &config).await,
+
+        #[cfg(feature = "sop")]
+        Commands::Sop { sop_command } => sop::handle_command(sop_command, &config),
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
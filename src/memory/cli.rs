@@ -1,13 +1,13 @@
 use super::traits::{Memory, MemoryCategory};
 use super::{
-    classify_memory_backend, create_memory_for_migration, effective_memory_backend_name,
+    classify_memory_backend, consolidation, create_memory_for_migration,
+    create_memory_with_storage_and_routes, effective_memory_backend_name, transfer,
     MemoryBackendKind,
 };
 use crate::config::Config;
-#[cfg(feature = "memory-postgres")]
-use anyhow::Context;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use console::style;
+use std::path::{Path, PathBuf};
 
 /// Handle `zeroclaw memory <subcommand>` CLI commands.
 pub async fn handle_command(command: crate::MemoryCommands, config: &Config) -> Result<()> {
@@ -17,12 +17,24 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
             session,
             limit,
             offset,
-        } => handle_list(config, category, session, limit, offset).await,
-        crate::MemoryCommands::Get { key } => handle_get(config, &key).await,
+            namespace,
+        } => handle_list(config, category, session, limit, offset, namespace).await,
+        crate::MemoryCommands::Get { key, namespace } => {
+            handle_get(config, &key, namespace.as_deref()).await
+        }
         crate::MemoryCommands::Stats => handle_stats(config).await,
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::Export {
+            output,
+            include_embeddings,
+        } => handle_export(config, output, include_embeddings).await,
+        crate::MemoryCommands::Import { input } => handle_import(config, &input).await,
+        crate::MemoryCommands::Migrate { from, to, yes } => {
+            handle_migrate(config, &from, &to, yes).await
+        }
+        crate::MemoryCommands::Consolidate { dry_run } => handle_consolidate(config, dry_run).await,
     }
 }
 
@@ -76,16 +88,53 @@ fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
     }
 }
 
+/// Create a fully configured backend (embeddings included) for export,
+/// import and migration, which may target a backend other than the
+/// configured one.
+fn create_transfer_memory(config: &Config, backend: &str) -> Result<Box<dyn Memory>> {
+    let backend = backend.trim().to_ascii_lowercase();
+    let kind = classify_memory_backend(&backend);
+    match kind {
+        MemoryBackendKind::None => bail!("Memory backend 'none' does not store entries."),
+        MemoryBackendKind::Unknown => bail!("Unknown memory backend '{backend}'."),
+        _ => {}
+    }
+
+    let mut memory_config = config.memory.clone();
+    memory_config.backend = backend;
+    // Only postgres reads [storage.provider.config]; for other backends its
+    // provider override would replace the requested backend.
+    let storage_provider =
+        matches!(kind, MemoryBackendKind::Postgres).then_some(&config.storage.provider.config);
+    create_memory_with_storage_and_routes(
+        &memory_config,
+        &config.embedding_routes,
+        storage_provider,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )
+}
+
+fn configured_backend(config: &Config) -> String {
+    effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    )
+}
+
 async fn handle_list(
     config: &Config,
     category: Option<String>,
     session: Option<String>,
     limit: usize,
     offset: usize,
+    namespace: Option<String>,
 ) -> Result<()> {
     let mem = create_cli_memory(config)?;
     let cat = category.as_deref().map(parse_category);
-    let entries = mem.list(cat.as_ref(), session.as_deref()).await?;
+    let entries = mem
+        .list_in(namespace.as_deref(), cat.as_ref(), session.as_deref())
+        .await?;
 
     if entries.is_empty() {
         println!("No memory entries found.");
@@ -122,17 +171,17 @@ async fn handle_list(
     Ok(())
 }
 
-async fn handle_get(config: &Config, key: &str) -> Result<()> {
+async fn handle_get(config: &Config, key: &str, namespace: Option<&str>) -> Result<()> {
     let mem = create_cli_memory(config)?;
 
     // Try exact match first.
-    if let Some(entry) = mem.get(key).await? {
+    if let Some(entry) = mem.get_in(namespace, key).await? {
         print_entry(&entry);
         return Ok(());
     }
 
     // Fall back to prefix match so users can copy partial keys from `list`.
-    let all = mem.list(None, None).await?;
+    let all = mem.list_in(namespace, None, None).await?;
     let matches: Vec<_> = all.iter().filter(|e| e.key.starts_with(key)).collect();
 
     match matches.len() {
@@ -157,6 +206,9 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
 fn print_entry(entry: &super::traits::MemoryEntry) {
     println!("Key:       {}", style(&entry.key).white().bold());
     println!("Category:  {}", entry.category);
+    if let Some(namespace) = &entry.namespace {
+        println!("Namespace: {namespace}");
+    }
     println!("Timestamp: {}", entry.timestamp);
     if let Some(sid) = &entry.session_id {
         println!("Session:   {sid}");
@@ -196,6 +248,11 @@ async fn handle_stats(config: &Config) -> Result<()> {
         }
     }
 
+    let namespaces = mem.namespaces().await.unwrap_or_default();
+    if !namespaces.is_empty() {
+        println!("\n  Person namespaces: {}", namespaces.join(", "));
+    }
+
     Ok(())
 }
 
@@ -251,6 +308,146 @@ async fn handle_clear(
     Ok(())
 }
 
+async fn handle_export(
+    config: &Config,
+    output: Option<PathBuf>,
+    include_embeddings: bool,
+) -> Result<()> {
+    let mem = create_transfer_memory(config, &configured_backend(config))?;
+
+    let Some(path) = output else {
+        let mut stdout = std::io::stdout().lock();
+        transfer::export(&*mem, &mut stdout, include_embeddings).await?;
+        return Ok(());
+    };
+
+    let file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = std::io::BufWriter::new(file);
+    let exported = transfer::export(&*mem, &mut writer, include_embeddings).await?;
+
+    println!(
+        "{} Exported {exported} entries from {} to {}",
+        style("✓").green().bold(),
+        mem.name(),
+        path.display(),
+    );
+    Ok(())
+}
+
+async fn handle_import(config: &Config, input: &Path) -> Result<()> {
+    let mem = create_transfer_memory(config, &configured_backend(config))?;
+    let file = std::fs::File::open(input)
+        .with_context(|| format!("Failed to open {}", input.display()))?;
+    let imported = transfer::import(&*mem, std::io::BufReader::new(file)).await?;
+
+    println!(
+        "{} Imported {imported} entries into {}",
+        style("✓").green().bold(),
+        mem.name(),
+    );
+    Ok(())
+}
+
+async fn handle_migrate(config: &Config, from: &str, to: &str, yes: bool) -> Result<()> {
+    if from.trim().eq_ignore_ascii_case(to.trim()) {
+        bail!("Source and target backend are both '{from}'.");
+    }
+
+    let source = create_transfer_memory(config, from)?;
+    let target = create_transfer_memory(config, to)?;
+    let total = source.count().await?;
+
+    if total == 0 {
+        println!("No entries to migrate in {}.", source.name());
+        return Ok(());
+    }
+
+    if !yes {
+        let confirmed = dialoguer::Confirm::new()
+            .with_prompt(format!(
+                "  Copy {total} entries from {} to {}? Existing keys in {} are replaced.",
+                source.name(),
+                target.name(),
+                target.name(),
+            ))
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let report = transfer::migrate(&*source, &*target, |copied, total| {
+        eprint!("\r  Copied {copied}/{total}");
+    })
+    .await?;
+    eprintln!();
+
+    println!("Migration {} → {}:\n", source.name(), target.name());
+    println!("  Source entries:  {}", report.source_count);
+    println!("  Copied:          {}", report.copied);
+    println!("  Target entries:  {}", report.target_count);
+
+    if !report.verified() {
+        for key in report.missing.iter().take(10) {
+            println!("  Missing: {key}");
+        }
+        bail!(
+            "Verification failed: {} of {} copied entries could not be read back from {}.",
+            report.missing.len(),
+            report.copied,
+            target.name()
+        );
+    }
+
+    println!(
+        "\n{} All {} entries verified in {}.",
+        style("✓").green().bold(),
+        report.copied,
+        target.name(),
+    );
+    Ok(())
+}
+
+async fn handle_consolidate(config: &Config, dry_run: bool) -> Result<()> {
+    let outcome = consolidation::run(config, dry_run).await;
+    if !dry_run {
+        consolidation::record_run(&config.workspace_dir, &outcome)?;
+    }
+    let (plans, report) = outcome?;
+
+    let mut first = true;
+    for plan in plans
+        .iter()
+        .filter(|plan| plan.namespace.is_none() || !plan.is_empty())
+    {
+        if !first {
+            println!();
+        }
+        print!("{}", plan.render());
+        first = false;
+    }
+    if plans.iter().all(consolidation::ConsolidationPlan::is_empty) {
+        println!("\nNothing to consolidate.");
+        return Ok(());
+    }
+
+    match report {
+        None => println!("\nDry run: no changes written."),
+        Some(report) => println!(
+            "\n{} Wrote {} facts, merged {} duplicates, summarized {} days, marked {} sources.",
+            style("✓").green().bold(),
+            report.facts_written,
+            report.duplicates_merged,
+            report.days_summarized,
+            report.sources_marked,
+        ),
+    }
+    Ok(())
+}
+
 /// Delete a single entry by exact key or prefix match.
 async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()> {
     // Resolve the target key (exact match or unique prefix).
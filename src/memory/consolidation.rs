@@ -0,0 +1,1064 @@
+//! Scheduled memory consolidation.
+//!
+//! Conversation autosaves pile up as raw turns. Consolidation asks the
+//! configured provider to turn them into durable `core` facts, to merge
+//! duplicate or contradicting facts, and to summarize old daily entries into
+//! one entry per day. Written entries record the keys they were derived from
+//! and reviewed conversation entries record what they fed into.
+//!
+//! [`plan`] only reads the store and [`apply`] performs the writes, so
+//! `zeroclaw memory consolidate --dry-run` can show the plan without changing
+//! anything. Each person namespace is consolidated on its own, so facts
+//! never move between people or into the shared scope.
+
+use super::metadata::{MemoryMetadata, MemorySource};
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::{classify_memory_backend, effective_memory_backend_name, MemoryBackendKind};
+use crate::config::{Config, MemoryConsolidationConfig};
+use crate::providers::structured::chat_json;
+use crate::providers::traits::{ChatMessage, Provider, ResponseFormat};
+use crate::providers::{self, ProviderRuntimeOptions};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+
+const STATE_FILE: &str = "memory_consolidation_state.json";
+/// Tool name recorded as the source of consolidated writes.
+const SOURCE_TOOL: &str = "memory_consolidation";
+/// Importance given to reviewed conversation entries so extracted facts
+/// outrank them in recall.
+const REVIEWED_IMPORTANCE: f64 = 0.2;
+/// Existing core facts shown to the model for updates and merges.
+const MAX_CORE_CONTEXT: usize = 200;
+/// Days summarized per run; older backlogs catch up over later runs.
+const MAX_SUMMARY_DAYS: usize = 7;
+/// Characters of each entry included in prompts.
+const MAX_ENTRY_CHARS: usize = 1_000;
+const SUMMARY_KEY_PREFIX: &str = "daily_summary_";
+const TEMPERATURE: f64 = 0.2;
+const DEFAULT_MODEL: &str = "anthropic/claude-sonnet-4";
+
+/// Changes proposed by one consolidation pass over one namespace.
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidationPlan {
+    /// Person namespace the plan covers; `None` is the shared scope.
+    pub namespace: Option<String>,
+    /// Conversation entries reviewed for facts.
+    pub reviewed: Vec<String>,
+    pub facts: Vec<PlannedFact>,
+    pub merges: Vec<PlannedMerge>,
+    pub summaries: Vec<PlannedSummary>,
+}
+
+/// A core fact to create or update.
+#[derive(Debug, Clone)]
+pub struct PlannedFact {
+    pub key: String,
+    pub content: String,
+    pub importance: f64,
+    /// Reviewed conversation keys the fact came from.
+    pub sources: Vec<String>,
+    /// Current content when the fact updates an existing core entry.
+    pub replaces: Option<String>,
+}
+
+/// Duplicate core facts folded into one entry.
+#[derive(Debug, Clone)]
+pub struct PlannedMerge {
+    pub key: String,
+    pub content: String,
+    /// Core keys removed after their content is folded into `key`.
+    pub duplicates: Vec<String>,
+}
+
+/// One day of daily entries replaced by a summary entry.
+#[derive(Debug, Clone)]
+pub struct PlannedSummary {
+    pub key: String,
+    pub date: NaiveDate,
+    pub content: String,
+    /// Daily keys summarized (and removed) by this entry.
+    pub sources: Vec<String>,
+}
+
+/// What [`apply`] wrote.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ConsolidationReport {
+    pub facts_written: usize,
+    pub duplicates_merged: usize,
+    pub days_summarized: usize,
+    pub sources_marked: usize,
+}
+
+impl ConsolidationReport {
+    fn absorb(&mut self, other: Self) {
+        self.facts_written += other.facts_written;
+        self.duplicates_merged += other.duplicates_merged;
+        self.days_summarized += other.days_summarized;
+        self.sources_marked += other.sources_marked;
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ConsolidationState {
+    last_run_at: Option<String>,
+    #[serde(default)]
+    last_report: Option<ConsolidationReport>,
+    #[serde(default)]
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Extraction {
+    #[serde(default)]
+    facts: Vec<ExtractedFact>,
+    #[serde(default)]
+    merges: Vec<ExtractedMerge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractedFact {
+    key: String,
+    content: String,
+    #[serde(default)]
+    importance: Option<f64>,
+    #[serde(default)]
+    sources: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractedMerge {
+    key: String,
+    content: String,
+    #[serde(default)]
+    duplicates: Vec<String>,
+}
+
+impl ConsolidationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.reviewed.is_empty()
+            && self.facts.is_empty()
+            && self.merges.is_empty()
+            && self.summaries.is_empty()
+    }
+
+    /// Human-readable listing for the CLI.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some(namespace) = &self.namespace {
+            let _ = writeln!(out, "Namespace {namespace}:");
+        }
+        let _ = writeln!(
+            out,
+            "Reviewed {} conversation entries.",
+            self.reviewed.len()
+        );
+
+        if !self.facts.is_empty() {
+            let _ = writeln!(out, "\nFacts ({}):", self.facts.len());
+            for fact in &self.facts {
+                let marker = if fact.replaces.is_some() { "~" } else { "+" };
+                let _ = writeln!(
+                    out,
+                    "  {marker} {}: {} (importance {:.1}, from {})",
+                    fact.key,
+                    fact.content,
+                    fact.importance,
+                    render_keys(&fact.sources)
+                );
+                if let Some(previous) = &fact.replaces {
+                    let _ = writeln!(out, "      was: {previous}");
+                }
+            }
+        }
+
+        if !self.merges.is_empty() {
+            let _ = writeln!(out, "\nMerges ({}):", self.merges.len());
+            for merge in &self.merges {
+                let _ = writeln!(
+                    out,
+                    "  = {}: {} (absorbs {})",
+                    merge.key,
+                    merge.content,
+                    merge.duplicates.join(", ")
+                );
+            }
+        }
+
+        if !self.summaries.is_empty() {
+            let _ = writeln!(out, "\nDaily summaries ({}):", self.summaries.len());
+            for summary in &self.summaries {
+                let _ = writeln!(
+                    out,
+                    "  {}: {} entries -> {}",
+                    summary.date,
+                    summary.sources.len(),
+                    summary.key
+                );
+            }
+        }
+
+        out
+    }
+}
+
+fn render_keys(keys: &[String]) -> String {
+    if keys.is_empty() {
+        "existing facts".to_string()
+    } else {
+        keys.join(", ")
+    }
+}
+
+/// Ask `provider` what to consolidate in `namespace`. Reads `memory` but
+/// never writes it.
+pub async fn plan(
+    memory: &dyn Memory,
+    namespace: Option<&str>,
+    provider: &dyn Provider,
+    model: &str,
+    config: &MemoryConsolidationConfig,
+) -> Result<ConsolidationPlan> {
+    let entries = memory.list_in(namespace, None, None).await?;
+    let mut plan = ConsolidationPlan {
+        namespace: namespace.map(String::from),
+        ..ConsolidationPlan::default()
+    };
+
+    let mut pending: Vec<&MemoryEntry> = entries
+        .iter()
+        .filter(|entry| {
+            entry.category == MemoryCategory::Conversation
+                && entry.metadata.consolidated_at.is_none()
+        })
+        .collect();
+    pending.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+    pending.truncate(config.batch_size.max(1));
+
+    if !pending.is_empty() {
+        let core: Vec<&MemoryEntry> = entries
+            .iter()
+            .filter(|entry| entry.category == MemoryCategory::Core)
+            .take(MAX_CORE_CONTEXT)
+            .collect();
+        let reply = chat_json(
+            provider,
+            &extraction_messages(&pending, &core),
+            &extraction_format(),
+            model,
+            TEMPERATURE,
+        )
+        .await
+        .context("fact extraction failed")?;
+        let extraction: Extraction =
+            serde_json::from_value(reply).context("fact extraction reply has the wrong shape")?;
+
+        plan.reviewed = pending.iter().map(|entry| entry.key.clone()).collect();
+        let (facts, merges) = validate_extraction(extraction, &entries, &plan.reviewed);
+        plan.facts = facts;
+        plan.merges = merges;
+    }
+
+    if config.summarize_daily_after_days > 0 {
+        let cutoff = Local::now().date_naive()
+            - Duration::days(i64::from(config.summarize_daily_after_days));
+        for (date, day) in old_daily_entries(&entries, cutoff)
+            .into_iter()
+            .take(MAX_SUMMARY_DAYS)
+        {
+            let key = format!("{SUMMARY_KEY_PREFIX}{date}");
+            let existing = entries.iter().find(|entry| entry.key == key);
+            let reply = chat_json(
+                provider,
+                &summary_messages(date, &day, existing),
+                &summary_format(),
+                model,
+                TEMPERATURE,
+            )
+            .await
+            .with_context(|| format!("daily summary for {date} failed"))?;
+            let Some(content) = reply
+                .get("summary")
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|summary| !summary.is_empty())
+            else {
+                continue;
+            };
+            plan.summaries.push(PlannedSummary {
+                key,
+                date,
+                content: content.to_string(),
+                sources: day.iter().map(|entry| entry.key.clone()).collect(),
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Drop facts and merges that point at unknown, non-core or reviewed-out keys.
+fn validate_extraction(
+    extraction: Extraction,
+    entries: &[MemoryEntry],
+    reviewed: &[String],
+) -> (Vec<PlannedFact>, Vec<PlannedMerge>) {
+    let by_key: HashMap<&str, &MemoryEntry> = entries
+        .iter()
+        .map(|entry| (entry.key.as_str(), entry))
+        .collect();
+    let reviewed: HashSet<&str> = reviewed.iter().map(String::as_str).collect();
+
+    let mut facts: Vec<PlannedFact> = Vec::new();
+    for fact in extraction.facts {
+        let key = normalize_key(&fact.key);
+        let content = fact.content.trim();
+        if key.is_empty() || content.is_empty() || facts.iter().any(|f| f.key == key) {
+            continue;
+        }
+        let replaces = match by_key.get(key.as_str()) {
+            // Never turn a conversation or daily entry into a fact in place.
+            Some(existing) if existing.category != MemoryCategory::Core => continue,
+            Some(existing) if existing.content.trim() == content => continue,
+            Some(existing) => Some(existing.content.clone()),
+            None => None,
+        };
+        let mut sources: Vec<String> = Vec::new();
+        for source in fact.sources {
+            if reviewed.contains(source.as_str()) && !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        facts.push(PlannedFact {
+            key,
+            content: content.to_string(),
+            importance: fact.importance.unwrap_or(0.7).clamp(0.0, 1.0),
+            sources,
+            replaces,
+        });
+    }
+
+    let is_core = |key: &str| {
+        by_key
+            .get(key)
+            .is_some_and(|entry| entry.category == MemoryCategory::Core)
+    };
+    let mut absorbed: HashSet<String> = HashSet::new();
+    let mut merges = Vec::new();
+    for merge in extraction.merges {
+        let key = merge.key.trim().to_string();
+        let content = merge.content.trim();
+        if content.is_empty() || absorbed.contains(&key) {
+            continue;
+        }
+        if !is_core(&key) && !facts.iter().any(|fact| fact.key == key) {
+            continue;
+        }
+        // Facts written in this pass stay; only older duplicates are removed.
+        let duplicates: Vec<String> = merge
+            .duplicates
+            .into_iter()
+            .map(|duplicate| duplicate.trim().to_string())
+            .filter(|duplicate| {
+                *duplicate != key
+                    && is_core(duplicate)
+                    && !facts.iter().any(|fact| fact.key == *duplicate)
+                    && !merges.iter().any(|m: &PlannedMerge| m.key == *duplicate)
+            })
+            .collect();
+        if duplicates.is_empty() {
+            continue;
+        }
+        absorbed.extend(duplicates.iter().cloned());
+        merges.push(PlannedMerge {
+            key,
+            content: content.to_string(),
+            duplicates,
+        });
+    }
+
+    (facts, merges)
+}
+
+/// Lowercase snake_case key, as the rest of the memory tooling writes them.
+fn normalize_key(raw: &str) -> String {
+    let mut key = String::new();
+    for ch in raw.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            key.push(ch.to_ascii_lowercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    key.trim_end_matches('_').to_string()
+}
+
+/// Daily entries from before `cutoff`, grouped by local date, oldest first.
+fn old_daily_entries(
+    entries: &[MemoryEntry],
+    cutoff: NaiveDate,
+) -> BTreeMap<NaiveDate, Vec<&MemoryEntry>> {
+    let mut days: BTreeMap<NaiveDate, Vec<&MemoryEntry>> = BTreeMap::new();
+    for entry in entries {
+        if entry.category != MemoryCategory::Daily || entry.key.starts_with(SUMMARY_KEY_PREFIX) {
+            continue;
+        }
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+            continue;
+        };
+        let date = timestamp.with_timezone(&Local).date_naive();
+        if date < cutoff {
+            days.entry(date).or_default().push(entry);
+        }
+    }
+    days
+}
+
+fn prompt_line(entry: &MemoryEntry) -> String {
+    let content: String = entry.content.chars().take(MAX_ENTRY_CHARS).collect();
+    format!("- [{}] {}", entry.key, content.replace('\n', " "))
+}
+
+fn extraction_messages(pending: &[&MemoryEntry], core: &[&MemoryEntry]) -> Vec<ChatMessage> {
+    let system = "You are a memory consolidation engine. Extract durable facts from new \
+        conversation entries: preferences, decisions, commitments, people, projects and \
+        long-lived context. Skip chit-chat, one-off requests and facts already recorded. \
+        When an entry contradicts an existing core fact, update that fact under its existing \
+        key with the newer information. Report existing core facts that duplicate each other \
+        as merges, keeping the most specific key.";
+
+    let mut user = String::from("Existing core facts:\n");
+    if core.is_empty() {
+        user.push_str("(none)\n");
+    }
+    for entry in core {
+        let _ = writeln!(user, "{}", prompt_line(entry));
+    }
+    user.push_str("\nNew conversation entries:\n");
+    for entry in pending {
+        let _ = writeln!(user, "{}", prompt_line(entry));
+    }
+    user.push_str(
+        "\nReturn facts with snake_case keys, importance from 0.0 to 1.0 and the \
+         conversation keys each fact came from. Return empty lists when nothing qualifies.",
+    );
+
+    vec![ChatMessage::system(system), ChatMessage::user(user)]
+}
+
+fn extraction_format() -> ResponseFormat {
+    ResponseFormat::json_schema(
+        "memory_consolidation",
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "facts": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "key": {
+                                "type": "string",
+                                "description": "snake_case key; reuse an existing core key to update it"
+                            },
+                            "content": { "type": "string" },
+                            "importance": { "type": "number" },
+                            "sources": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Conversation keys the fact came from"
+                            }
+                        },
+                        "required": ["key", "content", "importance", "sources"],
+                        "additionalProperties": false
+                    }
+                },
+                "merges": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "key": { "type": "string", "description": "Core key to keep" },
+                            "content": { "type": "string", "description": "Merged fact" },
+                            "duplicates": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Other core keys folded into key and removed"
+                            }
+                        },
+                        "required": ["key", "content", "duplicates"],
+                        "additionalProperties": false
+                    }
+                }
+            },
+            "required": ["facts", "merges"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+fn summary_messages(
+    date: NaiveDate,
+    day: &[&MemoryEntry],
+    existing: Option<&MemoryEntry>,
+) -> Vec<ChatMessage> {
+    let system = "You summarize one day of log entries for long-term memory. Keep decisions, \
+        outcomes, open tasks and facts worth remembering. Omit filler. Answer in a few short \
+        bullet points.";
+
+    let mut user = format!("Log entries from {date}:\n");
+    for entry in day {
+        let _ = writeln!(user, "{}", prompt_line(entry));
+    }
+    if let Some(existing) = existing {
+        let _ = write!(
+            user,
+            "\nExtend this earlier summary of the same day:\n{}\n",
+            existing.content
+        );
+    }
+
+    vec![ChatMessage::system(system), ChatMessage::user(user)]
+}
+
+fn summary_format() -> ResponseFormat {
+    ResponseFormat::json_schema(
+        "daily_summary",
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string" }
+            },
+            "required": ["summary"],
+            "additionalProperties": false
+        }),
+    )
+}
+
+fn consolidation_source() -> MemorySource {
+    MemorySource {
+        tool: Some(SOURCE_TOOL.into()),
+        ..MemorySource::default()
+    }
+}
+
+fn extend_unique(target: &mut Vec<String>, keys: impl IntoIterator<Item = String>) {
+    for key in keys {
+        if !target.contains(&key) {
+            target.push(key);
+        }
+    }
+}
+
+async fn existing_metadata(
+    memory: &dyn Memory,
+    namespace: Option<&str>,
+    key: &str,
+) -> Result<MemoryMetadata> {
+    Ok(memory
+        .get_in(namespace, key)
+        .await?
+        .map(|entry| entry.metadata)
+        .unwrap_or_default())
+}
+
+/// Write `plan` to `memory`, inside the plan's namespace.
+pub async fn apply(memory: &dyn Memory, plan: &ConsolidationPlan) -> Result<ConsolidationReport> {
+    let namespace = plan.namespace.as_deref();
+    let mut report = ConsolidationReport::default();
+
+    for fact in &plan.facts {
+        let mut metadata = existing_metadata(memory, namespace, &fact.key).await?;
+        metadata.importance = Some(fact.importance);
+        metadata.source = Some(consolidation_source());
+        extend_unique(&mut metadata.derived_from, fact.sources.iter().cloned());
+        memory
+            .store_in(
+                namespace,
+                &fact.key,
+                &fact.content,
+                MemoryCategory::Core,
+                None,
+                &metadata,
+            )
+            .await?;
+        report.facts_written += 1;
+    }
+
+    for merge in &plan.merges {
+        let mut metadata = existing_metadata(memory, namespace, &merge.key).await?;
+        for duplicate in &merge.duplicates {
+            let Some(entry) = memory.get_in(namespace, duplicate).await? else {
+                continue;
+            };
+            extend_unique(&mut metadata.derived_from, [duplicate.clone()]);
+            extend_unique(&mut metadata.derived_from, entry.metadata.derived_from);
+            metadata.importance = match (metadata.importance, entry.metadata.importance) {
+                (Some(kept), Some(absorbed)) => Some(kept.max(absorbed)),
+                (kept, absorbed) => kept.or(absorbed),
+            };
+        }
+        metadata.source = Some(consolidation_source());
+        memory
+            .store_in(
+                namespace,
+                &merge.key,
+                &merge.content,
+                MemoryCategory::Core,
+                None,
+                &metadata,
+            )
+            .await?;
+        for duplicate in &merge.duplicates {
+            if memory.forget_in(namespace, duplicate).await? {
+                report.duplicates_merged += 1;
+            }
+        }
+    }
+
+    for summary in &plan.summaries {
+        let mut metadata = existing_metadata(memory, namespace, &summary.key).await?;
+        metadata.source = Some(consolidation_source());
+        extend_unique(&mut metadata.derived_from, summary.sources.iter().cloned());
+        memory
+            .store_in(
+                namespace,
+                &summary.key,
+                &summary.content,
+                MemoryCategory::Daily,
+                None,
+                &metadata,
+            )
+            .await?;
+        for source in &summary.sources {
+            memory.forget_in(namespace, source).await?;
+        }
+        report.days_summarized += 1;
+    }
+
+    let reviewed_at = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    for key in &plan.reviewed {
+        let Some(entry) = memory.get_in(namespace, key).await? else {
+            continue;
+        };
+        let mut metadata = entry.metadata.clone();
+        metadata.consolidated_at = Some(reviewed_at.clone());
+        metadata.importance = metadata.importance.or(Some(REVIEWED_IMPORTANCE));
+        extend_unique(
+            &mut metadata.consolidated_into,
+            plan.facts
+                .iter()
+                .filter(|fact| fact.sources.contains(key))
+                .map(|fact| fact.key.clone()),
+        );
+        memory
+            .store_in(
+                namespace,
+                &entry.key,
+                &entry.content,
+                entry.category.clone(),
+                entry.session_id.as_deref(),
+                &metadata,
+            )
+            .await?;
+        report.sources_marked += 1;
+    }
+
+    Ok(report)
+}
+
+/// Plan a pass over every namespace of the configured backend with the
+/// configured provider and apply it unless `dry_run` is set. The shared
+/// scope's plan comes first.
+pub async fn run(
+    config: &Config,
+    dry_run: bool,
+) -> Result<(Vec<ConsolidationPlan>, Option<ConsolidationReport>)> {
+    let backend = effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    );
+    match classify_memory_backend(&backend) {
+        MemoryBackendKind::None => bail!("Memory backend 'none' has nothing to consolidate."),
+        // Unknown backends fall back to markdown.
+        MemoryBackendKind::Markdown | MemoryBackendKind::Unknown => {
+            bail!("Memory backend '{backend}' is append-only markdown and cannot be consolidated.")
+        }
+        _ => {}
+    }
+
+    let memory = super::create_memory_with_storage_and_routes(
+        &config.memory,
+        &config.embedding_routes,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+
+    let consolidation = &config.memory.consolidation;
+    let model = consolidation
+        .model
+        .as_deref()
+        .or(config.default_model.as_deref())
+        .unwrap_or(DEFAULT_MODEL);
+    let provider = providers::create_routed_provider_with_options(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &config.model_routes,
+        model,
+        &ProviderRuntimeOptions {
+            auth_profile_override: None,
+            provider_api_url: config.api_url.clone(),
+            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            reasoning_enabled: config.runtime.reasoning_enabled,
+            reasoning_level: config.effective_provider_reasoning_level(),
+            custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
+            max_tokens_override: None,
+            model_support_vision: config.model_support_vision,
+        },
+    )?;
+
+    let mut scopes = vec![None];
+    scopes.extend(memory.namespaces().await?.into_iter().map(Some));
+
+    let mut plans = Vec::with_capacity(scopes.len());
+    for namespace in &scopes {
+        plans.push(
+            plan(
+                &*memory,
+                namespace.as_deref(),
+                &*provider,
+                model,
+                consolidation,
+            )
+            .await?,
+        );
+    }
+    if dry_run {
+        return Ok((plans, None));
+    }
+
+    let mut report = ConsolidationReport::default();
+    for plan in &plans {
+        report.absorb(apply(&*memory, plan).await?);
+    }
+    Ok((plans, Some(report)))
+}
+
+/// Whether the scheduler should start a pass now.
+pub fn is_due(config: &Config) -> bool {
+    let consolidation = &config.memory.consolidation;
+    if !consolidation.enabled {
+        return false;
+    }
+    let Some(last_run) = read_state(&config.workspace_dir)
+        .last_run_at
+        .and_then(|raw| DateTime::parse_from_rfc3339(&raw).ok())
+    else {
+        return true;
+    };
+    let interval = Duration::hours(i64::from(consolidation.interval_hours.max(1)));
+    Utc::now().signed_duration_since(last_run.with_timezone(&Utc)) >= interval
+}
+
+/// Scheduled entry point: run a pass if one is due and record the outcome.
+///
+/// Failures are recorded too, so a broken provider is retried on the next
+/// interval rather than on every scheduler tick.
+pub async fn run_if_due(config: &Config) -> Result<()> {
+    if !is_due(config) {
+        return Ok(());
+    }
+
+    let outcome = run(config, false).await;
+    record_run(&config.workspace_dir, &outcome)?;
+    let (_, report) = outcome?;
+
+    if let Some(report) = report {
+        tracing::info!(
+            "memory consolidation complete: facts={} merged={} days_summarized={} sources_marked={}",
+            report.facts_written,
+            report.duplicates_merged,
+            report.days_summarized,
+            report.sources_marked,
+        );
+    }
+    Ok(())
+}
+
+/// Persist the time and result of a pass for [`is_due`].
+pub fn record_run(
+    workspace_dir: &Path,
+    outcome: &Result<(Vec<ConsolidationPlan>, Option<ConsolidationReport>)>,
+) -> Result<()> {
+    let state = ConsolidationState {
+        last_run_at: Some(Utc::now().to_rfc3339()),
+        last_report: outcome.as_ref().ok().and_then(|(_, report)| *report),
+        last_error: outcome.as_ref().err().map(ToString::to_string),
+    };
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(&state)?)?;
+    Ok(())
+}
+
+fn read_state(workspace_dir: &Path) -> ConsolidationState {
+    std::fs::read(state_path(workspace_dir))
+        .ok()
+        .and_then(|raw| serde_json::from_slice(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn state_path(workspace_dir: &Path) -> std::path::PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use tempfile::TempDir;
+
+    /// Replies with queued JSON strings in order.
+    struct ScriptedProvider {
+        replies: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[serde_json::Value]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().map(ToString::to_string).collect()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.replies
+                .lock()
+                .pop()
+                .ok_or_else(|| anyhow::anyhow!("no scripted reply left"))
+        }
+    }
+
+    async fn seeded() -> (TempDir, SqliteMemory) {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "conv_1",
+            "I moved to Lisbon last month",
+            MemoryCategory::Conversation,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store("conv_2", "thanks!", MemoryCategory::Conversation, None)
+            .await
+            .unwrap();
+        mem.store("home_city", "Lives in Berlin", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("city", "Based in Berlin", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        (tmp, mem)
+    }
+
+    fn extraction_reply() -> serde_json::Value {
+        serde_json::json!({
+            "facts": [
+                {"key": "Home City", "content": "Lives in Lisbon", "importance": 0.9, "sources": ["conv_1", "unknown"]},
+                {"key": "conv_2", "content": "not a fact", "importance": 0.1, "sources": []}
+            ],
+            "merges": [
+                {"key": "home_city", "content": "Lives in Lisbon", "duplicates": ["city", "missing"]}
+            ]
+        })
+    }
+
+    fn config() -> MemoryConsolidationConfig {
+        MemoryConsolidationConfig {
+            summarize_daily_after_days: 0,
+            ..MemoryConsolidationConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn plan_validates_reply_without_writing() {
+        let (_tmp, mem) = seeded().await;
+        let provider = ScriptedProvider::new(&[extraction_reply()]);
+
+        let plan = plan(&mem, None, &provider, "test", &config())
+            .await
+            .unwrap();
+
+        assert_eq!(plan.reviewed, ["conv_1", "conv_2"]);
+        assert_eq!(plan.facts.len(), 1);
+        let fact = &plan.facts[0];
+        assert_eq!(fact.key, "home_city");
+        assert_eq!(fact.sources, ["conv_1"]);
+        assert_eq!(fact.replaces.as_deref(), Some("Lives in Berlin"));
+        assert_eq!(plan.merges.len(), 1);
+        assert_eq!(plan.merges[0].duplicates, ["city"]);
+        assert!(plan.render().contains("~ home_city: Lives in Lisbon"));
+
+        assert_eq!(mem.count().await.unwrap(), 4);
+        let conv = mem.get("conv_1").await.unwrap().unwrap();
+        assert!(conv.metadata.consolidated_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn apply_writes_facts_merges_and_provenance() {
+        let (_tmp, mem) = seeded().await;
+        let provider = ScriptedProvider::new(&[extraction_reply()]);
+        let plan = plan(&mem, None, &provider, "test", &config())
+            .await
+            .unwrap();
+
+        let report = apply(&mem, &plan).await.unwrap();
+        assert_eq!(report.facts_written, 1);
+        assert_eq!(report.duplicates_merged, 1);
+        assert_eq!(report.sources_marked, 2);
+
+        let fact = mem.get("home_city").await.unwrap().unwrap();
+        assert_eq!(fact.content, "Lives in Lisbon");
+        assert_eq!(fact.metadata.derived_from, ["conv_1", "city"]);
+        assert_eq!(fact.metadata.history[0].content, "Lives in Berlin");
+        assert!(mem.get("city").await.unwrap().is_none());
+
+        let conv = mem.get("conv_1").await.unwrap().unwrap();
+        assert_eq!(conv.metadata.consolidated_into, ["home_city"]);
+        assert!(conv.metadata.consolidated_at.is_some());
+        assert_eq!(conv.metadata.importance, Some(REVIEWED_IMPORTANCE));
+
+        // Reviewed entries are not sent to the model again.
+        let idle = ScriptedProvider::new(&[]);
+        let next = super::plan(&mem, None, &idle, "test", &config())
+            .await
+            .unwrap();
+        assert!(next.is_empty());
+    }
+
+    #[tokio::test]
+    async fn namespaces_are_consolidated_separately() {
+        let (_tmp, mem) = seeded().await;
+        mem.store_in(
+            Some("alice"),
+            "conv_alice",
+            "I moved to Lisbon last month",
+            MemoryCategory::Conversation,
+            None,
+            &MemoryMetadata::default(),
+        )
+        .await
+        .unwrap();
+
+        let provider = ScriptedProvider::new(&[extraction_reply()]);
+        let shared = plan(&mem, None, &provider, "test", &config())
+            .await
+            .unwrap();
+        assert_eq!(shared.reviewed, ["conv_1", "conv_2"]);
+
+        let provider = ScriptedProvider::new(&[extraction_reply()]);
+        let personal = plan(&mem, Some("alice"), &provider, "test", &config())
+            .await
+            .unwrap();
+        assert_eq!(personal.reviewed, ["conv_alice"]);
+        assert!(personal.merges.is_empty());
+        assert!(personal.render().starts_with("Namespace alice:"));
+
+        apply(&mem, &personal).await.unwrap();
+        let own = mem
+            .get_in(Some("alice"), "home_city")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(own.content, "Lives in Lisbon");
+        let team = mem.get("home_city").await.unwrap().unwrap();
+        assert_eq!(team.content, "Lives in Berlin");
+        assert!(mem.get("city").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn old_daily_entries_are_summarized_per_day() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let old = (Utc::now() - Duration::days(30)).to_rfc3339();
+        for key in ["log_a", "log_b"] {
+            mem.import(
+                &MemoryEntry {
+                    id: String::new(),
+                    key: key.into(),
+                    content: format!("{key} happened"),
+                    category: MemoryCategory::Daily,
+                    timestamp: old.clone(),
+                    session_id: None,
+                    score: None,
+                    metadata: MemoryMetadata::default(),
+                    namespace: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        mem.store("log_today", "fresh", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+
+        let provider = ScriptedProvider::new(&[serde_json::json!({"summary": "- a and b"})]);
+        let config = MemoryConsolidationConfig::default();
+        let plan = plan(&mem, None, &provider, "test", &config).await.unwrap();
+        assert_eq!(plan.summaries.len(), 1);
+        assert_eq!(plan.summaries[0].sources.len(), 2);
+
+        let report = apply(&mem, &plan).await.unwrap();
+        assert_eq!(report.days_summarized, 1);
+        let summary = mem.get(&plan.summaries[0].key).await.unwrap().unwrap();
+        assert_eq!(summary.content, "- a and b");
+        assert_eq!(summary.category, MemoryCategory::Daily);
+        assert_eq!(summary.metadata.derived_from.len(), 2);
+        assert!(mem.get("log_a").await.unwrap().is_none());
+        assert!(mem.get("log_today").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn normalize_key_produces_snake_case() {
+        assert_eq!(normalize_key(" Home City! "), "home_city");
+        assert_eq!(normalize_key("user--tz"), "user_tz");
+        assert_eq!(normalize_key("???"), "");
+    }
+
+    #[test]
+    fn is_due_respects_enabled_flag_and_interval() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().to_path_buf(),
+            ..Config::default()
+        };
+        assert!(!is_due(&config));
+
+        config.memory.consolidation.enabled = true;
+        assert!(is_due(&config));
+
+        record_run(tmp.path(), &Ok((vec![ConsolidationPlan::default()], None))).unwrap();
+        assert!(!is_due(&config));
+    }
+}
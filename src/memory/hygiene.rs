@@ -17,6 +17,8 @@ struct HygieneReport {
     purged_memory_archives: u64,
     purged_session_archives: u64,
     pruned_conversation_rows: u64,
+    #[serde(default)]
+    purged_expired_rows: u64,
 }
 
 impl HygieneReport {
@@ -26,6 +28,7 @@ impl HygieneReport {
             + self.purged_memory_archives
             + self.purged_session_archives
             + self.pruned_conversation_rows
+            + self.purged_expired_rows
     }
 }
 
@@ -59,18 +62,20 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
             workspace_dir,
             config.conversation_retention_days,
         )?,
+        purged_expired_rows: purge_expired_rows(workspace_dir)?,
     };
 
     write_state(workspace_dir, &report)?;
 
     if report.total_actions() > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={} purged_expired_rows={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
             report.pruned_conversation_rows,
+            report.purged_expired_rows,
         );
     }
 
@@ -318,6 +323,27 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     Ok(u64::try_from(affected).unwrap_or(0))
 }
 
+fn purge_expired_rows(workspace_dir: &Path) -> Result<u64> {
+    let db_path = workspace_dir.join("memory").join("brain.db");
+    if !db_path.exists() {
+        return Ok(0);
+    }
+
+    let conn = Connection::open(db_path)?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+
+    // Databases not yet opened by this version lack the expiry column.
+    let has_expiry = conn
+        .prepare("SELECT 1 FROM pragma_table_info('memories') WHERE name = 'expires_at'")?
+        .exists([])?;
+    if !has_expiry {
+        return Ok(0);
+    }
+
+    let affected = crate::memory::sqlite::purge_expired_rows(&conn)?;
+    Ok(u64::try_from(affected).unwrap_or(0))
+}
+
 fn memory_date_from_filename(filename: &str) -> Option<NaiveDate> {
     let stem = filename.strip_suffix(".md")?;
     let date_part = stem.split('_').next().unwrap_or(stem);
@@ -539,4 +565,44 @@ mod tests {
             "core memory should remain"
         );
     }
+
+    #[tokio::test]
+    async fn purges_expired_rows_in_sqlite_backend() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+
+        let mem = SqliteMemory::new(workspace).unwrap();
+        let expired = crate::memory::MemoryMetadata {
+            expires_at: Some((Utc::now() - Duration::hours(1)).to_rfc3339()),
+            ..Default::default()
+        };
+        mem.store_with_metadata("stale", "old", MemoryCategory::Core, None, &expired)
+            .await
+            .unwrap();
+        mem.store("durable", "kept", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert!(
+            mem.get("stale").await.unwrap().is_none(),
+            "expired entries are hidden"
+        );
+        drop(mem);
+
+        let mut cfg = default_cfg();
+        cfg.archive_after_days = 0;
+        cfg.purge_after_days = 0;
+        cfg.conversation_retention_days = 0;
+
+        run_if_due(&cfg, workspace).unwrap();
+
+        let conn = Connection::open(workspace.join("memory").join("brain.db")).unwrap();
+        let keys: Vec<String> = conn
+            .prepare("SELECT key FROM memories")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(keys, ["durable"]);
+    }
 }
@@ -1,3 +1,4 @@
+use super::metadata::MemoryMetadata;
 use super::sqlite::SqliteMemory;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
@@ -226,6 +227,8 @@ impl LucidMemory {
                 timestamp: now.clone(),
                 session_id: None,
                 score: Some((1.0 - rank as f64 * 0.05).max(0.1)),
+                metadata: MemoryMetadata::default(),
+                namespace: None,
             });
         }
 
@@ -320,13 +323,70 @@ impl Memory for LucidMemory {
         Ok(())
     }
 
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.local
+            .store_with_metadata(key, content, category.clone(), session_id, metadata)
+            .await?;
+        self.sync_to_lucid_async(key, content, &category).await;
+        Ok(())
+    }
+
+    /// Namespaced writes stay local: Lucid has no notion of people, so
+    /// syncing them would expose personal facts through shared recall.
+    async fn store_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.local
+            .store_in(
+                namespace,
+                key,
+                content,
+                category.clone(),
+                session_id,
+                metadata,
+            )
+            .await?;
+        if namespace.is_none() {
+            self.sync_to_lucid_async(key, content, &category).await;
+        }
+        Ok(())
+    }
+
     async fn recall(
         &self,
         query: &str,
         limit: usize,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let local_results = self.local.recall(query, limit, session_id).await?;
+        self.recall_in(None, query, limit, session_id).await
+    }
+
+    /// Lucid only ever holds shared-scope writes, so its results are safe to
+    /// merge into any namespace's recall.
+    async fn recall_in(
+        &self,
+        namespace: Option<&str>,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let local_results = self
+            .local
+            .recall_in(namespace, query, limit, session_id)
+            .await?;
         if limit == 0
             || local_results.len() >= limit
             || local_results.len() >= self.local_hit_threshold
@@ -363,6 +423,14 @@ impl Memory for LucidMemory {
         self.local.get(key).await
     }
 
+    async fn get_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<MemoryEntry>> {
+        self.local.get_in(namespace, key).await
+    }
+
     async fn list(
         &self,
         category: Option<&MemoryCategory>,
@@ -371,10 +439,27 @@ impl Memory for LucidMemory {
         self.local.list(category, session_id).await
     }
 
+    async fn list_in(
+        &self,
+        namespace: Option<&str>,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.local.list_in(namespace, category, session_id).await
+    }
+
     async fn forget(&self, key: &str) -> anyhow::Result<bool> {
         self.local.forget(key).await
     }
 
+    async fn forget_in(&self, namespace: Option<&str>, key: &str) -> anyhow::Result<bool> {
+        self.local.forget_in(namespace, key).await
+    }
+
+    async fn namespaces(&self) -> anyhow::Result<Vec<String>> {
+        self.local.namespaces().await
+    }
+
     async fn count(&self) -> anyhow::Result<usize> {
         self.local.count().await
     }
@@ -382,6 +467,31 @@ impl Memory for LucidMemory {
     async fn health_check(&self) -> bool {
         self.local.health_check().await
     }
+
+    async fn purge_expired(&self) -> anyhow::Result<usize> {
+        self.local.purge_expired().await
+    }
+
+    async fn list_page(&self, offset: usize, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.local.list_page(offset, limit).await
+    }
+
+    async fn embedding(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<Vec<f32>>> {
+        self.local.embedding(namespace, key).await
+    }
+
+    async fn import(&self, entry: &MemoryEntry, embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        self.local.import(entry, embedding).await?;
+        if entry.namespace.is_none() {
+            self.sync_to_lucid_async(&entry.key, &entry.content, &entry.category)
+                .await;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(all(test, unix))]
@@ -1,14 +1,20 @@
+use super::metadata::{self, MemoryMetadata};
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
 use chrono::Local;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+/// Prefix of the HTML comment that carries an entry's metadata, e.g.
+/// `- **key**: content <!-- memory: {"tags":["work"]} -->`.
+const METADATA_MARKER: &str = " <!-- memory: ";
+
 /// Markdown-based memory — plain files as source of truth
 ///
 /// Layout:
 ///   workspace/MEMORY.md          — curated long-term memory (core)
 ///   workspace/memory/YYYY-MM-DD.md — daily logs (append-only)
+///   workspace/memory/people/<namespace>/ — the same pair per person
 pub struct MarkdownMemory {
     workspace_dir: PathBuf,
 }
@@ -28,18 +34,43 @@ impl MarkdownMemory {
         self.workspace_dir.join("MEMORY.md")
     }
 
-    fn daily_path(&self) -> PathBuf {
-        let date = Local::now().format("%Y-%m-%d").to_string();
-        self.memory_dir().join(format!("{date}.md"))
+    /// Directory of daily logs: the memory dir for the shared scope, a
+    /// per-person directory otherwise.
+    fn log_dir(&self, namespace: Option<&str>) -> PathBuf {
+        match namespace {
+            None => self.memory_dir(),
+            Some(ns) => {
+                let safe: String = ns
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                self.memory_dir().join("people").join(safe)
+            }
+        }
     }
 
-    async fn ensure_dirs(&self) -> anyhow::Result<()> {
-        fs::create_dir_all(self.memory_dir()).await?;
-        Ok(())
+    fn core_path_in(&self, namespace: Option<&str>) -> PathBuf {
+        match namespace {
+            None => self.core_path(),
+            Some(_) => self.log_dir(namespace).join("MEMORY.md"),
+        }
+    }
+
+    fn daily_path(&self, namespace: Option<&str>) -> PathBuf {
+        let date = Local::now().format("%Y-%m-%d").to_string();
+        self.log_dir(namespace).join(format!("{date}.md"))
     }
 
     async fn append_to_file(&self, path: &Path, content: &str) -> anyhow::Result<()> {
-        self.ensure_dirs().await?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
 
         let existing = if path.exists() {
             fs::read_to_string(path).await.unwrap_or_default()
@@ -48,7 +79,7 @@ impl MarkdownMemory {
         };
 
         let updated = if existing.is_empty() {
-            let header = if path == self.core_path() {
+            let header = if path.file_name().and_then(|n| n.to_str()) == Some("MEMORY.md") {
                 "# Long-Term Memory\n\n"
             } else {
                 let date = Local::now().format("%Y-%m-%d").to_string();
@@ -83,6 +114,7 @@ impl MarkdownMemory {
             .map(|(i, line)| {
                 let trimmed = line.trim();
                 let clean = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+                let (clean, metadata) = Self::split_metadata(clean);
                 MemoryEntry {
                     id: format!("{filename}:{i}"),
                     key: format!("{filename}:{i}"),
@@ -91,16 +123,33 @@ impl MarkdownMemory {
                     timestamp: filename.to_string(),
                     session_id: None,
                     score: None,
+                    metadata,
+                    namespace: None,
                 }
             })
+            .filter(|entry| !entry.metadata.is_expired())
             .collect()
     }
 
+    /// Split a trailing metadata comment off an entry line.
+    fn split_metadata(line: &str) -> (&str, MemoryMetadata) {
+        line.strip_suffix(" -->")
+            .and_then(|rest| rest.rsplit_once(METADATA_MARKER))
+            .map_or((line, MemoryMetadata::default()), |(content, raw)| {
+                (content, MemoryMetadata::from_column(Some(raw)))
+            })
+    }
+
     async fn read_all_entries(&self) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.read_entries(None).await
+    }
+
+    /// Entries of exactly one namespace (`None` = shared scope).
+    async fn read_entries(&self, namespace: Option<&str>) -> anyhow::Result<Vec<MemoryEntry>> {
         let mut entries = Vec::new();
 
         // Read MEMORY.md (core)
-        let core_path = self.core_path();
+        let core_path = self.core_path_in(namespace);
         if core_path.exists() {
             let content = fs::read_to_string(&core_path).await?;
             entries.extend(Self::parse_entries_from_file(
@@ -111,12 +160,12 @@ impl MarkdownMemory {
         }
 
         // Read daily logs
-        let mem_dir = self.memory_dir();
+        let mem_dir = self.log_dir(namespace);
         if mem_dir.exists() {
             let mut dir = fs::read_dir(&mem_dir).await?;
             while let Some(entry) = dir.next_entry().await? {
                 let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                if path.extension().and_then(|e| e.to_str()) == Some("md") && path != core_path {
                     let content = fs::read_to_string(&path).await?;
                     entries.extend(Self::parse_entries_from_file(
                         &path,
@@ -127,39 +176,43 @@ impl MarkdownMemory {
             }
         }
 
+        for entry in &mut entries {
+            entry.namespace = namespace.map(String::from);
+        }
         entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
         Ok(entries)
     }
-}
-
-#[async_trait]
-impl Memory for MarkdownMemory {
-    fn name(&self) -> &str {
-        "markdown"
-    }
 
-    async fn store(
+    async fn append_entry(
         &self,
+        namespace: Option<&str>,
         key: &str,
         content: &str,
-        category: MemoryCategory,
-        _session_id: Option<&str>,
+        category: &MemoryCategory,
+        metadata: &MemoryMetadata,
     ) -> anyhow::Result<()> {
-        let entry = format!("- **{key}**: {content}");
+        let mut entry = format!("- **{key}**: {content}");
+        if let Some(raw) = metadata.to_column() {
+            entry = format!("{entry}{METADATA_MARKER}{raw} -->");
+        }
         let path = match category {
-            MemoryCategory::Core => self.core_path(),
-            _ => self.daily_path(),
+            MemoryCategory::Core => self.core_path_in(namespace),
+            _ => self.daily_path(namespace),
         };
         self.append_to_file(&path, &entry).await
     }
 
-    async fn recall(
+    /// Keyword search over `namespace` (if any) and the shared scope.
+    async fn recall_scoped(
         &self,
+        namespace: Option<&str>,
         query: &str,
         limit: usize,
-        _session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
+        let mut all = self.read_entries(None).await?;
+        if namespace.is_some() {
+            all.extend(self.read_entries(namespace).await?);
+        }
         let query_lower = query.to_lowercase();
         let keywords: Vec<&str> = query_lower.split_whitespace().collect();
 
@@ -182,17 +235,91 @@ impl Memory for MarkdownMemory {
             })
             .collect();
 
-        scored.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        metadata::rank_by_importance(&mut scored);
         scored.truncate(limit);
         Ok(scored)
     }
+}
+
+#[async_trait]
+impl Memory for MarkdownMemory {
+    fn name(&self) -> &str {
+        "markdown"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.store_with_metadata(
+            key,
+            content,
+            category,
+            session_id,
+            &MemoryMetadata::default(),
+        )
+        .await
+    }
+
+    /// Markdown is append-only, so earlier lines already serve as the
+    /// update history.
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        _session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.append_entry(None, key, content, &category, metadata)
+            .await
+    }
+
+    async fn store_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        _session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.append_entry(namespace, key, content, &category, metadata)
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.recall_scoped(None, query, limit).await
+    }
+
+    async fn recall_in(
+        &self,
+        namespace: Option<&str>,
+        query: &str,
+        limit: usize,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.recall_scoped(namespace, query, limit).await
+    }
 
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
+        self.get_in(None, key).await
+    }
+
+    async fn get_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<MemoryEntry>> {
+        let all = self.read_entries(namespace).await?;
         Ok(all
             .into_iter()
             .find(|e| e.key == key || e.content.contains(key)))
@@ -201,9 +328,18 @@ impl Memory for MarkdownMemory {
     async fn list(
         &self,
         category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.list_in(None, category, session_id).await
+    }
+
+    async fn list_in(
+        &self,
+        namespace: Option<&str>,
+        category: Option<&MemoryCategory>,
         _session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
+        let all = self.read_entries(namespace).await?;
         match category {
             Some(cat) => Ok(all.into_iter().filter(|e| &e.category == cat).collect()),
             None => Ok(all),
@@ -216,6 +352,10 @@ impl Memory for MarkdownMemory {
         Ok(false)
     }
 
+    async fn forget_in(&self, _namespace: Option<&str>, _key: &str) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
     async fn count(&self) -> anyhow::Result<usize> {
         let all = self.read_all_entries().await?;
         Ok(all.len())
@@ -265,7 +405,7 @@ mod tests {
         mem.store("note", "Finished tests", MemoryCategory::Daily, None)
             .await
             .unwrap();
-        let path = mem.daily_path();
+        let path = mem.daily_path(None);
         let content = fs::read_to_string(path).await.unwrap();
         assert!(content.contains("Finished tests"));
     }
@@ -0,0 +1,343 @@
+//! Per-entry memory metadata: tags, importance, expiry, provenance and
+//! update history.
+
+use super::traits::MemoryEntry;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Importance assumed for entries that do not set one.
+pub const DEFAULT_IMPORTANCE: f64 = 0.5;
+/// Previous versions kept per entry.
+pub const MAX_HISTORY: usize = 10;
+
+/// Optional metadata attached to a memory entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryMetadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 0.0 (chatter) to 1.0 (durable fact). `None` means [`DEFAULT_IMPORTANCE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f64>,
+    /// RFC 3339 time after which the entry is hidden and purged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<MemorySource>,
+    /// Keys of the entries consolidation built this one from.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived_from: Vec<String>,
+    /// Keys of the entries consolidation derived from this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consolidated_into: Vec<String>,
+    /// When consolidation last reviewed this entry (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consolidated_at: Option<String>,
+    /// Earlier contents, oldest first (at most [`MAX_HISTORY`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<MemoryRevision>,
+}
+
+/// Where a memory came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemorySource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+}
+
+/// A previous version of an entry's content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryRevision {
+    pub content: String,
+    pub updated_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<MemorySource>,
+}
+
+/// The stored state of an entry about to be overwritten.
+pub struct PreviousVersion<'a> {
+    pub content: &'a str,
+    pub updated_at: &'a str,
+    pub metadata: MemoryMetadata,
+}
+
+impl MemoryMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn importance_or_default(&self) -> f64 {
+        self.importance
+            .map_or(DEFAULT_IMPORTANCE, |value| value.clamp(0.0, 1.0))
+    }
+
+    pub fn expires_at_utc(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+            .as_deref()
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+            .map(|at| at.with_timezone(&Utc))
+    }
+
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at_utc().is_some_and(|at| at <= now)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Serialize for a backend metadata column; empty metadata is stored as NULL.
+    pub fn to_column(&self) -> Option<String> {
+        if self.is_empty() {
+            None
+        } else {
+            serde_json::to_string(self).ok()
+        }
+    }
+
+    /// Parse a backend metadata column, treating bad JSON as no metadata.
+    pub fn from_column(raw: Option<&str>) -> Self {
+        raw.and_then(|raw| serde_json::from_str(raw).ok())
+            .unwrap_or_default()
+    }
+
+    /// Metadata for writing `content` over `previous`. `incoming` replaces
+    /// tags, importance, expiry and source; `None` keeps the previous ones.
+    /// Provenance is kept unless `incoming` sets it. A changed content
+    /// pushes the previous one onto the history.
+    pub fn for_write(
+        previous: Option<PreviousVersion<'_>>,
+        incoming: Option<&MemoryMetadata>,
+        content: &str,
+    ) -> Self {
+        let Some(previous) = previous else {
+            let mut metadata = incoming.cloned().unwrap_or_default();
+            metadata.history.clear();
+            return metadata;
+        };
+
+        let mut metadata = match incoming {
+            Some(incoming) => MemoryMetadata {
+                history: Vec::new(),
+                ..incoming.clone()
+            },
+            None => MemoryMetadata {
+                history: Vec::new(),
+                ..previous.metadata.clone()
+            },
+        };
+        if metadata.derived_from.is_empty() {
+            metadata.derived_from = previous.metadata.derived_from;
+        }
+        if metadata.consolidated_into.is_empty() {
+            metadata.consolidated_into = previous.metadata.consolidated_into;
+        }
+        if metadata.consolidated_at.is_none() {
+            metadata.consolidated_at = previous.metadata.consolidated_at;
+        }
+        metadata.history = previous.metadata.history;
+        if previous.content != content {
+            metadata.history.push(MemoryRevision {
+                content: previous.content.to_string(),
+                updated_at: previous.updated_at.to_string(),
+                source: previous.metadata.source,
+            });
+        }
+        let overflow = metadata.history.len().saturating_sub(MAX_HISTORY);
+        metadata.history.drain(..overflow);
+        metadata
+    }
+}
+
+/// Scale recall scores by importance (0.75x–1.25x, 1x at the default) and
+/// re-sort, so durable facts outrank chatter with a similar match.
+pub fn rank_by_importance(entries: &mut [MemoryEntry]) {
+    for entry in entries.iter_mut() {
+        if let Some(score) = entry.score.as_mut() {
+            *score *= 0.75 + 0.5 * entry.metadata.importance_or_default();
+        }
+    }
+    entries.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Parse an expiry given as an RFC 3339 time or a duration from now
+/// (`30m`, `12h`, `7d`, `2w`). Returns the normalized RFC 3339 UTC time.
+pub fn parse_expiry(raw: &str) -> anyhow::Result<String> {
+    let raw = raw.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(at
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Secs, true));
+    }
+
+    let split = raw
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow::anyhow!("expiry '{raw}' is missing a unit (m, h, d or w)"))?;
+    let (amount, unit) = raw.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| anyhow::anyhow!("expiry '{raw}' must start with a number"))?;
+    let duration = match unit.trim() {
+        "m" | "min" | "minutes" => Duration::try_minutes(amount),
+        "h" | "hours" => Duration::try_hours(amount),
+        "d" | "days" => Duration::try_days(amount),
+        "w" | "weeks" => Duration::try_weeks(amount),
+        other => anyhow::bail!("unknown expiry unit '{other}' (use m, h, d or w)"),
+    }
+    .filter(|duration| *duration > Duration::zero())
+    .ok_or_else(|| anyhow::anyhow!("expiry '{raw}' is out of range"))?;
+
+    Ok((Utc::now() + duration).to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryCategory;
+
+    fn entry(key: &str, score: f64, importance: Option<f64>) -> MemoryEntry {
+        MemoryEntry {
+            id: key.into(),
+            key: key.into(),
+            content: String::new(),
+            category: MemoryCategory::Core,
+            timestamp: String::new(),
+            session_id: None,
+            score: Some(score),
+            metadata: MemoryMetadata {
+                importance,
+                ..MemoryMetadata::default()
+            },
+            namespace: None,
+        }
+    }
+
+    #[test]
+    fn for_write_tracks_history_and_keeps_metadata_on_plain_store() {
+        let tagged = MemoryMetadata {
+            tags: vec!["work".into()],
+            importance: Some(0.9),
+            ..MemoryMetadata::default()
+        };
+        let first = MemoryMetadata::for_write(None, Some(&tagged), "v1");
+        assert_eq!(first.tags, ["work"]);
+        assert!(first.history.is_empty());
+
+        let second = MemoryMetadata::for_write(
+            Some(PreviousVersion {
+                content: "v1",
+                updated_at: "2026-01-01T00:00:00Z",
+                metadata: first,
+            }),
+            None,
+            "v2",
+        );
+        assert_eq!(second.tags, ["work"]);
+        assert_eq!(second.importance, Some(0.9));
+        assert_eq!(second.history.len(), 1);
+        assert_eq!(second.history[0].content, "v1");
+
+        let unchanged = MemoryMetadata::for_write(
+            Some(PreviousVersion {
+                content: "v2",
+                updated_at: "2026-01-02T00:00:00Z",
+                metadata: second,
+            }),
+            Some(&MemoryMetadata::default()),
+            "v2",
+        );
+        assert!(unchanged.tags.is_empty());
+        assert_eq!(unchanged.history.len(), 1);
+    }
+
+    #[test]
+    fn for_write_keeps_provenance_unless_replaced() {
+        let derived = MemoryMetadata {
+            derived_from: vec!["conv_1".into()],
+            ..MemoryMetadata::default()
+        };
+        let retagged = MemoryMetadata::for_write(
+            Some(PreviousVersion {
+                content: "v1",
+                updated_at: "2026-01-01T00:00:00Z",
+                metadata: derived,
+            }),
+            Some(&MemoryMetadata {
+                tags: vec!["work".into()],
+                ..MemoryMetadata::default()
+            }),
+            "v1",
+        );
+        assert_eq!(retagged.tags, ["work"]);
+        assert_eq!(retagged.derived_from, ["conv_1"]);
+    }
+
+    #[test]
+    fn for_write_caps_history() {
+        let mut metadata = MemoryMetadata::default();
+        for i in 0..(MAX_HISTORY + 5) {
+            let previous_content = format!("v{i}");
+            metadata = MemoryMetadata::for_write(
+                Some(PreviousVersion {
+                    content: &previous_content,
+                    updated_at: "2026-01-01T00:00:00Z",
+                    metadata,
+                }),
+                None,
+                &format!("v{}", i + 1),
+            );
+        }
+        assert_eq!(metadata.history.len(), MAX_HISTORY);
+        assert_eq!(metadata.history[0].content, "v5");
+    }
+
+    #[test]
+    fn expiry_parsing_and_checks() {
+        let at = parse_expiry("2026-03-01T12:00:00+02:00").unwrap();
+        assert_eq!(at, "2026-03-01T10:00:00Z");
+
+        let week = parse_expiry("7d").unwrap();
+        let metadata = MemoryMetadata {
+            expires_at: Some(week),
+            ..MemoryMetadata::default()
+        };
+        assert!(!metadata.is_expired());
+        assert!(metadata.is_expired_at(Utc::now() + Duration::days(8)));
+
+        assert!(parse_expiry("soon").is_err());
+        assert!(parse_expiry("5y").is_err());
+        assert!(parse_expiry("0d").is_err());
+    }
+
+    #[test]
+    fn importance_reorders_close_matches() {
+        let mut entries = vec![
+            entry("chatter", 0.8, Some(0.0)),
+            entry("fact", 0.7, Some(1.0)),
+            entry("plain", 0.75, None),
+        ];
+        rank_by_importance(&mut entries);
+        let keys: Vec<_> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["fact", "plain", "chatter"]);
+    }
+
+    #[test]
+    fn column_roundtrip_stores_empty_as_null() {
+        assert_eq!(MemoryMetadata::default().to_column(), None);
+        let metadata = MemoryMetadata {
+            tags: vec!["a".into()],
+            ..MemoryMetadata::default()
+        };
+        let column = metadata.to_column();
+        assert_eq!(MemoryMetadata::from_column(column.as_deref()), metadata);
+        assert!(MemoryMetadata::from_column(Some("not json")).is_empty());
+    }
+}
@@ -1,10 +1,13 @@
 pub mod backend;
 pub mod chunker;
 pub mod cli;
+pub mod consolidation;
 pub mod embeddings;
 pub mod hygiene;
 pub mod lucid;
 pub mod markdown;
+pub mod metadata;
+pub mod namespace;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
@@ -13,6 +16,7 @@ pub mod response_cache;
 pub mod snapshot;
 pub mod sqlite;
 pub mod traits;
+pub mod transfer;
 pub mod vector;
 
 #[allow(unused_imports)]
@@ -22,6 +26,8 @@ pub use backend::{
 };
 pub use lucid::LucidMemory;
 pub use markdown::MarkdownMemory;
+#[allow(unused_imports)]
+pub use metadata::{MemoryMetadata, MemorySource};
 pub use none::NoneMemory;
 #[cfg(feature = "memory-postgres")]
 pub use postgres::PostgresMemory;
@@ -0,0 +1,108 @@
+//! Per-person memory namespaces.
+//!
+//! A channel sender resolves to a namespace through `[memory.namespaces]`:
+//! senders linked to a person share that person's namespace across
+//! channels, anyone else is isolated as `channel:sender`. While a channel
+//! message is processed, its namespace is carried as a task-local so the
+//! memory tools read and write the right person's memories.
+
+use crate::config::MemoryNamespacesConfig;
+use std::future::Future;
+
+tokio::task_local! {
+    static ACTIVE_NAMESPACE: Option<String>;
+}
+
+/// Namespace for `sender` on `channel`; `None` (the shared scope) when
+/// namespaces are disabled.
+pub fn resolve(config: &MemoryNamespacesConfig, channel: &str, sender: &str) -> Option<String> {
+    config.enabled.then(|| person(config, channel, sender))
+}
+
+/// The person `sender` on `channel` is: the linked person's name, or
+/// `channel:sender` for anyone not linked. Unlike [`resolve`] this ignores
+/// `enabled`, so it can identify callers outside of memory.
+pub fn person(config: &MemoryNamespacesConfig, channel: &str, sender: &str) -> String {
+    let identity = format!("{channel}:{}", sender.trim());
+    let linked = config.people.iter().find(|person| {
+        person
+            .senders
+            .iter()
+            .any(|linked| linked.trim().eq_ignore_ascii_case(&identity))
+    });
+
+    match linked {
+        Some(person) if !person.name.trim().is_empty() => person.name.trim().to_string(),
+        _ => identity,
+    }
+}
+
+/// Run `future` with `namespace` as the active namespace.
+pub async fn with_namespace<F: Future>(namespace: Option<String>, future: F) -> F::Output {
+    ACTIVE_NAMESPACE.scope(namespace, future).await
+}
+
+/// Namespace of the channel message being processed; `None` outside one.
+pub fn active() -> Option<String> {
+    ACTIVE_NAMESPACE.try_with(Clone::clone).ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MemoryPersonConfig;
+
+    fn config_with_alice() -> MemoryNamespacesConfig {
+        MemoryNamespacesConfig {
+            enabled: true,
+            people: vec![MemoryPersonConfig {
+                name: "alice".into(),
+                senders: vec!["telegram:1001".into(), "Discord:Alice".into()],
+            }],
+        }
+    }
+
+    #[test]
+    fn linked_senders_share_a_namespace_across_channels() {
+        let config = config_with_alice();
+        assert_eq!(
+            resolve(&config, "telegram", "1001").as_deref(),
+            Some("alice")
+        );
+        assert_eq!(
+            resolve(&config, "discord", "alice").as_deref(),
+            Some("alice")
+        );
+    }
+
+    #[test]
+    fn unlinked_senders_are_isolated_per_channel() {
+        let config = config_with_alice();
+        assert_eq!(
+            resolve(&config, "telegram", "2002").as_deref(),
+            Some("telegram:2002")
+        );
+        assert_eq!(
+            resolve(&config, "discord", "1001").as_deref(),
+            Some("discord:1001")
+        );
+    }
+
+    #[test]
+    fn disabled_namespaces_resolve_to_shared_scope() {
+        let config = MemoryNamespacesConfig {
+            enabled: false,
+            ..config_with_alice()
+        };
+        assert_eq!(resolve(&config, "telegram", "1001"), None);
+        assert_eq!(person(&config, "telegram", "1001"), "alice");
+    }
+
+    #[tokio::test]
+    async fn active_namespace_is_scoped_to_the_future() {
+        assert_eq!(active(), None);
+        let inside = with_namespace(Some("alice".into()), async { active() }).await;
+        assert_eq!(inside.as_deref(), Some("alice"));
+        assert_eq!(active(), None);
+    }
+}
@@ -1,3 +1,4 @@
+use super::metadata::MemoryMetadata;
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
 
@@ -55,6 +56,49 @@ impl Memory for NoneMemory {
         Ok(false)
     }
 
+    async fn store_in(
+        &self,
+        _namespace: Option<&str>,
+        _key: &str,
+        _content: &str,
+        _category: MemoryCategory,
+        _session_id: Option<&str>,
+        _metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn recall_in(
+        &self,
+        _namespace: Option<&str>,
+        _query: &str,
+        _limit: usize,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_in(
+        &self,
+        _namespace: Option<&str>,
+        _key: &str,
+    ) -> anyhow::Result<Option<MemoryEntry>> {
+        Ok(None)
+    }
+
+    async fn list_in(
+        &self,
+        _namespace: Option<&str>,
+        _category: Option<&MemoryCategory>,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn forget_in(&self, _namespace: Option<&str>, _key: &str) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
     async fn count(&self) -> anyhow::Result<usize> {
         Ok(0)
     }
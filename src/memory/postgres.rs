@@ -1,3 +1,4 @@
+use super::metadata::{self, MemoryMetadata, PreviousVersion};
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -176,12 +177,124 @@ impl PostgresMemory {
             CREATE INDEX IF NOT EXISTS idx_memories_category ON {qualified_table}(category);
             CREATE INDEX IF NOT EXISTS idx_memories_session_id ON {qualified_table}(session_id);
             CREATE INDEX IF NOT EXISTS idx_memories_updated_at ON {qualified_table}(updated_at DESC);
+
+            ALTER TABLE {qualified_table} ADD COLUMN IF NOT EXISTS metadata TEXT;
+            ALTER TABLE {qualified_table} ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ;
+            CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON {qualified_table}(expires_at);
+
+            -- Per-person namespaces: keys are unique within a namespace only.
+            ALTER TABLE {qualified_table} ADD COLUMN IF NOT EXISTS namespace TEXT NOT NULL DEFAULT '';
+            DO $$
+            DECLARE constraint_name TEXT;
+            BEGIN
+                SELECT conname INTO constraint_name
+                FROM pg_constraint
+                WHERE conrelid = '{qualified_table}'::regclass
+                  AND contype = 'u'
+                  AND conkey = ARRAY[(
+                      SELECT attnum FROM pg_attribute
+                      WHERE attrelid = '{qualified_table}'::regclass AND attname = 'key'
+                  )];
+                IF constraint_name IS NOT NULL THEN
+                    EXECUTE format('ALTER TABLE {qualified_table} DROP CONSTRAINT %I', constraint_name);
+                END IF;
+            END $$;
+            CREATE UNIQUE INDEX IF NOT EXISTS idx_memories_namespace_key
+                ON {qualified_table}(namespace, key);
             "
         ))?;
 
         Ok(())
     }
 
+    /// Upsert an entry, recording the overwritten content in its history.
+    /// `metadata: None` keeps the entry's existing metadata.
+    async fn write_entry(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: &MemoryCategory,
+        session_id: Option<&str>,
+        metadata: Option<MemoryMetadata>,
+    ) -> Result<()> {
+        let qualified_table = self.qualified_table.clone();
+        let namespace = namespace.unwrap_or_default().to_string();
+        let key = key.to_string();
+        let content = content.to_string();
+        let category = Self::category_to_str(category);
+        let sid = session_id.map(str::to_string);
+        let mut client = self.client.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let now = Utc::now();
+            let mut tx = client.transaction()?;
+
+            let previous = tx.query_opt(
+                &format!(
+                    "SELECT content, updated_at, metadata FROM {qualified_table}
+                     WHERE namespace = $1 AND key = $2 FOR UPDATE"
+                ),
+                &[&namespace, &key],
+            )?;
+            let previous = previous.map(|row| {
+                let updated_at: DateTime<Utc> = row.get(1);
+                (
+                    row.get::<_, String>(0),
+                    updated_at.to_rfc3339(),
+                    MemoryMetadata::from_column(row.get::<_, Option<String>>(2).as_deref()),
+                )
+            });
+            let metadata = MemoryMetadata::for_write(
+                previous
+                    .as_ref()
+                    .map(|(content, updated_at, metadata)| PreviousVersion {
+                        content,
+                        updated_at,
+                        metadata: metadata.clone(),
+                    }),
+                metadata.as_ref(),
+                &content,
+            );
+
+            let stmt = format!(
+                "
+                INSERT INTO {qualified_table}
+                    (id, key, content, category, created_at, updated_at, session_id, metadata, expires_at, namespace)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (namespace, key) DO UPDATE SET
+                    content = EXCLUDED.content,
+                    category = EXCLUDED.category,
+                    updated_at = EXCLUDED.updated_at,
+                    session_id = EXCLUDED.session_id,
+                    metadata = EXCLUDED.metadata,
+                    expires_at = EXCLUDED.expires_at
+                "
+            );
+
+            let id = Uuid::new_v4().to_string();
+            tx.execute(
+                &stmt,
+                &[
+                    &id,
+                    &key,
+                    &content,
+                    &category,
+                    &now,
+                    &now,
+                    &sid,
+                    &metadata.to_column(),
+                    &metadata.expires_at_utc(),
+                    &namespace,
+                ],
+            )?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await?
+    }
+
     fn category_to_str(category: &MemoryCategory) -> String {
         match category {
             MemoryCategory::Core => "core".to_string(),
@@ -210,83 +323,24 @@ impl PostgresMemory {
             category: Self::parse_category(&row.get::<_, String>(3)),
             timestamp: timestamp.to_rfc3339(),
             session_id: row.get(5),
-            score: row.try_get(6).ok(),
+            score: row.try_get("score").ok(),
+            metadata: MemoryMetadata::from_column(
+                row.try_get::<_, Option<String>>("metadata")
+                    .ok()
+                    .flatten()
+                    .as_deref(),
+            ),
+            namespace: row
+                .try_get::<_, String>("namespace")
+                .ok()
+                .filter(|namespace| !namespace.is_empty()),
         })
     }
-}
 
-fn validate_identifier(value: &str, field_name: &str) -> Result<()> {
-    if value.is_empty() {
-        anyhow::bail!("{field_name} must not be empty");
-    }
-
-    let mut chars = value.chars();
-    let Some(first) = chars.next() else {
-        anyhow::bail!("{field_name} must not be empty");
-    };
-
-    if !(first.is_ascii_alphabetic() || first == '_') {
-        anyhow::bail!("{field_name} must start with an ASCII letter or underscore; got '{value}'");
-    }
-
-    if !chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_') {
-        anyhow::bail!(
-            "{field_name} can only contain ASCII letters, numbers, and underscores; got '{value}'"
-        );
-    }
-
-    Ok(())
-}
-
-fn quote_identifier(value: &str) -> String {
-    format!("\"{value}\"")
-}
-
-#[async_trait]
-impl Memory for PostgresMemory {
-    fn name(&self) -> &str {
-        "postgres"
-    }
-
-    async fn store(
-        &self,
-        key: &str,
-        content: &str,
-        category: MemoryCategory,
-        session_id: Option<&str>,
-    ) -> Result<()> {
-        let qualified_table = self.qualified_table.clone();
-        let key = key.to_string();
-        let content = content.to_string();
-        let category = Self::category_to_str(&category);
-        let sid = session_id.map(str::to_string);
-        let mut client = self.client.clone().lock_owned().await;
-
-        tokio::task::spawn_blocking(move || -> Result<()> {
-            let now = Utc::now();
-            let stmt = format!(
-                "
-                INSERT INTO {qualified_table}
-                    (id, key, content, category, created_at, updated_at, session_id)
-                VALUES
-                    ($1, $2, $3, $4, $5, $6, $7)
-                ON CONFLICT (key) DO UPDATE SET
-                    content = EXCLUDED.content,
-                    category = EXCLUDED.category,
-                    updated_at = EXCLUDED.updated_at,
-                    session_id = EXCLUDED.session_id
-                "
-            );
-
-            let id = Uuid::new_v4().to_string();
-            client.execute(&stmt, &[&id, &key, &content, &category, &now, &now, &sid])?;
-            Ok(())
-        })
-        .await?
-    }
-
-    async fn recall(
+    /// Keyword search over `namespace` (if any) and the shared scope.
+    async fn recall_scoped(
         &self,
+        namespace: Option<&str>,
         query: &str,
         limit: usize,
         session_id: Option<&str>,
@@ -294,6 +348,7 @@ impl Memory for PostgresMemory {
         let qualified_table = self.qualified_table.clone();
         let query = query.trim().to_string();
         let sid = session_id.map(str::to_string);
+        let namespace = namespace.unwrap_or_default().to_string();
         let mut client = self.client.clone().lock_owned().await;
 
         tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
@@ -303,10 +358,13 @@ impl Memory for PostgresMemory {
                        (
                          CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
                          CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
-                       ) AS score
+                       ) AS score,
+                       metadata, namespace
                 FROM {qualified_table}
                 WHERE ($2::TEXT IS NULL OR session_id = $2)
+                  AND namespace IN ('', $4)
                   AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
+                  AND (expires_at IS NULL OR expires_at > NOW())
                 ORDER BY score DESC, updated_at DESC
                 LIMIT $3
                 "
@@ -315,41 +373,50 @@ impl Memory for PostgresMemory {
             #[allow(clippy::cast_possible_wrap)]
             let limit_i64 = limit as i64;
 
-            let rows = client.query(&stmt, &[&query, &sid, &limit_i64])?;
-            rows.iter()
+            let rows = client.query(&stmt, &[&query, &sid, &limit_i64, &namespace])?;
+            let mut entries = rows
+                .iter()
                 .map(Self::row_to_entry)
-                .collect::<Result<Vec<MemoryEntry>>>()
+                .collect::<Result<Vec<MemoryEntry>>>()?;
+            metadata::rank_by_importance(&mut entries);
+            Ok(entries)
         })
         .await?
     }
 
-    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+    /// Get by key from exactly one namespace (`None` = shared scope).
+    async fn get_scoped(&self, namespace: Option<&str>, key: &str) -> Result<Option<MemoryEntry>> {
         let qualified_table = self.qualified_table.clone();
+        let namespace = namespace.unwrap_or_default().to_string();
         let key = key.to_string();
         let mut client = self.client.clone().lock_owned().await;
 
         tokio::task::spawn_blocking(move || -> Result<Option<MemoryEntry>> {
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id
+                SELECT id, key, content, category, created_at, session_id, metadata, namespace
                 FROM {qualified_table}
-                WHERE key = $1
+                WHERE namespace = $2 AND key = $1
+                  AND (expires_at IS NULL OR expires_at > NOW())
                 LIMIT 1
                 "
             );
 
-            let row = client.query_opt(&stmt, &[&key])?;
+            let row = client.query_opt(&stmt, &[&key, &namespace])?;
             row.as_ref().map(Self::row_to_entry).transpose()
         })
         .await?
     }
 
-    async fn list(
+    /// List entries of exactly one namespace (`None` = shared scope).
+    async fn list_scoped(
         &self,
+        namespace: Option<&str>,
         category: Option<&MemoryCategory>,
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
         let qualified_table = self.qualified_table.clone();
+        let namespace = namespace.unwrap_or_default().to_string();
         let category = category.map(Self::category_to_str);
         let sid = session_id.map(str::to_string);
         let mut client = self.client.clone().lock_owned().await;
@@ -357,17 +424,19 @@ impl Memory for PostgresMemory {
         tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id
+                SELECT id, key, content, category, created_at, session_id, metadata, namespace
                 FROM {qualified_table}
                 WHERE ($1::TEXT IS NULL OR category = $1)
                   AND ($2::TEXT IS NULL OR session_id = $2)
+                  AND namespace = $3
+                  AND (expires_at IS NULL OR expires_at > NOW())
                 ORDER BY updated_at DESC
                 "
             );
 
             let category_ref = category.as_deref();
             let session_ref = sid.as_deref();
-            let rows = client.query(&stmt, &[&category_ref, &session_ref])?;
+            let rows = client.query(&stmt, &[&category_ref, &session_ref, &namespace])?;
             rows.iter()
                 .map(Self::row_to_entry)
                 .collect::<Result<Vec<MemoryEntry>>>()
@@ -375,18 +444,172 @@ impl Memory for PostgresMemory {
         .await?
     }
 
-    async fn forget(&self, key: &str) -> Result<bool> {
+    /// Delete by key from exactly one namespace (`None` = shared scope).
+    async fn forget_scoped(&self, namespace: Option<&str>, key: &str) -> Result<bool> {
         let qualified_table = self.qualified_table.clone();
+        let namespace = namespace.unwrap_or_default().to_string();
         let key = key.to_string();
         let mut client = self.client.clone().lock_owned().await;
 
         tokio::task::spawn_blocking(move || -> Result<bool> {
-            let stmt = format!("DELETE FROM {qualified_table} WHERE key = $1");
-            let deleted = client.execute(&stmt, &[&key])?;
+            let stmt = format!("DELETE FROM {qualified_table} WHERE namespace = $2 AND key = $1");
+            let deleted = client.execute(&stmt, &[&key, &namespace])?;
             Ok(deleted > 0)
         })
         .await?
     }
+}
+
+fn validate_identifier(value: &str, field_name: &str) -> Result<()> {
+    if value.is_empty() {
+        anyhow::bail!("{field_name} must not be empty");
+    }
+
+    let mut chars = value.chars();
+    let Some(first) = chars.next() else {
+        anyhow::bail!("{field_name} must not be empty");
+    };
+
+    if !(first.is_ascii_alphabetic() || first == '_') {
+        anyhow::bail!("{field_name} must start with an ASCII letter or underscore; got '{value}'");
+    }
+
+    if !chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_') {
+        anyhow::bail!(
+            "{field_name} can only contain ASCII letters, numbers, and underscores; got '{value}'"
+        );
+    }
+
+    Ok(())
+}
+
+fn quote_identifier(value: &str) -> String {
+    format!("\"{value}\"")
+}
+
+#[async_trait]
+impl Memory for PostgresMemory {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        self.write_entry(None, key, content, &category, session_id, None)
+            .await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> Result<()> {
+        self.write_entry(
+            None,
+            key,
+            content,
+            &category,
+            session_id,
+            Some(metadata.clone()),
+        )
+        .await
+    }
+
+    async fn store_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> Result<()> {
+        self.write_entry(
+            namespace,
+            key,
+            content,
+            &category,
+            session_id,
+            Some(metadata.clone()),
+        )
+        .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.recall_scoped(None, query, limit, session_id).await
+    }
+
+    async fn recall_in(
+        &self,
+        namespace: Option<&str>,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.recall_scoped(namespace, query, limit, session_id)
+            .await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        self.get_scoped(None, key).await
+    }
+
+    async fn get_in(&self, namespace: Option<&str>, key: &str) -> Result<Option<MemoryEntry>> {
+        self.get_scoped(namespace, key).await
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.list_scoped(None, category, session_id).await
+    }
+
+    async fn list_in(
+        &self,
+        namespace: Option<&str>,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.list_scoped(namespace, category, session_id).await
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        self.forget_scoped(None, key).await
+    }
+
+    async fn forget_in(&self, namespace: Option<&str>, key: &str) -> Result<bool> {
+        self.forget_scoped(namespace, key).await
+    }
+
+    async fn namespaces(&self) -> Result<Vec<String>> {
+        let qualified_table = self.qualified_table.clone();
+        let mut client = self.client.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<String>> {
+            let stmt = format!(
+                "SELECT DISTINCT namespace FROM {qualified_table}
+                 WHERE namespace <> '' ORDER BY namespace"
+            );
+            let rows = client.query(&stmt, &[])?;
+            Ok(rows.iter().map(|row| row.get(0)).collect())
+        })
+        .await?
+    }
 
     async fn count(&self) -> Result<usize> {
         let qualified_table = self.qualified_table.clone();
@@ -408,6 +631,92 @@ impl Memory for PostgresMemory {
             .await
             .unwrap_or(false)
     }
+
+    async fn purge_expired(&self) -> Result<usize> {
+        let qualified_table = self.qualified_table.clone();
+        let mut client = self.client.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> Result<usize> {
+            let stmt = format!("DELETE FROM {qualified_table} WHERE expires_at <= NOW()");
+            let deleted = client.execute(&stmt, &[])?;
+            Ok(usize::try_from(deleted).unwrap_or(usize::MAX))
+        })
+        .await?
+    }
+
+    async fn list_page(&self, offset: usize, limit: usize) -> Result<Vec<MemoryEntry>> {
+        let qualified_table = self.qualified_table.clone();
+        let offset = i64::try_from(offset).context("memory page offset is too large")?;
+        let limit = i64::try_from(limit).context("memory page limit is too large")?;
+        let mut client = self.client.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> Result<Vec<MemoryEntry>> {
+            let stmt = format!(
+                "
+                SELECT id, key, content, category, created_at, session_id, metadata, namespace
+                FROM {qualified_table}
+                WHERE expires_at IS NULL OR expires_at > NOW()
+                ORDER BY created_at ASC, key ASC
+                LIMIT $1 OFFSET $2
+                "
+            );
+
+            let rows = client.query(&stmt, &[&limit, &offset])?;
+            rows.iter()
+                .map(Self::row_to_entry)
+                .collect::<Result<Vec<MemoryEntry>>>()
+        })
+        .await?
+    }
+
+    async fn import(&self, entry: &MemoryEntry, _embedding: Option<&[f32]>) -> Result<()> {
+        let qualified_table = self.qualified_table.clone();
+        let created_at = DateTime::parse_from_rfc3339(&entry.timestamp)
+            .map(|at| at.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        let category = Self::category_to_str(&entry.category);
+        let namespace = entry.namespace.clone().unwrap_or_default();
+        let entry = entry.clone();
+        let mut client = self.client.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let stmt = format!(
+                "
+                INSERT INTO {qualified_table}
+                    (id, key, content, category, created_at, updated_at, session_id, metadata, expires_at, namespace)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (namespace, key) DO UPDATE SET
+                    content = EXCLUDED.content,
+                    category = EXCLUDED.category,
+                    created_at = EXCLUDED.created_at,
+                    updated_at = EXCLUDED.updated_at,
+                    session_id = EXCLUDED.session_id,
+                    metadata = EXCLUDED.metadata,
+                    expires_at = EXCLUDED.expires_at
+                "
+            );
+
+            let id = Uuid::new_v4().to_string();
+            client.execute(
+                &stmt,
+                &[
+                    &id,
+                    &entry.key,
+                    &entry.content,
+                    &category,
+                    &created_at,
+                    &created_at,
+                    &entry.session_id,
+                    &entry.metadata.to_column(),
+                    &entry.metadata.expires_at_utc(),
+                    &namespace,
+                ],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
 }
 
 #[cfg(test)]
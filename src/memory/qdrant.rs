@@ -1,4 +1,5 @@
 use super::embeddings::EmbeddingProvider;
+use super::metadata::{self, MemoryMetadata, PreviousVersion};
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -160,78 +161,16 @@ impl QdrantMemory {
         Ok(())
     }
 
-    fn category_to_str(category: &MemoryCategory) -> String {
-        match category {
-            MemoryCategory::Core => "core".to_string(),
-            MemoryCategory::Daily => "daily".to_string(),
-            MemoryCategory::Conversation => "conversation".to_string(),
-            MemoryCategory::Custom(name) => name.clone(),
-        }
-    }
-
-    fn parse_category(value: &str) -> MemoryCategory {
-        match value {
-            "core" => MemoryCategory::Core,
-            "daily" => MemoryCategory::Daily,
-            "conversation" => MemoryCategory::Conversation,
-            other => MemoryCategory::Custom(other.to_string()),
-        }
-    }
-}
-
-/// Qdrant point payload structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct MemoryPayload {
-    key: String,
-    content: String,
-    category: String,
-    timestamp: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    session_id: Option<String>,
-}
-
-/// Qdrant search result
-#[derive(Debug, Deserialize)]
-struct QdrantSearchResult {
-    result: Vec<QdrantScoredPoint>,
-}
-
-#[derive(Debug, Deserialize)]
-struct QdrantScoredPoint {
-    id: serde_json::Value,
-    score: f64,
-    payload: Option<MemoryPayload>,
-}
-
-/// Qdrant scroll result
-#[derive(Debug, Deserialize)]
-struct QdrantScrollResult {
-    result: QdrantScrollPoints,
-}
-
-#[derive(Debug, Deserialize)]
-struct QdrantScrollPoints {
-    points: Vec<QdrantPoint>,
-}
-
-#[derive(Debug, Deserialize)]
-struct QdrantPoint {
-    id: serde_json::Value,
-    payload: Option<MemoryPayload>,
-}
-
-#[async_trait]
-impl Memory for QdrantMemory {
-    fn name(&self) -> &str {
-        "qdrant"
-    }
-
-    async fn store(
+    /// Embed and store a fresh write, carrying the previous version into
+    /// the update history. `metadata: None` keeps the existing metadata.
+    async fn write_point(
         &self,
+        namespace: Option<&str>,
         key: &str,
         content: &str,
         category: MemoryCategory,
         session_id: Option<&str>,
+        metadata: Option<&MemoryMetadata>,
     ) -> Result<()> {
         self.ensure_initialized().await?;
 
@@ -243,24 +182,40 @@ impl Memory for QdrantMemory {
             anyhow::bail!("Qdrant requires non-zero dimensional embeddings");
         }
 
-        let id = Uuid::new_v4().to_string();
-        let timestamp = Utc::now().to_rfc3339();
+        let previous = self.get_scoped(namespace, key).await?;
+        let metadata = MemoryMetadata::for_write(
+            previous.as_ref().map(|entry| PreviousVersion {
+                content: &entry.content,
+                updated_at: &entry.timestamp,
+                metadata: entry.metadata.clone(),
+            }),
+            metadata,
+            content,
+        );
 
         let payload = MemoryPayload {
             key: key.to_string(),
             content: content.to_string(),
             category: Self::category_to_str(&category),
-            timestamp,
+            timestamp: Utc::now().to_rfc3339(),
             session_id: session_id.map(str::to_string),
+            namespace: namespace.map(str::to_string),
+            metadata,
         };
 
+        self.upsert_point(payload, embedding).await
+    }
+
+    /// Replace the point stored under the payload's namespace and key.
+    async fn upsert_point(&self, payload: MemoryPayload, embedding: Vec<f32>) -> Result<()> {
         // Delete any existing point with the same key first
-        let _ = self.forget(key).await;
+        let _ = self
+            .forget_scoped(payload.namespace.as_deref(), &payload.key)
+            .await;
 
-        // Upsert point
         let upsert_body = serde_json::json!({
             "points": [{
-                "id": id,
+                "id": Uuid::new_v4().to_string(),
                 "vector": embedding,
                 "payload": payload
             }]
@@ -286,14 +241,59 @@ impl Memory for QdrantMemory {
         Ok(())
     }
 
-    async fn recall(
+    fn category_to_str(category: &MemoryCategory) -> String {
+        match category {
+            MemoryCategory::Core => "core".to_string(),
+            MemoryCategory::Daily => "daily".to_string(),
+            MemoryCategory::Conversation => "conversation".to_string(),
+            MemoryCategory::Custom(name) => name.clone(),
+        }
+    }
+
+    fn parse_category(value: &str) -> MemoryCategory {
+        match value {
+            "core" => MemoryCategory::Core,
+            "daily" => MemoryCategory::Daily,
+            "conversation" => MemoryCategory::Conversation,
+            other => MemoryCategory::Custom(other.to_string()),
+        }
+    }
+
+    /// Filter condition matching exactly one namespace. Shared-scope points
+    /// carry no `namespace` field at all.
+    fn namespace_condition(namespace: Option<&str>) -> serde_json::Value {
+        match namespace {
+            None => serde_json::json!({ "is_empty": { "key": "namespace" } }),
+            Some(ns) => serde_json::json!({
+                "key": "namespace",
+                "match": { "value": ns }
+            }),
+        }
+    }
+
+    /// Filter condition matching `namespace` and the shared scope.
+    fn recall_condition(namespace: Option<&str>) -> serde_json::Value {
+        match namespace {
+            None => Self::namespace_condition(None),
+            Some(_) => serde_json::json!({
+                "should": [
+                    Self::namespace_condition(None),
+                    Self::namespace_condition(namespace)
+                ]
+            }),
+        }
+    }
+
+    /// Vector search over `namespace` (if any) and the shared scope.
+    async fn recall_scoped(
         &self,
+        namespace: Option<&str>,
         query: &str,
         limit: usize,
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
         if query.trim().is_empty() {
-            return self.list(None, session_id).await;
+            return self.list_visible(namespace, session_id).await;
         }
 
         self.ensure_initialized().await?;
@@ -303,29 +303,25 @@ impl Memory for QdrantMemory {
 
         if embedding.is_empty() {
             // Fallback to listing if embeddings aren't available
-            return self.list(None, session_id).await;
+            return self.list_visible(namespace, session_id).await;
         }
 
-        // Build filter for session_id if provided
-        let filter = session_id.map(|sid| {
-            serde_json::json!({
-                "must": [{
-                    "key": "session_id",
-                    "match": { "value": sid }
-                }]
-            })
-        });
+        // Build filter for namespace and session_id if provided
+        let mut must_conditions = vec![Self::recall_condition(namespace)];
+        if let Some(sid) = session_id {
+            must_conditions.push(serde_json::json!({
+                "key": "session_id",
+                "match": { "value": sid }
+            }));
+        }
 
-        let mut search_body = serde_json::json!({
+        let search_body = serde_json::json!({
             "vector": embedding,
             "limit": limit,
-            "with_payload": true
+            "with_payload": true,
+            "filter": { "must": must_conditions }
         });
 
-        if let Some(f) = filter {
-            search_body["filter"] = f;
-        }
-
         let resp = self
             .request(
                 reqwest::Method::POST,
@@ -344,7 +340,7 @@ impl Memory for QdrantMemory {
 
         let result: QdrantSearchResult = resp.json().await?;
 
-        let entries = result
+        let mut entries: Vec<MemoryEntry> = result
             .result
             .into_iter()
             .filter_map(|point| {
@@ -363,23 +359,28 @@ impl Memory for QdrantMemory {
                     timestamp: payload.timestamp,
                     session_id: payload.session_id,
                     score: Some(point.score),
+                    metadata: payload.metadata,
+                    namespace: payload.namespace,
                 })
             })
+            .filter(|entry| !entry.metadata.is_expired())
             .collect();
 
+        metadata::rank_by_importance(&mut entries);
         Ok(entries)
     }
 
-    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+    /// Get by key from exactly one namespace (`None` = shared scope).
+    async fn get_scoped(&self, namespace: Option<&str>, key: &str) -> Result<Option<MemoryEntry>> {
         self.ensure_initialized().await?;
 
         // Scroll with filter for exact key match
         let scroll_body = serde_json::json!({
             "filter": {
-                "must": [{
-                    "key": "key",
-                    "match": { "value": key }
-                }]
+                "must": [
+                    { "key": "key", "match": { "value": key } },
+                    Self::namespace_condition(namespace)
+                ]
             },
             "limit": 1,
             "with_payload": true
@@ -419,21 +420,25 @@ impl Memory for QdrantMemory {
                 timestamp: payload.timestamp,
                 session_id: payload.session_id,
                 score: None,
+                metadata: payload.metadata,
+                namespace: payload.namespace,
             })
         });
 
-        Ok(entry)
+        Ok(entry.filter(|entry| !entry.metadata.is_expired()))
     }
 
-    async fn list(
+    /// List entries of exactly one namespace (`None` = shared scope).
+    async fn list_scoped(
         &self,
+        namespace: Option<&str>,
         category: Option<&MemoryCategory>,
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
         self.ensure_initialized().await?;
 
         // Build filter conditions
-        let mut must_conditions = Vec::new();
+        let mut must_conditions = vec![Self::namespace_condition(namespace)];
 
         if let Some(cat) = category {
             must_conditions.push(serde_json::json!({
@@ -449,15 +454,12 @@ impl Memory for QdrantMemory {
             }));
         }
 
-        let mut scroll_body = serde_json::json!({
+        let scroll_body = serde_json::json!({
             "limit": 1000,
-            "with_payload": true
+            "with_payload": true,
+            "filter": { "must": must_conditions }
         });
 
-        if !must_conditions.is_empty() {
-            scroll_body["filter"] = serde_json::json!({ "must": must_conditions });
-        }
-
         let resp = self
             .request(
                 reqwest::Method::POST,
@@ -496,23 +498,40 @@ impl Memory for QdrantMemory {
                     timestamp: payload.timestamp,
                     session_id: payload.session_id,
                     score: None,
+                    metadata: payload.metadata,
+                    namespace: payload.namespace,
                 })
             })
+            .filter(|entry| !entry.metadata.is_expired())
             .collect();
 
         Ok(entries)
     }
 
-    async fn forget(&self, key: &str) -> Result<bool> {
+    /// Everything `namespace` can see: its own entries, then shared ones.
+    async fn list_visible(
+        &self,
+        namespace: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let mut entries = self.list_scoped(namespace, None, session_id).await?;
+        if namespace.is_some() {
+            entries.extend(self.list_scoped(None, None, session_id).await?);
+        }
+        Ok(entries)
+    }
+
+    /// Delete by key from exactly one namespace (`None` = shared scope).
+    async fn forget_scoped(&self, namespace: Option<&str>, key: &str) -> Result<bool> {
         self.ensure_initialized().await?;
 
         // Delete points matching the key
         let delete_body = serde_json::json!({
             "filter": {
-                "must": [{
-                    "key": "key",
-                    "match": { "value": key }
-                }]
+                "must": [
+                    { "key": "key", "match": { "value": key } },
+                    Self::namespace_condition(namespace)
+                ]
             }
         });
 
@@ -536,6 +555,191 @@ impl Memory for QdrantMemory {
         // Qdrant doesn't return deleted count easily, assume success
         Ok(true)
     }
+}
+
+/// Qdrant point payload structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemoryPayload {
+    key: String,
+    content: String,
+    category: String,
+    timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "MemoryMetadata::is_empty")]
+    metadata: MemoryMetadata,
+}
+
+/// Qdrant search result
+#[derive(Debug, Deserialize)]
+struct QdrantSearchResult {
+    result: Vec<QdrantScoredPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantScoredPoint {
+    id: serde_json::Value,
+    score: f64,
+    payload: Option<MemoryPayload>,
+}
+
+/// Qdrant scroll result
+#[derive(Debug, Deserialize)]
+struct QdrantScrollResult {
+    result: QdrantScrollPoints,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantScrollPoints {
+    points: Vec<QdrantPoint>,
+}
+
+#[derive(Debug, Deserialize)]
+struct QdrantPoint {
+    id: serde_json::Value,
+    payload: Option<MemoryPayload>,
+}
+
+#[async_trait]
+impl Memory for QdrantMemory {
+    fn name(&self) -> &str {
+        "qdrant"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        self.write_point(None, key, content, category, session_id, None)
+            .await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> Result<()> {
+        self.write_point(None, key, content, category, session_id, Some(metadata))
+            .await
+    }
+
+    async fn store_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> Result<()> {
+        self.write_point(
+            namespace,
+            key,
+            content,
+            category,
+            session_id,
+            Some(metadata),
+        )
+        .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.recall_scoped(None, query, limit, session_id).await
+    }
+
+    async fn recall_in(
+        &self,
+        namespace: Option<&str>,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.recall_scoped(namespace, query, limit, session_id)
+            .await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        self.get_scoped(None, key).await
+    }
+
+    async fn get_in(&self, namespace: Option<&str>, key: &str) -> Result<Option<MemoryEntry>> {
+        self.get_scoped(namespace, key).await
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.list_scoped(None, category, session_id).await
+    }
+
+    async fn list_in(
+        &self,
+        namespace: Option<&str>,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        self.list_scoped(namespace, category, session_id).await
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        self.forget_scoped(None, key).await
+    }
+
+    async fn forget_in(&self, namespace: Option<&str>, key: &str) -> Result<bool> {
+        self.forget_scoped(namespace, key).await
+    }
+
+    async fn namespaces(&self) -> Result<Vec<String>> {
+        self.ensure_initialized().await?;
+
+        let scroll_body = serde_json::json!({
+            "limit": 10_000,
+            "with_payload": true,
+            "filter": { "must_not": [Self::namespace_condition(None)] }
+        });
+
+        let resp = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/collections/{}/points/scroll", self.collection),
+            )
+            .json(&scroll_body)
+            .send()
+            .await
+            .context("failed to scroll Qdrant")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Qdrant scroll failed ({status}): {text}");
+        }
+
+        let result: QdrantScrollResult = resp.json().await?;
+        let mut namespaces: Vec<String> = result
+            .result
+            .points
+            .into_iter()
+            .filter_map(|point| point.payload?.namespace)
+            .collect();
+        namespaces.sort();
+        namespaces.dedup();
+        Ok(namespaces)
+    }
 
     async fn count(&self) -> Result<usize> {
         self.ensure_initialized().await?;
@@ -573,6 +777,86 @@ impl Memory for QdrantMemory {
 
         matches!(resp, Ok(r) if r.status().is_success())
     }
+
+    async fn list_page(&self, offset: usize, limit: usize) -> Result<Vec<MemoryEntry>> {
+        self.ensure_initialized().await?;
+
+        // Scroll returns points in id order; skip the earlier pages locally.
+        let scroll_body = serde_json::json!({
+            "limit": offset + limit,
+            "with_payload": true
+        });
+
+        let resp = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/collections/{}/points/scroll", self.collection),
+            )
+            .json(&scroll_body)
+            .send()
+            .await
+            .context("failed to scroll Qdrant")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Qdrant scroll failed ({status}): {text}");
+        }
+
+        let result: QdrantScrollResult = resp.json().await?;
+
+        Ok(result
+            .result
+            .points
+            .into_iter()
+            .skip(offset)
+            .filter_map(|point| {
+                let payload = point.payload?;
+                let id = match &point.id {
+                    serde_json::Value::String(s) => s.clone(),
+                    serde_json::Value::Number(n) => n.to_string(),
+                    _ => return None,
+                };
+
+                Some(MemoryEntry {
+                    id,
+                    key: payload.key,
+                    content: payload.content,
+                    category: Self::parse_category(&payload.category),
+                    timestamp: payload.timestamp,
+                    session_id: payload.session_id,
+                    score: None,
+                    metadata: payload.metadata,
+                    namespace: payload.namespace,
+                })
+            })
+            .collect())
+    }
+
+    async fn import(&self, entry: &MemoryEntry, _embedding: Option<&[f32]>) -> Result<()> {
+        self.ensure_initialized().await?;
+
+        // Points embed "key\ncontent", so vectors exported from other
+        // backends do not fit; always embed afresh.
+        let combined_text = format!("{}\n{}", entry.key, entry.content);
+        let embedding = self.embedder.embed_one(&combined_text).await?;
+
+        if embedding.is_empty() {
+            anyhow::bail!("Qdrant requires non-zero dimensional embeddings");
+        }
+
+        let payload = MemoryPayload {
+            key: entry.key.clone(),
+            content: entry.content.clone(),
+            category: Self::category_to_str(&entry.category),
+            timestamp: entry.timestamp.clone(),
+            session_id: entry.session_id.clone(),
+            namespace: entry.namespace.clone(),
+            metadata: entry.metadata.clone(),
+        };
+
+        self.upsert_point(payload, embedding).await
+    }
 }
 
 #[cfg(test)]
@@ -618,6 +902,8 @@ mod tests {
             category: "core".into(),
             timestamp: "2026-02-20T00:00:00Z".into(),
             session_id: Some("session-1".into()),
+            namespace: None,
+            metadata: MemoryMetadata::default(),
         };
 
         let json = serde_json::to_string(&payload).unwrap();
@@ -634,9 +920,12 @@ mod tests {
             category: "core".into(),
             timestamp: "2026-02-20T00:00:00Z".into(),
             session_id: None,
+            namespace: None,
+            metadata: MemoryMetadata::default(),
         };
 
         let json = serde_json::to_string(&payload).unwrap();
         assert!(!json.contains("session_id"));
+        assert!(!json.contains("metadata"));
     }
 }
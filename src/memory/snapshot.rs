@@ -35,12 +35,23 @@ pub fn export_snapshot(workspace_dir: &Path) -> Result<usize> {
     let conn = Connection::open(&db_path)?;
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
 
-    let mut stmt = conn.prepare(
+    // Person namespaces stay out of the snapshot, which anyone with the
+    // workspace can read. Databases not yet opened by this version have no
+    // namespace column and hold shared entries only.
+    let has_namespace = conn
+        .prepare("SELECT 1 FROM pragma_table_info('memories') WHERE name = 'namespace'")?
+        .exists([])?;
+    let scope = if has_namespace {
+        " AND namespace = ''"
+    } else {
+        ""
+    };
+    let mut stmt = conn.prepare(&format!(
         "SELECT key, content, category, created_at, updated_at
          FROM memories
-         WHERE category = 'core'
-         ORDER BY updated_at DESC",
-    )?;
+         WHERE category = 'core'{scope}
+         ORDER BY updated_at DESC"
+    ))?;
 
     let rows: Vec<(String, String, String, String, String)> = stmt
         .query_map([], |row| {
@@ -1,4 +1,5 @@
 use super::embeddings::EmbeddingProvider;
+use super::metadata::{self, MemoryMetadata, PreviousVersion};
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use super::vector;
 use anyhow::Context;
@@ -14,6 +15,27 @@ use std::time::Duration;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
+/// Columns read by [`SqliteMemory::row_to_entry`], in order.
+const ENTRY_COLUMNS: &str =
+    "id, key, content, category, created_at, session_id, metadata, namespace";
+
+/// FTS5 sync triggers, recreated whenever the `memories` table is rebuilt.
+const FTS_TRIGGERS: &str = "
+    CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
+        INSERT INTO memories_fts(rowid, key, content)
+        VALUES (new.rowid, new.key, new.content);
+    END;
+    CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
+        INSERT INTO memories_fts(memories_fts, rowid, key, content)
+        VALUES ('delete', old.rowid, old.key, old.content);
+    END;
+    CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
+        INSERT INTO memories_fts(memories_fts, rowid, key, content)
+        VALUES ('delete', old.rowid, old.key, old.content);
+        INSERT INTO memories_fts(rowid, key, content)
+        VALUES (new.rowid, new.key, new.content);
+    END;";
+
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
@@ -145,22 +167,6 @@ impl SqliteMemory {
                 key, content, content=memories, content_rowid=rowid
             );
 
-            -- FTS5 triggers: keep in sync with memories table
-            CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-
             -- Embedding cache with LRU eviction
             CREATE TABLE IF NOT EXISTS embedding_cache (
                 content_hash TEXT PRIMARY KEY,
@@ -170,6 +176,8 @@ impl SqliteMemory {
             );
             CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
         )?;
+        // FTS5 triggers: keep in sync with memories table
+        conn.execute_batch(FTS_TRIGGERS)?;
 
         // Migration: add session_id column if not present (safe to run repeatedly)
         let has_session_id: bool = conn
@@ -183,9 +191,186 @@ impl SqliteMemory {
             )?;
         }
 
+        // Migration: add metadata + expiry columns if not present
+        let has_metadata: bool = conn
+            .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
+            .query_row([], |row| row.get::<_, String>(0))?
+            .contains("expires_at");
+        if !has_metadata {
+            conn.execute_batch(
+                "ALTER TABLE memories ADD COLUMN metadata TEXT;
+                 ALTER TABLE memories ADD COLUMN expires_at TEXT;
+                 CREATE INDEX IF NOT EXISTS idx_memories_expires ON memories(expires_at);",
+            )?;
+        }
+
+        // Migration: per-person namespaces. Keys become unique per namespace,
+        // which SQLite can only express by rebuilding the table. Rowids are
+        // kept so the external-content FTS index stays valid.
+        let has_namespace: bool = conn
+            .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
+            .query_row([], |row| row.get::<_, String>(0))?
+            .contains("namespace");
+        if !has_namespace {
+            conn.execute_batch(&format!(
+                "BEGIN;
+                 CREATE TABLE memories_new (
+                     id          TEXT PRIMARY KEY,
+                     namespace   TEXT NOT NULL DEFAULT '',
+                     key         TEXT NOT NULL,
+                     content     TEXT NOT NULL,
+                     category    TEXT NOT NULL DEFAULT 'core',
+                     embedding   BLOB,
+                     created_at  TEXT NOT NULL,
+                     updated_at  TEXT NOT NULL,
+                     session_id  TEXT,
+                     metadata    TEXT,
+                     expires_at  TEXT,
+                     UNIQUE(namespace, key)
+                 );
+                 INSERT INTO memories_new (rowid, id, key, content, category, embedding,
+                                           created_at, updated_at, session_id, metadata, expires_at)
+                     SELECT rowid, id, key, content, category, embedding,
+                            created_at, updated_at, session_id, metadata, expires_at
+                     FROM memories;
+                 DROP TABLE memories;
+                 ALTER TABLE memories_new RENAME TO memories;
+                 CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
+                 CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
+                 CREATE INDEX IF NOT EXISTS idx_memories_session ON memories(session_id);
+                 CREATE INDEX IF NOT EXISTS idx_memories_expires ON memories(expires_at);
+                 {FTS_TRIGGERS}
+                 COMMIT;"
+            ))?;
+        }
+
         Ok(())
     }
 
+    /// Map a row selected with [`ENTRY_COLUMNS`].
+    fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<MemoryEntry> {
+        Ok(MemoryEntry {
+            id: row.get(0)?,
+            key: row.get(1)?,
+            content: row.get(2)?,
+            category: Self::str_to_category(&row.get::<_, String>(3)?),
+            timestamp: row.get(4)?,
+            session_id: row.get(5)?,
+            score: None,
+            metadata: MemoryMetadata::from_column(row.get::<_, Option<String>>(6)?.as_deref()),
+            namespace: Some(row.get::<_, String>(7)?).filter(|ns| !ns.is_empty()),
+        })
+    }
+
+    /// Upsert an entry, recording the overwritten content in its history.
+    /// `metadata: None` keeps the entry's existing tags, importance, expiry
+    /// and source. An import passes the original `timestamp` and has its
+    /// metadata stored as-is; otherwise the write is stamped with the
+    /// current time.
+    async fn write_entry(
+        &self,
+        entry: MemoryEntry,
+        metadata: Option<MemoryMetadata>,
+        embedding: Option<Vec<f32>>,
+        timestamp: Option<String>,
+    ) -> anyhow::Result<()> {
+        let embedding_bytes = embedding.map(|emb| vector::vec_to_bytes(&emb));
+        let conn = self.conn.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let now = Local::now().to_rfc3339();
+            let cat = Self::category_to_str(&entry.category);
+            let namespace = entry.namespace.clone().unwrap_or_default();
+            let id = Uuid::new_v4().to_string();
+
+            let timestamp_given = timestamp.is_some();
+            let metadata = if timestamp_given {
+                metadata.unwrap_or_default()
+            } else {
+                let previous: Option<(String, String, Option<String>)> = conn
+                    .query_row(
+                        "SELECT content, updated_at, metadata FROM memories
+                         WHERE namespace = ?1 AND key = ?2",
+                        params![namespace, entry.key],
+                        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                    )
+                    .ok();
+                MemoryMetadata::for_write(
+                    previous
+                        .as_ref()
+                        .map(|(content, updated_at, metadata)| PreviousVersion {
+                            content,
+                            updated_at,
+                            metadata: MemoryMetadata::from_column(metadata.as_deref()),
+                        }),
+                    metadata.as_ref(),
+                    &entry.content,
+                )
+            };
+            let expires_at = metadata
+                .expires_at_utc()
+                .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+            let created_at = timestamp.clone().unwrap_or_else(|| now.clone());
+            let updated_at = timestamp.unwrap_or(now);
+
+            conn.execute(
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id, metadata, expires_at, namespace)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?12)
+                 ON CONFLICT(namespace, key) DO UPDATE SET
+                    content = excluded.content,
+                    category = excluded.category,
+                    embedding = excluded.embedding,
+                    created_at = CASE WHEN ?11 THEN excluded.created_at ELSE memories.created_at END,
+                    updated_at = excluded.updated_at,
+                    session_id = excluded.session_id,
+                    metadata = excluded.metadata,
+                    expires_at = excluded.expires_at",
+                params![
+                    id,
+                    entry.key,
+                    entry.content,
+                    cat,
+                    embedding_bytes,
+                    created_at,
+                    updated_at,
+                    entry.session_id,
+                    metadata.to_column(),
+                    expires_at,
+                    timestamp_given,
+                    namespace
+                ],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
+
+    /// Store a fresh write (current time, computed embedding).
+    async fn write_new(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: Option<MemoryMetadata>,
+    ) -> anyhow::Result<()> {
+        // Compute embedding (async, before blocking work)
+        let embedding = self.get_or_compute_embedding(content).await?;
+        let entry = MemoryEntry {
+            id: String::new(),
+            key: key.to_string(),
+            content: content.to_string(),
+            category,
+            timestamp: String::new(),
+            session_id: session_id.map(String::from),
+            score: None,
+            metadata: MemoryMetadata::default(),
+            namespace: namespace.map(String::from),
+        };
+        self.write_entry(entry, metadata, embedding, None).await
+    }
+
     fn category_to_str(cat: &MemoryCategory) -> String {
         match cat {
             MemoryCategory::Core => "core".into(),
@@ -282,10 +467,11 @@ impl SqliteMemory {
         Ok(Some(embedding))
     }
 
-    /// FTS5 BM25 keyword search
+    /// FTS5 BM25 keyword search over `namespace` and the shared scope
     fn fts5_search(
         conn: &Connection,
         query: &str,
+        namespace: &str,
         limit: usize,
     ) -> anyhow::Result<Vec<(String, f32)>> {
         // Escape FTS5 special chars and build query
@@ -302,7 +488,7 @@ impl SqliteMemory {
         let sql = "SELECT m.id, bm25(memories_fts) as score
                    FROM memories_fts f
                    JOIN memories m ON m.rowid = f.rowid
-                   WHERE memories_fts MATCH ?1
+                   WHERE memories_fts MATCH ?1 AND m.namespace IN ('', ?3)
                    ORDER BY score
                    LIMIT ?2";
 
@@ -310,7 +496,7 @@ impl SqliteMemory {
         #[allow(clippy::cast_possible_wrap)]
         let limit_i64 = limit as i64;
 
-        let rows = stmt.query_map(params![fts_query, limit_i64], |row| {
+        let rows = stmt.query_map(params![fts_query, limit_i64, namespace], |row| {
             let id: String = row.get(0)?;
             let score: f64 = row.get(1)?;
             // BM25 returns negative scores (lower = better), negate for ranking
@@ -327,18 +513,23 @@ impl SqliteMemory {
 
     /// Vector similarity search: scan embeddings and compute cosine similarity.
     ///
-    /// Optional `category` and `session_id` filters reduce full-table scans
-    /// when the caller already knows the scope of relevant memories.
+    /// Only `namespace` and the shared scope are scanned. Optional `category`
+    /// and `session_id` filters reduce full-table scans when the caller
+    /// already knows the scope of relevant memories.
     fn vector_search(
         conn: &Connection,
         query_embedding: &[f32],
+        namespace: &str,
         limit: usize,
         category: Option<&str>,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<(String, f32)>> {
-        let mut sql = "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL".to_string();
-        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
-        let mut idx = 1;
+        let mut sql = "SELECT id, embedding FROM memories
+                       WHERE embedding IS NOT NULL AND namespace IN ('', ?1)"
+            .to_string();
+        let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> =
+            vec![Box::new(namespace.to_string())];
+        let mut idx = 2;
 
         if let Some(cat) = category {
             let _ = write!(sql, " AND category = ?{idx}");
@@ -423,55 +614,11 @@ impl SqliteMemory {
 
         Ok(count)
     }
-}
-
-#[async_trait]
-impl Memory for SqliteMemory {
-    fn name(&self) -> &str {
-        "sqlite"
-    }
-
-    async fn store(
-        &self,
-        key: &str,
-        content: &str,
-        category: MemoryCategory,
-        session_id: Option<&str>,
-    ) -> anyhow::Result<()> {
-        // Compute embedding (async, before blocking work)
-        let embedding_bytes = self
-            .get_or_compute_embedding(content)
-            .await?
-            .map(|emb| vector::vec_to_bytes(&emb));
-
-        let conn = self.conn.clone().lock_owned().await;
-        let key = key.to_string();
-        let content = content.to_string();
-        let sid = session_id.map(String::from);
-
-        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-            let now = Local::now().to_rfc3339();
-            let cat = Self::category_to_str(&category);
-            let id = Uuid::new_v4().to_string();
-
-            conn.execute(
-                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-                 ON CONFLICT(key) DO UPDATE SET
-                    content = excluded.content,
-                    category = excluded.category,
-                    embedding = excluded.embedding,
-                    updated_at = excluded.updated_at,
-                    session_id = excluded.session_id",
-                params![id, key, content, cat, embedding_bytes, now, now, sid],
-            )?;
-            Ok(())
-        })
-        .await?
-    }
 
-    async fn recall(
+    /// Hybrid search over `namespace` (if any) and the shared scope.
+    async fn recall_scoped(
         &self,
+        namespace: Option<&str>,
         query: &str,
         limit: usize,
         session_id: Option<&str>,
@@ -486,6 +633,7 @@ impl Memory for SqliteMemory {
         let conn = self.conn.clone().lock_owned().await;
         let query = query.to_string();
         let sid = session_id.map(String::from);
+        let namespace = namespace.unwrap_or_default().to_string();
         let vector_weight = self.vector_weight;
         let keyword_weight = self.keyword_weight;
 
@@ -493,11 +641,13 @@ impl Memory for SqliteMemory {
             let session_ref = sid.as_deref();
 
             // FTS5 BM25 keyword search
-            let keyword_results = Self::fts5_search(&conn, &query, limit * 2).unwrap_or_default();
+            let keyword_results =
+                Self::fts5_search(&conn, &query, &namespace, limit * 2).unwrap_or_default();
 
             // Vector similarity search (if embeddings available)
             let vector_results = if let Some(ref qe) = query_embedding {
-                Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
+                Self::vector_search(&conn, qe, &namespace, limit * 2, None, session_ref)
+                    .unwrap_or_default()
             } else {
                 Vec::new()
             };
@@ -531,10 +681,8 @@ impl Memory for SqliteMemory {
                     .map(|i| format!("?{i}"))
                     .collect::<Vec<_>>()
                     .join(", ");
-                let sql = format!(
-                    "SELECT id, key, content, category, created_at, session_id \
-                     FROM memories WHERE id IN ({placeholders})"
-                );
+                let sql =
+                    format!("SELECT {ENTRY_COLUMNS} FROM memories WHERE id IN ({placeholders})");
                 let mut stmt = conn.prepare(&sql)?;
                 let id_params: Vec<Box<dyn rusqlite::types::ToSql>> = merged
                     .iter()
@@ -542,34 +690,17 @@ impl Memory for SqliteMemory {
                     .collect();
                 let params_ref: Vec<&dyn rusqlite::types::ToSql> =
                     id_params.iter().map(AsRef::as_ref).collect();
-                let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                    Ok((
-                        row.get::<_, String>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, String>(3)?,
-                        row.get::<_, String>(4)?,
-                        row.get::<_, Option<String>>(5)?,
-                    ))
-                })?;
+                let rows = stmt.query_map(params_ref.as_slice(), Self::row_to_entry)?;
 
                 let mut entry_map = std::collections::HashMap::new();
                 for row in rows {
-                    let (id, key, content, cat, ts, sid) = row?;
-                    entry_map.insert(id, (key, content, cat, ts, sid));
+                    let entry = row?;
+                    entry_map.insert(entry.id.clone(), entry);
                 }
 
                 for scored in &merged {
-                    if let Some((key, content, cat, ts, sid)) = entry_map.remove(&scored.id) {
-                        let entry = MemoryEntry {
-                            id: scored.id.clone(),
-                            key,
-                            content,
-                            category: Self::str_to_category(&cat),
-                            timestamp: ts,
-                            session_id: sid,
-                            score: Some(f64::from(scored.final_score)),
-                        };
+                    if let Some(mut entry) = entry_map.remove(&scored.id) {
+                        entry.score = Some(f64::from(scored.final_score));
                         if let Some(filter_sid) = session_ref {
                             if entry.session_id.as_deref() != Some(filter_sid) {
                                 continue;
@@ -600,10 +731,11 @@ impl Memory for SqliteMemory {
                         .collect();
                     let where_clause = conditions.join(" OR ");
                     let sql = format!(
-                        "SELECT id, key, content, category, created_at, session_id FROM memories
-                         WHERE {where_clause}
+                        "SELECT {ENTRY_COLUMNS} FROM memories
+                         WHERE ({where_clause}) AND namespace IN ('', ?{})
                          ORDER BY updated_at DESC
                          LIMIT ?{}",
+                        keywords.len() * 2 + 2,
                         keywords.len() * 2 + 1
                     );
                     let mut stmt = conn.prepare(&sql)?;
@@ -614,17 +746,13 @@ impl Memory for SqliteMemory {
                     }
                     #[allow(clippy::cast_possible_wrap)]
                     param_values.push(Box::new(limit as i64));
+                    param_values.push(Box::new(namespace.clone()));
                     let params_ref: Vec<&dyn rusqlite::types::ToSql> =
                         param_values.iter().map(AsRef::as_ref).collect();
                     let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                        Ok(MemoryEntry {
-                            id: row.get(0)?,
-                            key: row.get(1)?,
-                            content: row.get(2)?,
-                            category: Self::str_to_category(&row.get::<_, String>(3)?),
-                            timestamp: row.get(4)?,
-                            session_id: row.get(5)?,
+                        Self::row_to_entry(row).map(|entry| MemoryEntry {
                             score: Some(1.0),
+                            ..entry
                         })
                     })?;
                     for row in rows {
@@ -639,43 +767,43 @@ impl Memory for SqliteMemory {
                 }
             }
 
+            results.retain(|entry| !entry.metadata.is_expired());
+            metadata::rank_by_importance(&mut results);
             results.truncate(limit);
             Ok(results)
         })
         .await?
     }
 
-    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+    /// Get by key from exactly one namespace (`None` = shared scope).
+    async fn get_scoped(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<MemoryEntry>> {
         let conn = self.conn.clone().lock_owned().await;
         let key = key.to_string();
+        let namespace = namespace.unwrap_or_default().to_string();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Option<MemoryEntry>> {
-            let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at, session_id FROM memories WHERE key = ?1",
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM memories WHERE namespace = ?1 AND key = ?2"
+            ))?;
 
-            let mut rows = stmt.query_map(params![key], |row| {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: row.get(5)?,
-                    score: None,
-                })
-            })?;
+            let mut rows = stmt.query_map(params![namespace, key], Self::row_to_entry)?;
 
             match rows.next() {
-                Some(Ok(entry)) => Ok(Some(entry)),
+                Some(Ok(entry)) if !entry.metadata.is_expired() => Ok(Some(entry)),
                 _ => Ok(None),
             }
         })
         .await?
     }
 
-    async fn list(
+    /// List entries of exactly one namespace (`None` = shared scope).
+    async fn list_scoped(
         &self,
+        namespace: Option<&str>,
         category: Option<&MemoryCategory>,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
@@ -684,30 +812,23 @@ impl Memory for SqliteMemory {
         let conn = self.conn.clone().lock_owned().await;
         let category = category.cloned();
         let sid = session_id.map(String::from);
+        let namespace = namespace.unwrap_or_default().to_string();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let session_ref = sid.as_deref();
             let mut results = Vec::new();
 
-            let row_mapper = |row: &rusqlite::Row| -> rusqlite::Result<MemoryEntry> {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: row.get(5)?,
-                    score: None,
-                })
-            };
-
             if let Some(ref cat) = category {
                 let cat_str = Self::category_to_str(cat);
-                let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id FROM memories
-                     WHERE category = ?1 ORDER BY updated_at DESC LIMIT ?2",
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     WHERE namespace = ?3 AND category = ?1
+                     ORDER BY updated_at DESC LIMIT ?2"
+                ))?;
+                let rows = stmt.query_map(
+                    params![cat_str, DEFAULT_LIST_LIMIT, namespace],
+                    Self::row_to_entry,
                 )?;
-                let rows = stmt.query_map(params![cat_str, DEFAULT_LIST_LIMIT], row_mapper)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -718,11 +839,12 @@ impl Memory for SqliteMemory {
                     results.push(entry);
                 }
             } else {
-                let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id FROM memories
-                     ORDER BY updated_at DESC LIMIT ?1",
-                )?;
-                let rows = stmt.query_map(params![DEFAULT_LIST_LIMIT], row_mapper)?;
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     WHERE namespace = ?2 ORDER BY updated_at DESC LIMIT ?1"
+                ))?;
+                let rows =
+                    stmt.query_map(params![DEFAULT_LIST_LIMIT, namespace], Self::row_to_entry)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -734,21 +856,154 @@ impl Memory for SqliteMemory {
                 }
             }
 
+            results.retain(|entry| !entry.metadata.is_expired());
             Ok(results)
         })
         .await?
     }
 
-    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+    /// Delete by key from exactly one namespace (`None` = shared scope).
+    async fn forget_scoped(&self, namespace: Option<&str>, key: &str) -> anyhow::Result<bool> {
         let conn = self.conn.clone().lock_owned().await;
         let key = key.to_string();
+        let namespace = namespace.unwrap_or_default().to_string();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<bool> {
-            let affected = conn.execute("DELETE FROM memories WHERE key = ?1", params![key])?;
+            let affected = conn.execute(
+                "DELETE FROM memories WHERE namespace = ?1 AND key = ?2",
+                params![namespace, key],
+            )?;
             Ok(affected > 0)
         })
         .await?
     }
+}
+
+#[async_trait]
+impl Memory for SqliteMemory {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.write_new(None, key, content, category, session_id, None)
+            .await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.write_new(
+            None,
+            key,
+            content,
+            category,
+            session_id,
+            Some(metadata.clone()),
+        )
+        .await
+    }
+
+    async fn store_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.write_new(
+            namespace,
+            key,
+            content,
+            category,
+            session_id,
+            Some(metadata.clone()),
+        )
+        .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.recall_scoped(None, query, limit, session_id).await
+    }
+
+    async fn recall_in(
+        &self,
+        namespace: Option<&str>,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.recall_scoped(namespace, query, limit, session_id)
+            .await
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        self.get_scoped(None, key).await
+    }
+
+    async fn get_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<MemoryEntry>> {
+        self.get_scoped(namespace, key).await
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.list_scoped(None, category, session_id).await
+    }
+
+    async fn list_in(
+        &self,
+        namespace: Option<&str>,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.list_scoped(namespace, category, session_id).await
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.forget_scoped(None, key).await
+    }
+
+    async fn forget_in(&self, namespace: Option<&str>, key: &str) -> anyhow::Result<bool> {
+        self.forget_scoped(namespace, key).await
+    }
+
+    async fn namespaces(&self) -> anyhow::Result<Vec<String>> {
+        let conn = self.conn.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<String>> {
+            let mut stmt = conn.prepare(
+                "SELECT DISTINCT namespace FROM memories WHERE namespace != '' ORDER BY namespace",
+            )?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })
+        .await?
+    }
 
     async fn count(&self) -> anyhow::Result<usize> {
         let conn = self.conn.clone().lock_owned().await;
@@ -768,6 +1023,86 @@ impl Memory for SqliteMemory {
             .await
             .unwrap_or(false)
     }
+
+    async fn purge_expired(&self) -> anyhow::Result<usize> {
+        let conn = self.conn.clone().lock_owned().await;
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<usize> {
+            Ok(purge_expired_rows(&conn)?)
+        })
+        .await?
+    }
+
+    async fn list_page(&self, offset: usize, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self.conn.clone().lock_owned().await;
+        #[allow(clippy::cast_possible_wrap)]
+        let (offset, limit) = (offset as i64, limit as i64);
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
+            let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM memories
+                 WHERE expires_at IS NULL OR expires_at > ?3
+                 ORDER BY rowid LIMIT ?1 OFFSET ?2"
+            ))?;
+            let rows = stmt.query_map(params![limit, offset, now], Self::row_to_entry)?;
+            Ok(rows.collect::<Result<Vec<_>, _>>()?)
+        })
+        .await?
+    }
+
+    async fn embedding(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<Vec<f32>>> {
+        let conn = self.conn.clone().lock_owned().await;
+        let key = key.to_string();
+        let namespace = namespace.unwrap_or_default().to_string();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<Option<Vec<f32>>> {
+            let blob: Option<Vec<u8>> = conn
+                .query_row(
+                    "SELECT embedding FROM memories WHERE namespace = ?1 AND key = ?2",
+                    params![namespace, key],
+                    |row| row.get(0),
+                )
+                .ok()
+                .flatten();
+            Ok(blob
+                .filter(|bytes| !bytes.is_empty())
+                .map(|bytes| vector::bytes_to_vec(&bytes)))
+        })
+        .await?
+    }
+
+    async fn import(&self, entry: &MemoryEntry, embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        // Reuse the exported vector only when it fits this store's embedder;
+        // a store without an embedder keeps it as-is for later use.
+        let dimensions = self.embedder.dimensions();
+        let embedding = match embedding {
+            Some(vector) if dimensions == 0 || vector.len() == dimensions => Some(vector.to_vec()),
+            _ => self.get_or_compute_embedding(&entry.content).await?,
+        };
+        let timestamp = Some(entry.timestamp.clone());
+        self.write_entry(
+            entry.clone(),
+            Some(entry.metadata.clone()),
+            embedding,
+            timestamp,
+        )
+        .await
+    }
+}
+
+/// Delete rows whose `expires_at` has passed. Shared with memory hygiene,
+/// which opens `brain.db` directly.
+pub(crate) fn purge_expired_rows(conn: &Connection) -> rusqlite::Result<usize> {
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    conn.execute(
+        "DELETE FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1",
+        params![now],
+    )
 }
 
 #[cfg(test)]
@@ -911,6 +1246,92 @@ mod tests {
         assert_eq!(all.len(), 3);
     }
 
+    #[tokio::test]
+    async fn sqlite_metadata_tracks_history_expiry_and_importance() {
+        let (_tmp, mem) = temp_sqlite();
+        let important = MemoryMetadata {
+            tags: vec!["lang".into()],
+            importance: Some(1.0),
+            ..MemoryMetadata::default()
+        };
+        mem.store_with_metadata("a", "Rust v1", MemoryCategory::Core, None, &important)
+            .await
+            .unwrap();
+        mem.store("a", "Rust v2", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let entry = mem.get("a").await.unwrap().unwrap();
+        assert_eq!(entry.content, "Rust v2");
+        assert_eq!(entry.metadata.tags, ["lang"]);
+        assert_eq!(entry.metadata.history.len(), 1);
+        assert_eq!(entry.metadata.history[0].content, "Rust v1");
+
+        let unimportant = MemoryMetadata {
+            importance: Some(0.0),
+            ..MemoryMetadata::default()
+        };
+        mem.store_with_metadata("b", "Rust Rust", MemoryCategory::Core, None, &unimportant)
+            .await
+            .unwrap();
+        let recalled = mem.recall("Rust", 10, None).await.unwrap();
+        assert_eq!(recalled[0].key, "a");
+
+        let expired = MemoryMetadata {
+            expires_at: Some("2000-01-01T00:00:00Z".into()),
+            ..MemoryMetadata::default()
+        };
+        mem.store_with_metadata("old", "Rust v0", MemoryCategory::Core, None, &expired)
+            .await
+            .unwrap();
+        assert!(mem.get("old").await.unwrap().is_none());
+        assert_eq!(mem.list(None, None).await.unwrap().len(), 2);
+        assert_eq!(mem.recall("Rust", 10, None).await.unwrap().len(), 2);
+
+        assert_eq!(mem.purge_expired().await.unwrap(), 1);
+        assert_eq!(mem.count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn sqlite_list_page_and_import_keep_order_and_timestamps() {
+        let (_tmp, mem) = temp_sqlite();
+        for key in ["a", "b", "c"] {
+            mem.store(key, "v", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+
+        let keys: Vec<_> = mem
+            .list_page(1, 5)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.key)
+            .collect();
+        assert_eq!(keys, ["b", "c"]);
+
+        let entry = MemoryEntry {
+            id: String::new(),
+            key: "old".into(),
+            content: "from an export".into(),
+            category: MemoryCategory::Daily,
+            timestamp: "2025-01-02T03:04:05+00:00".into(),
+            session_id: Some("s1".into()),
+            score: None,
+            metadata: MemoryMetadata::default(),
+            namespace: None,
+        };
+        mem.import(&entry, Some(&[0.5, 0.25])).await.unwrap();
+
+        let restored = mem.get("old").await.unwrap().unwrap();
+        assert_eq!(restored.timestamp, entry.timestamp);
+        assert_eq!(restored.session_id.as_deref(), Some("s1"));
+        assert_eq!(
+            mem.embedding(None, "old").await.unwrap(),
+            Some(vec![0.5, 0.25])
+        );
+    }
+
     #[tokio::test]
     async fn sqlite_list_by_category() {
         let (_tmp, mem) = temp_sqlite();
@@ -1774,6 +2195,109 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn sqlite_namespaces_isolate_entries() {
+        let (_tmp, mem) = temp_sqlite();
+        let metadata = MemoryMetadata::default();
+        for (namespace, content) in [
+            (None, "Team prefers tea"),
+            (Some("alice"), "Alice prefers coffee"),
+            (Some("bob"), "Bob prefers juice"),
+        ] {
+            mem.store_in(
+                namespace,
+                "drink",
+                content,
+                MemoryCategory::Core,
+                None,
+                &metadata,
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(mem.count().await.unwrap(), 3);
+        assert_eq!(mem.namespaces().await.unwrap(), ["alice", "bob"]);
+        assert_eq!(
+            mem.get("drink").await.unwrap().unwrap().content,
+            "Team prefers tea"
+        );
+        let alice = mem.get_in(Some("alice"), "drink").await.unwrap().unwrap();
+        assert_eq!(alice.content, "Alice prefers coffee");
+        assert_eq!(alice.namespace.as_deref(), Some("alice"));
+
+        let recalled = mem
+            .recall_in(Some("alice"), "prefers", 10, None)
+            .await
+            .unwrap();
+        let contents: Vec<&str> = recalled.iter().map(|e| e.content.as_str()).collect();
+        assert!(contents.contains(&"Alice prefers coffee"));
+        assert!(contents.contains(&"Team prefers tea"));
+        assert!(!contents.contains(&"Bob prefers juice"));
+
+        let shared = mem.recall("prefers", 10, None).await.unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(mem.list_in(Some("bob"), None, None).await.unwrap().len(), 1);
+
+        assert!(mem.forget_in(Some("bob"), "drink").await.unwrap());
+        assert!(mem.get_in(Some("bob"), "drink").await.unwrap().is_none());
+        assert!(mem.get("drink").await.unwrap().is_some());
+        assert!(mem.get_in(Some("alice"), "drink").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn schema_migration_adds_namespaces_to_legacy_table() {
+        let tmp = TempDir::new().unwrap();
+        let db_dir = tmp.path().join("memory");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        {
+            let conn = Connection::open(db_dir.join("brain.db")).unwrap();
+            conn.execute_batch(&format!(
+                "CREATE TABLE memories (
+                    id TEXT PRIMARY KEY,
+                    key TEXT NOT NULL UNIQUE,
+                    content TEXT NOT NULL,
+                    category TEXT NOT NULL DEFAULT 'core',
+                    embedding BLOB,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL,
+                    session_id TEXT,
+                    metadata TEXT,
+                    expires_at TEXT
+                );
+                CREATE VIRTUAL TABLE memories_fts USING fts5(
+                    key, content, content=memories, content_rowid=rowid
+                );
+                {FTS_TRIGGERS}
+                INSERT INTO memories (id, key, content, category, created_at, updated_at)
+                VALUES ('id-1', 'lang', 'Prefers Rust', 'core', '2026-01-01', '2026-01-01');"
+            ))
+            .unwrap();
+        }
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let recalled = mem.recall("Rust", 5, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].key, "lang");
+        assert!(recalled[0].namespace.is_none());
+
+        mem.store_in(
+            Some("alice"),
+            "lang",
+            "Prefers Go",
+            MemoryCategory::Core,
+            None,
+            &MemoryMetadata::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(mem.count().await.unwrap(), 2);
+        assert_eq!(
+            mem.get("lang").await.unwrap().unwrap().content,
+            "Prefers Rust"
+        );
+    }
+
     // ── §4.1 Concurrent write contention tests ──────────────
 
     #[tokio::test]
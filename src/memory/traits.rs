@@ -1,3 +1,4 @@
+use super::metadata::MemoryMetadata;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,11 @@ pub struct MemoryEntry {
     pub timestamp: String,
     pub session_id: Option<String>,
     pub score: Option<f64>,
+    #[serde(default)]
+    pub metadata: MemoryMetadata,
+    /// Person namespace the entry belongs to; `None` is the shared scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 impl std::fmt::Debug for MemoryEntry {
@@ -22,6 +28,8 @@ impl std::fmt::Debug for MemoryEntry {
             .field("category", &self.category)
             .field("timestamp", &self.timestamp)
             .field("score", &self.score)
+            .field("metadata", &self.metadata)
+            .field("namespace", &self.namespace)
             .finish_non_exhaustive()
     }
 }
@@ -66,6 +74,41 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<()>;
 
+    /// Store a memory with tags, importance, expiry and source. Overwrites
+    /// keep the previous content in the update history. The default drops
+    /// the metadata.
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        _metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.store(key, content, category, session_id).await
+    }
+
+    /// Store into a person namespace (`None` = shared scope), replacing
+    /// metadata like [`Memory::store_with_metadata`]. The default only
+    /// supports the shared scope.
+    async fn store_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        match namespace {
+            None => {
+                self.store_with_metadata(key, content, category, session_id, metadata)
+                    .await
+            }
+            Some(_) => anyhow::bail!(namespaces_unsupported(self.name())),
+        }
+    }
+
     /// Recall memories matching a query (keyword search), optionally scoped to a session
     async fn recall(
         &self,
@@ -74,9 +117,35 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>>;
 
+    /// Recall from a person namespace and the shared scope together.
+    async fn recall_in(
+        &self,
+        namespace: Option<&str>,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        match namespace {
+            None => self.recall(query, limit, session_id).await,
+            Some(_) => anyhow::bail!(namespaces_unsupported(self.name())),
+        }
+    }
+
     /// Get a specific memory by key
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>>;
 
+    /// Get a memory by key from exactly one namespace.
+    async fn get_in(
+        &self,
+        namespace: Option<&str>,
+        key: &str,
+    ) -> anyhow::Result<Option<MemoryEntry>> {
+        match namespace {
+            None => self.get(key).await,
+            Some(_) => anyhow::bail!(namespaces_unsupported(self.name())),
+        }
+    }
+
     /// List all memory keys, optionally filtered by category and/or session
     async fn list(
         &self,
@@ -84,19 +153,244 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>>;
 
+    /// List memories of exactly one namespace.
+    async fn list_in(
+        &self,
+        namespace: Option<&str>,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        match namespace {
+            None => self.list(category, session_id).await,
+            Some(_) => anyhow::bail!(namespaces_unsupported(self.name())),
+        }
+    }
+
     /// Remove a memory by key
     async fn forget(&self, key: &str) -> anyhow::Result<bool>;
 
+    /// Remove a memory by key from exactly one namespace.
+    async fn forget_in(&self, namespace: Option<&str>, key: &str) -> anyhow::Result<bool> {
+        match namespace {
+            None => self.forget(key).await,
+            Some(_) => anyhow::bail!(namespaces_unsupported(self.name())),
+        }
+    }
+
+    /// Person namespaces holding at least one entry.
+    async fn namespaces(&self) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
     /// Count total memories
     async fn count(&self) -> anyhow::Result<usize>;
 
     /// Health check
     async fn health_check(&self) -> bool;
+
+    /// Delete entries whose expiry has passed and return how many were
+    /// removed. The default scans the shared scope and every namespace.
+    async fn purge_expired(&self) -> anyhow::Result<usize> {
+        let mut scopes = vec![None];
+        scopes.extend(self.namespaces().await?.into_iter().map(Some));
+
+        let mut purged = 0;
+        for namespace in scopes.iter().map(Option::as_deref) {
+            for entry in self.list_in(namespace, None, None).await? {
+                if entry.metadata.is_expired() && self.forget_in(namespace, &entry.key).await? {
+                    purged += 1;
+                }
+            }
+        }
+        Ok(purged)
+    }
+
+    /// List one page of all memories in a stable order, for export and
+    /// migration. The default sorts `list` output by timestamp and key.
+    async fn list_page(&self, offset: usize, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        let mut entries = self.list(None, None).await?;
+        entries.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        Ok(entries.into_iter().skip(offset).take(limit).collect())
+    }
+
+    /// Stored embedding for a memory, if the backend keeps one
+    async fn embedding(
+        &self,
+        _namespace: Option<&str>,
+        _key: &str,
+    ) -> anyhow::Result<Option<Vec<f32>>> {
+        Ok(None)
+    }
+
+    /// Restore an exported entry, keeping its timestamp and embedding where
+    /// the backend can. The default stores it as a new write.
+    async fn import(&self, entry: &MemoryEntry, _embedding: Option<&[f32]>) -> anyhow::Result<()> {
+        self.store_in(
+            entry.namespace.as_deref(),
+            &entry.key,
+            &entry.content,
+            entry.category.clone(),
+            entry.session_id.as_deref(),
+            &entry.metadata,
+        )
+        .await
+    }
+}
+
+fn namespaces_unsupported(backend: &str) -> String {
+    format!("memory backend '{backend}' does not support per-person namespaces")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use parking_lot::Mutex;
+
+    /// Minimal namespaced backend that keeps the default `purge_expired`.
+    #[derive(Default)]
+    struct ScopedMemory {
+        entries: Mutex<Vec<MemoryEntry>>,
+    }
+
+    impl ScopedMemory {
+        fn insert(&self, namespace: Option<&str>, key: &str, expires_at: Option<&str>) {
+            self.entries.lock().push(MemoryEntry {
+                id: key.into(),
+                key: key.into(),
+                content: key.into(),
+                category: MemoryCategory::Core,
+                timestamp: "2026-02-16T00:00:00Z".into(),
+                session_id: None,
+                score: None,
+                metadata: MemoryMetadata {
+                    expires_at: expires_at.map(str::to_string),
+                    ..MemoryMetadata::default()
+                },
+                namespace: namespace.map(str::to_string),
+            });
+        }
+
+        fn keys(&self) -> Vec<String> {
+            self.entries.lock().iter().map(|e| e.key.clone()).collect()
+        }
+    }
+
+    #[async_trait]
+    impl Memory for ScopedMemory {
+        fn name(&self) -> &str {
+            "scoped"
+        }
+
+        async fn store(
+            &self,
+            key: &str,
+            _content: &str,
+            _category: MemoryCategory,
+            _session_id: Option<&str>,
+        ) -> anyhow::Result<()> {
+            self.insert(None, key, None);
+            Ok(())
+        }
+
+        async fn recall(
+            &self,
+            _query: &str,
+            _limit: usize,
+            _session_id: Option<&str>,
+        ) -> anyhow::Result<Vec<MemoryEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+            self.get_in(None, key).await
+        }
+
+        async fn get_in(
+            &self,
+            namespace: Option<&str>,
+            key: &str,
+        ) -> anyhow::Result<Option<MemoryEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .iter()
+                .find(|e| e.namespace.as_deref() == namespace && e.key == key)
+                .cloned())
+        }
+
+        async fn list(
+            &self,
+            category: Option<&MemoryCategory>,
+            session_id: Option<&str>,
+        ) -> anyhow::Result<Vec<MemoryEntry>> {
+            self.list_in(None, category, session_id).await
+        }
+
+        async fn list_in(
+            &self,
+            namespace: Option<&str>,
+            _category: Option<&MemoryCategory>,
+            _session_id: Option<&str>,
+        ) -> anyhow::Result<Vec<MemoryEntry>> {
+            Ok(self
+                .entries
+                .lock()
+                .iter()
+                .filter(|e| e.namespace.as_deref() == namespace)
+                .cloned()
+                .collect())
+        }
+
+        async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+            self.forget_in(None, key).await
+        }
+
+        async fn forget_in(&self, namespace: Option<&str>, key: &str) -> anyhow::Result<bool> {
+            let mut entries = self.entries.lock();
+            let before = entries.len();
+            entries.retain(|e| !(e.namespace.as_deref() == namespace && e.key == key));
+            Ok(entries.len() < before)
+        }
+
+        async fn namespaces(&self) -> anyhow::Result<Vec<String>> {
+            let mut namespaces: Vec<String> = self
+                .entries
+                .lock()
+                .iter()
+                .filter_map(|e| e.namespace.clone())
+                .collect();
+            namespaces.sort();
+            namespaces.dedup();
+            Ok(namespaces)
+        }
+
+        async fn count(&self) -> anyhow::Result<usize> {
+            Ok(self.entries.lock().len())
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn default_purge_expired_covers_every_namespace() {
+        let memory = ScopedMemory::default();
+        let past = "2020-01-01T00:00:00Z";
+        let future = "2999-01-01T00:00:00Z";
+        memory.insert(None, "shared_old", Some(past));
+        memory.insert(None, "shared_kept", None);
+        memory.insert(Some("alice"), "alice_old", Some(past));
+        memory.insert(Some("alice"), "alice_kept", Some(future));
+        memory.insert(Some("bob"), "bob_old", Some(past));
+
+        assert_eq!(memory.purge_expired().await.unwrap(), 3);
+        assert_eq!(memory.keys(), ["shared_kept", "alice_kept"]);
+    }
 
     #[test]
     fn memory_category_display_outputs_expected_values() {
@@ -130,6 +424,8 @@ mod tests {
             timestamp: "2026-02-16T00:00:00Z".into(),
             session_id: Some("session-abc".into()),
             score: Some(0.98),
+            metadata: MemoryMetadata::default(),
+            namespace: Some("alice".into()),
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -141,5 +437,6 @@ mod tests {
         assert_eq!(parsed.category, MemoryCategory::Core);
         assert_eq!(parsed.session_id.as_deref(), Some("session-abc"));
         assert_eq!(parsed.score, Some(0.98));
+        assert_eq!(parsed.namespace.as_deref(), Some("alice"));
     }
 }
@@ -0,0 +1,354 @@
+//! Versioned JSONL export/import of memories and streaming migration
+//! between any two [`Memory`] backends.
+//!
+//! An export starts with a header line
+//! (`{"format":"zeroclaw-memory","version":1,...}`) followed by one JSON
+//! object per memory entry.
+
+use super::metadata::MemoryMetadata;
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// Value of the header `format` field.
+pub const EXPORT_FORMAT: &str = "zeroclaw-memory";
+/// Current export format version.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Entries read from a backend per page.
+const PAGE_SIZE: usize = 500;
+
+/// First line of an export.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportHeader {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    /// Backend the entries were exported from.
+    pub backend: String,
+    /// Whether records carry their embedding vectors.
+    pub embeddings: bool,
+}
+
+/// One exported memory entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub key: String,
+    pub content: String,
+    pub category: String,
+    pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Person namespace; absent for the shared scope.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "MemoryMetadata::is_empty")]
+    pub metadata: MemoryMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl ExportRecord {
+    fn into_entry(self) -> (MemoryEntry, Option<Vec<f32>>) {
+        let entry = MemoryEntry {
+            id: String::new(),
+            key: self.key,
+            content: self.content,
+            category: parse_category(&self.category),
+            timestamp: self.timestamp,
+            session_id: self.session_id,
+            score: None,
+            metadata: self.metadata,
+            namespace: self.namespace,
+        };
+        (entry, self.embedding)
+    }
+}
+
+/// Counts reported by [`migrate`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Entries in the source before copying.
+    pub source_count: usize,
+    /// Entries written to the target.
+    pub copied: usize,
+    /// Entries in the target after copying.
+    pub target_count: usize,
+    /// Copied keys that could not be read back from the target.
+    pub missing: Vec<String>,
+}
+
+impl MigrationReport {
+    pub fn verified(&self) -> bool {
+        self.missing.is_empty() && self.copied == self.source_count
+    }
+}
+
+/// Write every entry in `memory` to `writer` as JSONL. Returns the number
+/// of entries written.
+pub async fn export<W: Write>(
+    memory: &dyn Memory,
+    writer: &mut W,
+    include_embeddings: bool,
+) -> Result<usize> {
+    let header = ExportHeader {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        backend: memory.name().to_string(),
+        embeddings: include_embeddings,
+    };
+    serde_json::to_writer(&mut *writer, &header)?;
+    writer.write_all(b"\n")?;
+
+    let mut written = 0;
+    loop {
+        let page = memory.list_page(written, PAGE_SIZE).await?;
+        let page_len = page.len();
+        for entry in page {
+            let embedding = if include_embeddings {
+                memory
+                    .embedding(entry.namespace.as_deref(), &entry.key)
+                    .await?
+            } else {
+                None
+            };
+            let record = ExportRecord {
+                key: entry.key,
+                content: entry.content,
+                category: entry.category.to_string(),
+                timestamp: entry.timestamp,
+                session_id: entry.session_id,
+                namespace: entry.namespace,
+                metadata: entry.metadata,
+                embedding,
+            };
+            serde_json::to_writer(&mut *writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+        written += page_len;
+        if page_len < PAGE_SIZE {
+            break;
+        }
+    }
+
+    writer.flush()?;
+    Ok(written)
+}
+
+/// Read an export produced by [`export`] into `memory`. Entries with an
+/// existing key are replaced. Returns the number of entries imported.
+pub async fn import<R: BufRead>(memory: &dyn Memory, reader: R) -> Result<usize> {
+    let mut lines = reader
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |l| !l.trim().is_empty()));
+
+    let Some((_, header_line)) = lines.next() else {
+        bail!("Memory export is empty");
+    };
+    let header: ExportHeader =
+        serde_json::from_str(&header_line?).context("Memory export is missing its header line")?;
+    if header.format != EXPORT_FORMAT {
+        bail!(
+            "Unsupported export format '{}' (expected '{EXPORT_FORMAT}')",
+            header.format
+        );
+    }
+    if header.version > EXPORT_VERSION {
+        bail!(
+            "Memory export version {} is newer than supported version {EXPORT_VERSION}",
+            header.version
+        );
+    }
+
+    let mut imported = 0;
+    for (index, line) in lines {
+        let record: ExportRecord = serde_json::from_str(&line?)
+            .with_context(|| format!("Invalid memory record on line {}", index + 1))?;
+        let (entry, embedding) = record.into_entry();
+        memory
+            .import(&entry, embedding.as_deref())
+            .await
+            .with_context(|| format!("Failed to import memory '{}'", entry.key))?;
+        imported += 1;
+    }
+
+    Ok(imported)
+}
+
+/// Copy every entry from `source` to `target`, page by page, then verify
+/// that each copied key can be read back. `progress` receives
+/// `(copied, source_count)` after each page.
+pub async fn migrate(
+    source: &dyn Memory,
+    target: &dyn Memory,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<MigrationReport> {
+    let mut report = MigrationReport {
+        source_count: source.count().await?,
+        ..MigrationReport::default()
+    };
+
+    loop {
+        let page = source.list_page(report.copied, PAGE_SIZE).await?;
+        let page_len = page.len();
+        for entry in &page {
+            let embedding = source
+                .embedding(entry.namespace.as_deref(), &entry.key)
+                .await?;
+            target
+                .import(entry, embedding.as_deref())
+                .await
+                .with_context(|| format!("Failed to copy memory '{}'", entry.key))?;
+        }
+        for entry in &page {
+            if target
+                .get_in(entry.namespace.as_deref(), &entry.key)
+                .await?
+                .is_none()
+            {
+                report.missing.push(entry.key.clone());
+            }
+        }
+        report.copied += page_len;
+        progress(report.copied, report.source_count);
+        if page_len < PAGE_SIZE {
+            break;
+        }
+    }
+
+    report.target_count = target.count().await?;
+    Ok(report)
+}
+
+fn parse_category(raw: &str) -> MemoryCategory {
+    match raw {
+        "core" => MemoryCategory::Core,
+        "daily" => MemoryCategory::Daily,
+        "conversation" => MemoryCategory::Conversation,
+        other => MemoryCategory::Custom(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    async fn seeded_sqlite(dir: &TempDir) -> SqliteMemory {
+        let mem = SqliteMemory::new(dir.path()).unwrap();
+        mem.store("lang", "Prefers Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("standup", "Shipped export", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        mem.store(
+            "chat_1",
+            "Asked about backups",
+            MemoryCategory::Conversation,
+            Some("sess-1"),
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "note",
+            "Custom note",
+            MemoryCategory::Custom("project_notes".into()),
+            None,
+        )
+        .await
+        .unwrap();
+        mem
+    }
+
+    #[tokio::test]
+    async fn export_import_roundtrip_preserves_entries() {
+        let source_dir = TempDir::new().unwrap();
+        let source = seeded_sqlite(&source_dir).await;
+        let original = source.get("chat_1").await.unwrap().unwrap();
+
+        let mut buffer = Vec::new();
+        let exported = export(&source, &mut buffer, true).await.unwrap();
+        assert_eq!(exported, 4);
+
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        let header: ExportHeader = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(header.format, EXPORT_FORMAT);
+        assert_eq!(header.version, EXPORT_VERSION);
+        assert_eq!(header.backend, "sqlite");
+
+        let target_dir = TempDir::new().unwrap();
+        let target = SqliteMemory::new(target_dir.path()).unwrap();
+        let imported = import(&target, buffer.as_slice()).await.unwrap();
+        assert_eq!(imported, 4);
+        assert_eq!(target.count().await.unwrap(), 4);
+
+        let restored = target.get("chat_1").await.unwrap().unwrap();
+        assert_eq!(restored.content, "Asked about backups");
+        assert_eq!(restored.category, MemoryCategory::Conversation);
+        assert_eq!(restored.session_id.as_deref(), Some("sess-1"));
+        assert_eq!(restored.timestamp, original.timestamp);
+        assert_eq!(
+            target.get("note").await.unwrap().unwrap().category,
+            MemoryCategory::Custom("project_notes".into())
+        );
+    }
+
+    #[tokio::test]
+    async fn import_rejects_unknown_format_and_newer_versions() {
+        let dir = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(dir.path()).unwrap();
+
+        let wrong_format = r#"{"format":"other","version":1,"exported_at":"","backend":"sqlite","embeddings":false}"#;
+        assert!(import(&mem, wrong_format.as_bytes()).await.is_err());
+
+        let newer = format!(
+            r#"{{"format":"{EXPORT_FORMAT}","version":{},"exported_at":"","backend":"sqlite","embeddings":false}}"#,
+            EXPORT_VERSION + 1
+        );
+        let err = import(&mem, newer.as_bytes()).await.unwrap_err();
+        assert!(err.to_string().contains("newer"));
+
+        assert!(import(&mem, "".as_bytes()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn import_reports_bad_record_line() {
+        let dir = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(dir.path()).unwrap();
+        let input = format!(
+            "{{\"format\":\"{EXPORT_FORMAT}\",\"version\":1,\"exported_at\":\"\",\"backend\":\"sqlite\",\"embeddings\":false}}\n\
+             {{\"key\":\"a\",\"content\":\"x\",\"category\":\"core\",\"timestamp\":\"2026-01-01T00:00:00Z\"}}\n\
+             not json\n"
+        );
+
+        let err = import(&mem, input.as_bytes()).await.unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+    }
+
+    #[tokio::test]
+    async fn migrate_copies_between_backends_and_verifies() {
+        let source_dir = TempDir::new().unwrap();
+        let source = seeded_sqlite(&source_dir).await;
+        let target_dir = TempDir::new().unwrap();
+        let target = SqliteMemory::new(target_dir.path()).unwrap();
+
+        let mut updates = Vec::new();
+        let report = migrate(&source, &target, |copied, total| {
+            updates.push((copied, total));
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(report.source_count, 4);
+        assert_eq!(report.copied, 4);
+        assert_eq!(report.target_count, 4);
+        assert!(report.verified(), "missing: {:?}", report.missing);
+        assert_eq!(updates.last(), Some(&(4, 4)));
+    }
+}
@@ -144,6 +144,15 @@ impl RuntimeTraceLogger {
 static TRACE_LOGGER: LazyLock<RwLock<Option<Arc<RuntimeTraceLogger>>>> =
     LazyLock::new(|| RwLock::new(None));
 
+static TRACE_EVENTS: LazyLock<tokio::sync::broadcast::Sender<RuntimeTraceEvent>> =
+    LazyLock::new(|| tokio::sync::broadcast::channel(256).0);
+
+/// Subscribe to runtime trace events as they are recorded. Events are
+/// published even when trace storage is disabled.
+pub fn subscribe() -> tokio::sync::broadcast::Receiver<RuntimeTraceEvent> {
+    TRACE_EVENTS.subscribe()
+}
+
 /// Resolve runtime trace storage mode from config.
 pub fn storage_mode_from_config(config: &ObservabilityConfig) -> RuntimeTraceStorageMode {
     let mode = RuntimeTraceStorageMode::from_raw(&config.runtime_trace_mode);
@@ -207,9 +216,9 @@ pub fn record_event(
         .read()
         .unwrap_or_else(|e| e.into_inner())
         .clone();
-    let Some(logger) = logger else {
+    if logger.is_none() && TRACE_EVENTS.receiver_count() == 0 {
         return;
-    };
+    }
 
     let event = RuntimeTraceEvent {
         id: Uuid::new_v4().to_string(),
@@ -224,9 +233,12 @@ pub fn record_event(
         payload,
     };
 
-    if let Err(err) = logger.append(&event) {
-        tracing::warn!("Failed to write runtime trace event: {err}");
+    if let Some(logger) = logger {
+        if let Err(err) = logger.append(&event) {
+            tracing::warn!("Failed to write runtime trace event: {err}");
+        }
     }
+    let _ = TRACE_EVENTS.send(event);
 }
 
 /// Load recent runtime trace events from storage.
@@ -411,4 +423,29 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().id, target_id);
     }
+
+    #[test]
+    fn record_event_publishes_to_subscribers_without_storage() {
+        let mut rx = subscribe();
+        record_event(
+            "trace_feed_probe",
+            Some("webchat"),
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+            serde_json::json!({ "tool": "shell" }),
+        );
+
+        // Other tests may record events concurrently; look for ours.
+        loop {
+            let event = rx.try_recv().unwrap();
+            if event.event_type == "trace_feed_probe" {
+                assert_eq!(event.channel.as_deref(), Some("webchat"));
+                assert_eq!(event.payload["tool"], "shell");
+                break;
+            }
+        }
+    }
 }
@@ -152,6 +152,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        sop: crate::config::SopConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
@@ -174,6 +175,11 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
         agents_ipc: crate::config::AgentsIpcConfig::default(),
+        a2a: crate::config::A2aConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
+        forge: crate::config::ForgeConfig::default(),
+        vault: crate::config::VaultConfig::default(),
+        image_gen: crate::config::ImageGenConfig::default(),
         model_support_vision: None,
     };
 
@@ -395,6 +401,8 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
         qdrant: crate::config::QdrantConfig::default(),
+        consolidation: crate::config::MemoryConsolidationConfig::default(),
+        namespaces: crate::config::MemoryNamespacesConfig::default(),
     }
 }
 
@@ -508,6 +516,7 @@ async fn run_quick_setup_with_home(
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        sop: crate::config::SopConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
@@ -530,6 +539,11 @@ async fn run_quick_setup_with_home(
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
         agents_ipc: crate::config::AgentsIpcConfig::default(),
+        a2a: crate::config::A2aConfig::default(),
+        home_assistant: crate::config::HomeAssistantConfig::default(),
+        forge: crate::config::ForgeConfig::default(),
+        vault: crate::config::VaultConfig::default(),
+        image_gen: crate::config::ImageGenConfig::default(),
         model_support_vision: None,
     };
 
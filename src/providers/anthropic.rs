@@ -1,11 +1,16 @@
+use crate::providers::streaming;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    Provider, ProviderCapabilities, ResponseFormat, StreamChunk, StreamError, StreamEvent,
+    StreamOptions, StreamResult, TokenUsage, ToolCall as ProviderToolCall,
 };
+use crate::tools::schema::SchemaCleanr;
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 pub struct AnthropicProvider {
     credential: Option<String>,
@@ -51,6 +56,10 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -164,6 +173,48 @@ struct NativeContentIn {
     input: Option<serde_json::Value>,
 }
 
+/// One `data:` payload of a Messages API event stream.
+#[derive(Debug, Deserialize)]
+struct MessagesStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    content_block: Option<NativeContentIn>,
+    #[serde(default)]
+    delta: Option<StreamEventDelta>,
+    #[serde(default)]
+    message: Option<StreamEventMessage>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+    #[serde(default)]
+    error: Option<StreamEventError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamEventDelta {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    partial_json: Option<String>,
+    #[serde(default)]
+    thinking: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamEventMessage {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamEventError {
+    message: String,
+}
+
 impl AnthropicProvider {
     pub fn new(credential: Option<&str>) -> Self {
         Self::with_base_url(credential, None)
@@ -413,6 +464,38 @@ impl AnthropicProvider {
             .ok_or_else(|| anyhow::anyhow!("No response from Anthropic"))
     }
 
+    /// Tool definition used to force schema-shaped output.
+    fn response_format_tool(format: &ResponseFormat) -> ToolSpec {
+        let (name, schema) = match format {
+            ResponseFormat::JsonObject => (
+                "json_response".to_string(),
+                serde_json::json!({"type": "object"}),
+            ),
+            ResponseFormat::JsonSchema { name, schema, .. } => (
+                name.clone(),
+                SchemaCleanr::clean_for_anthropic(schema.clone()),
+            ),
+        };
+        ToolSpec {
+            name,
+            description: "Return the final answer as the input of this tool.".to_string(),
+            parameters: schema,
+        }
+    }
+
+    /// Move the forced response tool's input into the text slot so callers
+    /// see the JSON answer exactly as with providers that return it as text.
+    fn unwrap_response_tool(response: &mut ProviderChatResponse, tool_name: &str) {
+        if let Some(pos) = response
+            .tool_calls
+            .iter()
+            .position(|call| call.name == tool_name)
+        {
+            let call = response.tool_calls.remove(pos);
+            response.text = Some(call.arguments);
+        }
+    }
+
     fn parse_native_response(response: NativeChatResponse) -> ProviderChatResponse {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
@@ -461,6 +544,127 @@ impl AnthropicProvider {
         }
     }
 
+    /// Parse one line of a Messages API event stream.
+    ///
+    /// `tool_blocks` tracks which content block indices are `tool_use`
+    /// blocks, so `content_block_stop` only closes tool calls.
+    fn parse_stream_line(
+        line: &str,
+        tool_blocks: &mut BTreeSet<usize>,
+    ) -> StreamResult<Vec<StreamEvent>> {
+        let Some(data) = streaming::sse_data(line) else {
+            return Ok(Vec::new());
+        };
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let event: MessagesStreamEvent = serde_json::from_str(data).map_err(StreamError::Json)?;
+        let index = event.index.unwrap_or_default();
+        let usage = |u: AnthropicUsage| {
+            StreamEvent::Usage(TokenUsage {
+                input_tokens: u.input_tokens,
+                output_tokens: u.output_tokens,
+            })
+        };
+        match event.kind.as_str() {
+            "message_start" => Ok(event
+                .message
+                .and_then(|m| m.usage)
+                .map(usage)
+                .into_iter()
+                .collect()),
+            "content_block_start" => match event.content_block {
+                Some(block) if block.kind == "tool_use" => {
+                    tool_blocks.insert(index);
+                    Ok(vec![StreamEvent::ToolCallStart {
+                        index,
+                        id: block.id.unwrap_or_default(),
+                        name: block.name.unwrap_or_default(),
+                    }])
+                }
+                _ => Ok(Vec::new()),
+            },
+            "content_block_delta" => {
+                let Some(delta) = event.delta else {
+                    return Ok(Vec::new());
+                };
+                let event = match delta.kind.as_deref() {
+                    Some("input_json_delta") => delta
+                        .partial_json
+                        .filter(|json| !json.is_empty())
+                        .map(|arguments| StreamEvent::ToolCallDelta { index, arguments }),
+                    Some("thinking_delta") => delta
+                        .thinking
+                        .filter(|thinking| !thinking.is_empty())
+                        .map(StreamEvent::ReasoningDelta),
+                    _ => delta
+                        .text
+                        .filter(|text| !text.is_empty())
+                        .map(StreamEvent::TextDelta),
+                };
+                Ok(event.into_iter().collect())
+            }
+            "content_block_stop" if tool_blocks.remove(&index) => {
+                Ok(vec![StreamEvent::ToolCallEnd { index }])
+            }
+            "message_delta" => Ok(event.usage.map(usage).into_iter().collect()),
+            "message_stop" => Ok(vec![StreamEvent::Done]),
+            "error" => Err(StreamError::Provider(format!(
+                "Anthropic stream error: {}",
+                event
+                    .error
+                    .map(|e| e.message)
+                    .unwrap_or_else(|| "unknown error".to_string())
+            ))),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn stream_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[ToolSpec]>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return streaming::error_stream(
+                "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token).",
+            );
+        };
+
+        let (system, mut native_messages) = Self::convert_messages(messages);
+        if Self::should_cache_conversation(messages) {
+            Self::apply_cache_to_last_message(&mut native_messages);
+        }
+
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: 4096,
+            system,
+            messages: native_messages,
+            temperature,
+            tools: Self::convert_tools(tools),
+            tool_choice: None,
+            stream: true,
+        };
+
+        let request = self
+            .http_client()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .header("accept", "text/event-stream")
+            .json(&native_request);
+        let request = self.apply_auth(request, credential);
+
+        let mut tool_blocks = BTreeSet::new();
+        streaming::stream_events("Anthropic", request, move |line| {
+            Self::parse_stream_line(line, &mut tool_blocks)
+        })
+    }
+
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.anthropic", 120, 10)
     }
@@ -530,13 +734,35 @@ impl Provider for AnthropicProvider {
             Self::apply_cache_to_last_message(&mut messages);
         }
 
+        // Anthropic has no response-format parameter: structured output is
+        // obtained by forcing a tool whose input schema is the requested one.
+        let response_tool = request.response_format.map(Self::response_format_tool);
+        let mut tools = Self::convert_tools(request.tools);
+        let tool_choice = response_tool.as_ref().map(|spec| {
+            let native_tools = tools.get_or_insert_with(Vec::new);
+            let choice = if native_tools.is_empty() {
+                serde_json::json!({"type": "tool", "name": spec.name})
+            } else {
+                serde_json::json!({"type": "any"})
+            };
+            native_tools.push(NativeToolSpec {
+                name: &spec.name,
+                description: &spec.description,
+                input_schema: &spec.parameters,
+                cache_control: None,
+            });
+            choice
+        });
+
         let native_request = NativeChatRequest {
             model: model.to_string(),
             max_tokens: 4096,
             system: system_prompt,
             messages,
             temperature,
-            tools: Self::convert_tools(request.tools),
+            tools,
+            tool_choice,
+            stream: false,
         };
 
         let req = self
@@ -552,7 +778,11 @@ impl Provider for AnthropicProvider {
         }
 
         let native_response: NativeChatResponse = response.json().await?;
-        Ok(Self::parse_native_response(native_response))
+        let mut parsed = Self::parse_native_response(native_response);
+        if let Some(spec) = response_tool {
+            Self::unwrap_response_tool(&mut parsed, &spec.name);
+        }
+        Ok(parsed)
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -563,6 +793,7 @@ impl Provider for AnthropicProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: true,
         }
     }
 
@@ -609,10 +840,54 @@ impl Provider for AnthropicProvider {
             } else {
                 Some(&tool_specs)
             },
+            response_format: None,
         };
         self.chat(request, model, temperature).await
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        streaming::text_chunks(
+            self.stream_native(messages, None, model, temperature),
+            options.count_tokens,
+        )
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        request: ProviderChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        self.stream_native(request.messages, request.tools, model, temperature)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(credential) = self.credential.as_ref() {
             let mut request = self
@@ -1208,6 +1483,8 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            tool_choice: None,
+            stream: false,
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -1413,4 +1690,283 @@ mod tests {
         assert!(caps.vision);
         assert!(caps.native_tool_calling);
     }
+
+    fn parse_line(line: &str) -> Vec<StreamEvent> {
+        AnthropicProvider::parse_stream_line(line, &mut BTreeSet::new()).unwrap()
+    }
+
+    #[test]
+    fn parse_stream_line_extracts_text_deltas() {
+        let line = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#;
+        assert_eq!(parse_line(line), vec![StreamEvent::TextDelta("Hi".into())]);
+        assert!(parse_line("event: content_block_delta").is_empty());
+        assert!(parse_line(r#"data: {"type":"ping"}"#).is_empty());
+        assert_eq!(
+            parse_line(r#"data: {"type":"message_stop"}"#),
+            vec![StreamEvent::Done]
+        );
+    }
+
+    #[test]
+    fn parse_stream_line_tracks_tool_use_blocks_thinking_and_usage() {
+        let mut tool_blocks = BTreeSet::new();
+        let mut parse =
+            |line: &str| AnthropicProvider::parse_stream_line(line, &mut tool_blocks).unwrap();
+
+        assert_eq!(
+            parse(
+                r#"data: {"type":"message_start","message":{"usage":{"input_tokens":12,"output_tokens":1}}}"#
+            ),
+            vec![StreamEvent::Usage(TokenUsage {
+                input_tokens: Some(12),
+                output_tokens: Some(1),
+            })]
+        );
+        assert_eq!(
+            parse(
+                r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"plan"}}"#
+            ),
+            vec![StreamEvent::ReasoningDelta("plan".into())]
+        );
+        assert!(parse(r#"data: {"type":"content_block_stop","index":0}"#).is_empty());
+        assert_eq!(
+            parse(
+                r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"shell","input":{}}}"#
+            ),
+            vec![StreamEvent::ToolCallStart {
+                index: 1,
+                id: "toolu_1".into(),
+                name: "shell".into(),
+            }]
+        );
+        assert_eq!(
+            parse(
+                r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"command\":\"ls\"}"}}"#
+            ),
+            vec![StreamEvent::ToolCallDelta {
+                index: 1,
+                arguments: r#"{"command":"ls"}"#.into(),
+            }]
+        );
+        assert_eq!(
+            parse(r#"data: {"type":"content_block_stop","index":1}"#),
+            vec![StreamEvent::ToolCallEnd { index: 1 }]
+        );
+        assert_eq!(
+            parse(
+                r#"data: {"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":40}}"#
+            ),
+            vec![StreamEvent::Usage(TokenUsage {
+                input_tokens: None,
+                output_tokens: Some(40),
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_stream_line_surfaces_error_events() {
+        let line =
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        let err = AnthropicProvider::parse_stream_line(line, &mut BTreeSet::new()).unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_assembles_native_tool_calls() {
+        use crate::providers::traits::StreamAccumulator;
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":9}}}\n\n",
+            "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Checking\"}}\n\n",
+            "data: {\"type\":\"content_block_stop\",\"index\":0}\n\n",
+            "data: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",\"id\":\"toolu_1\",\"name\":\"shell\",\"input\":{}}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{\\\"command\\\":\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"\\\"ls\\\"}\"}}\n\n",
+            "data: {\"type\":\"content_block_stop\",\"index\":1}\n\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"tool_use\"},\"usage\":{\"output_tokens\":7}}\n\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "tools": [{"name": "shell"}]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::with_base_url(Some("test-key"), Some(&server.uri()));
+        let messages = [ChatMessage::user("list files")];
+        let tools = [ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let request = ProviderChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+            response_format: None,
+        };
+        let mut stream =
+            provider.stream_chat(request, "claude-sonnet-4", 0.0, StreamOptions::new(true));
+        let mut acc = StreamAccumulator::new();
+        while let Some(event) = stream.next().await {
+            acc.push(&event.unwrap());
+        }
+
+        assert!(acc.is_done());
+        let response = acc.finish();
+        assert_eq!(response.text.as_deref(), Some("Checking"));
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].id, "toolu_1");
+        assert_eq!(response.tool_calls[0].name, "shell");
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(9));
+        assert_eq!(usage.output_tokens, Some(7));
+    }
+
+    #[tokio::test]
+    async fn chat_forces_response_tool_for_json_schema() {
+        use crate::providers::traits::ResponseFormat;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(body_partial_json(serde_json::json!({
+                "tool_choice": {"type": "tool", "name": "verdict"},
+                "tools": [{"name": "verdict", "input_schema": {"type": "object"}}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "content": [{
+                    "type": "tool_use",
+                    "id": "toolu_1",
+                    "name": "verdict",
+                    "input": {"ok": true}
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::with_base_url(Some("test-key"), Some(&server.uri()));
+        let messages = [ChatMessage::user("is it ok?")];
+        let format = ResponseFormat::json_schema(
+            "verdict",
+            serde_json::json!({"type": "object", "properties": {"ok": {"type": "boolean"}}}),
+        );
+        let request = ProviderChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: Some(&format),
+        };
+
+        let response = provider
+            .chat(request, "claude-sonnet-4", 0.0)
+            .await
+            .unwrap();
+
+        assert!(response.tool_calls.is_empty());
+        assert_eq!(response.text.as_deref(), Some(r#"{"ok":true}"#));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_with_history_streams_sse_deltas() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"usage\":{\"input_tokens\":5}}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hel\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"lo\"}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(header("x-api-key", "test-key"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "system": "Be brief",
+                "messages": [{"role": "user"}]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::with_base_url(Some("test-key"), Some(&server.uri()));
+        let messages = [ChatMessage::system("Be brief"), ChatMessage::user("hi")];
+        let chunks: Vec<_> = provider
+            .stream_chat_with_history(&messages, "claude-sonnet-4", 0.0, StreamOptions::new(true))
+            .collect()
+            .await;
+
+        let chunks: Vec<StreamChunk> = chunks.into_iter().map(Result::unwrap).collect();
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(chunks.iter().filter(|c| c.is_final).count(), 1);
+        assert!(chunks.last().unwrap().is_final);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_reports_http_errors() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(529).set_body_string("overloaded"))
+            .mount(&server)
+            .await;
+
+        let provider = AnthropicProvider::with_base_url(Some("test-key"), Some(&server.uri()));
+        let chunks: Vec<_> = provider
+            .stream_chat_with_system(None, "hi", "claude-sonnet-4", 0.0, StreamOptions::new(true))
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 1);
+        let err = chunks.into_iter().next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("Anthropic API error (529"));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_fails_without_key() {
+        use futures_util::StreamExt;
+
+        let provider = AnthropicProvider::new(None);
+        let mut stream = provider.stream_chat_with_system(
+            None,
+            "hi",
+            "claude-sonnet-4",
+            0.0,
+            StreamOptions::new(true),
+        );
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("credentials not set"));
+    }
 }
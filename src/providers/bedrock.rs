@@ -1212,6 +1212,7 @@ impl Provider for BedrockProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: false,
         }
     }
 
@@ -1392,10 +1393,8 @@ impl Provider for BedrockProvider {
         let client = self.http_client();
 
         // We need to send the request asynchronously, then convert the response to a stream.
-        // Use a channel to bridge the async setup with the streaming response.
-        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-        tokio::spawn(async move {
+        // The producer is spawned on first poll so unused fallback streams stay idle.
+        super::streaming::spawn_chunk_stream(move |tx| async move {
             let payload = match serde_json::to_vec(&request) {
                 Ok(p) => p,
                 Err(e) => {
@@ -1520,12 +1519,7 @@ impl Provider for BedrockProvider {
             }
 
             let _ = tx.send(Ok(StreamChunk::final_chunk())).await;
-        });
-
-        stream::unfold(rx, |mut rx| async {
-            rx.recv().await.map(|chunk| (chunk, rx))
         })
-        .boxed()
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
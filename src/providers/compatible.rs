@@ -757,13 +757,7 @@ fn sse_bytes_to_chunks(
     response: reqwest::Response,
     count_tokens: bool,
 ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-    // Create a channel to send chunks
-    let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-    tokio::spawn(async move {
-        // Buffer for incomplete lines
-        let mut buffer = String::new();
-
+    super::streaming::spawn_chunk_stream(move |tx| async move {
         // Get response body as bytes stream
         match response.error_for_status_ref() {
             Ok(_) => {}
@@ -774,31 +768,21 @@ fn sse_bytes_to_chunks(
         }
 
         let mut bytes_stream = response.bytes_stream();
+        // Buffer for incomplete lines (and split UTF-8 sequences)
+        let mut buffer = super::streaming::LineBuffer::default();
 
         while let Some(item) = bytes_stream.next().await {
             match item {
                 Ok(bytes) => {
-                    // Convert bytes to string and process line by line
-                    let text = match String::from_utf8(bytes.to_vec()) {
-                        Ok(t) => t,
+                    let lines = match buffer.push(&bytes) {
+                        Ok(lines) => lines,
                         Err(e) => {
-                            let _ = tx
-                                .send(Err(StreamError::InvalidSse(format!(
-                                    "Invalid UTF-8: {}",
-                                    e
-                                ))))
-                                .await;
+                            let _ = tx.send(Err(e)).await;
                             break;
                         }
                     };
 
-                    buffer.push_str(&text);
-
-                    // Process complete lines
-                    while let Some(pos) = buffer.find('\n') {
-                        let line = buffer.drain(..=pos).collect::<String>();
-                        buffer = buffer[pos + 1..].to_string();
-
+                    for line in lines {
                         match parse_sse_line(&line) {
                             Ok(Some(content)) => {
                                 let mut chunk = StreamChunk::delta(content);
@@ -826,13 +810,7 @@ fn sse_bytes_to_chunks(
 
         // Send final chunk
         let _ = tx.send(Ok(StreamChunk::final_chunk())).await;
-    });
-
-    // Convert channel receiver to stream
-    stream::unfold(rx, |mut rx| async {
-        rx.recv().await.map(|chunk| (chunk, rx))
     })
-    .boxed()
 }
 
 fn first_nonempty(text: Option<&str>) -> Option<String> {
@@ -1612,6 +1590,7 @@ impl Provider for OpenAiCompatibleProvider {
             // prompt-guided tool calling for those providers.
             native_tool_calling: self.native_tool_calling,
             vision: self.supports_vision,
+            structured_output: false,
         }
     }
 
@@ -2193,10 +2172,8 @@ impl Provider for OpenAiCompatibleProvider {
         let client = self.http_client();
         let auth_header = self.auth_header.clone();
 
-        // Use a channel to bridge the async HTTP response to the stream
-        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-        tokio::spawn(async move {
+        // Bridge the async HTTP response to the stream (spawned on first poll)
+        super::streaming::spawn_chunk_stream(move |tx| async move {
             // Build request with auth
             let mut req_builder = client.post(&url).json(&request);
 
@@ -2241,13 +2218,7 @@ impl Provider for OpenAiCompatibleProvider {
                     break; // Receiver dropped
                 }
             }
-        });
-
-        // Convert channel receiver to stream
-        stream::unfold(rx, |mut rx| async move {
-            rx.recv().await.map(|chunk| (chunk, rx))
         })
-        .boxed()
     }
 
     async fn warmup(&self) -> anyhow::Result<()> {
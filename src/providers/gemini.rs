@@ -5,10 +5,16 @@
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
 use crate::auth::AuthService;
-use crate::providers::traits::{ChatMessage, ChatResponse, Provider, TokenUsage};
+use crate::providers::streaming;
+use crate::providers::traits::{
+    ChatMessage, ChatResponse, Provider, ResponseFormat, StreamChunk, StreamError, StreamEvent,
+    StreamOptions, StreamResult, TokenUsage,
+};
+use crate::tools::schema::SchemaCleanr;
 use async_trait::async_trait;
 use base64::Engine;
 use directories::UserDirs;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -24,6 +30,8 @@ pub struct GeminiProvider {
     auth_service: Option<AuthService>,
     /// Override profile name for managed auth.
     auth_profile_override: Option<String>,
+    /// Base URL of the public Generative Language API used for streaming.
+    public_api_base: String,
 }
 
 /// Mutable OAuth token state — supports runtime refresh for long-lived processes.
@@ -144,6 +152,22 @@ struct GenerationConfig {
     temperature: f64,
     #[serde(rename = "maxOutputTokens")]
     max_output_tokens: u32,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+}
+
+impl GenerationConfig {
+    /// Apply a structured-output request: JSON MIME type plus an optional
+    /// schema cleaned to the OpenAPI subset Gemini accepts.
+    fn with_response_format(mut self, format: Option<&ResponseFormat>) -> Self {
+        if let Some(format) = format {
+            self.response_mime_type = Some("application/json".to_string());
+            self.response_schema = format.schema().cloned().map(SchemaCleanr::clean_for_gemini);
+        }
+        self
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -450,6 +474,7 @@ impl GeminiProvider {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            public_api_base: PUBLIC_API_ENDPOINT.to_string(),
         }
     }
 
@@ -523,6 +548,7 @@ impl GeminiProvider {
                 None
             },
             auth_profile_override: profile_override,
+            public_api_base: PUBLIC_API_ENDPOINT.to_string(),
         }
     }
 
@@ -933,12 +959,152 @@ impl GeminiProvider {
 }
 
 impl GeminiProvider {
+    /// Split chat history into a system instruction and Gemini `contents`.
+    /// Gemini uses the `model` role for assistant turns; other roles are dropped.
+    fn convert_messages(messages: &[ChatMessage]) -> (Option<Content>, Vec<Content>) {
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for msg in messages {
+            let role = match msg.role.as_str() {
+                "system" => {
+                    system_parts.push(&msg.content);
+                    continue;
+                }
+                "user" => "user",
+                "assistant" => "model",
+                _ => continue,
+            };
+            contents.push(Content {
+                role: Some(role.to_string()),
+                parts: vec![Part {
+                    text: msg.content.clone(),
+                }],
+            });
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(Content {
+                role: None,
+                parts: vec![Part {
+                    text: system_parts.join("\n\n"),
+                }],
+            })
+        };
+
+        (system_instruction, contents)
+    }
+
+    /// Parse one line of a `streamGenerateContent?alt=sse` response.
+    /// Thinking parts become reasoning deltas; `usageMetadata` is cumulative,
+    /// so each report simply replaces the previous one.
+    fn parse_stream_line(line: &str) -> StreamResult<Vec<StreamEvent>> {
+        let Some(data) = streaming::sse_data(line) else {
+            return Ok(Vec::new());
+        };
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response: GenerateContentResponse =
+            serde_json::from_str(data).map_err(StreamError::Json)?;
+        let response = response.into_effective_response();
+        if let Some(err) = response.error {
+            return Err(StreamError::Provider(format!(
+                "Gemini stream error: {}",
+                err.message
+            )));
+        }
+
+        let mut text = String::new();
+        let mut reasoning = String::new();
+        let parts = response
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.content)
+            .map(|content| content.parts)
+            .unwrap_or_default();
+        for part in parts {
+            if let Some(part_text) = part.text {
+                if part.thought {
+                    reasoning.push_str(&part_text);
+                } else {
+                    text.push_str(&part_text);
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        if !reasoning.is_empty() {
+            events.push(StreamEvent::ReasoningDelta(reasoning));
+        }
+        if !text.is_empty() {
+            events.push(StreamEvent::TextDelta(text));
+        }
+        if let Some(usage) = response.usage_metadata {
+            events.push(StreamEvent::Usage(TokenUsage {
+                input_tokens: usage.prompt_token_count,
+                output_tokens: usage.candidates_token_count,
+            }));
+        }
+        Ok(events)
+    }
+
+    fn stream_messages(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let Some(auth) = self.auth.as_ref() else {
+            return streaming::error_stream(
+                "Gemini API key not found. Set GEMINI_API_KEY or run `zeroclaw onboard`.",
+            );
+        };
+        if !auth.is_api_key() {
+            return streaming::error_stream(
+                "Gemini streaming requires API key authentication; OAuth sessions use non-streaming requests.",
+            );
+        }
+
+        let (system_instruction, contents) = Self::convert_messages(messages);
+        let request = GenerateContentRequest {
+            contents,
+            system_instruction,
+            generation_config: GenerationConfig {
+                temperature,
+                max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
+            },
+        };
+
+        let url = format!(
+            "{}/{}:streamGenerateContent?alt=sse&key={}",
+            self.public_api_base,
+            Self::format_model_name(model),
+            auth.api_key_credential()
+        );
+        let request = self.http_client().post(url).json(&request);
+
+        streaming::stream_lines(
+            "Gemini",
+            request,
+            options.count_tokens,
+            Self::parse_stream_line,
+        )
+    }
+
     async fn send_generate_content(
         &self,
         contents: Vec<Content>,
         system_instruction: Option<Content>,
         model: &str,
         temperature: f64,
+        response_format: Option<&ResponseFormat>,
     ) -> anyhow::Result<(String, Option<TokenUsage>)> {
         let auth = self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -988,7 +1154,10 @@ impl GeminiProvider {
             generation_config: GenerationConfig {
                 temperature,
                 max_output_tokens: 8192,
-            },
+                response_mime_type: None,
+                response_schema: None,
+            }
+            .with_response_format(response_format),
         };
 
         let url = Self::build_generate_content_url(model, auth);
@@ -1167,7 +1336,7 @@ impl Provider for GeminiProvider {
         }];
 
         let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(contents, system_instruction, model, temperature, None)
             .await?;
         Ok(text)
     }
@@ -1178,48 +1347,10 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in messages {
-            match msg.role.as_str() {
-                "system" => {
-                    system_parts.push(&msg.content);
-                }
-                "user" => {
-                    contents.push(Content {
-                        role: Some("user".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                "assistant" => {
-                    // Gemini API uses "model" role instead of "assistant"
-                    contents.push(Content {
-                        role: Some("model".to_string()),
-                        parts: vec![Part {
-                            text: msg.content.clone(),
-                        }],
-                    });
-                }
-                _ => {}
-            }
-        }
-
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
+        let (system_instruction, contents) = Self::convert_messages(messages);
 
         let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(contents, system_instruction, model, temperature, None)
             .await?;
         Ok(text)
     }
@@ -1230,41 +1361,16 @@ impl Provider for GeminiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in request.messages {
-            match msg.role.as_str() {
-                "system" => system_parts.push(&msg.content),
-                "user" => contents.push(Content {
-                    role: Some("user".to_string()),
-                    parts: vec![Part {
-                        text: msg.content.clone(),
-                    }],
-                }),
-                "assistant" => contents.push(Content {
-                    role: Some("model".to_string()),
-                    parts: vec![Part {
-                        text: msg.content.clone(),
-                    }],
-                }),
-                _ => {}
-            }
-        }
-
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
+        let (system_instruction, contents) = Self::convert_messages(request.messages);
 
         let (text, usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                temperature,
+                request.response_format,
+            )
             .await?;
 
         Ok(ChatResponse {
@@ -1275,6 +1381,40 @@ impl Provider for GeminiProvider {
         })
     }
 
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.auth.as_ref().is_some_and(GeminiAuth::is_api_key)
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_messages(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.stream_messages(messages, model, temperature, options)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(auth) = self.auth.as_ref() {
             match auth {
@@ -1350,6 +1490,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None,
             auth_profile_override: None,
+            public_api_base: PUBLIC_API_ENDPOINT.to_string(),
         }
     }
 
@@ -1552,6 +1693,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1593,6 +1736,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1637,6 +1782,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1674,6 +1821,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1703,6 +1852,8 @@ mod tests {
                 generation_config: Some(GenerationConfig {
                     temperature: 0.7,
                     max_output_tokens: 8192,
+                    response_mime_type: None,
+                    response_schema: None,
                 }),
             },
         };
@@ -1718,6 +1869,37 @@ mod tests {
         assert!(json.contains("\"temperature\":0.7"));
     }
 
+    #[test]
+    fn generation_config_carries_cleaned_response_schema() {
+        use crate::providers::traits::ResponseFormat;
+
+        let base = GenerationConfig {
+            temperature: 0.7,
+            max_output_tokens: 8192,
+            response_mime_type: None,
+            response_schema: None,
+        };
+        let plain = serde_json::to_value(base.clone().with_response_format(None)).unwrap();
+        assert!(plain.get("responseMimeType").is_none());
+        assert!(plain.get("responseSchema").is_none());
+
+        let format = ResponseFormat::json_schema(
+            "verdict",
+            serde_json::json!({
+                "type": "object",
+                "properties": {"ok": {"type": "boolean"}},
+                "additionalProperties": false
+            }),
+        );
+        let json = serde_json::to_value(base.with_response_format(Some(&format))).unwrap();
+        assert_eq!(json["responseMimeType"], "application/json");
+        assert_eq!(
+            json["responseSchema"]["properties"]["ok"]["type"],
+            "boolean"
+        );
+        assert!(json["responseSchema"].get("additionalProperties").is_none());
+    }
+
     #[test]
     fn internal_request_omits_generation_config_when_none() {
         let request = InternalGenerateContentEnvelope {
@@ -2123,6 +2305,7 @@ mod tests {
             oauth_index: Arc::new(tokio::sync::Mutex::new(0)),
             auth_service: None, // Missing auth_service
             auth_profile_override: None,
+            public_api_base: PUBLIC_API_ENDPOINT.to_string(),
         };
 
         let result = provider.warmup().await;
@@ -2141,4 +2324,114 @@ mod tests {
         // Should succeed without making HTTP requests
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn convert_messages_maps_roles_and_joins_system() {
+        let messages = [
+            ChatMessage::system("a"),
+            ChatMessage::user("hi"),
+            ChatMessage::assistant("hello"),
+            ChatMessage::system("b"),
+            ChatMessage::tool("ignored"),
+        ];
+        let (system, contents) = GeminiProvider::convert_messages(&messages);
+        assert_eq!(system.unwrap().parts[0].text, "a\n\nb");
+        let roles: Vec<_> = contents
+            .iter()
+            .map(|c| c.role.as_deref().unwrap())
+            .collect();
+        assert_eq!(roles, vec!["user", "model"]);
+    }
+
+    #[test]
+    fn parse_stream_line_separates_thinking_parts() {
+        let line = r#"data: {"candidates":[{"content":{"parts":[{"text":"hmm","thought":true},{"text":"Hi"}]}}]}"#;
+        assert_eq!(
+            GeminiProvider::parse_stream_line(line).unwrap(),
+            vec![
+                StreamEvent::ReasoningDelta("hmm".into()),
+                StreamEvent::TextDelta("Hi".into())
+            ]
+        );
+        let usage_only = r#"data: {"usageMetadata":{"promptTokenCount":3}}"#;
+        assert_eq!(
+            GeminiProvider::parse_stream_line(usage_only).unwrap(),
+            vec![StreamEvent::Usage(TokenUsage {
+                input_tokens: Some(3),
+                output_tokens: None,
+            })]
+        );
+        let err = GeminiProvider::parse_stream_line(r#"data: {"error":{"message":"quota"}}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("quota"));
+    }
+
+    #[test]
+    fn streaming_only_advertised_for_api_key_auth() {
+        let api_key = test_provider(Some(GeminiAuth::ExplicitKey("k".into())));
+        assert!(api_key.supports_streaming());
+        assert!(!test_provider(Some(test_oauth_auth("t"))).supports_streaming());
+        assert!(!test_provider(None).supports_streaming());
+    }
+
+    #[tokio::test]
+    async fn stream_chat_with_history_streams_sse_deltas() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"Hel\"}]}}]}\r\n\r\n",
+            "data: {\"candidates\":[{\"content\":{\"role\":\"model\",\"parts\":[{\"text\":\"lo\"}]},\"finishReason\":\"STOP\"}],\"usageMetadata\":{\"promptTokenCount\":3,\"candidatesTokenCount\":2}}\r\n\r\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/models/gemini-2.0-flash:streamGenerateContent"))
+            .and(query_param("alt", "sse"))
+            .and(query_param("key", "api-key-123"))
+            .and(body_partial_json(serde_json::json!({
+                "systemInstruction": {"parts": [{"text": "Be brief"}]},
+                "contents": [{"role": "user", "parts": [{"text": "hi"}]}]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = GeminiProvider {
+            public_api_base: server.uri(),
+            ..test_provider(Some(GeminiAuth::ExplicitKey("api-key-123".into())))
+        };
+        let messages = [ChatMessage::system("Be brief"), ChatMessage::user("hi")];
+        let chunks: Vec<StreamChunk> = provider
+            .stream_chat_with_history(&messages, "gemini-2.0-flash", 0.0, StreamOptions::new(true))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(chunks.iter().filter(|c| c.is_final).count(), 1);
+        assert!(chunks.last().unwrap().is_final);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_rejects_oauth_auth() {
+        use futures_util::StreamExt;
+
+        let provider = test_provider(Some(test_oauth_auth("token")));
+        let mut stream = provider.stream_chat_with_system(
+            None,
+            "hi",
+            "gemini-2.0-flash",
+            0.0,
+            StreamOptions::new(true),
+        );
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("requires API key"));
+    }
 }
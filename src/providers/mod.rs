@@ -27,13 +27,16 @@ pub mod openai_codex;
 pub mod openrouter;
 pub mod reliable;
 pub mod router;
+pub(crate) mod streaming;
+pub mod structured;
 pub mod telnyx;
+pub mod tokens;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ResponseFormat, StreamAccumulator, StreamEvent, StreamOptions, ToolCall, ToolResultMessage,
 };
 
 use crate::auth::AuthService;
@@ -1,8 +1,12 @@
 use crate::multimodal;
+use crate::providers::streaming;
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+    ChatMessage, ChatResponse, Provider, ProviderCapabilities, ResponseFormat, StreamChunk,
+    StreamError, StreamEvent, StreamOptions, StreamResult, TokenUsage, ToolCall,
 };
+use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -25,6 +29,9 @@ struct ChatRequest {
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
+    /// `"json"` or a JSON schema constraining the reply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -80,6 +87,21 @@ struct ResponseMessage {
     thinking: Option<String>,
 }
 
+/// One NDJSON line of a streaming `/api/chat` response.
+#[derive(Debug, Deserialize)]
+struct StreamLine {
+    #[serde(default)]
+    message: Option<ResponseMessage>,
+    #[serde(default)]
+    done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct OllamaToolCall {
     id: Option<String>,
@@ -213,6 +235,15 @@ impl OllamaProvider {
             options: Options { temperature },
             think: self.reasoning_enabled,
             tools: tools.map(|t| t.to_vec()),
+            format: None,
+        }
+    }
+
+    /// Map a response format to Ollama's `format` request field.
+    fn response_format_json(format: &ResponseFormat) -> serde_json::Value {
+        match format {
+            ResponseFormat::JsonObject => serde_json::Value::String("json".to_string()),
+            ResponseFormat::JsonSchema { schema, .. } => schema.clone(),
         }
     }
 
@@ -343,6 +374,89 @@ impl OllamaProvider {
             .collect()
     }
 
+    /// Native `/api/chat` call returning structured tool calls and usage.
+    async fn chat_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+        model: &str,
+        temperature: f64,
+        format: Option<serde_json::Value>,
+    ) -> anyhow::Result<ChatResponse> {
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+
+        let api_messages = self.convert_messages(messages);
+
+        // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
+        // tools_to_openai_format() in loop_.rs — pass them through directly.
+        let tools_opt = if tools.is_empty() { None } else { Some(tools) };
+
+        let response = self
+            .send_request(
+                api_messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                tools_opt,
+                format,
+            )
+            .await?;
+
+        let usage = if response.prompt_eval_count.is_some() || response.eval_count.is_some() {
+            Some(TokenUsage {
+                input_tokens: response.prompt_eval_count,
+                output_tokens: response.eval_count,
+            })
+        } else {
+            None
+        };
+
+        // Native tool calls returned by the model.
+        if !response.message.tool_calls.is_empty() {
+            let tool_calls: Vec<ToolCall> = response
+                .message
+                .tool_calls
+                .iter()
+                .map(|tc| {
+                    let (name, args) = Self::extract_tool_name_and_args(tc);
+                    ToolCall {
+                        id: tc
+                            .id
+                            .clone()
+                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                        name,
+                        arguments: serde_json::to_string(&args)
+                            .unwrap_or_else(|_| "{}".to_string()),
+                    }
+                })
+                .collect();
+            let text = Self::normalize_response_text(response.message.content);
+            return Ok(ChatResponse {
+                text,
+                tool_calls,
+                usage,
+                reasoning_content: None,
+            });
+        }
+
+        // Plain text response.
+        let content = response.message.content;
+        let text = if let Some(content) = Self::normalize_response_text(content) {
+            content
+        } else {
+            Self::fallback_text_for_empty_content(
+                &normalized_model,
+                response.message.thinking.as_deref(),
+            )
+        };
+        Ok(ChatResponse {
+            text: Some(text),
+            tool_calls: vec![],
+            usage,
+            reasoning_content: None,
+        })
+    }
+
     /// Send a request to Ollama and get the parsed response.
     /// Pass `tools` to enable native function-calling for models that support it.
     async fn send_request(
@@ -352,8 +466,10 @@ impl OllamaProvider {
         temperature: f64,
         should_auth: bool,
         tools: Option<&[serde_json::Value]>,
+        format: Option<serde_json::Value>,
     ) -> anyhow::Result<ApiChatResponse> {
-        let request = self.build_chat_request(messages, model, temperature, tools);
+        let mut request = self.build_chat_request(messages, model, temperature, tools);
+        request.format = format;
 
         let url = format!("{}/api/chat", self.base_url);
 
@@ -413,6 +529,116 @@ impl OllamaProvider {
         Ok(chat_response)
     }
 
+    /// Parse one NDJSON line of a streaming `/api/chat` response.
+    ///
+    /// Ollama sends each tool call complete in a single frame, so it is
+    /// emitted as start, full-argument delta and end at once. `next_tool_index`
+    /// numbers tool calls across frames.
+    fn parse_stream_line(
+        line: &str,
+        next_tool_index: &mut usize,
+    ) -> StreamResult<Vec<StreamEvent>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let parsed: StreamLine = serde_json::from_str(line).map_err(StreamError::Json)?;
+        if let Some(error) = parsed.error {
+            return Err(StreamError::Provider(format!(
+                "Ollama stream error: {error}"
+            )));
+        }
+
+        let mut events = Vec::new();
+        if let Some(message) = parsed.message {
+            if let Some(thinking) = message.thinking.filter(|t| !t.is_empty()) {
+                events.push(StreamEvent::ReasoningDelta(thinking));
+            }
+            if !message.content.is_empty() {
+                events.push(StreamEvent::TextDelta(message.content));
+            }
+            for tc in &message.tool_calls {
+                let index = *next_tool_index;
+                *next_tool_index += 1;
+                let (name, args) = Self::extract_tool_name_and_args(tc);
+                events.push(StreamEvent::ToolCallStart {
+                    index,
+                    id: tc.id.clone().unwrap_or_default(),
+                    name,
+                });
+                events.push(StreamEvent::ToolCallDelta {
+                    index,
+                    arguments: serde_json::to_string(&args).unwrap_or_else(|_| "{}".to_string()),
+                });
+                events.push(StreamEvent::ToolCallEnd { index });
+            }
+        }
+        if parsed.done {
+            if parsed.prompt_eval_count.is_some() || parsed.eval_count.is_some() {
+                events.push(StreamEvent::Usage(TokenUsage {
+                    input_tokens: parsed.prompt_eval_count,
+                    output_tokens: parsed.eval_count,
+                }));
+            }
+            events.push(StreamEvent::Done);
+        }
+        Ok(events)
+    }
+
+    fn stream_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[serde_json::Value]>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let (normalized_model, should_auth) = match self.resolve_request_details(model) {
+            Ok(details) => details,
+            Err(e) => return streaming::error_stream(e.to_string()),
+        };
+
+        let mut request = self.build_chat_request(
+            self.convert_messages(messages),
+            &normalized_model,
+            temperature,
+            tools,
+        );
+        request.stream = true;
+
+        let mut request_builder = self
+            .http_client()
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&request);
+        if should_auth {
+            if let Some(key) = self.api_key.as_ref() {
+                request_builder = request_builder.bearer_auth(key);
+            }
+        }
+
+        let mut next_tool_index = 0;
+        streaming::stream_events("Ollama", request_builder, move |line| {
+            Self::parse_stream_line(line, &mut next_tool_index)
+        })
+    }
+
+    /// Convert `ToolSpec`s to the OpenAI-compatible JSON that `/api/chat` accepts.
+    fn tool_specs_to_json(specs: &[ToolSpec]) -> Vec<serde_json::Value> {
+        specs
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "type": "function",
+                    "function": {
+                        "name": s.name,
+                        "description": s.description,
+                        "parameters": s.parameters
+                    }
+                })
+            })
+            .collect()
+    }
+
     /// Convert Ollama tool calls to the JSON format expected by parse_tool_calls in loop_.rs
     ///
     /// Handles quirky model behavior where tool calls are wrapped:
@@ -422,7 +648,7 @@ impl OllamaProvider {
         let formatted_calls: Vec<serde_json::Value> = tool_calls
             .iter()
             .map(|tc| {
-                let (tool_name, tool_args) = self.extract_tool_name_and_args(tc);
+                let (tool_name, tool_args) = Self::extract_tool_name_and_args(tc);
 
                 // Arguments must be a JSON string for parse_tool_calls compatibility
                 let args_str =
@@ -447,7 +673,7 @@ impl OllamaProvider {
     }
 
     /// Extract the actual tool name and arguments from potentially nested structures
-    fn extract_tool_name_and_args(&self, tc: &OllamaToolCall) -> (String, serde_json::Value) {
+    fn extract_tool_name_and_args(tc: &OllamaToolCall) -> (String, serde_json::Value) {
         let name = &tc.function.name;
         let args = &tc.function.arguments;
 
@@ -491,6 +717,7 @@ impl Provider for OllamaProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: true,
         }
     }
 
@@ -525,7 +752,14 @@ impl Provider for OllamaProvider {
         });
 
         let response = self
-            .send_request(messages, &normalized_model, temperature, should_auth, None)
+            .send_request(
+                messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                None,
+                None,
+            )
             .await?;
 
         // If model returned tool calls, format them for loop_.rs's parse_tool_calls
@@ -566,6 +800,7 @@ impl Provider for OllamaProvider {
                 temperature,
                 should_auth,
                 None,
+                None,
             )
             .await?;
 
@@ -597,77 +832,55 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
-
-        let api_messages = self.convert_messages(messages);
-
-        // Tools arrive pre-formatted in OpenAI/Ollama-compatible JSON from
-        // tools_to_openai_format() in loop_.rs — pass them through directly.
-        let tools_opt = if tools.is_empty() { None } else { Some(tools) };
-
-        let response = self
-            .send_request(
-                api_messages,
-                &normalized_model,
-                temperature,
-                should_auth,
-                tools_opt,
-            )
-            .await?;
+        self.chat_native(messages, tools, model, temperature, None)
+            .await
+    }
 
-        let usage = if response.prompt_eval_count.is_some() || response.eval_count.is_some() {
-            Some(TokenUsage {
-                input_tokens: response.prompt_eval_count,
-                output_tokens: response.eval_count,
-            })
-        } else {
-            None
-        };
+    fn supports_streaming(&self) -> bool {
+        true
+    }
 
-        // Native tool calls returned by the model.
-        if !response.message.tool_calls.is_empty() {
-            let tool_calls: Vec<ToolCall> = response
-                .message
-                .tool_calls
-                .iter()
-                .map(|tc| {
-                    let (name, args) = self.extract_tool_name_and_args(tc);
-                    ToolCall {
-                        id: tc
-                            .id
-                            .clone()
-                            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
-                        name,
-                        arguments: serde_json::to_string(&args)
-                            .unwrap_or_else(|_| "{}".to_string()),
-                    }
-                })
-                .collect();
-            let text = Self::normalize_response_text(response.message.content);
-            return Ok(ChatResponse {
-                text,
-                tool_calls,
-                usage,
-                reasoning_content: None,
-            });
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
         }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
 
-        // Plain text response.
-        let content = response.message.content;
-        let text = if let Some(content) = Self::normalize_response_text(content) {
-            content
-        } else {
-            Self::fallback_text_for_empty_content(
-                &normalized_model,
-                response.message.thinking.as_deref(),
-            )
-        };
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: vec![],
-            usage,
-            reasoning_content: None,
-        })
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        streaming::text_chunks(
+            self.stream_native(messages, None, model, temperature),
+            options.count_tokens,
+        )
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        request: crate::providers::traits::ChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        let tools = request
+            .tools
+            .filter(|specs| !specs.is_empty())
+            .map(Self::tool_specs_to_json);
+        self.stream_native(request.messages, tools.as_deref(), model, temperature)
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -683,26 +896,22 @@ impl Provider for OllamaProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_with_tools.
-        if let Some(specs) = request.tools {
-            if !specs.is_empty() {
-                let tools: Vec<serde_json::Value> = specs
-                    .iter()
-                    .map(|s| {
-                        serde_json::json!({
-                            "type": "function",
-                            "function": {
-                                "name": s.name,
-                                "description": s.description,
-                                "parameters": s.parameters
-                            }
-                        })
-                    })
-                    .collect();
-                return self
-                    .chat_with_tools(request.messages, &tools, model, temperature)
-                    .await;
-            }
+        // Convert ToolSpec to OpenAI-compatible JSON and use the native endpoint.
+        let tools = request
+            .tools
+            .filter(|specs| !specs.is_empty())
+            .map(Self::tool_specs_to_json);
+        if tools.is_some() || request.response_format.is_some() {
+            let format = request.response_format.map(Self::response_format_json);
+            return self
+                .chat_native(
+                    request.messages,
+                    tools.as_deref().unwrap_or_default(),
+                    model,
+                    temperature,
+                    format,
+                )
+                .await;
         }
 
         // No tools — fall back to plain text chat.
@@ -826,6 +1035,24 @@ mod tests {
         assert!(json.get("think").is_none());
     }
 
+    #[test]
+    fn response_format_maps_to_ollama_format_field() {
+        use crate::providers::traits::ResponseFormat;
+
+        assert_eq!(
+            OllamaProvider::response_format_json(&ResponseFormat::JsonObject),
+            serde_json::json!("json")
+        );
+        let schema = serde_json::json!({"type": "object", "required": ["ok"]});
+        assert_eq!(
+            OllamaProvider::response_format_json(&ResponseFormat::json_schema(
+                "verdict",
+                schema.clone()
+            )),
+            schema
+        );
+    }
+
     #[test]
     fn request_includes_think_when_reasoning_configured() {
         let provider = OllamaProvider::new_with_reasoning(None, None, Some(false));
@@ -904,7 +1131,6 @@ mod tests {
 
     #[test]
     fn extract_tool_name_handles_nested_tool_call() {
-        let provider = OllamaProvider::new(None, None);
         let tc = OllamaToolCall {
             id: Some("call_123".into()),
             function: OllamaFunction {
@@ -915,14 +1141,13 @@ mod tests {
                 }),
             },
         };
-        let (name, args) = provider.extract_tool_name_and_args(&tc);
+        let (name, args) = OllamaProvider::extract_tool_name_and_args(&tc);
         assert_eq!(name, "shell");
         assert_eq!(args.get("command").unwrap(), "date");
     }
 
     #[test]
     fn extract_tool_name_handles_prefixed_name() {
-        let provider = OllamaProvider::new(None, None);
         let tc = OllamaToolCall {
             id: Some("call_123".into()),
             function: OllamaFunction {
@@ -930,14 +1155,13 @@ mod tests {
                 arguments: serde_json::json!({"command": "ls"}),
             },
         };
-        let (name, args) = provider.extract_tool_name_and_args(&tc);
+        let (name, args) = OllamaProvider::extract_tool_name_and_args(&tc);
         assert_eq!(name, "shell");
         assert_eq!(args.get("command").unwrap(), "ls");
     }
 
     #[test]
     fn extract_tool_name_handles_normal_call() {
-        let provider = OllamaProvider::new(None, None);
         let tc = OllamaToolCall {
             id: Some("call_123".into()),
             function: OllamaFunction {
@@ -945,7 +1169,7 @@ mod tests {
                 arguments: serde_json::json!({"path": "/tmp/test"}),
             },
         };
-        let (name, args) = provider.extract_tool_name_and_args(&tc);
+        let (name, args) = OllamaProvider::extract_tool_name_and_args(&tc);
         assert_eq!(name, "file_read");
         assert_eq!(args.get("path").unwrap(), "/tmp/test");
     }
@@ -1069,4 +1293,113 @@ mod tests {
         assert!(resp.prompt_eval_count.is_none());
         assert!(resp.eval_count.is_none());
     }
+
+    #[test]
+    fn parse_stream_line_handles_ndjson_frames() {
+        let mut index = 0;
+        let delta =
+            r#"{"model":"llama3","message":{"role":"assistant","content":"Hi"},"done":false}"#;
+        assert_eq!(
+            OllamaProvider::parse_stream_line(delta, &mut index).unwrap(),
+            vec![StreamEvent::TextDelta("Hi".into())]
+        );
+        let done = r#"{"model":"llama3","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":4,"eval_count":3}"#;
+        assert_eq!(
+            OllamaProvider::parse_stream_line(done, &mut index).unwrap(),
+            vec![
+                StreamEvent::Usage(TokenUsage {
+                    input_tokens: Some(4),
+                    output_tokens: Some(3),
+                }),
+                StreamEvent::Done
+            ]
+        );
+        assert!(OllamaProvider::parse_stream_line("", &mut index)
+            .unwrap()
+            .is_empty());
+        let err =
+            OllamaProvider::parse_stream_line(r#"{"error":"model 'x' not found"}"#, &mut index)
+                .unwrap_err();
+        assert!(err.to_string().contains("model 'x' not found"));
+    }
+
+    #[test]
+    fn parse_stream_line_emits_complete_tool_calls_and_thinking() {
+        let mut index = 0;
+        let line = r#"{"message":{"role":"assistant","content":"","thinking":"hmm","tool_calls":[{"function":{"name":"tool.shell","arguments":{"command":"ls"}}}]},"done":false}"#;
+        let events = OllamaProvider::parse_stream_line(line, &mut index).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                StreamEvent::ReasoningDelta("hmm".into()),
+                StreamEvent::ToolCallStart {
+                    index: 0,
+                    id: String::new(),
+                    name: "shell".into(),
+                },
+                StreamEvent::ToolCallDelta {
+                    index: 0,
+                    arguments: r#"{"command":"ls"}"#.into(),
+                },
+                StreamEvent::ToolCallEnd { index: 0 },
+            ]
+        );
+        assert_eq!(index, 1);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_with_history_streams_ndjson() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"Hel\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"lo\"},\"done\":false}\n",
+            "{\"message\":{\"role\":\"assistant\",\"content\":\"\"},\"done\":true,\"eval_count\":2}\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "llama3",
+                "stream": true
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "application/x-ndjson")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OllamaProvider::new(Some(&server.uri()), None);
+        let messages = [ChatMessage::system("Be brief"), ChatMessage::user("hi")];
+        let chunks: Vec<StreamChunk> = provider
+            .stream_chat_with_history(&messages, "llama3", 0.0, StreamOptions::new(true))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(chunks.iter().filter(|c| c.is_final).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_rejects_cloud_model_on_local_endpoint() {
+        use futures_util::StreamExt;
+
+        let provider = OllamaProvider::new(None, None);
+        let mut stream = provider.stream_chat_with_system(
+            None,
+            "hi",
+            "llama3:cloud",
+            0.0,
+            StreamOptions::new(true),
+        );
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("cloud routing"));
+    }
 }
@@ -1,9 +1,13 @@
+use crate::providers::streaming;
+use crate::providers::structured;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    Provider, StreamChunk, StreamEvent, StreamOptions, StreamResult, TokenUsage,
+    ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -67,6 +71,18 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<NativeStreamOptions>,
+}
+
+/// Asks for a trailing usage chunk on streamed responses.
+#[derive(Debug, Serialize)]
+struct NativeStreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -304,6 +320,45 @@ impl OpenAiProvider {
         }
     }
 
+    fn stream_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[ToolSpec]>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return streaming::error_stream(
+                "OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.",
+            );
+        };
+
+        let tools = Self::convert_tools(tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(messages),
+            temperature,
+            max_tokens: self.max_tokens_override,
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            response_format: None,
+            stream: true,
+            stream_options: Some(NativeStreamOptions {
+                include_usage: true,
+            }),
+        };
+
+        let request = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .header("Accept", "text/event-stream")
+            .json(&native_request);
+
+        let mut parser = streaming::ChatCompletionsParser::new("OpenAI");
+        streaming::stream_events("OpenAI", request, move |line| parser.parse_line(line))
+    }
+
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.openai", 120, 10)
     }
@@ -383,6 +438,11 @@ impl Provider for OpenAiProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request
+                .response_format
+                .map(structured::openai_response_format),
+            stream: false,
+            stream_options: None,
         };
 
         let response = self
@@ -417,6 +477,10 @@ impl Provider for OpenAiProvider {
         true
     }
 
+    fn supports_structured_output(&self) -> bool {
+        true
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -447,6 +511,9 @@ impl Provider for OpenAiProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            stream: false,
+            stream_options: None,
         };
 
         let response = self
@@ -477,6 +544,49 @@ impl Provider for OpenAiProvider {
         Ok(result)
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        streaming::text_chunks(
+            self.stream_native(messages, None, model, temperature),
+            options.count_tokens,
+        )
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        request: ProviderChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        self.stream_native(request.messages, request.tools, model, temperature)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         if let Some(credential) = self.credential.as_ref() {
             self.http_client()
@@ -847,4 +957,147 @@ mod tests {
         assert!(json.contains("reasoning_content"));
         assert!(json.contains("thinking..."));
     }
+
+    #[tokio::test]
+    async fn stream_chat_with_history_streams_sse_deltas() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"lo\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "gpt-4o",
+                "stream": true,
+                "messages": [
+                    {"role": "system", "content": "Be brief"},
+                    {"role": "user", "content": "hi"}
+                ]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let base_url = format!("{}/v1", server.uri());
+        let provider = OpenAiProvider::with_base_url(Some(&base_url), Some("test-key"));
+        let messages = [ChatMessage::system("Be brief"), ChatMessage::user("hi")];
+        let chunks: Vec<StreamChunk> = provider
+            .stream_chat_with_history(&messages, "gpt-4o", 0.0, StreamOptions::new(true))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(chunks.iter().filter(|c| c.is_final).count(), 1);
+        assert!(chunks.last().unwrap().is_final);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_assembles_tool_call_deltas_and_usage() {
+        use crate::providers::traits::StreamAccumulator;
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"tool_calls\":[{\"index\":0,\"id\":\"call_1\",\"type\":\"function\",\"function\":{\"name\":\"shell\",\"arguments\":\"\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"command\\\":\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"ls\\\"}\"}}]}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":11,\"completion_tokens\":6}}\n\n",
+            "data: [DONE]\n\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "stream": true,
+                "stream_options": {"include_usage": true},
+                "tool_choice": "auto",
+                "tools": [{"type": "function", "function": {"name": "shell"}}]
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = OpenAiProvider::with_base_url(Some(&server.uri()), Some("test-key"));
+        let messages = [ChatMessage::user("list files")];
+        let tools = [ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let request = ProviderChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+            response_format: None,
+        };
+        let events: Vec<StreamEvent> = provider
+            .stream_chat(request, "gpt-4o", 0.0, StreamOptions::new(true))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert!(events.contains(&StreamEvent::ToolCallEnd { index: 0 }));
+        assert_eq!(
+            events.iter().filter(|e| **e == StreamEvent::Done).count(),
+            1
+        );
+        let mut acc = StreamAccumulator::new();
+        for event in &events {
+            acc.push(event);
+        }
+        let response = acc.finish();
+        assert!(response.text.is_none());
+        assert_eq!(response.tool_calls.len(), 1);
+        assert_eq!(response.tool_calls[0].id, "call_1");
+        assert_eq!(response.tool_calls[0].name, "shell");
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(11));
+        assert_eq!(usage.output_tokens, Some(6));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_reports_http_errors() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429).set_body_string("rate limited"))
+            .mount(&server)
+            .await;
+
+        let provider = OpenAiProvider::with_base_url(Some(&server.uri()), Some("test-key"));
+        let chunks: Vec<_> = provider
+            .stream_chat_with_system(None, "hi", "gpt-4o", 0.0, StreamOptions::new(true))
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 1);
+        let err = chunks.into_iter().next().unwrap().unwrap_err();
+        assert!(err.to_string().contains("OpenAI API error (429"));
+    }
 }
@@ -651,6 +651,7 @@ impl Provider for OpenAiCodexProvider {
         ProviderCapabilities {
             native_tool_calling: false,
             vision: true,
+            structured_output: false,
         }
     }
 
@@ -1,14 +1,21 @@
 use crate::multimodal;
+use crate::providers::streaming;
+use crate::providers::structured;
 use crate::providers::traits::{
     ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    Provider, ProviderCapabilities, StreamChunk, StreamEvent, StreamOptions, StreamResult,
+    TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
+use futures_util::stream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+const DEFAULT_BASE_URL: &str = "https://openrouter.ai/api/v1";
+
 pub struct OpenRouterProvider {
+    base_url: String,
     credential: Option<String>,
     max_tokens_override: Option<u32>,
 }
@@ -73,6 +80,10 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -157,11 +168,22 @@ impl OpenRouterProvider {
 
     pub fn new_with_max_tokens(credential: Option<&str>, max_tokens_override: Option<u32>) -> Self {
         Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
             credential: credential.map(ToString::to_string),
             max_tokens_override: max_tokens_override.filter(|value| *value > 0),
         }
     }
 
+    /// Create a provider with an optional custom base URL.
+    /// Defaults to `https://openrouter.ai/api/v1` when `base_url` is `None`.
+    pub fn with_base_url(base_url: Option<&str>, credential: Option<&str>) -> Self {
+        let mut provider = Self::new(credential);
+        if let Some(url) = base_url {
+            provider.base_url = url.trim_end_matches('/').to_string();
+        }
+        provider
+    }
+
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
         let items = tools?;
         if items.is_empty() {
@@ -305,6 +327,47 @@ impl OpenRouterProvider {
         }
     }
 
+    fn stream_native(
+        &self,
+        messages: &[ChatMessage],
+        tools: Option<&[ToolSpec]>,
+        model: &str,
+        temperature: f64,
+    ) -> stream::BoxStream<'static, StreamResult<StreamEvent>> {
+        let Some(credential) = self.credential.as_ref() else {
+            return streaming::error_stream(
+                "OpenRouter API key not set. Run `zeroclaw onboard` or set OPENROUTER_API_KEY env var.",
+            );
+        };
+
+        let tools = Self::convert_tools(tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(messages),
+            temperature,
+            max_tokens: self.max_tokens_override,
+            tool_choice: tools.as_ref().map(|_| "auto".to_string()),
+            tools,
+            response_format: None,
+            stream: true,
+        };
+
+        let request = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .header(
+                "HTTP-Referer",
+                "https://github.com/theonlyhennygod/zeroclaw",
+            )
+            .header("X-Title", "ZeroClaw")
+            .header("Accept", "text/event-stream")
+            .json(&native_request);
+
+        let mut parser = streaming::ChatCompletionsParser::new("OpenRouter");
+        streaming::stream_events("OpenRouter", request, move |line| parser.parse_line(line))
+    }
+
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.openrouter", 120, 10)
     }
@@ -316,6 +379,7 @@ impl Provider for OpenRouterProvider {
         ProviderCapabilities {
             native_tool_calling: true,
             vision: true,
+            structured_output: true,
         }
     }
 
@@ -324,7 +388,7 @@ impl Provider for OpenRouterProvider {
         // This prevents the first real chat request from timing out on cold start.
         if let Some(credential) = self.credential.as_ref() {
             self.http_client()
-                .get("https://openrouter.ai/api/v1/auth/key")
+                .get(format!("{}/auth/key", self.base_url))
                 .header("Authorization", format!("Bearer {credential}"))
                 .send()
                 .await?
@@ -366,7 +430,7 @@ impl Provider for OpenRouterProvider {
 
         let response = self
             .http_client()
-            .post("https://openrouter.ai/api/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -417,7 +481,7 @@ impl Provider for OpenRouterProvider {
 
         let response = self
             .http_client()
-            .post("https://openrouter.ai/api/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -462,11 +526,15 @@ impl Provider for OpenRouterProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            response_format: request
+                .response_format
+                .map(structured::openai_response_format),
+            stream: false,
         };
 
         let response = self
             .http_client()
-            .post("https://openrouter.ai/api/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -501,6 +569,49 @@ impl Provider for OpenRouterProvider {
         true
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.stream_chat_with_history(&messages, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        streaming::text_chunks(
+            self.stream_native(messages, None, model, temperature),
+            options.count_tokens,
+        )
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        request: ProviderChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        self.stream_native(request.messages, request.tools, model, temperature)
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -557,11 +668,13 @@ impl Provider for OpenRouterProvider {
             max_tokens: self.max_tokens_override,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            response_format: None,
+            stream: false,
         };
 
         let response = self
             .http_client()
-            .post("https://openrouter.ai/api/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {credential}"))
             .header(
                 "HTTP-Referer",
@@ -1057,4 +1170,88 @@ mod tests {
         assert!(json.contains("reasoning_content"));
         assert!(json.contains("thinking..."));
     }
+
+    #[test]
+    fn with_base_url_trims_trailing_slash() {
+        let provider = OpenRouterProvider::with_base_url(Some("http://localhost:9/api/v1/"), None);
+        assert_eq!(provider.base_url, "http://localhost:9/api/v1");
+        assert_eq!(OpenRouterProvider::new(None).base_url, DEFAULT_BASE_URL);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_with_history_streams_sse_deltas() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            ": OPENROUTER PROCESSING\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hel\"}}]}\n\n",
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"lo\"}}]}\n\n",
+            "data: [DONE]\n\n",
+        );
+        Mock::given(method("POST"))
+            .and(path("/api/v1/chat/completions"))
+            .and(header("authorization", "Bearer test-key"))
+            .and(header("x-title", "ZeroClaw"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "anthropic/claude-sonnet-4",
+                "stream": true
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let base_url = format!("{}/api/v1", server.uri());
+        let provider = OpenRouterProvider::with_base_url(Some(&base_url), Some("test-key"));
+        let messages = [ChatMessage::user("hi")];
+        let chunks: Vec<StreamChunk> = provider
+            .stream_chat_with_history(
+                &messages,
+                "anthropic/claude-sonnet-4",
+                0.0,
+                StreamOptions::new(true),
+            )
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "Hello");
+        assert_eq!(chunks.iter().filter(|c| c.is_final).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn stream_chat_surfaces_mid_stream_errors() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let body = concat!(
+            "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"partial\"}}]}\n\n",
+            "data: {\"error\":{\"message\":\"provider disconnected\",\"code\":502}}\n\n",
+        );
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(&server)
+            .await;
+
+        let provider = OpenRouterProvider::with_base_url(Some(&server.uri()), Some("test-key"));
+        let chunks: Vec<_> = provider
+            .stream_chat_with_system(None, "hi", "openai/gpt-4o", 0.0, StreamOptions::new(true))
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_ref().unwrap().delta, "partial");
+        let err = chunks[1].as_ref().unwrap_err();
+        assert!(err.to_string().contains("provider disconnected"));
+    }
 }
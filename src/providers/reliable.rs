@@ -1,5 +1,5 @@
 use super::traits::{
-    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamEvent, StreamOptions, StreamResult,
 };
 use super::Provider;
 use async_trait::async_trait;
@@ -621,6 +621,16 @@ impl Provider for ReliableProvider {
         })
     }
 
+    fn supports_structured_output(&self) -> bool {
+        // Any member may end up serving the request, so only report native
+        // support when every one of them has it.
+        !self.providers.is_empty()
+            && self
+                .providers
+                .iter()
+                .all(|(_, provider)| provider.supports_structured_output())
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -757,6 +767,7 @@ impl Provider for ReliableProvider {
                         let req = ChatRequest {
                             messages: request.messages,
                             tools: request.tools,
+                            response_format: request.response_format,
                         };
                         match provider.chat(req, sent_model, temperature).await {
                             Ok(resp) => {
@@ -873,71 +884,23 @@ impl Provider for ReliableProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        // Try each provider/model combination for streaming
-        // For streaming, we use the first provider that supports it and has streaming enabled
-        for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
-            if !provider.supports_streaming() || !options.enabled {
-                continue;
-            }
-
-            // Clone provider data for the stream
-            let provider_clone = provider_name.clone();
-
-            // Try the first model in the chain for streaming, with provider remap applied.
-            let base_model = match self.model_chain(model).first() {
-                Some(m) => *m,
-                None => model,
-            };
-            let current_model = self
-                .provider_model_chain(base_model, provider_name, provider_index == 0)
-                .first()
-                .copied()
-                .unwrap_or(base_model)
-                .to_string();
-
-            // For streaming, we attempt once and propagate errors
-            // The caller can retry the entire request if needed
-            let stream = provider.stream_chat_with_system(
-                system_prompt,
-                message,
-                &current_model,
-                temperature,
-                options,
-            );
-
-            // Use a channel to bridge the stream with logging
-            let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-            tokio::spawn(async move {
-                let mut stream = stream;
-                while let Some(chunk) = stream.next().await {
-                    if let Err(ref e) = chunk {
-                        tracing::warn!(
-                            provider = provider_clone,
-                            model = current_model,
-                            "Streaming error: {e}"
-                        );
-                    }
-                    if tx.send(chunk).await.is_err() {
-                        break; // Receiver dropped
-                    }
-                }
-            });
-
-            // Convert channel receiver to stream
-            return stream::unfold(rx, |mut rx| async move {
-                rx.recv().await.map(|chunk| (chunk, rx))
-            })
-            .boxed();
+        if !options.enabled {
+            return super::streaming::error_stream("No provider supports streaming");
         }
-
-        // No streaming support available
-        stream::once(async move {
-            Err(super::traits::StreamError::Provider(
-                "No provider supports streaming".to_string(),
-            ))
-        })
-        .boxed()
+        let candidates = self.stream_candidates(
+            model,
+            |provider| provider.supports_streaming(),
+            |provider, current_model| {
+                provider.stream_chat_with_system(
+                    system_prompt,
+                    message,
+                    current_model,
+                    temperature,
+                    options,
+                )
+            },
+        );
+        stream_with_fallback(candidates, "No provider supports streaming")
     }
 
     fn stream_chat_with_history(
@@ -947,51 +910,132 @@ impl Provider for ReliableProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        for (provider_name, provider) in &self.providers {
-            if !provider.supports_streaming() || !options.enabled {
-                continue;
-            }
+        if !options.enabled {
+            return super::streaming::error_stream("No provider supports streaming");
+        }
+        let candidates = self.stream_candidates(
+            model,
+            |provider| provider.supports_streaming(),
+            |provider, current_model| {
+                provider.stream_chat_with_history(messages, current_model, temperature, options)
+            },
+        );
+        stream_with_fallback(candidates, "No provider supports streaming")
+    }
 
-            let provider_clone = provider_name.clone();
-            let current_model = match self.model_chain(model).first() {
-                Some(m) => m.to_string(),
-                None => model.to_string(),
-            };
-
-            let stream =
-                provider.stream_chat_with_history(messages, &current_model, temperature, options);
-
-            let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-            tokio::spawn(async move {
-                let mut stream = stream;
-                while let Some(chunk) = stream.next().await {
-                    if let Err(ref e) = chunk {
-                        tracing::warn!(
-                            provider = provider_clone,
-                            model = current_model,
-                            "Streaming error: {e}"
-                        );
-                    }
-                    if tx.send(chunk).await.is_err() {
-                        break;
-                    }
-                }
-            });
+    /// Every provider takes part: those without native streaming replay
+    /// their non-streaming `chat` response through the default `stream_chat`.
+    fn stream_chat<'a>(
+        &'a self,
+        request: ChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        let candidates = self.stream_candidates(
+            model,
+            |_| true,
+            |provider, current_model| {
+                provider.stream_chat(request, current_model, temperature, options)
+            },
+        );
+        stream_with_fallback(candidates, "No providers configured")
+    }
+}
+
+/// A prepared (not yet polled) stream for one provider/model pair.
+struct StreamCandidate<'a, T> {
+    provider: String,
+    model: String,
+    stream: stream::BoxStream<'a, StreamResult<T>>,
+}
 
-            return stream::unfold(rx, |mut rx| async move {
-                rx.recv().await.map(|chunk| (chunk, rx))
+impl ReliableProvider {
+    /// Prepare one stream per provider accepted by `include`, in fallback
+    /// order, using the first model of each provider's (remapped) chain.
+    ///
+    /// Provider streams are lazy, so unused fallbacks never send a request.
+    fn stream_candidates<'a, 's, T>(
+        &'a self,
+        model: &str,
+        include: impl Fn(&dyn Provider) -> bool,
+        open: impl Fn(&'a dyn Provider, &str) -> stream::BoxStream<'s, StreamResult<T>>,
+    ) -> Vec<StreamCandidate<'s, T>> {
+        let base_model = self.model_chain(model).first().copied().unwrap_or(model);
+        self.providers
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, provider))| include(provider.as_ref()))
+            .map(|(provider_index, (provider_name, provider))| {
+                let current_model = self
+                    .provider_model_chain(base_model, provider_name, provider_index == 0)
+                    .first()
+                    .copied()
+                    .unwrap_or(base_model)
+                    .to_string();
+                StreamCandidate {
+                    provider: provider_name.clone(),
+                    stream: open(provider.as_ref(), &current_model),
+                    model: current_model,
+                }
             })
-            .boxed();
-        }
+            .collect()
+    }
+}
 
-        stream::once(async move {
-            Err(super::traits::StreamError::Provider(
-                "No provider supports streaming".to_string(),
-            ))
-        })
-        .boxed()
+/// Poll candidates in order until one yields its first item, then commit to
+/// it. A provider that fails before producing output is skipped; once output
+/// has been forwarded, later errors are passed through instead of switching
+/// providers mid-answer.
+fn stream_with_fallback<'a, T: Send + 'static>(
+    candidates: Vec<StreamCandidate<'a, T>>,
+    empty_message: &str,
+) -> stream::BoxStream<'a, StreamResult<T>> {
+    if candidates.is_empty() {
+        return super::streaming::error_stream(empty_message);
     }
+
+    stream::once(async move {
+        let mut failures = Vec::new();
+
+        for StreamCandidate {
+            provider,
+            model,
+            mut stream,
+        } in candidates
+        {
+            match stream.next().await {
+                Some(Ok(first)) => {
+                    let rest = stream.inspect(move |item| {
+                        if let Err(e) = item {
+                            tracing::warn!(provider, model, "Streaming error: {e}");
+                        }
+                    });
+                    return stream::once(async move { Ok(first) }).chain(rest).boxed();
+                }
+                Some(Err(e)) => {
+                    tracing::warn!(
+                        provider,
+                        model,
+                        "Streaming failed before first chunk, trying next provider: {e}"
+                    );
+                    failures.push(format!("provider={provider} model={model} error={e}"));
+                }
+                None => {
+                    failures.push(format!(
+                        "provider={provider} model={model} error=empty stream"
+                    ));
+                }
+            }
+        }
+
+        super::streaming::error_stream(format!(
+            "All providers failed to stream. Attempts:\n{}",
+            failures.join("\n")
+        ))
+    })
+    .flatten()
+    .boxed()
 }
 
 #[cfg(test)]
@@ -1479,13 +1523,13 @@ mod tests {
     async fn provider_keyed_model_fallbacks_remap_fallback_provider_models() {
         let primary = Arc::new(ModelAwareMock {
             calls: Arc::new(AtomicUsize::new(0)),
-            models_seen: parking_lot::Mutex::new(Vec::new()),
+            models_seen: tokio::sync::Mutex::new(Vec::new()),
             fail_models: vec!["glm-5", "glm-4.7"],
             response: "never",
         });
         let fallback = Arc::new(ModelAwareMock {
             calls: Arc::new(AtomicUsize::new(0)),
-            models_seen: parking_lot::Mutex::new(Vec::new()),
+            models_seen: tokio::sync::Mutex::new(Vec::new()),
             fail_models: vec![],
             response: "ok from remap",
         });
@@ -1513,12 +1557,12 @@ mod tests {
         let result = provider.simple_chat("hello", "glm-5", 0.0).await.unwrap();
         assert_eq!(result, "ok from remap");
 
-        let primary_seen = primary.models_seen.lock();
+        let primary_seen = primary.models_seen.lock().await;
         assert_eq!(primary_seen.len(), 2);
         assert_eq!(primary_seen[0], "glm-5");
         assert_eq!(primary_seen[1], "glm-4.7");
 
-        let fallback_seen = fallback.models_seen.lock();
+        let fallback_seen = fallback.models_seen.lock().await;
         assert_eq!(fallback_seen.len(), 1);
         assert_eq!(fallback_seen[0], "anthropic/claude-sonnet-4");
         assert!(!fallback_seen.iter().any(|m| m == "glm-5"));
@@ -1891,6 +1935,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1927,6 +1972,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "test-model", 0.0).await.unwrap();
 
@@ -1998,6 +2044,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let err = provider
             .chat(request, "test", 0.0)
@@ -2114,6 +2161,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "claude-opus", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("ok from sonnet"));
@@ -2162,6 +2210,7 @@ mod tests {
         let request = ChatRequest {
             messages: &messages,
             tools: None,
+            response_format: None,
         };
         let result = provider.chat(request, "test", 0.0).await.unwrap();
         assert_eq!(result.text.as_deref(), Some("from fallback"));
@@ -2233,4 +2282,204 @@ mod tests {
         // No override set → should defer to provider default (false)
         assert!(!provider.supports_vision());
     }
+
+    // ── Streaming fallback ──
+
+    fn openai_at(uri: &str) -> Box<dyn Provider> {
+        Box::new(crate::providers::openai::OpenAiProvider::with_base_url(
+            Some(uri),
+            Some("test-key"),
+        ))
+    }
+
+    #[tokio::test]
+    async fn streaming_falls_back_when_primary_fails_before_first_chunk() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let primary = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .expect(1)
+            .mount(&primary)
+            .await;
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+                "data: {\"choices\":[{\"delta\":{\"content\":\"from \"}}]}\n\n",
+                "data: {\"choices\":[{\"delta\":{\"content\":\"fallback\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            )))
+            .expect(1)
+            .mount(&fallback)
+            .await;
+
+        let provider = ReliableProvider::new(
+            vec![
+                ("primary".into(), openai_at(&primary.uri())),
+                ("fallback".into(), openai_at(&fallback.uri())),
+            ],
+            1,
+            50,
+        );
+
+        let chunks: Vec<StreamChunk> = provider
+            .stream_chat_with_history(
+                &[ChatMessage::user("hi")],
+                "gpt-4o",
+                0.0,
+                StreamOptions::new(true),
+            )
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "from fallback");
+        assert!(chunks.last().unwrap().is_final);
+    }
+
+    #[tokio::test]
+    async fn streaming_does_not_contact_fallback_when_primary_succeeds() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let primary = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+                "data: {\"choices\":[{\"delta\":{\"content\":\"primary\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            )))
+            .expect(1)
+            .mount(&primary)
+            .await;
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&fallback)
+            .await;
+
+        let provider = ReliableProvider::new(
+            vec![
+                ("primary".into(), openai_at(&primary.uri())),
+                ("fallback".into(), openai_at(&fallback.uri())),
+            ],
+            1,
+            50,
+        );
+
+        let text: String = provider
+            .stream_chat_with_system(None, "hi", "gpt-4o", 0.0, StreamOptions::new(true))
+            .map(|chunk| chunk.unwrap().delta)
+            .collect()
+            .await;
+        assert_eq!(text, "primary");
+    }
+
+    #[tokio::test]
+    async fn streaming_reports_all_failures_when_every_provider_fails() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+
+        let provider = ReliableProvider::new(
+            vec![
+                ("a".into(), openai_at(&server.uri())),
+                ("b".into(), openai_at(&server.uri())),
+            ],
+            1,
+            50,
+        );
+
+        let chunks: Vec<_> = provider
+            .stream_chat_with_system(None, "hi", "gpt-4o", 0.0, StreamOptions::new(true))
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 1);
+        let err = chunks[0].as_ref().unwrap_err().to_string();
+        assert!(err.contains("All providers failed to stream"));
+        assert!(err.contains("provider=a"));
+        assert!(err.contains("provider=b"));
+    }
+
+    #[tokio::test]
+    async fn streaming_without_capable_provider_errors() {
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(MockProvider {
+                    calls: Arc::new(AtomicUsize::new(0)),
+                    fail_until_attempt: 0,
+                    response: "ok",
+                    error: "",
+                }) as Box<dyn Provider>,
+            )],
+            1,
+            50,
+        );
+
+        assert!(!provider.supports_streaming());
+        let chunks: Vec<_> = provider
+            .stream_chat_with_system(None, "hi", "m", 0.0, StreamOptions::new(true))
+            .collect()
+            .await;
+        assert!(chunks[0]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("No provider supports streaming"));
+    }
+
+    #[tokio::test]
+    async fn stream_chat_falls_back_to_non_streaming_provider() {
+        use crate::providers::traits::StreamAccumulator;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let primary = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .expect(1)
+            .mount(&primary)
+            .await;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                ("primary".into(), openai_at(&primary.uri())),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&calls),
+                        fail_until_attempt: 0,
+                        response: "from fallback",
+                        error: "",
+                    }) as Box<dyn Provider>,
+                ),
+            ],
+            1,
+            50,
+        );
+
+        let messages = [ChatMessage::user("hi")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: None,
+        };
+        let mut acc = StreamAccumulator::new();
+        let mut events = provider.stream_chat(request, "gpt-4o", 0.0, StreamOptions::new(true));
+        while let Some(event) = events.next().await {
+            acc.push(&event.unwrap());
+        }
+
+        assert!(acc.is_done());
+        assert_eq!(acc.finish().text.as_deref(), Some("from fallback"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -1,6 +1,9 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamEvent, StreamOptions, StreamResult,
+};
 use super::Provider;
 use async_trait::async_trait;
+use futures_util::stream;
 use std::collections::HashMap;
 
 /// A single route: maps a task hint to a provider + model combo.
@@ -175,6 +178,72 @@ impl Provider for RouterProvider {
         })
     }
 
+    fn supports_structured_output(&self) -> bool {
+        // Any member may end up serving the request, so only report native
+        // support when every one of them has it.
+        !self.providers.is_empty()
+            && self
+                .providers
+                .iter()
+                .all(|(_, provider)| provider.supports_structured_output())
+    }
+
+    /// True when any routed provider can stream; a request routed to a
+    /// non-streaming provider gets that provider's fallback behaviour.
+    fn supports_streaming(&self) -> bool {
+        self.providers
+            .iter()
+            .any(|(_, provider)| provider.supports_streaming())
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (provider_name, provider) = &self.providers[provider_idx];
+        tracing::info!(
+            provider = provider_name.as_str(),
+            model = resolved_model.as_str(),
+            "Router dispatching streaming request"
+        );
+        provider.stream_chat_with_system(
+            system_prompt,
+            message,
+            &resolved_model,
+            temperature,
+            options,
+        )
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.stream_chat_with_history(messages, &resolved_model, temperature, options)
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        request: ChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.stream_chat(request, &resolved_model, temperature, options)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
@@ -475,4 +544,69 @@ mod tests {
         assert_eq!(mocks[1].last_model().await, "claude-opus");
         assert_eq!(mocks[0].call_count(), 0);
     }
+
+    #[tokio::test]
+    async fn streaming_routes_hint_to_resolved_provider_and_model() {
+        use futures_util::StreamExt;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat/completions"))
+            .and(body_partial_json(serde_json::json!({
+                "model": "gpt-4o-mini",
+                "stream": true
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_string(concat!(
+                "data: {\"choices\":[{\"delta\":{\"content\":\"routed\"}}]}\n\n",
+                "data: [DONE]\n\n",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let default = Arc::new(MockProvider::new("default"));
+        let router = RouterProvider::new(
+            vec![
+                (
+                    "default".to_string(),
+                    Box::new(Arc::clone(&default)) as Box<dyn Provider>,
+                ),
+                (
+                    "openai".to_string(),
+                    Box::new(crate::providers::openai::OpenAiProvider::with_base_url(
+                        Some(&server.uri()),
+                        Some("test-key"),
+                    )),
+                ),
+            ],
+            vec![(
+                "fast".to_string(),
+                Route {
+                    provider_name: "openai".to_string(),
+                    model: "gpt-4o-mini".to_string(),
+                },
+            )],
+            "default-model".to_string(),
+        );
+
+        assert!(router.supports_streaming());
+        let messages = [ChatMessage::user("hi")];
+        let chunks: Vec<StreamChunk> = router
+            .stream_chat_with_history(&messages, "hint:fast", 0.0, StreamOptions::new(true))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = chunks.iter().map(|c| c.delta.as_str()).collect();
+        assert_eq!(text, "routed");
+        assert_eq!(default.call_count(), 0);
+    }
+
+    #[test]
+    fn supports_streaming_false_when_no_provider_streams() {
+        let (router, _) = make_router(vec![("a", "x"), ("b", "y")], vec![]);
+        assert!(!router.supports_streaming());
+    }
 }
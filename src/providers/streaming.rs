@@ -0,0 +1,569 @@
+//! Shared plumbing for native provider streaming.
+//!
+//! Providers stream either Server-Sent Events (`data: {...}` lines) or
+//! newline-delimited JSON. Both are line-oriented, so each provider only
+//! supplies a per-line parser producing [`StreamEvent`]s; this module handles
+//! buffering, UTF-8 decoding, HTTP error mapping and the bridge into a
+//! `BoxStream` of events or of text-only [`StreamChunk`]s.
+
+use super::traits::{StreamChunk, StreamError, StreamEvent, StreamResult, TokenUsage};
+use futures_util::{stream, StreamExt};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+use std::future::Future;
+use tokio::sync::mpsc;
+
+/// Sending half of a chunk stream produced by [`spawn_chunk_stream`].
+pub(crate) type ChunkSender = mpsc::Sender<StreamResult<StreamChunk>>;
+
+/// Accumulates raw body bytes and yields complete lines.
+///
+/// Decoding happens per line, so multi-byte UTF-8 characters split across
+/// network chunks are reassembled before conversion.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Append bytes and return every line completed by them.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> StreamResult<Vec<String>> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.pending.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            lines.push(decode_line(&line)?);
+        }
+        Ok(lines)
+    }
+
+    /// Flush a trailing line that was not newline-terminated.
+    pub(crate) fn finish(&mut self) -> StreamResult<Option<String>> {
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+        let line = std::mem::take(&mut self.pending);
+        decode_line(&line).map(Some)
+    }
+}
+
+fn decode_line(bytes: &[u8]) -> StreamResult<String> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|e| StreamError::InvalidSse(format!("Invalid UTF-8: {e}")))?;
+    Ok(text.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Payload of an SSE `data:` line; `None` for comments, blank lines and
+/// other fields such as `event:`.
+pub(crate) fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim)
+}
+
+/// One `data:` payload of an OpenAI-style `/chat/completions` stream.
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChatCompletionChoice>,
+    #[serde(default)]
+    usage: Option<ChatCompletionUsage>,
+    #[serde(default)]
+    error: Option<ChatCompletionError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    #[serde(default)]
+    delta: Option<ChatCompletionDelta>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// DeepSeek-style reasoning field.
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    /// OpenRouter-style reasoning field.
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatCompletionToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionToolCallDelta {
+    #[serde(default)]
+    index: usize,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<ChatCompletionFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionFunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionUsage {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionError {
+    message: String,
+}
+
+/// Line parser for OpenAI-style chat completions SSE streams (OpenAI,
+/// OpenRouter). Handles the `[DONE]` sentinel and in-band errors.
+///
+/// The wire format has no per-call end marker, so open tool calls are closed
+/// when a choice reports a `finish_reason` or `[DONE]` arrives.
+#[derive(Debug)]
+pub(crate) struct ChatCompletionsParser {
+    provider: &'static str,
+    open_tool_calls: BTreeSet<usize>,
+}
+
+impl ChatCompletionsParser {
+    pub(crate) fn new(provider: &'static str) -> Self {
+        Self {
+            provider,
+            open_tool_calls: BTreeSet::new(),
+        }
+    }
+
+    pub(crate) fn parse_line(&mut self, line: &str) -> StreamResult<Vec<StreamEvent>> {
+        let Some(data) = sse_data(line) else {
+            return Ok(Vec::new());
+        };
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+        if data == "[DONE]" {
+            let mut events = self.close_tool_calls();
+            events.push(StreamEvent::Done);
+            return Ok(events);
+        }
+
+        let chunk: ChatCompletionChunk = serde_json::from_str(data).map_err(StreamError::Json)?;
+        if let Some(error) = chunk.error {
+            return Err(StreamError::Provider(format!(
+                "{} stream error: {}",
+                self.provider, error.message
+            )));
+        }
+
+        let mut events = Vec::new();
+        if let Some(choice) = chunk.choices.into_iter().next() {
+            if let Some(delta) = choice.delta {
+                if let Some(reasoning) = delta
+                    .reasoning_content
+                    .or(delta.reasoning)
+                    .filter(|r| !r.is_empty())
+                {
+                    events.push(StreamEvent::ReasoningDelta(reasoning));
+                }
+                if let Some(content) = delta.content.filter(|c| !c.is_empty()) {
+                    events.push(StreamEvent::TextDelta(content));
+                }
+                for call in delta.tool_calls {
+                    let (name, arguments) = call
+                        .function
+                        .map(|f| (f.name, f.arguments))
+                        .unwrap_or_default();
+                    if self.open_tool_calls.insert(call.index) {
+                        events.push(StreamEvent::ToolCallStart {
+                            index: call.index,
+                            id: call.id.unwrap_or_default(),
+                            name: name.unwrap_or_default(),
+                        });
+                    }
+                    if let Some(arguments) = arguments.filter(|a| !a.is_empty()) {
+                        events.push(StreamEvent::ToolCallDelta {
+                            index: call.index,
+                            arguments,
+                        });
+                    }
+                }
+            }
+            if choice.finish_reason.is_some() {
+                events.extend(self.close_tool_calls());
+            }
+        }
+        if let Some(usage) = chunk.usage {
+            events.push(StreamEvent::Usage(TokenUsage {
+                input_tokens: usage.prompt_tokens,
+                output_tokens: usage.completion_tokens,
+            }));
+        }
+        Ok(events)
+    }
+
+    fn close_tool_calls(&mut self) -> Vec<StreamEvent> {
+        std::mem::take(&mut self.open_tool_calls)
+            .into_iter()
+            .map(|index| StreamEvent::ToolCallEnd { index })
+            .collect()
+    }
+}
+
+/// Bridge a producer task into a boxed stream.
+///
+/// The producer is spawned on first poll, not on call, so wrappers such as
+/// `ReliableProvider` can prepare fallback streams without firing requests.
+pub(crate) fn spawn_stream<T, F, Fut>(producer: F) -> stream::BoxStream<'static, StreamResult<T>>
+where
+    T: Send + 'static,
+    F: FnOnce(mpsc::Sender<StreamResult<T>>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    stream::once(async move {
+        let (tx, rx) = mpsc::channel::<StreamResult<T>>(100);
+        tokio::spawn(producer(tx));
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        })
+    })
+    .flatten()
+    .boxed()
+}
+
+/// [`spawn_stream`] specialised to text chunks.
+pub(crate) fn spawn_chunk_stream<F, Fut>(
+    producer: F,
+) -> stream::BoxStream<'static, StreamResult<StreamChunk>>
+where
+    F: FnOnce(ChunkSender) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    spawn_stream(producer)
+}
+
+/// Single-item stream for failures detected before any request is sent.
+pub(crate) fn error_stream<T: Send + 'static>(
+    message: impl Into<String>,
+) -> stream::BoxStream<'static, StreamResult<T>> {
+    let message = message.into();
+    stream::once(async move { Err(StreamError::Provider(message)) }).boxed()
+}
+
+/// Reduce an event stream to text chunks for the `stream_chat_*` methods.
+/// Reasoning, tool-call and usage events are dropped.
+pub(crate) fn text_chunks(
+    events: stream::BoxStream<'_, StreamResult<StreamEvent>>,
+    count_tokens: bool,
+) -> stream::BoxStream<'_, StreamResult<StreamChunk>> {
+    events
+        .filter_map(move |event| async move {
+            match event {
+                Ok(StreamEvent::TextDelta(text)) => {
+                    let chunk = StreamChunk::delta(text);
+                    Some(Ok(if count_tokens {
+                        chunk.with_token_estimate()
+                    } else {
+                        chunk
+                    }))
+                }
+                Ok(StreamEvent::Done) => Some(Ok(StreamChunk::final_chunk())),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            }
+        })
+        .boxed()
+}
+
+/// Send `request` and stream its line-oriented body through `parse` as text
+/// chunks. See [`stream_events`].
+pub(crate) fn stream_lines<P>(
+    provider: &'static str,
+    request: reqwest::RequestBuilder,
+    count_tokens: bool,
+    parse: P,
+) -> stream::BoxStream<'static, StreamResult<StreamChunk>>
+where
+    P: FnMut(&str) -> StreamResult<Vec<StreamEvent>> + Send + 'static,
+{
+    text_chunks(stream_events(provider, request, parse), count_tokens)
+}
+
+/// Send `request` and stream its line-oriented body through `parse`.
+///
+/// Non-2xx responses become a single sanitized `StreamError::Provider`.
+/// Exactly one [`StreamEvent::Done`] is emitted on success, whether the
+/// provider sent an explicit end marker or simply closed the body.
+pub(crate) fn stream_events<P>(
+    provider: &'static str,
+    request: reqwest::RequestBuilder,
+    parse: P,
+) -> stream::BoxStream<'static, StreamResult<StreamEvent>>
+where
+    P: FnMut(&str) -> StreamResult<Vec<StreamEvent>> + Send + 'static,
+{
+    spawn_stream(move |tx| forward_lines(provider, request, tx, parse))
+}
+
+async fn forward_lines<P>(
+    provider: &'static str,
+    request: reqwest::RequestBuilder,
+    tx: mpsc::Sender<StreamResult<StreamEvent>>,
+    mut parse: P,
+) where
+    P: FnMut(&str) -> StreamResult<Vec<StreamEvent>> + Send,
+{
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(e) => {
+            let _ = tx.send(Err(StreamError::Http(e))).await;
+            return;
+        }
+    };
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<failed to read provider error body>".to_string());
+        let sanitized = super::sanitize_api_error(&body);
+        let _ = tx
+            .send(Err(StreamError::Provider(format!(
+                "{provider} API error ({status}): {sanitized}"
+            ))))
+            .await;
+        return;
+    }
+
+    let mut body = response.bytes_stream();
+    let mut buffer = LineBuffer::default();
+
+    while let Some(item) = body.next().await {
+        let bytes = match item {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let _ = tx.send(Err(StreamError::Http(e))).await;
+                return;
+            }
+        };
+        let lines = match buffer.push(&bytes) {
+            Ok(lines) => lines,
+            Err(e) => {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+        };
+        for line in lines {
+            if !emit(&tx, parse(&line)).await {
+                return;
+            }
+        }
+    }
+
+    match buffer.finish() {
+        Ok(Some(line)) => {
+            if !emit(&tx, parse(&line)).await {
+                return;
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            let _ = tx.send(Err(e)).await;
+            return;
+        }
+    }
+
+    let _ = tx.send(Ok(StreamEvent::Done)).await;
+}
+
+/// Forward the events parsed from one line. Returns `false` once the stream
+/// is finished (end marker, error, or receiver dropped).
+async fn emit(
+    tx: &mpsc::Sender<StreamResult<StreamEvent>>,
+    events: StreamResult<Vec<StreamEvent>>,
+) -> bool {
+    let events = match events {
+        Ok(events) => events,
+        Err(e) => {
+            let _ = tx.send(Err(e)).await;
+            return false;
+        }
+    };
+    for event in events {
+        let done = event == StreamEvent::Done;
+        if tx.send(Ok(event)).await.is_err() || done {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_buffer_splits_multiple_lines_in_one_chunk() {
+        let mut buffer = LineBuffer::default();
+        let lines = buffer.push(b"data: a\n\ndata: b\r\npartial").unwrap();
+        assert_eq!(lines, vec!["data: a", "", "data: b"]);
+        assert_eq!(buffer.finish().unwrap().as_deref(), Some("partial"));
+        assert!(buffer.finish().unwrap().is_none());
+    }
+
+    #[test]
+    fn line_buffer_reassembles_split_utf8() {
+        let mut buffer = LineBuffer::default();
+        let bytes = "héllo\n".as_bytes();
+        assert!(buffer.push(&bytes[..2]).unwrap().is_empty());
+        assert_eq!(buffer.push(&bytes[2..]).unwrap(), vec!["héllo"]);
+    }
+
+    #[test]
+    fn line_buffer_rejects_invalid_utf8() {
+        let mut buffer = LineBuffer::default();
+        assert!(matches!(
+            buffer.push(&[0xff, b'\n']),
+            Err(StreamError::InvalidSse(_))
+        ));
+    }
+
+    #[test]
+    fn sse_data_extracts_payload_only() {
+        assert_eq!(sse_data("data: {\"a\":1}"), Some("{\"a\":1}"));
+        assert_eq!(sse_data("data:[DONE]"), Some("[DONE]"));
+        assert_eq!(sse_data("event: message_start"), None);
+        assert_eq!(sse_data(": keep-alive"), None);
+    }
+
+    fn parse(line: &str) -> Vec<StreamEvent> {
+        ChatCompletionsParser::new("OpenAI")
+            .parse_line(line)
+            .unwrap()
+    }
+
+    #[test]
+    fn chat_completions_parser_handles_content_and_sentinel() {
+        let line = r#"data: {"choices":[{"index":0,"delta":{"content":"Hi"}}]}"#;
+        assert_eq!(parse(line), vec![StreamEvent::TextDelta("Hi".into())]);
+        let role_only = r#"data: {"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#;
+        assert!(parse(role_only).is_empty());
+        assert!(parse(": keep-alive").is_empty());
+        assert_eq!(parse("data: [DONE]"), vec![StreamEvent::Done]);
+    }
+
+    #[test]
+    fn chat_completions_parser_emits_reasoning_and_usage() {
+        let reasoning = r#"data: {"choices":[{"delta":{"reasoning_content":"hmm"}}]}"#;
+        assert_eq!(
+            parse(reasoning),
+            vec![StreamEvent::ReasoningDelta("hmm".into())]
+        );
+        let openrouter = r#"data: {"choices":[{"delta":{"reasoning":"so"}}]}"#;
+        assert_eq!(
+            parse(openrouter),
+            vec![StreamEvent::ReasoningDelta("so".into())]
+        );
+        let usage = r#"data: {"choices":[],"usage":{"prompt_tokens":3,"completion_tokens":5}}"#;
+        assert_eq!(
+            parse(usage),
+            vec![StreamEvent::Usage(TokenUsage {
+                input_tokens: Some(3),
+                output_tokens: Some(5),
+            })]
+        );
+    }
+
+    #[test]
+    fn chat_completions_parser_tracks_tool_call_deltas() {
+        let mut parser = ChatCompletionsParser::new("OpenAI");
+        let start = r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"shell","arguments":""}}]}}]}"#;
+        assert_eq!(
+            parser.parse_line(start).unwrap(),
+            vec![StreamEvent::ToolCallStart {
+                index: 0,
+                id: "call_1".into(),
+                name: "shell".into(),
+            }]
+        );
+        let args = r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"command\":"}}]}}]}"#;
+        assert_eq!(
+            parser.parse_line(args).unwrap(),
+            vec![StreamEvent::ToolCallDelta {
+                index: 0,
+                arguments: "{\"command\":".into(),
+            }]
+        );
+        let finish = r#"data: {"choices":[{"delta":{},"finish_reason":"tool_calls"}]}"#;
+        assert_eq!(
+            parser.parse_line(finish).unwrap(),
+            vec![StreamEvent::ToolCallEnd { index: 0 }]
+        );
+        assert_eq!(
+            parser.parse_line("data: [DONE]").unwrap(),
+            vec![StreamEvent::Done]
+        );
+    }
+
+    #[test]
+    fn chat_completions_parser_surfaces_in_band_errors() {
+        let line = r#"data: {"error":{"message":"upstream timeout","code":502}}"#;
+        let err = ChatCompletionsParser::new("OpenRouter")
+            .parse_line(line)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Provider error: OpenRouter stream error: upstream timeout"
+        );
+        assert!(matches!(
+            ChatCompletionsParser::new("OpenAI").parse_line("data: {not json"),
+            Err(StreamError::Json(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn text_chunks_keeps_text_and_final_only() {
+        let events = stream::iter(vec![
+            Ok(StreamEvent::ReasoningDelta("think".into())),
+            Ok(StreamEvent::TextDelta("abcd".into())),
+            Ok(StreamEvent::ToolCallStart {
+                index: 0,
+                id: "c".into(),
+                name: "shell".into(),
+            }),
+            Ok(StreamEvent::Done),
+        ])
+        .boxed();
+        let chunks: Vec<_> = text_chunks(events, true).collect().await;
+        assert_eq!(chunks.len(), 2);
+        let first = chunks[0].as_ref().unwrap();
+        assert_eq!(first.delta, "abcd");
+        assert_eq!(first.token_count, 1);
+        assert!(chunks[1].as_ref().unwrap().is_final);
+    }
+
+    #[tokio::test]
+    async fn spawn_chunk_stream_is_lazy() {
+        let started = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = started.clone();
+        let mut stream = spawn_chunk_stream(move |tx| async move {
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            let _ = tx.send(Ok(StreamChunk::delta("hi"))).await;
+        });
+        tokio::task::yield_now().await;
+        assert!(!started.load(std::sync::atomic::Ordering::SeqCst));
+
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk.delta, "hi");
+        assert!(started.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -0,0 +1,302 @@
+//! Schema-constrained JSON responses.
+//!
+//! [`chat_json`] asks a provider for JSON shaped by a [`ResponseFormat`],
+//! validates the reply with [`SchemaCleanr::validate_instance`] and, when the
+//! reply does not parse or validate, sends the problems back to the model for
+//! a single repair attempt. Providers that map the format natively
+//! (`supports_structured_output`) rarely need the repair turn; the rest get
+//! the format described in the system prompt.
+
+use crate::providers::traits::{ChatMessage, ChatRequest, Provider, ResponseFormat};
+use crate::tools::schema::SchemaCleanr;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Number of repair turns after the first reply fails validation.
+const MAX_REPAIR_ATTEMPTS: usize = 1;
+
+/// Map a response format to the Chat Completions `response_format` field.
+pub(crate) fn openai_response_format(format: &ResponseFormat) -> Value {
+    match format {
+        ResponseFormat::JsonObject => serde_json::json!({"type": "json_object"}),
+        ResponseFormat::JsonSchema {
+            name,
+            schema,
+            strict,
+        } => serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": name,
+                "schema": SchemaCleanr::clean_for_openai(schema.clone()),
+                "strict": strict,
+            }
+        }),
+    }
+}
+
+/// Extract a JSON value from model text, tolerating markdown code fences
+/// and prose around a single top-level object or array.
+pub fn extract_json(text: &str) -> Option<Value> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Some(value);
+    }
+
+    let unfenced = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.trim_end().strip_suffix("```"))
+        .map(str::trim);
+    if let Some(inner) = unfenced {
+        if let Ok(value) = serde_json::from_str(inner) {
+            return Some(value);
+        }
+    }
+
+    let start = trimmed.find(['{', '['])?;
+    let close = if trimmed[start..].starts_with('{') {
+        '}'
+    } else {
+        ']'
+    };
+    let end = trimmed.rfind(close)?;
+    if end <= start {
+        return None;
+    }
+    serde_json::from_str(&trimmed[start..=end]).ok()
+}
+
+/// Parse and validate a reply against `format`, returning every problem found.
+pub fn check_response(format: &ResponseFormat, text: &str) -> Result<Value, Vec<String>> {
+    let Some(value) = extract_json(text) else {
+        return Err(vec!["reply is not valid JSON".to_string()]);
+    };
+
+    match format.schema() {
+        Some(schema) => {
+            let errors = SchemaCleanr::validate_instance(schema, &value);
+            if errors.is_empty() {
+                Ok(value)
+            } else {
+                Err(errors)
+            }
+        }
+        None if value.is_object() => Ok(value),
+        None => Err(vec!["reply must be a JSON object".to_string()]),
+    }
+}
+
+/// Ask `provider` for a JSON reply matching `format`.
+///
+/// The last message in `messages` should carry the actual request. Fails
+/// when the provider errors or the reply is still invalid after the repair
+/// attempt.
+pub async fn chat_json(
+    provider: &dyn Provider,
+    messages: &[ChatMessage],
+    format: &ResponseFormat,
+    model: &str,
+    temperature: f64,
+) -> anyhow::Result<Value> {
+    chat_json_with_repairs(
+        provider,
+        messages,
+        format,
+        model,
+        temperature,
+        MAX_REPAIR_ATTEMPTS,
+    )
+    .await
+}
+
+/// [`chat_json`] with an explicit number of repair turns. Callers with a
+/// cheaper fallback than another round trip pass `0`.
+pub async fn chat_json_with_repairs(
+    provider: &dyn Provider,
+    messages: &[ChatMessage],
+    format: &ResponseFormat,
+    model: &str,
+    temperature: f64,
+    max_repairs: usize,
+) -> anyhow::Result<Value> {
+    let mut conversation = messages.to_vec();
+
+    // OpenAI's `json_object` mode also requires JSON to be mentioned in the
+    // prompt, so object mode always gets the short instruction.
+    if !provider.supports_structured_output() || matches!(format, ResponseFormat::JsonObject) {
+        let instructions = format.instructions();
+        if let Some(system) = conversation.iter_mut().find(|m| m.role == "system") {
+            if !system.content.is_empty() {
+                system.content.push_str("\n\n");
+            }
+            system.content.push_str(&instructions);
+        } else {
+            conversation.insert(0, ChatMessage::system(instructions));
+        }
+    }
+
+    let mut attempt = 0;
+    loop {
+        let request = ChatRequest {
+            messages: &conversation,
+            tools: None,
+            response_format: Some(format),
+        };
+        let response = provider.chat(request, model, temperature).await?;
+        let text = response.text.unwrap_or_default();
+
+        let errors = match check_response(format, &text) {
+            Ok(value) => return Ok(value),
+            Err(errors) => errors,
+        };
+        if attempt >= max_repairs {
+            anyhow::bail!(
+                "Structured response failed validation: {}",
+                errors.join("; ")
+            );
+        }
+        attempt += 1;
+
+        tracing::debug!(
+            attempt,
+            errors = errors.len(),
+            "Structured response invalid, asking for a repair"
+        );
+        let mut repair =
+            String::from("Your previous reply did not match the required JSON format:\n");
+        for error in &errors {
+            let _ = writeln!(repair, "- {error}");
+        }
+        repair.push_str("Reply again with only the corrected JSON.");
+        conversation.push(ChatMessage::assistant(text));
+        conversation.push(ChatMessage::user(repair));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    struct ScriptedProvider {
+        replies: Mutex<Vec<&'static str>>,
+        seen: Mutex<Vec<Vec<ChatMessage>>>,
+        native: bool,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&'static str], native: bool) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().copied().collect()),
+                seen: Mutex::new(Vec::new()),
+                native,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        fn supports_structured_output(&self) -> bool {
+            self.native
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            unreachable!("chat_with_history is overridden")
+        }
+
+        async fn chat_with_history(
+            &self,
+            messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.seen.lock().push(messages.to_vec());
+            Ok(self.replies.lock().pop().unwrap_or("").to_string())
+        }
+    }
+
+    fn summary_format() -> ResponseFormat {
+        ResponseFormat::json_schema(
+            "summary",
+            json!({
+                "type": "object",
+                "properties": { "bullets": { "type": "array", "items": { "type": "string" } } },
+                "required": ["bullets"],
+                "additionalProperties": false
+            }),
+        )
+    }
+
+    #[test]
+    fn extract_json_tolerates_fences_and_prose() {
+        assert_eq!(extract_json("{\"a\":1}"), Some(json!({"a": 1})));
+        assert_eq!(
+            extract_json("```json\n{\"a\": [1, 2]}\n```"),
+            Some(json!({"a": [1, 2]}))
+        );
+        assert_eq!(
+            extract_json("Here you go: {\"a\": true} hope that helps"),
+            Some(json!({"a": true}))
+        );
+        assert_eq!(extract_json("no json here"), None);
+    }
+
+    #[test]
+    fn openai_response_format_maps_both_variants() {
+        assert_eq!(
+            openai_response_format(&ResponseFormat::JsonObject),
+            json!({"type": "json_object"})
+        );
+        let mapped = openai_response_format(&summary_format());
+        assert_eq!(mapped["type"], "json_schema");
+        assert_eq!(mapped["json_schema"]["name"], "summary");
+        assert_eq!(mapped["json_schema"]["strict"], true);
+        assert_eq!(
+            mapped["json_schema"]["schema"]["required"],
+            json!(["bullets"])
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_json_repairs_invalid_reply_once() {
+        let provider =
+            ScriptedProvider::new(&["{\"items\": []}", "{\"bullets\": [\"ok\"]}"], false);
+        let messages = [ChatMessage::user("summarize")];
+
+        let value = chat_json(&provider, &messages, &summary_format(), "model", 0.0)
+            .await
+            .unwrap();
+
+        assert_eq!(value, json!({"bullets": ["ok"]}));
+        let seen = provider.seen.lock();
+        assert_eq!(seen.len(), 2);
+        assert!(seen[0][0].content.contains("JSON Schema"));
+        let repair = &seen[1].last().unwrap().content;
+        assert!(repair.contains("missing required property 'bullets'"));
+        assert!(repair.contains("unexpected property 'items'"));
+    }
+
+    #[tokio::test]
+    async fn chat_json_gives_up_after_failed_repair() {
+        let provider = ScriptedProvider::new(&["nope", "still nope"], true);
+        let messages = [ChatMessage::user("summarize")];
+
+        let err = chat_json(&provider, &messages, &summary_format(), "model", 0.0)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("not valid JSON"));
+        let seen = provider.seen.lock();
+        assert_eq!(seen.len(), 2);
+        // Native providers get the schema via the request, not the prompt.
+        assert_eq!(seen[0].len(), 1);
+    }
+}
@@ -0,0 +1,241 @@
+//! Token counting and per-model context-window metadata.
+//!
+//! [`counter_for_model`] returns the most accurate [`TokenCounter`] available
+//! for a model: an exact BPE tokenizer for OpenAI models and a scaled BPE
+//! approximation for Anthropic models when the `tokenizer-bpe` feature is
+//! enabled, and the character heuristic otherwise. [`context_window_for_model`]
+//! maps model ids to their documented input window.
+
+use crate::providers::traits::ChatMessage;
+use std::sync::Arc;
+
+/// Per-message framing cost (role markers, separators) added by chat APIs.
+pub const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Window assumed for models missing from the table below. Deliberately
+/// conservative so unknown local models are not overrun.
+pub const DEFAULT_CONTEXT_WINDOW: usize = 32_768;
+
+/// Known context windows, matched by model-id prefix after stripping any
+/// `vendor/` routing prefix. Longer prefixes must come before shorter ones.
+const CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    ("claude-", 200_000),
+    ("gpt-5", 400_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("o1-mini", 128_000),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4-mini", 200_000),
+    ("codex-", 200_000),
+    ("gemini-1.5-pro", 2_097_152),
+    ("gemini-", 1_048_576),
+    ("grok-4", 256_000),
+    ("grok-", 131_072),
+    ("deepseek-", 128_000),
+    ("kimi-", 256_000),
+    ("moonshot-", 128_000),
+    ("glm-4.5", 128_000),
+    ("glm-4.6", 200_000),
+    ("glm-", 128_000),
+    ("qwen3", 128_000),
+    ("qwen", 32_768),
+    ("minimax-", 1_000_000),
+    ("mistral-large", 128_000),
+    ("mistral-", 32_768),
+    ("llama3.1", 128_000),
+    ("llama3.2", 128_000),
+    ("llama3.3", 128_000),
+    ("llama-3", 128_000),
+    ("llama3", 8_192),
+    ("gemma3", 128_000),
+];
+
+/// Counts tokens for budget decisions.
+pub trait TokenCounter: Send + Sync {
+    /// Short identifier for logs (e.g. `"o200k"`, `"heuristic"`).
+    fn name(&self) -> &'static str;
+
+    /// Tokens in a piece of text.
+    fn count(&self, text: &str) -> usize;
+
+    /// Tokens in a message including framing overhead.
+    fn count_message(&self, message: &ChatMessage) -> usize {
+        self.count(&message.content) + MESSAGE_OVERHEAD_TOKENS
+    }
+
+    /// Tokens in a whole conversation.
+    fn count_messages(&self, messages: &[ChatMessage]) -> usize {
+        messages.iter().map(|m| self.count_message(m)).sum()
+    }
+}
+
+/// Character-based estimate: ~4 ASCII characters per token, one token per
+/// non-ASCII character (CJK and emoji rarely merge). Errs high for accented
+/// Latin text, which is the safe direction for budgeting.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicCounter;
+
+impl TokenCounter for HeuristicCounter {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    fn count(&self, text: &str) -> usize {
+        let (ascii, other) = text.chars().fold((0usize, 0usize), |(a, o), c| {
+            if c.is_ascii() {
+                (a + 1, o)
+            } else {
+                (a, o + 1)
+            }
+        });
+        ascii.div_ceil(4) + other
+    }
+}
+
+/// Heuristic token estimate for `text`.
+pub fn estimate_tokens(text: &str) -> usize {
+    HeuristicCounter.count(text)
+}
+
+#[cfg(feature = "tokenizer-bpe")]
+mod bpe {
+    use super::TokenCounter;
+    use tiktoken_rs::CoreBPE;
+
+    /// Tiktoken BPE counter, optionally scaled up for model families whose
+    /// own tokenizer is not public but produces more tokens than `cl100k`.
+    pub struct BpeCounter {
+        pub(super) name: &'static str,
+        pub(super) bpe: &'static CoreBPE,
+        pub(super) scale_percent: usize,
+    }
+
+    impl TokenCounter for BpeCounter {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn count(&self, text: &str) -> usize {
+            let tokens = self.bpe.encode_ordinary(text).len();
+            (tokens * self.scale_percent).div_ceil(100)
+        }
+    }
+}
+
+#[cfg(feature = "tokenizer-bpe")]
+pub use bpe::BpeCounter;
+
+/// Strip routing prefixes (`anthropic/claude-…`, `models/gemini-…`) and
+/// lowercase the remaining model id.
+fn base_model_id(model: &str) -> String {
+    model
+        .rsplit('/')
+        .next()
+        .unwrap_or(model)
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Documented input window for `model`, or [`DEFAULT_CONTEXT_WINDOW`].
+pub fn context_window_for_model(model: &str) -> usize {
+    let id = base_model_id(model);
+    CONTEXT_WINDOWS
+        .iter()
+        .find(|(prefix, _)| id.starts_with(prefix))
+        .map_or(DEFAULT_CONTEXT_WINDOW, |(_, window)| *window)
+}
+
+/// Most accurate counter available for `model`.
+pub fn counter_for_model(model: &str) -> Arc<dyn TokenCounter> {
+    #[cfg(feature = "tokenizer-bpe")]
+    {
+        let id = base_model_id(model);
+        let o200k = ["gpt-4o", "gpt-4.1", "gpt-5", "o1", "o3", "o4", "codex-"];
+        if o200k.iter().any(|prefix| id.starts_with(prefix)) {
+            return Arc::new(BpeCounter {
+                name: "o200k",
+                bpe: tiktoken_rs::o200k_base_singleton(),
+                scale_percent: 100,
+            });
+        }
+        if id.starts_with("gpt-") {
+            return Arc::new(BpeCounter {
+                name: "cl100k",
+                bpe: tiktoken_rs::cl100k_base_singleton(),
+                scale_percent: 100,
+            });
+        }
+        if id.starts_with("claude-") {
+            // Claude's tokenizer yields roughly 15% more tokens than cl100k.
+            return Arc::new(BpeCounter {
+                name: "cl100k-claude",
+                bpe: tiktoken_rs::cl100k_base_singleton(),
+                scale_percent: 115,
+            });
+        }
+    }
+    #[cfg(not(feature = "tokenizer-bpe"))]
+    let _ = model;
+
+    Arc::new(HeuristicCounter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heuristic_counts_ascii_by_four_and_wide_chars_individually() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens("日本語"), 3);
+    }
+
+    #[test]
+    fn count_messages_includes_framing_overhead() {
+        let messages = [ChatMessage::user("abcd"), ChatMessage::assistant("")];
+        assert_eq!(
+            HeuristicCounter.count_messages(&messages),
+            1 + 2 * MESSAGE_OVERHEAD_TOKENS
+        );
+    }
+
+    #[test]
+    fn context_window_matches_prefix_after_vendor_prefix() {
+        assert_eq!(context_window_for_model("claude-sonnet-4-5"), 200_000);
+        assert_eq!(
+            context_window_for_model("anthropic/claude-3.5-sonnet"),
+            200_000
+        );
+        assert_eq!(context_window_for_model("gpt-4o-mini"), 128_000);
+        assert_eq!(context_window_for_model("gpt-4"), 8_192);
+        assert_eq!(context_window_for_model("GPT-4.1"), 1_047_576);
+        assert_eq!(context_window_for_model("qwen3:8b"), 128_000);
+        assert_eq!(
+            context_window_for_model("some-local-model"),
+            DEFAULT_CONTEXT_WINDOW
+        );
+    }
+
+    #[test]
+    fn counter_for_unknown_model_is_heuristic() {
+        assert_eq!(counter_for_model("llama3.2").name(), "heuristic");
+    }
+
+    #[cfg(feature = "tokenizer-bpe")]
+    #[test]
+    fn counter_for_openai_and_anthropic_models_uses_bpe() {
+        let gpt = counter_for_model("openai/gpt-4o");
+        assert_eq!(gpt.name(), "o200k");
+        assert_eq!(gpt.count("hello world"), 2);
+
+        let claude = counter_for_model("claude-sonnet-4");
+        assert_eq!(claude.name(), "cl100k-claude");
+        assert!(claude.count("hello world") >= 2);
+    }
+}
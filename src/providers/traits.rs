@@ -50,7 +50,7 @@ pub struct ToolCall {
 }
 
 /// Raw token counts from a single LLM API response.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct TokenUsage {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
@@ -84,11 +84,61 @@ impl ChatResponse {
     }
 }
 
+/// Requested shape of the model's final text response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseFormat {
+    /// Any syntactically valid JSON object.
+    JsonObject,
+    /// JSON constrained by a schema. `name` identifies the schema to
+    /// providers that require one (OpenAI `json_schema.name`, the forced
+    /// Anthropic tool name).
+    JsonSchema {
+        name: String,
+        schema: serde_json::Value,
+        strict: bool,
+    },
+}
+
+impl ResponseFormat {
+    /// Schema-constrained format with strict enforcement where supported.
+    pub fn json_schema(name: impl Into<String>, schema: serde_json::Value) -> Self {
+        Self::JsonSchema {
+            name: name.into(),
+            schema,
+            strict: true,
+        }
+    }
+
+    /// The schema carried by this format, if any.
+    pub fn schema(&self) -> Option<&serde_json::Value> {
+        match self {
+            Self::JsonObject => None,
+            Self::JsonSchema { schema, .. } => Some(schema),
+        }
+    }
+
+    /// Prompt text describing the format for providers without a native
+    /// response-format parameter.
+    pub fn instructions(&self) -> String {
+        match self {
+            Self::JsonObject => {
+                "Respond with a single JSON object only. Do not wrap it in markdown code fences or add any prose.".to_string()
+            }
+            Self::JsonSchema { schema, .. } => format!(
+                "Respond with a single JSON value that conforms to this JSON Schema. Do not wrap it in markdown code fences or add any prose.\n\n{}",
+                serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string())
+            ),
+        }
+    }
+}
+
 /// Request payload for provider chat calls.
 #[derive(Debug, Clone, Copy)]
 pub struct ChatRequest<'a> {
     pub messages: &'a [ChatMessage],
     pub tools: Option<&'a [ToolSpec]>,
+    /// Constrain the final text response to JSON. `None` means free text.
+    pub response_format: Option<&'a ResponseFormat>,
 }
 
 /// A tool result to feed back to the LLM.
@@ -155,13 +205,170 @@ impl StreamChunk {
         }
     }
 
-    /// Estimate tokens (rough approximation: ~4 chars per token).
+    /// Estimate tokens with the shared heuristic counter.
     pub fn with_token_estimate(mut self) -> Self {
-        self.token_count = self.delta.len().div_ceil(4);
+        self.token_count = super::tokens::estimate_tokens(&self.delta);
         self
     }
 }
 
+/// A structured event from a streaming chat response.
+///
+/// Tool calls are keyed by `index`, which is stable within one response:
+/// `ToolCallStart` precedes the argument deltas for its index, and
+/// `ToolCallEnd` follows them when the provider marks the call complete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamEvent {
+    /// Answer text delta.
+    TextDelta(String),
+    /// Reasoning/thinking delta from thinking models.
+    ReasoningDelta(String),
+    /// A new tool call was opened.
+    ToolCallStart {
+        index: usize,
+        id: String,
+        name: String,
+    },
+    /// A fragment of a tool call's JSON arguments.
+    ToolCallDelta { index: usize, arguments: String },
+    /// The tool call at `index` is complete.
+    ToolCallEnd { index: usize },
+    /// Token usage. Fields left `None` keep any value reported earlier.
+    Usage(TokenUsage),
+    /// The response is complete.
+    Done,
+}
+
+impl StreamEvent {
+    /// Replay a non-streaming response as the equivalent event sequence.
+    pub fn from_response(response: ChatResponse) -> Vec<Self> {
+        let mut events = Vec::new();
+        if let Some(reasoning) = response.reasoning_content.filter(|r| !r.is_empty()) {
+            events.push(Self::ReasoningDelta(reasoning));
+        }
+        if let Some(text) = response.text.filter(|t| !t.is_empty()) {
+            events.push(Self::TextDelta(text));
+        }
+        for (index, call) in response.tool_calls.into_iter().enumerate() {
+            events.push(Self::ToolCallStart {
+                index,
+                id: call.id,
+                name: call.name,
+            });
+            events.push(Self::ToolCallDelta {
+                index,
+                arguments: call.arguments,
+            });
+            events.push(Self::ToolCallEnd { index });
+        }
+        if let Some(usage) = response.usage {
+            events.push(Self::Usage(usage));
+        }
+        events.push(Self::Done);
+        events
+    }
+}
+
+/// Assembles [`StreamEvent`]s back into a [`ChatResponse`].
+///
+/// Shared by every streaming consumer so partial tool calls, reasoning and
+/// usage are merged the same way regardless of provider.
+#[derive(Debug, Default)]
+pub struct StreamAccumulator {
+    text: String,
+    reasoning: String,
+    tool_calls: std::collections::BTreeMap<usize, ToolCall>,
+    usage: Option<TokenUsage>,
+    done: bool,
+}
+
+impl StreamAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one event into the response under construction.
+    pub fn push(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::TextDelta(text) => self.text.push_str(text),
+            StreamEvent::ReasoningDelta(text) => self.reasoning.push_str(text),
+            StreamEvent::ToolCallStart { index, id, name } => {
+                let call = self.tool_call_mut(*index);
+                if !id.is_empty() {
+                    call.id.clone_from(id);
+                }
+                if !name.is_empty() {
+                    call.name.clone_from(name);
+                }
+            }
+            StreamEvent::ToolCallDelta { index, arguments } => {
+                self.tool_call_mut(*index).arguments.push_str(arguments);
+            }
+            StreamEvent::ToolCallEnd { .. } => {}
+            StreamEvent::Usage(usage) => {
+                let merged = self.usage.get_or_insert_with(TokenUsage::default);
+                if usage.input_tokens.is_some() {
+                    merged.input_tokens = usage.input_tokens;
+                }
+                if usage.output_tokens.is_some() {
+                    merged.output_tokens = usage.output_tokens;
+                }
+            }
+            StreamEvent::Done => self.done = true,
+        }
+    }
+
+    /// Answer text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether a tool call has been opened.
+    pub fn has_tool_calls(&self) -> bool {
+        !self.tool_calls.is_empty()
+    }
+
+    /// Whether the provider signalled the end of the response.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Build the final response. Tool calls are ordered by index; calls
+    /// without a name are dropped, missing IDs are generated and empty
+    /// arguments become `{}`.
+    pub fn finish(self) -> ChatResponse {
+        let tool_calls = self
+            .tool_calls
+            .into_values()
+            .filter(|call| !call.name.is_empty())
+            .map(|mut call| {
+                if call.id.is_empty() {
+                    call.id = uuid::Uuid::new_v4().to_string();
+                }
+                if call.arguments.trim().is_empty() {
+                    call.arguments = "{}".to_string();
+                }
+                call
+            })
+            .collect();
+
+        ChatResponse {
+            text: (!self.text.is_empty()).then_some(self.text),
+            tool_calls,
+            usage: self.usage,
+            reasoning_content: (!self.reasoning.is_empty()).then_some(self.reasoning),
+        }
+    }
+
+    fn tool_call_mut(&mut self, index: usize) -> &mut ToolCall {
+        self.tool_calls.entry(index).or_insert_with(|| ToolCall {
+            id: String::new(),
+            name: String::new(),
+            arguments: String::new(),
+        })
+    }
+}
+
 /// Options for streaming chat requests.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct StreamOptions {
@@ -233,6 +440,11 @@ pub struct ProviderCapabilities {
     pub native_tool_calling: bool,
     /// Whether the provider supports vision / image inputs.
     pub vision: bool,
+    /// Whether the provider maps [`ResponseFormat`] to an API-native
+    /// constraint (e.g. OpenAI `response_format`, Ollama `format`).
+    ///
+    /// When `false`, the format is described in the system prompt instead.
+    pub structured_output: bool,
 }
 
 /// Provider-specific tool payload formats.
@@ -388,6 +600,11 @@ pub trait Provider: Send + Sync {
         self.capabilities().vision
     }
 
+    /// Whether provider constrains output to a [`ResponseFormat`] natively.
+    fn supports_structured_output(&self) -> bool {
+        self.capabilities().structured_output
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -422,6 +639,9 @@ pub trait Provider: Send + Sync {
     /// Streaming chat with optional system prompt.
     /// Returns an async stream of text chunks.
     /// Default implementation falls back to non-streaming chat.
+    ///
+    /// Implementations should not send anything until the stream is first
+    /// polled: `ReliableProvider` prepares fallback streams up front.
     fn stream_chat_with_system(
         &self,
         _system_prompt: Option<&str>,
@@ -455,6 +675,37 @@ pub trait Provider: Send + Sync {
             .unwrap_or("");
         self.stream_chat_with_system(system, last_user, model, temperature, options)
     }
+
+    /// Structured streaming chat: the streaming counterpart of `chat`.
+    ///
+    /// Yields text, reasoning and tool-call events and ends with
+    /// [`StreamEvent::Done`]; feed them to a [`StreamAccumulator`] to recover
+    /// the `ChatResponse`. The default implementation awaits `chat` and
+    /// replays the response as a single batch of events, so every provider
+    /// can be consumed this way. Like the other stream methods, nothing
+    /// should be sent before the stream is first polled.
+    fn stream_chat<'a>(
+        &'a self,
+        request: ChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+        _options: StreamOptions,
+    ) -> stream::BoxStream<'a, StreamResult<StreamEvent>> {
+        let model = model.to_string();
+        stream::once(async move {
+            let events: Vec<StreamResult<StreamEvent>> =
+                match self.chat(request, &model, temperature).await {
+                    Ok(response) => StreamEvent::from_response(response)
+                        .into_iter()
+                        .map(Ok)
+                        .collect(),
+                    Err(e) => vec![Err(StreamError::Provider(e.to_string()))],
+                };
+            stream::iter(events)
+        })
+        .flatten()
+        .boxed()
+    }
 }
 
 /// Build tool instructions text for prompt-guided tool calling.
@@ -502,6 +753,7 @@ mod tests {
             ProviderCapabilities {
                 native_tool_calling: true,
                 vision: true,
+                structured_output: false,
             }
         }
 
@@ -557,6 +809,100 @@ mod tests {
         assert_eq!(with_tools.text_or_empty(), "Let me check");
     }
 
+    #[test]
+    fn stream_accumulator_assembles_interleaved_tool_calls() {
+        let mut acc = StreamAccumulator::new();
+        for event in [
+            StreamEvent::ReasoningDelta("plan ".into()),
+            StreamEvent::ReasoningDelta("it".into()),
+            StreamEvent::TextDelta("Working".into()),
+            StreamEvent::ToolCallStart {
+                index: 1,
+                id: "b".into(),
+                name: "file_read".into(),
+            },
+            StreamEvent::ToolCallStart {
+                index: 0,
+                id: "a".into(),
+                name: "shell".into(),
+            },
+            StreamEvent::ToolCallDelta {
+                index: 0,
+                arguments: "{\"command\":".into(),
+            },
+            StreamEvent::ToolCallDelta {
+                index: 1,
+                arguments: "{}".into(),
+            },
+            StreamEvent::ToolCallDelta {
+                index: 0,
+                arguments: "\"ls\"}".into(),
+            },
+            StreamEvent::ToolCallStart {
+                index: 2,
+                id: String::new(),
+                name: String::new(),
+            },
+            StreamEvent::Usage(TokenUsage {
+                input_tokens: Some(10),
+                output_tokens: None,
+            }),
+            StreamEvent::Usage(TokenUsage {
+                input_tokens: None,
+                output_tokens: Some(4),
+            }),
+        ] {
+            acc.push(&event);
+        }
+        assert!(acc.has_tool_calls());
+        assert!(!acc.is_done());
+        acc.push(&StreamEvent::Done);
+        assert!(acc.is_done());
+
+        let response = acc.finish();
+        assert_eq!(response.text.as_deref(), Some("Working"));
+        assert_eq!(response.reasoning_content.as_deref(), Some("plan it"));
+        let names: Vec<_> = response
+            .tool_calls
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["shell", "file_read"]);
+        assert_eq!(response.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(
+            response.usage,
+            Some(TokenUsage {
+                input_tokens: Some(10),
+                output_tokens: Some(4),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn default_stream_chat_replays_chat_response() {
+        let provider = MockProvider {
+            supports_native: false,
+        };
+        let messages = [ChatMessage::user("hello")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+            response_format: None,
+        };
+        let events: Vec<StreamEvent> = provider
+            .stream_chat(request, "model", 0.0, StreamOptions::new(true))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(events.last(), Some(&StreamEvent::Done));
+        let mut acc = StreamAccumulator::new();
+        for event in &events {
+            acc.push(event);
+        }
+        assert_eq!(acc.finish().text.as_deref(), Some("response"));
+    }
+
     #[test]
     fn token_usage_default_is_none() {
         let usage = TokenUsage::default();
@@ -617,14 +963,17 @@ mod tests {
         let caps1 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
+            structured_output: false,
         };
         let caps2 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
+            structured_output: false,
         };
         let caps3 = ProviderCapabilities {
             native_tool_calling: false,
             vision: false,
+            structured_output: false,
         };
 
         assert_eq!(caps1, caps2);
@@ -784,6 +1133,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -801,6 +1151,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: None,
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -901,6 +1252,7 @@ mod tests {
                 ChatMessage::system("BASE_SYSTEM_PROMPT"),
             ],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -923,6 +1275,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::system("BASE"), ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let response = provider.chat(request, "model", 0.7).await.unwrap();
@@ -945,6 +1298,7 @@ mod tests {
         let request = ChatRequest {
             messages: &[ChatMessage::user("Hello")],
             tools: Some(&tools),
+            response_format: None,
         };
 
         let err = provider.chat(request, "model", 0.7).await.unwrap_err();
@@ -6,10 +6,22 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::PathBuf;
-use tokio::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use tokio::sync::{broadcast, Mutex};
 use uuid::Uuid;
 
+/// Rotated backups kept next to the audit log (`audit.log.1.log` … `.10.log`).
+const MAX_ROTATED_LOGS: usize = 10;
+
+static AUDIT_EVENTS: LazyLock<broadcast::Sender<AuditEvent>> =
+    LazyLock::new(|| broadcast::channel(256).0);
+
+/// Subscribe to audit events as they are written by any logger.
+pub fn subscribe() -> broadcast::Receiver<AuditEvent> {
+    AUDIT_EVENTS.subscribe()
+}
+
 /// Audit event types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -192,9 +204,15 @@ impl AuditLogger {
         writeln!(file, "{}", line)?;
         file.sync_all()?;
 
+        let _ = AUDIT_EVENTS.send(event.clone());
         Ok(())
     }
 
+    /// Path of the active audit log file.
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
     /// Log a command execution event.
     pub fn log_command_event(&self, entry: CommandExecutionLog<'_>) -> Result<()> {
         let event = AuditEvent::new(AuditEventType::CommandExecution)
@@ -246,7 +264,7 @@ impl AuditLogger {
 
     /// Rotate the log file
     fn rotate(&self) -> Result<()> {
-        for i in (1..10).rev() {
+        for i in (1..MAX_ROTATED_LOGS).rev() {
             let old_name = format!("{}.{}.log", self.log_path.display(), i);
             let new_name = format!("{}.{}.log", self.log_path.display(), i + 1);
             let _ = std::fs::rename(&old_name, &new_name);
@@ -258,6 +276,37 @@ impl AuditLogger {
     }
 }
 
+/// Load the newest `limit` audit events from `log_path` and its rotated
+/// backups, oldest first. Backups are only read while more events are needed.
+/// Unparseable lines are skipped.
+pub fn load_events(log_path: &Path, limit: usize) -> Result<Vec<AuditEvent>> {
+    let files = std::iter::once(log_path.to_path_buf()).chain(
+        (1..=MAX_ROTATED_LOGS).map(|i| PathBuf::from(format!("{}.{i}.log", log_path.display()))),
+    );
+
+    let mut newest_files_first = Vec::new();
+    let mut loaded = 0;
+    for file in files.filter(|file| file.exists()) {
+        if loaded >= limit {
+            break;
+        }
+        let raw = std::fs::read_to_string(&file)?;
+        let mut events = Vec::new();
+        for line in raw.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            match serde_json::from_str::<AuditEvent>(line) {
+                Ok(event) => events.push(event),
+                Err(err) => tracing::warn!("Skipping malformed audit log line: {err}"),
+            }
+        }
+        loaded += events.len();
+        newest_files_first.push(events);
+    }
+
+    let mut events: Vec<AuditEvent> = newest_files_first.into_iter().rev().flatten().collect();
+    events.drain(..events.len().saturating_sub(limit));
+    Ok(events)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -420,4 +469,36 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn load_events_reads_rotated_logs_oldest_first() -> Result<()> {
+        let tmp = TempDir::new()?;
+        let config = AuditConfig {
+            enabled: true,
+            max_size_mb: 10,
+            ..Default::default()
+        };
+        let logger = AuditLogger::new(config, tmp.path().to_path_buf())?;
+        let mut rx = subscribe();
+
+        let older = AuditEvent::new(AuditEventType::AuthFailure);
+        let newer = AuditEvent::new(AuditEventType::AuthSuccess);
+        logger.log(&older)?;
+        logger.rotate()?;
+        logger.log(&newer)?;
+
+        let events = load_events(logger.log_path(), 10)?;
+        let ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+        assert_eq!(ids, [older.event_id.as_str(), newer.event_id.as_str()]);
+
+        let events = load_events(logger.log_path(), 1)?;
+        let ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+        assert_eq!(ids, [newer.event_id.as_str()]);
+
+        let published = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter(|e| e.event_id == older.event_id || e.event_id == newer.event_id)
+            .count();
+        assert_eq!(published, 2);
+        Ok(())
+    }
 }
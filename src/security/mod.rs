@@ -50,7 +50,7 @@ pub use estop::{EstopLevel, EstopManager, EstopState, ResumeSelector};
 #[allow(unused_imports)]
 pub use otp::OtpValidator;
 #[allow(unused_imports)]
-pub use pairing::PairingGuard;
+pub use pairing::{PairingGuard, TokenGrant, TokenScope};
 pub use policy::{AutonomyLevel, SecurityPolicy};
 #[allow(unused_imports)]
 pub use secrets::SecretStore;
@@ -7,8 +7,16 @@
 //
 // Already-paired tokens are persisted in config so restarts don't require
 // re-pairing.
+//
+// Paired tokens grant full access. Scoped API tokens (`[[gateway.api_tokens]]`)
+// carry a label, a set of scopes, an optional expiry and a revocation stamp,
+// and are issued via `zeroclaw gateway tokens` or `/api/tokens`.
 
+use crate::config::GatewayApiToken;
+use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -25,6 +33,89 @@ const FAILED_ATTEMPT_RETENTION_SECS: u64 = 900; // 15 min
 /// Minimum interval between full sweeps of the failed-attempt map.
 const FAILED_ATTEMPT_SWEEP_INTERVAL_SECS: u64 = 300; // 5 min
 
+/// Access granted by a gateway bearer token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenScope {
+    /// Read-only dashboard access: `GET /api/*` and the event stream.
+    Read,
+    /// Chat endpoints: `/webhook`, `/ws/chat` and the OpenAI-compatible `/v1/*`.
+    Chat,
+    /// Everything, including config, memory and cron changes, token
+    /// management and node control.
+    Admin,
+}
+
+impl TokenScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Chat => "chat",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl std::str::FromStr for TokenScope {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "read" => Ok(Self::Read),
+            "chat" => Ok(Self::Chat),
+            "admin" => Ok(Self::Admin),
+            other => anyhow::bail!("unknown token scope '{other}' (expected read, chat or admin)"),
+        }
+    }
+}
+
+/// The identity and scopes behind an authenticated request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenGrant {
+    /// API token id; `None` for paired tokens and when pairing is disabled.
+    pub token_id: Option<String>,
+    pub label: String,
+    pub scopes: Vec<TokenScope>,
+}
+
+impl TokenGrant {
+    fn full_access(label: &str) -> Self {
+        Self {
+            token_id: None,
+            label: label.to_string(),
+            scopes: vec![TokenScope::Admin],
+        }
+    }
+
+    /// Whether this grant covers `scope`. `admin` covers every scope.
+    pub fn allows(&self, scope: TokenScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| *s == scope || *s == TokenScope::Admin)
+    }
+}
+
+/// Create a new scoped API token. Returns the plaintext token, which is
+/// shown once, and the record to persist (which keeps only its hash).
+pub fn issue_api_token(
+    label: &str,
+    scopes: Vec<TokenScope>,
+    expires_at: Option<DateTime<Utc>>,
+) -> (String, GatewayApiToken) {
+    let token = generate_token();
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    let record = GatewayApiToken {
+        id: format!("tok_{}", &id[..12]),
+        label: label.to_string(),
+        hash: hash_token(&token),
+        scopes,
+        created_at: Utc::now(),
+        expires_at,
+        revoked_at: None,
+    };
+    (token, record)
+}
+
 /// Per-client failed attempt state with optional absolute lockout deadline.
 #[derive(Debug, Clone, Copy)]
 struct FailedAttemptState {
@@ -47,6 +138,8 @@ pub struct PairingGuard {
     pairing_code: Arc<Mutex<Option<String>>>,
     /// Set of SHA-256 hashed bearer tokens (persisted across restarts).
     paired_tokens: Arc<Mutex<HashSet<String>>>,
+    /// Scoped API tokens, including expired and revoked ones.
+    api_tokens: Arc<Mutex<Vec<GatewayApiToken>>>,
     /// Brute-force protection: per-client failed attempt state + last sweep timestamp.
     failed_attempts: Arc<Mutex<(HashMap<String, FailedAttemptState>, Instant)>>,
 }
@@ -80,10 +173,17 @@ impl PairingGuard {
             require_pairing,
             pairing_code: Arc::new(Mutex::new(code)),
             paired_tokens: Arc::new(Mutex::new(tokens)),
+            api_tokens: Arc::new(Mutex::new(Vec::new())),
             failed_attempts: Arc::new(Mutex::new((HashMap::new(), Instant::now()))),
         }
     }
 
+    /// Accept the scoped API tokens from `[[gateway.api_tokens]]`.
+    pub fn with_api_tokens(self, api_tokens: Vec<GatewayApiToken>) -> Self {
+        *self.api_tokens.lock() = api_tokens;
+        self
+    }
+
     /// The one-time pairing code (only set when no tokens exist yet).
     pub fn pairing_code(&self) -> Option<String> {
         self.pairing_code.lock().clone()
@@ -199,20 +299,67 @@ impl PairingGuard {
         }
     }
 
-    /// Check if a bearer token is valid (compares against stored hashes).
-    pub fn is_authenticated(&self, token: &str) -> bool {
+    /// Resolve a bearer token to its grant. Expired and revoked API tokens
+    /// are rejected. With pairing disabled every request gets full access.
+    pub fn authenticate(&self, token: &str) -> Option<TokenGrant> {
         if !self.require_pairing {
-            return true;
+            return Some(TokenGrant::full_access("pairing disabled"));
         }
         let hashed = hash_token(token);
-        let tokens = self.paired_tokens.lock();
-        tokens.contains(&hashed)
+        if self.paired_tokens.lock().contains(&hashed) {
+            return Some(TokenGrant::full_access("paired"));
+        }
+        let now = Utc::now();
+        self.api_tokens
+            .lock()
+            .iter()
+            .find(|t| t.is_active(now) && constant_time_eq(&t.hash, &hashed))
+            .map(|t| TokenGrant {
+                token_id: Some(t.id.clone()),
+                label: t.label.clone(),
+                scopes: t.scopes.clone(),
+            })
+    }
+
+    /// Check if a bearer token is valid (compares against stored hashes).
+    pub fn is_authenticated(&self, token: &str) -> bool {
+        self.authenticate(token).is_some()
     }
 
-    /// Returns true if the gateway is already paired (has at least one token).
+    /// Returns true if the gateway is already paired (has at least one
+    /// paired token or active API token).
     pub fn is_paired(&self) -> bool {
-        let tokens = self.paired_tokens.lock();
-        !tokens.is_empty()
+        let now = Utc::now();
+        !self.paired_tokens.lock().is_empty()
+            || self.api_tokens.lock().iter().any(|t| t.is_active(now))
+    }
+
+    /// Issue a scoped API token; see [`issue_api_token`].
+    pub fn issue_api_token(
+        &self,
+        label: &str,
+        scopes: Vec<TokenScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> (String, GatewayApiToken) {
+        let (token, record) = issue_api_token(label, scopes, expires_at);
+        self.api_tokens.lock().push(record.clone());
+        (token, record)
+    }
+
+    /// Revoke an API token by id. Returns `false` if no such token exists.
+    /// Revoking an already-revoked token keeps the original timestamp.
+    pub fn revoke_api_token(&self, id: &str) -> bool {
+        let mut tokens = self.api_tokens.lock();
+        let Some(token) = tokens.iter_mut().find(|t| t.id == id) else {
+            return false;
+        };
+        token.revoked_at.get_or_insert_with(Utc::now);
+        true
+    }
+
+    /// All API token records (for listing and persisting to config).
+    pub fn api_tokens(&self) -> Vec<GatewayApiToken> {
+        self.api_tokens.lock().clone()
     }
 
     /// Get all paired token hashes (for persisting to config).
@@ -709,4 +856,61 @@ mod tests {
             "Legitimate client should not be locked out by attacker"
         );
     }
+
+    // ── Scoped API tokens ────────────────────────────────────
+
+    #[test]
+    async fn api_token_grants_only_its_scopes() {
+        let guard = PairingGuard::new(true, &[]);
+        let (token, record) = guard.issue_api_token("dashboard", vec![TokenScope::Read], None);
+        assert!(record.id.starts_with("tok_"));
+        assert_eq!(record.hash, hash_token(&token));
+
+        let grant = guard
+            .authenticate(&token)
+            .expect("token should authenticate");
+        assert_eq!(grant.token_id.as_deref(), Some(record.id.as_str()));
+        assert!(grant.allows(TokenScope::Read));
+        assert!(!grant.allows(TokenScope::Chat));
+        assert!(!grant.allows(TokenScope::Admin));
+        assert!(guard.is_paired());
+    }
+
+    #[test]
+    async fn paired_tokens_and_disabled_pairing_grant_admin() {
+        let guard = PairingGuard::new(true, &["zc_legacy".into()]);
+        let grant = guard.authenticate("zc_legacy").unwrap();
+        assert!(grant.allows(TokenScope::Admin) && grant.allows(TokenScope::Chat));
+
+        let open = PairingGuard::new(false, &[]);
+        assert!(open
+            .authenticate("anything")
+            .unwrap()
+            .allows(TokenScope::Admin));
+    }
+
+    #[test]
+    async fn expired_and_revoked_api_tokens_are_rejected() {
+        let (expired, mut expired_record) = issue_api_token("old", vec![TokenScope::Chat], None);
+        expired_record.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        let guard = PairingGuard::new(true, &[]).with_api_tokens(vec![expired_record]);
+        assert!(!guard.is_authenticated(&expired));
+        assert!(!guard.is_paired());
+
+        let (token, record) = guard.issue_api_token("bot", vec![TokenScope::Chat], None);
+        assert!(guard.is_authenticated(&token));
+        assert!(guard.revoke_api_token(&record.id));
+        assert!(!guard.is_authenticated(&token));
+        assert!(!guard.revoke_api_token("tok_missing"));
+
+        let stored = guard.api_tokens();
+        assert_eq!(stored.len(), 2);
+        assert!(stored[1].revoked_at.is_some());
+    }
+
+    #[test]
+    async fn token_scope_parses_case_insensitively() {
+        assert_eq!("Admin".parse::<TokenScope>().unwrap(), TokenScope::Admin);
+        assert!("root".parse::<TokenScope>().is_err());
+    }
 }
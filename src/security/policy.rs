@@ -106,6 +106,7 @@ pub struct SecurityPolicy {
     pub require_approval_for_medium_risk: bool,
     pub block_high_risk_commands: bool,
     pub shell_env_passthrough: Vec<String>,
+    pub allowed_mqtt_topics: Vec<String>,
     pub tracker: ActionTracker,
 }
 
@@ -158,11 +159,30 @@ impl Default for SecurityPolicy {
             require_approval_for_medium_risk: true,
             block_high_risk_commands: true,
             shell_env_passthrough: vec![],
+            allowed_mqtt_topics: Vec::new(),
             tracker: ActionTracker::new(),
         }
     }
 }
 
+/// Match a concrete topic against an MQTT topic filter.
+fn mqtt_filter_matches(filter: &str, topic: &str) -> bool {
+    if filter.is_empty() {
+        return false;
+    }
+    let mut topic_levels = topic.split('/');
+    for filter_level in filter.split('/') {
+        if filter_level == "#" {
+            return true;
+        }
+        match topic_levels.next() {
+            Some(level) if filter_level == "+" || filter_level == level => {}
+            _ => return false,
+        }
+    }
+    topic_levels.next().is_none()
+}
+
 fn home_dir() -> Option<PathBuf> {
     std::env::var_os("HOME").map(PathBuf::from)
 }
@@ -1012,6 +1032,20 @@ impl SecurityPolicy {
         )
     }
 
+    // ── MQTT Publish Gating ────────────────────────────────────────────
+    // Publishing actuates real devices, so the topic allowlist is deny-by-
+    // default. Entries are MQTT topic filters (`+` single level, `#` tail).
+
+    /// Check if the agent may publish to a concrete MQTT topic.
+    pub fn is_mqtt_topic_allowed(&self, topic: &str) -> bool {
+        if topic.is_empty() || topic.contains(['+', '#', '\0']) {
+            return false;
+        }
+        self.allowed_mqtt_topics
+            .iter()
+            .any(|filter| mqtt_filter_matches(filter.trim(), topic))
+    }
+
     /// Check if autonomy level permits any action at all
     pub fn can_act(&self) -> bool {
         self.autonomy != AutonomyLevel::ReadOnly
@@ -1089,6 +1123,7 @@ impl SecurityPolicy {
             require_approval_for_medium_risk: autonomy_config.require_approval_for_medium_risk,
             block_high_risk_commands: autonomy_config.block_high_risk_commands,
             shell_env_passthrough: autonomy_config.shell_env_passthrough.clone(),
+            allowed_mqtt_topics: autonomy_config.allowed_mqtt_topics.clone(),
             tracker: ActionTracker::new(),
         }
     }
@@ -2363,4 +2398,62 @@ mod tests {
             "URL-encoded parent dir traversal must be blocked"
         );
     }
+
+    // ── MQTT topic allowlist ────────────────────────────────
+
+    fn mqtt_policy(topics: &[&str]) -> SecurityPolicy {
+        SecurityPolicy {
+            allowed_mqtt_topics: topics.iter().map(|t| (*t).to_string()).collect(),
+            ..SecurityPolicy::default()
+        }
+    }
+
+    #[test]
+    fn mqtt_topic_denied_by_default() {
+        let p = default_policy();
+        assert!(!p.is_mqtt_topic_allowed("home/livingroom/light/set"));
+    }
+
+    #[test]
+    fn mqtt_topic_exact_match_allowed() {
+        let p = mqtt_policy(&["home/livingroom/light/set"]);
+        assert!(p.is_mqtt_topic_allowed("home/livingroom/light/set"));
+        assert!(!p.is_mqtt_topic_allowed("home/livingroom/light"));
+        assert!(!p.is_mqtt_topic_allowed("home/livingroom/light/set/extra"));
+    }
+
+    #[test]
+    fn mqtt_topic_single_level_wildcard() {
+        let p = mqtt_policy(&["home/+/light/set"]);
+        assert!(p.is_mqtt_topic_allowed("home/kitchen/light/set"));
+        assert!(!p.is_mqtt_topic_allowed("home/kitchen/fan/set"));
+        assert!(!p.is_mqtt_topic_allowed("home/light/set"));
+    }
+
+    #[test]
+    fn mqtt_topic_multi_level_wildcard() {
+        let p = mqtt_policy(&["devices/#"]);
+        assert!(p.is_mqtt_topic_allowed("devices/pump"));
+        assert!(p.is_mqtt_topic_allowed("devices/pump/1/cmd"));
+        assert!(!p.is_mqtt_topic_allowed("sensors/pump"));
+    }
+
+    #[test]
+    fn mqtt_topic_rejects_wildcards_in_publish_topic() {
+        let p = mqtt_policy(&["#"]);
+        assert!(p.is_mqtt_topic_allowed("anything/goes"));
+        assert!(!p.is_mqtt_topic_allowed("devices/+/cmd"));
+        assert!(!p.is_mqtt_topic_allowed("devices/#"));
+        assert!(!p.is_mqtt_topic_allowed(""));
+    }
+
+    #[test]
+    fn from_config_maps_mqtt_topics() {
+        let autonomy_config = crate::config::AutonomyConfig {
+            allowed_mqtt_topics: vec!["devices/+/cmd".into()],
+            ..crate::config::AutonomyConfig::default()
+        };
+        let policy = SecurityPolicy::from_config(&autonomy_config, Path::new("/tmp/ws"));
+        assert!(policy.is_mqtt_topic_allowed("devices/pump/cmd"));
+    }
 }
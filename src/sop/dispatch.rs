@@ -62,28 +62,33 @@ fn action_label(action: &SopRunAction) -> &'static str {
 /// 1. Lock → `match_trigger` → collect SOP names → drop lock
 /// 2. Lock → for each name: `start_run` → collect results → drop lock
 /// 3. Async (no lock): audit each started run
-#[tracing::instrument(skip(engine, audit), fields(source = %event.source, topic = ?event.topic))]
 pub async fn dispatch_sop_event(
     engine: &Arc<Mutex<SopEngine>>,
     audit: &SopAuditLogger,
     event: SopEvent,
 ) -> Vec<DispatchResult> {
     // Phase 1: match
-    let matched_names: Vec<String> = match engine.lock() {
-        Ok(eng) => eng
-            .match_trigger(&event)
-            .iter()
-            .map(|s| s.name.clone())
-            .collect(),
+    let matched = engine
+        .lock()
+        .map(|eng| {
+            eng.match_trigger(&event)
+                .iter()
+                .map(|s| s.name.clone())
+                .collect::<Vec<String>>()
+        })
+        .map_err(|e| e.to_string());
+    let matched_names = match matched {
+        Ok(names) => names,
         Err(e) => {
-            crate::health::mark_component_error("sop_dispatch", format!("lock poisoned: {e}"));
+            crate::health::mark_component_error("sop_dispatch", format!("lock poisoned: {e}"))
+                .await;
             warn!("SOP dispatch: engine lock poisoned during match phase: {e}");
             return vec![];
         }
     };
 
     if matched_names.is_empty() {
-        debug!("SOP dispatch: no match for event");
+        debug!(source = %event.source, topic = ?event.topic, "SOP dispatch: no match for event");
         return vec![DispatchResult::NoMatch];
     }
 
@@ -93,50 +98,54 @@ pub async fn dispatch_sop_event(
         matched_names
     );
 
-    // Phase 2: start runs
-    let mut results = Vec::new();
-    let mut started_runs: Vec<SopRun> = Vec::new();
-
-    {
-        let mut eng = match engine.lock() {
-            Ok(e) => e,
-            Err(e) => {
-                crate::health::mark_component_error("sop_dispatch", format!("lock poisoned: {e}"));
-                warn!("SOP dispatch: engine lock poisoned during start phase: {e}");
-                return vec![];
-            }
-        };
-
-        for sop_name in &matched_names {
-            match eng.start_run(sop_name, event.clone()) {
-                Ok(action) => {
-                    // Extract run_id from the action (authoritative source)
-                    let run_id = extract_run_id_from_action(&action).to_string();
-                    // Snapshot the run for audit (must be done under lock)
-                    if let Some(run) = eng.active_runs().get(&run_id) {
-                        started_runs.push(run.clone());
+    // Phase 2: start runs. The guard must not live across an await, so the
+    // whole phase runs inside the closure.
+    let started = engine
+        .lock()
+        .map(|mut eng| {
+            let mut results = Vec::new();
+            let mut started_runs: Vec<SopRun> = Vec::new();
+            for sop_name in &matched_names {
+                match eng.start_run(sop_name, event.clone()) {
+                    Ok(action) => {
+                        // Extract run_id from the action (authoritative source)
+                        let run_id = extract_run_id_from_action(&action).to_string();
+                        // Snapshot the run for audit (must be done under lock)
+                        if let Some(run) = eng.active_runs().get(&run_id) {
+                            started_runs.push(run.clone());
+                        }
+                        info!(
+                            "SOP dispatch: started '{}' run {run_id} (action: {})",
+                            sop_name,
+                            action_label(&action),
+                        );
+                        results.push(DispatchResult::Started {
+                            run_id,
+                            sop_name: sop_name.clone(),
+                            action,
+                        });
+                    }
+                    Err(e) => {
+                        info!("SOP dispatch: skipped '{}': {e}", sop_name);
+                        results.push(DispatchResult::Skipped {
+                            sop_name: sop_name.clone(),
+                            reason: e.to_string(),
+                        });
                     }
-                    info!(
-                        "SOP dispatch: started '{}' run {run_id} (action: {})",
-                        sop_name,
-                        action_label(&action),
-                    );
-                    results.push(DispatchResult::Started {
-                        run_id,
-                        sop_name: sop_name.clone(),
-                        action,
-                    });
-                }
-                Err(e) => {
-                    info!("SOP dispatch: skipped '{}': {e}", sop_name);
-                    results.push(DispatchResult::Skipped {
-                        sop_name: sop_name.clone(),
-                        reason: e.to_string(),
-                    });
                 }
             }
+            (results, started_runs)
+        })
+        .map_err(|e| e.to_string());
+    let (results, started_runs) = match started {
+        Ok(started) => started,
+        Err(e) => {
+            crate::health::mark_component_error("sop_dispatch", format!("lock poisoned: {e}"))
+                .await;
+            warn!("SOP dispatch: engine lock poisoned during start phase: {e}");
+            return vec![];
         }
-    } // lock dropped
+    };
 
     // Phase 3: audit (async, no lock)
     for run in &started_runs {
@@ -145,7 +154,7 @@ pub async fn dispatch_sop_event(
         }
     }
 
-    crate::health::mark_component_ok("sop_dispatch");
+    crate::health::mark_component_ok("sop_dispatch").await;
     results
 }
 
@@ -250,7 +259,7 @@ impl SopCronCache {
             for trigger in &sop.triggers {
                 if let super::types::SopTrigger::Cron { expression } = trigger {
                     // Normalize 5-field crontab to 6-field (prepend seconds)
-                    let normalized = match crate::cron::schedule::normalize_expression(expression) {
+                    let normalized = match crate::cron::normalize_expression(expression) {
                         Ok(n) => n,
                         Err(e) => {
                             warn!(
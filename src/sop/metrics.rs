@@ -621,12 +621,17 @@ mod tests {
     use super::*;
     use crate::sop::types::{SopEvent, SopStepResult, SopTriggerSource};
 
+    /// Fixture timestamps stay inside the rolling metric windows.
+    fn minutes_ago(minutes: i64) -> String {
+        (chrono::Utc::now() - chrono::Duration::minutes(minutes)).to_rfc3339()
+    }
+
     fn make_event() -> SopEvent {
         SopEvent {
             source: SopTriggerSource::Manual,
             topic: None,
             payload: None,
-            timestamp: "2026-02-19T12:00:00Z".into(),
+            timestamp: minutes_ago(10),
         }
     }
 
@@ -644,8 +649,8 @@ mod tests {
             status,
             current_step: total_steps,
             total_steps,
-            started_at: "2026-02-19T12:00:00Z".into(),
-            completed_at: Some("2026-02-19T12:05:00Z".into()),
+            started_at: minutes_ago(10),
+            completed_at: Some(minutes_ago(5)),
             step_results,
             waiting_since: None,
         }
@@ -656,8 +661,8 @@ mod tests {
             step_number: number,
             status,
             output: format!("Step {number}"),
-            started_at: "2026-02-19T12:00:00Z".into(),
-            completed_at: Some("2026-02-19T12:01:00Z".into()),
+            started_at: minutes_ago(10),
+            completed_at: Some(minutes_ago(9)),
         }
     }
 
@@ -1241,7 +1246,7 @@ mod tests {
             status: SopRunStatus::Running,
             current_step: 1,
             total_steps: 3,
-            started_at: "2026-02-19T12:00:00Z".into(),
+            started_at: minutes_ago(10),
             completed_at: None,
             step_results: vec![],
             waiting_since: None,
@@ -1338,7 +1343,7 @@ mod tests {
             status: SopRunStatus::Running,
             current_step: 1,
             total_steps: 3,
-            started_at: "2026-02-19T12:00:00Z".into(),
+            started_at: minutes_ago(10),
             completed_at: None,
             step_results: vec![],
             waiting_since: None,
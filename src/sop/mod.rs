@@ -5,6 +5,8 @@ pub mod engine;
 #[cfg(feature = "ampersona-gates")]
 pub mod gates;
 pub mod metrics;
+#[cfg(feature = "channel-mqtt")]
+pub mod mqtt;
 pub mod types;
 
 pub use audit::SopAuditLogger;
@@ -20,10 +22,75 @@ pub use types::{
 
 use anyhow::Result;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 use tracing::warn;
 
+use crate::config::Config;
+use crate::memory::Memory;
+use dispatch::{check_sop_cron_triggers, process_headless_results, SopCronCache};
+
 use types::{SopManifest, SopMeta};
 
+// ── Shared runtime ──────────────────────────────────────────────
+
+/// Engine, audit logger and metrics shared by every trigger source (MQTT,
+/// webhook, cron) and the `sop_*` tools running in this process.
+#[derive(Clone)]
+pub struct SopRuntime {
+    pub engine: Arc<Mutex<SopEngine>>,
+    pub audit: Arc<SopAuditLogger>,
+    pub metrics: Arc<SopMetricsCollector>,
+}
+
+static SHARED_RUNTIME: OnceLock<SopRuntime> = OnceLock::new();
+
+impl SopRuntime {
+    /// Build a runtime and load SOPs from the workspace.
+    pub fn new(config: &Config, memory: Arc<dyn Memory>) -> Self {
+        let mut engine = SopEngine::new(config.sop.clone());
+        engine.reload(&config.workspace_dir);
+        Self {
+            engine: Arc::new(Mutex::new(engine)),
+            audit: Arc::new(SopAuditLogger::new(memory)),
+            metrics: Arc::new(SopMetricsCollector::new()),
+        }
+    }
+
+    /// Process-wide runtime if one has been initialized.
+    pub fn shared() -> Option<Self> {
+        SHARED_RUNTIME.get().cloned()
+    }
+
+    /// Start runs for cron triggers that fired since `last_check` and
+    /// resolve approval timeouts. Called from the cron scheduler tick.
+    pub async fn poll(&self, cache: &SopCronCache, last_check: &mut chrono::DateTime<chrono::Utc>) {
+        let results = check_sop_cron_triggers(&self.engine, &self.audit, cache, last_check).await;
+        process_headless_results(&results).await;
+
+        let timed_out = match self.engine.lock() {
+            Ok(mut engine) => engine.check_approval_timeouts(),
+            Err(e) => {
+                warn!("SOP engine lock poisoned during timeout check: {e}");
+                return;
+            }
+        };
+        if !timed_out.is_empty() {
+            tracing::info!(
+                "SOP approval timeout auto-approved {} run(s)",
+                timed_out.len()
+            );
+        }
+    }
+}
+
+/// Process-wide runtime, created from the first caller's config so the
+/// daemon's trigger sources and every tool registry share one engine.
+pub fn shared_runtime(config: &Config, memory: Arc<dyn Memory>) -> SopRuntime {
+    SHARED_RUNTIME
+        .get_or_init(|| SopRuntime::new(config, memory))
+        .clone()
+}
+
 // ── SOP directory helpers ───────────────────────────────────────
 
 /// Return the default SOPs directory: `<workspace>/sops`.
@@ -813,4 +880,53 @@ type = "manual"
         ));
         assert!(matches!(manifest.triggers[4], SopTrigger::Manual));
     }
+
+    #[tokio::test]
+    async fn runtime_poll_starts_due_cron_sop() {
+        let dir = tempfile::tempdir().unwrap();
+        let sop_dir = dir.path().join("sops").join("nightly");
+        fs::create_dir_all(&sop_dir).unwrap();
+        fs::write(
+            sop_dir.join("SOP.toml"),
+            r#"
+[sop]
+name = "nightly"
+description = "Every-minute cron SOP"
+execution_mode = "auto"
+
+[[triggers]]
+type = "cron"
+expression = "* * * * *"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            sop_dir.join("SOP.md"),
+            "## Steps\n\n1. **Check** — Look around.\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            workspace_dir: dir.path().to_path_buf(),
+            ..Config::default()
+        };
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let memory: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, dir.path(), None).unwrap());
+        let runtime = SopRuntime::new(&config, memory);
+        let cache = SopCronCache::from_engine(&runtime.engine);
+        let mut last_check = chrono::Utc::now() - chrono::Duration::minutes(2);
+
+        runtime.poll(&cache, &mut last_check).await;
+
+        let engine = runtime.engine.lock().unwrap();
+        assert_eq!(engine.active_runs().len(), 1);
+        assert_eq!(
+            engine.active_runs().values().next().unwrap().sop_name,
+            "nightly"
+        );
+    }
 }
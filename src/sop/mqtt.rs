@@ -0,0 +1,76 @@
+//! MQTT → SOP event fan-in listener.
+//!
+//! Routes publishes on `mqtt.topics` to the SOP engine via
+//! `dispatch_sop_event`. Chat traffic on the request/reply topics is
+//! handled separately by [`crate::channels::mqtt::MqttChannel`].
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use rumqttc::{AsyncClient, Event, Packet};
+use tracing::{info, warn};
+
+use crate::channels::mqtt::{mqtt_options, qos_from_level};
+use crate::config::MqttConfig;
+use crate::sop::audit::SopAuditLogger;
+use crate::sop::dispatch::{dispatch_sop_event, process_headless_results};
+use crate::sop::engine::{now_iso8601, SopEngine};
+use crate::sop::types::{SopEvent, SopTriggerSource};
+
+const RECONNECT_BACKOFF_SECS: u64 = 5;
+
+/// Run the MQTT SOP listener loop.
+///
+/// Subscribes to configured topics on every connect and dispatches
+/// incoming publishes to the SOP engine. Runs until cancelled.
+pub async fn run_mqtt_sop_listener(
+    config: &MqttConfig,
+    engine: Arc<Mutex<SopEngine>>,
+    audit: Arc<SopAuditLogger>,
+) -> Result<()> {
+    config.validate()?;
+
+    let sop_client_id = format!("{}-sop", config.client_id);
+    let (client, mut eventloop) = AsyncClient::new(mqtt_options(config, &sop_client_id), 64);
+    let qos = qos_from_level(config.qos);
+
+    loop {
+        match eventloop.poll().await {
+            Ok(Event::Incoming(Packet::Publish(msg))) => {
+                let topic = msg.topic.clone();
+                let payload = String::from_utf8_lossy(&msg.payload).to_string();
+
+                let event = SopEvent {
+                    source: SopTriggerSource::Mqtt,
+                    topic: Some(topic),
+                    payload: Some(payload),
+                    timestamp: now_iso8601(),
+                };
+
+                let results = dispatch_sop_event(&engine, &audit, event).await;
+                process_headless_results(&results).await;
+            }
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                // Subscribe on every (re)connect: clean sessions drop
+                // subscriptions when the broker connection is lost.
+                for topic in &config.topics {
+                    client.subscribe(topic, qos).await?;
+                    info!("MQTT SOP listener: subscribed to '{topic}'");
+                }
+                crate::health::mark_component_ok("sop_mqtt").await;
+                info!("MQTT SOP listener: connected to broker");
+            }
+            Ok(_) => {
+                // Other events (PingResp, SubAck, etc.) — ignore
+            }
+            Err(e) => {
+                crate::health::mark_component_error("sop_mqtt", e.to_string()).await;
+                warn!("MQTT SOP listener: connection error: {e}");
+                // The next poll reconnects; back off so a down broker is
+                // not hammered.
+                tokio::time::sleep(Duration::from_secs(RECONNECT_BACKOFF_SECS)).await;
+            }
+        }
+    }
+}
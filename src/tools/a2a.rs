@@ -0,0 +1,513 @@
+//! Client for remote Agent-to-Agent (A2A) protocol agents.
+//!
+//! Talks JSON-RPC to the agents configured under `[a2a.agents.<name>]`, which
+//! may be other ZeroClaw gateways or agents built on any A2A framework.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::A2aRemoteAgentConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const A2A_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+pub struct A2aTool {
+    security: Arc<SecurityPolicy>,
+    agents: HashMap<String, A2aRemoteAgentConfig>,
+}
+
+impl A2aTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        agents: HashMap<String, A2aRemoteAgentConfig>,
+    ) -> Self {
+        Self { security, agents }
+    }
+
+    fn agent(&self, args: &Value) -> Result<(&str, &A2aRemoteAgentConfig), String> {
+        let name = args
+            .get("agent")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| "Missing 'agent' parameter".to_string())?;
+        self.agents
+            .get_key_value(name)
+            .map(|(name, agent)| (name.as_str(), agent))
+            .ok_or_else(|| {
+                let mut available: Vec<&str> = self.agents.keys().map(String::as_str).collect();
+                available.sort_unstable();
+                format!(
+                    "Unknown A2A agent '{name}'. Configured agents: {}",
+                    available.join(", ")
+                )
+            })
+    }
+
+    fn client(agent: &A2aRemoteAgentConfig) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.a2a",
+            agent.timeout_secs.max(1),
+            A2A_CONNECT_TIMEOUT_SECS,
+        )
+    }
+
+    fn authorize(
+        request: reqwest::RequestBuilder,
+        agent: &A2aRemoteAgentConfig,
+    ) -> reqwest::RequestBuilder {
+        match agent.token.as_deref().map(str::trim) {
+            Some(token) if !token.is_empty() => request.bearer_auth(token),
+            _ => request,
+        }
+    }
+
+    async fn rpc(
+        &self,
+        agent: &A2aRemoteAgentConfig,
+        method: &str,
+        params: Value,
+    ) -> anyhow::Result<Value> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": uuid::Uuid::new_v4().to_string(),
+            "method": method,
+            "params": params,
+        });
+        let response = Self::authorize(Self::client(agent).post(&agent.url), agent)
+            .json(&body)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!(
+                "Remote agent returned HTTP {status}: {}",
+                crate::util::truncate_with_ellipsis(&text, 300)
+            );
+        }
+        let reply: Value = serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("Remote agent returned invalid JSON-RPC: {e}"))?;
+        if let Some(error) = reply.get("error") {
+            anyhow::bail!(
+                "Remote agent error {}: {}",
+                error.get("code").and_then(Value::as_i64).unwrap_or(0),
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+            );
+        }
+        reply
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Remote agent response has no result"))
+    }
+
+    async fn fetch_card(&self, agent: &A2aRemoteAgentConfig) -> anyhow::Result<Value> {
+        let endpoint = reqwest::Url::parse(&agent.url)
+            .map_err(|e| anyhow::anyhow!("Invalid A2A agent URL '{}': {e}", agent.url))?;
+        let client = Self::client(agent);
+        let mut last_status = None;
+        for path in ["/.well-known/agent-card.json", "/.well-known/agent.json"] {
+            let url = endpoint.join(path)?;
+            let response = Self::authorize(client.get(url), agent).send().await?;
+            if response.status().is_success() {
+                return Ok(response.json().await?);
+            }
+            last_status = Some(response.status());
+        }
+        anyhow::bail!(
+            "Agent card not found at {} (HTTP {})",
+            endpoint.join("/.well-known/agent-card.json")?,
+            last_status.map(|s| s.as_u16()).unwrap_or_default()
+        )
+    }
+}
+
+fn parts_text(parts: Option<&Value>) -> Vec<String> {
+    parts
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|part| match part.get("text").and_then(Value::as_str) {
+            Some(text) => Some(text.to_string()),
+            None => part.get("data").map(Value::to_string),
+        })
+        .collect()
+}
+
+/// Human-readable summary of a `Task` or `Message` result.
+fn render_result(agent_name: &str, result: &Value) -> String {
+    if result.get("kind").and_then(Value::as_str) == Some("message") {
+        return format!(
+            "Agent: {agent_name}\n\n{}",
+            parts_text(result.get("parts")).join("\n")
+        );
+    }
+
+    let state = result
+        .pointer("/status/state")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let task_id = result.get("id").and_then(Value::as_str).unwrap_or("");
+    let mut lines = vec![
+        format!("Agent: {agent_name}"),
+        format!("State: {state}"),
+        format!("Task: {task_id}"),
+    ];
+    if let Some(context_id) = result.get("contextId").and_then(Value::as_str) {
+        lines.push(format!("Context: {context_id}"));
+    }
+
+    let mut reply: Vec<String> = result
+        .get("artifacts")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .flat_map(|artifact| parts_text(artifact.get("parts")))
+        .collect();
+    if reply.is_empty() {
+        reply = parts_text(result.pointer("/status/message/parts"));
+    }
+    if !reply.is_empty() {
+        lines.push(String::new());
+        lines.push(reply.join("\n"));
+    }
+    if matches!(state, "submitted" | "working") {
+        lines.push(String::new());
+        lines.push(format!(
+            "The task is still running. Check it later with action \"status\" and task_id \"{task_id}\"."
+        ));
+    }
+    lines.join("\n")
+}
+
+fn render_card(agent_name: &str, card: &Value) -> String {
+    let field = |key: &str| card.get(key).and_then(Value::as_str).unwrap_or("");
+    let mut lines = vec![
+        format!("Agent: {agent_name}"),
+        format!("Name: {}", field("name")),
+        format!("Description: {}", field("description")),
+        format!("Endpoint: {}", field("url")),
+        format!(
+            "Streaming: {}",
+            card.pointer("/capabilities/streaming")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        ),
+    ];
+    let skills: Vec<String> = card
+        .get("skills")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|skill| {
+            let name = skill
+                .get("name")
+                .or_else(|| skill.get("id"))
+                .and_then(Value::as_str)
+                .unwrap_or("?");
+            match skill.get("description").and_then(Value::as_str) {
+                Some(description) => format!("- {name}: {description}"),
+                None => format!("- {name}"),
+            }
+        })
+        .collect();
+    if !skills.is_empty() {
+        lines.push("Skills:".into());
+        lines.extend(skills);
+    }
+    lines.join("\n")
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for A2aTool {
+    fn name(&self) -> &str {
+        "a2a"
+    }
+
+    fn description(&self) -> &str {
+        "Call remote agents over the Agent-to-Agent (A2A) protocol. Actions: \
+         'list' configured agents, 'discover' an agent's card and skills, \
+         'send' a message (pass context_id to continue a conversation), \
+         'status' of a task by task_id."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        let mut names: Vec<&str> = self.agents.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "discover", "send", "status"],
+                    "description": "Operation to perform"
+                },
+                "agent": {
+                    "type": "string",
+                    "enum": names,
+                    "description": "Configured remote agent name"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Message to send (action 'send')"
+                },
+                "context_id": {
+                    "type": "string",
+                    "description": "Context ID from an earlier reply, to continue that conversation"
+                },
+                "task_id": {
+                    "type": "string",
+                    "description": "Task ID to check (action 'status')"
+                },
+                "wait": {
+                    "type": "boolean",
+                    "description": "Wait for the remote task to finish (default true); false returns immediately"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args.get("action").and_then(Value::as_str).unwrap_or("");
+
+        if action == "list" {
+            let mut names: Vec<&String> = self.agents.keys().collect();
+            names.sort_unstable();
+            let output = names
+                .into_iter()
+                .map(|name| format!("{name}: {}", self.agents[name].url))
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        }
+
+        let (agent_name, agent) = match self.agent(&args) {
+            Ok(found) => found,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        let result = match action {
+            "discover" => self
+                .fetch_card(agent)
+                .await
+                .map(|card| render_card(agent_name, &card)),
+            "send" => {
+                let Some(message) = args
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(str::trim)
+                    .filter(|message| !message.is_empty())
+                else {
+                    return Ok(failure("Missing 'message' parameter"));
+                };
+                if let Err(error) = self
+                    .security
+                    .enforce_tool_operation(ToolOperation::Act, "a2a")
+                {
+                    return Ok(failure(error));
+                }
+
+                let mut outgoing = json!({
+                    "role": "user",
+                    "parts": [{"kind": "text", "text": message}],
+                    "messageId": uuid::Uuid::new_v4().to_string(),
+                    "kind": "message",
+                });
+                if let Some(context_id) = args.get("context_id").and_then(Value::as_str) {
+                    outgoing["contextId"] = json!(context_id);
+                }
+                let blocking = args.get("wait").and_then(Value::as_bool).unwrap_or(true);
+                self.rpc(
+                    agent,
+                    "message/send",
+                    json!({"message": outgoing, "configuration": {"blocking": blocking}}),
+                )
+                .await
+                .map(|result| render_result(agent_name, &result))
+            }
+            "status" => {
+                let Some(task_id) = args.get("task_id").and_then(Value::as_str) else {
+                    return Ok(failure("Missing 'task_id' parameter"));
+                };
+                self.rpc(agent, "tasks/get", json!({"id": task_id}))
+                    .await
+                    .map(|result| render_result(agent_name, &result))
+            }
+            other => {
+                return Ok(failure(format!(
+                    "Unknown action '{other}'. Use list, discover, send or status"
+                )))
+            }
+        };
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => failure(e.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_security(autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(url: String, autonomy: AutonomyLevel) -> A2aTool {
+        let agents = HashMap::from([(
+            "research".to_string(),
+            A2aRemoteAgentConfig {
+                url,
+                token: Some("zc_token".into()),
+                timeout_secs: 5,
+            },
+        )]);
+        A2aTool::new(test_security(autonomy), agents)
+    }
+
+    #[tokio::test]
+    async fn send_posts_message_and_renders_task_reply() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/a2a"))
+            .and(header("authorization", "Bearer zc_token"))
+            .and(body_partial_json(json!({
+                "method": "message/send",
+                "params": {"message": {"contextId": "ctx-9", "parts": [{"kind": "text", "text": "Find papers"}]}}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "result": {
+                    "kind": "task",
+                    "id": "task-1",
+                    "contextId": "ctx-9",
+                    "status": {"state": "completed"},
+                    "artifacts": [{"artifactId": "a", "parts": [{"kind": "text", "text": "Three papers found."}]}]
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tool = test_tool(format!("{}/a2a", server.uri()), AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({
+                "action": "send",
+                "agent": "research",
+                "message": "Find papers",
+                "context_id": "ctx-9"
+            }))
+            .await
+            .unwrap();
+
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("State: completed"));
+        assert!(result.output.contains("Context: ctx-9"));
+        assert!(result.output.contains("Three papers found."));
+    }
+
+    #[tokio::test]
+    async fn remote_errors_and_unknown_agents_fail() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": "1",
+                "error": {"code": -32001, "message": "Task not found: nope"}
+            })))
+            .mount(&server)
+            .await;
+
+        let tool = test_tool(format!("{}/a2a", server.uri()), AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"action": "status", "agent": "research", "task_id": "nope"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("-32001"));
+
+        let result = tool
+            .execute(json!({"action": "status", "agent": "other", "task_id": "x"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Unknown A2A agent 'other'"));
+    }
+
+    #[tokio::test]
+    async fn discover_falls_back_to_legacy_card_path() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/.well-known/agent.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "name": "Researcher",
+                "description": "Finds papers",
+                "url": "https://research.example.com/a2a",
+                "capabilities": {"streaming": true},
+                "skills": [{"id": "search", "name": "Search", "description": "Literature search"}]
+            })))
+            .mount(&server)
+            .await;
+
+        let tool = test_tool(format!("{}/a2a", server.uri()), AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({"action": "discover", "agent": "research"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Name: Researcher"));
+        assert!(result.output.contains("- Search: Literature search"));
+    }
+
+    #[tokio::test]
+    async fn send_is_blocked_in_read_only_mode() {
+        let tool = test_tool("http://127.0.0.1:9/a2a".into(), AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({"action": "send", "agent": "research", "message": "hi"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn running_tasks_point_to_status_action() {
+        let output = render_result(
+            "research",
+            &json!({"kind": "task", "id": "t-1", "status": {"state": "working"}}),
+        );
+        assert!(output.contains("State: working"));
+        assert!(output.contains("task_id \"t-1\""));
+    }
+}
@@ -1071,7 +1071,7 @@ impl Tool for BrowserTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -74,7 +74,7 @@ impl Tool for BrowserOpenTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -162,7 +162,7 @@ impl Tool for ContentSearchTool {
             .min(MAX_RESULTS);
 
         // --- Rate limit check ---
-        if self.security.is_rate_limited().await {
+        if self.security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -198,7 +198,7 @@ impl Tool for ContentSearchTool {
         }
 
         // Record action to consume rate limit budget
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -1,6 +1,8 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::Config;
-use crate::cron::{self, DeliveryConfig, JobType, Schedule, SessionTarget};
+use crate::cron::{
+    self, CatchUpPolicy, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -27,7 +29,7 @@ impl CronAddTool {
             });
         }
 
-        if self.security.is_rate_limited().await {
+        if self.security.is_rate_limited() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -35,7 +37,7 @@ impl CronAddTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -55,10 +57,12 @@ impl Tool for CronAddTool {
 
     fn description(&self) -> &str {
         "Create a scheduled cron job (shell or agent) with cron/at/every schedules. \
-         Use job_type='agent' with a prompt to run the AI agent on schedule. \
+         Use job_type='agent' with a prompt to run the AI agent on schedule, or on an event \
+         (kind 'watch' for new/changed workspace files, 'message' for matching channel messages, 'state' for Home Assistant state changes). \
          To deliver output to a channel (Discord, Telegram, Slack, Mattermost), set \
          delivery={\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id_or_chat_id>\"}. \
-         This is the preferred tool for sending scheduled/delayed messages to users via channels."
+         This is the preferred tool for sending scheduled/delayed messages to users via channels. \
+         Chain jobs with on_success/on_failure (ids of existing jobs to run afterwards)."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -68,7 +72,7 @@ impl Tool for CronAddTool {
                 "name": { "type": "string" },
                 "schedule": {
                     "type": "object",
-                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms}"
+                    "description": "Schedule object: {kind:'cron',expr,tz?} | {kind:'at',at} | {kind:'every',every_ms} | {kind:'watch',path,pattern?} (agent jobs; runs when a file under the workspace-relative path is created or modified) | {kind:'message',channel?,sender?,keyword?,regex?} (agent jobs; runs when an inbound channel message matches) | {kind:'state',entity_id,from?,to?} (agent jobs; runs when a Home Assistant entity matching the entity_id glob changes state). Event prompts may use {path}, {change}, {channel}, {sender}, {message}, {entity_id}, {from}, {to}"
                 },
                 "job_type": { "type": "string", "enum": ["shell", "agent"] },
                 "command": { "type": "string" },
@@ -80,12 +84,35 @@ impl Tool for CronAddTool {
                     "description": "Delivery config to send job output to a channel. Example: {\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id>\"}",
                     "properties": {
                         "mode": { "type": "string", "enum": ["none", "announce"], "description": "Set to 'announce' to deliver output to a channel" },
-                        "channel": { "type": "string", "enum": ["telegram", "discord", "slack", "mattermost"], "description": "Channel type to deliver to" },
-                        "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, etc." },
+                        "channel": { "type": "string", "description": "Configured channel to deliver to (telegram, discord, slack, matrix, signal, email, irc, ...)" },
+                        "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, email address, etc." },
+                        "targets": {
+                            "type": "array",
+                            "description": "Additional recipients, each {channel, to}",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "channel": { "type": "string" },
+                                    "to": { "type": "string" }
+                                },
+                                "required": ["channel", "to"]
+                            }
+                        },
+                        "template": { "type": "string", "description": "Message template with {job_name}, {job_id}, {status}, {duration}, {output} placeholders; defaults to the raw output" },
+                        "max_retries": { "type": "integer", "minimum": 0, "description": "Retries per target after a failed send (default 0)" },
+                        "when": { "type": "string", "enum": ["always", "on_failure", "on_change"], "description": "Announce every run (default), only failed runs, or only runs whose output differs from the previous run" },
                         "best_effort": { "type": "boolean", "description": "If true, delivery failure does not fail the job" }
                     }
                 },
                 "delete_after_run": { "type": "boolean" },
+                "on_success": { "type": "string", "description": "ID of an existing job to run after this job succeeds" },
+                "on_failure": { "type": "string", "description": "ID of an existing job to run after this job fails" },
+                "catch_up": {
+                    "type": "string",
+                    "enum": ["skip", "once", "all"],
+                    "description": "Runs missed while the scheduler was down: skip them, run once (default), or run once per missed time"
+                },
+                "max_concurrent": { "type": "integer", "minimum": 1, "description": "Runs of this job allowed at once; extra triggers are skipped (default 1)" },
                 "approved": {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk shell commands in supervised mode",
@@ -158,6 +185,16 @@ impl Tool for CronAddTool {
             .get("approved")
             .and_then(serde_json::Value::as_bool)
             .unwrap_or(false);
+        let options = match job_options_patch(&self.config, &args) {
+            Ok(options) => options,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                });
+            }
+        };
 
         let result = match job_type {
             JobType::Shell => {
@@ -180,11 +217,37 @@ impl Tool for CronAddTool {
                     });
                 }
 
+                let delivery = match args.get("delivery") {
+                    Some(v) => match serde_json::from_value::<DeliveryConfig>(v.clone()) {
+                        Ok(cfg) => Some(cfg),
+                        Err(e) => {
+                            return Ok(ToolResult {
+                                success: false,
+                                output: String::new(),
+                                error: Some(format!("Invalid delivery config: {e}")),
+                            });
+                        }
+                    },
+                    None => None,
+                };
+
                 if let Some(blocked) = self.enforce_mutation_allowed("cron_add").await {
                     return Ok(blocked);
                 }
 
-                cron::add_shell_job(&self.config, name, schedule, command)
+                cron::add_shell_job(&self.config, name, schedule, command).and_then(|job| {
+                    match delivery {
+                        Some(delivery) => cron::configure_new_job(
+                            &self.config,
+                            &job,
+                            CronJobPatch {
+                                delivery: Some(delivery),
+                                ..CronJobPatch::default()
+                            },
+                        ),
+                        None => Ok(job),
+                    }
+                })
             }
             JobType::Agent => {
                 let prompt = match args.get("prompt").and_then(serde_json::Value::as_str) {
@@ -248,6 +311,11 @@ impl Tool for CronAddTool {
             }
         };
 
+        let result = result.and_then(|job| match options {
+            Some(options) => cron::configure_new_job(&self.config, &job, options),
+            None => Ok(job),
+        });
+
         match result {
             Ok(job) => Ok(ToolResult {
                 success: true,
@@ -257,7 +325,11 @@ impl Tool for CronAddTool {
                     "job_type": job.job_type,
                     "schedule": job.schedule,
                     "next_run": job.next_run,
-                    "enabled": job.enabled
+                    "enabled": job.enabled,
+                    "on_success": job.on_success,
+                    "on_failure": job.on_failure,
+                    "catch_up": job.catch_up,
+                    "max_concurrent": job.max_concurrent
                 }))?,
                 error: None,
             }),
@@ -270,6 +342,50 @@ impl Tool for CronAddTool {
     }
 }
 
+/// Chaining, catch-up and concurrency options, applied once the job exists.
+/// Follow-up jobs are checked up front so a bad id never leaves a half-configured job.
+fn job_options_patch(
+    config: &Config,
+    args: &serde_json::Value,
+) -> Result<Option<CronJobPatch>, String> {
+    let mut patch = CronJobPatch::default();
+    for (key, slot) in [
+        ("on_success", &mut patch.on_success),
+        ("on_failure", &mut patch.on_failure),
+    ] {
+        if let Some(id) = args.get(key).and_then(serde_json::Value::as_str) {
+            let id = id.trim();
+            if id.is_empty() {
+                continue;
+            }
+            if let Err(e) = cron::get_job(config, id) {
+                return Err(format!("Invalid {key}: {e}"));
+            }
+            *slot = Some(id.to_string());
+        }
+    }
+    if let Some(v) = args.get("catch_up") {
+        patch.catch_up = Some(
+            serde_json::from_value::<CatchUpPolicy>(v.clone())
+                .map_err(|e| format!("Invalid catch_up: {e}"))?,
+        );
+    }
+    if let Some(v) = args.get("max_concurrent") {
+        let max = v
+            .as_u64()
+            .and_then(|max| u32::try_from(max).ok())
+            .filter(|max| *max >= 1)
+            .ok_or_else(|| "Invalid max_concurrent: expected an integer >= 1".to_string())?;
+        patch.max_concurrent = Some(max);
+    }
+
+    let empty = patch.on_success.is_none()
+        && patch.on_failure.is_none()
+        && patch.catch_up.is_none()
+        && patch.max_concurrent.is_none();
+    Ok((!empty).then_some(patch))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,4 +598,68 @@ mod tests {
             .unwrap_or_default()
             .contains("Missing 'prompt'"));
     }
+
+    #[tokio::test]
+    async fn applies_chaining_catch_up_and_concurrency_options() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+        let cleanup = cron::add_shell_job(
+            &cfg,
+            Some("cleanup".into()),
+            Schedule::Every {
+                every_ms: 3_600_000,
+            },
+            "echo cleanup",
+        )
+        .unwrap();
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "0 3 * * *" },
+                "command": "echo backup",
+                "on_failure": cleanup.id,
+                "catch_up": "skip",
+                "max_concurrent": 2,
+                "delivery": {
+                    "mode": "announce",
+                    "channel": "telegram",
+                    "to": "123",
+                    "when": "on_failure"
+                }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let output: serde_json::Value = serde_json::from_str(&result.output).unwrap();
+        let job = cron::get_job(&cfg, output["id"].as_str().unwrap()).unwrap();
+        assert_eq!(job.on_failure.as_deref(), Some(cleanup.id.as_str()));
+        assert_eq!(job.on_success, None);
+        assert_eq!(job.catch_up, CatchUpPolicy::Skip);
+        assert_eq!(job.max_concurrent, 2);
+        assert_eq!(job.delivery.when, cron::DeliveryCondition::OnFailure);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_follow_up_job_before_creating() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = test_config(&tmp).await;
+        let tool = CronAddTool::new(cfg.clone(), test_security(&cfg));
+
+        let result = tool
+            .execute(json!({
+                "schedule": { "kind": "cron", "expr": "*/5 * * * *" },
+                "command": "echo ok",
+                "on_success": "missing-job"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap_or_default()
+            .contains("Invalid on_success"));
+        assert!(cron::list_jobs(&cfg).unwrap().is_empty());
+    }
 }
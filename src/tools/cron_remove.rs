@@ -27,7 +27,7 @@ impl CronRemoveTool {
             });
         }
 
-        if self.security.is_rate_limited().await {
+        if self.security.is_rate_limited() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -35,7 +35,7 @@ impl CronRemoveTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
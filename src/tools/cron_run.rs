@@ -75,7 +75,7 @@ impl Tool for CronRunTool {
             });
         }
 
-        if self.security.is_rate_limited().await {
+        if self.security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -107,7 +107,7 @@ impl Tool for CronRunTool {
             }
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -27,7 +27,7 @@ impl CronUpdateTool {
             });
         }
 
-        if self.security.is_rate_limited().await {
+        if self.security.is_rate_limited() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -35,7 +35,7 @@ impl CronUpdateTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -54,7 +54,8 @@ impl Tool for CronUpdateTool {
     }
 
     fn description(&self) -> &str {
-        "Patch an existing cron job (schedule, command, prompt, enabled, delivery, model, etc.)"
+        "Patch an existing cron job (schedule, command, prompt, enabled, delivery, model, \
+         on_success/on_failure follow-up jobs, catch_up, max_concurrent, etc.)"
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -62,7 +63,10 @@ impl Tool for CronUpdateTool {
             "type": "object",
             "properties": {
                 "job_id": { "type": "string" },
-                "patch": { "type": "object" },
+                "patch": {
+                    "type": "object",
+                    "description": "Fields to change. on_success/on_failure take a job ID (\"\" clears it); catch_up is skip|once|all; max_concurrent >= 1; delivery.when is always|on_failure|on_change"
+                },
                 "approved": {
                     "type": "boolean",
                     "description": "Set true to explicitly approve medium/high-risk shell commands in supervised mode",
@@ -232,7 +232,6 @@ impl Tool for DelegateTool {
         if let Err(error) = self
             .security
             .enforce_tool_operation(ToolOperation::Act, "delegate")
-            .await
         {
             return Ok(ToolResult {
                 success: false,
@@ -412,6 +411,7 @@ impl DelegateTool {
                 None,
                 None,
                 &[],
+                None,
             ),
         )
         .await;
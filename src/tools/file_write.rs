@@ -61,7 +61,7 @@ impl Tool for FileWriteTool {
             });
         }
 
-        if self.security.is_rate_limited().await {
+        if self.security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -138,7 +138,7 @@ impl Tool for FileWriteTool {
             }
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -0,0 +1,1241 @@
+//! GitHub/GitLab issues, pull requests and CI status.
+//!
+//! Talks to the forge configured under `[forge]`. Every action is limited to
+//! repositories matching `allowed_repos`; comments and pull requests also
+//! need `Act` permission. Pull requests are opened from a branch of the
+//! workspace repository, pushed with the forge token.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{ForgeConfig, ForgeProvider};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const FORGE_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_LIST_LIMIT: u64 = 20;
+const MAX_LIST_LIMIT: u64 = 100;
+const MAX_BODY_CHARS: usize = 4000;
+const MAX_COMMENT_CHARS: usize = 1500;
+
+pub struct ForgeTool {
+    security: Arc<SecurityPolicy>,
+    config: ForgeConfig,
+    workspace_dir: PathBuf,
+}
+
+/// An issue comment, review or review comment, normalised across forges.
+struct Comment {
+    author: String,
+    created: String,
+    body: String,
+    /// `path:line` for comments on a diff.
+    location: Option<String>,
+}
+
+impl ForgeTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: ForgeConfig, workspace_dir: PathBuf) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+        }
+    }
+
+    fn gitlab(&self) -> bool {
+        self.config.provider == ForgeProvider::Gitlab
+    }
+
+    fn api_base(&self) -> String {
+        match self.config.api_url.as_deref().map(str::trim) {
+            Some(url) if !url.is_empty() => url.trim_end_matches('/').to_string(),
+            _ if self.gitlab() => "https://gitlab.com/api/v4".into(),
+            _ => "https://api.github.com".into(),
+        }
+    }
+
+    /// Web base URL of the forge, used for git pushes.
+    fn web_base(&self) -> String {
+        let api = self.api_base();
+        let web = api
+            .strip_suffix("/api/v4")
+            .or_else(|| api.strip_suffix("/api/v3"))
+            .unwrap_or(&api);
+        web.replacen("://api.", "://", 1)
+    }
+
+    fn token(&self) -> Result<&str, String> {
+        self.config
+            .token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| "forge.token is not configured".to_string())
+    }
+
+    /// API path prefix for `repo`.
+    fn repo_path(&self, repo: &str) -> String {
+        if self.gitlab() {
+            format!("/projects/{}", urlencoding::encode(repo))
+        } else {
+            format!("/repos/{repo}")
+        }
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        let token = self.token()?;
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.forge",
+            self.config.timeout_secs.max(1),
+            FORGE_CONNECT_TIMEOUT_SECS,
+        );
+        let request = client
+            .request(method, format!("{}{path}", self.api_base()))
+            .header("User-Agent", "zeroclaw");
+        Ok(if self.gitlab() {
+            request.header("PRIVATE-TOKEN", token)
+        } else {
+            request
+                .bearer_auth(token)
+                .header("Accept", "application/vnd.github+json")
+        })
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, String> {
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!(
+                "Forge returned HTTP {status}: {}",
+                crate::util::truncate_with_ellipsis(&text, 300)
+            ));
+        }
+        serde_json::from_str(&text).map_err(|e| format!("Forge returned invalid JSON: {e}"))
+    }
+
+    async fn get(&self, path: &str) -> Result<Value, String> {
+        self.send(self.request(reqwest::Method::GET, path)?).await
+    }
+
+    async fn post(&self, path: &str, body: Value) -> Result<Value, String> {
+        self.send(self.request(reqwest::Method::POST, path)?.json(&body))
+            .await
+    }
+
+    /// Validated repository from `args`, checked against `allowed_repos`.
+    fn repo<'a>(&self, args: &'a Value) -> Result<&'a str, String> {
+        let repo = args
+            .get("repo")
+            .and_then(Value::as_str)
+            .map(|repo| repo.trim().trim_matches('/'))
+            .filter(|repo| !repo.is_empty())
+            .ok_or_else(|| "Missing 'repo' parameter".to_string())?;
+        let segments: Vec<&str> = repo.split('/').collect();
+        let valid_segments = segments.iter().all(|segment| {
+            !segment.is_empty()
+                && *segment != "."
+                && *segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+        let valid_depth = if self.gitlab() {
+            segments.len() >= 2
+        } else {
+            segments.len() == 2
+        };
+        if !valid_segments || !valid_depth {
+            return Err(format!(
+                "Invalid repo '{repo}'. Use {}",
+                if self.gitlab() {
+                    "group/project"
+                } else {
+                    "owner/repo"
+                }
+            ));
+        }
+        if !repo_allowed(&self.config.allowed_repos, repo) {
+            return Err(format!("Repository '{repo}' is not in forge.allowed_repos"));
+        }
+        Ok(repo)
+    }
+
+    fn enforce_act(&self) -> Result<(), String> {
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "forge")
+    }
+
+    async fn list_issues(&self, repo: &str, args: &Value) -> Result<String, String> {
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .unwrap_or(DEFAULT_LIST_LIMIT)
+            .clamp(1, MAX_LIST_LIMIT);
+        let state = match args.get("state").and_then(Value::as_str).unwrap_or("open") {
+            "open" if self.gitlab() => "opened",
+            "open" => "open",
+            "closed" => "closed",
+            "all" => "all",
+            other => return Err(format!("Invalid state '{other}'. Use open, closed or all")),
+        };
+        let mut path = format!(
+            "{}/issues?state={state}&per_page={limit}",
+            self.repo_path(repo)
+        );
+        if let Some(labels) = string_arg(args, "labels") {
+            let _ = write!(path, "&labels={}", urlencoding::encode(labels));
+        }
+
+        let issues = self.get(&path).await?;
+        let lines: Vec<String> = issues
+            .as_array()
+            .into_iter()
+            .flatten()
+            // GitHub lists pull requests as issues too.
+            .filter(|issue| issue.get("pull_request").is_none())
+            .map(|issue| {
+                let labels = self.labels(issue);
+                format!(
+                    "#{} [{}] {}{} by {}",
+                    self.number(issue),
+                    str_at(issue, "/state"),
+                    str_at(issue, "/title"),
+                    if labels.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", labels.join(", "))
+                    },
+                    self.author(issue)
+                )
+            })
+            .collect();
+        Ok(if lines.is_empty() {
+            format!(
+                "No {} issues in {repo}.",
+                args["state"].as_str().unwrap_or("open")
+            )
+        } else {
+            lines.join("\n")
+        })
+    }
+
+    async fn get_issue(&self, repo: &str, number: u64) -> Result<String, String> {
+        let base = format!("{}/issues/{number}", self.repo_path(repo));
+        let issue = self.get(&base).await?;
+        let comments_path = if self.gitlab() {
+            format!("{base}/notes?sort=asc&per_page={MAX_LIST_LIMIT}")
+        } else {
+            format!("{base}/comments?per_page={MAX_LIST_LIMIT}")
+        };
+        let comments = self.comments(&self.get(&comments_path).await?);
+
+        let body_key = if self.gitlab() {
+            "/description"
+        } else {
+            "/body"
+        };
+        let url_key = if self.gitlab() {
+            "/web_url"
+        } else {
+            "/html_url"
+        };
+        let mut output = format!(
+            "#{number} {} [{}] by {}\n{}",
+            str_at(&issue, "/title"),
+            str_at(&issue, "/state"),
+            self.author(&issue),
+            str_at(&issue, url_key)
+        );
+        let labels = self.labels(&issue);
+        if !labels.is_empty() {
+            let _ = write!(output, "\nLabels: {}", labels.join(", "));
+        }
+        let body = str_at(&issue, body_key);
+        if !body.trim().is_empty() {
+            let _ = write!(
+                output,
+                "\n\n{}",
+                crate::util::truncate_with_ellipsis(body.trim(), MAX_BODY_CHARS)
+            );
+        }
+        render_comments(&mut output, "Comments", &comments);
+        Ok(output)
+    }
+
+    async fn comment(&self, repo: &str, number: u64, args: &Value) -> Result<String, String> {
+        let body = string_arg(args, "body").ok_or("Missing 'body' parameter")?;
+        let pull_request = args
+            .get("pull_request")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        self.enforce_act()?;
+
+        let path = match (self.gitlab(), pull_request) {
+            (true, true) => format!("{}/merge_requests/{number}/notes", self.repo_path(repo)),
+            (true, false) => format!("{}/issues/{number}/notes", self.repo_path(repo)),
+            // GitHub pull request conversations use the issue comments API.
+            (false, _) => format!("{}/issues/{number}/comments", self.repo_path(repo)),
+        };
+        let created = self.post(&path, json!({"body": body})).await?;
+        let url = created
+            .get("html_url")
+            .and_then(Value::as_str)
+            .map(|url| format!(": {url}"))
+            .unwrap_or_default();
+        Ok(format!("Commented on {repo}#{number}{url}"))
+    }
+
+    async fn git(&self, args: &[&str], envs: &[(&str, String)]) -> Result<String, String> {
+        let output = tokio::process::Command::new("git")
+            .args(args)
+            .envs(envs.iter().map(|(key, value)| (*key, value.as_str())))
+            .current_dir(&self.workspace_dir)
+            .output()
+            .await
+            .map_err(|e| format!("Failed to run git: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Switch the workspace repository to `branch`, creating it from the
+    /// current HEAD when it does not exist yet.
+    async fn switch_branch(&self, branch: &str) -> Result<(), String> {
+        if branch.starts_with('-')
+            || self
+                .git(&["check-ref-format", "--branch", branch], &[])
+                .await
+                .is_err()
+        {
+            return Err(format!("Invalid branch name '{branch}'"));
+        }
+        let exists = self
+            .git(
+                &[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("refs/heads/{branch}"),
+                ],
+                &[],
+            )
+            .await
+            .is_ok();
+        if exists {
+            self.git(&["checkout", branch], &[]).await?;
+        } else {
+            self.git(&["checkout", "-b", branch], &[]).await?;
+        }
+        Ok(())
+    }
+
+    /// Git config passed through the environment so the token never shows
+    /// up in process arguments or `.git/config`.
+    fn push_env(&self) -> Result<Vec<(&'static str, String)>, String> {
+        let user = if self.gitlab() {
+            "oauth2"
+        } else {
+            "x-access-token"
+        };
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("{user}:{}", self.token()?));
+        Ok(vec![
+            ("GIT_TERMINAL_PROMPT", "0".into()),
+            ("GIT_CONFIG_COUNT", "1".into()),
+            ("GIT_CONFIG_KEY_0", "http.extraHeader".into()),
+            (
+                "GIT_CONFIG_VALUE_0",
+                format!("Authorization: Basic {credentials}"),
+            ),
+        ])
+    }
+
+    async fn create_pull_request(&self, repo: &str, args: &Value) -> Result<String, String> {
+        let title = string_arg(args, "title").ok_or("Missing 'title' parameter")?;
+        let body = string_arg(args, "body").unwrap_or("");
+        let draft = args.get("draft").and_then(Value::as_bool).unwrap_or(false);
+        let push = args.get("push").and_then(Value::as_bool).unwrap_or(true);
+        self.enforce_act()?;
+
+        let current = self
+            .git(&["rev-parse", "--abbrev-ref", "HEAD"], &[])
+            .await
+            .map_err(|e| format!("Workspace is not a git repository with commits: {e}"))?;
+        let branch = match string_arg(args, "branch") {
+            Some(branch) if branch != current => {
+                self.switch_branch(branch).await?;
+                branch.to_string()
+            }
+            _ if current == "HEAD" => {
+                return Err("Workspace is on a detached HEAD; pass 'branch'".into());
+            }
+            _ => current,
+        };
+
+        let base = match string_arg(args, "base") {
+            Some(base) => base.to_string(),
+            None => {
+                let project = self.get(&self.repo_path(repo)).await?;
+                project
+                    .get("default_branch")
+                    .and_then(Value::as_str)
+                    .ok_or("Could not determine the default branch; pass 'base'")?
+                    .to_string()
+            }
+        };
+        if branch == base {
+            return Err(format!(
+                "Head branch '{branch}' is the base branch; pass a new 'branch'"
+            ));
+        }
+
+        if push {
+            let remote = format!("{}/{repo}.git", self.web_base());
+            self.git(
+                &["push", &remote, &format!("HEAD:refs/heads/{branch}")],
+                &self.push_env()?,
+            )
+            .await?;
+        }
+
+        let created = if self.gitlab() {
+            let title = if draft {
+                format!("Draft: {title}")
+            } else {
+                title.to_string()
+            };
+            self.post(
+                &format!("{}/merge_requests", self.repo_path(repo)),
+                json!({
+                    "source_branch": branch,
+                    "target_branch": base,
+                    "title": title,
+                    "description": body,
+                }),
+            )
+            .await?
+        } else {
+            self.post(
+                &format!("{}/pulls", self.repo_path(repo)),
+                json!({
+                    "head": branch,
+                    "base": base,
+                    "title": title,
+                    "body": body,
+                    "draft": draft,
+                }),
+            )
+            .await?
+        };
+
+        Ok(format!(
+            "Opened {} #{} ({branch} → {base}): {}",
+            if self.gitlab() {
+                "merge request"
+            } else {
+                "pull request"
+            },
+            self.number(&created),
+            str_at(
+                &created,
+                if self.gitlab() {
+                    "/web_url"
+                } else {
+                    "/html_url"
+                }
+            )
+        ))
+    }
+
+    async fn checks(&self, repo: &str, args: &Value) -> Result<String, String> {
+        let number = args.get("number").and_then(Value::as_u64);
+        let git_ref = string_arg(args, "ref");
+        if number.is_none() && git_ref.is_none() {
+            return Err("Pass 'number' (pull request) or 'ref' (branch or commit)".into());
+        }
+        if self.gitlab() {
+            self.gitlab_pipeline(repo, number, git_ref).await
+        } else {
+            self.github_checks(repo, number, git_ref).await
+        }
+    }
+
+    async fn github_checks(
+        &self,
+        repo: &str,
+        number: Option<u64>,
+        git_ref: Option<&str>,
+    ) -> Result<String, String> {
+        let sha = match (number, git_ref) {
+            (Some(number), _) => {
+                let pull = self
+                    .get(&format!("{}/pulls/{number}", self.repo_path(repo)))
+                    .await?;
+                str_at(&pull, "/head/sha").to_string()
+            }
+            (None, Some(git_ref)) => git_ref.to_string(),
+            (None, None) => unreachable!("checked by caller"),
+        };
+        let commit = format!(
+            "{}/commits/{}",
+            self.repo_path(repo),
+            urlencoding::encode(&sha)
+        );
+        let runs = self
+            .get(&format!("{commit}/check-runs?per_page=100"))
+            .await?;
+        let statuses = self.get(&format!("{commit}/status")).await?;
+
+        let mut lines: Vec<String> = runs
+            .pointer("/check_runs")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|run| {
+                let result = run
+                    .get("conclusion")
+                    .and_then(Value::as_str)
+                    .unwrap_or_else(|| str_at(run, "/status"));
+                format!("- {}: {result}", str_at(run, "/name"))
+            })
+            .collect();
+        lines.extend(
+            statuses
+                .pointer("/statuses")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .map(|status| {
+                    format!(
+                        "- {}: {}",
+                        str_at(status, "/context"),
+                        str_at(status, "/state")
+                    )
+                }),
+        );
+
+        let short = sha.get(..12).unwrap_or(&sha);
+        Ok(if lines.is_empty() {
+            format!("No checks reported for {short}.")
+        } else {
+            format!("Checks for {short}:\n{}", lines.join("\n"))
+        })
+    }
+
+    async fn gitlab_pipeline(
+        &self,
+        repo: &str,
+        number: Option<u64>,
+        git_ref: Option<&str>,
+    ) -> Result<String, String> {
+        let pipelines = match (number, git_ref) {
+            (Some(number), _) => {
+                self.get(&format!(
+                    "{}/merge_requests/{number}/pipelines",
+                    self.repo_path(repo)
+                ))
+                .await?
+            }
+            (None, Some(git_ref)) => {
+                self.get(&format!(
+                    "{}/pipelines?ref={}&per_page=1",
+                    self.repo_path(repo),
+                    urlencoding::encode(git_ref)
+                ))
+                .await?
+            }
+            (None, None) => unreachable!("checked by caller"),
+        };
+        let Some(pipeline) = pipelines.as_array().and_then(|list| list.first()) else {
+            return Ok("No pipelines found.".into());
+        };
+        let id = pipeline.get("id").and_then(Value::as_u64).unwrap_or(0);
+        let jobs = self
+            .get(&format!(
+                "{}/pipelines/{id}/jobs?per_page=100",
+                self.repo_path(repo)
+            ))
+            .await?;
+
+        let mut output = format!(
+            "Pipeline #{id} ({}): {}",
+            str_at(pipeline, "/ref"),
+            str_at(pipeline, "/status")
+        );
+        for job in jobs.as_array().into_iter().flatten() {
+            let _ = write!(
+                output,
+                "\n- {}/{}: {}",
+                str_at(job, "/stage"),
+                str_at(job, "/name"),
+                str_at(job, "/status")
+            );
+        }
+        Ok(output)
+    }
+
+    async fn reviews(&self, repo: &str, number: u64) -> Result<String, String> {
+        let mut output = String::new();
+        if self.gitlab() {
+            let notes = self
+                .get(&format!(
+                    "{}/merge_requests/{number}/notes?sort=asc&per_page={MAX_LIST_LIMIT}",
+                    self.repo_path(repo)
+                ))
+                .await?;
+            render_comments(&mut output, "Review comments", &self.comments(&notes));
+        } else {
+            let pull = format!("{}/pulls/{number}", self.repo_path(repo));
+            let reviews = self
+                .get(&format!("{pull}/reviews?per_page={MAX_LIST_LIMIT}"))
+                .await?;
+            let review_lines: Vec<String> = reviews
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|review| {
+                    let body = str_at(review, "/body").trim();
+                    format!(
+                        "- {}: {}{}",
+                        self.author(review),
+                        str_at(review, "/state"),
+                        if body.is_empty() {
+                            String::new()
+                        } else {
+                            format!(
+                                " — {}",
+                                crate::util::truncate_with_ellipsis(body, MAX_COMMENT_CHARS)
+                            )
+                        }
+                    )
+                })
+                .collect();
+            if !review_lines.is_empty() {
+                let _ = write!(output, "Reviews:\n{}", review_lines.join("\n"));
+            }
+            let comments = self
+                .get(&format!("{pull}/comments?per_page={MAX_LIST_LIMIT}"))
+                .await?;
+            render_comments(&mut output, "Review comments", &self.comments(&comments));
+        }
+
+        Ok(if output.is_empty() {
+            format!("No reviews on {repo}#{number}.")
+        } else {
+            output.trim_start().to_string()
+        })
+    }
+
+    fn number(&self, item: &Value) -> u64 {
+        let key = if self.gitlab() { "iid" } else { "number" };
+        item.get(key).and_then(Value::as_u64).unwrap_or(0)
+    }
+
+    fn author<'a>(&self, item: &'a Value) -> &'a str {
+        let key = if self.gitlab() {
+            "/author/username"
+        } else {
+            "/user/login"
+        };
+        str_at(item, key)
+    }
+
+    fn labels(&self, item: &Value) -> Vec<String> {
+        item.get("labels")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|label| {
+                label
+                    .as_str()
+                    .or_else(|| label.get("name").and_then(Value::as_str))
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    /// Comments from an issue comments, notes or review comments listing.
+    /// GitLab system notes (label changes, pushes, …) are dropped.
+    fn comments(&self, list: &Value) -> Vec<Comment> {
+        list.as_array()
+            .into_iter()
+            .flatten()
+            .filter(|comment| comment.get("system").and_then(Value::as_bool) != Some(true))
+            .map(|comment| {
+                let location = if self.gitlab() {
+                    comment
+                        .pointer("/position/new_path")
+                        .and_then(Value::as_str)
+                        .map(|path| {
+                            match comment
+                                .pointer("/position/new_line")
+                                .and_then(Value::as_u64)
+                            {
+                                Some(line) => format!("{path}:{line}"),
+                                None => path.to_string(),
+                            }
+                        })
+                } else {
+                    comment.get("path").and_then(Value::as_str).map(|path| {
+                        match comment
+                            .get("line")
+                            .and_then(Value::as_u64)
+                            .or_else(|| comment.get("original_line").and_then(Value::as_u64))
+                        {
+                            Some(line) => format!("{path}:{line}"),
+                            None => path.to_string(),
+                        }
+                    })
+                };
+                Comment {
+                    author: self.author(comment).to_string(),
+                    created: str_at(comment, "/created_at").to_string(),
+                    body: str_at(comment, "/body").trim().to_string(),
+                    location,
+                }
+            })
+            .collect()
+    }
+}
+
+fn str_at<'a>(value: &'a Value, pointer: &str) -> &'a str {
+    value.pointer(pointer).and_then(Value::as_str).unwrap_or("")
+}
+
+fn string_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Whether `repo` matches any `allowed_repos` pattern (case-insensitive,
+/// `*` does not cross `/`).
+fn repo_allowed(patterns: &[String], repo: &str) -> bool {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+    patterns.iter().any(|pattern| {
+        glob::Pattern::new(pattern.trim().trim_matches('/'))
+            .is_ok_and(|pattern| pattern.matches_with(repo, options))
+    })
+}
+
+fn render_comments(output: &mut String, heading: &str, comments: &[Comment]) {
+    if comments.is_empty() {
+        return;
+    }
+    let _ = write!(output, "\n\n{heading} ({}):", comments.len());
+    for comment in comments {
+        let location = comment
+            .location
+            .as_deref()
+            .map(|location| format!(" on {location}"))
+            .unwrap_or_default();
+        let _ = write!(
+            output,
+            "\n- {}{location} ({}): {}",
+            comment.author,
+            comment.created,
+            crate::util::truncate_with_ellipsis(&comment.body, MAX_COMMENT_CHARS)
+        );
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for ForgeTool {
+    fn name(&self) -> &str {
+        "forge"
+    }
+
+    fn description(&self) -> &str {
+        "Work with GitHub/GitLab issues and pull requests on allowlisted repos. Actions: \
+         'list_issues', 'get_issue' (with comments), 'comment' on an issue or pull request, \
+         'create_pull_request' (switches the workspace repo to 'branch', pushes it and opens \
+         the pull/merge request), 'checks' (CI status of a pull request or ref), \
+         'reviews' (review verdicts and comments on a pull request)."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list_issues", "get_issue", "comment", "create_pull_request", "checks", "reviews"],
+                    "description": "Operation to perform"
+                },
+                "repo": {
+                    "type": "string",
+                    "description": "Repository as owner/repo (GitHub) or group/project (GitLab)"
+                },
+                "number": {
+                    "type": "integer",
+                    "description": "Issue or pull/merge request number"
+                },
+                "state": {
+                    "type": "string",
+                    "enum": ["open", "closed", "all"],
+                    "description": "Issue state filter (list_issues, default open)"
+                },
+                "labels": {
+                    "type": "string",
+                    "description": "Comma-separated label filter (list_issues)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum issues to list (default 20, max 100)"
+                },
+                "body": {
+                    "type": "string",
+                    "description": "Comment text (comment) or pull request description (create_pull_request)"
+                },
+                "pull_request": {
+                    "type": "boolean",
+                    "description": "Comment on a pull/merge request instead of an issue (comment)"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Pull request title (create_pull_request)"
+                },
+                "branch": {
+                    "type": "string",
+                    "description": "Head branch; created from the current HEAD if missing (create_pull_request, default current branch)"
+                },
+                "base": {
+                    "type": "string",
+                    "description": "Target branch (create_pull_request, default the repo's default branch)"
+                },
+                "draft": {
+                    "type": "boolean",
+                    "description": "Open as a draft (create_pull_request)"
+                },
+                "push": {
+                    "type": "boolean",
+                    "description": "Push the branch before opening the pull request (default true)"
+                },
+                "ref": {
+                    "type": "string",
+                    "description": "Branch or commit SHA to report CI status for (checks)"
+                }
+            },
+            "required": ["action", "repo"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args.get("action").and_then(Value::as_str).unwrap_or("");
+        let repo = match self.repo(&args) {
+            Ok(repo) => repo,
+            Err(e) => return Ok(failure(e)),
+        };
+        let number = || {
+            args.get("number")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| "Missing 'number' parameter".to_string())
+        };
+
+        let result = match action {
+            "list_issues" => self.list_issues(repo, &args).await,
+            "get_issue" => match number() {
+                Ok(number) => self.get_issue(repo, number).await,
+                Err(e) => Err(e),
+            },
+            "comment" => match number() {
+                Ok(number) => self.comment(repo, number, &args).await,
+                Err(e) => Err(e),
+            },
+            "create_pull_request" => self.create_pull_request(repo, &args).await,
+            "checks" => self.checks(repo, &args).await,
+            "reviews" => match number() {
+                Ok(number) => self.reviews(repo, number).await,
+                Err(e) => Err(e),
+            },
+            other => Err(format!(
+                "Unknown action '{other}'. Use list_issues, get_issue, comment, \
+                 create_pull_request, checks or reviews"
+            )),
+        };
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => failure(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_security(autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(
+        provider: ForgeProvider,
+        api_url: String,
+        autonomy: AutonomyLevel,
+        workspace: &std::path::Path,
+    ) -> ForgeTool {
+        ForgeTool::new(
+            test_security(autonomy),
+            ForgeConfig {
+                enabled: true,
+                provider,
+                api_url: Some(api_url),
+                token: Some("forge_token".into()),
+                allowed_repos: vec!["acme/*".into(), "group/sub/app".into()],
+                timeout_secs: 5,
+            },
+            workspace.to_path_buf(),
+        )
+    }
+
+    fn git(dir: &std::path::Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[tokio::test]
+    async fn github_list_issues_skips_pull_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/issues"))
+            .and(query_param("state", "open"))
+            .and(query_param("labels", "bug"))
+            .and(header("authorization", "Bearer forge_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"number": 7, "title": "Crash on start", "state": "open",
+                 "labels": [{"name": "bug"}], "user": {"login": "alice"}},
+                {"number": 8, "title": "Fix crash", "state": "open", "labels": [],
+                 "user": {"login": "bob"}, "pull_request": {}}
+            ])))
+            .mount(&server)
+            .await;
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(
+            ForgeProvider::Github,
+            server.uri(),
+            AutonomyLevel::Supervised,
+            tmp.path(),
+        );
+
+        let result = tool
+            .execute(json!({"action": "list_issues", "repo": "acme/app", "labels": "bug"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "#7 [open] Crash on start (bug) by alice");
+    }
+
+    #[tokio::test]
+    async fn gitlab_get_issue_includes_notes_without_system_notes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/projects/group%2Fsub%2Fapp/issues/3"))
+            .and(header("private-token", "forge_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "iid": 3, "title": "Slow build", "state": "opened",
+                "description": "CI takes 40 minutes", "labels": ["ci"],
+                "author": {"username": "carol"}, "web_url": "https://gitlab.example/group/sub/app/-/issues/3"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/projects/group%2Fsub%2Fapp/issues/3/notes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"body": "added ~ci label", "system": true, "author": {"username": "carol"}},
+                {"body": "Cache the cargo registry", "system": false,
+                 "author": {"username": "dave"}, "created_at": "2026-01-02"}
+            ])))
+            .mount(&server)
+            .await;
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(
+            ForgeProvider::Gitlab,
+            server.uri(),
+            AutonomyLevel::Supervised,
+            tmp.path(),
+        );
+
+        let result = tool
+            .execute(json!({"action": "get_issue", "repo": "group/sub/app", "number": 3}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("#3 Slow build [opened] by carol"));
+        assert!(result.output.contains("Labels: ci"));
+        assert!(result.output.contains("CI takes 40 minutes"));
+        assert!(result
+            .output
+            .contains("Comments (1):\n- dave (2026-01-02): Cache the cargo registry"));
+    }
+
+    #[tokio::test]
+    async fn repos_outside_allowlist_are_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(
+            ForgeProvider::Github,
+            "http://127.0.0.1:9".into(),
+            AutonomyLevel::Supervised,
+            tmp.path(),
+        );
+        for repo in ["other/app", "acme/../other", "acme", "acme/app/extra"] {
+            let result = tool
+                .execute(json!({"action": "list_issues", "repo": repo}))
+                .await
+                .unwrap();
+            assert!(!result.success, "{repo} should be rejected");
+        }
+    }
+
+    #[tokio::test]
+    async fn comment_requires_act_permission() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/app/issues/7/comments"))
+            .and(body_partial_json(json!({"body": "Looking into it"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "html_url": "https://github.com/acme/app/issues/7#issuecomment-1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let tmp = TempDir::new().unwrap();
+        let args = json!({"action": "comment", "repo": "acme/app", "number": 7, "body": "Looking into it"});
+
+        let read_only = test_tool(
+            ForgeProvider::Github,
+            server.uri(),
+            AutonomyLevel::ReadOnly,
+            tmp.path(),
+        );
+        let result = read_only.execute(args.clone()).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+
+        let tool = test_tool(
+            ForgeProvider::Github,
+            server.uri(),
+            AutonomyLevel::Supervised,
+            tmp.path(),
+        );
+        let result = tool.execute(args).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("issuecomment-1"));
+    }
+
+    #[tokio::test]
+    async fn create_pull_request_switches_branch_and_opens_pull() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"default_branch": "main"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/app/pulls"))
+            .and(body_partial_json(json!({
+                "head": "fix/typo", "base": "main", "title": "Fix typo", "draft": true
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "number": 12, "html_url": "https://github.com/acme/app/pull/12"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tmp = TempDir::new().unwrap();
+        git(tmp.path(), &["init", "-q", "-b", "main"]);
+        std::fs::write(tmp.path().join("README.md"), "hello").unwrap();
+        git(tmp.path(), &["add", "README.md"]);
+        git(tmp.path(), &["commit", "-q", "-m", "init"]);
+        let tool = test_tool(
+            ForgeProvider::Github,
+            server.uri(),
+            AutonomyLevel::Supervised,
+            tmp.path(),
+        );
+
+        let result = tool
+            .execute(json!({
+                "action": "create_pull_request",
+                "repo": "acme/app",
+                "title": "Fix typo",
+                "branch": "fix/typo",
+                "draft": true,
+                "push": false
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.output,
+            "Opened pull request #12 (fix/typo → main): https://github.com/acme/app/pull/12"
+        );
+        assert_eq!(
+            git(tmp.path(), &["rev-parse", "--abbrev-ref", "HEAD"]),
+            "fix/typo"
+        );
+
+        let result = tool
+            .execute(json!({
+                "action": "create_pull_request",
+                "repo": "acme/app",
+                "title": "Again",
+                "branch": "main",
+                "push": false
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("base branch"));
+    }
+
+    #[tokio::test]
+    async fn github_checks_and_reviews_for_pull_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/pulls/12"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "head": {"sha": "0123456789abcdef0123"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/repos/acme/app/commits/0123456789abcdef0123/check-runs",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"check_runs": [
+                    {"name": "build", "status": "completed", "conclusion": "success"},
+                    {"name": "test", "status": "in_progress", "conclusion": null}
+                ]})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/commits/0123456789abcdef0123/status"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"statuses": [
+                    {"context": "ci/legacy", "state": "failure"}
+                ]})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/pulls/12/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"user": {"login": "erin"}, "state": "CHANGES_REQUESTED", "body": "See inline notes"}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/app/pulls/12/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"user": {"login": "erin"}, "path": "src/main.rs", "line": 42,
+                 "body": "Handle the error here", "created_at": "2026-01-03"}
+            ])))
+            .mount(&server)
+            .await;
+        let tmp = TempDir::new().unwrap();
+        let tool = test_tool(
+            ForgeProvider::Github,
+            server.uri(),
+            AutonomyLevel::ReadOnly,
+            tmp.path(),
+        );
+
+        let result = tool
+            .execute(json!({"action": "checks", "repo": "acme/app", "number": 12}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.output,
+            "Checks for 0123456789ab:\n- build: success\n- test: in_progress\n- ci/legacy: failure"
+        );
+
+        let result = tool
+            .execute(json!({"action": "reviews", "repo": "acme/app", "number": 12}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result
+            .output
+            .starts_with("Reviews:\n- erin: CHANGES_REQUESTED — See inline notes"));
+        assert!(result
+            .output
+            .contains("- erin on src/main.rs:42 (2026-01-03): Handle the error here"));
+    }
+
+    #[test]
+    fn web_base_is_derived_from_api_url() {
+        let tmp = TempDir::new().unwrap();
+        let tool = |provider, url: &str| {
+            let mut tool = test_tool(provider, url.into(), AutonomyLevel::Supervised, tmp.path());
+            if url.is_empty() {
+                tool.config.api_url = None;
+            }
+            tool.web_base()
+        };
+        assert_eq!(tool(ForgeProvider::Github, ""), "https://github.com");
+        assert_eq!(tool(ForgeProvider::Gitlab, ""), "https://gitlab.com");
+        assert_eq!(
+            tool(ForgeProvider::Github, "https://ghe.example.com/api/v3/"),
+            "https://ghe.example.com"
+        );
+    }
+
+    #[test]
+    fn repo_patterns_do_not_cross_path_segments() {
+        let patterns = vec!["acme/*".to_string()];
+        assert!(repo_allowed(&patterns, "acme/app"));
+        assert!(repo_allowed(&patterns, "ACME/App"));
+        assert!(!repo_allowed(&patterns, "acme/sub/app"));
+        assert!(!repo_allowed(&[], "acme/app"));
+    }
+}
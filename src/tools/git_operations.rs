@@ -539,7 +539,7 @@ impl Tool for GitOperationsTool {
         }
 
         // Record action for rate limiting
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -0,0 +1,671 @@
+//! Home Assistant control over its REST API.
+//!
+//! Reads (entities, areas, states) are always allowed; service calls require
+//! the service domain in `[home_assistant].allowed_domains` and, when
+//! `allowed_entities` is set, explicit `entity_id` targets matching it.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::HomeAssistantConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+const HA_CONNECT_TIMEOUT_SECS: u64 = 10;
+const MAX_LISTED_ENTITIES: usize = 200;
+
+/// Target selectors Home Assistant expands to entities server-side; they
+/// cannot be checked against `allowed_entities`.
+const INDIRECT_TARGET_KEYS: [&str; 4] = ["area_id", "device_id", "floor_id", "label_id"];
+
+const AREAS_TEMPLATE: &str = "{% set ns = namespace(areas=[]) %}\
+{% for area in areas() %}\
+{% set ns.areas = ns.areas + [{'id': area, 'name': area_name(area), 'entities': area_entities(area)}] %}\
+{% endfor %}{{ ns.areas | tojson }}";
+
+const AREA_ENTITIES_TEMPLATE: &str = "{{ area_entities(area) | tojson }}";
+
+pub struct HomeAssistantTool {
+    security: Arc<SecurityPolicy>,
+    config: HomeAssistantConfig,
+}
+
+impl HomeAssistantTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: HomeAssistantConfig) -> Self {
+        Self { security, config }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.config.url.trim_end_matches('/'))
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let token = self
+            .config
+            .token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("home_assistant.token is not configured"))?;
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.home_assistant",
+            self.config.timeout_secs.max(1),
+            HA_CONNECT_TIMEOUT_SECS,
+        );
+        Ok(client.request(method, self.url(path)).bearer_auth(token))
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> anyhow::Result<String> {
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!(
+                "Home Assistant returned HTTP {status}: {}",
+                crate::util::truncate_with_ellipsis(&text, 300)
+            );
+        }
+        Ok(text)
+    }
+
+    async fn get_json(&self, path: &str) -> anyhow::Result<Value> {
+        let text = self.send(self.request(reqwest::Method::GET, path)?).await?;
+        serde_json::from_str(&text)
+            .map_err(|e| anyhow::anyhow!("Home Assistant returned invalid JSON: {e}"))
+    }
+
+    async fn render_template(&self, template: &str, variables: Value) -> anyhow::Result<Value> {
+        let text = self
+            .send(
+                self.request(reqwest::Method::POST, "/api/template")?
+                    .json(&json!({"template": template, "variables": variables})),
+            )
+            .await?;
+        serde_json::from_str(text.trim())
+            .map_err(|e| anyhow::anyhow!("Unexpected template output from Home Assistant: {e}"))
+    }
+
+    async fn list_entities(
+        &self,
+        domain: Option<&str>,
+        area: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let states = self.get_json("/api/states").await?;
+        let area_entities: Option<Vec<String>> = match area {
+            Some(area) => Some(serde_json::from_value(
+                self.render_template(AREA_ENTITIES_TEMPLATE, json!({"area": area}))
+                    .await?,
+            )?),
+            None => None,
+        };
+
+        let mut lines: Vec<String> = states
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|state| {
+                let entity_id = state.get("entity_id")?.as_str()?;
+                if domain.is_some_and(|domain| entity_domain(entity_id) != domain) {
+                    return None;
+                }
+                if area_entities
+                    .as_ref()
+                    .is_some_and(|ids| !ids.iter().any(|id| id == entity_id))
+                {
+                    return None;
+                }
+                Some(render_state_line(entity_id, state))
+            })
+            .collect();
+        lines.sort_unstable();
+
+        if lines.is_empty() {
+            return Ok("No matching entities.".into());
+        }
+        let total = lines.len();
+        lines.truncate(MAX_LISTED_ENTITIES);
+        if total > MAX_LISTED_ENTITIES {
+            lines.push(format!(
+                "… {} more (filter by domain or area)",
+                total - MAX_LISTED_ENTITIES
+            ));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    async fn list_areas(&self) -> anyhow::Result<String> {
+        let areas = self.render_template(AREAS_TEMPLATE, json!({})).await?;
+        let lines: Vec<String> = areas
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|area| {
+                let id = area.get("id").and_then(Value::as_str).unwrap_or("?");
+                let name = area.get("name").and_then(Value::as_str).unwrap_or(id);
+                let count = area
+                    .get("entities")
+                    .and_then(Value::as_array)
+                    .map_or(0, Vec::len);
+                format!("{id}: {name} ({count} entities)")
+            })
+            .collect();
+        Ok(if lines.is_empty() {
+            "No areas defined.".into()
+        } else {
+            lines.join("\n")
+        })
+    }
+
+    async fn get_state(&self, entity_id: &str) -> anyhow::Result<String> {
+        let state = self.get_json(&format!("/api/states/{entity_id}")).await?;
+        let mut output = render_state_line(entity_id, &state);
+        if let Some(changed) = state.get("last_changed").and_then(Value::as_str) {
+            let _ = write!(output, "\nLast changed: {changed}");
+        }
+        if let Some(attributes) = state.get("attributes").filter(|a| a.is_object()) {
+            let _ = write!(
+                output,
+                "\nAttributes: {}",
+                serde_json::to_string_pretty(attributes)?
+            );
+        }
+        Ok(output)
+    }
+
+    /// Check a service call against the configured allowlists.
+    fn check_service_call(
+        &self,
+        domain: &str,
+        entity_ids: &[String],
+        data: &serde_json::Map<String, Value>,
+    ) -> Result<(), String> {
+        let domain_allowed =
+            self.config.allowed_domains.iter().any(|allowed| {
+                allowed.trim() == "*" || allowed.trim().eq_ignore_ascii_case(domain)
+            });
+        if !domain_allowed {
+            return Err(format!(
+                "Service domain '{domain}' is not in home_assistant.allowed_domains"
+            ));
+        }
+
+        if self.config.allowed_entities.is_empty() {
+            return Ok(());
+        }
+        if let Some(key) = INDIRECT_TARGET_KEYS
+            .iter()
+            .find(|key| data.contains_key(**key))
+        {
+            return Err(format!(
+                "'{key}' targets are not allowed while home_assistant.allowed_entities is set; \
+                 target entities by entity_id"
+            ));
+        }
+        if entity_ids.is_empty() {
+            return Err(
+                "Service calls must target entity_id while home_assistant.allowed_entities is set"
+                    .into(),
+            );
+        }
+        match entity_ids
+            .iter()
+            .find(|entity_id| !entity_allowed(&self.config.allowed_entities, entity_id))
+        {
+            Some(denied) => Err(format!(
+                "Entity '{denied}' is not in home_assistant.allowed_entities"
+            )),
+            None => Ok(()),
+        }
+    }
+
+    async fn call_service(&self, args: &Value) -> Result<String, String> {
+        let domain = required_identifier(args, "domain")?;
+        let service = required_identifier(args, "service")?;
+
+        let mut data = match args.get("data") {
+            None | Some(Value::Null) => serde_json::Map::new(),
+            Some(Value::Object(data)) => data.clone(),
+            Some(_) => return Err("'data' must be an object".into()),
+        };
+        let mut entity_ids = entity_id_list(args.get("entity_id"))?;
+        entity_ids.extend(entity_id_list(data.get("entity_id"))?);
+        if let Some(invalid) = entity_ids.iter().find(|id| !is_valid_entity_id(id)) {
+            return Err(format!("Invalid entity_id '{invalid}'"));
+        }
+        entity_ids.sort_unstable();
+        entity_ids.dedup();
+
+        self.check_service_call(domain, &entity_ids, &data)?;
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "home_assistant")?;
+
+        if !entity_ids.is_empty() {
+            data.insert("entity_id".into(), json!(entity_ids));
+        }
+        let request = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/api/services/{domain}/{service}"),
+            )
+            .map_err(|e| e.to_string())?
+            .json(&Value::Object(data));
+        let text = self.send(request).await.map_err(|e| e.to_string())?;
+
+        let changed: Vec<String> = serde_json::from_str::<Value>(&text)
+            .ok()
+            .as_ref()
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|state| {
+                let entity_id = state.get("entity_id")?.as_str()?;
+                Some(render_state_line(entity_id, state))
+            })
+            .collect();
+        let mut output = format!("Called {domain}.{service}");
+        if !entity_ids.is_empty() {
+            let _ = write!(output, " on {}", entity_ids.join(", "));
+        }
+        if changed.is_empty() {
+            output.push_str(". No entity states changed.");
+        } else {
+            output.push_str(".\nChanged states:\n");
+            output.push_str(&changed.join("\n"));
+        }
+        Ok(output)
+    }
+}
+
+fn entity_domain(entity_id: &str) -> &str {
+    entity_id
+        .split_once('.')
+        .map_or(entity_id, |(domain, _)| domain)
+}
+
+fn is_identifier(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_valid_entity_id(entity_id: &str) -> bool {
+    entity_id
+        .split_once('.')
+        .is_some_and(|(domain, object_id)| is_identifier(domain) && is_identifier(object_id))
+}
+
+/// Whether `entity_id` matches any of the `allowed_entities` glob patterns.
+fn entity_allowed(patterns: &[String], entity_id: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        glob::Pattern::new(pattern.trim()).is_ok_and(|pattern| pattern.matches(entity_id))
+    })
+}
+
+fn required_identifier<'a>(args: &'a Value, key: &str) -> Result<&'a str, String> {
+    let value = args
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| format!("Missing '{key}' parameter"))?;
+    if is_identifier(value) {
+        Ok(value)
+    } else {
+        Err(format!("Invalid {key} '{value}'"))
+    }
+}
+
+fn entity_id_list(value: Option<&Value>) -> Result<Vec<String>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(id)) => Ok(id
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect()),
+        Some(Value::Array(ids)) => ids
+            .iter()
+            .map(|id| {
+                id.as_str()
+                    .map(|id| id.trim().to_string())
+                    .ok_or_else(|| "'entity_id' entries must be strings".to_string())
+            })
+            .collect(),
+        Some(_) => Err("'entity_id' must be a string or an array of strings".into()),
+    }
+}
+
+fn render_state_line(entity_id: &str, state: &Value) -> String {
+    let value = state
+        .get("state")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let unit = state
+        .pointer("/attributes/unit_of_measurement")
+        .and_then(Value::as_str)
+        .map(|unit| format!(" {unit}"))
+        .unwrap_or_default();
+    match state
+        .pointer("/attributes/friendly_name")
+        .and_then(Value::as_str)
+    {
+        Some(name) => format!("{entity_id} ({name}): {value}{unit}"),
+        None => format!("{entity_id}: {value}{unit}"),
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for HomeAssistantTool {
+    fn name(&self) -> &str {
+        "home_assistant"
+    }
+
+    fn description(&self) -> &str {
+        "Read and control Home Assistant. Actions: 'list_entities' (optionally \
+         filtered by domain or area), 'list_areas', 'get_state' of an entity, \
+         'call_service' (e.g. light.turn_on) on allowlisted domains and entities."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list_entities", "list_areas", "get_state", "call_service"],
+                    "description": "Operation to perform"
+                },
+                "domain": {
+                    "type": "string",
+                    "description": "Entity domain filter (list_entities) or service domain (call_service), e.g. 'light'"
+                },
+                "area": {
+                    "type": "string",
+                    "description": "Area ID or name to filter entities by (list_entities)"
+                },
+                "entity_id": {
+                    "type": ["string", "array"],
+                    "items": {"type": "string"},
+                    "description": "Entity ID (get_state) or target entity IDs (call_service), e.g. 'light.kitchen'"
+                },
+                "service": {
+                    "type": "string",
+                    "description": "Service name (call_service), e.g. 'turn_on'"
+                },
+                "data": {
+                    "type": "object",
+                    "description": "Extra service data (call_service), e.g. {\"brightness_pct\": 50}"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args.get("action").and_then(Value::as_str).unwrap_or("");
+        let optional = |key: &str| {
+            args.get(key)
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let result = match action {
+            "list_entities" => self
+                .list_entities(optional("domain"), optional("area"))
+                .await
+                .map_err(|e| e.to_string()),
+            "list_areas" => self.list_areas().await.map_err(|e| e.to_string()),
+            "get_state" => match optional("entity_id") {
+                Some(entity_id) if is_valid_entity_id(entity_id) => {
+                    self.get_state(entity_id).await.map_err(|e| e.to_string())
+                }
+                Some(entity_id) => Err(format!("Invalid entity_id '{entity_id}'")),
+                None => Err("Missing 'entity_id' parameter".into()),
+            },
+            "call_service" => self.call_service(&args).await,
+            other => Err(format!(
+                "Unknown action '{other}'. Use list_entities, list_areas, get_state or call_service"
+            )),
+        };
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => failure(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_security(autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(url: String, autonomy: AutonomyLevel) -> HomeAssistantTool {
+        HomeAssistantTool::new(
+            test_security(autonomy),
+            HomeAssistantConfig {
+                enabled: true,
+                url,
+                token: Some("ha_token".into()),
+                allowed_domains: vec!["light".into()],
+                allowed_entities: vec!["light.kitchen_*".into()],
+                timeout_secs: 5,
+            },
+        )
+    }
+
+    fn states() -> Value {
+        json!([
+            {"entity_id": "light.kitchen_main", "state": "on",
+             "attributes": {"friendly_name": "Kitchen"}},
+            {"entity_id": "sensor.hall_temperature", "state": "21.5",
+             "attributes": {"unit_of_measurement": "°C"}},
+            {"entity_id": "lock.front_door", "state": "locked", "attributes": {}}
+        ])
+    }
+
+    #[tokio::test]
+    async fn list_entities_filters_by_domain_and_area() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/states"))
+            .and(header("authorization", "Bearer ha_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(states()))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/template"))
+            .and(body_partial_json(json!({"variables": {"area": "hall"}})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(r#"["sensor.hall_temperature", "lock.front_door"]"#),
+            )
+            .mount(&server)
+            .await;
+        let tool = test_tool(server.uri(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"action": "list_entities", "domain": "light"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "light.kitchen_main (Kitchen): on");
+
+        let result = tool
+            .execute(json!({"action": "list_entities", "area": "hall"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.output,
+            "lock.front_door: locked\nsensor.hall_temperature: 21.5 °C"
+        );
+    }
+
+    #[tokio::test]
+    async fn list_areas_renders_template_output() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/template"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"[{"id": "kitchen", "name": "Kitchen", "entities": ["light.kitchen_main"]}]"#,
+            ))
+            .mount(&server)
+            .await;
+        let tool = test_tool(server.uri(), AutonomyLevel::Supervised);
+
+        let result = tool.execute(json!({"action": "list_areas"})).await.unwrap();
+        assert_eq!(result.output, "kitchen: Kitchen (1 entities)");
+    }
+
+    #[tokio::test]
+    async fn get_state_includes_attributes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/states/lock.front_door"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "entity_id": "lock.front_door",
+                "state": "unlocked",
+                "last_changed": "2026-01-01T00:00:00+00:00",
+                "attributes": {"friendly_name": "Front door"}
+            })))
+            .mount(&server)
+            .await;
+        let tool = test_tool(server.uri(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"action": "get_state", "entity_id": "lock.front_door"}))
+            .await
+            .unwrap();
+        assert!(result
+            .output
+            .starts_with("lock.front_door (Front door): unlocked"));
+        assert!(result.output.contains("\"friendly_name\""));
+
+        let result = tool
+            .execute(json!({"action": "get_state", "entity_id": "../config"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn call_service_posts_allowlisted_targets() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/services/light/turn_on"))
+            .and(body_json(json!({
+                "brightness_pct": 40,
+                "entity_id": ["light.kitchen_main"]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"entity_id": "light.kitchen_main", "state": "on", "attributes": {}}
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let tool = test_tool(server.uri(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({
+                "action": "call_service",
+                "domain": "light",
+                "service": "turn_on",
+                "entity_id": "light.kitchen_main",
+                "data": {"brightness_pct": 40}
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("light.kitchen_main: on"));
+    }
+
+    #[tokio::test]
+    async fn call_service_rejects_calls_outside_allowlists() {
+        let tool = test_tool("http://127.0.0.1:9".into(), AutonomyLevel::Supervised);
+
+        for (args, expected) in [
+            (
+                json!({"domain": "lock", "service": "unlock", "entity_id": "lock.front_door"}),
+                "allowed_domains",
+            ),
+            (
+                json!({"domain": "light", "service": "turn_on", "entity_id": "light.bedroom"}),
+                "allowed_entities",
+            ),
+            (
+                json!({"domain": "light", "service": "turn_on", "data": {"area_id": "kitchen"}}),
+                "area_id",
+            ),
+            (
+                json!({"domain": "light", "service": "turn_on"}),
+                "target entity_id",
+            ),
+        ] {
+            let mut args = args;
+            args["action"] = json!("call_service");
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(
+                result.error.as_deref().unwrap_or("").contains(expected),
+                "{:?}",
+                result.error
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn call_service_is_blocked_in_read_only_mode() {
+        let tool = test_tool("http://127.0.0.1:9".into(), AutonomyLevel::ReadOnly);
+        let result = tool
+            .execute(json!({
+                "action": "call_service",
+                "domain": "light",
+                "service": "turn_off",
+                "entity_id": "light.kitchen_main"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[test]
+    fn entity_patterns_use_globs() {
+        let patterns = vec!["light.kitchen_*".to_string(), "switch.fan".to_string()];
+        assert!(entity_allowed(&patterns, "light.kitchen_main"));
+        assert!(entity_allowed(&patterns, "switch.fan"));
+        assert!(!entity_allowed(&patterns, "switch.fan_2"));
+        assert!(!is_valid_entity_id("light"));
+        assert!(!is_valid_entity_id("light.kitchen/../x"));
+    }
+}
@@ -198,7 +198,7 @@ impl Tool for HttpRequestTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -0,0 +1,793 @@
+//! Image generation through OpenAI-compatible or Stable Diffusion APIs.
+//!
+//! Talks to the backend configured under `[image_gen]`. Images are written
+//! to `output_dir` inside the workspace and returned as `[IMAGE:<path>]`
+//! markers, which channels turn into photo uploads. When `[cost]` is enabled
+//! every call is checked against the budget and recorded as a cost entry.
+
+use super::traits::{Tool, ToolResult};
+use crate::config::{ImageGenBackend, ImageGenConfig};
+use crate::cost::{BudgetCheck, CostTracker, TokenUsage};
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use base64::Engine;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const IMAGE_GEN_CONNECT_TIMEOUT_SECS: u64 = 10;
+const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+const MAX_SLUG_CHARS: usize = 40;
+
+pub struct ImageGenerateTool {
+    security: Arc<SecurityPolicy>,
+    config: ImageGenConfig,
+    workspace_dir: PathBuf,
+    cost_tracker: Option<Arc<CostTracker>>,
+}
+
+/// One generated image before it is written to disk.
+struct GeneratedImage {
+    bytes: Vec<u8>,
+    /// Prompt as rewritten by the backend (DALL·E 3 does this).
+    revised_prompt: Option<String>,
+}
+
+impl ImageGenerateTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        config: ImageGenConfig,
+        workspace_dir: PathBuf,
+    ) -> Self {
+        Self {
+            security,
+            config,
+            workspace_dir,
+            cost_tracker: None,
+        }
+    }
+
+    /// Check generations against the `[cost]` budget and record their cost.
+    pub fn with_cost_tracker(mut self, cost_tracker: Option<Arc<CostTracker>>) -> Self {
+        self.cost_tracker = cost_tracker;
+        self
+    }
+
+    fn api_base(&self) -> String {
+        match self.config.api_url.as_deref().map(str::trim) {
+            Some(url) if !url.is_empty() => url.trim_end_matches('/').to_string(),
+            _ => match self.config.backend {
+                ImageGenBackend::Openai => "https://api.openai.com/v1".into(),
+                ImageGenBackend::StableDiffusion => "http://127.0.0.1:7860".into(),
+            },
+        }
+    }
+
+    fn api_key(&self) -> Option<String> {
+        let configured = self
+            .config
+            .api_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(String::from);
+        match self.config.backend {
+            ImageGenBackend::Openai => configured.or_else(|| {
+                std::env::var("OPENAI_API_KEY")
+                    .ok()
+                    .filter(|key| !key.trim().is_empty())
+            }),
+            ImageGenBackend::StableDiffusion => configured,
+        }
+    }
+
+    /// Model name used for cost records.
+    fn cost_model(&self) -> String {
+        match self.config.backend {
+            ImageGenBackend::Openai => format!("image/{}", self.config.model),
+            ImageGenBackend::StableDiffusion => "image/stable-diffusion".into(),
+        }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.image_generate",
+            self.config.timeout_secs.max(1),
+            IMAGE_GEN_CONNECT_TIMEOUT_SECS,
+        )
+    }
+
+    async fn post(&self, url: &str, body: &Value) -> Result<Value, String> {
+        let mut request = self.client().post(url).json(body);
+        if let Some(key) = self.api_key() {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Image API request failed: {e}"))?;
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(format!(
+                "Image API returned HTTP {status}: {}",
+                crate::providers::sanitize_api_error(&text)
+            ));
+        }
+        serde_json::from_str(&text).map_err(|e| format!("Image API returned invalid JSON: {e}"))
+    }
+
+    async fn download(&self, url: &str) -> Result<Vec<u8>, String> {
+        let response = self
+            .client()
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| format!("Image download failed: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Image download returned HTTP {}",
+                response.status()
+            ));
+        }
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Image download failed: {e}"))?;
+        check_size(bytes.len())?;
+        Ok(bytes.to_vec())
+    }
+
+    async fn generate_openai(
+        &self,
+        prompt: &str,
+        size: &str,
+        count: u32,
+    ) -> Result<Vec<GeneratedImage>, String> {
+        if self.api_key().is_none() && self.config.api_url.is_none() {
+            return Err(
+                "image_gen.api_key is not configured (and OPENAI_API_KEY is unset)".to_string(),
+            );
+        }
+        let mut body = json!({
+            "model": self.config.model,
+            "prompt": prompt,
+            "n": count,
+            "size": size,
+        });
+        // gpt-image-* always returns base64 and rejects `response_format`.
+        if self.config.model.starts_with("dall-e") {
+            body["response_format"] = json!("b64_json");
+        }
+        let response = self
+            .post(&format!("{}/images/generations", self.api_base()), &body)
+            .await?;
+
+        let mut images = Vec::new();
+        for item in response
+            .get("data")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let bytes = if let Some(encoded) = item.get("b64_json").and_then(Value::as_str) {
+                decode_image(encoded)?
+            } else if let Some(url) = item.get("url").and_then(Value::as_str) {
+                self.download(url).await?
+            } else {
+                continue;
+            };
+            images.push(GeneratedImage {
+                bytes,
+                revised_prompt: item
+                    .get("revised_prompt")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            });
+        }
+        Ok(images)
+    }
+
+    async fn generate_stable_diffusion(
+        &self,
+        prompt: &str,
+        negative_prompt: &str,
+        size: &str,
+        count: u32,
+    ) -> Result<Vec<GeneratedImage>, String> {
+        let (width, height) = parse_dimensions(size).ok_or_else(|| {
+            format!("Stable Diffusion needs an explicit WIDTHxHEIGHT size, got '{size}'")
+        })?;
+        let body = json!({
+            "prompt": prompt,
+            "negative_prompt": negative_prompt,
+            "width": width,
+            "height": height,
+            "batch_size": count,
+        });
+        let response = self
+            .post(&format!("{}/sdapi/v1/txt2img", self.api_base()), &body)
+            .await?;
+
+        response
+            .get("images")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|encoded| {
+                Ok(GeneratedImage {
+                    bytes: decode_image(encoded)?,
+                    revised_prompt: None,
+                })
+            })
+            .collect()
+    }
+
+    /// Write `images` under the output folder and return their absolute paths.
+    fn save(&self, prompt: &str, images: &[GeneratedImage]) -> Result<Vec<PathBuf>, String> {
+        let dir = self.output_dir()?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {e}", dir.display()))?;
+        let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+        let slug = slugify(prompt);
+
+        let mut paths = Vec::with_capacity(images.len());
+        for image in images {
+            let id = uuid::Uuid::new_v4().simple().to_string();
+            let path = dir.join(format!(
+                "{stamp}-{slug}-{}.{}",
+                &id[..8],
+                image_extension(&image.bytes)
+            ));
+            std::fs::write(&path, &image.bytes)
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn output_dir(&self) -> Result<PathBuf, String> {
+        let relative = Path::new(self.config.output_dir.trim());
+        if relative.is_absolute()
+            || relative
+                .components()
+                .any(|part| matches!(part, std::path::Component::ParentDir))
+        {
+            return Err(format!(
+                "image_gen.output_dir must be a path inside the workspace, got '{}'",
+                self.config.output_dir
+            ));
+        }
+        let workspace = std::fs::canonicalize(&self.workspace_dir)
+            .unwrap_or_else(|_| self.workspace_dir.clone());
+        Ok(workspace.join(relative))
+    }
+
+    async fn check_budget(&self, estimated_cost_usd: f64) -> Result<(), String> {
+        let Some(tracker) = &self.cost_tracker else {
+            return Ok(());
+        };
+        match tracker.check_budget(estimated_cost_usd).await {
+            Ok(BudgetCheck::Exceeded {
+                current_usd,
+                limit_usd,
+                period,
+            }) => Err(format!(
+                "Image generation blocked: {period:?} cost budget exceeded \
+                 (${current_usd:.2} of ${limit_usd:.2})"
+            )),
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::warn!("image_generate: budget check failed: {e}");
+                Ok(())
+            }
+        }
+    }
+
+    async fn record_cost(&self, images: usize) {
+        let Some(tracker) = &self.cost_tracker else {
+            return;
+        };
+        let usage = TokenUsage {
+            model: self.cost_model(),
+            input_tokens: 0,
+            output_tokens: 0,
+            total_tokens: 0,
+            cost_usd: self.config.cost_per_image_usd.max(0.0) * images as f64,
+            timestamp: chrono::Utc::now(),
+        };
+        if let Err(e) = tracker.record_usage(usage).await {
+            tracing::warn!("image_generate: failed to record cost: {e}");
+        }
+    }
+
+    async fn generate(&self, args: &Value) -> Result<String, String> {
+        let prompt = args
+            .get("prompt")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|prompt| !prompt.is_empty())
+            .ok_or_else(|| "Missing 'prompt' parameter".to_string())?;
+        let size = args
+            .get("size")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|size| !size.is_empty())
+            .unwrap_or(&self.config.size);
+        if size != "auto" && parse_dimensions(size).is_none() {
+            return Err(format!(
+                "Invalid size '{size}'; use WIDTHxHEIGHT (e.g. 1024x1024)"
+            ));
+        }
+        let max_images = self.config.max_images.max(1);
+        let count =
+            u32::try_from(args.get("n").and_then(Value::as_u64).unwrap_or(1)).unwrap_or(u32::MAX);
+        if count == 0 || count > max_images {
+            return Err(format!("'n' must be between 1 and {max_images}"));
+        }
+
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "image_generate")?;
+        self.check_budget(self.config.cost_per_image_usd.max(0.0) * f64::from(count))
+            .await?;
+
+        let images = match self.config.backend {
+            ImageGenBackend::Openai => self.generate_openai(prompt, size, count).await?,
+            ImageGenBackend::StableDiffusion => {
+                let negative_prompt = args
+                    .get("negative_prompt")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                self.generate_stable_diffusion(prompt, negative_prompt, size, count)
+                    .await?
+            }
+        };
+        if images.is_empty() {
+            return Err("Image API returned no images".to_string());
+        }
+        self.record_cost(images.len()).await;
+        let paths = self.save(prompt, &images)?;
+
+        let mut output = format!(
+            "Generated {} image{}:\n",
+            paths.len(),
+            if paths.len() == 1 { "" } else { "s" }
+        );
+        for path in &paths {
+            let _ = writeln!(output, "[IMAGE:{}]", path.display());
+        }
+        if let Some(revised) = images
+            .iter()
+            .find_map(|image| image.revised_prompt.as_deref())
+        {
+            let _ = writeln!(output, "Revised prompt: {revised}");
+        }
+        output.push_str("Include the [IMAGE:...] markers in your reply to send the images.");
+        Ok(output)
+    }
+}
+
+fn decode_image(encoded: &str) -> Result<Vec<u8>, String> {
+    // Some servers prefix the payload with a data URI header.
+    let payload = encoded
+        .split_once(";base64,")
+        .map_or(encoded, |(_, data)| data)
+        .trim();
+    check_size(payload.len() / 4 * 3)?;
+    base64::engine::general_purpose::STANDARD
+        .decode(payload)
+        .map_err(|e| format!("Image API returned invalid base64 image data: {e}"))
+}
+
+fn check_size(bytes: usize) -> Result<(), String> {
+    if bytes > MAX_IMAGE_BYTES {
+        return Err(format!(
+            "Generated image exceeds {} MB",
+            MAX_IMAGE_BYTES / (1024 * 1024)
+        ));
+    }
+    Ok(())
+}
+
+/// Parse `WIDTHxHEIGHT`.
+fn parse_dimensions(size: &str) -> Option<(u32, u32)> {
+    let (width, height) = size.split_once(['x', 'X'])?;
+    let width = width.trim().parse().ok().filter(|w| *w > 0)?;
+    let height = height.trim().parse().ok().filter(|h| *h > 0)?;
+    Some((width, height))
+}
+
+/// File extension for the image format detected from its magic bytes.
+fn image_extension(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "jpg"
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "webp"
+    } else {
+        "png"
+    }
+}
+
+/// Short, filesystem-safe name derived from the prompt.
+fn slugify(prompt: &str) -> String {
+    let mut slug = String::new();
+    for word in prompt
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if slug.len() + word.len() + 1 > MAX_SLUG_CHARS {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    if slug.is_empty() {
+        "image".into()
+    } else {
+        slug
+    }
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for ImageGenerateTool {
+    fn name(&self) -> &str {
+        "image_generate"
+    }
+
+    fn description(&self) -> &str {
+        "Generate images from a text prompt (diagrams, illustrations, pictures). \
+         Images are saved to the workspace and returned as [IMAGE:<path>] markers; \
+         include the markers in your reply to send the images to the user."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "prompt": {
+                    "type": "string",
+                    "description": "Detailed description of the image to generate"
+                },
+                "size": {
+                    "type": "string",
+                    "description": "Image size as WIDTHxHEIGHT (e.g. 1024x1024, 1536x1024). Defaults to the configured size"
+                },
+                "n": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Number of images to generate (default 1)"
+                },
+                "negative_prompt": {
+                    "type": "string",
+                    "description": "Things to avoid in the image (Stable Diffusion backends only)"
+                }
+            },
+            "required": ["prompt"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        Ok(match self.generate(&args).await {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => failure(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CostConfig;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PNG_BYTES: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 1, 2, 3];
+    const JPEG_BYTES: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 4, 5, 6];
+
+    fn encode(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn test_security(autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        })
+    }
+
+    fn test_tool(
+        backend: ImageGenBackend,
+        api_url: String,
+        autonomy: AutonomyLevel,
+        workspace: &Path,
+    ) -> ImageGenerateTool {
+        ImageGenerateTool::new(
+            test_security(autonomy),
+            ImageGenConfig {
+                enabled: true,
+                backend,
+                api_url: Some(api_url),
+                api_key: Some("img-key".into()),
+                ..ImageGenConfig::default()
+            },
+            workspace.to_path_buf(),
+        )
+    }
+
+    fn saved_paths(output: &str) -> Vec<PathBuf> {
+        output
+            .lines()
+            .filter_map(|line| line.strip_prefix("[IMAGE:")?.strip_suffix(']'))
+            .map(PathBuf::from)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn openai_backend_saves_base64_images_to_workspace() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/images/generations"))
+            .and(header("authorization", "Bearer img-key"))
+            .and(body_partial_json(json!({
+                "model": "gpt-image-1",
+                "prompt": "A lighthouse at dusk",
+                "n": 2,
+                "size": "1536x1024"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{"b64_json": encode(PNG_BYTES)}, {"b64_json": encode(JPEG_BYTES)}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let workspace = TempDir::new().unwrap();
+        let tool = test_tool(
+            ImageGenBackend::Openai,
+            format!("{}/v1", server.uri()),
+            AutonomyLevel::Supervised,
+            workspace.path(),
+        );
+
+        let result = tool
+            .execute(json!({"prompt": "A lighthouse at dusk", "n": 2, "size": "1536x1024"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+        assert!(result.output.starts_with("Generated 2 images:"));
+
+        let paths = saved_paths(&result.output);
+        assert_eq!(paths.len(), 2);
+        let images_dir = workspace.path().canonicalize().unwrap().join("images");
+        assert!(paths.iter().all(|p| p.starts_with(&images_dir)));
+        assert!(paths[0].to_string_lossy().contains("a-lighthouse-at-dusk"));
+        assert_eq!(paths[0].extension().unwrap(), "png");
+        assert_eq!(paths[1].extension().unwrap(), "jpg");
+        assert_eq!(std::fs::read(&paths[0]).unwrap(), PNG_BYTES);
+        assert_eq!(std::fs::read(&paths[1]).unwrap(), JPEG_BYTES);
+    }
+
+    #[tokio::test]
+    async fn dall_e_url_results_are_downloaded() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/images/generations"))
+            .and(body_partial_json(json!({"response_format": "b64_json"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{
+                    "url": format!("{}/blob/cat.png", server.uri()),
+                    "revised_prompt": "A fluffy cat on a windowsill"
+                }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/blob/cat.png"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(PNG_BYTES))
+            .mount(&server)
+            .await;
+        let workspace = TempDir::new().unwrap();
+        let mut tool = test_tool(
+            ImageGenBackend::Openai,
+            format!("{}/v1", server.uri()),
+            AutonomyLevel::Supervised,
+            workspace.path(),
+        );
+        tool.config.model = "dall-e-3".into();
+
+        let result = tool.execute(json!({"prompt": "a cat"})).await.unwrap();
+        assert!(result.success, "{result:?}");
+        assert!(result
+            .output
+            .contains("Revised prompt: A fluffy cat on a windowsill"));
+        let paths = saved_paths(&result.output);
+        assert_eq!(std::fs::read(&paths[0]).unwrap(), PNG_BYTES);
+    }
+
+    #[tokio::test]
+    async fn stable_diffusion_backend_uses_txt2img() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/sdapi/v1/txt2img"))
+            .and(body_partial_json(json!({
+                "prompt": "isometric server rack diagram",
+                "negative_prompt": "text, watermark",
+                "width": 768,
+                "height": 512,
+                "batch_size": 1
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "images": [format!("data:image/png;base64,{}", encode(PNG_BYTES))],
+                "parameters": {}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let workspace = TempDir::new().unwrap();
+        let tool = test_tool(
+            ImageGenBackend::StableDiffusion,
+            server.uri(),
+            AutonomyLevel::Supervised,
+            workspace.path(),
+        );
+
+        let result = tool
+            .execute(json!({
+                "prompt": "isometric server rack diagram",
+                "negative_prompt": "text, watermark",
+                "size": "768x512"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+        let paths = saved_paths(&result.output);
+        assert_eq!(std::fs::read(&paths[0]).unwrap(), PNG_BYTES);
+
+        let auto = tool
+            .execute(json!({"prompt": "anything", "size": "auto"}))
+            .await
+            .unwrap();
+        assert!(!auto.success);
+        assert!(auto.error.unwrap().contains("WIDTHxHEIGHT"));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_requests_without_calling_the_api() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let workspace = TempDir::new().unwrap();
+        let tool = test_tool(
+            ImageGenBackend::Openai,
+            format!("{}/v1", server.uri()),
+            AutonomyLevel::Supervised,
+            workspace.path(),
+        );
+
+        for (args, expected) in [
+            (json!({}), "Missing 'prompt'"),
+            (json!({"prompt": "x", "n": 5}), "between 1 and 4"),
+            (json!({"prompt": "x", "n": 0}), "between 1 and 4"),
+            (json!({"prompt": "x", "size": "huge"}), "Invalid size"),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains(expected));
+        }
+
+        let read_only = test_tool(
+            ImageGenBackend::Openai,
+            format!("{}/v1", server.uri()),
+            AutonomyLevel::ReadOnly,
+            workspace.path(),
+        );
+        let result = read_only.execute(json!({"prompt": "x"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn api_errors_are_reported() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/images/generations"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": {"message": "Your request was rejected by the safety system."}
+            })))
+            .mount(&server)
+            .await;
+        let workspace = TempDir::new().unwrap();
+        let tool = test_tool(
+            ImageGenBackend::Openai,
+            format!("{}/v1", server.uri()),
+            AutonomyLevel::Supervised,
+            workspace.path(),
+        );
+
+        let result = tool.execute(json!({"prompt": "x"})).await.unwrap();
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("HTTP 400"));
+        assert!(error.contains("safety system"));
+        assert!(!workspace.path().join("images").exists());
+    }
+
+    #[tokio::test]
+    async fn generations_are_recorded_and_limited_by_cost_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/images/generations"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{"b64_json": encode(PNG_BYTES)}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let workspace = TempDir::new().unwrap();
+        let tracker = Arc::new(
+            CostTracker::new(
+                CostConfig {
+                    enabled: true,
+                    daily_limit_usd: 0.05,
+                    ..CostConfig::default()
+                },
+                workspace.path(),
+            )
+            .unwrap(),
+        );
+        let tool = test_tool(
+            ImageGenBackend::Openai,
+            format!("{}/v1", server.uri()),
+            AutonomyLevel::Supervised,
+            workspace.path(),
+        )
+        .with_cost_tracker(Some(tracker.clone()));
+
+        let result = tool.execute(json!({"prompt": "x"})).await.unwrap();
+        assert!(result.success, "{result:?}");
+        let summary = tracker.get_summary().await.unwrap();
+        assert!((summary.session_cost_usd - 0.04).abs() < 1e-9);
+        assert!(summary.by_model.contains_key("image/gpt-image-1"));
+
+        let blocked = tool.execute(json!({"prompt": "x"})).await.unwrap();
+        assert!(!blocked.success);
+        assert!(blocked.error.unwrap().contains("budget exceeded"));
+    }
+
+    #[test]
+    fn helpers_parse_sizes_and_name_files() {
+        assert_eq!(parse_dimensions("1024x768"), Some((1024, 768)));
+        assert_eq!(parse_dimensions("512X512"), Some((512, 512)));
+        assert_eq!(parse_dimensions("0x512"), None);
+        assert_eq!(parse_dimensions("auto"), None);
+        assert_eq!(
+            slugify("A diagram: Kafka -> Flink -> S3!"),
+            "a-diagram-kafka-flink-s3"
+        );
+        assert_eq!(slugify("日本の風景"), "image");
+        assert_eq!(image_extension(b"RIFF\0\0\0\0WEBPVP8 "), "webp");
+        assert_eq!(image_extension(JPEG_BYTES), "jpg");
+        assert_eq!(image_extension(PNG_BYTES), "png");
+    }
+}
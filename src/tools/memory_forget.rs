@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{namespace, Memory};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -35,6 +35,11 @@ impl Tool for MemoryForgetTool {
                 "key": {
                     "type": "string",
                     "description": "The key of the memory to forget"
+                },
+                "scope": {
+                    "type": "string",
+                    "enum": ["personal", "shared"],
+                    "description": "'personal' (default) forgets from the current person's memories; 'shared' from memories visible to everyone."
                 }
             },
             "required": ["key"]
@@ -47,10 +52,23 @@ impl Tool for MemoryForgetTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))?;
 
+        let namespace = match args.get("scope").and_then(|v| v.as_str()) {
+            Some("personal") | None => namespace::active(),
+            Some("shared") => None,
+            Some(other) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "'scope' must be 'personal' or 'shared', got '{other}'"
+                    )),
+                });
+            }
+        };
+
         if let Err(error) = self
             .security
             .enforce_tool_operation(ToolOperation::Act, "memory_forget")
-            .await
         {
             return Ok(ToolResult {
                 success: false,
@@ -59,7 +77,7 @@ impl Tool for MemoryForgetTool {
             });
         }
 
-        match self.memory.forget(key).await {
+        match self.memory.forget_in(namespace.as_deref(), key).await {
             Ok(true) => Ok(ToolResult {
                 success: true,
                 output: format!("Forgot memory: {key}"),
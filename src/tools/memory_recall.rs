@@ -1,10 +1,13 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{namespace, Memory, MemoryMetadata};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
 use std::sync::Arc;
 
+/// Recall this many times `limit` entries when filtering by tag.
+const TAG_FILTER_OVERFETCH: usize = 4;
+
 /// Let the agent search its own memory
 pub struct MemoryRecallTool {
     memory: Arc<dyn Memory>,
@@ -37,6 +40,11 @@ impl Tool for MemoryRecallTool {
                 "limit": {
                     "type": "integer",
                     "description": "Max results to return (default: 5)"
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only return memories carrying at least one of these tags"
                 }
             },
             "required": ["query"]
@@ -55,7 +63,39 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        let tags: Vec<String> = args
+            .get("tags")
+            .and_then(serde_json::Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(|tag| tag.trim().to_ascii_lowercase())
+                    .filter(|tag| !tag.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Over-fetch when filtering by tag so the filter still fills `limit`.
+        let fetch_limit = if tags.is_empty() {
+            limit
+        } else {
+            limit.saturating_mul(TAG_FILTER_OVERFETCH)
+        };
+        let recalled = self
+            .memory
+            .recall_in(namespace::active().as_deref(), query, fetch_limit, None)
+            .await
+            .map(|entries| {
+                entries
+                    .into_iter()
+                    .filter(|entry| {
+                        tags.is_empty() || entry.metadata.tags.iter().any(|tag| tags.contains(tag))
+                    })
+                    .take(limit)
+                    .collect::<Vec<_>>()
+            });
+
+        match recalled {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
@@ -69,8 +109,11 @@ impl Tool for MemoryRecallTool {
                         .map_or_else(String::new, |s| format!(" [{s:.0}%]"));
                     let _ = writeln!(
                         output,
-                        "- [{}] {}: {}{score}",
-                        entry.category, entry.key, entry.content
+                        "- [{}] {}: {}{score}{}",
+                        entry.category,
+                        entry.key,
+                        entry.content,
+                        metadata_suffix(&entry.metadata)
                     );
                 }
                 Ok(ToolResult {
@@ -88,6 +131,25 @@ impl Tool for MemoryRecallTool {
     }
 }
 
+/// Render tags, importance and expiry as ` (tags: a, b; importance: 0.9; expires: ...)`.
+fn metadata_suffix(metadata: &MemoryMetadata) -> String {
+    let mut parts = Vec::new();
+    if !metadata.tags.is_empty() {
+        parts.push(format!("tags: {}", metadata.tags.join(", ")));
+    }
+    if let Some(importance) = metadata.importance {
+        parts.push(format!("importance: {importance:.1}"));
+    }
+    if let Some(expires_at) = &metadata.expires_at {
+        parts.push(format!("expires: {expires_at}"));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join("; "))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,6 +211,32 @@ mod tests {
         assert!(result.output.contains("Found 3"));
     }
 
+    #[tokio::test]
+    async fn recall_filters_by_tag_and_shows_metadata() {
+        let (_tmp, mem) = seeded_mem();
+        let tagged = MemoryMetadata {
+            tags: vec!["work".into()],
+            importance: Some(0.9),
+            ..MemoryMetadata::default()
+        };
+        mem.store_with_metadata("job", "Rust at work", MemoryCategory::Core, None, &tagged)
+            .await
+            .unwrap();
+        mem.store("hobby", "Rust at home", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool
+            .execute(json!({"query": "Rust", "tags": ["Work"]}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Found 1"));
+        assert!(result.output.contains("tags: work; importance: 0.9"));
+        assert!(!result.output.contains("hobby"));
+    }
+
     #[tokio::test]
     async fn recall_missing_query() {
         let (_tmp, mem) = seeded_mem();
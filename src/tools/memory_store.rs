@@ -1,5 +1,7 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::{Memory, MemoryCategory};
+use crate::memory::metadata::parse_expiry;
+use crate::memory::namespace;
+use crate::memory::{Memory, MemoryCategory, MemoryMetadata, MemorySource};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -43,6 +45,24 @@ impl Tool for MemoryStoreTool {
                 "category": {
                     "type": "string",
                     "description": "Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or a custom category name. Defaults to 'core'."
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional tags for filtering recall (e.g. ['work', 'travel'])"
+                },
+                "importance": {
+                    "type": "number",
+                    "description": "Optional importance from 0.0 to 1.0; higher values rank earlier in recall. Defaults to 0.5."
+                },
+                "expires": {
+                    "type": "string",
+                    "description": "Optional expiry: a duration like '30m', '12h', '7d', '2w' or an RFC 3339 timestamp. Expired memories are hidden and later purged."
+                },
+                "scope": {
+                    "type": "string",
+                    "enum": ["personal", "shared"],
+                    "description": "'personal' (default) keeps the memory private to the person you are talking to; 'shared' makes it visible to everyone."
                 }
             },
             "required": ["key", "content"]
@@ -67,10 +87,62 @@ impl Tool for MemoryStoreTool {
             Some(other) => MemoryCategory::Custom(other.to_string()),
         };
 
+        let tags = args
+            .get("tags")
+            .and_then(serde_json::Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(|tag| tag.trim().to_ascii_lowercase())
+                    .filter(|tag| !tag.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let importance = match args.get("importance").and_then(serde_json::Value::as_f64) {
+            Some(value) if !(0.0..=1.0).contains(&value) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "'importance' must be between 0.0 and 1.0, got {value}"
+                    )),
+                });
+            }
+            other => other,
+        };
+
+        let expires_at = match args.get("expires").and_then(|v| v.as_str()) {
+            Some(raw) => match parse_expiry(raw) {
+                Ok(expires_at) => Some(expires_at),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid 'expires': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let namespace = match args.get("scope").and_then(|v| v.as_str()) {
+            Some("personal") | None => namespace::active(),
+            Some("shared") => None,
+            Some(other) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "'scope' must be 'personal' or 'shared', got '{other}'"
+                    )),
+                });
+            }
+        };
+
         if let Err(error) = self
             .security
             .enforce_tool_operation(ToolOperation::Act, "memory_store")
-            .await
         {
             return Ok(ToolResult {
                 success: false,
@@ -79,10 +151,36 @@ impl Tool for MemoryStoreTool {
             });
         }
 
-        match self.memory.store(key, content, category, None).await {
+        let output = match &expires_at {
+            Some(expires_at) => format!("Stored memory: {key} (expires {expires_at})"),
+            None => format!("Stored memory: {key}"),
+        };
+        let metadata = MemoryMetadata {
+            tags,
+            importance,
+            expires_at,
+            source: Some(MemorySource {
+                tool: Some("memory_store".into()),
+                ..MemorySource::default()
+            }),
+            ..MemoryMetadata::default()
+        };
+
+        match self
+            .memory
+            .store_in(
+                namespace.as_deref(),
+                key,
+                content,
+                category,
+                None,
+                &metadata,
+            )
+            .await
+        {
             Ok(()) => Ok(ToolResult {
                 success: true,
-                output: format!("Stored memory: {key}"),
+                output,
                 error: None,
             }),
             Err(e) => Ok(ToolResult {
@@ -165,6 +263,55 @@ mod tests {
         assert_eq!(entry.category, MemoryCategory::Custom("project".into()));
     }
 
+    #[tokio::test]
+    async fn store_with_tags_importance_and_expiry() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        let result = tool
+            .execute(json!({
+                "key": "trip",
+                "content": "Flying to Lisbon",
+                "tags": ["Travel", " "],
+                "importance": 0.8,
+                "expires": "7d"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("expires"));
+
+        let entry = mem.get("trip").await.unwrap().unwrap();
+        assert_eq!(entry.metadata.tags, ["travel"]);
+        assert_eq!(entry.metadata.importance, Some(0.8));
+        assert!(entry.metadata.expires_at.is_some());
+        assert_eq!(
+            entry
+                .metadata
+                .source
+                .and_then(|source| source.tool)
+                .as_deref(),
+            Some("memory_store")
+        );
+    }
+
+    #[tokio::test]
+    async fn store_rejects_invalid_importance_and_expiry() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        let result = tool
+            .execute(json!({"key": "k", "content": "v", "importance": 2.0}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        let result = tool
+            .execute(json!({"key": "k", "content": "v", "expires": "soon"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(mem.count().await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn store_missing_key() {
         let (_tmp, mem) = test_mem();
@@ -222,4 +369,27 @@ mod tests {
             .contains("Rate limit exceeded"));
         assert!(mem.get("lang").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn store_uses_active_namespace_unless_shared() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        namespace::with_namespace(Some("alice".into()), async {
+            let personal = tool
+                .execute(json!({"key": "diet", "content": "Vegetarian"}))
+                .await
+                .unwrap();
+            assert!(personal.success);
+            let shared = tool
+                .execute(json!({"key": "wifi", "content": "Guest wifi is open", "scope": "shared"}))
+                .await
+                .unwrap();
+            assert!(shared.success);
+        })
+        .await;
+
+        assert!(mem.get("diet").await.unwrap().is_none());
+        assert!(mem.get_in(Some("alice"), "diet").await.unwrap().is_some());
+        assert!(mem.get("wifi").await.unwrap().is_some());
+    }
 }
@@ -15,6 +15,7 @@
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
+pub mod a2a;
 pub mod agents_ipc;
 pub mod browser;
 pub mod browser_open;
@@ -31,6 +32,7 @@ pub mod delegate;
 pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
+pub mod forge;
 pub mod git_operations;
 pub mod glob_search;
 #[cfg(feature = "hardware")]
@@ -39,12 +41,16 @@ pub mod hardware_board_info;
 pub mod hardware_memory_map;
 #[cfg(feature = "hardware")]
 pub mod hardware_memory_read;
+pub mod home_assistant;
 pub mod http_request;
+pub mod image_generate;
 pub mod image_info;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod model_routing_config;
+#[cfg(feature = "channel-mqtt")]
+pub mod mqtt_publish;
 pub mod pdf_read;
 pub mod process;
 pub mod proxy_config;
@@ -53,13 +59,25 @@ pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 pub mod shell;
+#[cfg(feature = "sop")]
+pub mod sop_advance;
+#[cfg(feature = "sop")]
+pub mod sop_approve;
+#[cfg(feature = "sop")]
+pub mod sop_execute;
+#[cfg(feature = "sop")]
+pub mod sop_list;
+#[cfg(feature = "sop")]
+pub mod sop_status;
 pub mod task_plan;
 pub mod traits;
 pub mod url_validation;
+pub mod vault;
 pub mod wasm_module;
 pub mod web_fetch;
 pub mod web_search_tool;
 
+pub use a2a::A2aTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
 pub use composio::ComposioTool;
@@ -74,6 +92,7 @@ pub use delegate::DelegateTool;
 pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
+pub use forge::ForgeTool;
 pub use git_operations::GitOperationsTool;
 pub use glob_search::GlobSearchTool;
 #[cfg(feature = "hardware")]
@@ -82,12 +101,16 @@ pub use hardware_board_info::HardwareBoardInfoTool;
 pub use hardware_memory_map::HardwareMemoryMapTool;
 #[cfg(feature = "hardware")]
 pub use hardware_memory_read::HardwareMemoryReadTool;
+pub use home_assistant::HomeAssistantTool;
 pub use http_request::HttpRequestTool;
+pub use image_generate::ImageGenerateTool;
 pub use image_info::ImageInfoTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
+#[cfg(feature = "channel-mqtt")]
+pub use mqtt_publish::MqttPublishTool;
 pub use pdf_read::PdfReadTool;
 pub use process::ProcessTool;
 pub use proxy_config::ProxyConfigTool;
@@ -97,10 +120,21 @@ pub use schedule::ScheduleTool;
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+#[cfg(feature = "sop")]
+pub use sop_advance::SopAdvanceTool;
+#[cfg(feature = "sop")]
+pub use sop_approve::SopApproveTool;
+#[cfg(feature = "sop")]
+pub use sop_execute::SopExecuteTool;
+#[cfg(feature = "sop")]
+pub use sop_list::SopListTool;
+#[cfg(feature = "sop")]
+pub use sop_status::SopStatusTool;
 pub use task_plan::TaskPlanTool;
 pub use traits::Tool;
 #[allow(unused_imports)]
 pub use traits::{ToolResult, ToolSpec};
+pub use vault::VaultTool;
 pub use wasm_module::WasmModuleTool;
 pub use web_fetch::WebFetchTool;
 pub use web_search_tool::WebSearchTool;
@@ -250,7 +284,7 @@ pub fn all_tools_with_runtime(
         Arc::new(CronRunsTool::new(config.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(MemoryForgetTool::new(memory.clone(), security.clone())),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(TaskPlanTool::new(security.clone())),
         Arc::new(ModelRoutingConfigTool::new(
@@ -366,6 +400,39 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // MQTT publishing (feature-gated; topics are allowlisted in SecurityPolicy)
+    #[cfg(feature = "channel-mqtt")]
+    if let Some(ref mqtt) = root_config.channels_config.mqtt {
+        tool_arcs.push(Arc::new(MqttPublishTool::new(
+            mqtt.clone(),
+            security.clone(),
+        )));
+    }
+
+    // Standard operating procedures (feature-gated; the engine is shared with
+    // the daemon's MQTT/webhook/cron trigger sources)
+    #[cfg(feature = "sop")]
+    if root_config.sop.enabled {
+        let sop = crate::sop::shared_runtime(root_config, memory.clone());
+        tool_arcs.push(Arc::new(SopListTool::new(sop.engine.clone())));
+        tool_arcs.push(Arc::new(
+            SopExecuteTool::new(sop.engine.clone()).with_audit(sop.audit.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopApproveTool::new(sop.engine.clone())
+                .with_audit(sop.audit.clone())
+                .with_collector(sop.metrics.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopAdvanceTool::new(sop.engine.clone())
+                .with_audit(sop.audit.clone())
+                .with_collector(sop.metrics.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopStatusTool::new(sop.engine).with_collector(sop.metrics),
+        ));
+    }
+
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
@@ -420,6 +487,63 @@ pub fn all_tools_with_runtime(
         tool_arcs.push(Arc::new(delegate_tool));
     }
 
+    // Remote Agent-to-Agent (A2A) agents
+    if !root_config.a2a.agents.is_empty() {
+        tool_arcs.push(Arc::new(A2aTool::new(
+            security.clone(),
+            root_config.a2a.agents.clone(),
+        )));
+    }
+
+    // GitHub/GitLab issues and pull requests (opt-in)
+    if root_config.forge.enabled {
+        tool_arcs.push(Arc::new(ForgeTool::new(
+            security.clone(),
+            root_config.forge.clone(),
+            workspace_dir.to_path_buf(),
+        )));
+    }
+
+    // Markdown notes vault (opt-in)
+    if root_config.vault.enabled {
+        tool_arcs.push(Arc::new(VaultTool::new(
+            security.clone(),
+            root_config.vault.clone(),
+            workspace_dir,
+        )));
+    }
+
+    // Home Assistant (opt-in)
+    if root_config.home_assistant.enabled {
+        tool_arcs.push(Arc::new(HomeAssistantTool::new(
+            security.clone(),
+            root_config.home_assistant.clone(),
+        )));
+    }
+
+    // Image generation (opt-in)
+    if root_config.image_gen.enabled {
+        let cost_tracker = if root_config.cost.enabled {
+            match crate::cost::CostTracker::new(root_config.cost.clone(), workspace_dir) {
+                Ok(tracker) => Some(Arc::new(tracker)),
+                Err(e) => {
+                    tracing::warn!("image_generate: cost tracking unavailable: {e}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        tool_arcs.push(Arc::new(
+            ImageGenerateTool::new(
+                security.clone(),
+                root_config.image_gen.clone(),
+                workspace_dir.to_path_buf(),
+            )
+            .with_cost_tracker(cost_tracker),
+        ));
+    }
+
     // Inter-process agent communication (opt-in)
     if root_config.agents_ipc.enabled {
         match agents_ipc::IpcDb::open(workspace_dir, &root_config.agents_ipc) {
@@ -49,7 +49,7 @@ impl ModelRoutingConfigTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
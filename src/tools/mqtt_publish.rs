@@ -0,0 +1,259 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::mqtt::{publish_once, qos_from_level};
+use crate::config::MqttConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Publish to allowlisted MQTT topics — lets the agent and SOP steps actuate devices.
+pub struct MqttPublishTool {
+    config: MqttConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl MqttPublishTool {
+    pub fn new(config: MqttConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn failure(error: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for MqttPublishTool {
+    fn name(&self) -> &str {
+        "mqtt_publish"
+    }
+
+    fn description(&self) -> &str {
+        "Publish a message to an MQTT topic to command a device (e.g. switch a relay, set a thermostat). Only topics in autonomy.allowed_mqtt_topics are permitted."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "topic": {
+                    "type": "string",
+                    "description": "Concrete topic to publish to (no '+' or '#' wildcards)"
+                },
+                "payload": {
+                    "description": "Message payload: a string is sent as-is, any other JSON value is serialized"
+                },
+                "qos": {
+                    "type": "integer",
+                    "description": "QoS level 0, 1 or 2. Defaults to the configured mqtt.qos."
+                },
+                "retain": {
+                    "type": "boolean",
+                    "description": "Set the retain flag. Defaults to the configured mqtt.retain."
+                }
+            },
+            "required": ["topic", "payload"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let topic = args
+            .get("topic")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'topic' parameter"))?;
+
+        let payload = match args.get("payload") {
+            Some(serde_json::Value::String(text)) => text.clone().into_bytes(),
+            Some(value) => serde_json::to_vec(value)?,
+            None => anyhow::bail!("Missing 'payload' parameter"),
+        };
+
+        let qos = match args.get("qos").and_then(|v| v.as_u64()) {
+            Some(level @ 0..=2) => u8::try_from(level).unwrap_or(self.config.qos),
+            Some(level) => {
+                return Ok(Self::failure(format!(
+                    "Invalid 'qos': {level}. Expected 0, 1 or 2"
+                )))
+            }
+            None => self.config.qos,
+        };
+        let retain = args
+            .get("retain")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(self.config.retain);
+
+        if !self.security.is_mqtt_topic_allowed(topic) {
+            return Ok(Self::failure(format!(
+                "MQTT topic '{topic}' is not in autonomy.allowed_mqtt_topics"
+            )));
+        }
+
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "mqtt_publish")
+        {
+            return Ok(Self::failure(error));
+        }
+
+        let size = payload.len();
+        match publish_once(&self.config, topic, payload, qos_from_level(qos), retain).await {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Published {size} bytes to '{topic}' (qos {qos}, retain {retain})"),
+                error: None,
+            }),
+            Err(e) => Ok(Self::failure(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn test_config() -> MqttConfig {
+        MqttConfig {
+            broker_url: "mqtt://127.0.0.1:1".into(),
+            client_id: "zeroclaw-test".into(),
+            topics: vec!["sensors/#".into()],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            request_topic: None,
+            reply_topic: None,
+            allowed_senders: vec![],
+            retain: false,
+        }
+    }
+
+    fn security_with_topics(topics: &[&str]) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            allowed_mqtt_topics: topics.iter().map(|t| (*t).to_string()).collect(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[test]
+    fn name_and_schema() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&[]));
+        assert_eq!(tool.name(), "mqtt_publish");
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["topic"].is_object());
+        assert!(schema["properties"]["payload"].is_object());
+    }
+
+    #[tokio::test]
+    async fn publish_missing_topic() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&["#"]));
+        let result = tool.execute(json!({"payload": "on"})).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn publish_missing_payload() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&["#"]));
+        let result = tool.execute(json!({"topic": "devices/pump/set"})).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn publish_blocked_when_topic_not_allowlisted() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&["devices/+/set"]));
+        let result = tool
+            .execute(json!({"topic": "garage/door/open", "payload": "1"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("allowed_mqtt_topics"));
+    }
+
+    #[tokio::test]
+    async fn publish_rejects_wildcard_topic() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&["#"]));
+        let result = tool
+            .execute(json!({"topic": "devices/+/set", "payload": "1"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+    }
+
+    #[tokio::test]
+    async fn publish_rejects_invalid_qos() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&["#"]));
+        let result = tool
+            .execute(json!({"topic": "devices/pump/set", "payload": "1", "qos": 3}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.as_deref().unwrap_or("").contains("qos"));
+    }
+
+    #[tokio::test]
+    async fn publish_blocked_in_readonly_mode() {
+        let readonly = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::ReadOnly,
+            allowed_mqtt_topics: vec!["#".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = MqttPublishTool::new(test_config(), readonly);
+        let result = tool
+            .execute(json!({"topic": "devices/pump/set", "payload": "on"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("read-only mode"));
+    }
+
+    #[tokio::test]
+    async fn publish_blocked_when_rate_limited() {
+        let limited = Arc::new(SecurityPolicy {
+            max_actions_per_hour: 0,
+            allowed_mqtt_topics: vec!["#".into()],
+            ..SecurityPolicy::default()
+        });
+        let tool = MqttPublishTool::new(test_config(), limited);
+        let result = tool
+            .execute(json!({"topic": "devices/pump/set", "payload": "on"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("Rate limit exceeded"));
+    }
+
+    #[tokio::test]
+    async fn publish_reports_unreachable_broker() {
+        let tool = MqttPublishTool::new(test_config(), security_with_topics(&["devices/#"]));
+        let result = tool
+            .execute(json!({"topic": "devices/pump/set", "payload": {"state": "on"}}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .as_deref()
+            .unwrap_or("")
+            .contains("devices/pump/set"));
+    }
+}
@@ -47,7 +47,7 @@ impl ProxyConfigTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Some(ToolResult {
                 success: false,
                 output: String::new(),
@@ -120,7 +120,7 @@ impl Tool for PushoverTool {
             });
         }
 
-        if !self.security.record_action().await {
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -170,6 +170,217 @@ impl SchemaCleanr {
         Ok(())
     }
 
+    /// Validate a JSON value against a schema.
+    ///
+    /// Covers the subset of JSON Schema that structured-output requests rely
+    /// on (`type`, `properties`, `required`, `additionalProperties`, `items`,
+    /// `enum`, `const`, unions, length and range bounds, local `$ref`).
+    /// Returns one human-readable message per violation so callers can feed
+    /// them back to the model in a repair prompt. An empty vector means the
+    /// instance is valid.
+    pub fn validate_instance(schema: &Value, instance: &Value) -> Vec<String> {
+        let defs = schema
+            .as_object()
+            .map(Self::extract_defs)
+            .unwrap_or_default();
+        let mut errors = Vec::new();
+        Self::validate_node(schema, instance, "$", &defs, 0, &mut errors);
+        errors
+    }
+
+    fn validate_node(
+        schema: &Value,
+        instance: &Value,
+        path: &str,
+        defs: &HashMap<String, Value>,
+        depth: usize,
+        errors: &mut Vec<String>,
+    ) {
+        const MAX_DEPTH: usize = 64;
+
+        let Some(obj) = schema.as_object() else {
+            // `true` / `{}` accept everything; `false` rejects everything.
+            if schema == &Value::Bool(false) {
+                errors.push(format!("{path}: no value is allowed here"));
+            }
+            return;
+        };
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        if let Some(Value::String(ref_value)) = obj.get("$ref") {
+            match Self::parse_local_ref(ref_value).and_then(|name| defs.get(&name)) {
+                Some(definition) => {
+                    Self::validate_node(definition, instance, path, defs, depth + 1, errors);
+                }
+                None => errors.push(format!("{path}: cannot resolve {ref_value}")),
+            }
+            return;
+        }
+
+        if let Some(expected) = obj.get("type") {
+            let matches = match expected {
+                Value::String(t) => Self::instance_has_type(instance, t),
+                Value::Array(types) => types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .any(|t| Self::instance_has_type(instance, t)),
+                _ => true,
+            };
+            if !matches {
+                errors.push(format!(
+                    "{path}: expected type {expected}, got {}",
+                    Self::instance_type_name(instance)
+                ));
+                return;
+            }
+        }
+
+        if let Some(Value::Array(allowed)) = obj.get("enum") {
+            if !allowed.contains(instance) {
+                errors.push(format!(
+                    "{path}: value must be one of {}",
+                    Value::from(allowed.clone())
+                ));
+            }
+        }
+        if let Some(constant) = obj.get("const") {
+            if constant != instance {
+                errors.push(format!("{path}: value must equal {constant}"));
+            }
+        }
+
+        for key in ["anyOf", "oneOf"] {
+            if let Some(Value::Array(variants)) = obj.get(key) {
+                let matching = variants
+                    .iter()
+                    .filter(|variant| {
+                        let mut scratch = Vec::new();
+                        Self::validate_node(variant, instance, path, defs, depth + 1, &mut scratch);
+                        scratch.is_empty()
+                    })
+                    .count();
+                if matching == 0 || (key == "oneOf" && matching > 1) {
+                    errors.push(format!("{path}: value does not match {key} variants"));
+                }
+            }
+        }
+        if let Some(Value::Array(all)) = obj.get("allOf") {
+            for variant in all {
+                Self::validate_node(variant, instance, path, defs, depth + 1, errors);
+            }
+        }
+
+        match instance {
+            Value::Object(map) => {
+                let properties = obj.get("properties").and_then(Value::as_object);
+                if let Some(Value::Array(required)) = obj.get("required") {
+                    for key in required.iter().filter_map(Value::as_str) {
+                        if !map.contains_key(key) {
+                            errors.push(format!("{path}: missing required property '{key}'"));
+                        }
+                    }
+                }
+                for (key, value) in map {
+                    let child = format!("{path}.{key}");
+                    if let Some(prop_schema) = properties.and_then(|p| p.get(key)) {
+                        Self::validate_node(prop_schema, value, &child, defs, depth + 1, errors);
+                    } else {
+                        match obj.get("additionalProperties") {
+                            Some(Value::Bool(false)) => {
+                                errors.push(format!("{path}: unexpected property '{key}'"));
+                            }
+                            Some(extra @ Value::Object(_)) => {
+                                Self::validate_node(extra, value, &child, defs, depth + 1, errors);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            Value::Array(items) => {
+                if let Some(item_schema) = obj.get("items") {
+                    for (idx, item) in items.iter().enumerate() {
+                        let child = format!("{path}[{idx}]");
+                        Self::validate_node(item_schema, item, &child, defs, depth + 1, errors);
+                    }
+                }
+                Self::check_bound(obj, "minItems", items.len(), path, "items", errors);
+                Self::check_bound(obj, "maxItems", items.len(), path, "items", errors);
+            }
+            Value::String(s) => {
+                let len = s.chars().count();
+                Self::check_bound(obj, "minLength", len, path, "characters", errors);
+                Self::check_bound(obj, "maxLength", len, path, "characters", errors);
+            }
+            Value::Number(n) => {
+                let value = n.as_f64().unwrap_or_default();
+                if let Some(min) = obj.get("minimum").and_then(Value::as_f64) {
+                    if value < min {
+                        errors.push(format!("{path}: {value} is below minimum {min}"));
+                    }
+                }
+                if let Some(max) = obj.get("maximum").and_then(Value::as_f64) {
+                    if value > max {
+                        errors.push(format!("{path}: {value} is above maximum {max}"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_bound(
+        obj: &Map<String, Value>,
+        keyword: &str,
+        actual: usize,
+        path: &str,
+        unit: &str,
+        errors: &mut Vec<String>,
+    ) {
+        let Some(bound) = obj.get(keyword).and_then(Value::as_u64) else {
+            return;
+        };
+        let bound = usize::try_from(bound).unwrap_or(usize::MAX);
+        let violated = if keyword.starts_with("min") {
+            actual < bound
+        } else {
+            actual > bound
+        };
+        if violated {
+            errors.push(format!("{path}: {keyword} is {bound} {unit}, got {actual}"));
+        }
+    }
+
+    fn instance_has_type(instance: &Value, expected: &str) -> bool {
+        match expected {
+            "object" => instance.is_object(),
+            "array" => instance.is_array(),
+            "string" => instance.is_string(),
+            "boolean" => instance.is_boolean(),
+            "null" => instance.is_null(),
+            "number" => instance.is_number(),
+            "integer" => {
+                instance.is_i64()
+                    || instance.is_u64()
+                    || instance.as_f64().is_some_and(|n| n.fract() == 0.0)
+            }
+            _ => true,
+        }
+    }
+
+    fn instance_type_name(instance: &Value) -> &'static str {
+        match instance {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
     // --------------------------------------------------------------------
     // Internal implementation
     // --------------------------------------------------------------------
@@ -835,4 +1046,54 @@ mod tests {
         assert_eq!(cleaned["not"]["type"], "integer");
         assert!(cleaned["not"].get("minimum").is_none());
     }
+
+    #[test]
+    fn test_validate_instance_reports_each_violation() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "summary": { "type": "string", "minLength": 1 },
+                "tags": { "type": "array", "items": { "$ref": "#/$defs/Tag" }, "maxItems": 2 },
+                "score": { "type": "integer", "minimum": 0, "maximum": 10 }
+            },
+            "required": ["summary", "score"],
+            "additionalProperties": false,
+            "$defs": {
+                "Tag": { "type": "string", "enum": ["a", "b"] }
+            }
+        });
+
+        let valid = json!({ "summary": "ok", "tags": ["a"], "score": 3 });
+        assert!(SchemaCleanr::validate_instance(&schema, &valid).is_empty());
+
+        let invalid = json!({ "summary": "", "tags": ["a", "c", "b"], "score": 11.5, "extra": 1 });
+        let errors = SchemaCleanr::validate_instance(&schema, &invalid);
+        assert!(errors.iter().any(|e| e.contains("$.summary: minLength")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("$.tags[1]: value must be one of")));
+        assert!(errors.iter().any(|e| e.contains("$.tags: maxItems")));
+        assert!(errors.iter().any(|e| e.contains("$.score: expected type")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("unexpected property 'extra'")));
+
+        let missing = json!({ "summary": "ok" });
+        let errors = SchemaCleanr::validate_instance(&schema, &missing);
+        assert_eq!(
+            errors,
+            vec!["$: missing required property 'score'".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_validate_instance_handles_nullable_unions() {
+        let schema = json!({
+            "anyOf": [{ "type": "string" }, { "type": "null" }]
+        });
+
+        assert!(SchemaCleanr::validate_instance(&schema, &json!(null)).is_empty());
+        assert!(SchemaCleanr::validate_instance(&schema, &json!("x")).is_empty());
+        assert_eq!(SchemaCleanr::validate_instance(&schema, &json!(1)).len(), 1);
+    }
 }
@@ -0,0 +1,873 @@
+//! Obsidian-style Markdown vault: search, wikilinks and backlinks,
+//! frontmatter, daily notes and templates.
+//!
+//! Every note path is resolved inside the configured vault root, which must
+//! itself pass `SecurityPolicy` (workspace or `[autonomy].allowed_roots`).
+//! Searches run through `content_search` scoped to the vault.
+
+use super::content_search::ContentSearchTool;
+use super::traits::{Tool, ToolResult};
+use crate::config::VaultConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// Directory depth scanned below the vault root.
+const MAX_VAULT_DEPTH: usize = 16;
+/// Notes indexed per scan; larger vaults are cut off.
+const MAX_VAULT_NOTES: usize = 20_000;
+/// Characters of a note returned by `read`.
+const MAX_READ_CHARS: usize = 100_000;
+/// Notes larger than this are skipped when collecting backlinks.
+const MAX_BACKLINK_SCAN_BYTES: u64 = 1_048_576;
+const MAX_BACKLINKS: usize = 200;
+const MAX_SEARCH_RESULTS: u64 = 100;
+
+/// `[[target]]`, `[[target#heading]]`, `[[target|alias]]` and `![[embeds]]`;
+/// group 1 is the link target.
+static WIKILINK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"!?\[\[([^\[\]|#^]+)(?:[#^][^\[\]|]*)?(?:\|[^\[\]]*)?\]\]").unwrap()
+});
+
+pub struct VaultTool {
+    security: Arc<SecurityPolicy>,
+    config: VaultConfig,
+    /// Configured vault root, expanded but not yet canonicalized.
+    root: PathBuf,
+}
+
+impl VaultTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: VaultConfig, workspace_dir: &Path) -> Self {
+        let expanded = PathBuf::from(shellexpand::tilde(config.path.trim()).as_ref());
+        let root = if expanded.is_absolute() {
+            expanded
+        } else {
+            workspace_dir.join(expanded)
+        };
+        Self {
+            security,
+            config,
+            root,
+        }
+    }
+
+    /// Canonical vault root, checked against the security policy.
+    fn vault_root(&self) -> Result<PathBuf, String> {
+        if self.config.path.trim().is_empty() {
+            return Err("vault.path is not configured".into());
+        }
+        let root = self
+            .root
+            .canonicalize()
+            .map_err(|e| format!("Vault root {} is not accessible: {e}", self.root.display()))?;
+        if !root.is_dir() {
+            return Err(format!("Vault root {} is not a directory", root.display()));
+        }
+        if !self.security.is_resolved_path_allowed(&root) {
+            return Err(self.security.resolved_path_violation_message(&root));
+        }
+        Ok(root)
+    }
+
+    /// Absolute path of vault-relative `relative`, refusing paths that
+    /// resolve (through symlinks) outside `root`.
+    fn confine(root: &Path, relative: &Path) -> Result<PathBuf, String> {
+        let path = root.join(relative);
+        let mut existing = path.as_path();
+        while !existing.exists() {
+            existing = existing
+                .parent()
+                .ok_or_else(|| format!("Invalid note path '{}'", display(relative)))?;
+        }
+        let canonical = existing
+            .canonicalize()
+            .map_err(|e| format!("Cannot resolve '{}': {e}", display(relative)))?;
+        if !canonical.starts_with(root) {
+            return Err(format!(
+                "Note path '{}' escapes the vault",
+                display(relative)
+            ));
+        }
+        match path.strip_prefix(existing) {
+            Ok(rest) if !rest.as_os_str().is_empty() => Ok(canonical.join(rest)),
+            _ => Ok(canonical),
+        }
+    }
+
+    /// Existing note for `note`: a vault-relative path, or a bare name
+    /// resolved like a wikilink. Returns the absolute and relative paths.
+    fn find_note(&self, root: &Path, note: &str) -> Result<(PathBuf, PathBuf), String> {
+        let relative = self.relative_note(note)?;
+        let path = Self::confine(root, &relative)?;
+        if path.is_file() {
+            return Ok((path, relative));
+        }
+        let relative = resolve_link(&markdown_files(root), note)
+            .ok_or_else(|| format!("Note '{note}' not found in the vault"))?;
+        Ok((Self::confine(root, &relative)?, relative))
+    }
+
+    /// Vault-relative path for `note`, with `.md` appended when missing.
+    fn relative_note(&self, note: &str) -> Result<PathBuf, String> {
+        let note = note.trim().replace('\\', "/");
+        if note.is_empty() {
+            return Err("Missing 'note' parameter".into());
+        }
+        if note.starts_with('~')
+            || Path::new(&note).is_absolute()
+            || !self.security.is_path_allowed(&note)
+        {
+            return Err(format!(
+                "Note path '{note}' must be relative to the vault root"
+            ));
+        }
+        Ok(if note.to_lowercase().ends_with(".md") {
+            PathBuf::from(note)
+        } else {
+            PathBuf::from(format!("{note}.md"))
+        })
+    }
+
+    async fn search(&self, root: PathBuf, args: &Value) -> anyhow::Result<ToolResult> {
+        let Some(query) = string_arg(args, "query") else {
+            return Ok(failure("Missing 'query' parameter"));
+        };
+        let scoped = SecurityPolicy {
+            workspace_dir: root,
+            ..(*self.security).clone()
+        };
+        ContentSearchTool::new(Arc::new(scoped))
+            .execute(json!({
+                "pattern": query,
+                "path": ".",
+                "include": "*.md",
+                "output_mode": string_arg(args, "output_mode").unwrap_or("content"),
+                "case_sensitive": args.get("case_sensitive").and_then(Value::as_bool).unwrap_or(false),
+                "max_results": MAX_SEARCH_RESULTS,
+            }))
+            .await
+    }
+
+    async fn read(&self, root: &Path, note: &str) -> Result<String, String> {
+        let (path, relative) = self.find_note(root, note)?;
+        let content = read_note(&path).await?;
+        Ok(format!(
+            "{}\n\n{}",
+            display(&relative),
+            crate::util::truncate_with_ellipsis(&content, MAX_READ_CHARS)
+        ))
+    }
+
+    async fn links(&self, root: &Path, note: &str) -> Result<String, String> {
+        let (path, relative) = self.find_note(root, note)?;
+        let notes = markdown_files(root);
+        let content = read_note(&path).await?;
+
+        let mut output = String::new();
+        let outgoing = wikilinks(&content);
+        let _ = write!(output, "Outgoing links ({}):", outgoing.len());
+        for (_, target) in &outgoing {
+            let resolved = resolve_link(&notes, target)
+                .map_or_else(|| "(no such note)".to_string(), |found| display(&found));
+            let _ = write!(output, "\n- [[{target}]] → {resolved}");
+        }
+
+        let stem = relative
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let mut backlinks = Vec::new();
+        for other in notes.iter().filter(|other| **other != relative) {
+            let other_path = root.join(other);
+            if std::fs::metadata(&other_path).map_or(true, |m| m.len() > MAX_BACKLINK_SCAN_BYTES) {
+                continue;
+            }
+            let Ok(text) = tokio::fs::read_to_string(&other_path).await else {
+                continue;
+            };
+            for (line, target) in wikilinks(&text) {
+                // Cheap name check before the full resolution.
+                if link_name(&target) == stem
+                    && resolve_link(&notes, &target).as_ref() == Some(&relative)
+                {
+                    let snippet = text.lines().nth(line - 1).unwrap_or("").trim();
+                    backlinks.push(format!(
+                        "- {}:{line}: {}",
+                        display(other),
+                        crate::util::truncate_with_ellipsis(snippet, 200)
+                    ));
+                }
+            }
+            if backlinks.len() >= MAX_BACKLINKS {
+                break;
+            }
+        }
+        let _ = write!(output, "\n\nBacklinks ({}):", backlinks.len());
+        for backlink in backlinks.iter().take(MAX_BACKLINKS) {
+            let _ = write!(output, "\n{backlink}");
+        }
+        Ok(format!("{}\n\n{output}", display(&relative)))
+    }
+
+    async fn frontmatter(&self, root: &Path, note: &str, args: &Value) -> Result<String, String> {
+        let (path, relative) = self.find_note(root, note)?;
+        let content = read_note(&path).await?;
+        let (mut fields, body) = parse_frontmatter(&content)?;
+
+        let set = match args.get("set") {
+            None | Some(Value::Null) => None,
+            Some(Value::Object(set)) => Some(set),
+            Some(_) => return Err("'set' must be an object".into()),
+        };
+        let remove: Vec<&str> = args
+            .get("remove")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+
+        if set.is_some() || !remove.is_empty() {
+            self.security
+                .enforce_tool_operation(ToolOperation::Act, "vault")?;
+            for key in remove {
+                fields.remove(key);
+            }
+            if let Some(set) = set {
+                fields.extend(set.iter().map(|(key, value)| (key.clone(), value.clone())));
+            }
+            let updated = render_note(&fields, body)?;
+            tokio::fs::write(&path, updated)
+                .await
+                .map_err(|e| format!("Failed to write {}: {e}", display(&relative)))?;
+        }
+
+        let rendered =
+            serde_json::to_string_pretty(&Value::Object(fields)).map_err(|e| e.to_string())?;
+        Ok(format!("{} frontmatter:\n{rendered}", display(&relative)))
+    }
+
+    async fn load_template(&self, root: &Path, name: &str) -> Result<String, String> {
+        let relative = Path::new(self.config.templates_folder.trim().trim_matches('/'))
+            .join(self.relative_note(name)?);
+        let path = Self::confine(root, &relative)?;
+        if !path.is_file() {
+            return Err(format!("Template '{}' not found", display(&relative)));
+        }
+        read_note(&path).await
+    }
+
+    async fn append_daily(&self, root: &Path, args: &Value) -> Result<String, String> {
+        let content = string_arg(args, "content").ok_or("Missing 'content' parameter")?;
+        let date = match string_arg(args, "date") {
+            Some(date) => chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date '{date}', expected YYYY-MM-DD"))?,
+            None => chrono::Local::now().date_naive(),
+        };
+        let mut name = String::new();
+        write!(name, "{}", date.format(&self.config.daily_format))
+            .map_err(|_| format!("Invalid vault.daily_format '{}'", self.config.daily_format))?;
+        let relative = Path::new(self.config.daily_folder.trim().trim_matches('/'))
+            .join(self.relative_note(&name)?);
+        let path = Self::confine(root, &relative)?;
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "vault")?;
+
+        let mut text = if path.is_file() {
+            read_note(&path).await?
+        } else {
+            match self.config.daily_template.as_deref().map(str::trim) {
+                Some(template) if !template.is_empty() => {
+                    let title = relative
+                        .file_stem()
+                        .map(|stem| stem.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    render_template(
+                        &self.load_template(root, template).await?,
+                        &title,
+                        date.and_time(chrono::Local::now().time()),
+                    )
+                }
+                _ => String::new(),
+            }
+        };
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(content);
+        text.push('\n');
+
+        write_note(&path, &text).await?;
+        Ok(format!("Appended to {}", display(&relative)))
+    }
+
+    async fn create(&self, root: &Path, note: &str, args: &Value) -> Result<String, String> {
+        let relative = self.relative_note(note)?;
+        let path = Self::confine(root, &relative)?;
+        if path.exists() {
+            return Err(format!("Note '{}' already exists", display(&relative)));
+        }
+        let extra = match args.get("frontmatter") {
+            None | Some(Value::Null) => None,
+            Some(Value::Object(extra)) => Some(extra),
+            Some(_) => return Err("'frontmatter' must be an object".into()),
+        };
+        self.security
+            .enforce_tool_operation(ToolOperation::Act, "vault")?;
+
+        let title = relative
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut text = match string_arg(args, "template") {
+            Some(template) => render_template(
+                &self.load_template(root, template).await?,
+                &title,
+                chrono::Local::now().naive_local(),
+            ),
+            None => String::new(),
+        };
+        if let Some(content) = string_arg(args, "content") {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(content);
+            text.push('\n');
+        }
+        if let Some(extra) = extra {
+            let (mut fields, body) = parse_frontmatter(&text)?;
+            fields.extend(
+                extra
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            );
+            text = render_note(&fields, body)?;
+        }
+
+        write_note(&path, &text).await?;
+        Ok(format!("Created {}", display(&relative)))
+    }
+}
+
+/// Vault-relative path with `/` separators.
+fn display(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn string_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+}
+
+async fn read_note(path: &Path) -> Result<String, String> {
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))
+}
+
+async fn write_note(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    tokio::fs::write(path, content)
+        .await
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+/// Vault-relative paths of all Markdown notes, skipping hidden folders
+/// (`.obsidian`, `.git`, `.trash`) and not following symlinks.
+fn markdown_files(root: &Path) -> Vec<PathBuf> {
+    fn walk(root: &Path, dir: &Path, depth: usize, out: &mut Vec<PathBuf>) {
+        if depth > MAX_VAULT_DEPTH || out.len() >= MAX_VAULT_NOTES {
+            return;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                walk(root, &path, depth + 1, out);
+            } else if file_type.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+                && out.len() < MAX_VAULT_NOTES
+            {
+                if let Ok(relative) = path.strip_prefix(root) {
+                    out.push(relative.to_path_buf());
+                }
+            }
+        }
+    }
+
+    let mut notes = Vec::new();
+    walk(root, root, 0, &mut notes);
+    notes.sort();
+    notes
+}
+
+/// Lowercased note name a link target points at, without folders or `.md`.
+fn link_name(target: &str) -> String {
+    let target = target.trim().replace('\\', "/").to_lowercase();
+    let name = target.rsplit('/').next().unwrap_or(&target);
+    name.strip_suffix(".md").unwrap_or(name).to_string()
+}
+
+/// Note a wikilink target resolves to: an exact or trailing path match when
+/// the target has folders, otherwise any note with that name (the shallowest
+/// wins, as in Obsidian).
+fn resolve_link(notes: &[PathBuf], target: &str) -> Option<PathBuf> {
+    let target = target
+        .trim()
+        .replace('\\', "/")
+        .trim_start_matches('/')
+        .to_lowercase();
+    let target = target.strip_suffix(".md").unwrap_or(&target);
+    let suffix = format!("/{target}");
+    notes
+        .iter()
+        .filter(|note| {
+            let path = display(note).to_lowercase();
+            let path = path.strip_suffix(".md").unwrap_or(&path);
+            if target.contains('/') {
+                path == target || path.ends_with(&suffix)
+            } else {
+                path.rsplit('/').next() == Some(target)
+            }
+        })
+        .min_by_key(|note| (note.components().count(), display(note)))
+        .cloned()
+}
+
+/// `(1-based line, target)` of every wikilink outside fenced code blocks.
+fn wikilinks(content: &str) -> Vec<(usize, String)> {
+    let mut in_fence = false;
+    let mut links = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        links.extend(
+            WIKILINK_RE
+                .captures_iter(line)
+                .map(|captures| (index + 1, captures[1].trim().to_string())),
+        );
+    }
+    links
+}
+
+/// Split `---` YAML frontmatter from the note body.
+fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+fn parse_frontmatter(content: &str) -> Result<(Map<String, Value>, &str), String> {
+    let (yaml, body) = split_frontmatter(content);
+    let Some(yaml) = yaml.filter(|yaml| !yaml.trim().is_empty()) else {
+        return Ok((Map::new(), body));
+    };
+    match serde_yml::from_str::<Value>(yaml) {
+        Ok(Value::Object(fields)) => Ok((fields, body)),
+        Ok(Value::Null) => Ok((Map::new(), body)),
+        Ok(_) => Err("Frontmatter is not a YAML mapping".into()),
+        Err(e) => Err(format!("Invalid YAML frontmatter: {e}")),
+    }
+}
+
+fn render_note(fields: &Map<String, Value>, body: &str) -> Result<String, String> {
+    if fields.is_empty() {
+        return Ok(body.to_string());
+    }
+    let yaml = serde_yml::to_string(fields).map_err(|e| e.to_string())?;
+    Ok(format!("---\n{yaml}---\n{body}"))
+}
+
+/// Fill Obsidian core template variables `{{title}}`, `{{date}}` and `{{time}}`.
+fn render_template(template: &str, title: &str, now: chrono::NaiveDateTime) -> String {
+    template
+        .replace("{{title}}", title)
+        .replace("{{date}}", &now.format("%Y-%m-%d").to_string())
+        .replace("{{time}}", &now.format("%H:%M").to_string())
+}
+
+fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
+
+#[async_trait]
+impl Tool for VaultTool {
+    fn name(&self) -> &str {
+        "vault"
+    }
+
+    fn description(&self) -> &str {
+        "Work with the Markdown (Obsidian) notes vault. Actions: 'search' note contents \
+         by regex, 'read' a note, 'links' (outgoing [[wikilinks]] and backlinks), \
+         'frontmatter' (read, or update with set/remove), 'append_daily' to today's \
+         daily note, 'create' a note (optionally from a template). Notes are vault-relative \
+         paths or bare note names like wikilinks."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["search", "read", "links", "frontmatter", "append_daily", "create"],
+                    "description": "Operation to perform"
+                },
+                "note": {
+                    "type": "string",
+                    "description": "Note path relative to the vault (e.g. 'Projects/Plan.md') or note name (e.g. 'Plan')"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Regex to search for (search)"
+                },
+                "case_sensitive": {
+                    "type": "boolean",
+                    "description": "Case-sensitive search (default false)"
+                },
+                "output_mode": {
+                    "type": "string",
+                    "enum": ["content", "files_with_matches", "count"],
+                    "description": "Search output mode (default content)"
+                },
+                "set": {
+                    "type": "object",
+                    "description": "Frontmatter fields to add or overwrite (frontmatter)"
+                },
+                "remove": {
+                    "type": "array",
+                    "items": {"type": "string"},
+                    "description": "Frontmatter fields to remove (frontmatter)"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Text to append (append_daily) or note body (create)"
+                },
+                "date": {
+                    "type": "string",
+                    "description": "Daily note date as YYYY-MM-DD (append_daily, default today)"
+                },
+                "template": {
+                    "type": "string",
+                    "description": "Template name from the templates folder (create)"
+                },
+                "frontmatter": {
+                    "type": "object",
+                    "description": "Frontmatter fields for the new note (create)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let action = args.get("action").and_then(Value::as_str).unwrap_or("");
+        let root = match self.vault_root() {
+            Ok(root) => root,
+            Err(e) => return Ok(failure(e)),
+        };
+        if action == "search" {
+            return self.search(root, &args).await;
+        }
+
+        // An empty note is reported as missing by `relative_note`.
+        let note = string_arg(&args, "note").unwrap_or("");
+        let result = match action {
+            "read" => self.read(&root, note).await,
+            "links" => self.links(&root, note).await,
+            "frontmatter" => self.frontmatter(&root, note, &args).await,
+            "append_daily" => self.append_daily(&root, &args).await,
+            "create" => self.create(&root, note, &args).await,
+            other => Err(format!(
+                "Unknown action '{other}'. Use search, read, links, frontmatter, append_daily or create"
+            )),
+        };
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => failure(e),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    fn vault_tool(workspace: &Path, autonomy: AutonomyLevel) -> VaultTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        });
+        let config = VaultConfig {
+            enabled: true,
+            path: "notes".into(),
+            daily_template: Some("Daily".into()),
+            ..VaultConfig::default()
+        };
+        VaultTool::new(security, config, workspace)
+    }
+
+    fn write(workspace: &Path, relative: &str, content: &str) {
+        let path = workspace.join("notes").join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(workspace: &Path, relative: &str) -> String {
+        std::fs::read_to_string(workspace.join("notes").join(relative)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn links_resolve_wikilinks_and_backlinks() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Projects/Plan.md",
+            "See [[Roadmap]] and [[Missing|later]].\n```\n[[Roadmap]]\n```\n",
+        );
+        write(tmp.path(), "Roadmap.md", "# Roadmap\n");
+        write(
+            tmp.path(),
+            "Daily/2026-01-01.md",
+            "Notes\nWorked on [[Projects/Plan#Goals|the plan]]\n",
+        );
+        write(tmp.path(), ".obsidian/cache.md", "[[Plan]]\n");
+        let tool = vault_tool(tmp.path(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"action": "links", "note": "Plan"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.output,
+            "Projects/Plan.md\n\nOutgoing links (2):\n- [[Roadmap]] → Roadmap.md\n\
+             - [[Missing]] → (no such note)\n\nBacklinks (1):\n\
+             - Daily/2026-01-01.md:2: Worked on [[Projects/Plan#Goals|the plan]]"
+        );
+    }
+
+    #[tokio::test]
+    async fn frontmatter_is_read_and_updated_in_place() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Plan.md",
+            "---\ntags: [work]\nstatus: draft\n---\n# Plan\nBody\n",
+        );
+        let tool = vault_tool(tmp.path(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"action": "frontmatter", "note": "Plan.md"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("\"status\": \"draft\""));
+
+        let result = tool
+            .execute(json!({
+                "action": "frontmatter",
+                "note": "Plan",
+                "set": {"status": "done", "reviewed": true},
+                "remove": ["tags"]
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let content = read(tmp.path(), "Plan.md");
+        assert!(content.starts_with("---\n"));
+        assert!(content.contains("status: done\n"));
+        assert!(content.contains("reviewed: true\n"));
+        assert!(!content.contains("tags"));
+        assert!(content.ends_with("---\n# Plan\nBody\n"));
+    }
+
+    #[tokio::test]
+    async fn append_daily_creates_note_from_template() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "Templates/Daily.md", "# {{title}}\n\n## Log");
+        let tool = vault_tool(tmp.path(), AutonomyLevel::Supervised);
+
+        for content in ["first", "second"] {
+            let result = tool
+                .execute(
+                    json!({"action": "append_daily", "date": "2026-01-02", "content": content}),
+                )
+                .await
+                .unwrap();
+            assert!(result.success, "{:?}", result.error);
+            assert_eq!(result.output, "Appended to Daily/2026-01-02.md");
+        }
+        assert_eq!(
+            read(tmp.path(), "Daily/2026-01-02.md"),
+            "# 2026-01-02\n\n## Log\nfirst\nsecond\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn create_uses_template_and_frontmatter_but_never_overwrites() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Templates/Meeting.md",
+            "---\ntype: meeting\n---\n# {{title}}\n",
+        );
+        let tool = vault_tool(tmp.path(), AutonomyLevel::Supervised);
+        let args = json!({
+            "action": "create",
+            "note": "Meetings/Standup",
+            "template": "Meeting",
+            "content": "- agenda",
+            "frontmatter": {"attendees": ["ana", "bo"]}
+        });
+
+        let result = tool.execute(args.clone()).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output, "Created Meetings/Standup.md");
+        let content = read(tmp.path(), "Meetings/Standup.md");
+        assert!(content.contains("type: meeting\n"));
+        assert!(content.contains("attendees:\n- ana\n- bo\n"));
+        assert!(content.ends_with("---\n# Standup\n- agenda\n"));
+
+        let result = tool.execute(args).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn paths_are_confined_to_the_vault() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "Plan.md", "plan\n");
+        std::fs::write(tmp.path().join("secret.md"), "secret").unwrap();
+        let tool = vault_tool(tmp.path(), AutonomyLevel::Supervised);
+
+        for note in ["../secret.md", "/etc/passwd", "~/notes"] {
+            let result = tool
+                .execute(json!({"action": "read", "note": note}))
+                .await
+                .unwrap();
+            assert!(!result.success, "{note} should be rejected");
+        }
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(tmp.path(), tmp.path().join("notes/escape")).unwrap();
+            let result = tool
+                .execute(json!({"action": "read", "note": "escape/secret.md"}))
+                .await
+                .unwrap();
+            assert!(!result.success);
+            assert!(result.error.unwrap().contains("escapes the vault"));
+        }
+
+        let outside = TempDir::new().unwrap();
+        let mut config = tool.config.clone();
+        config.path = outside.path().display().to_string();
+        let tool = VaultTool::new(tool.security.clone(), config, tmp.path());
+        let result = tool
+            .execute(json!({"action": "read", "note": "Plan"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allowed_roots"));
+    }
+
+    #[tokio::test]
+    async fn writes_are_blocked_in_read_only_mode() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "Plan.md", "plan\n");
+        let tool = vault_tool(tmp.path(), AutonomyLevel::ReadOnly);
+
+        let result = tool
+            .execute(json!({"action": "create", "note": "New"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+
+        let result = tool
+            .execute(json!({"action": "read", "note": "Plan"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn search_runs_content_search_inside_the_vault() {
+        let tmp = TempDir::new().unwrap();
+        write(tmp.path(), "Roadmap.md", "Ship the Q3 release\n");
+        write(tmp.path(), "Other.txt", "Q3 release\n");
+        std::fs::write(tmp.path().join("outside.md"), "Q3 release\n").unwrap();
+        let tool = vault_tool(tmp.path(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({"action": "search", "query": "q3 RELEASE", "output_mode": "files_with_matches"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("Roadmap.md"));
+        assert!(!result.output.contains("Other.txt"));
+        assert!(!result.output.contains("outside.md"));
+    }
+
+    #[test]
+    fn frontmatter_split_handles_missing_and_empty_blocks() {
+        assert_eq!(split_frontmatter("# Title\n"), (None, "# Title\n"));
+        assert_eq!(split_frontmatter("---\n---\nbody"), (Some(""), "body"));
+        assert_eq!(
+            split_frontmatter("---\na: 1\n---\nbody"),
+            (Some("a: 1\n"), "body")
+        );
+        assert_eq!(
+            split_frontmatter("---\nunterminated"),
+            (None, "---\nunterminated")
+        );
+    }
+}
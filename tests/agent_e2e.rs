@@ -988,6 +988,7 @@ async fn e2e_agent_research_prompt_guided() {
             ProviderCapabilities {
                 native_tool_calling: false, // Key difference!
                 vision: false,
+                structured_output: false,
             }
         }
 
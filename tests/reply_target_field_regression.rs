@@ -9,6 +9,17 @@ use std::path::{Path, PathBuf};
 const SCAN_PATHS: &[&str] = &["src", "examples"];
 const FORBIDDEN_PATTERNS: &[&str] = &[".reply_to", "reply_to:"];
 
+/// Returns true when `pattern` occurs in `line` as a whole field name, i.e. not
+/// as the prefix of a longer identifier such as `reply_topic`.
+fn contains_field_pattern(line: &str, pattern: &str) -> bool {
+    line.match_indices(pattern).any(|(idx, _)| {
+        !line[idx + pattern.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
 fn collect_rs_files(dir: &Path, out: &mut Vec<PathBuf>) {
     let entries = fs::read_dir(dir)
         .unwrap_or_else(|err| panic!("Failed to read directory {}: {err}", dir.display()));
@@ -46,7 +57,7 @@ fn source_does_not_use_legacy_reply_to_field() {
 
         for (line_idx, line) in content.lines().enumerate() {
             for pattern in FORBIDDEN_PATTERNS {
-                if line.contains(pattern) {
+                if contains_field_pattern(line, pattern) {
                     let rel = file_path
                         .strip_prefix(root)
                         .unwrap_or(&file_path)
@@ -68,3 +79,17 @@ fn source_does_not_use_legacy_reply_to_field() {
         violations.join("\n")
     );
 }
+
+#[test]
+fn field_pattern_ignores_longer_identifiers() {
+    assert!(contains_field_pattern("msg.reply_to.clone()", ".reply_to"));
+    assert!(contains_field_pattern("    reply_to: None,", "reply_to:"));
+    assert!(!contains_field_pattern(
+        "self.config.reply_topic",
+        ".reply_to"
+    ));
+    assert!(!contains_field_pattern(
+        "mqtt.reply_topic is required",
+        ".reply_to"
+    ));
+}
@@ -0,0 +1,292 @@
+//! SOP MQTT fan-in end-to-end tests.
+//!
+//! Runs `run_mqtt_sop_listener` against a minimal in-process MQTT 3.1.1
+//! broker stand-in and checks that a publish on a configured topic starts
+//! the matching SOP.
+#![cfg(all(feature = "sop", feature = "channel-mqtt"))]
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use zeroclaw::config::{Config, MemoryConfig, MqttConfig};
+use zeroclaw::memory::Memory;
+use zeroclaw::sop::{SopRuntime, SopTriggerSource};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Broker stand-in
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Read one control packet, returning (first header byte, body).
+async fn read_packet(stream: &mut TcpStream) -> Option<(u8, Vec<u8>)> {
+    let header = stream.read_u8().await.ok()?;
+    let mut len = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = stream.read_u8().await.ok()?;
+        len |= usize::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    let mut body = vec![0; len];
+    stream.read_exact(&mut body).await.ok()?;
+    Some((header, body))
+}
+
+fn encode_len(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+/// QoS 0 PUBLISH packet.
+fn publish_packet(topic: &str, payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&u16::try_from(topic.len()).unwrap().to_be_bytes());
+    body.extend_from_slice(topic.as_bytes());
+    body.extend_from_slice(payload);
+    let mut packet = vec![0x30];
+    encode_len(body.len(), &mut packet);
+    packet.extend_from_slice(&body);
+    packet
+}
+
+/// Topic filters from a SUBSCRIBE body (after the packet id).
+fn subscribed_topics(body: &[u8]) -> Vec<String> {
+    let mut topics = Vec::new();
+    let mut i = 2;
+    while i + 2 <= body.len() {
+        let len = usize::from(u16::from_be_bytes([body[i], body[i + 1]]));
+        i += 2;
+        topics.push(String::from_utf8_lossy(&body[i..i + len]).into_owned());
+        i += len + 1; // requested QoS byte
+    }
+    topics
+}
+
+/// Accept one client, acknowledge its CONNECT and SUBSCRIBE packets and
+/// forward every publish received on `outgoing` once it has subscribed.
+async fn run_broker(
+    listener: TcpListener,
+    subscribed: mpsc::UnboundedSender<String>,
+    mut outgoing: mpsc::UnboundedReceiver<Vec<u8>>,
+) {
+    let (mut stream, _) = listener.accept().await.unwrap();
+    loop {
+        tokio::select! {
+            packet = read_packet(&mut stream) => {
+                let Some((header, body)) = packet else { return };
+                match header >> 4 {
+                    1 => stream.write_all(&[0x20, 0x02, 0x00, 0x00]).await.unwrap(),
+                    8 => {
+                        let topics = subscribed_topics(&body);
+                        let mut suback = vec![0x90];
+                        encode_len(2 + topics.len(), &mut suback);
+                        suback.extend_from_slice(&body[..2]);
+                        suback.extend(std::iter::repeat_n(0x00, topics.len()));
+                        stream.write_all(&suback).await.unwrap();
+                        for topic in topics {
+                            let _ = subscribed.send(topic);
+                        }
+                    }
+                    12 => stream.write_all(&[0xD0, 0x00]).await.unwrap(),
+                    14 => return,
+                    _ => {}
+                }
+            }
+            Some(packet) = outgoing.recv() => stream.write_all(&packet).await.unwrap(),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn runtime_with_mqtt_sop(workspace: &std::path::Path) -> SopRuntime {
+    let sop_dir = workspace.join("sops").join("pump-alarm");
+    std::fs::create_dir_all(&sop_dir).unwrap();
+    std::fs::write(
+        sop_dir.join("SOP.toml"),
+        r#"
+[sop]
+name = "pump-alarm"
+description = "React to pump pressure alarms"
+execution_mode = "auto"
+
+[[triggers]]
+type = "mqtt"
+topic = "plant/pump/+/alarm"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        sop_dir.join("SOP.md"),
+        "## Steps\n\n1. **Inspect** — Read the alarm payload.\n",
+    )
+    .unwrap();
+
+    let config = Config {
+        workspace_dir: workspace.to_path_buf(),
+        ..Config::default()
+    };
+    let mem_cfg = MemoryConfig {
+        backend: "sqlite".into(),
+        ..MemoryConfig::default()
+    };
+    let memory: Arc<dyn Memory> =
+        Arc::from(zeroclaw::memory::create_memory(&mem_cfg, workspace, None).unwrap());
+    SopRuntime::new(&config, memory)
+}
+
+fn mqtt_config(port: u16) -> MqttConfig {
+    MqttConfig {
+        broker_url: format!("mqtt://127.0.0.1:{port}"),
+        client_id: "zeroclaw-e2e".into(),
+        topics: vec!["plant/pump/+/alarm".into()],
+        qos: 0,
+        username: None,
+        password: None,
+        use_tls: false,
+        keep_alive_secs: 30,
+        request_topic: None,
+        reply_topic: None,
+        allowed_senders: vec![],
+        retain: false,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[tokio::test]
+async fn mqtt_publish_starts_matching_sop() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let runtime = runtime_with_mqtt_sop(tmp.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sub_tx, mut sub_rx) = mpsc::unbounded_channel();
+    let (pub_tx, pub_rx) = mpsc::unbounded_channel();
+    tokio::spawn(run_broker(listener, sub_tx, pub_rx));
+
+    let config = mqtt_config(port);
+    let engine = runtime.engine.clone();
+    let audit = runtime.audit.clone();
+    let listener_task = tokio::spawn(async move {
+        zeroclaw::sop::mqtt::run_mqtt_sop_listener(&config, engine, audit).await
+    });
+
+    let topic = tokio::time::timeout(Duration::from_secs(10), sub_rx.recv())
+        .await
+        .expect("listener should subscribe")
+        .unwrap();
+    assert_eq!(topic, "plant/pump/+/alarm");
+
+    pub_tx
+        .send(publish_packet("plant/pump/3/alarm", br#"{"pressure":9.1}"#))
+        .unwrap();
+
+    let run = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let Some(run) = runtime
+                .engine
+                .lock()
+                .unwrap()
+                .active_runs()
+                .values()
+                .next()
+                .cloned()
+            {
+                return run;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("publish should start a run");
+
+    assert_eq!(run.sop_name, "pump-alarm");
+    assert_eq!(run.trigger_event.source, SopTriggerSource::Mqtt);
+    assert_eq!(
+        run.trigger_event.topic.as_deref(),
+        Some("plant/pump/3/alarm")
+    );
+    assert_eq!(
+        run.trigger_event.payload.as_deref(),
+        Some(r#"{"pressure":9.1}"#)
+    );
+
+    listener_task.abort();
+}
+
+#[tokio::test]
+async fn mqtt_publish_on_unmatched_topic_starts_nothing() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let runtime = runtime_with_mqtt_sop(tmp.path());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sub_tx, mut sub_rx) = mpsc::unbounded_channel();
+    let (pub_tx, pub_rx) = mpsc::unbounded_channel();
+    tokio::spawn(run_broker(listener, sub_tx, pub_rx));
+
+    let mut config = mqtt_config(port);
+    config.topics.push("plant/#".into());
+    let engine = runtime.engine.clone();
+    let audit = runtime.audit.clone();
+    let listener_task = tokio::spawn(async move {
+        zeroclaw::sop::mqtt::run_mqtt_sop_listener(&config, engine, audit).await
+    });
+
+    for _ in 0..2 {
+        tokio::time::timeout(Duration::from_secs(10), sub_rx.recv())
+            .await
+            .expect("listener should subscribe")
+            .unwrap();
+    }
+
+    pub_tx
+        .send(publish_packet("plant/boiler/1/alarm", b"hot"))
+        .unwrap();
+    // A matching publish after the unmatched one proves the first was
+    // processed without starting anything.
+    pub_tx
+        .send(publish_packet("plant/pump/1/alarm", b"low"))
+        .unwrap();
+
+    let runs = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let runs: Vec<_> = runtime
+                .engine
+                .lock()
+                .unwrap()
+                .active_runs()
+                .values()
+                .map(|run| run.trigger_event.topic.clone())
+                .collect();
+            if !runs.is_empty() {
+                return runs;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("matching publish should start a run");
+
+    assert_eq!(runs, vec![Some("plant/pump/1/alarm".to_string())]);
+
+    listener_task.abort();
+}
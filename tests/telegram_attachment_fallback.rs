@@ -16,7 +16,6 @@ use zeroclaw::channels::traits::{Channel, SendMessage};
 /// Helper: create a TelegramChannel pointing at a mock server.
 async fn test_channel(mock_url: &str) -> TelegramChannel {
     TelegramChannel::new("TEST_TOKEN".into(), vec!["*".into()], false)
-        .await
         .with_api_base(mock_url.to_string())
 }
 